@@ -4,19 +4,18 @@ use ratatui::Frame;
 use ratatui::layout::{Constraint, Direction, Layout};
 use crate::constants::{ABOUT_US, ABOUT_US_TEXT};
 use crate::model::{Model, ModelResponse};
-use crate::ui::{render_border, render_centered_text, render_footer_spans};
+use crate::ui::{render_border, render_centered_text, render_footer_spans, read_event};
+use crate::widgets::scroll_view::ScrollView;
 
 // ---- About Us Screen ----
 pub struct AboutUsScreen {
-    scroll_offset: usize,
-    scroll_necessary: bool
+    scroll: ScrollView,
 }
 
 impl AboutUsScreen {
     pub fn new() -> Self {
         Self {
-            scroll_offset: 0,
-            scroll_necessary: false
+            scroll: ScrollView::new(),
         }
     }
 }
@@ -24,7 +23,7 @@ impl AboutUsScreen {
 // ---- TRAIT IMPLEMENTATIONS ----
 impl Model for AboutUsScreen {
     fn update(&mut self) -> std::io::Result<ModelResponse> {
-        if let Event::Key(key) = event::read()? {
+        if let Event::Key(key) = read_event(false)? {
             if key.kind == event::KeyEventKind::Release {
                 return Ok(ModelResponse::Refresh);
             }
@@ -32,23 +31,11 @@ impl Model for AboutUsScreen {
                 KeyCode::Char('q') => Ok(ModelResponse::Exit),
                 KeyCode::Char('m') => Ok(ModelResponse::NavToMainMenu),
                 KeyCode::Up | KeyCode::Char('k') => {
-                    if self.scroll_offset > 0 {
-                        if self.scroll_offset == 2 {
-                            self.scroll_offset -= 2;
-                        } else {
-                            self.scroll_offset -= 1;
-                        }
-                    }
+                    self.scroll.scroll(-1);
                     Ok(ModelResponse::Refresh)
                 },
                 KeyCode::Down | KeyCode::Char('j') => {
-                    if self.scroll_necessary {
-                        if self.scroll_offset == 0 {
-                            self.scroll_offset += 2;
-                        } else {
-                            self.scroll_offset += 1;
-                        }
-                    }
+                    self.scroll.scroll(1);
                     Ok(ModelResponse::Refresh)
                 },
                 _ => Ok(ModelResponse::Refresh),
@@ -76,77 +63,13 @@ impl Model for AboutUsScreen {
         // Split ABOUT_US_TEXT into lines
         let text_lines: Vec<&str> = ABOUT_US_TEXT.split('\n').collect();
 
-        // Calculate the visible area height (accounting for indicator rows)
-        let content_area = menu_layout[1];
-        let needs_top_indicator = self.scroll_offset > 0;
-        let max_scroll = text_lines.len().saturating_sub(content_area.height as usize - 2);
-        let needs_bottom_indicator = self.scroll_offset < max_scroll;
-
-        // Adjust scroll position if needed
-        if self.scroll_offset > max_scroll {
-            self.scroll_offset = max_scroll;
-        }
-        if text_lines.len() >= content_area.height as usize {
-            self.scroll_necessary = true;
-        }
-
-        // Create layout for indicators and content
-        let indicator_constraints = match (needs_top_indicator, needs_bottom_indicator) {
-            (true, true) => vec![
-                Constraint::Length(1),  // Top indicator
-                Constraint::Min(1),     // Content
-                Constraint::Length(1),  // Bottom indicator
-            ],
-            (true, false) => vec![
-                Constraint::Length(1),  // Top indicator
-                Constraint::Min(1),     // Content
-            ],
-            (false, true) => vec![
-                Constraint::Min(1),     // Content
-                Constraint::Length(1),  // Bottom indicator
-            ],
-            (false, false) => vec![
-                Constraint::Min(1),     // Content only
-            ],
-        };
-
-        let content_chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints(indicator_constraints)
-            .split(content_area);
-
-        // Calculate visible content area and height
-        let text_area_idx = if needs_top_indicator { 1 } else { 0 };
-        let text_area = content_chunks[text_area_idx];
-        let visible_height = text_area.height as usize;
-
-        // Create a slice of visible lines based on scroll position
-        let end_idx = (self.scroll_offset + visible_height).min(text_lines.len());
-        let visible_text = text_lines[self.scroll_offset..end_idx].join("\n");
-
-        // Render content
+        // Show as much of the text as fits, with arrows when there's more
+        let text_area = self.scroll.render(frame, menu_layout[1], text_lines.len());
+        let offset = self.scroll.offset();
+        let end_idx = (offset + text_area.height as usize).min(text_lines.len());
+        let visible_text = text_lines[offset..end_idx].join("\n");
         render_centered_text(frame, text_area, &visible_text);
 
-        // Add scroll indicators
-        use ratatui::widgets::{Paragraph};
-        use ratatui::style::{Style, Color};
-        use ratatui::layout::Alignment;
-
-        if needs_top_indicator {
-            let up_indicator = Paragraph::new("↑")
-                .style(Style::default().fg(Color::Cyan))
-                .alignment(Alignment::Center);
-            frame.render_widget(up_indicator, content_chunks[0]);
-        }
-
-        if needs_bottom_indicator {
-            let bottom_idx = content_chunks.len() - 1;
-            let down_indicator = Paragraph::new("↓")
-                .style(Style::default().fg(Color::Cyan))
-                .alignment(Alignment::Center);
-            frame.render_widget(down_indicator, content_chunks[bottom_idx]);
-        }
-
         // Render Footer
         let footer_area = menu_layout[2];
 
@@ -1,18 +1,33 @@
-/// `App` stores the application state for the TUI.
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use crate::logic::config;
+use crate::logic::game::history::HandHistory;
+use crate::model::Model;
+use crate::ui::ActionPalette;
+
+/// `App` stores the application state for the TUI that outlives any one screen.
 ///
-/// While currently empty, this struct will be expanded to contain various
-/// pieces of state as the application grows, such as:
-/// - User input and selections
-/// - Data being displayed
-/// - UI navigation state
-/// - Application configuration
-pub struct App {}
+/// Screens are rebuilt whenever the user navigates, so anything that should
+/// survive navigation lives here, such as:
+/// - The hand history for the current session
+/// - A game left open while reviewing its history
+/// - The palette chart actions are colored in
+pub struct App {
+    /// Every round played this session, shared with the screens that use it
+    pub hand_history: Rc<RefCell<HandHistory>>,
+
+    /// Game screen set aside while another screen is shown on top of it
+    pub suspended_game: Option<Box<dyn Model>>,
+
+    /// How chart actions are colored, picked in the strategy calculator or settings
+    pub palette: Rc<Cell<ActionPalette>>,
+}
 
 impl App {
     /// Creates a new instance of the application state.
     ///
-    /// Returns an empty `App` struct, which will be populated with state
-    /// as features are added to the application.
+    /// Returns an `App` with an empty hand history, no game in progress and
+    /// the palette named in the config.
     ///
     /// # Examples
     ///
@@ -20,6 +35,10 @@ impl App {
     /// let app = App::new();
     /// ```
     pub fn new() -> Self {
-        Self {}
+        Self {
+            hand_history: Rc::new(RefCell::new(HandHistory::new())),
+            suspended_game: None,
+            palette: Rc::new(Cell::new(config::current().theme)),
+        }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,173 @@
+use std::path::{Path, PathBuf};
+use crate::constants::HISTORY_DATABASE_FILE;
+use crate::logic::app_dirs::{data_file, deviations_dir, exports_dir};
+use crate::logic::config::strategies_dir;
+use crate::logic::deviations::load_index_sets;
+use crate::logic::export::{export_csv, export_html, export_png, export_svg};
+use crate::logic::history_store::{export_history_csv, HistoryStore};
+use crate::logic::simulator::head_to_head::head_to_head;
+use crate::logic::simulator::batch::{summary_table, BatchMatrix, BatchRow};
+use crate::logic::simulator::engine::{simulate, simulate_in_detail, SimulationConfig, SimulationProgress, CONFIDENCE_Z};
+use crate::logic::simulator::report::{export_report_csv, export_report_json, SimulationReport};
+use crate::logic::simulator::sessions::ROUNDS_PER_HOUR;
+use crate::logic::strategy_calculator_logic::{load_strategies, BlackjackStrategy};
+
+/// Rounds simulated when no count is given
+const DEFAULT_SIMULATED_ROUNDS: u64 = 1_000_000;
+
+/// Length of the sessions an exported simulation is cut into
+const EXPORTED_SESSION_HOURS: u64 = 4;
+
+const USAGE: &str = concat!(
+"Usage: jacks-blackjack [command]\n",
+"\n",
+"Run without a command to start the game.\n",
+"\n",
+"Commands:\n",
+"  export csv <strategy> [directory]    Write a strategy's charts to CSV files\n",
+"  export html <strategy> [directory]   Write a strategy's charts to a web page\n",
+"  export svg <strategy> [directory]    Write a strategy's charts as a wallet card SVG\n",
+"  export png <strategy> [directory]    Write a strategy's charts as a wallet card PNG\n",
+"  export stats [directory]             Write every session, hand and drill answer\n",
+"                                       played to CSV files for a spreadsheet\n",
+"  simulate <strategy> [rounds] [seed] [--export directory]\n",
+"                                       Play a strategy under its own rules and report\n",
+"                                       its EV, spread and win, push and loss rates,\n",
+"                                       writing the full results to JSON and CSV files\n",
+"                                       with --export\n",
+"  compare <strategy> <strategy> [rounds] [seed]\n",
+"                                       Play two strategies against the same cards\n",
+"                                       under the first's rules and report how far\n",
+"                                       apart their EVs are\n",
+"  batch <file>                         Play every game in a batch file one after\n",
+"                                       another and compare them in a table\n",
+"\n",
+"<strategy> is the name of a chart in the strategies directory, e.g. default-strategy,\n",
+"or the path to a strategy JSON file. Files are written to exports in the data\n",
+"directory by default.\n",
+"Batch files are described in the README, see resources/batches for an example.\n",
+);
+
+/// Runs a command given on the command line instead of starting the game.
+pub fn run(args: &[String]) -> Result<(), String> {
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    match args.as_slice() {
+        ["export", "stats", rest @ ..] if rest.len() <= 1 => {
+            let dir = &rest.first().map_or_else(exports_dir, PathBuf::from);
+            let store = HistoryStore::open(&data_file(HISTORY_DATABASE_FILE), "Statistics").map_err(|err| format!("Couldn't open the play history: {}", err))?;
+            for path in export_history_csv(&store, dir)? {
+                println!("Wrote {}", path.display());
+            }
+            Ok(())
+        }
+        ["export", format, strategy, rest @ ..] if rest.len() <= 1 => {
+            let dir = &rest.first().map_or_else(exports_dir, PathBuf::from);
+            let (name, strategy) = find_strategy(strategy)?;
+            let paths = match *format {
+                "csv" => export_csv(&strategy, &name, dir)?,
+                "html" => vec![export_html(&strategy, &name, dir)?],
+                "svg" => vec![export_svg(&strategy, &name, dir)?],
+                "png" => vec![export_png(&strategy, &name, dir)?],
+                _ => return Err(format!("Unknown export format: {}\n\n{}", format, USAGE)),
+            };
+            for path in paths {
+                println!("Wrote {}", path.display());
+            }
+            Ok(())
+        }
+        ["simulate", strategy, rest @ ..] => {
+            let (rest, export) = match rest {
+                [rest @ .., "--export", dir] => (rest, Some(Path::new(*dir))),
+                _ => (rest, None),
+            };
+            if rest.len() > 2 {
+                return Err(USAGE.to_string());
+            }
+            let (name, strategy) = find_strategy(strategy)?;
+            let rounds = match rest.first() {
+                Some(rounds) => rounds.replace('_', "").parse().map_err(|_| format!("Not a number of rounds: {}", rounds))?,
+                None => DEFAULT_SIMULATED_ROUNDS,
+            };
+            let seed = rest.get(1)
+                .map(|seed| seed.parse().map_err(|_| format!("Not a seed: {}", seed)))
+                .transpose()?;
+
+            let config = SimulationConfig::for_strategy(strategy, rounds, seed);
+            let result = match export {
+                Some(dir) => {
+                    let detail = simulate_in_detail(config.clone(), EXPORTED_SESSION_HOURS * ROUNDS_PER_HOUR, &SimulationProgress::default())?;
+                    let report = SimulationReport::new(&name, &config, &detail, None);
+                    println!("Wrote {}", export_report_json(&report, dir)?.display());
+                    for path in export_report_csv(&report, dir)? {
+                        println!("Wrote {}", path.display());
+                    }
+                    detail.result
+                }
+                None => simulate(config)?,
+            };
+            println!("{} over {} rounds", name, result.rounds);
+            let (low, high) = result.ev_interval();
+            println!("  EV per round         {:+.3}% ± {:.3}%", result.ev() * 100.0, (high - result.ev()) * 100.0);
+            println!("  95% confidence       {:+.3}% to {:+.3}%", low * 100.0, high * 100.0);
+            println!("  Standard deviation   {:.3} units a round, {:.2} units per 100 rounds", result.std_dev(), result.std_dev_per_100());
+            println!("  Won / pushed / lost  {:.2}% / {:.2}% / {:.2}%", result.win_rate() * 100.0, result.push_rate() * 100.0, result.loss_rate() * 100.0);
+            Ok(())
+        }
+        ["compare", first, second, rest @ ..] if rest.len() <= 2 => {
+            let (first_name, first) = find_strategy(first)?;
+            let (second_name, second) = find_strategy(second)?;
+            let rounds = match rest.first() {
+                Some(rounds) => rounds.replace('_', "").parse().map_err(|_| format!("Not a number of rounds: {}", rounds))?,
+                None => DEFAULT_SIMULATED_ROUNDS,
+            };
+            let seed = rest.get(1)
+                .map(|seed| seed.parse().map_err(|_| format!("Not a seed: {}", seed)))
+                .transpose()?;
+
+            let config = SimulationConfig::for_strategy(first, rounds, seed);
+            println!("{} against {} over {} rounds of {}", second_name, first_name, rounds, config.rules);
+            let compared = head_to_head(config, second, &SimulationProgress::default())?;
+            let (low, high) = compared.delta_interval();
+            let width = first_name.len().max(second_name.len()).max(21);
+            println!("  {:<width$}  {:+.3}% a round", first_name, compared.first.ev() * 100.0);
+            println!("  {:<width$}  {:+.3}% a round", second_name, compared.second.ev() * 100.0);
+            println!("  {:<width$}  {:+.3}% ± {:.3}%", "EV difference", compared.ev_delta() * 100.0, (high - compared.ev_delta()) * 100.0);
+            println!("  {:<width$}  {:+.3}% to {:+.3}%", "95% confidence", low * 100.0, high * 100.0);
+            println!("  {:<width$}  ± {:.3}%, {:.0} times the rounds for the same margin", "Played on own shoes", CONFIDENCE_Z * compared.unpaired_error() * 100.0, compared.variance_reduction());
+            println!("  {:<width$}  {:.2}% / {:.2}% / {:.2}%", "Better / same / worse", compared.difference.win_rate() * 100.0, compared.difference.push_rate() * 100.0, compared.difference.loss_rate() * 100.0);
+            Ok(())
+        }
+        ["batch", file] => {
+            let matrix = BatchMatrix::from_file(file)?;
+            let runs = matrix.runs(find_strategy, &load_index_sets(&deviations_dir()))?;
+            let total = runs.len();
+            let mut rows = vec![];
+            for (played, run) in runs.into_iter().enumerate() {
+                let penetration = run.config.table.penetration;
+                println!("Playing {} of {}: {}, {:.0}% penetration, {}", played + 1, total, run.strategy, penetration * 100.0, run.counting);
+                let result = simulate(run.config)?;
+                rows.push(BatchRow { strategy: run.strategy, penetration, counting: run.counting, result });
+            }
+            println!();
+            println!("{}", summary_table(&rows));
+            Ok(())
+        }
+        ["help"] | ["--help"] | ["-h"] => {
+            print!("{}", USAGE);
+            Ok(())
+        }
+        _ => Err(USAGE.to_string()),
+    }
+}
+
+/// A strategy by the name it's loaded under, or read from a file path.
+fn find_strategy(strategy: &str) -> Result<(String, BlackjackStrategy), String> {
+    if let Some(found) = load_strategies(&strategies_dir()).remove(strategy) {
+        return Ok((strategy.to_string(), found));
+    }
+    let path = Path::new(strategy);
+    let name = path.file_stem().and_then(|stem| stem.to_str()).unwrap_or(strategy);
+    BlackjackStrategy::from_file(strategy)
+        .map(|found| (name.to_string(), found))
+        .map_err(|err| format!("Couldn't load strategy {}: {}", strategy, err))
+}
@@ -0,0 +1,12 @@
+use std::io::{self, Write};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+
+/// Puts `text` on the system clipboard by asking the terminal to, with an
+/// OSC 52 escape sequence. It works over SSH too, but terminals that don't
+/// support it quietly ignore it.
+pub fn copy_to_clipboard(text: &str) -> io::Result<()> {
+    let mut stdout = io::stdout();
+    write!(stdout, "\x1b]52;c;{}\x07", STANDARD.encode(text))?;
+    stdout.flush()
+}
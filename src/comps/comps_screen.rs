@@ -0,0 +1,289 @@
+use crate::logic::comps::{CompsConfig, CompsEstimate, COMP_RATES, DEFAULT_COMP_RATE, DEFAULT_PIT_EDGE, DEFAULT_PIT_ROUNDS, PIT_EDGES, PIT_ROUNDS_PER_HOUR};
+use crate::logic::config::{self, strategies_dir};
+use crate::logic::ev::house_edge;
+use crate::logic::strategy_calculator_logic::{load_strategies, BlackjackStrategy};
+use crate::model::{Model, ModelResponse};
+use crate::ui::{create_common_layout, dollars, render_border, render_centered_text, render_key_spans, split_content_horizontally, read_event, step_through};
+use ratatui::crossterm::event;
+use ratatui::crossterm::event::{Event, KeyCode};
+use ratatui::layout::{Alignment, Constraint, Direction, Layout, Margin, Rect};
+use ratatui::prelude::{Line, Stylize};
+use ratatui::style::Color;
+use ratatui::widgets::{Block, Paragraph, Wrap};
+use ratatui::Frame;
+use std::fmt;
+
+/// Average bets to rate, in dollars
+const AVERAGE_BET_OPTIONS: [f64; 7] = [5.0, 10.0, 25.0, 50.0, 100.0, 250.0, 500.0];
+const DEFAULT_AVERAGE_BET: usize = 2;
+
+/// Hours of play to rate
+const HOURS_OPTIONS: [f64; 6] = [1.0, 2.0, 4.0, 8.0, 12.0, 24.0];
+const DEFAULT_HOURS: usize = 2;
+
+/// Rounds an hour really played, from a full table to heads up
+const ROUNDS_PER_HOUR_OPTIONS: [u32; 5] = [50, 60, 80, 100, 200];
+const DEFAULT_ROUNDS_PER_HOUR: usize = 2;
+
+// ---- Settings ----
+/// What goes into the estimate, in the order they're listed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CompsOption {
+    Strategy,
+    AverageBet,
+    Hours,
+    RoundsPerHour,
+    PitEdge,
+    PitRounds,
+    CompRate,
+}
+
+impl fmt::Display for CompsOption {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            CompsOption::Strategy => "Strategy",
+            CompsOption::AverageBet => "Average Bet",
+            CompsOption::Hours => "Hours",
+            CompsOption::RoundsPerHour => "Rounds / Hour",
+            CompsOption::PitEdge => "Pit's House Edge",
+            CompsOption::PitRounds => "Pit's Rounds / Hour",
+            CompsOption::CompRate => "Comp Rate",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+const COMPS_OPTIONS: [CompsOption; 7] = [
+    CompsOption::Strategy,
+    CompsOption::AverageBet,
+    CompsOption::Hours,
+    CompsOption::RoundsPerHour,
+    CompsOption::PitEdge,
+    CompsOption::PitRounds,
+    CompsOption::CompRate,
+];
+
+// ---- Comps Screen ----
+/// Weighs the comps a stretch of play earns, rated the way the pit rates
+/// it, against what the play really costs with a chart's house edge.
+pub struct CompsScreen {
+    /// Charts that can be played, by name
+    strategies: Vec<(String, BlackjackStrategy)>,
+
+    /// Position in `strategies`
+    strategy: usize,
+
+    /// Worked out house edge under the chart's rules
+    house_edge: f64,
+
+    /// Positions in `AVERAGE_BET_OPTIONS`, `HOURS_OPTIONS`,
+    /// `ROUNDS_PER_HOUR_OPTIONS`, `PIT_EDGES`, `PIT_ROUNDS_PER_HOUR` and
+    /// `COMP_RATES`
+    average_bet: usize,
+    hours: usize,
+    rounds_per_hour: usize,
+    pit_edge: usize,
+    pit_rounds: usize,
+    comp_rate: usize,
+
+    /// Position in `COMPS_OPTIONS`
+    active_option: usize,
+}
+
+impl CompsScreen {
+    pub fn new() -> Self {
+        let mut strategies: Vec<(String, BlackjackStrategy)> = load_strategies(&strategies_dir()).into_iter().collect();
+        strategies.sort_by(|a, b| a.0.cmp(&b.0));
+        let strategy = strategies.iter().position(|(name, _)| name == "default-strategy").unwrap_or(0);
+        let mut screen = Self {
+            strategies,
+            strategy,
+            house_edge: 0.0,
+            average_bet: DEFAULT_AVERAGE_BET,
+            hours: DEFAULT_HOURS,
+            rounds_per_hour: DEFAULT_ROUNDS_PER_HOUR,
+            pit_edge: DEFAULT_PIT_EDGE,
+            pit_rounds: DEFAULT_PIT_ROUNDS,
+            comp_rate: DEFAULT_COMP_RATE,
+            active_option: 0,
+        };
+        screen.update_house_edge();
+        screen
+    }
+
+    fn update_house_edge(&mut self) {
+        if let Some((_, strategy)) = self.strategies.get(self.strategy) {
+            self.house_edge = house_edge(&strategy.rules, &config::current().table);
+        }
+    }
+
+    fn config(&self) -> CompsConfig {
+        CompsConfig {
+            average_bet: AVERAGE_BET_OPTIONS[self.average_bet],
+            hours: HOURS_OPTIONS[self.hours],
+            pit_edge: PIT_EDGES[self.pit_edge],
+            pit_rounds_per_hour: PIT_ROUNDS_PER_HOUR[self.pit_rounds] as f64,
+            comp_rate: COMP_RATES[self.comp_rate],
+            house_edge: self.house_edge,
+            rounds_per_hour: ROUNDS_PER_HOUR_OPTIONS[self.rounds_per_hour] as f64,
+        }
+    }
+
+    fn change_option(&mut self, step: isize) {
+        match COMPS_OPTIONS[self.active_option] {
+            CompsOption::Strategy if !self.strategies.is_empty() => {
+                self.strategy = step_through(self.strategy, step, self.strategies.len());
+                self.update_house_edge();
+            }
+            CompsOption::Strategy => {}
+            CompsOption::AverageBet => self.average_bet = step_through(self.average_bet, step, AVERAGE_BET_OPTIONS.len()),
+            CompsOption::Hours => self.hours = step_through(self.hours, step, HOURS_OPTIONS.len()),
+            CompsOption::RoundsPerHour => self.rounds_per_hour = step_through(self.rounds_per_hour, step, ROUNDS_PER_HOUR_OPTIONS.len()),
+            CompsOption::PitEdge => self.pit_edge = step_through(self.pit_edge, step, PIT_EDGES.len()),
+            CompsOption::PitRounds => self.pit_rounds = step_through(self.pit_rounds, step, PIT_ROUNDS_PER_HOUR.len()),
+            CompsOption::CompRate => self.comp_rate = step_through(self.comp_rate, step, COMP_RATES.len()),
+        }
+    }
+
+    fn render_settings(&self, frame: &mut Frame, rect: Rect) {
+        let mut lines: Vec<Line> = vec![];
+        for (index, option) in COMPS_OPTIONS.iter().enumerate() {
+            let value = match option {
+                CompsOption::Strategy => self.strategies.get(self.strategy).map_or("None".to_string(), |(name, _)| name.clone()),
+                CompsOption::AverageBet => format!("${:.0}", AVERAGE_BET_OPTIONS[self.average_bet]),
+                CompsOption::Hours => format!("{:.0}", HOURS_OPTIONS[self.hours]),
+                CompsOption::RoundsPerHour => ROUNDS_PER_HOUR_OPTIONS[self.rounds_per_hour].to_string(),
+                CompsOption::PitEdge => format!("{:.1}%", PIT_EDGES[self.pit_edge] * 100.0),
+                CompsOption::PitRounds => PIT_ROUNDS_PER_HOUR[self.pit_rounds].to_string(),
+                CompsOption::CompRate => format!("{:.0}%", COMP_RATES[self.comp_rate] * 100.0),
+            };
+            let selected = index == self.active_option;
+            let text = format!("{}{}: < {} >", if selected { "> " } else { "" }, option, value);
+            lines.push(Line::from(""));
+            lines.push(if selected { Line::from(text).fg(Color::Green) } else { Line::from(text) });
+        }
+
+        if let Some((_, strategy)) = self.strategies.get(self.strategy) {
+            lines.push(Line::from(""));
+            lines.push(Line::from(strategy.rules.to_string()).fg(Color::Cyan));
+            lines.push(Line::from(format!("House Edge Played Perfectly: {:.3}%", self.house_edge * 100.0)).fg(Color::Cyan));
+        }
+
+        let settings = Paragraph::new(lines)
+            .bold()
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true })
+            .block(Block::default());
+        frame.render_widget(settings, rect);
+    }
+
+    /// The pit's rating and the comps it earns beside what the play really
+    /// costs, and whether the comps make up for it.
+    fn render_estimate(&self, frame: &mut Frame, rect: Rect) {
+        let config = self.config();
+        let estimate = CompsEstimate::new(&config);
+        let mut lines = vec![
+            Line::from(""),
+            Line::from("As the Pit Rates It").bold(),
+            Line::from(format!(
+                "${:.0} × {:.0} hours × {} rounds × {:.1}%",
+                config.average_bet,
+                config.hours,
+                config.pit_rounds_per_hour,
+                config.pit_edge * 100.0,
+            )),
+            Line::from(format!("Theoretical Loss: {}", dollars(estimate.theoretical_loss))),
+            Line::from(format!("Comps at {:.0}%: {}", config.comp_rate * 100.0, dollars(estimate.comps))).fg(Color::Cyan),
+            Line::from(""),
+            Line::from("What It Really Costs").bold(),
+            Line::from(format!(
+                "${:.0} × {:.0} hours × {} rounds × {:.3}%",
+                config.average_bet,
+                config.hours,
+                config.rounds_per_hour,
+                config.house_edge * 100.0,
+            )),
+            Line::from(format!("Expected Loss: {}", dollars(estimate.expected_loss))),
+            Line::from(format!("Per Hour: {}", dollars(estimate.expected_loss / config.hours))),
+            Line::from(""),
+            Line::from(format!("Comps Less Expected Loss: {}", dollars(estimate.net()))).bold()
+                .fg(if estimate.net() >= 0.0 { Color::Green } else { Color::Yellow }),
+        ];
+        lines.push(Line::from(if estimate.net() >= 0.0 {
+            "The comps are worth more than the play costs".to_string()
+        } else {
+            format!("The play costs {} more than the comps are worth", dollars(-estimate.net()))
+        }));
+        if let Some(rate) = estimate.break_even_rate() {
+            lines.push(Line::from(format!("Comps would need to be {:.0}% of theoretical to break even", rate * 100.0)));
+        }
+        lines.push(Line::from(""));
+        lines.push(Line::from("The pit rates every player at the same edge, so perfect play on good rules loses far less than it earns credit for").fg(Color::Yellow));
+
+        let estimate = Paragraph::new(lines)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true })
+            .block(Block::default());
+        frame.render_widget(estimate, rect.inner(Margin { vertical: 0, horizontal: 2 }));
+    }
+
+    fn footer_spans(&self) -> Vec<String> {
+        vec![
+            " Q ".to_string(), " Quit ".to_string(),
+            " M ".to_string(), " Menu ".to_string(),
+            " J/↓ ".to_string(), " Down ".to_string(),
+            " K/↑ ".to_string(), " Up ".to_string(),
+            " H/← L/→ ".to_string(), " Change ".to_string(),
+        ]
+    }
+}
+
+// ---- TRAIT IMPLEMENTATIONS ----
+impl Model for CompsScreen {
+    fn update(&mut self) -> std::io::Result<ModelResponse> {
+        if let Event::Key(key) = read_event(false)? {
+            if key.kind == event::KeyEventKind::Release {
+                return Ok(ModelResponse::Refresh);
+            }
+            match key.code {
+                KeyCode::Char('q') => return Ok(ModelResponse::Exit),
+                KeyCode::Char('m') => return Ok(ModelResponse::NavToMainMenu),
+                KeyCode::Char('j') | KeyCode::Down => {
+                    self.active_option = (self.active_option + 1) % COMPS_OPTIONS.len();
+                }
+                KeyCode::Char('k') | KeyCode::Up => {
+                    self.active_option = (self.active_option + COMPS_OPTIONS.len() - 1) % COMPS_OPTIONS.len();
+                }
+                KeyCode::Char('h') | KeyCode::Left => self.change_option(-1),
+                KeyCode::Char('l') | KeyCode::Right => self.change_option(1),
+                _ => {}
+            }
+        }
+        Ok(ModelResponse::Refresh)
+    }
+
+    fn ui(&mut self, frame: &mut Frame) {
+        let main_chunks = create_common_layout(frame.area());
+        let horizontal_chunks = split_content_horizontally(main_chunks[1]);
+
+        let left_section = horizontal_chunks[0];
+        render_border(frame, left_section);
+        render_centered_text(frame, left_section, " Play ");
+        self.render_settings(frame, left_section.inner(Margin { vertical: 1, horizontal: 1 }));
+
+        let right_section = horizontal_chunks[1];
+        render_border(frame, right_section);
+        render_centered_text(frame, right_section, " Comps and Theoretical Loss ");
+        self.render_estimate(frame, right_section.inner(Margin { vertical: 1, horizontal: 1 }));
+
+        // Render Footer
+        let footer_layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Length(5),
+                Constraint::Min(20),
+            ])
+            .split(main_chunks[2]);
+        render_key_spans(frame, self.footer_spans(), footer_layout[1]);
+    }
+}
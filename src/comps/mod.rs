@@ -0,0 +1 @@
+pub mod comps_screen;
\ No newline at end of file
@@ -1,3 +1,35 @@
+use include_dir::{include_dir, Dir};
+
+/// Strategy charts built into the app, copied into the strategies
+/// directory the first time it's made
+pub static BUNDLED_STRATEGIES: Dir = include_dir!("$CARGO_MANIFEST_DIR/resources/strategies");
+
+/// Count-based index plays built into the app, copied into the deviations
+/// directory the first time it's made
+pub static BUNDLED_DEVIATIONS: Dir = include_dir!("$CARGO_MANIFEST_DIR/resources/deviations");
+
+/// Streaks of right answers in every drill, shared by the training screens
+pub const STREAKS_FILE: &str = "streaks.json";
+
+/// Right and wrong plays in each chart cell, from before the play history kept them
+pub const DECISION_ACCURACY_FILE: &str = "decision_accuracy.json";
+
+/// Simulation settings saved under a name to run again
+pub const SIMULATION_PRESETS_FILE: &str = "simulation_presets.json";
+
+/// Rules the strategy calculator was last left on, to reopen on
+pub const LAST_RULES_FILE: &str = "last_rules.json";
+
+/// Bet ramp found for a game, offered by the game's bet advisor
+pub const BET_RAMP_FILE: &str = "bet_ramp.json";
+
+/// Hands played and drill answers given, kept between sessions
+pub const HISTORY_DATABASE_FILE: &str = "history.db";
+
+/// Index of the curated strategy packs that can be installed from the calculator,
+/// kept in the registry directory of the repository
+pub const REGISTRY_INDEX_URL: &str = "https://raw.githubusercontent.com/freesidejockey/jacks-blackjack/main/registry/index.json";
+
 pub const TITLE: &str = concat!(
 "\n\n",
 "    /$$$$$                     /$$       /$$              /$$$$$$$  /$$                     /$$                               /$$      \n",
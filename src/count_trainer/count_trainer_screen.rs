@@ -0,0 +1,1417 @@
+use crate::constants::{HISTORY_DATABASE_FILE, STREAKS_FILE};
+use crate::logic::app_dirs::{data_file, deviations_dir};
+use crate::logic::config::strategies_dir;
+use crate::logic::history_store::HistoryStore;
+use crate::logic::back_counting::{BackCounter, Entry, DEFAULT_ENTRY_THRESHOLD, ENTRY_THRESHOLDS, OTHER_PLAYERS};
+use crate::logic::count_trainer::{best_time, CountDrill, CountTrainer, DeckEstimationDrill, DeviationDrill, SpeedResult, SpeedRun, TrueCountDrill, TrueCountRounding, ANSWER_TIME, CARDS_PER_CHECK};
+use crate::logic::counting::COUNTING_SYSTEMS;
+use crate::logic::deviations::{load_index_sets, IndexPlay, IndexSet, IndexSituation};
+use crate::logic::counting_stats::{format_day, now, CountingStats, DrillResult, DrillSummary};
+use crate::logic::game::animation::DealAnimation;
+use crate::logic::game::shoe::Shoe;
+use crate::logic::game::table::TableConfig;
+use crate::logic::strategy_calculator_logic::{find_nearest_strategy, load_strategies, BlackjackStrategy, GameVariant, StrategyVariables, SurrenderRule};
+use crate::logic::streaks::Streaks;
+use crate::model::{Model, ModelResponse};
+use crate::ui::{create_common_layout, render_border, render_centered_text, render_key_spans, render_streak, split_content_horizontally, read_event};
+use crate::widgets::card_widget::{CardRow, CardStyle, CARD_HEIGHT};
+use crate::widgets::table_view::TableView;
+use ratatui::crossterm::event;
+use ratatui::crossterm::event::{Event, KeyCode};
+use ratatui::layout::{Alignment, Constraint, Direction, Layout, Margin, Rect};
+use ratatui::prelude::{Line, Stylize};
+use ratatui::style::{Color, Style};
+use ratatui::widgets::{Bar, BarChart, BarGroup, Block, Borders, Paragraph, Row, Table};
+use ratatui::Frame;
+use rand::Rng;
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+use std::time::{Duration, Instant};
+
+/// Where every drill answer is saved, for the stats view
+const COUNTING_STATS_FILE: &str = "counting_stats.json";
+
+/// How long each card is shown for, slowest first. Speed runs start at
+/// the chosen speed and get quicker.
+const SPEEDS: [Duration; 8] = [
+    Duration::from_millis(2000),
+    Duration::from_millis(1500),
+    Duration::from_millis(1000),
+    Duration::from_millis(750),
+    Duration::from_millis(500),
+    Duration::from_millis(350),
+    Duration::from_millis(250),
+    Duration::from_millis(150),
+];
+const DEFAULT_SPEED: usize = 2;
+
+/// Shoe sizes that can be trained with
+const DECK_OPTIONS: [u8; 4] = [1, 2, 6, 8];
+
+/// Most recent cards left on screen as they stream past
+const STREAM_LENGTH: usize = 6;
+
+/// Cards kept back from a speed run, so its count isn't known before it starts
+const HELD_BACK: RangeInclusive<usize> = 1..=4;
+
+/// Longest running count that can be typed in
+const MAX_COUNT_LENGTH: usize = 4;
+
+/// Inside width of the discard tray
+const TRAY_WIDTH: usize = 11;
+
+/// Fill levels for the top line of the discard tray, in eighths of a line
+const TRAY_FILL: [&str; 9] = [" ", "▁", "▂", "▃", "▄", "▅", "▆", "▇", "█"];
+
+/// Card intervals the watched table waits between rounds
+const ROUND_GAP: u32 = 4;
+
+// ---- Count Trainer Screen ----
+/// Deals a shoe a card at a time for practicing a card count, asking for
+/// the running count as it goes and scoring the counts once the cut card
+/// comes out. Converting to true counts, judging the decks left that takes,
+/// and counting against the clock are drilled on the side.
+pub struct CountTrainerScreen {
+    drill: CountDrill,
+    trainer: CountTrainer,
+    true_count: TrueCountDrill,
+    deck_estimation: DeckEstimationDrill,
+
+    /// Cards being flashed for the speed count, or waiting for their count
+    speed_run: Option<SpeedRun>,
+    speed_results: Vec<SpeedResult>,
+
+    /// Index sets with plays to drill, and the position of the one drilled.
+    /// There's no deviation drill when none were loaded.
+    index_sets: Vec<IndexSet>,
+    index_set: usize,
+    deviations: Option<DeviationDrill>,
+
+    /// The table watched for back counting, its hands played by the chart
+    /// nearest its rules, and how much of the last round has been shown
+    strategies: HashMap<String, BlackjackStrategy>,
+    back_counter: BackCounter,
+    /// Position in `ENTRY_THRESHOLDS`
+    entry_threshold: usize,
+    table_animation: DealAnimation,
+
+    /// When the current question was asked, or the speed run's last card went
+    asked_at: Instant,
+
+    /// Position in `COUNTING_SYSTEMS`
+    system: usize,
+
+    /// Position in `DECK_OPTIONS`
+    decks: usize,
+
+    /// Position in `SPEEDS`
+    speed: usize,
+    paused: bool,
+    last_card: Instant,
+
+    /// Every answer given, this run and before
+    stats: CountingStats,
+
+    /// Right answers in a row in each drill, carried over between sessions
+    streaks: Streaks,
+    /// Database every answer is kept in, none when it can't be opened
+    store: Option<HistoryStore>,
+
+    /// Showing the stats for the drill instead of the drill itself
+    showing_stats: bool,
+
+    /// Running count being typed in
+    count_input: String,
+    card_style: CardStyle,
+    message: String,
+}
+
+impl CountTrainerScreen {
+    pub fn new() -> Self {
+        let (system, decks) = (0, 0);
+        let index_sets: Vec<IndexSet> = load_index_sets(&deviations_dir()).into_iter()
+            .filter(|set| !set.plays.is_empty())
+            .collect();
+        let strategies = load_strategies(&strategies_dir());
+        Self {
+            drill: CountDrill::RunningCount,
+            trainer: Self::new_trainer(system, decks),
+            true_count: TrueCountDrill::new(DECK_OPTIONS[decks], TrueCountRounding::default()),
+            deck_estimation: DeckEstimationDrill::new(DECK_OPTIONS[decks]),
+            speed_run: None,
+            speed_results: Vec::new(),
+            deviations: Self::new_deviation_drill(&index_sets, 0),
+            index_sets,
+            index_set: 0,
+            back_counter: Self::new_back_counter(&strategies, system, decks, DEFAULT_ENTRY_THRESHOLD),
+            strategies,
+            entry_threshold: DEFAULT_ENTRY_THRESHOLD,
+            table_animation: DealAnimation::new(),
+            asked_at: Instant::now(),
+            system,
+            decks,
+            speed: DEFAULT_SPEED,
+            paused: true,
+            last_card: Instant::now(),
+            stats: CountingStats::load(&data_file(COUNTING_STATS_FILE)),
+            streaks: Streaks::load(&data_file(STREAKS_FILE)),
+            store: HistoryStore::open(&data_file(HISTORY_DATABASE_FILE), "Count Trainer").ok(),
+            showing_stats: false,
+            count_input: String::new(),
+            card_style: CardStyle::default(),
+            message: CountDrill::RunningCount.instructions().to_string(),
+        }
+    }
+
+    fn new_trainer(system: usize, decks: usize) -> CountTrainer {
+        let shoe = Shoe::new(DECK_OPTIONS[decks], TableConfig::default().penetration);
+        CountTrainer::new(shoe, COUNTING_SYSTEMS[system])
+    }
+
+    fn new_deviation_drill(index_sets: &[IndexSet], index_set: usize) -> Option<DeviationDrill> {
+        index_sets.get(index_set).and_then(|set| DeviationDrill::new(set.clone()).ok())
+    }
+
+    /// A table dealt from the chosen shoe with the game's usual rules.
+    fn new_back_counter(strategies: &HashMap<String, BlackjackStrategy>, system: usize, decks: usize, entry_threshold: usize) -> BackCounter {
+        let rules = StrategyVariables {
+            decks: DECK_OPTIONS[decks],
+            dealer_stands_on_soft_17: true,
+            double_after_split: true,
+            dealer_peak: true,
+            surrender_allowed: SurrenderRule::AnyUpcard,
+            variant: GameVariant::Classic,
+        };
+        let strategy = find_nearest_strategy(strategies, &rules).map(|(_, strategy, _)| strategy.clone());
+        BackCounter::new(rules, TableConfig::default(), strategy, COUNTING_SYSTEMS[system], ENTRY_THRESHOLDS[entry_threshold])
+    }
+
+    /// Starts over with a fresh shoe, waiting to be started, and fresh sets
+    /// of true count, deck estimation and deviation questions. Any speed run
+    /// is called off, but best times are kept, and the watched table starts
+    /// on a new shoe with nobody seated.
+    fn new_shoe(&mut self) {
+        self.trainer = Self::new_trainer(self.system, self.decks);
+        self.true_count = TrueCountDrill::new(DECK_OPTIONS[self.decks], self.true_count.rounding());
+        self.deck_estimation = DeckEstimationDrill::new(DECK_OPTIONS[self.decks]);
+        self.deviations = Self::new_deviation_drill(&self.index_sets, self.index_set);
+        self.back_counter = Self::new_back_counter(&self.strategies, self.system, self.decks, self.entry_threshold);
+        self.table_animation = DealAnimation::new();
+        self.speed_run = None;
+        self.paused = true;
+        self.count_input.clear();
+        self.asked_at = Instant::now();
+        self.message = self.drill.instructions().to_string();
+    }
+
+    /// Moves on to the next drill, pausing the shoe so no cards go by unseen.
+    fn switch_drill(&mut self) {
+        self.drill = self.drill.next();
+        self.paused = true;
+        self.speed_run = None;
+        self.count_input.clear();
+        self.asked_at = Instant::now();
+        self.message = match self.drill {
+            CountDrill::RunningCount if self.trainer.is_finished() => "Shoe done, press N for a new shoe",
+            CountDrill::RunningCount if !self.trainer.dealt().is_empty() => "Paused, press Space to carry on",
+            drill => drill.instructions(),
+        }.to_string();
+    }
+
+    /// Switches between the drill and its stats, pausing the shoe and
+    /// calling off any speed run so nothing goes by unseen.
+    fn toggle_stats(&mut self) {
+        self.showing_stats = !self.showing_stats;
+        self.paused = true;
+        self.speed_run = None;
+        self.count_input.clear();
+        self.asked_at = Instant::now();
+        if !self.showing_stats && !self.trainer.dealt().is_empty() && !self.trainer.is_finished() {
+            self.message = "Paused, press Space to carry on".to_string();
+        }
+    }
+
+    /// Saves an answer given in the current drill to the counting stats and
+    /// the drill's streak.
+    fn record(&mut self, correct: bool, error: Option<f64>, time: Option<Duration>, speed: Option<Duration>) {
+        // Index plays are drilled with the counting system of their set
+        let system = match &self.deviations {
+            Some(deviations) if self.drill == CountDrill::Deviations => deviations.set().system.clone(),
+            _ => COUNTING_SYSTEMS[self.system].name().to_string(),
+        };
+        self.stats.record(DrillResult {
+            drill: self.drill,
+            system,
+            decks: DECK_OPTIONS[self.decks],
+            speed: speed.map(|speed| speed.as_secs_f64()),
+            correct,
+            error,
+            time: time.map(|time| time.as_secs_f64()),
+            at: now(),
+        });
+        if let Err(err) = self.stats.save(&data_file(COUNTING_STATS_FILE)) {
+            self.message = format!("Could not save counting stats: {}", err);
+        }
+        self.streaks.record(&self.drill.to_string(), correct);
+        if let Err(err) = self.streaks.save(&data_file(STREAKS_FILE)) {
+            self.message = format!("Could not save streaks: {}", err);
+        }
+        if let Some(store) = &mut self.store
+            && let Err(err) = store.record_answer(&self.drill.to_string(), correct) {
+            self.message = format!("Could not save the answer: {}", err);
+        }
+    }
+
+    /// Whether typed digits go to a count.
+    fn taking_count(&self) -> bool {
+        match self.drill {
+            CountDrill::RunningCount => self.trainer.awaiting_count(),
+            CountDrill::TrueCount | CountDrill::DeckEstimation => true,
+            CountDrill::SpeedCount => self.speed_run.as_ref().is_some_and(|run| run.current().is_none()),
+            CountDrill::Deviations | CountDrill::BackCounting => false,
+        }
+    }
+
+    /// Shuffles a shoe of the chosen size and starts flashing it at the
+    /// chosen speed, keeping a few cards back.
+    fn start_speed_run(&mut self) {
+        let decks = DECK_OPTIONS[self.decks];
+        let shoe = Shoe::new(decks, 1.0);
+        let cards = shoe.upcoming();
+        let held_back = rand::rng().random_range(HELD_BACK);
+        let cards = cards[..cards.len() - held_back].to_vec();
+        self.speed_run = Some(SpeedRun::new(cards, decks, COUNTING_SYSTEMS[self.system], SPEEDS[self.speed]));
+        self.last_card = Instant::now();
+        self.count_input.clear();
+        self.message.clear();
+    }
+
+    /// Flashes the next card when it's time, then runs the clock on the count.
+    fn tick_speed_run(&mut self) {
+        let Some(run) = &mut self.speed_run else {
+            return;
+        };
+        if run.current().is_some() {
+            if self.last_card.elapsed() >= run.flash_duration() {
+                self.last_card = Instant::now();
+                if !run.advance() {
+                    self.asked_at = Instant::now();
+                }
+            }
+        } else if self.asked_at.elapsed() > ANSWER_TIME {
+            self.finish_speed_run(None);
+        }
+    }
+
+    /// Scores the count given for the speed run, or none when time ran out.
+    fn finish_speed_run(&mut self, answer: Option<i32>) {
+        let Some(run) = self.speed_run.take() else {
+            return;
+        };
+        let result = run.finish(answer, self.asked_at.elapsed());
+        let best = best_time(&self.speed_results, result.decks);
+        self.speed_results.push(result);
+        self.count_input.clear();
+
+        let time = result.time.as_secs_f64();
+        self.message = match result.answer {
+            None => format!("Time's up, the count was {:+}", result.expected),
+            Some(answer) if !result.is_correct() => format!("The count was {:+}, not {:+}", result.expected, answer),
+            Some(_) if best.is_none_or(|best| result.time < best) => format!("Right, a best time of {:.1}s", time),
+            Some(_) => format!("Right, in {:.1}s", time),
+        };
+        let error = result.answer.map(|answer| (answer - result.expected).abs() as f64);
+        self.record(result.is_correct(), error, Some(result.time), Some(result.first_flash));
+    }
+
+    fn change_speed(&mut self, increment: isize) {
+        self.speed = self.speed.saturating_add_signed(increment).min(SPEEDS.len() - 1);
+    }
+
+    fn toggle_pause(&mut self) {
+        if self.drill == CountDrill::RunningCount && self.trainer.is_finished() {
+            return;
+        }
+        self.paused = !self.paused;
+        self.message = if self.paused { "Paused, press Space to carry on" } else { "" }.to_string();
+        self.last_card = Instant::now();
+    }
+
+    fn update_count_input(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Enter => {
+                let answered = match self.drill {
+                    CountDrill::RunningCount => self.count_input.parse().map(|count| self.answer_running_count(count)).is_ok(),
+                    CountDrill::TrueCount => self.count_input.parse().map(|count| self.answer_true_count(count)).is_ok(),
+                    CountDrill::DeckEstimation => self.count_input.parse().map(|decks| self.answer_deck_estimate(decks)).is_ok(),
+                    CountDrill::SpeedCount => self.count_input.parse().map(|count| self.finish_speed_run(Some(count))).is_ok(),
+                    CountDrill::Deviations | CountDrill::BackCounting => false,
+                };
+                if !answered {
+                    self.message = match self.drill {
+                        CountDrill::DeckEstimation => "Type the decks remaining, e.g. 2.5".to_string(),
+                        CountDrill::SpeedCount => "Type the running count, e.g. 3 or -2".to_string(),
+                        drill => format!("Type the {}, e.g. 3 or -2", drill.to_string().to_lowercase()),
+                    };
+                }
+            }
+            KeyCode::Backspace => {
+                self.count_input.pop();
+            }
+            KeyCode::Char(c @ ('0'..='9' | '-' | '+' | '.')) if self.count_input.len() < MAX_COUNT_LENGTH => {
+                self.count_input.push(c);
+            }
+            _ => {}
+        }
+    }
+
+    /// Scores the running count, and the whole shoe once the cut card is out.
+    fn answer_running_count(&mut self, count: i32) {
+        let check = self.trainer.answer(count);
+        self.count_input.clear();
+        self.message = if check.is_correct() {
+            format!("Right, the count is {:+}", check.expected)
+        } else {
+            format!("The count was {:+}, not {:+}", check.expected, check.answer)
+        };
+        if self.trainer.is_finished() {
+            self.message = format!(
+                "Shoe done: {} of {} counts right ({:.0}%), off by {:.1} on average",
+                self.trainer.checks().iter().filter(|check| check.is_correct()).count(),
+                self.trainer.checks().len(),
+                self.trainer.accuracy() * 100.0,
+                self.trainer.average_error(),
+            );
+        }
+        self.record(check.is_correct(), Some((check.answer - check.expected).abs() as f64), None, Some(SPEEDS[self.speed]));
+        self.last_card = Instant::now();
+    }
+
+    /// Scores the true count against the chosen rounding, showing the
+    /// working, and asks the next question.
+    fn answer_true_count(&mut self, count: i32) {
+        let attempt = self.true_count.answer(count, self.asked_at.elapsed());
+        self.count_input.clear();
+        self.asked_at = Instant::now();
+
+        let rounded = match self.true_count.rounding() {
+            TrueCountRounding::Floor => "floored",
+            TrueCountRounding::Round => "rounded",
+        };
+        let working = format!(
+            "{:+} over {} decks is {:+.2}, {} to {:+}",
+            attempt.question.running_count,
+            attempt.question.decks_remaining,
+            attempt.question.true_count(),
+            rounded,
+            attempt.expected,
+        );
+        self.message = if attempt.is_correct() {
+            format!("Right, {}", working)
+        } else {
+            format!("{}, not {:+}", working, attempt.answer)
+        };
+        self.record(attempt.is_correct(), Some((attempt.answer - attempt.expected).abs() as f64), Some(attempt.time), None);
+    }
+
+    /// Scores the decks remaining given and shows the next tray.
+    fn answer_deck_estimate(&mut self, decks: f64) {
+        let estimate = self.deck_estimation.answer(decks, self.asked_at.elapsed());
+        self.count_input.clear();
+        self.asked_at = Instant::now();
+
+        let left = estimate.question.decks_remaining();
+        self.message = if estimate.is_correct() {
+            format!("Right, {:.2} decks were left", left)
+        } else {
+            format!("{:.2} decks were left, not {}", left, estimate.answer)
+        };
+        self.record(estimate.is_correct(), Some(estimate.error()), Some(estimate.time), None);
+    }
+
+    fn cycle_index_set(&mut self) {
+        if self.index_sets.is_empty() {
+            return;
+        }
+        self.index_set = (self.index_set + 1) % self.index_sets.len();
+        self.deviations = Self::new_deviation_drill(&self.index_sets, self.index_set);
+        self.asked_at = Instant::now();
+        self.message = format!("Drilling the {}", self.index_sets[self.index_set].name);
+    }
+
+    /// Scores choosing the index play, or basic strategy, and deals the next hand.
+    fn answer_deviation(&mut self, deviated: bool) {
+        let Some(deviations) = &mut self.deviations else {
+            return;
+        };
+        let answer = deviations.answer(deviated, self.asked_at.elapsed());
+        self.asked_at = Instant::now();
+
+        let question = &answer.question;
+        let index_play = describe_index_play(&question.play);
+        self.message = match (answer.is_correct(), question.deviates()) {
+            (true, true) => format!("Right, {}", index_play),
+            (true, false) => format!("Right, basic strategy: {} only", index_play),
+            (false, true) => format!("At {:+} it's the index play: {}", question.true_count, index_play),
+            (false, false) => format!("At {:+} it's basic strategy: {} only", question.true_count, index_play),
+        };
+        self.record(answer.is_correct(), None, Some(answer.time), None);
+    }
+
+    /// Reveals the watched round a card at a time at the chosen speed, then
+    /// after a short gap deals the next, shuffling once the cut card is out.
+    fn tick_back_counting(&mut self) {
+        let animating = self.back_counter.round().is_some_and(|round| !self.table_animation.is_done(round));
+        let delay = if animating { SPEEDS[self.speed] } else { SPEEDS[self.speed] * ROUND_GAP };
+        if self.paused || self.last_card.elapsed() < delay {
+            return;
+        }
+        self.last_card = Instant::now();
+        if let Some(round) = self.back_counter.round()
+            && self.table_animation.step(round) {
+            return;
+        }
+        if self.back_counter.needs_shuffle() {
+            self.shuffle_table();
+            return;
+        }
+        let round = self.back_counter.deal();
+        self.table_animation.start(round);
+    }
+
+    /// Starts the watched table on a new shoe, getting up first if seated.
+    fn shuffle_table(&mut self) {
+        if self.back_counter.is_seated() {
+            self.leave_table("Shuffled, left");
+        } else {
+            self.message = "Shuffled, the count starts over".to_string();
+        }
+        self.back_counter.shuffle();
+        self.table_animation = DealAnimation::new();
+    }
+
+    /// Sits down at the watched table from the next round, or gets up from it.
+    fn toggle_seat(&mut self) {
+        if self.back_counter.is_seated() {
+            self.leave_table("Left");
+            return;
+        }
+        let Ok(entry) = self.back_counter.enter() else {
+            return;
+        };
+        self.message = if entry.is_well_timed() {
+            format!("Sat down at {:+.1}, at the {:+} threshold", entry.true_count, entry.threshold)
+        } else {
+            format!("Sat down at {:+.1}, short of the {:+} threshold", entry.true_count, entry.threshold)
+        };
+    }
+
+    /// Gets up from the watched table and scores the entry against the
+    /// threshold, `how` starting the message.
+    fn leave_table(&mut self, how: &str) {
+        let Some(entry) = self.back_counter.leave().cloned() else {
+            return;
+        };
+        let timing = if entry.is_well_timed() { "at" } else { "short of" };
+        self.message = format!(
+            "{} at {:+.1} after {} rounds, {:+} units. Sat down at {:+.1}, {} the {:+} threshold",
+            how,
+            entry.exit_true_count.unwrap_or_default(),
+            entry.rounds,
+            entry.net,
+            entry.true_count,
+            timing,
+            entry.threshold,
+        );
+        self.record(entry.is_well_timed(), None, None, None);
+    }
+
+    fn cycle_entry_threshold(&mut self) {
+        self.entry_threshold = (self.entry_threshold + 1) % ENTRY_THRESHOLDS.len();
+        self.back_counter.set_threshold(ENTRY_THRESHOLDS[self.entry_threshold]);
+        self.message = format!("Sit down at a true count of {:+} or more", ENTRY_THRESHOLDS[self.entry_threshold]);
+    }
+
+    /// The rounding used and how quick and accurate the true counts have been.
+    fn render_true_count_stats(&self, frame: &mut Frame, rect: Rect) {
+        let drill = &self.true_count;
+        let attempts = drill.attempts();
+        let mut lines = vec![
+            Line::from(""),
+            Line::from(format!("Rounding: {}", drill.rounding())),
+            Line::from(format!("Up To {} Decks Remaining", DECK_OPTIONS[self.decks])),
+            Line::from(format!("Answered: {}", attempts.len())),
+            Line::from(format!(
+                "Right: {} ({:.0}%)",
+                attempts.iter().filter(|attempt| attempt.is_correct()).count(),
+                drill.accuracy() * 100.0,
+            )),
+            Line::from(format!("Average Time: {:.1}s", drill.average_time().as_secs_f64())),
+            Line::from(""),
+            Line::from("Answers Given").bold(),
+        ];
+        // The latest answers when they don't all fit
+        let fits = (rect.height as usize).saturating_sub(lines.len());
+        for attempt in attempts[attempts.len().saturating_sub(fits)..].iter().rev() {
+            let question = format!("{:+} / {}", attempt.question.running_count, attempt.question.decks_remaining);
+            let line = if attempt.is_correct() {
+                Line::from(format!("{}: {:+} in {:.1}s", question, attempt.answer, attempt.time.as_secs_f64())).fg(Color::Green)
+            } else {
+                Line::from(format!("{}: {:+}, was {:+}", question, attempt.answer, attempt.expected)).fg(Color::Red)
+            };
+            lines.push(line);
+        }
+
+        let stats = Paragraph::new(lines)
+            .alignment(Alignment::Center)
+            .block(Block::default());
+        frame.render_widget(stats, rect);
+    }
+
+    /// The running count and decks remaining to convert, with the answer
+    /// being typed.
+    fn render_true_count_question(&self, frame: &mut Frame, rect: Rect) {
+        let layout = Layout::vertical([
+            Constraint::Min(0),
+            Constraint::Length(2),
+            Constraint::Length(2),
+            Constraint::Length(2),
+            Constraint::Length(2),
+            Constraint::Length(2),
+            Constraint::Min(0),
+        ]).split(rect);
+
+        let question = self.true_count.question();
+        let lines = [
+            format!("Running Count: {:+}", question.running_count),
+            format!("Decks Remaining: {}", question.decks_remaining),
+        ];
+        for (line, area) in lines.into_iter().zip(&layout[1..3]) {
+            frame.render_widget(Paragraph::new(line).bold().alignment(Alignment::Center), *area);
+        }
+        let prompt = Paragraph::new(format!("True Count: {}_", self.count_input))
+            .bold()
+            .fg(Color::Yellow)
+            .alignment(Alignment::Center);
+        frame.render_widget(prompt, layout[4]);
+        let message = Paragraph::new(self.message.as_str())
+            .bold()
+            .alignment(Alignment::Center);
+        frame.render_widget(message, layout[5]);
+    }
+
+    /// The shoe being dealt, and the speed it's dealt at, with every count
+    /// given so far.
+    fn render_progress(&self, frame: &mut Frame, rect: Rect) {
+        let mut lines = vec![
+            Line::from(""),
+            Line::from(format!("System: {}", self.trainer.system().name())),
+            Line::from(format!("Decks: {}", DECK_OPTIONS[self.decks])),
+            Line::from(format!("Starting Count: {:+}", self.trainer.system().initial_running_count(DECK_OPTIONS[self.decks]))),
+            Line::from(format!("Speed: {:.2}s a card", SPEEDS[self.speed].as_secs_f64())),
+            Line::from(format!("Cards Dealt: {}", self.trainer.dealt().len())),
+            Line::from(format!("Cards To Cut Card: {}", self.trainer.cards_left())),
+            Line::from(format!("Count Asked Every {} Cards", CARDS_PER_CHECK)),
+            Line::from(""),
+            Line::from("Counts Given").bold(),
+        ];
+        // The latest counts when they don't all fit
+        let fits = (rect.height as usize).saturating_sub(lines.len());
+        let checks = self.trainer.checks();
+        for check in &checks[checks.len().saturating_sub(fits)..] {
+            let line = if check.is_correct() {
+                Line::from(format!("{} cards: {:+}", check.cards_seen, check.answer)).fg(Color::Green)
+            } else {
+                Line::from(format!("{} cards: {:+}, was {:+}", check.cards_seen, check.answer, check.expected)).fg(Color::Red)
+            };
+            lines.push(line);
+        }
+
+        let progress = Paragraph::new(lines)
+            .alignment(Alignment::Center)
+            .block(Block::default());
+        frame.render_widget(progress, rect);
+    }
+
+    /// The latest cards dealt, newest on the right, with the count prompt
+    /// under them when it's asked for.
+    fn render_stream(&self, frame: &mut Frame, rect: Rect) {
+        let layout = Layout::vertical([
+            Constraint::Min(0),
+            Constraint::Length(CARD_HEIGHT),
+            Constraint::Length(2),
+            Constraint::Length(2),
+            Constraint::Length(2),
+            Constraint::Min(0),
+        ]).split(rect);
+
+        let dealt = self.trainer.dealt();
+        let shown = &dealt[dealt.len().saturating_sub(STREAM_LENGTH)..];
+        frame.render_widget(CardRow::from_cards(shown, 0).style(self.card_style), layout[1]);
+
+        if self.trainer.awaiting_count() {
+            let prompt = Paragraph::new(format!("Running Count: {}_", self.count_input))
+                .bold()
+                .fg(Color::Yellow)
+                .alignment(Alignment::Center);
+            frame.render_widget(prompt, layout[3]);
+        }
+        let message = Paragraph::new(self.message.as_str())
+            .bold()
+            .alignment(Alignment::Center);
+        frame.render_widget(message, layout[4]);
+    }
+
+    /// How close and how quick the deck estimates have been.
+    fn render_deck_estimation_stats(&self, frame: &mut Frame, rect: Rect) {
+        let drill = &self.deck_estimation;
+        let estimates = drill.estimates();
+        let mut lines = vec![
+            Line::from(""),
+            Line::from(format!("Shoe: {} Decks", DECK_OPTIONS[self.decks])),
+            Line::from(format!("Answered: {}", estimates.len())),
+            Line::from(format!(
+                "Within A Quarter Deck: {} ({:.0}%)",
+                estimates.iter().filter(|estimate| estimate.is_correct()).count(),
+                drill.accuracy() * 100.0,
+            )),
+            Line::from(format!("Average Error: {:.2} Decks", drill.average_error())),
+            Line::from(format!("Average Time: {:.1}s", drill.average_time().as_secs_f64())),
+            Line::from(""),
+            Line::from("Estimates Given").bold(),
+        ];
+        // The latest estimates when they don't all fit
+        let fits = (rect.height as usize).saturating_sub(lines.len());
+        for estimate in estimates[estimates.len().saturating_sub(fits)..].iter().rev() {
+            let text = format!("{} for {:.2} decks", estimate.answer, estimate.question.decks_remaining());
+            let color = if estimate.is_correct() { Color::Green } else { Color::Red };
+            lines.push(Line::from(text).fg(color));
+        }
+
+        let stats = Paragraph::new(lines)
+            .alignment(Alignment::Center)
+            .block(Block::default());
+        frame.render_widget(stats, rect);
+    }
+
+    /// The discard tray as deep as the cards played, with the estimate
+    /// being typed under it. Each deck takes two lines when there's room.
+    fn render_discard_tray(&self, frame: &mut Frame, rect: Rect) {
+        let question = self.deck_estimation.question();
+        let lines_per_deck = if rect.height >= question.decks as u16 * 2 + 8 { 2 } else { 1 };
+        let tray_height = question.decks as u16 * lines_per_deck;
+        let layout = Layout::vertical([
+            Constraint::Min(0),
+            Constraint::Length(2),
+            Constraint::Length(tray_height + 1),
+            Constraint::Length(1),
+            Constraint::Length(2),
+            Constraint::Length(2),
+            Constraint::Min(0),
+        ]).split(rect);
+
+        let caption = Paragraph::new(format!("{} Deck Shoe", question.decks)).bold().alignment(Alignment::Center);
+        frame.render_widget(caption, layout[1]);
+
+        // Filled from the bottom, the top line partly when ASCII cards aren't wanted
+        let eighths = (question.decks_discarded() * lines_per_deck as f64 * 8.0).round() as usize;
+        let tray = (0..tray_height as usize).rev().map(|line| {
+            let filled = eighths.saturating_sub(line * 8).min(8);
+            let fill = match self.card_style {
+                CardStyle::Unicode => TRAY_FILL[filled],
+                CardStyle::Ascii => if filled >= 4 { "#" } else { " " },
+            };
+            Line::from(fill.repeat(TRAY_WIDTH))
+        }).collect::<Vec<_>>();
+        let tray_width = TRAY_WIDTH as u16 + 2;
+        let tray_area = Rect {
+            x: layout[2].x + layout[2].width.saturating_sub(tray_width) / 2,
+            width: tray_width.min(layout[2].width),
+            ..layout[2]
+        };
+        let tray = Paragraph::new(tray)
+            .fg(Color::Blue)
+            .block(Block::new().borders(Borders::LEFT | Borders::RIGHT | Borders::BOTTOM).fg(Color::White));
+        frame.render_widget(tray, tray_area);
+
+        let prompt = Paragraph::new(format!("Decks Remaining: {}_", self.count_input))
+            .bold()
+            .fg(Color::Yellow)
+            .alignment(Alignment::Center);
+        frame.render_widget(prompt, layout[4]);
+        let message = Paragraph::new(self.message.as_str())
+            .bold()
+            .alignment(Alignment::Center);
+        frame.render_widget(message, layout[5]);
+    }
+
+    /// The best time for each shoe size, and how the latest runs went.
+    fn render_speed_stats(&self, frame: &mut Frame, rect: Rect) {
+        let mut lines = vec![
+            Line::from(""),
+            Line::from(format!("System: {}", COUNTING_SYSTEMS[self.system].name())),
+            Line::from(format!("Shoe: {} Decks", DECK_OPTIONS[self.decks])),
+            Line::from(format!("Starting Speed: {:.2}s a card", SPEEDS[self.speed].as_secs_f64())),
+            Line::from(format!("Time To Answer: {}s", ANSWER_TIME.as_secs())),
+            Line::from(""),
+            Line::from("Best Times").bold(),
+        ];
+        for decks in DECK_OPTIONS {
+            let best = best_time(&self.speed_results, decks)
+                .map_or("-".to_string(), |time| format!("{:.1}s", time.as_secs_f64()));
+            lines.push(Line::from(format!("{} Decks: {}", decks, best)));
+        }
+        lines.extend([Line::from(""), Line::from("Runs").bold()]);
+
+        // The latest runs when they don't all fit
+        let results = &self.speed_results;
+        let fits = (rect.height as usize).saturating_sub(lines.len());
+        for result in results[results.len().saturating_sub(fits)..].iter().rev() {
+            let line = match result.answer {
+                _ if result.is_correct() => {
+                    Line::from(format!("{} Decks: {:+} in {:.1}s", result.decks, result.expected, result.time.as_secs_f64())).fg(Color::Green)
+                }
+                Some(answer) => Line::from(format!("{} Decks: {:+}, was {:+}", result.decks, answer, result.expected)).fg(Color::Red),
+                None => Line::from(format!("{} Decks: Late, was {:+}", result.decks, result.expected)).fg(Color::Red),
+            };
+            lines.push(line);
+        }
+
+        let stats = Paragraph::new(lines)
+            .alignment(Alignment::Center)
+            .block(Block::default());
+        frame.render_widget(stats, rect);
+    }
+
+    /// The card being flashed and how far through the run it is, then the
+    /// clock while the count is given.
+    fn render_speed_run(&self, frame: &mut Frame, rect: Rect) {
+        let layout = Layout::vertical([
+            Constraint::Min(0),
+            Constraint::Length(CARD_HEIGHT),
+            Constraint::Length(2),
+            Constraint::Length(2),
+            Constraint::Length(2),
+            Constraint::Min(0),
+        ]).split(rect);
+
+        if let Some(run) = &self.speed_run {
+            let (shown, total) = run.progress();
+            match run.current() {
+                Some(card) => {
+                    frame.render_widget(CardRow::from_cards(&[card], 0).style(self.card_style), layout[1]);
+                    let progress = Paragraph::new(format!("Card {} of {}", shown, total)).alignment(Alignment::Center);
+                    frame.render_widget(progress, layout[2]);
+                }
+                None => {
+                    let left = ANSWER_TIME.saturating_sub(self.asked_at.elapsed()).as_secs_f64();
+                    let clock = Paragraph::new(format!("{:.1}s left", left))
+                        .bold()
+                        .fg(if left < 3.0 { Color::Red } else { Color::White })
+                        .alignment(Alignment::Center);
+                    frame.render_widget(clock, layout[2]);
+                    let prompt = Paragraph::new(format!("Final Count: {}_", self.count_input))
+                        .bold()
+                        .fg(Color::Yellow)
+                        .alignment(Alignment::Center);
+                    frame.render_widget(prompt, layout[3]);
+                }
+            }
+        }
+        let message = Paragraph::new(self.message.as_str())
+            .bold()
+            .alignment(Alignment::Center);
+        frame.render_widget(message, layout[4]);
+    }
+
+    /// The index set drilled and how quick and accurate the choices have been.
+    fn render_deviation_stats(&self, frame: &mut Frame, rect: Rect) {
+        let Some(drill) = &self.deviations else {
+            return;
+        };
+        let answers = drill.answers();
+        let mut lines = vec![
+            Line::from(""),
+            Line::from(format!("Index Set: {}", drill.set().name)),
+            Line::from(format!("System: {}", drill.set().system)),
+            Line::from(format!("Plays: {}", drill.set().plays.len())),
+            Line::from(format!("Answered: {}", answers.len())),
+            Line::from(format!(
+                "Right: {} ({:.0}%)",
+                answers.iter().filter(|answer| answer.is_correct()).count(),
+                drill.accuracy() * 100.0,
+            )),
+            Line::from(format!("Average Time: {:.1}s", drill.average_time().as_secs_f64())),
+            Line::from(""),
+            Line::from("Answers Given").bold(),
+        ];
+        // The latest answers when they don't all fit
+        let fits = (rect.height as usize).saturating_sub(lines.len());
+        for answer in answers[answers.len().saturating_sub(fits)..].iter().rev() {
+            let choice = if answer.deviated { "Index Play" } else { "Basic" };
+            let line = Line::from(format!("{} at {:+}: {}", answer.question.play.situation, answer.question.true_count, choice));
+            lines.push(if answer.is_correct() { line.fg(Color::Green) } else { line.fg(Color::Red) });
+        }
+
+        let stats = Paragraph::new(lines)
+            .alignment(Alignment::Center)
+            .block(Block::default());
+        frame.render_widget(stats, rect);
+    }
+
+    /// The dealer's upcard over the player's hand, with the true count to
+    /// decide the play at.
+    fn render_deviation_question(&self, frame: &mut Frame, rect: Rect) {
+        let Some(drill) = &self.deviations else {
+            let empty = Paragraph::new(format!("\nNo index plays were loaded from {}", deviations_dir()))
+                .alignment(Alignment::Center);
+            frame.render_widget(empty, rect);
+            return;
+        };
+        let layout = Layout::vertical([
+            Constraint::Min(0),
+            Constraint::Length(1),
+            Constraint::Length(CARD_HEIGHT),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(CARD_HEIGHT),
+            Constraint::Length(2),
+            Constraint::Length(2),
+            Constraint::Length(2),
+            Constraint::Min(0),
+        ]).split(rect);
+
+        let question = drill.question();
+        frame.render_widget(Paragraph::new("Dealer").bold().alignment(Alignment::Center), layout[1]);
+        frame.render_widget(CardRow::from_cards(&[question.upcard], 0).style(self.card_style), layout[2]);
+        frame.render_widget(Paragraph::new("Player").bold().alignment(Alignment::Center), layout[4]);
+        frame.render_widget(CardRow::from_cards(&question.hand, 0).style(self.card_style), layout[5]);
+
+        let mut count = format!("{} True Count: {:+}", drill.set().system, question.true_count);
+        if question.play.situation == IndexSituation::Insurance {
+            count.push_str("  -  Insurance?");
+        }
+        frame.render_widget(Paragraph::new(count).bold().alignment(Alignment::Center), layout[6]);
+        let prompt = Paragraph::new("B: Basic Strategy    I: Index Play")
+            .bold()
+            .fg(Color::Yellow)
+            .alignment(Alignment::Center);
+        frame.render_widget(prompt, layout[7]);
+        let message = Paragraph::new(self.message.as_str())
+            .bold()
+            .alignment(Alignment::Center);
+        frame.render_widget(message, layout[8]);
+    }
+
+    /// The threshold, rounds watched and played, and how entries at the
+    /// threshold have done against those short of it.
+    fn render_back_counting_stats(&self, frame: &mut Frame, rect: Rect) {
+        let counter = &self.back_counter;
+        let entries = counter.entries();
+        let (well_timed_rounds, well_timed_net) = counter.results(true);
+        let (early_rounds, early_net) = counter.results(false);
+        let mut lines = vec![
+            Line::from(""),
+            Line::from(format!("System: {}", counter.system().name())),
+            Line::from(format!("Decks: {}", DECK_OPTIONS[self.decks])),
+            Line::from(format!("Enter At: {:+}", counter.threshold())),
+            Line::from(format!("Speed: {:.2}s a card", SPEEDS[self.speed].as_secs_f64())),
+            Line::from(""),
+            Line::from(format!("Rounds Watched: {}", counter.rounds_watched())),
+            Line::from(format!("Rounds Played: {}", well_timed_rounds + early_rounds)),
+            Line::from(format!(
+                "Entries: {} ({} Well Timed)",
+                entries.len(),
+                entries.iter().filter(|entry| entry.is_well_timed()).count(),
+            )),
+            Line::from(""),
+            Line::from("Units Won").bold(),
+            Line::from(format!("Well Timed: {:+} in {} Rounds", well_timed_net, well_timed_rounds)),
+            Line::from(format!("Too Early: {:+} in {} Rounds", early_net, early_rounds)),
+            Line::from(format!(
+                "Rounds Below Threshold: {}",
+                entries.iter().map(|entry| entry.rounds_below).sum::<u32>(),
+            )),
+            Line::from(""),
+            Line::from("Entries").bold(),
+        ];
+        // The latest entries when they don't all fit
+        let fits = (rect.height as usize).saturating_sub(lines.len());
+        for entry in entries[entries.len().saturating_sub(fits)..].iter().rev() {
+            let line = Line::from(describe_entry(entry));
+            lines.push(if entry.is_well_timed() { line.fg(Color::Green) } else { line.fg(Color::Red) });
+        }
+
+        let stats = Paragraph::new(lines)
+            .alignment(Alignment::Center)
+            .block(Block::default());
+        frame.render_widget(stats, rect);
+    }
+
+    /// The watched table's last round as far as it's been dealt, with the
+    /// back counter's seat and the decks left to judge the true count by.
+    fn render_back_counting_table(&self, frame: &mut Frame, rect: Rect) {
+        let layout = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(CARD_HEIGHT + 2),
+            Constraint::Min(0),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ]).split(rect);
+
+        let counter = &self.back_counter;
+        let seat = if counter.is_seated() { "Seated at Third Base" } else { "Watching" };
+        let status = format!("{}  -  About {:.1} Decks Left", seat, (counter.decks_remaining() * 2.0).round() / 2.0);
+        frame.render_widget(Paragraph::new(status).bold().alignment(Alignment::Center), layout[0]);
+
+        match counter.round() {
+            Some(round) => {
+                let table = TableView::new(round, &self.table_animation, self.card_style);
+                table.render_dealer(frame, layout[1]);
+                table.render_spots(frame, layout[2], "Players", |spot| {
+                    if spot < OTHER_PLAYERS { format!("Player {}", spot + 1) } else { "You".to_string() }
+                });
+            }
+            None => {
+                let waiting = Paragraph::new("A fresh shoe, waiting for the first round")
+                    .fg(Color::DarkGray)
+                    .alignment(Alignment::Center);
+                frame.render_widget(waiting, layout[2]);
+            }
+        }
+
+        let prompt = if counter.is_seated() {
+            "W: Leave the Table".to_string()
+        } else {
+            format!("W: Sit Down (Enter At {:+})", counter.threshold())
+        };
+        frame.render_widget(Paragraph::new(prompt).bold().fg(Color::Yellow).alignment(Alignment::Center), layout[3]);
+        let message = Paragraph::new(self.message.as_str())
+            .bold()
+            .alignment(Alignment::Center);
+        frame.render_widget(message, layout[4]);
+    }
+
+    /// How the drill has gone overall, since the first day it was
+    /// practiced, and with each counting system and speed.
+    fn render_stats_summary(&self, frame: &mut Frame, rect: Rect) {
+        let Some(summary) = self.stats.summary(self.drill) else {
+            let empty = Paragraph::new(format!("\nNo {} answers yet", self.drill))
+                .alignment(Alignment::Center);
+            frame.render_widget(empty, rect);
+            return;
+        };
+        let units = if self.drill == CountDrill::DeckEstimation { " Decks" } else { "" };
+        let mut lines = vec![Line::from("")];
+        lines.extend(summary_lines(&summary, units));
+
+        // Improvement from the first day practiced to the latest
+        let days = self.stats.by_day(self.drill);
+        lines.push(Line::from(format!("Days Practiced: {}", days.len())));
+        if let [(_, first), .., (_, latest)] = days.as_slice() {
+            let change = (latest.accuracy() - first.accuracy()) * 100.0;
+            let line = Line::from(format!("Since First Day: {:+.0}% Right", change));
+            lines.push(if change >= 0.0 { line.fg(Color::Green) } else { line.fg(Color::Red) });
+        }
+
+        lines.extend([Line::from(""), Line::from("By Counting System").bold()]);
+        for (system, summary) in self.stats.by_system(self.drill) {
+            lines.push(Line::from(format!("{}: {:.0}% of {}", system, summary.accuracy() * 100.0, summary.answers)));
+        }
+        let speeds = self.stats.by_speed(self.drill);
+        if !speeds.is_empty() {
+            let heading = if self.drill == CountDrill::SpeedCount { "By Starting Speed" } else { "By Speed" };
+            lines.extend([Line::from(""), Line::from(heading).bold()]);
+            for (speed, summary) in speeds {
+                lines.push(Line::from(format!("{:.2}s a card: {:.0}% of {}", speed, summary.accuracy() * 100.0, summary.answers)));
+            }
+        }
+
+        let stats = Paragraph::new(lines)
+            .alignment(Alignment::Center)
+            .block(Block::default());
+        frame.render_widget(stats, rect);
+    }
+
+    /// The share of answers right on each day practiced, charted for the
+    /// latest days, with every day listed newest first under the chart.
+    fn render_stats_trend(&self, frame: &mut Frame, rect: Rect) {
+        let days = self.stats.by_day(self.drill);
+        if days.is_empty() {
+            return;
+        }
+        let [chart_area, table_area] = Layout::vertical([
+            Constraint::Percentage(50),
+            Constraint::Min(0),
+        ]).areas(rect.inner(Margin { vertical: 1, horizontal: 1 }));
+
+        // As many of the latest days as there's room for, month and day only
+        let fits = (chart_area.width as usize / 7).max(1);
+        let bars = days[days.len().saturating_sub(fits)..].iter().map(|(day, summary)| {
+            let percent = summary.accuracy() * 100.0;
+            Bar::default()
+                .value(percent.round() as u64)
+                .text_value(format!("{:.0}", percent))
+                .label(Line::from(format_day(*day)[5..].to_string()))
+        }).collect::<Vec<_>>();
+        let chart_width = bars.len() as u16 * 7;
+        let chart = BarChart::default()
+            .block(Block::new()
+                .title("% Right By Day")
+                .title_alignment(Alignment::Center)
+                .style(Style::new().bold()))
+            .data(BarGroup::default().bars(&bars))
+            .max(100)
+            .bar_width(6)
+            .bar_gap(1)
+            .bar_style(Style::new().green())
+            .value_style(Style::new().black().on_green());
+        frame.render_widget(chart, chart_area.inner(Margin {
+            vertical: 0,
+            horizontal: chart_area.width.saturating_sub(chart_width) / 2,
+        }));
+
+        let units = if self.drill == CountDrill::DeckEstimation { " Decks" } else { "" };
+        let rows = days.iter().rev().map(|(day, summary)| {
+            Row::new(vec![
+                format_day(*day),
+                summary.answers.to_string(),
+                format!("{:.0}%", summary.accuracy() * 100.0),
+                summary.average_error.map_or("-".to_string(), |error| format!("{:.2}{}", error, units)),
+                summary.average_time.map_or("-".to_string(), |time| format!("{:.1}s", time)),
+            ])
+        });
+        let table = Table::new(rows, [
+            Constraint::Length(10),
+            Constraint::Length(7),
+            Constraint::Length(5),
+            Constraint::Length(11),
+            Constraint::Length(8),
+        ])
+            .header(Row::new(vec!["Day", "Answers", "Right", "Avg Error", "Avg Time"]).bold().underlined())
+            .column_spacing(2);
+        let table_width = 10 + 7 + 5 + 11 + 8 + 2 * 4;
+        frame.render_widget(table, table_area.inner(Margin {
+            vertical: 1,
+            horizontal: table_area.width.saturating_sub(table_width) / 2,
+        }));
+    }
+
+    fn footer_spans(&self) -> Vec<String> {
+        if self.showing_stats {
+            return vec![
+                " Q ".to_string(), " Quit ".to_string(),
+                " M ".to_string(), " Menu ".to_string(),
+                " Tab ".to_string(), format!(" {} Stats ", self.drill.next()),
+                " T ".to_string(), " Back To Drill ".to_string(),
+            ];
+        }
+        let mut spans = vec![
+            " Q ".to_string(), " Quit ".to_string(),
+            " M ".to_string(), " Menu ".to_string(),
+            " Tab ".to_string(), format!(" {} Drill ", self.drill.next()),
+            " T ".to_string(), " Stats ".to_string(),
+        ];
+        if self.drill == CountDrill::TrueCount {
+            spans.extend([
+                " Enter ".to_string(), " Give True Count ".to_string(),
+                " R ".to_string(), format!(" {} Instead ", self.true_count.rounding().toggle()),
+                " D ".to_string(), " Decks ".to_string(),
+                " N ".to_string(), " Start Over ".to_string(),
+            ]);
+            return spans;
+        }
+        if self.drill == CountDrill::SpeedCount {
+            if self.taking_count() {
+                spans.extend([" Enter ".to_string(), " Give Count ".to_string()]);
+            } else if self.speed_run.is_none() {
+                spans.extend([" Space ".to_string(), " Flash Cards ".to_string()]);
+            }
+            spans.extend([
+                " H/L ".to_string(), " Starting Speed ".to_string(),
+                " S ".to_string(), " Counting System ".to_string(),
+                " D ".to_string(), " Decks ".to_string(),
+                " V ".to_string(), format!(" {} Cards ", self.card_style.toggle()),
+            ]);
+            return spans;
+        }
+        if self.drill == CountDrill::Deviations {
+            spans.extend([
+                " B ".to_string(), " Basic Strategy ".to_string(),
+                " I ".to_string(), " Index Play ".to_string(),
+                " S ".to_string(), " Index Set ".to_string(),
+                " N ".to_string(), " Start Over ".to_string(),
+                " V ".to_string(), format!(" {} Cards ", self.card_style.toggle()),
+            ]);
+            return spans;
+        }
+        if self.drill == CountDrill::BackCounting {
+            spans.extend([
+                " Space ".to_string(), if self.paused { " Deal " } else { " Pause " }.to_string(),
+                " W ".to_string(), if self.back_counter.is_seated() { " Leave " } else { " Sit Down " }.to_string(),
+                " E ".to_string(), " Entry Threshold ".to_string(),
+                " H/L ".to_string(), " Speed ".to_string(),
+                " S ".to_string(), " Counting System ".to_string(),
+                " D ".to_string(), " Decks ".to_string(),
+                " N ".to_string(), " New Shoe ".to_string(),
+                " V ".to_string(), format!(" {} Cards ", self.card_style.toggle()),
+            ]);
+            return spans;
+        }
+        if self.drill == CountDrill::DeckEstimation {
+            spans.extend([
+                " Enter ".to_string(), " Give Decks Remaining ".to_string(),
+                " D ".to_string(), " Decks ".to_string(),
+                " N ".to_string(), " Start Over ".to_string(),
+            ]);
+            return spans;
+        }
+        if self.trainer.awaiting_count() {
+            spans.extend([" Enter ".to_string(), " Give Count ".to_string()]);
+        } else if !self.trainer.is_finished() {
+            spans.extend([" Space ".to_string(), if self.paused { " Deal " } else { " Pause " }.to_string()]);
+        }
+        spans.extend([
+            " H/L ".to_string(), " Speed ".to_string(),
+            " S ".to_string(), " Counting System ".to_string(),
+            " D ".to_string(), " Decks ".to_string(),
+            " N ".to_string(), " New Shoe ".to_string(),
+            " V ".to_string(), format!(" {} Cards ", self.card_style.toggle()),
+        ]);
+        spans
+    }
+}
+
+/// Answers, share right, average error and average time, one to a line.
+fn summary_lines(summary: &DrillSummary, units: &str) -> Vec<Line<'static>> {
+    let mut lines = vec![
+        Line::from(format!("Answers: {}", summary.answers)),
+        Line::from(format!("Right: {} ({:.0}%)", summary.correct, summary.accuracy() * 100.0)),
+    ];
+    if let Some(error) = summary.average_error {
+        lines.push(Line::from(format!("Average Error: {:.2}{}", error, units)));
+    }
+    if let Some(time) = summary.average_time {
+        lines.push(Line::from(format!("Average Time: {:.1}s", time)));
+    }
+    lines
+}
+
+/// An index play the way it's said, e.g. "Stand 16 vs 10 at 0 or more".
+fn describe_index_play(play: &IndexPlay) -> String {
+    match &play.situation {
+        IndexSituation::Insurance => format!("Insure at {}", play.counts()),
+        IndexSituation::Hand(scenario) => format!("{} {} at {}", play.play_name(), scenario, play.counts()),
+    }
+}
+
+/// A stretch at the watched table, e.g. "+2.4 to +0.3: +2 in 6 Rounds".
+fn describe_entry(entry: &Entry) -> String {
+    match entry.exit_true_count {
+        Some(exit) => format!("{:+.1} to {:+.1}: {:+} in {} Rounds", entry.true_count, exit, entry.net, entry.rounds),
+        None => format!("{:+.1}, Seated: {:+} in {} Rounds", entry.true_count, entry.net, entry.rounds),
+    }
+}
+
+// ---- TRAIT IMPLEMENTATIONS ----
+impl Model for CountTrainerScreen {
+    fn update(&mut self) -> std::io::Result<ModelResponse> {
+        if let Event::Key(key) = read_event(false)? {
+            if key.kind == event::KeyEventKind::Release {
+                return Ok(ModelResponse::Refresh);
+            }
+            if self.showing_stats {
+                return match key.code {
+                    KeyCode::Char('q') => Ok(ModelResponse::Exit),
+                    KeyCode::Char('m') => Ok(ModelResponse::NavToMainMenu),
+                    KeyCode::Tab => {
+                        self.switch_drill();
+                        Ok(ModelResponse::Refresh)
+                    }
+                    KeyCode::Char('t') | KeyCode::Esc => {
+                        self.toggle_stats();
+                        Ok(ModelResponse::Refresh)
+                    }
+                    _ => Ok(ModelResponse::Refresh),
+                };
+            }
+            // Digits, signs and Enter go to the count while it's asked for
+            if self.taking_count()
+                && matches!(key.code, KeyCode::Enter | KeyCode::Backspace | KeyCode::Char('0'..='9' | '-' | '+' | '.'))
+            {
+                self.update_count_input(key.code);
+                return Ok(ModelResponse::Refresh);
+            }
+            return match key.code {
+                KeyCode::Char('q') => Ok(ModelResponse::Exit),
+                KeyCode::Char('m') => Ok(ModelResponse::NavToMainMenu),
+                KeyCode::Tab => {
+                    self.switch_drill();
+                    Ok(ModelResponse::Refresh)
+                }
+                KeyCode::Char('t') => {
+                    self.toggle_stats();
+                    Ok(ModelResponse::Refresh)
+                }
+                KeyCode::Char('r') if self.drill == CountDrill::TrueCount => {
+                    self.true_count.set_rounding(self.true_count.rounding().toggle());
+                    Ok(ModelResponse::Refresh)
+                }
+                // The rest only apply to dealing the shoe, except changing the decks and starting over
+                KeyCode::Char('d') => {
+                    self.decks = (self.decks + 1) % DECK_OPTIONS.len();
+                    self.new_shoe();
+                    Ok(ModelResponse::Refresh)
+                }
+                KeyCode::Char('n') => {
+                    self.new_shoe();
+                    Ok(ModelResponse::Refresh)
+                }
+                KeyCode::Char('b') if self.drill == CountDrill::Deviations => {
+                    self.answer_deviation(false);
+                    Ok(ModelResponse::Refresh)
+                }
+                KeyCode::Char('i') if self.drill == CountDrill::Deviations => {
+                    self.answer_deviation(true);
+                    Ok(ModelResponse::Refresh)
+                }
+                KeyCode::Char('s') if self.drill == CountDrill::Deviations => {
+                    self.cycle_index_set();
+                    Ok(ModelResponse::Refresh)
+                }
+                KeyCode::Char('v') if self.drill == CountDrill::Deviations => {
+                    self.card_style = self.card_style.toggle();
+                    Ok(ModelResponse::Refresh)
+                }
+                KeyCode::Char('w') if self.drill == CountDrill::BackCounting => {
+                    self.toggle_seat();
+                    Ok(ModelResponse::Refresh)
+                }
+                KeyCode::Char('e') if self.drill == CountDrill::BackCounting => {
+                    self.cycle_entry_threshold();
+                    Ok(ModelResponse::Refresh)
+                }
+                _ if matches!(self.drill, CountDrill::TrueCount | CountDrill::DeckEstimation | CountDrill::Deviations) => Ok(ModelResponse::Refresh),
+                KeyCode::Char(' ') if self.drill == CountDrill::SpeedCount => {
+                    if self.speed_run.is_none() {
+                        self.start_speed_run();
+                    }
+                    Ok(ModelResponse::Refresh)
+                }
+                KeyCode::Char(' ') => {
+                    self.toggle_pause();
+                    Ok(ModelResponse::Refresh)
+                }
+                KeyCode::Char('l') | KeyCode::Right => {
+                    self.change_speed(1);
+                    Ok(ModelResponse::Refresh)
+                }
+                KeyCode::Char('h') | KeyCode::Left => {
+                    self.change_speed(-1);
+                    Ok(ModelResponse::Refresh)
+                }
+                KeyCode::Char('s') => {
+                    self.system = (self.system + 1) % COUNTING_SYSTEMS.len();
+                    self.new_shoe();
+                    Ok(ModelResponse::Refresh)
+                }
+                KeyCode::Char('v') => {
+                    self.card_style = self.card_style.toggle();
+                    Ok(ModelResponse::Refresh)
+                }
+                _ => Ok(ModelResponse::Refresh),
+            }
+        }
+        Ok(ModelResponse::Refresh)
+    }
+
+    fn tick(&mut self) {
+        if self.showing_stats {
+            return;
+        }
+        if self.drill == CountDrill::SpeedCount {
+            self.tick_speed_run();
+            return;
+        }
+        if self.drill == CountDrill::BackCounting {
+            self.tick_back_counting();
+            return;
+        }
+        if self.drill != CountDrill::RunningCount || self.paused || self.last_card.elapsed() < SPEEDS[self.speed] {
+            return;
+        }
+        if self.trainer.deal().is_some() {
+            self.last_card = Instant::now();
+            self.message.clear();
+        }
+    }
+
+    fn ui(&mut self, frame: &mut Frame) {
+        let main_chunks = create_common_layout(frame.area());
+        let horizontal_chunks = split_content_horizontally(main_chunks[1]);
+
+        let left_section = horizontal_chunks[0];
+        render_border(frame, left_section);
+        let right_section = horizontal_chunks[1];
+        render_border(frame, right_section);
+        let (left_area, right_area) = (
+            left_section.inner(Margin { vertical: 1, horizontal: 1 }),
+            right_section.inner(Margin { vertical: 1, horizontal: 1 }),
+        );
+        match self.drill {
+            _ if self.showing_stats => {
+                render_centered_text(frame, left_section, &format!(" {} Stats ", self.drill));
+                self.render_stats_summary(frame, left_area);
+                render_centered_text(frame, right_section, " Trend ");
+                self.render_stats_trend(frame, right_area);
+            }
+            CountDrill::RunningCount => {
+                render_centered_text(frame, left_section, " Shoe ");
+                self.render_progress(frame, left_area);
+                render_centered_text(frame, right_section, &format!(" {} Count Trainer ", self.trainer.system().name()));
+                self.render_stream(frame, right_area);
+            }
+            CountDrill::TrueCount => {
+                render_centered_text(frame, left_section, " Answers ");
+                self.render_true_count_stats(frame, left_area);
+                render_centered_text(frame, right_section, " True Count Drill ");
+                self.render_true_count_question(frame, right_area);
+            }
+            CountDrill::DeckEstimation => {
+                render_centered_text(frame, left_section, " Estimates ");
+                self.render_deck_estimation_stats(frame, left_area);
+                render_centered_text(frame, right_section, " Deck Estimation Drill ");
+                self.render_discard_tray(frame, right_area);
+            }
+            CountDrill::Deviations => {
+                render_centered_text(frame, left_section, " Answers ");
+                self.render_deviation_stats(frame, left_area);
+                render_centered_text(frame, right_section, " Deviation Drill ");
+                self.render_deviation_question(frame, right_area);
+            }
+            CountDrill::BackCounting => {
+                render_centered_text(frame, left_section, " Entries ");
+                self.render_back_counting_stats(frame, left_area);
+                render_centered_text(frame, right_section, " Back Counting ");
+                self.render_back_counting_table(frame, right_area);
+            }
+            CountDrill::SpeedCount => {
+                render_centered_text(frame, left_section, " Speed Runs ");
+                self.render_speed_stats(frame, left_area);
+                render_centered_text(frame, right_section, &format!(" {} Speed Count ", COUNTING_SYSTEMS[self.system].name()));
+                self.render_speed_run(frame, right_area);
+            }
+        }
+        render_streak(frame, left_section, self.streaks.get(&self.drill.to_string()));
+
+        // Render Footer
+        let footer_layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Length(5),
+                Constraint::Min(20),
+            ])
+            .split(main_chunks[2]);
+        render_key_spans(frame, self.footer_spans(), footer_layout[1]);
+    }
+}
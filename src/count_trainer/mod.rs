@@ -0,0 +1 @@
+pub mod count_trainer_screen;
\ No newline at end of file
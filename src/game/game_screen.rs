@@ -0,0 +1,1452 @@
+use crate::constants::{BET_RAMP_FILE, HISTORY_DATABASE_FILE};
+use crate::logic::app_dirs::{data_file, deviations_dir};
+use crate::logic::config::{self, strategies_dir};
+use crate::logic::history_store::HistoryStore;
+use crate::logic::bet_spread::{BetRamp, CustomRamp, RampUnits, BANKROLL_UNITS, BET_SPREADS, DEFAULT_BANKROLL_UNITS, DEFAULT_BET_SPREAD};
+use crate::logic::count_trainer::{CountQuiz, QUIZ_EVERY};
+use crate::logic::counting::{COUNTING_SYSTEMS, HI_LO};
+use crate::logic::decision_accuracy::DecisionAccuracy;
+use crate::logic::deviations::{load_index_sets, IndexSet};
+use crate::logic::ev::{bust_chance, counts_of, ChartHand};
+use crate::logic::game::advisor::{advise, chart_hand, review, Review};
+use crate::logic::game::animation::{DealAnimation, PeekState};
+use crate::logic::game::bankroll::{Bankroll, DEFAULT_BANKROLL};
+use crate::logic::game::history::HandHistory;
+use crate::logic::game::practice::PracticeHistory;
+use crate::logic::game::round::{PlayerAction, Round, RoundPhase};
+use crate::logic::game::scenario::Scenario;
+use crate::logic::game::shoe::{Shoe, ShuffleProcedure, CUT_RANGE};
+use crate::logic::game::table::{TableConfig, PENETRATION_OPTIONS};
+use crate::logic::strategy_calculator_logic::{find_nearest_strategy, load_strategies, BlackjackStrategy, GameVariant, StrategyVariables, SurrenderRule};
+use crate::model::{Model, ModelResponse};
+use crate::widgets::card_widget::{CardStyle, CARD_HEIGHT};
+use crate::widgets::table_view::TableView;
+use crate::ui::{create_common_layout, format_net, render_border, render_centered_text, render_key_spans, split_content_horizontally, ActionPalette, read_event};
+use ratatui::crossterm::event;
+use ratatui::crossterm::event::{Event, KeyCode};
+use ratatui::layout::{Alignment, Constraint, Direction, Layout, Margin, Rect};
+use ratatui::prelude::{Line, Stylize};
+use ratatui::style::Color;
+use ratatui::widgets::{Block, Paragraph, Wrap};
+use ratatui::Frame;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::fmt;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+const DEFAULT_BET: f64 = 10.0;
+const BANKROLL_FILE: &str = "bankroll.json";
+const MIN_SPLIT_HANDS: u8 = 2;
+const MAX_SPLIT_HANDS: u8 = 4;
+const MAX_SPOTS: usize = 3;
+/// Hands per second auto play can run at
+const AUTO_PLAY_SPEEDS: [f64; 7] = [0.5, 1.0, 2.0, 5.0, 10.0, 20.0, 50.0];
+const DEFAULT_AUTO_PLAY_SPEED: usize = 1;
+/// Delay between cards being revealed
+const DEAL_DELAY: Duration = Duration::from_millis(300);
+/// How long the dealer is shown checking the hole card
+const PEEK_DELAY: Duration = Duration::from_millis(900);
+const MAX_BURN_CARDS: u8 = 3;
+/// How far the cut moves with each key press, as a fraction of the shoe
+const CUT_STEP: f64 = 0.05;
+/// Width of the shoe drawn when the player cuts
+const CUT_GAUGE_WIDTH: usize = 40;
+/// Longest scenario description that can be typed in
+const MAX_SCENARIO_LENGTH: usize = 24;
+/// Longest count that can be typed in when quizzed
+const MAX_COUNT_LENGTH: usize = 4;
+
+// ---- Count Panel ----
+/// What the count panel above the table info shows.
+#[derive(Clone, Copy, PartialEq)]
+enum CountPanel {
+    Hidden,
+    /// The running and true count of the cards seen so far
+    Shown,
+    /// The counts kept hidden, and asked for every few hands
+    Quiz,
+}
+
+impl CountPanel {
+    fn next(self) -> Self {
+        match self {
+            CountPanel::Hidden => CountPanel::Shown,
+            CountPanel::Shown => CountPanel::Quiz,
+            CountPanel::Quiz => CountPanel::Hidden,
+        }
+    }
+}
+
+/// Which count is being asked for when quizzed.
+#[derive(Clone, Copy, PartialEq)]
+enum CountQuestion {
+    Running,
+    True,
+}
+
+impl fmt::Display for CountQuestion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CountQuestion::Running => write!(f, "Running Count"),
+            CountQuestion::True => write!(f, "True Count"),
+        }
+    }
+}
+
+// ---- Game Screen ----
+pub struct GameScreen {
+    rules: StrategyVariables,
+    /// Surrender rule to go back to when leaving Free Bet
+    classic_surrender: SurrenderRule,
+    table: TableConfig,
+    shoe: Shoe,
+    round: Option<Round>,
+    /// Wager for each betting spot in play
+    spot_bets: Vec<f64>,
+    /// Spot whose bet the +/- keys adjust
+    betting_spot: usize,
+    /// Index into the table's chip denominations
+    selected_chip: usize,
+    session_net: f64,
+    bankroll: Bankroll,
+    insurance_offers: u32,
+    insurance_correct: u32,
+    /// Loaded strategy charts, the one matching the table rules is used for hints
+    strategies: HashMap<String, BlackjackStrategy>,
+    show_hints: bool,
+    /// The last round's decisions graded against the strategy chart
+    feedback: Vec<Review>,
+    decisions_correct: u32,
+    decisions_reviewed: u32,
+    /// Session hand history, shared with the hand history screen
+    history: Rc<RefCell<HandHistory>>,
+    /// Database every hand and chart cell play is kept in, none when it can't be opened
+    store: Option<HistoryStore>,
+    /// How hints color the play, shared with the other screens
+    palette: Rc<Cell<ActionPalette>>,
+    /// Hi-Lo running and true count when the current round's bets went out
+    count_at_bet: (i32, f64),
+    count_panel: CountPanel,
+    /// Position in `COUNTING_SYSTEMS` of the count the panel keeps
+    count_system: usize,
+    count_quiz: CountQuiz,
+    /// Count being typed in when quizzed, and which count it is
+    count_input: Option<(CountQuestion, String)>,
+    /// Positions in `BET_SPREADS` and `BANKROLL_UNITS` of the bet ramp the
+    /// panel recommends bets from, one past the spreads for the custom ramp
+    bet_spread: usize,
+    bankroll_units: usize,
+    /// Ramp saved from the bet ramp finder, offered after the spreads
+    custom_ramp: Option<CustomRamp>,
+    /// Index plays, insurance is graded against the counted system's index
+    index_sets: Vec<IndexSet>,
+    /// Bets placed while counting, and how many were what the ramp called for
+    bets_checked: u32,
+    bets_on_ramp: u32,
+    /// Whether the engine is playing basic strategy on its own
+    auto_play: bool,
+    /// Practice rounds aren't scored and decisions can be taken back
+    practice: bool,
+    practice_history: PracticeHistory,
+    /// Situation the shoe is stacked to deal every round, in practice
+    scenario: Option<Scenario>,
+    /// Scenario being typed in
+    scenario_input: Option<String>,
+    /// Where the player is about to cut the freshly shuffled shoe
+    cutting: Option<f64>,
+    /// Index into `AUTO_PLAY_SPEEDS`
+    auto_play_speed: usize,
+    card_style: CardStyle,
+    /// Which of the round's cards have been revealed so far
+    animation: DealAnimation,
+    /// When the last card was revealed or auto play hand played
+    last_step: Instant,
+    message: String,
+}
+
+impl GameScreen {
+    pub fn new(history: Rc<RefCell<HandHistory>>, palette: Rc<Cell<ActionPalette>>) -> Self {
+        let rules = config::current().rules;
+        let table = config::current().table;
+        let shoe = Shoe::from_rules(&rules, &table);
+        let bet = table.clamp_bet(DEFAULT_BET);
+        let classic_surrender = match rules.variant {
+            GameVariant::Classic => rules.surrender_allowed,
+            GameVariant::FreeBet => SurrenderRule::AnyUpcard,
+        };
+
+        Self {
+            rules,
+            classic_surrender,
+            table,
+            shoe,
+            round: None,
+            spot_bets: vec![bet],
+            betting_spot: 0,
+            selected_chip: 0,
+            session_net: 0.0,
+            bankroll: Bankroll::load(&data_file(BANKROLL_FILE)),
+            insurance_offers: 0,
+            insurance_correct: 0,
+            strategies: load_strategies(&strategies_dir()),
+            show_hints: false,
+            feedback: Vec::new(),
+            decisions_correct: 0,
+            decisions_reviewed: 0,
+            history,
+            store: HistoryStore::open(&data_file(HISTORY_DATABASE_FILE), "Game").ok(),
+            palette,
+            count_at_bet: (0, 0.0),
+            count_panel: CountPanel::Hidden,
+            count_system: 0,
+            count_quiz: CountQuiz::new(),
+            count_input: None,
+            bet_spread: DEFAULT_BET_SPREAD,
+            bankroll_units: DEFAULT_BANKROLL_UNITS,
+            custom_ramp: CustomRamp::load(&data_file(BET_RAMP_FILE)),
+            index_sets: load_index_sets(&deviations_dir()),
+            bets_checked: 0,
+            bets_on_ramp: 0,
+            auto_play: false,
+            practice: false,
+            practice_history: PracticeHistory::new(),
+            scenario: None,
+            scenario_input: None,
+            cutting: None,
+            auto_play_speed: DEFAULT_AUTO_PLAY_SPEED,
+            card_style: CardStyle::default(),
+            animation: DealAnimation::new(),
+            last_step: Instant::now(),
+            message: "Press N to deal".to_string(),
+        }
+    }
+
+    fn round_in_progress(&self) -> bool {
+        matches!(&self.round, Some(round) if round.phase() != RoundPhase::Complete)
+    }
+
+    fn insurance_offered(&self) -> bool {
+        matches!(&self.round, Some(round) if round.phase() == RoundPhase::Insurance)
+    }
+
+    /// Deals the next round, returns whether a round was dealt.
+    fn deal(&mut self) -> bool {
+        if self.round_in_progress() {
+            self.message = "Finish the current hand first".to_string();
+            return false;
+        }
+        // Quizzed on the count every few hands, before the bets go out
+        if self.count_panel == CountPanel::Quiz && !self.auto_play && self.count_quiz.is_due() {
+            self.ask_count();
+            return false;
+        }
+        // Scenarios are dealt to a single spot
+        let spots = if self.scenario.is_some() { 1 } else { self.spot_bets.len() };
+        // Practice money never leaves the bankroll
+        let total_bet: f64 = self.spot_bets[..spots].iter().sum();
+        if !self.practice && !self.bankroll.can_cover(0.0, self.table.min_bet) {
+            self.message = "Bankroll can't cover the table minimum, press B to buy in again".to_string();
+            return false;
+        }
+        if !self.practice && !self.bankroll.can_cover(0.0, total_bet) {
+            self.message = format!("Bankroll can't cover ${:.2} in bets, lower your wager", total_bet);
+            return false;
+        }
+
+        let shuffled = self.shoe.needs_shuffle();
+        if shuffled {
+            // The player is offered the cut, auto play leaves it to the dealer
+            if self.table.player_cuts && !self.auto_play {
+                self.cutting = Some(0.5);
+                self.message.clear();
+                return false;
+            }
+            self.shoe.shuffle();
+        }
+
+        if let Some(scenario) = &self.scenario
+            && scenario.stack(&mut self.shoe, spots).is_err() {
+            // The cards it needs have all been dealt, start the shoe over
+            self.shoe.shuffle();
+            if let Err(err) = scenario.stack(&mut self.shoe, spots) {
+                self.message = err;
+                return false;
+            }
+        }
+
+        let ramp_warning = self.check_bets_against_ramp(spots);
+        self.count_at_bet = (self.shoe.running_count(&HI_LO), self.shoe.true_count(&HI_LO));
+        let round = Round::deal(&self.rules, &self.table, &self.spot_bets[..spots], &mut self.shoe);
+        self.animation.start(&round);
+        self.last_step = Instant::now();
+        self.round = Some(round);
+        if !self.auto_play {
+            self.count_quiz.round_dealt();
+        }
+        self.practice_history.clear();
+        self.feedback.clear();
+        self.message.clear();
+        if shuffled {
+            self.message = "Cut card reached, the shoe has been shuffled".to_string();
+        }
+        if self.insurance_offered() {
+            self.message = "Dealer shows an Ace. Insurance?".to_string();
+        }
+        self.on_round_updated();
+        if let Some(warning) = ramp_warning {
+            self.message = if self.message.is_empty() { warning } else { format!("{}  |  {}", warning, self.message) };
+        }
+        true
+    }
+
+    /// The counted system's insurance index while the player is counting.
+    /// Auto play sticks to basic strategy, so it never has one.
+    fn insurance_index(&self) -> Option<i32> {
+        if self.count_panel == CountPanel::Hidden || self.auto_play {
+            return None;
+        }
+        let system = COUNTING_SYSTEMS[self.count_system];
+        self.index_sets.iter()
+            .filter(|set| set.system == system.name())
+            .find_map(|set| set.insurance_index())
+    }
+
+    fn decide_insurance(&mut self, take: bool) {
+        let counted = self.insurance_index().map(|index| (index, self.seen_true_count()));
+        let Some(round) = self.round.as_mut() else {
+            return;
+        };
+        let cost = round.total_wagered() / 2.0;
+        if take && !self.practice && !self.bankroll.can_cover(round.total_wagered(), cost) {
+            self.message = "Bankroll can't cover the insurance bet".to_string();
+            return;
+        }
+        if self.practice {
+            self.practice_history.record(round, &self.shoe);
+        }
+        if let Some((index, true_count)) = counted {
+            round.set_insurance_index(index, true_count);
+        }
+        if let Err(err) = round.resolve_insurance(take, &mut self.shoe) {
+            self.message = err;
+            return;
+        }
+
+        if let Some(decision) = round.insurance()
+            && !self.auto_play {
+            if !self.practice {
+                self.insurance_offers += 1;
+                if decision.correct {
+                    self.insurance_correct += 1;
+                }
+            }
+            let verdict = if decision.correct { "correct" } else { "incorrect" };
+            let advice = if round.insurance_recommended() { "take" } else { "decline" };
+            let by = match counted {
+                Some((index, true_count)) => format!("at a true count of {:+.1} the {:+} index", true_count, index),
+                None => "basic strategy".to_string(),
+            };
+            self.message = format!(
+                "Insurance {}: {}, {} says {}",
+                if decision.taken { "taken" } else { "declined" },
+                verdict,
+                by,
+                advice,
+            );
+        }
+        self.on_round_updated();
+    }
+
+    fn act(&mut self, action: PlayerAction) {
+        let Some(round) = self.round.as_mut() else {
+            self.message = "Press N to deal".to_string();
+            return;
+        };
+        // Doubling and splitting both put another bet the size of the hand's on
+        // the table, unless Free Bet is covering it
+        if matches!(action, PlayerAction::Double | PlayerAction::Split)
+            && !self.practice
+            && round.can(action)
+            && let Some(bet) = round.action_cost(action)
+            && !self.bankroll.can_cover(round.total_wagered(), bet) {
+            self.message = format!("Bankroll can't cover another ${:.2} to {}", bet, action.to_string().to_lowercase());
+            return;
+        }
+        if self.practice && round.can(action) {
+            self.practice_history.record(round, &self.shoe);
+        }
+        let index = round.active_hand_index();
+        match round.apply(action, &mut self.shoe) {
+            Ok(()) => {
+                if action == PlayerAction::Split
+                    && let Some(index) = index {
+                    self.animation.split(index);
+                }
+                self.message.clear();
+                self.on_round_updated();
+            }
+            Err(err) => self.message = err,
+        }
+    }
+
+    /// Switches between scored play and practice, only between rounds.
+    fn toggle_practice(&mut self) {
+        if self.round_in_progress() {
+            self.message = "Practice mode can only change between hands".to_string();
+            return;
+        }
+        self.practice = !self.practice;
+        self.practice_history.clear();
+        // A stacked shoe has no place in scored play
+        self.scenario = None;
+        self.message = if self.practice {
+            "Practice mode: hands aren't scored, Z takes back a decision, W re-deals the hand".to_string()
+        } else {
+            "Practice mode off, hands count again".to_string()
+        };
+    }
+
+    fn start_scenario_input(&mut self) {
+        if self.round_in_progress() {
+            self.message = "Scenarios can only change between hands".to_string();
+            return;
+        }
+        self.scenario_input = Some(self.scenario.map(|scenario| scenario.to_string()).unwrap_or_default());
+        self.message.clear();
+    }
+
+    /// Typing a scenario takes every key until it's dealt or cancelled.
+    fn update_scenario_input(&mut self, key: KeyCode) {
+        let Some(input) = self.scenario_input.as_mut() else {
+            return;
+        };
+        match key {
+            KeyCode::Enter if input.trim().is_empty() => {
+                self.scenario_input = None;
+                self.scenario = None;
+                self.message = "Scenario cleared, dealing from the shoe as it comes".to_string();
+            }
+            KeyCode::Enter => match Scenario::parse(input) {
+                Ok(scenario) => {
+                    self.scenario_input = None;
+                    self.scenario = Some(scenario);
+                    // Stacked rounds are practice, they'd skew the bankroll and stats
+                    self.practice = true;
+                    self.message = format!("Dealing {} every hand, press O to change or clear it", scenario);
+                }
+                Err(err) => self.message = err,
+            },
+            KeyCode::Esc => {
+                self.scenario_input = None;
+                self.message.clear();
+            }
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Char(c) if input.chars().count() < MAX_SCENARIO_LENGTH => input.push(c),
+            _ => {}
+        }
+    }
+
+    /// Shows the count panel, then quizzes on the count, then hides it.
+    fn cycle_count_panel(&mut self) {
+        self.count_panel = self.count_panel.next();
+        let name = COUNTING_SYSTEMS[self.count_system].name();
+        self.message = match self.count_panel {
+            CountPanel::Hidden => "Count panel hidden".to_string(),
+            CountPanel::Shown => format!("Showing the {} count of the cards seen", name),
+            CountPanel::Quiz => format!("{} count hidden, you'll be asked for it every {} hands", name, QUIZ_EVERY),
+        };
+    }
+
+    fn cycle_count_system(&mut self) {
+        self.count_system = (self.count_system + 1) % COUNTING_SYSTEMS.len();
+        let system = COUNTING_SYSTEMS[self.count_system];
+        self.message = match &self.custom_ramp {
+            // The custom ramp only bets its own system's counts
+            Some(custom) if self.bet_spread == BET_SPREADS.len() && !custom.is_for(system) => format!(
+                "Counting with {}, spreading {} as the custom ramp is for {}",
+                system.name(),
+                BET_SPREADS[DEFAULT_BET_SPREAD],
+                custom.system,
+            ),
+            _ => format!("Counting with {}", system.name()),
+        };
+    }
+
+    /// Running count of the cards shown so far, for the panel's system.
+    /// Cards still being revealed and a face down hole card aren't counted.
+    fn seen_running_count(&self) -> i32 {
+        let system = COUNTING_SYSTEMS[self.count_system];
+        let hidden = self.round.as_ref().map(|round| self.animation.hidden_cards(round)).unwrap_or_default();
+        self.shoe.running_count(system) - hidden.iter().map(|card| system.tag(card.rank)).sum::<i32>()
+    }
+
+    fn seen_true_count(&self) -> f64 {
+        COUNTING_SYSTEMS[self.count_system].true_count(self.seen_running_count(), self.shoe.decks_remaining().max(0.5))
+    }
+
+    /// The bet ramp recommended bets come from, sized to the current balance.
+    fn bet_ramp(&self) -> BetRamp {
+        let units = match BET_SPREADS.get(self.bet_spread) {
+            Some(spread) => RampUnits::Spread(*spread),
+            None => RampUnits::custom_or(self.custom_ramp.clone(), COUNTING_SYSTEMS[self.count_system], BET_SPREADS[DEFAULT_BET_SPREAD]),
+        };
+        BetRamp::new(
+            units,
+            self.bankroll.balance,
+            BANKROLL_UNITS[self.bankroll_units],
+            self.table.min_bet,
+            self.table.max_bet,
+        )
+    }
+
+    /// Moves on to the next spread, and on to the custom ramp after the
+    /// last when one has been saved. The ramp is read again each time so
+    /// one saved since the game started is picked up, and picking it counts
+    /// with the system it was worked out for.
+    fn cycle_bet_spread(&mut self) {
+        self.custom_ramp = CustomRamp::load(&data_file(BET_RAMP_FILE));
+        let choices = BET_SPREADS.len() + self.custom_ramp.is_some() as usize;
+        self.bet_spread = (self.bet_spread + 1) % choices;
+        if let Some(custom) = self.custom_ramp.as_ref().filter(|_| self.bet_spread == BET_SPREADS.len()) {
+            match COUNTING_SYSTEMS.iter().position(|system| custom.is_for(*system)) {
+                Some(system) => self.count_system = system,
+                None => {
+                    self.bet_spread = 0;
+                    self.message = format!("The saved ramp is for {}, which the game can't count with", custom.system);
+                    return;
+                }
+            }
+        }
+        let ramp = self.bet_ramp();
+        self.message = match &self.custom_ramp {
+            Some(custom) if self.bet_spread == BET_SPREADS.len() => format!("Betting the {} ramp for {} in units of ${:.0}", custom, custom.system, ramp.unit),
+            _ => format!("Spreading {} units of ${:.0}", ramp.spread, ramp.unit),
+        };
+    }
+
+    fn cycle_bankroll_units(&mut self) {
+        self.bankroll_units = (self.bankroll_units + 1) % BANKROLL_UNITS.len();
+        self.message = format!(
+            "Bankroll split into {} units of ${:.0}",
+            BANKROLL_UNITS[self.bankroll_units],
+            self.bet_ramp().unit,
+        );
+    }
+
+    /// Checks the bets about to go out against the ramp while counting,
+    /// returning a warning when any are off it. Auto play and stacked
+    /// scenarios don't bet off the count, so they aren't checked.
+    fn check_bets_against_ramp(&mut self, spots: usize) -> Option<String> {
+        if self.count_panel == CountPanel::Hidden || self.auto_play || self.scenario.is_some() {
+            return None;
+        }
+        let true_count = self.seen_true_count();
+        let ramp = self.bet_ramp();
+        let off_ramp: Vec<String> = self.spot_bets[..spots].iter()
+            .filter(|bet| !ramp.is_on_ramp(**bet, true_count))
+            .map(|bet| format!("${:.2}", bet))
+            .collect();
+        self.bets_checked += spots as u32;
+        self.bets_on_ramp += (spots - off_ramp.len()) as u32;
+        if off_ramp.is_empty() {
+            return None;
+        }
+        Some(format!(
+            "Off the ramp: bet {} at a true count of {:+.1}, the ramp says ${:.2}",
+            off_ramp.join(" and "),
+            true_count,
+            ramp.bet(true_count),
+        ))
+    }
+
+    /// Asks for the running count before the next hand is dealt.
+    fn ask_count(&mut self) {
+        self.count_quiz.ask();
+        self.count_input = Some((CountQuestion::Running, String::new()));
+        self.message = "Before the next hand, what's the count?".to_string();
+    }
+
+    /// Typing a count takes every key until it's answered or skipped.
+    fn update_count_input(&mut self, key: KeyCode) {
+        let Some((question, input)) = self.count_input.as_mut() else {
+            return;
+        };
+        match key {
+            KeyCode::Enter => match input.parse() {
+                Ok(answer) => {
+                    let question = *question;
+                    self.answer_count(question, answer);
+                }
+                Err(_) => self.message = "Type the count, e.g. 3 or -2".to_string(),
+            },
+            KeyCode::Esc => {
+                self.count_input = None;
+                self.message = "Count skipped, press N to deal".to_string();
+            }
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Char(c @ ('0'..='9' | '-' | '+')) if input.len() < MAX_COUNT_LENGTH => input.push(c),
+            _ => {}
+        }
+    }
+
+    /// Scores a count given when quizzed. A balanced system's running count
+    /// is followed by the true count.
+    fn answer_count(&mut self, question: CountQuestion, answer: i32) {
+        let verdict = match question {
+            CountQuestion::Running => {
+                let running_count = self.seen_running_count();
+                if self.count_quiz.answer_running_count(answer, running_count) {
+                    format!("Right, the running count is {:+}", running_count)
+                } else {
+                    format!("The running count is {:+}, not {:+}", running_count, answer)
+                }
+            }
+            CountQuestion::True => {
+                let true_count = self.seen_true_count();
+                if self.count_quiz.answer_true_count(answer, true_count) {
+                    format!("Right, the true count is {:+.1}", true_count)
+                } else {
+                    format!("The true count is {:+.1}, not {:+}", true_count, answer)
+                }
+            }
+        };
+        if question == CountQuestion::Running && COUNTING_SYSTEMS[self.count_system].is_balanced() {
+            self.count_input = Some((CountQuestion::True, String::new()));
+            self.message = format!("{}. And the true count?", verdict);
+        } else {
+            self.count_input = None;
+            self.message = format!("{}  -  Press N to deal", verdict);
+        }
+    }
+
+    /// Takes back the last decision of a practice round.
+    fn undo(&mut self) {
+        match self.practice_history.undo() {
+            Some((round, shoe)) => self.restore(round, shoe, "Took back the last decision"),
+            None => self.message = self.nothing_to_undo(),
+        }
+    }
+
+    /// Puts a practice round back to how it was dealt, to play it again.
+    fn redeal(&mut self) {
+        match self.practice_history.redeal() {
+            Some((round, shoe)) => self.restore(round, shoe, "Dealt the same hand again"),
+            None => self.message = self.nothing_to_undo(),
+        }
+    }
+
+    fn nothing_to_undo(&self) -> String {
+        if self.practice {
+            "No decisions to take back".to_string()
+        } else {
+            "Decisions can only be taken back in practice mode, press X".to_string()
+        }
+    }
+
+    fn restore(&mut self, round: Round, shoe: Shoe, message: &str) {
+        self.animation.start(&round);
+        self.animation.finish(&round);
+        self.shoe = shoe;
+        self.feedback.clear();
+        self.message = if round.phase() == RoundPhase::Insurance {
+            "Dealer shows an Ace. Insurance?".to_string()
+        } else {
+            message.to_string()
+        };
+        self.round = Some(round);
+    }
+
+    /// Sets how many spots are played each round, only between rounds.
+    fn set_spots(&mut self, spots: usize) {
+        if self.round_in_progress() {
+            self.message = "Spots can only change between hands".to_string();
+            return;
+        }
+        // Keep the existing bets, new spots start at the default wager
+        self.spot_bets.resize(spots.clamp(1, MAX_SPOTS), self.table.clamp_bet(DEFAULT_BET));
+        self.betting_spot = self.betting_spot.min(self.spot_bets.len() - 1);
+    }
+
+    /// Adds or removes one of the selected chip on the selected spot, only between rounds.
+    fn adjust_bet(&mut self, direction: f64) {
+        if self.round_in_progress() {
+            self.message = "Bets can only change between hands".to_string();
+            return;
+        }
+        let chip = self.table.chip_denominations[self.selected_chip];
+        let bet = &mut self.spot_bets[self.betting_spot];
+        *bet = self.table.adjust_bet(*bet, chip * direction);
+    }
+
+    fn cycle_chip(&mut self, increment: isize) {
+        let chips = self.table.chip_denominations.len() as isize;
+        self.selected_chip = (self.selected_chip as isize + increment).rem_euclid(chips) as usize;
+    }
+
+    fn cycle_betting_spot(&mut self) {
+        self.betting_spot = (self.betting_spot + 1) % self.spot_bets.len();
+    }
+
+    /// The strategy chart for the current table rules, or the closest one
+    /// loaded when none is for exactly these rules.
+    fn strategy(&self) -> Option<(String, &BlackjackStrategy)> {
+        find_nearest_strategy(&self.strategies, &self.rules).map(|(name, strategy, _)| (name, strategy))
+    }
+
+    /// Says which rules the chart differs on when it's only the closest one,
+    /// e.g. "Approximate chart, differs on: DAS".
+    fn approximate_chart(&self) -> Option<String> {
+        find_nearest_strategy(&self.strategies, &self.rules)
+            .filter(|(_, _, differences)| !differences.is_empty())
+            .map(|(_, _, differences)| format!("Approximate chart, differs on: {}", differences.join(", ")))
+    }
+
+    fn toggle_hints(&mut self) {
+        self.show_hints = !self.show_hints;
+        self.message = match (self.show_hints, self.strategy()) {
+            (false, _) => "Strategy hints off".to_string(),
+            (true, Some((name, _))) => match self.approximate_chart() {
+                Some(approximate) => format!("Strategy hints on, using {} ({})", name, approximate.to_lowercase()),
+                None => format!("Strategy hints on, using {}", name),
+            },
+            (true, None) => "Strategy hints on, but no charts were loaded".to_string(),
+        };
+    }
+
+    fn toggle_auto_play(&mut self) {
+        self.auto_play = !self.auto_play;
+        self.message = if self.auto_play {
+            format!("Auto play on at {} hands/s", AUTO_PLAY_SPEEDS[self.auto_play_speed])
+        } else {
+            "Auto play off".to_string()
+        };
+    }
+
+    fn change_auto_play_speed(&mut self, increment: isize) {
+        self.auto_play_speed = self.auto_play_speed
+            .saturating_add_signed(increment)
+            .min(AUTO_PLAY_SPEEDS.len() - 1);
+    }
+
+    /// Whether cards of the current round are still being revealed.
+    fn animating(&self) -> bool {
+        self.round.as_ref().is_some_and(|round| !self.animation.is_done(round))
+    }
+
+    fn finish_animation(&mut self) {
+        if let Some(round) = &self.round {
+            self.animation.finish(round);
+        }
+    }
+
+    fn auto_play_interval(&self) -> Duration {
+        Duration::from_secs_f64(1.0 / AUTO_PLAY_SPEEDS[self.auto_play_speed])
+    }
+
+    /// Plays one whole hand with basic strategy: finishing the current round
+    /// if one is in progress, otherwise dealing a new one. Auto play stops
+    /// when the next round can't be dealt.
+    fn auto_play_hand(&mut self) {
+        if !self.round_in_progress() && !self.deal() {
+            self.auto_play = false;
+            return;
+        }
+        // Auto play can run far faster than cards could be dealt one by one
+        self.finish_animation();
+
+        while let Some(round) = &self.round {
+            match round.phase() {
+                RoundPhase::Complete => break,
+                RoundPhase::Insurance => {
+                    let take = round.insurance_recommended();
+                    self.decide_insurance(take);
+                }
+                RoundPhase::PlayerTurn => {
+                    let decisions = round.decisions().len();
+                    let action = self.strategy()
+                        .and_then(|(_, strategy)| advise(strategy, round))
+                        .map_or(PlayerAction::Stand, |advice| advice.action);
+                    self.act(action);
+
+                    // The bankroll may not cover a double or split, stand instead
+                    if self.round.as_ref().is_some_and(|round| round.decisions().len() == decisions) {
+                        self.act(PlayerAction::Stand);
+                    }
+                }
+            }
+        }
+        self.finish_animation();
+    }
+
+    /// Switches between a peeking dealer and European no hole card rules.
+    fn toggle_hole_card_rule(&mut self) {
+        if self.round_in_progress() {
+            self.message = "Table rules can only change between hands".to_string();
+            return;
+        }
+        self.rules.dealer_peak = !self.rules.dealer_peak;
+    }
+
+    /// Switches between classic blackjack and Free Bet, which has no surrender.
+    fn toggle_variant(&mut self) {
+        if self.round_in_progress() {
+            self.message = "Table rules can only change between hands".to_string();
+            return;
+        }
+        (self.rules.variant, self.rules.surrender_allowed) = match self.rules.variant {
+            GameVariant::Classic => {
+                self.classic_surrender = self.rules.surrender_allowed;
+                (GameVariant::FreeBet, SurrenderRule::NotAllowed)
+            }
+            GameVariant::FreeBet => (GameVariant::Classic, self.classic_surrender),
+        };
+    }
+
+    /// Cycles where the cut card is placed, only between rounds.
+    fn cycle_penetration(&mut self) {
+        if self.round_in_progress() {
+            self.message = "Table rules can only change between hands".to_string();
+            return;
+        }
+        let current = PENETRATION_OPTIONS.iter()
+            .position(|option| *option == self.table.penetration)
+            .unwrap_or(0);
+        self.table.penetration = PENETRATION_OPTIONS[(current + 1) % PENETRATION_OPTIONS.len()];
+        self.shoe.set_penetration(self.table.penetration);
+    }
+
+    /// Switches between a perfectly random shuffle and a dealer's riffle shuffle.
+    fn toggle_shuffle_procedure(&mut self) {
+        if self.round_in_progress() {
+            self.message = "Table rules can only change between hands".to_string();
+            return;
+        }
+        self.table.shuffle = match self.table.shuffle {
+            ShuffleProcedure::Random => ShuffleProcedure::Riffle,
+            ShuffleProcedure::Riffle => ShuffleProcedure::Random,
+        };
+        self.shoe.set_procedure(self.table.shuffle);
+        self.message = format!("{} shuffle from the next shoe", self.table.shuffle);
+    }
+
+    /// Cycles how many cards are burned after a shuffle, only between rounds.
+    fn cycle_burn_cards(&mut self) {
+        if self.round_in_progress() {
+            self.message = "Table rules can only change between hands".to_string();
+            return;
+        }
+        self.table.burn_cards = (self.table.burn_cards + 1) % (MAX_BURN_CARDS + 1);
+        self.shoe.set_burn_cards(self.table.burn_cards);
+    }
+
+    fn toggle_player_cuts(&mut self) {
+        self.table.player_cuts = !self.table.player_cuts;
+    }
+
+    fn move_cut(&mut self, direction: f64) {
+        if let Some(at) = self.cutting.as_mut() {
+            *at = (*at + CUT_STEP * direction).clamp(*CUT_RANGE.start(), *CUT_RANGE.end());
+        }
+    }
+
+    /// Shuffles and cuts where the player chose, or lets the dealer cut, then deals.
+    fn cut_shoe(&mut self, player_cut: bool) {
+        let Some(at) = self.cutting.take() else {
+            return;
+        };
+        if player_cut {
+            self.shoe.shuffle_and_cut(at);
+        } else {
+            self.shoe.shuffle();
+        }
+        if self.deal() && self.message.is_empty() {
+            self.message = "Shoe shuffled and cut".to_string();
+        }
+    }
+
+    /// Cycles the maximum number of split hands, only between rounds.
+    fn cycle_split_limit(&mut self) {
+        if self.round_in_progress() {
+            self.message = "Table rules can only change between hands".to_string();
+            return;
+        }
+        self.table.max_split_hands = if self.table.max_split_hands >= MAX_SPLIT_HANDS {
+            MIN_SPLIT_HANDS
+        } else {
+            self.table.max_split_hands + 1
+        };
+    }
+
+    /// Records the result once the round has been settled.
+    fn on_round_updated(&mut self) {
+        if let Some(round) = &self.round
+            && round.phase() == RoundPhase::Complete
+            && self.practice {
+            self.message = format!("Practice {}  -  Z to undo, W to re-deal, N for a new hand", format_net(round.net_result()));
+            self.review_decisions();
+            return;
+        }
+        if let Some(round) = &self.round
+            && round.phase() == RoundPhase::Complete {
+            let net = round.net_result();
+            self.session_net += net;
+            self.bankroll.settle(net);
+            let mut message = format!("{}  -  Press N to deal again", format_net(net));
+            if round.insurance().is_some_and(|decision| decision.taken) {
+                message = format!("Insurance {}  |  {}", format_net(round.insurance_net()), message);
+            }
+            self.message = message;
+            self.history.borrow_mut().record(round, self.count_at_bet.0, self.count_at_bet.1);
+            self.review_decisions();
+            self.store_hand();
+
+            if let Err(err) = self.bankroll.save(&data_file(BANKROLL_FILE)) {
+                self.message = format!("Could not save bankroll: {}", err);
+            }
+        }
+    }
+
+    /// Grades the finished round's decisions against the strategy chart.
+    fn review_decisions(&mut self) {
+        // Auto play follows the chart, it shouldn't count towards the player's accuracy
+        if self.auto_play {
+            self.feedback.clear();
+            return;
+        }
+        let (Some(round), Some((chart, strategy))) = (&self.round, self.strategy()) else {
+            return;
+        };
+        let reviews: Vec<Review> = round.decisions().iter()
+            .filter_map(|decision| review(strategy, decision))
+            .collect();
+        // The chart row each decision was played from
+        let hands: Vec<ChartHand> = reviews.iter()
+            .map(|review| chart_hand(strategy, &review.decision.hand, review.decision.available.contains(&PlayerAction::Split)))
+            .collect();
+
+        // Practice decisions are still graded, they just don't count
+        if !self.practice {
+            self.decisions_reviewed += reviews.len() as u32;
+            self.decisions_correct += reviews.iter().filter(|review| review.is_correct()).count() as u32;
+            let mut plays = DecisionAccuracy::default();
+            for (review, hand) in reviews.iter().zip(hands) {
+                plays.record(&chart, hand, review.decision.upcard, review.is_correct());
+            }
+            if let Some(store) = &mut self.store
+                && let Err(err) = store.add_cells(&plays) {
+                self.message = format!("Could not save decision accuracy: {}", err);
+            }
+        }
+        self.feedback = reviews;
+    }
+
+    /// Keeps the round just recorded in the history database, with how
+    /// many of its decisions were right when they were graded.
+    fn store_hand(&mut self) {
+        let graded = (!self.auto_play && self.strategy().is_some())
+            .then(|| self.feedback.iter().filter(|review| review.is_correct()).count() as u32);
+        let history = self.history.borrow();
+        let (Some(store), Some(record)) = (&mut self.store, history.records().last()) else {
+            return;
+        };
+        if let Err(err) = store.record_hand(record, graded) {
+            self.message = format!("Could not save the hand: {}", err);
+        }
+    }
+
+    fn decision_accuracy(&self) -> String {
+        if self.decisions_reviewed == 0 {
+            return "-".to_string();
+        }
+        format!("{:.1}%", self.decisions_correct as f64 / self.decisions_reviewed as f64 * 100.0)
+    }
+
+    /// Starts over with a fresh bankroll, only between rounds.
+    fn rebuy(&mut self) {
+        if self.round_in_progress() {
+            self.message = "You can only buy in between hands".to_string();
+            return;
+        }
+        self.bankroll = Bankroll::new(DEFAULT_BANKROLL);
+        self.message = format!("Bought in for ${:.2}", DEFAULT_BANKROLL);
+        if let Err(err) = self.bankroll.save(&data_file(BANKROLL_FILE)) {
+            self.message = format!("Could not save bankroll: {}", err);
+        }
+    }
+
+    /// Money on the table in the current round.
+    fn committed(&self) -> f64 {
+        match &self.round {
+            Some(round) if round.phase() != RoundPhase::Complete => round.total_wagered(),
+            _ => 0.0,
+        }
+    }
+
+    /// Warns when the bankroll can no longer cover the table minimum, the
+    /// next round's bets, or a double/split on the active hand.
+    fn bankroll_warning(&self) -> Option<String> {
+        if let Some(round) = &self.round
+            && let Some(bet) = round.action_cost(PlayerAction::Double)
+            && !self.bankroll.can_cover(self.committed(), bet) {
+            return Some("Can't cover a double or split".to_string());
+        }
+        if self.round_in_progress() {
+            return None;
+        }
+        if !self.bankroll.can_cover(0.0, self.table.min_bet) {
+            Some("Below the table minimum".to_string())
+        } else if !self.bankroll.can_cover(0.0, self.spot_bets.iter().sum()) {
+            Some("Can't cover the current bets".to_string())
+        } else {
+            None
+        }
+    }
+
+    fn render_table_info(&self, frame: &mut Frame, rect: Rect) {
+        let mut lines = vec![
+            Line::from(""),
+            Line::from("Rules").bold(),
+            Line::from(format!("Variant: {}", self.rules.variant)),
+            Line::from(format!("Decks: {}", self.rules.decks)),
+            Line::from(if self.rules.dealer_stands_on_soft_17 { "Dealer Stands on Soft 17" } else { "Dealer Hits Soft 17" }),
+            Line::from(format!("Double After Split: {}", if self.rules.double_after_split { "Allowed" } else { "Not Allowed" })),
+            Line::from(format!("Surrender: {}", self.rules.surrender_allowed.to_string())),
+            Line::from(format!("Dealer Peek: {}", if self.rules.dealer_peak { "Yes" } else { "No (ENHC)" })),
+            Line::from(format!("Split Up To: {} Hands", self.table.max_split_hands)),
+            Line::from(format!("Strategy Hints: {}", match (self.show_hints, self.strategy().is_some()) {
+                (false, _) => "Off",
+                (true, true) if self.approximate_chart().is_some() => "On (Approximate)",
+                (true, true) => "On",
+                (true, false) => "No Charts Loaded",
+            })),
+            Line::from(""),
+            Line::from("Bankroll").bold(),
+            Line::from(format!("Balance: ${:.2}", self.bankroll.balance)),
+            Line::from(format!("On the Table: ${:.2}", self.committed())),
+            Line::from(format!("Since Buy In: {}", format_net(self.bankroll.profit()))),
+            Line::from(self.bankroll_warning().unwrap_or_default()).fg(Color::Red),
+            Line::from(""),
+            Line::from("Session").bold(),
+            Line::from(format!("Net: {}", format_net(self.session_net))),
+            Line::from(format!("Insurance: {}/{} Correct", self.insurance_correct, self.insurance_offers)),
+            Line::from(format!("Decisions: {}/{} Correct ({})", self.decisions_correct, self.decisions_reviewed, self.decision_accuracy())),
+            Line::from(if self.auto_play {
+                format!("Auto Play: {} Hands/s", AUTO_PLAY_SPEEDS[self.auto_play_speed])
+            } else {
+                "Auto Play: Off".to_string()
+            }),
+            Line::from(""),
+            Line::from("Betting").bold(),
+            Line::from(format!("Table Limits: ${:.0} - ${:.0}", self.table.min_bet, self.table.max_bet)),
+            Line::from(format!("Chip: < ${:.0} >", self.table.chip_denominations[self.selected_chip])),
+        ];
+
+        // Bets for the next round, the spot being adjusted is highlighted
+        for (spot, bet) in self.spot_bets.iter().enumerate() {
+            let text = format!("Spot {}: ${:.2}", spot + 1, bet);
+            if spot == self.betting_spot && self.spot_bets.len() > 1 {
+                lines.push(Line::from(format!("> {}", text)).fg(Color::Green));
+            } else {
+                lines.push(Line::from(text));
+            }
+        }
+
+        lines.extend([
+            Line::from(""),
+            Line::from("Shoe").bold(),
+            Line::from(format!("{} Decks, {:.0}% Penetration", self.shoe.total_decks(), self.shoe.penetration() * 100.0)),
+            Line::from(format!("{}, Burn {}, {} Cuts", self.table.shuffle, self.table.burn_cards, if self.table.player_cuts { "Player" } else { "Dealer" })),
+            Line::from(format!("Cards Left: {}", self.shoe.remaining())),
+            Line::from(format!("Decks Remaining: {:.1}", self.shoe.decks_remaining())),
+            Line::from(if self.shoe.needs_shuffle() { "Cut Card Out - Shuffle Next Hand" } else { "" }),
+        ]);
+
+        // The count panel goes first so it's never cut off
+        lines.splice(1..1, self.count_lines());
+
+        let info = Paragraph::new(lines)
+            .alignment(Alignment::Center)
+            .block(Block::default());
+        frame.render_widget(info, rect);
+    }
+
+    /// The count panel: the counts of the cards seen so far, or how the
+    /// quiz is going while they're hidden.
+    fn count_lines(&self) -> Vec<Line<'static>> {
+        let system = COUNTING_SYSTEMS[self.count_system];
+        let mut lines = vec![Line::from(format!("{} Count", system.name())).bold()];
+        match self.count_panel {
+            CountPanel::Hidden => return Vec::new(),
+            CountPanel::Shown => {
+                let true_count = self.seen_true_count();
+                let ramp = self.bet_ramp();
+                lines.push(Line::from(format!("Running: {:+}", self.seen_running_count())));
+                lines.push(Line::from(if system.is_balanced() {
+                    format!("True: {:+.1}", true_count)
+                } else {
+                    format!("True: {:+.1} (From Pivot)", true_count)
+                }));
+                lines.push(Line::from(format!("Spread: {} of ${:.0}", ramp.spread, ramp.unit)));
+                lines.push(Line::from(format!("Ramp Bet: ${:.2}", ramp.bet(true_count))).fg(Color::Green));
+            }
+            CountPanel::Quiz => {
+                lines.push(Line::from(format!("Quiz: {}/{} Right", self.count_quiz.right(), self.count_quiz.asked())));
+                lines.push(Line::from(match self.count_quiz.rounds_until_due() {
+                    _ if self.count_input.is_some() => "Asking Now".to_string(),
+                    0 => "Asked Before the Next Hand".to_string(),
+                    1 => "Asked in 1 Hand".to_string(),
+                    hands => format!("Asked in {} Hands", hands),
+                }));
+            }
+        }
+        if self.bets_checked > 0 {
+            lines.push(Line::from(format!("Bets on the Ramp: {}/{}", self.bets_on_ramp, self.bets_checked)));
+        }
+        lines.push(Line::from(""));
+        lines
+    }
+
+    /// Shows the basic strategy play for the active hand before the player
+    /// acts, and the chance a hit would bust it.
+    fn render_hint(&self, frame: &mut Frame, rect: Rect, round: &Round) {
+        let mut spans = Vec::new();
+        if let Some((_, strategy)) = self.strategy()
+            && let Some(advice) = advise(strategy, round)
+        {
+            spans.extend([
+                "Hint: ".into(),
+                format!(" {} ", advice.action).bold().fg(Color::Black).bg(self.palette.get().color(&strategy.action_legend, &advice.code)),
+            ]);
+            if let Some(approximate) = self.approximate_chart() {
+                spans.push(format!("  {}", approximate).fg(Color::Yellow));
+            }
+        }
+        if let Some(chance) = self.hit_bust_chance(round).filter(|chance| *chance > 0.0) {
+            if !spans.is_empty() {
+                spans.push("   ".into());
+            }
+            spans.push(format!("Bust if Hit: {:.0}%", chance * 100.0).into());
+        }
+        frame.render_widget(Paragraph::new(Line::from(spans)).alignment(Alignment::Center), rect);
+    }
+
+    /// Chance that hitting the active hand busts it, from the cards the player
+    /// hasn't seen: the rest of the shoe and the dealer's hole card.
+    fn hit_bust_chance(&self, round: &Round) -> Option<f64> {
+        let hand = &round.hands()[round.active_hand_index()?].hand;
+        if !round.can(PlayerAction::Hit) {
+            return None;
+        }
+        let hole_card = round.dealer().cards().get(1);
+        let unseen = counts_of(self.shoe.upcoming().iter().chain(hole_card));
+        Some(bust_chance(&unseen, hand.total(), hand.is_soft()))
+    }
+
+    /// Lists the last round's decisions, mistakes in red.
+    fn render_feedback(&self, frame: &mut Frame, rect: Rect) {
+        let mut spans = Vec::new();
+        for (index, review) in self.feedback.iter().enumerate() {
+            if index > 0 {
+                spans.push("; ".into());
+            }
+            let color = if review.is_correct() { Color::Green } else { Color::Red };
+            spans.push(review.to_string().fg(color));
+        }
+        let feedback = Paragraph::new(Line::from(spans))
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true });
+        frame.render_widget(feedback, rect);
+    }
+
+    fn render_table(&self, frame: &mut Frame, rect: Rect) {
+        let inner = rect.inner(Margin { vertical: 2, horizontal: 2 });
+        let table_layout = Layout::vertical([
+            Constraint::Length(CARD_HEIGHT + 2),
+            Constraint::Length(2),
+            Constraint::Min(8),
+            Constraint::Length(3),
+            Constraint::Length(2),
+        ]).split(inner);
+
+        if let Some(round) = &self.round {
+            let view = TableView::new(round, &self.animation, self.card_style);
+            view.render_dealer(frame, table_layout[0]);
+            view.render_spots(frame, table_layout[2], "Player", |spot| format!("Spot {}", spot + 1));
+            if self.show_hints && !self.animating() {
+                self.render_hint(frame, table_layout[1], round);
+            }
+        }
+
+        // Results would give away the cards still to come
+        if self.animating() {
+            return;
+        }
+        self.render_feedback(frame, table_layout[3]);
+
+        let message = match (&self.scenario_input, self.cutting, &self.count_input) {
+            (None, None, Some((question, input))) => vec![
+                Line::from(format!("{}: {}_", question, input)).fg(Color::Yellow),
+                Line::from(self.message.as_str()),
+            ],
+            (_, Some(at), _) => {
+                let cut = (at * CUT_GAUGE_WIDTH as f64).round() as usize;
+                vec![
+                    Line::from(format!("[{}|{}]", "=".repeat(cut), "=".repeat(CUT_GAUGE_WIDTH - cut))).fg(Color::Yellow),
+                    Line::from(format!("Cut the shoe {:.0}% of the way in", at * 100.0)),
+                ]
+            }
+            (Some(input), None, _) => vec![
+                Line::from(format!("Scenario: {}_", input)).fg(Color::Yellow),
+                Line::from(if self.message.is_empty() { "e.g. soft 18 vs 9, 8,8 vs 10 or 16 vs A" } else { self.message.as_str() }),
+            ],
+            (None, None, None) => vec![Line::from(self.message.as_str())],
+        };
+        let message = Paragraph::new(message)
+            .bold()
+            .alignment(Alignment::Center);
+        frame.render_widget(message, table_layout[4]);
+    }
+
+    fn footer_spans(&self) -> Vec<String> {
+        if self.cutting.is_some() {
+            return vec![
+                " Left/Right ".to_string(), " Move Cut ".to_string(),
+                " Enter ".to_string(), " Cut ".to_string(),
+                " Esc ".to_string(), " Dealer Cuts ".to_string(),
+            ];
+        }
+        if self.scenario_input.is_some() {
+            return vec![
+                " Enter ".to_string(), " Deal Scenario ".to_string(),
+                " Enter Blank ".to_string(), " Clear Scenario ".to_string(),
+                " Esc ".to_string(), " Cancel ".to_string(),
+            ];
+        }
+        if self.count_input.is_some() {
+            return vec![
+                " Enter ".to_string(), " Answer ".to_string(),
+                " Esc ".to_string(), " Skip ".to_string(),
+            ];
+        }
+        let mut spans = vec![
+            " Q ".to_string(), " Quit ".to_string(),
+            " M ".to_string(), " Menu ".to_string(),
+        ];
+
+        if self.auto_play {
+            spans.extend([" A ".to_string(), " Stop Auto Play ".to_string()]);
+            spans.extend([" </> ".to_string(), " Speed ".to_string()]);
+            return spans;
+        }
+
+        if self.insurance_offered() {
+            spans.extend([" Y ".to_string(), " Take Insurance ".to_string()]);
+            spans.extend([" N ".to_string(), " No Insurance ".to_string()]);
+            return spans;
+        }
+
+        let actions = self.round.as_ref().map(|round| round.available_actions()).unwrap_or_default();
+        if actions.is_empty() {
+            spans.extend([" N ".to_string(), " Deal ".to_string()]);
+            spans.extend([" R ".to_string(), " Split Limit ".to_string()]);
+            spans.extend([" 1-3 ".to_string(), " Spots ".to_string()]);
+            spans.extend([" +/- ".to_string(), " Bet ".to_string()]);
+            spans.extend([" [/] ".to_string(), " Chip ".to_string()]);
+            if self.spot_bets.len() > 1 {
+                spans.extend([" Tab ".to_string(), " Spot ".to_string()]);
+            }
+            spans.extend([" C ".to_string(), " Penetration ".to_string()]);
+            spans.extend([" G ".to_string(), " Shuffle ".to_string()]);
+            spans.extend([" K ".to_string(), " Burn ".to_string()]);
+            spans.extend([" T ".to_string(), " Player Cut ".to_string()]);
+            spans.extend([" E ".to_string(), " Peek/ENHC ".to_string()]);
+            spans.extend([" F ".to_string(), " Free Bet ".to_string()]);
+            spans.extend([" B ".to_string(), " Buy In ".to_string()]);
+            spans.extend([" L ".to_string(), " History ".to_string()]);
+            spans.extend([" A ".to_string(), " Auto Play ".to_string()]);
+            spans.extend([" X ".to_string(), if self.practice { " Scored Play " } else { " Practice " }.to_string()]);
+            spans.extend([" O ".to_string(), " Scenario ".to_string()]);
+            spans.extend([" V ".to_string(), format!(" {} Cards ", self.card_style.toggle())]);
+            if self.count_panel != CountPanel::Hidden {
+                spans.extend([" Shift+J ".to_string(), " Bet Spread ".to_string()]);
+                spans.extend([" Shift+I ".to_string(), " Bankroll Units ".to_string()]);
+            }
+        }
+        for action in actions {
+            let key = match action {
+                PlayerAction::Hit => " H ",
+                PlayerAction::Stand => " S ",
+                PlayerAction::Double => " D ",
+                PlayerAction::Split => " P ",
+                PlayerAction::Surrender => " U ",
+            };
+            let free = self.round.as_ref().is_some_and(|round| round.is_free(action));
+            spans.extend([key.to_string(), if free { format!(" Free {} ", action) } else { format!(" {} ", action) }]);
+        }
+        if self.practice_history.can_undo() {
+            spans.extend([" Z ".to_string(), " Undo ".to_string()]);
+            spans.extend([" W ".to_string(), " Re-deal ".to_string()]);
+        }
+        spans.extend([" ? ".to_string(), if self.show_hints { " Hide Hints " } else { " Show Hints " }.to_string()]);
+        spans.extend([" I ".to_string(), match self.count_panel.next() {
+            CountPanel::Hidden => " Hide Count ",
+            CountPanel::Shown => " Show Count ",
+            CountPanel::Quiz => " Quiz Count ",
+        }.to_string()]);
+        if self.count_panel != CountPanel::Hidden {
+            spans.extend([" J ".to_string(), " Counting System ".to_string()]);
+        }
+        spans
+    }
+}
+
+// ---- TRAIT IMPLEMENTATIONS ----
+impl Model for GameScreen {
+    fn update(&mut self) -> std::io::Result<ModelResponse> {
+        if let Event::Key(key) = read_event(self.scenario_input.is_some() || self.count_input.is_some())? {
+            if key.kind == event::KeyEventKind::Release {
+                return Ok(ModelResponse::Refresh);
+            }
+            if self.scenario_input.is_some() {
+                self.update_scenario_input(key.code);
+                return Ok(ModelResponse::Refresh);
+            }
+            if self.count_input.is_some() {
+                self.update_count_input(key.code);
+                return Ok(ModelResponse::Refresh);
+            }
+            if self.cutting.is_some() {
+                match key.code {
+                    KeyCode::Left => self.move_cut(-1.0),
+                    KeyCode::Right => self.move_cut(1.0),
+                    KeyCode::Enter => self.cut_shoe(true),
+                    KeyCode::Esc => self.cut_shoe(false),
+                    KeyCode::Char('q') => return Ok(ModelResponse::Exit),
+                    _ => {}
+                }
+                return Ok(ModelResponse::Refresh);
+            }
+            // Any key other than quit skips the rest of the dealing
+            if self.animating() && key.code != KeyCode::Char('q') {
+                self.finish_animation();
+                return Ok(ModelResponse::Refresh);
+            }
+            if self.auto_play {
+                match key.code {
+                    KeyCode::Char('q') => return Ok(ModelResponse::Exit),
+                    KeyCode::Char('a') | KeyCode::Esc => self.toggle_auto_play(),
+                    KeyCode::Char('<') | KeyCode::Char(',') => self.change_auto_play_speed(-1),
+                    KeyCode::Char('>') | KeyCode::Char('.') => self.change_auto_play_speed(1),
+                    _ => {}
+                }
+                return Ok(ModelResponse::Refresh);
+            }
+            match key.code {
+                KeyCode::Char('q') => return Ok(ModelResponse::Exit),
+                KeyCode::Char('m') => return Ok(ModelResponse::NavToMainMenu),
+                KeyCode::Char('a') => self.toggle_auto_play(),
+                KeyCode::Char('v') => self.card_style = self.card_style.toggle(),
+                KeyCode::Char('l') if !self.round_in_progress() => return Ok(ModelResponse::NavToHandHistory),
+                KeyCode::Char('y') if self.insurance_offered() => self.decide_insurance(true),
+                KeyCode::Char('n') if self.insurance_offered() => self.decide_insurance(false),
+                KeyCode::Char('n') | KeyCode::Enter => {
+                    self.deal();
+                }
+                KeyCode::Char('h') => self.act(PlayerAction::Hit),
+                KeyCode::Char('s') => self.act(PlayerAction::Stand),
+                KeyCode::Char('d') => self.act(PlayerAction::Double),
+                KeyCode::Char('p') => self.act(PlayerAction::Split),
+                KeyCode::Char('u') => self.act(PlayerAction::Surrender),
+                KeyCode::Char('r') => self.cycle_split_limit(),
+                KeyCode::Char('c') => self.cycle_penetration(),
+                KeyCode::Char('g') => self.toggle_shuffle_procedure(),
+                KeyCode::Char('k') => self.cycle_burn_cards(),
+                KeyCode::Char('t') => self.toggle_player_cuts(),
+                KeyCode::Char('e') => self.toggle_hole_card_rule(),
+                KeyCode::Char('f') => self.toggle_variant(),
+                KeyCode::Char('+') | KeyCode::Char('=') => self.adjust_bet(1.0),
+                KeyCode::Char('-') => self.adjust_bet(-1.0),
+                KeyCode::Char('[') => self.cycle_chip(-1),
+                KeyCode::Char(']') => self.cycle_chip(1),
+                KeyCode::Tab => self.cycle_betting_spot(),
+                KeyCode::Char('b') => self.rebuy(),
+                KeyCode::Char('x') => self.toggle_practice(),
+                KeyCode::Char('o') => self.start_scenario_input(),
+                KeyCode::Char('z') => self.undo(),
+                KeyCode::Char('w') => self.redeal(),
+                KeyCode::Char('?') => self.toggle_hints(),
+                KeyCode::Char('i') => self.cycle_count_panel(),
+                KeyCode::Char('j') => self.cycle_count_system(),
+                KeyCode::Char('J') => self.cycle_bet_spread(),
+                KeyCode::Char('I') => self.cycle_bankroll_units(),
+                KeyCode::Char(digit @ '1'..='3') => self.set_spots(digit as usize - '0' as usize),
+                _ => {}
+            }
+        }
+        Ok(ModelResponse::Refresh)
+    }
+
+    fn tick(&mut self) {
+        let delay = if self.auto_play {
+            self.auto_play_interval()
+        } else if self.animation.peek() == PeekState::Peeking {
+            PEEK_DELAY
+        } else {
+            DEAL_DELAY
+        };
+        if self.last_step.elapsed() < delay {
+            return;
+        }
+        if let Some(round) = &self.round
+            && self.animation.step(round) {
+            self.last_step = Instant::now();
+            return;
+        }
+        if self.auto_play {
+            self.auto_play_hand();
+            self.last_step = Instant::now();
+        }
+    }
+
+    fn ui(&mut self, frame: &mut Frame) {
+        // Create main vertical layout
+        let main_chunks = create_common_layout(frame.area());
+        let main_area = main_chunks[1];
+
+        let horizontal_chunks = split_content_horizontally(main_area);
+
+        // Render the table information section
+        let left_section = horizontal_chunks[0];
+        render_border(frame, left_section);
+        render_centered_text(frame, left_section, " Table ");
+        self.render_table_info(frame, left_section.inner(Margin { vertical: 1, horizontal: 1 }));
+
+        // Render the playing area
+        let right_section = horizontal_chunks[1];
+        render_border(frame, right_section);
+        let title = match (&self.scenario, self.practice) {
+            (Some(scenario), _) => format!(" Blackjack - {} ", scenario),
+            (None, true) => " Blackjack - Practice ".to_string(),
+            (None, false) => " Blackjack ".to_string(),
+        };
+        render_centered_text(frame, right_section, &title);
+        self.render_table(frame, right_section);
+
+        // Render Footer
+        let footer_area = main_chunks[2];
+
+        let footer_layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Length(5),
+                Constraint::Min(20),
+            ])
+            .split(footer_area);
+        render_key_spans(frame, self.footer_spans(), footer_layout[1]);
+    }
+}
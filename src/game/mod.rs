@@ -0,0 +1 @@
+pub mod game_screen;
\ No newline at end of file
@@ -0,0 +1,343 @@
+use crate::logic::config::strategies_dir;
+use crate::logic::game::advisor::{recommend, Advice};
+use crate::logic::game::history::{HandHistory, HandRecord, ReplayPosition};
+use crate::logic::game::round::{HandOutcome, PlayerAction, PlayerHand};
+use crate::logic::strategy_calculator_logic::{find_matching_strategy, load_strategies, BlackjackStrategy};
+use crate::model::{Model, ModelResponse};
+use crate::ui::{create_common_layout, format_net, render_border, render_centered_text, render_key_spans, split_content_horizontally, ActionPalette, read_event};
+use ratatui::crossterm::event;
+use ratatui::crossterm::event::{Event, KeyCode};
+use ratatui::layout::{Alignment, Constraint, Direction, Layout, Margin, Rect};
+use ratatui::prelude::{Line, Stylize};
+use ratatui::style::Color;
+use ratatui::widgets::{Block, Paragraph};
+use ratatui::Frame;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::rc::Rc;
+
+// ---- Hand History Screen ----
+pub struct HandHistoryScreen {
+    history: Rc<RefCell<HandHistory>>,
+    /// How the replay colors strategy plays, shared with the other screens
+    palette: Rc<Cell<ActionPalette>>,
+    /// Index of the selected record, the most recent hand is selected first
+    selected: usize,
+    /// First record shown in the list, kept so the selection stays visible
+    scroll_offset: usize,
+    /// Strategy charts the replay compares decisions against
+    strategies: HashMap<String, BlackjackStrategy>,
+    /// Where the replay is, while replaying
+    replay: Option<ReplayPosition>,
+}
+
+impl HandHistoryScreen {
+    pub fn new(history: Rc<RefCell<HandHistory>>, palette: Rc<Cell<ActionPalette>>) -> Self {
+        let selected = history.borrow().len().saturating_sub(1);
+        Self {
+            history,
+            palette,
+            selected,
+            scroll_offset: 0,
+            strategies: load_strategies(&strategies_dir()),
+            replay: None,
+        }
+    }
+
+    fn toggle_replay(&mut self) {
+        self.replay = match self.replay {
+            Some(_) => None,
+            None if self.selected < self.history.borrow().len() => Some(ReplayPosition { hand: self.selected, step: 0 }),
+            None => None,
+        };
+    }
+
+    fn move_replay(&mut self, forward: bool) {
+        let Some(position) = self.replay else {
+            return;
+        };
+        let history = self.history.borrow();
+        let position = if forward { history.step_forward(position) } else { history.step_back(position) };
+        self.selected = position.hand;
+        self.replay = Some(position);
+    }
+
+    /// Jumps ahead to the next decision that went against the strategy chart.
+    fn next_mistake(&mut self) {
+        let Some(mut position) = self.replay else {
+            return;
+        };
+        let history = self.history.borrow();
+        loop {
+            let next = history.step_forward(position);
+            if next == position {
+                return;
+            }
+            position = next;
+            let record = &history.records()[position.hand];
+            if let Some((decision, Some(advice))) = self.advice_at(record, position.step)
+                && decision != advice.action {
+                break;
+            }
+        }
+        self.selected = position.hand;
+        self.replay = Some(position);
+    }
+
+    /// The decision made at a replay step and what the chart for the hand's
+    /// rules recommended, if the step is a decision.
+    fn advice_at(&self, record: &HandRecord, step: usize) -> Option<(PlayerAction, Option<Advice>)> {
+        let decision = record.decisions.get(step)?;
+        let advice = find_matching_strategy(&self.strategies, &record.rules)
+            .and_then(|(_, strategy)| recommend(strategy, &decision.hand, decision.upcard, &decision.available));
+        Some((decision.action, advice))
+    }
+
+    fn move_selection(&mut self, increment: isize) {
+        let len = self.history.borrow().len();
+        if len == 0 {
+            return;
+        }
+        self.selected = self.selected.saturating_add_signed(increment).min(len - 1);
+    }
+
+    fn outcome_color(net: f64) -> Color {
+        if net > 0.0 {
+            Color::Green
+        } else if net < 0.0 {
+            Color::Red
+        } else {
+            Color::Yellow
+        }
+    }
+
+    fn render_hand_list(&mut self, frame: &mut Frame, rect: Rect) {
+        let history = self.history.borrow();
+        let visible = rect.height as usize;
+        if visible == 0 {
+            return;
+        }
+
+        // Scroll just far enough to keep the selected hand on screen
+        if self.selected < self.scroll_offset {
+            self.scroll_offset = self.selected;
+        } else if self.selected >= self.scroll_offset + visible {
+            self.scroll_offset = self.selected + 1 - visible;
+        }
+
+        let lines: Vec<Line> = history.records()
+            .iter()
+            .enumerate()
+            .skip(self.scroll_offset)
+            .take(visible)
+            .map(|(index, record)| {
+                let text = format!("Hand {:>3}  {:>10}", record.number, format_net(record.net));
+                if index == self.selected {
+                    Line::from(format!("> {}", text)).fg(Color::Green)
+                } else {
+                    Line::from(text).fg(Self::outcome_color(record.net))
+                }
+            })
+            .collect();
+
+        let list = Paragraph::new(lines)
+            .alignment(Alignment::Center)
+            .block(Block::default());
+        frame.render_widget(list, rect);
+    }
+
+    fn describe_player_hand(player: &PlayerHand) -> Line<'static> {
+        let result = match player.outcome {
+            Some(outcome) => format!("{} {}", outcome, format_net(player.net_result())),
+            None => String::new(),
+        };
+        let color = match player.outcome {
+            Some(HandOutcome::Blackjack) | Some(HandOutcome::Win) => Color::Green,
+            Some(HandOutcome::Lose) => Color::Red,
+            _ => Color::Yellow,
+        };
+        Line::from(vec![
+            format!("Spot {}: {} ({})  Bet ${:.2}  ", player.spot + 1, player.hand, player.hand.total(), player.bet).into(),
+            result.fg(color),
+        ])
+    }
+
+    fn render_record(&self, frame: &mut Frame, rect: Rect, record: &HandRecord) {
+        let mut lines = vec![
+            Line::from(format!("Hand {}", record.number)).bold(),
+            Line::from(format!("Total Bet: ${:.2}", record.total_bet())),
+            Line::from(format!("Net: {}", format_net(record.net))).fg(Self::outcome_color(record.net)),
+            Line::from(format!("Count at Bet: RC {:+}, TC {:+.1}", record.running_count, record.true_count)),
+            Line::from(""),
+            Line::from("Dealer").bold(),
+            Line::from(format!("{} ({})", record.dealer, record.dealer.total())),
+            Line::from(""),
+            Line::from("Player").bold(),
+        ];
+        lines.extend(record.hands.iter().map(Self::describe_player_hand));
+
+        if let Some(insurance) = record.insurance {
+            lines.push(Line::from(""));
+            lines.push(Line::from(format!(
+                "Insurance {} ({})",
+                if insurance.taken { "taken" } else { "declined" },
+                format_net(record.insurance_net),
+            )));
+        }
+
+        lines.push(Line::from(""));
+        lines.push(Line::from("Decisions").bold());
+        if record.decisions.is_empty() {
+            lines.push(Line::from("None"));
+        }
+        for decision in &record.decisions {
+            lines.push(Line::from(format!("You {}", decision)));
+        }
+
+        let details = Paragraph::new(lines)
+            .alignment(Alignment::Center)
+            .block(Block::default());
+        frame.render_widget(details, rect);
+    }
+
+    /// Shows one decision of the replay next to the strategy recommendation.
+    fn render_replay_step(&self, frame: &mut Frame, rect: Rect, record: &HandRecord, step: usize) {
+        let decision = &record.decisions[step];
+        let mut lines = vec![
+            Line::from(format!("Replay - Hand {}, Decision {} of {}", record.number, step + 1, record.decisions.len())).bold(),
+            Line::from(""),
+            Line::from(format!("Dealer Shows: {}", decision.upcard)),
+            Line::from(format!("Your Hand: {} ({})", decision.hand, decision.hand.chart_label())),
+            Line::from(""),
+            Line::from(format!("You: {}", decision.action)),
+        ];
+
+        match self.advice_at(record, step) {
+            Some((action, Some(advice))) => {
+                let no_legend = HashMap::new();
+                let legend = find_matching_strategy(&self.strategies, &record.rules)
+                    .map_or(&no_legend, |(_, strategy)| &strategy.action_legend);
+                lines.push(Line::from(vec![
+                    "Strategy: ".into(),
+                    format!(" {} ", advice.action).bold().fg(Color::Black).bg(self.palette.get().color(legend, &advice.code)),
+                ]));
+                lines.push(if action == advice.action {
+                    Line::from("Matches strategy").fg(Color::Green)
+                } else {
+                    Line::from("Deviation").fg(Color::Red)
+                });
+            }
+            _ => lines.push(Line::from("Strategy: no chart for these rules")),
+        }
+
+        // Every decision in the hand, the current one marked
+        lines.extend([Line::from(""), Line::from("Decisions").bold()]);
+        for (index, decision) in record.decisions.iter().enumerate() {
+            let text = format!("You {}", decision);
+            let line = match self.advice_at(record, index) {
+                Some((action, Some(advice))) if action != advice.action => Line::from(format!("{} ✗", text)).fg(Color::Red),
+                _ => Line::from(format!("{} ✓", text)),
+            };
+            lines.push(if index == step { line.bold().underlined() } else { line });
+        }
+
+        let details = Paragraph::new(lines)
+            .alignment(Alignment::Center)
+            .block(Block::default());
+        frame.render_widget(details, rect);
+    }
+
+    fn footer_spans(&self) -> Vec<String> {
+        let mut spans = vec![
+            " Q ".to_string(), " Quit ".to_string(),
+            " M ".to_string(), " Menu ".to_string(),
+        ];
+        if self.replay.is_some() {
+            spans.extend([" R/Esc ".to_string(), " Stop Replay ".to_string()]);
+            spans.extend([" L/→ ".to_string(), " Step Forward ".to_string()]);
+            spans.extend([" H/← ".to_string(), " Step Back ".to_string()]);
+            spans.extend([" X ".to_string(), " Next Mistake ".to_string()]);
+            return spans;
+        }
+        spans.extend([
+            " Esc ".to_string(), " Back to Game ".to_string(),
+            " J/↓ ".to_string(), " Next ".to_string(),
+            " K/↑ ".to_string(), " Previous ".to_string(),
+            " PgDn/PgUp ".to_string(), " Page ".to_string(),
+            " R ".to_string(), " Replay ".to_string(),
+        ]);
+        spans
+    }
+}
+
+// ---- TRAIT IMPLEMENTATIONS ----
+impl Model for HandHistoryScreen {
+    fn update(&mut self) -> std::io::Result<ModelResponse> {
+        if let Event::Key(key) = read_event(false)? {
+            if key.kind == event::KeyEventKind::Release {
+                return Ok(ModelResponse::Refresh);
+            }
+            if self.replay.is_some() {
+                match key.code {
+                    KeyCode::Char('q') => return Ok(ModelResponse::Exit),
+                    KeyCode::Char('m') => return Ok(ModelResponse::NavToMainMenu),
+                    KeyCode::Char('r') | KeyCode::Esc => self.toggle_replay(),
+                    KeyCode::Char('l') | KeyCode::Right => self.move_replay(true),
+                    KeyCode::Char('h') | KeyCode::Left => self.move_replay(false),
+                    KeyCode::Char('x') => self.next_mistake(),
+                    _ => {}
+                }
+                return Ok(ModelResponse::Refresh);
+            }
+            match key.code {
+                KeyCode::Char('q') => return Ok(ModelResponse::Exit),
+                KeyCode::Char('r') => self.toggle_replay(),
+                KeyCode::Char('m') => return Ok(ModelResponse::NavToMainMenu),
+                KeyCode::Esc | KeyCode::Char('l') | KeyCode::Char('b') => return Ok(ModelResponse::NavToGame),
+                KeyCode::Char('j') | KeyCode::Down => self.move_selection(1),
+                KeyCode::Char('k') | KeyCode::Up => self.move_selection(-1),
+                KeyCode::PageDown => self.move_selection(10),
+                KeyCode::PageUp => self.move_selection(-10),
+                KeyCode::Home => self.selected = 0,
+                KeyCode::End => self.move_selection(isize::MAX),
+                _ => {}
+            }
+        }
+        Ok(ModelResponse::Refresh)
+    }
+
+    fn ui(&mut self, frame: &mut Frame) {
+        let main_chunks = create_common_layout(frame.area());
+        let horizontal_chunks = split_content_horizontally(main_chunks[1]);
+
+        // Render the list of hands
+        let left_section = horizontal_chunks[0];
+        render_border(frame, left_section);
+        render_centered_text(frame, left_section, " Hands ");
+        self.render_hand_list(frame, left_section.inner(Margin { vertical: 2, horizontal: 1 }));
+
+        // Render the selected hand
+        let right_section = horizontal_chunks[1];
+        render_border(frame, right_section);
+        render_centered_text(frame, right_section, " Hand History ");
+        let details_area = right_section.inner(Margin { vertical: 2, horizontal: 2 });
+        let history = self.history.borrow();
+        match (history.records().get(self.selected), self.replay) {
+            (Some(record), Some(position)) if position.step < record.decisions.len() => {
+                self.render_replay_step(frame, details_area, record, position.step)
+            }
+            (Some(record), _) => self.render_record(frame, details_area, record),
+            (None, _) => render_centered_text(frame, details_area, "No hands played yet this session"),
+        }
+
+        // Render Footer
+        let footer_layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Length(5),
+                Constraint::Min(20),
+            ])
+            .split(main_chunks[2]);
+        render_key_spans(frame, self.footer_spans(), footer_layout[1]);
+    }
+}
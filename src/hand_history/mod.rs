@@ -0,0 +1 @@
+pub mod hand_history_screen;
\ No newline at end of file
@@ -0,0 +1,608 @@
+use crate::logic::game::animation::{DealAnimation, PeekState};
+use crate::logic::game::round::PlayerAction;
+use crate::logic::game::shared_table::{SharedTable, MIN_PLAYERS};
+use crate::logic::game::shoe::Shoe;
+use crate::logic::config;
+use crate::logic::lan::{ClientMessage, Connection, ServerMessage, TableServer};
+use crate::logic::strategy_calculator_logic::{GameVariant, StrategyVariables, SurrenderRule};
+use crate::model::{Model, ModelResponse};
+use crate::ui::{create_common_layout, format_net, render_border, render_centered_text, render_key_spans, split_content_horizontally, read_event};
+use crate::widgets::card_widget::{CardStyle, CARD_HEIGHT};
+use crate::widgets::table_view::TableView;
+use ratatui::crossterm::event;
+use ratatui::crossterm::event::{Event, KeyCode};
+use ratatui::layout::{Alignment, Constraint, Direction, Layout, Margin, Rect};
+use ratatui::prelude::{Line, Stylize};
+use ratatui::style::Color;
+use ratatui::widgets::{Block, Paragraph};
+use ratatui::Frame;
+use std::time::{Duration, Instant};
+
+/// Delay between cards being revealed
+const DEAL_DELAY: Duration = Duration::from_millis(300);
+/// How long the dealer is shown checking the hole card
+const PEEK_DELAY: Duration = Duration::from_millis(900);
+/// Longest name a player can be given
+const MAX_NAME_LENGTH: usize = 16;
+
+/// How the table is shared between players.
+enum Link {
+    /// Every seat is played from this terminal
+    Local,
+    /// This terminal runs the table and plays the first seat, the other
+    /// players connect over TCP
+    Host(TableServer),
+    /// The table runs on another machine, this terminal plays one seat
+    Client {
+        connection: Connection,
+        seat: Option<usize>,
+        connected: bool,
+    },
+}
+
+// ---- Hotseat Screen ----
+/// Several players taking turns at one table, each with their own bankroll,
+/// bet and results. Players share this terminal, or join over the network.
+pub struct HotseatScreen {
+    table: SharedTable,
+
+    /// Running a home game: the terminal is only the dealer, calling out
+    /// payouts and keeping the house's books
+    dealer_mode: bool,
+
+    /// Only dealt from when this terminal runs the table
+    shoe: Shoe,
+    link: Link,
+
+    /// Seat whose bet is being adjusted between rounds when playing locally
+    selected_seat: usize,
+    selected_chip: usize,
+
+    /// New name being typed for the selected seat
+    renaming: Option<String>,
+
+    /// Round number, hand count and active hand last shown, to follow deals
+    /// and splits made by other players
+    shown: (u32, usize, Option<usize>),
+    card_style: CardStyle,
+    animation: DealAnimation,
+    last_step: Instant,
+    message: String,
+}
+
+impl HotseatScreen {
+    /// A table for players sharing this terminal.
+    pub fn new() -> Self {
+        let mut screen = Self::with_link(Link::Local);
+        let _ = screen.table.set_players(MIN_PLAYERS);
+        screen.message = "Press N to deal".to_string();
+        screen
+    }
+
+    /// Deals for a home game, where players give their decisions to whoever
+    /// is at the keyboard.
+    pub fn dealer() -> Self {
+        let mut screen = Self::new();
+        screen.dealer_mode = true;
+        screen.message = "Press R to name the players, N to deal".to_string();
+        screen
+    }
+
+    /// Runs a table other players can join, with the host in the first seat.
+    pub fn host(server: TableServer, name: &str) -> Result<Self, String> {
+        let port = server.port();
+        let mut screen = Self::with_link(Link::Host(server));
+        screen.table.join(name)?;
+        screen.message = format!("Waiting for players on port {}, press N to deal", port);
+        Ok(screen)
+    }
+
+    /// Plays at a table hosted elsewhere, the connection must already have asked to join.
+    pub fn join(connection: Connection) -> Self {
+        let mut screen = Self::with_link(Link::Client { connection, seat: None, connected: true });
+        screen.message = "Joining the table...".to_string();
+        screen
+    }
+
+    fn with_link(link: Link) -> Self {
+        let rules = StrategyVariables {
+            decks: 6,
+            dealer_stands_on_soft_17: true,
+            double_after_split: true,
+            dealer_peak: true,
+            surrender_allowed: SurrenderRule::AnyUpcard,
+            variant: GameVariant::Classic,
+        };
+        let table = config::current().table;
+        let shoe = Shoe::from_rules(&rules, &table);
+
+        Self {
+            table: SharedTable::new(&rules, &table),
+            dealer_mode: false,
+            shoe,
+            link,
+            selected_seat: 0,
+            selected_chip: 0,
+            renaming: None,
+            shown: (0, 0, None),
+            card_style: CardStyle::default(),
+            animation: DealAnimation::new(),
+            last_step: Instant::now(),
+            message: String::new(),
+        }
+    }
+
+    /// The seat this terminal plays: the acting seat when everyone is
+    /// local, otherwise the player's own seat.
+    fn own_seat(&self) -> Option<usize> {
+        match &self.link {
+            Link::Local => self.table.acting_seat(),
+            Link::Host(_) => Some(0),
+            Link::Client { seat, .. } => *seat,
+        }
+    }
+
+    /// Seat whose bet and bankroll the betting keys change.
+    fn betting_seat(&self) -> Option<usize> {
+        match &self.link {
+            Link::Local => Some(self.selected_seat),
+            Link::Host(_) | Link::Client { .. } => self.own_seat(),
+        }
+    }
+
+    /// Passes a request to the table: applied here when this terminal runs
+    /// the table, sent to the host otherwise.
+    fn request(&mut self, message: ClientMessage) {
+        if let Link::Client { connection, connected, .. } = &mut self.link {
+            if !*connected {
+                self.message = "Disconnected from the host".to_string();
+            } else if let Err(err) = connection.send(&message) {
+                self.message = format!("Lost the connection to the host: {}", err);
+                *connected = false;
+            }
+            return;
+        }
+
+        let Some(seat) = self.own_seat() else {
+            return;
+        };
+        let result = match message {
+            ClientMessage::Join { .. } => Ok(()),
+            ClientMessage::Bet(bet) => self.table.set_bet(seat, bet),
+            ClientMessage::Rebuy => self.table.rebuy(self.betting_seat().unwrap_or(seat)),
+            ClientMessage::Insurance(take) => self.table.decide_insurance(seat, take, &mut self.shoe),
+            ClientMessage::Action(action) => self.table.act(seat, action, &mut self.shoe),
+        };
+        match result {
+            Ok(()) => {
+                self.message.clear();
+                self.table_changed();
+            }
+            Err(err) => self.message = err,
+        }
+    }
+
+    /// Keeps the animation in step with the table and lets remote players know.
+    fn table_changed(&mut self) {
+        if let Some(round) = self.table.round() {
+            let (round_number, hands, active) = self.shown;
+            if self.table.round_number() != round_number {
+                self.animation.start(round);
+                self.last_step = Instant::now();
+            } else if round.hands().len() > hands
+                && let Some(index) = active {
+                self.animation.split(index);
+            }
+            self.shown = (self.table.round_number(), round.hands().len(), round.active_hand_index());
+        }
+
+        if let Link::Host(server) = &mut self.link {
+            server.broadcast(&self.table);
+        }
+    }
+
+    fn deal(&mut self) {
+        if matches!(self.link, Link::Client { .. }) {
+            self.message = "The host deals the cards".to_string();
+            return;
+        }
+        match self.table.deal(&mut self.shoe) {
+            Ok(()) => {
+                self.message.clear();
+                self.table_changed();
+            }
+            Err(err) => self.message = err,
+        }
+    }
+
+    fn set_players(&mut self, players: usize) {
+        if !matches!(self.link, Link::Local) {
+            self.message = "Players join over the network".to_string();
+            return;
+        }
+        match self.table.set_players(players) {
+            Ok(()) => self.selected_seat = self.selected_seat.min(self.table.seats().len() - 1),
+            Err(err) => self.message = err,
+        }
+    }
+
+    fn adjust_bet(&mut self, direction: f64) {
+        let Some(seat) = self.betting_seat().and_then(|seat| self.table.seats().get(seat)) else {
+            return;
+        };
+        let chip = self.table.table.chip_denominations[self.selected_chip];
+        let bet = self.table.table.adjust_bet(seat.bet, chip * direction);
+        if let Link::Local = self.link {
+            // Locally the selected seat bets, not whoever is acting
+            match self.table.set_bet(self.selected_seat, bet) {
+                Ok(()) => self.table_changed(),
+                Err(err) => self.message = err,
+            }
+        } else {
+            self.request(ClientMessage::Bet(bet));
+        }
+    }
+
+    fn cycle_chip(&mut self, increment: isize) {
+        let chips = self.table.table.chip_denominations.len() as isize;
+        self.selected_chip = (self.selected_chip as isize + increment).rem_euclid(chips) as usize;
+    }
+
+    fn start_renaming(&mut self) {
+        if !matches!(self.link, Link::Local) {
+            self.message = "Players choose their names when they join".to_string();
+            return;
+        }
+        let name = self.table.seats().get(self.selected_seat).map(|seat| seat.name.clone());
+        self.renaming = name;
+    }
+
+    /// Typing a player's name takes every key until it's saved or cancelled.
+    fn update_renaming(&mut self, key: KeyCode) {
+        let Some(name) = self.renaming.as_mut() else {
+            return;
+        };
+        match key {
+            KeyCode::Enter => match self.table.rename(self.selected_seat, name) {
+                Ok(()) => {
+                    self.renaming = None;
+                    self.message.clear();
+                }
+                Err(err) => self.message = err,
+            },
+            KeyCode::Esc => {
+                self.renaming = None;
+                self.message.clear();
+            }
+            KeyCode::Backspace => {
+                name.pop();
+            }
+            KeyCode::Char(c) if name.chars().count() < MAX_NAME_LENGTH => name.push(c),
+            _ => {}
+        }
+    }
+
+    fn cycle_selected_seat(&mut self) {
+        self.selected_seat = (self.selected_seat + 1) % self.table.seats().len().max(1);
+    }
+
+    /// Picks up players joining and their decisions, or the host's latest table.
+    fn poll_network(&mut self) {
+        let mut changed = false;
+        match &mut self.link {
+            Link::Local => {}
+            Link::Host(server) => changed = server.poll(&mut self.table, &mut self.shoe),
+            Link::Client { connection, seat, connected } => {
+                if !*connected {
+                    return;
+                }
+                match connection.receive::<ServerMessage>() {
+                    Ok(messages) => {
+                        for message in messages {
+                            match message {
+                                ServerMessage::Welcome { seat: given } => *seat = Some(given),
+                                ServerMessage::Table(table) => {
+                                    self.table = *table;
+                                    changed = true;
+                                }
+                                ServerMessage::Rejected(err) => self.message = err,
+                            }
+                        }
+                    }
+                    Err(_) => {
+                        *connected = false;
+                        self.message = "The host closed the table".to_string();
+                    }
+                }
+                if changed {
+                    self.message.clear();
+                }
+            }
+        }
+        if changed {
+            self.table_changed();
+        }
+    }
+
+    fn animating(&self) -> bool {
+        self.table.round().is_some_and(|round| !self.animation.is_done(round))
+    }
+
+    fn finish_animation(&mut self) {
+        if let Some(round) = self.table.round() {
+            self.animation.finish(round);
+        }
+    }
+
+    fn render_players(&self, frame: &mut Frame, rect: Rect) {
+        let acting = self.table.acting_seat().filter(|_| !self.animating());
+        let table = &self.table.table;
+        let mut lines = vec![
+            Line::from(""),
+            Line::from(format!("Table Limits: ${:.0} - ${:.0}", table.min_bet, table.max_bet)),
+            Line::from(format!("Chip: < ${:.0} >", table.chip_denominations[self.selected_chip])),
+        ];
+
+        let betting = self.betting_seat().filter(|_| !self.table.round_in_progress());
+        let settlements = self.table.settlements();
+        for (index, seat) in self.table.seats().iter().enumerate() {
+            let stats = &seat.stats;
+            let name = if let Some(name) = self.renaming.as_ref().filter(|_| index == self.selected_seat) {
+                Line::from(format!("> {}_ <", name)).bold().fg(Color::Yellow)
+            } else if Some(index) == acting {
+                Line::from(format!("> {} <", seat.name)).bold().fg(Color::Green)
+            } else if Some(index) == betting {
+                Line::from(format!("> {}", seat.name)).bold().fg(Color::Green)
+            } else {
+                Line::from(seat.name.clone()).bold()
+            };
+            let win_rate = stats.win_rate().map_or("-".to_string(), |rate| format!("{:.0}%", rate * 100.0));
+            let status = if seat.away {
+                "Away"
+            } else if !seat.can_play() {
+                "Can't cover the bet"
+            } else {
+                ""
+            };
+
+            // The dealer calls out what to pay or take from each player
+            let last_line = if self.dealer_mode {
+                match settlements.iter().find(|(seat, _)| *seat == index).map(|(_, net)| *net) {
+                    Some(net) if net > 0.0 => Line::from(format!("Pay ${:.2}", net)).bold().fg(Color::Green),
+                    Some(net) if net < 0.0 => Line::from(format!("Collect ${:.2}", -net)).bold().fg(Color::Red),
+                    Some(_) => Line::from("Push").bold(),
+                    None => Line::from(""),
+                }
+            } else {
+                Line::from(format!("Blackjacks: {}  Hands: {}", stats.blackjacks, stats.hands))
+            };
+            let record = if self.dealer_mode {
+                format!("Net: {}  Action: ${:.0}", format_net(stats.net), stats.wagered)
+            } else {
+                format!("Net: {}  Best: {}", format_net(stats.net), format_net(stats.best_round))
+            };
+
+            lines.extend([
+                Line::from(""),
+                name,
+                Line::from(format!("Balance: ${:.2}", seat.bankroll.balance)),
+                Line::from(format!("Bet: ${:.2}", seat.bet)),
+                Line::from(record),
+                Line::from(format!("W/L/P: {}/{}/{} ({})", stats.wins, stats.losses, stats.pushes, win_rate)),
+                last_line,
+                Line::from(status).fg(Color::Red),
+            ]);
+        }
+
+        let info = Paragraph::new(lines)
+            .alignment(Alignment::Center)
+            .block(Block::default());
+        frame.render_widget(info, rect);
+    }
+
+    /// The house rules being dealt to and how the house stands, for dealer mode.
+    fn house_lines(&self) -> Vec<Line<'static>> {
+        let rules = &self.table.rules;
+        let action: f64 = self.table.seats().iter().map(|seat| seat.stats.wagered).sum();
+        vec![
+            Line::from(format!(
+                "{}, {} Decks, {}, {}, Surrender: {}",
+                rules.variant,
+                rules.decks,
+                if rules.dealer_stands_on_soft_17 { "S17" } else { "H17" },
+                if rules.double_after_split { "DAS" } else { "No DAS" },
+                rules.surrender_allowed.to_string(),
+            )),
+            Line::from(format!("House: {} on ${:.2} of action", format_net(self.table.house_net()), action)).bold(),
+        ]
+    }
+
+    /// Whose turn it is, above the hands.
+    fn turn_line(&self) -> Line<'static> {
+        let Some(seat) = self.table.acting_seat() else {
+            return Line::from("");
+        };
+        let name = &self.table.seats()[seat].name;
+        let yours = !matches!(self.link, Link::Local) && self.own_seat() == Some(seat);
+        match (self.table.insurance_offered(), yours) {
+            (true, true) => Line::from("Dealer shows an Ace. Insurance?").bold().fg(Color::Yellow),
+            (true, false) => Line::from(format!("Dealer shows an Ace. {}, insurance?", name)).bold().fg(Color::Yellow),
+            (false, true) => Line::from("Your turn").bold().fg(Color::Green),
+            (false, false) => Line::from(format!("{} to act", name)).bold().fg(Color::Green),
+        }
+    }
+
+    fn render_table(&self, frame: &mut Frame, rect: Rect) {
+        let inner = rect.inner(Margin { vertical: 2, horizontal: 2 });
+        let table_layout = Layout::vertical([
+            Constraint::Length(CARD_HEIGHT + 2),
+            Constraint::Length(2),
+            Constraint::Min(8),
+            Constraint::Length(2),
+            Constraint::Length(if self.dealer_mode { 2 } else { 0 }),
+        ]).split(inner);
+        if self.dealer_mode {
+            frame.render_widget(Paragraph::new(self.house_lines()).alignment(Alignment::Center), table_layout[4]);
+        }
+
+        if let Some(round) = self.table.round() {
+            let view = TableView::new(round, &self.animation, self.card_style);
+            view.render_dealer(frame, table_layout[0]);
+            view.render_spots(frame, table_layout[2], "Players", |spot| {
+                self.table.seat_at(spot).map_or(String::new(), |seat| self.table.seats()[seat].name.clone())
+            });
+        }
+
+        // Results would give away the cards still to come
+        if self.animating() {
+            return;
+        }
+        frame.render_widget(Paragraph::new(self.turn_line()).alignment(Alignment::Center), table_layout[1]);
+
+        let text = if self.message.is_empty() { &self.table.announcement } else { &self.message };
+        let message = Paragraph::new(text.as_str())
+            .bold()
+            .alignment(Alignment::Center);
+        frame.render_widget(message, table_layout[3]);
+    }
+
+    fn footer_spans(&self) -> Vec<String> {
+        if self.renaming.is_some() {
+            return vec![
+                " Enter ".to_string(), " Save Name ".to_string(),
+                " Esc ".to_string(), " Cancel ".to_string(),
+            ];
+        }
+        let mut spans = vec![
+            " Q ".to_string(), " Quit ".to_string(),
+            " M ".to_string(), " Menu ".to_string(),
+        ];
+
+        let our_turn = self.table.acting_seat().is_some() && self.table.acting_seat() == self.own_seat();
+        if self.table.insurance_offered() && our_turn {
+            spans.extend([" Y ".to_string(), " Take Insurance ".to_string()]);
+            spans.extend([" N ".to_string(), " No Insurance ".to_string()]);
+            return spans;
+        }
+
+        if !self.table.round_in_progress() {
+            if !matches!(self.link, Link::Client { .. }) {
+                spans.extend([" N ".to_string(), " Deal ".to_string()]);
+            }
+            if let Link::Local = self.link {
+                spans.extend([" 2-4 ".to_string(), " Players ".to_string()]);
+                spans.extend([" Tab ".to_string(), " Player ".to_string()]);
+                spans.extend([" R ".to_string(), " Rename ".to_string()]);
+            }
+            spans.extend([" +/- ".to_string(), " Bet ".to_string()]);
+            spans.extend([" [/] ".to_string(), " Chip ".to_string()]);
+            spans.extend([" B ".to_string(), " Buy In ".to_string()]);
+            spans.extend([" V ".to_string(), format!(" {} Cards ", self.card_style.toggle())]);
+        }
+        let actions = match self.table.round() {
+            Some(round) if our_turn => round.available_actions(),
+            _ => Vec::new(),
+        };
+        for action in actions {
+            let key = match action {
+                PlayerAction::Hit => " H ",
+                PlayerAction::Stand => " S ",
+                PlayerAction::Double => " D ",
+                PlayerAction::Split => " P ",
+                PlayerAction::Surrender => " U ",
+            };
+            spans.extend([key.to_string(), format!(" {} ", action)]);
+        }
+        spans
+    }
+}
+
+// ---- TRAIT IMPLEMENTATIONS ----
+impl Model for HotseatScreen {
+    fn update(&mut self) -> std::io::Result<ModelResponse> {
+        if let Event::Key(key) = read_event(self.renaming.is_some())? {
+            if key.kind == event::KeyEventKind::Release {
+                return Ok(ModelResponse::Refresh);
+            }
+            if self.renaming.is_some() {
+                self.update_renaming(key.code);
+                return Ok(ModelResponse::Refresh);
+            }
+            // Any key other than quit skips the rest of the dealing
+            if self.animating() && key.code != KeyCode::Char('q') {
+                self.finish_animation();
+                return Ok(ModelResponse::Refresh);
+            }
+            let insurance = self.table.insurance_offered();
+            match key.code {
+                KeyCode::Char('q') => return Ok(ModelResponse::Exit),
+                KeyCode::Char('m') => return Ok(ModelResponse::NavToMainMenu),
+                KeyCode::Char('v') => self.card_style = self.card_style.toggle(),
+                KeyCode::Char('y') if insurance => self.request(ClientMessage::Insurance(true)),
+                KeyCode::Char('n') if insurance => self.request(ClientMessage::Insurance(false)),
+                KeyCode::Char('n') | KeyCode::Enter => self.deal(),
+                KeyCode::Char('h') => self.request(ClientMessage::Action(PlayerAction::Hit)),
+                KeyCode::Char('s') => self.request(ClientMessage::Action(PlayerAction::Stand)),
+                KeyCode::Char('d') => self.request(ClientMessage::Action(PlayerAction::Double)),
+                KeyCode::Char('p') => self.request(ClientMessage::Action(PlayerAction::Split)),
+                KeyCode::Char('u') => self.request(ClientMessage::Action(PlayerAction::Surrender)),
+                KeyCode::Char('+') | KeyCode::Char('=') => self.adjust_bet(1.0),
+                KeyCode::Char('-') => self.adjust_bet(-1.0),
+                KeyCode::Char('[') => self.cycle_chip(-1),
+                KeyCode::Char(']') => self.cycle_chip(1),
+                KeyCode::Tab => self.cycle_selected_seat(),
+                KeyCode::Char('r') if !self.table.round_in_progress() => self.start_renaming(),
+                KeyCode::Char('b') => self.request(ClientMessage::Rebuy),
+                KeyCode::Char(digit @ '2'..='4') => self.set_players(digit as usize - '0' as usize),
+                _ => {}
+            }
+        }
+        Ok(ModelResponse::Refresh)
+    }
+
+    fn tick(&mut self) {
+        self.poll_network();
+
+        let delay = if self.animation.peek() == PeekState::Peeking { PEEK_DELAY } else { DEAL_DELAY };
+        if self.last_step.elapsed() < delay {
+            return;
+        }
+        if let Some(round) = self.table.round()
+            && self.animation.step(round) {
+            self.last_step = Instant::now();
+        }
+    }
+
+    fn ui(&mut self, frame: &mut Frame) {
+        let main_chunks = create_common_layout(frame.area());
+        let horizontal_chunks = split_content_horizontally(main_chunks[1]);
+
+        // Render the players and their results
+        let left_section = horizontal_chunks[0];
+        render_border(frame, left_section);
+        render_centered_text(frame, left_section, " Players ");
+        self.render_players(frame, left_section.inner(Margin { vertical: 1, horizontal: 1 }));
+
+        // Render the playing area
+        let right_section = horizontal_chunks[1];
+        render_border(frame, right_section);
+        let title = match &self.link {
+            Link::Local if self.dealer_mode => " Dealer Mode ",
+            Link::Local => " Hotseat Blackjack ",
+            Link::Host(_) => " LAN Blackjack - Host ",
+            Link::Client { .. } => " LAN Blackjack ",
+        };
+        render_centered_text(frame, right_section, title);
+        self.render_table(frame, right_section);
+
+        // Render Footer
+        let footer_layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Length(5),
+                Constraint::Min(20),
+            ])
+            .split(main_chunks[2]);
+        render_key_spans(frame, self.footer_spans(), footer_layout[1]);
+    }
+}
@@ -0,0 +1 @@
+pub mod hotseat_screen;
\ No newline at end of file
@@ -0,0 +1,229 @@
+use crate::hotseat::hotseat_screen::HotseatScreen;
+use crate::logic::lan::{local_ip, ClientMessage, Connection, TableServer, DEFAULT_PORT};
+use crate::model::{Model, ModelResponse};
+use crate::ui::{create_common_layout, render_border, render_centered_text, render_key_spans};
+use ratatui::crossterm::event;
+use ratatui::crossterm::event::{Event, KeyCode};
+use ratatui::layout::{Alignment, Constraint, Direction, Layout, Margin};
+use ratatui::prelude::{Color, Line, Stylize};
+use ratatui::widgets::{Block, Paragraph};
+use ratatui::Frame;
+use std::fmt;
+use std::net::IpAddr;
+
+/// Longest name or address that can be typed in
+const MAX_INPUT_LENGTH: usize = 32;
+
+// ---- LAN Lobby Screen ----
+/// Hosts a table for other players on the network or joins one, then hands
+/// over to the table itself.
+pub struct LanLobbyScreen {
+    stage: Stage,
+}
+
+enum Stage {
+    Lobby(Lobby),
+    Playing(Box<HotseatScreen>),
+}
+
+struct Lobby {
+    role: Role,
+    name: String,
+    address: String,
+    field: Field,
+    error: String,
+
+    /// Where other players should connect when hosting
+    local_ip: Option<IpAddr>,
+}
+
+impl LanLobbyScreen {
+    pub fn new() -> Self {
+        Self {
+            stage: Stage::Lobby(Lobby {
+                role: Role::Host,
+                name: String::new(),
+                address: format!("127.0.0.1:{}", DEFAULT_PORT),
+                field: Field::Name,
+                error: String::new(),
+                local_ip: local_ip(),
+            }),
+        }
+    }
+}
+
+impl Lobby {
+    /// Starts or joins the table, leaving the error to show if that fails.
+    fn connect(&mut self) -> Option<HotseatScreen> {
+        let result = match self.role {
+            Role::Host => TableServer::host(DEFAULT_PORT)
+                .map_err(|err| format!("Couldn't host on port {}: {}", DEFAULT_PORT, err))
+                .and_then(|server| HotseatScreen::host(server, &self.name)),
+            Role::Join => Connection::connect(self.address.trim())
+                .and_then(|mut connection| {
+                    connection.send(&ClientMessage::Join { name: self.name.clone() })?;
+                    Ok(connection)
+                })
+                .map(HotseatScreen::join)
+                .map_err(|err| format!("Couldn't join {}: {}", self.address.trim(), err)),
+        };
+        result.map_err(|err| self.error = err).ok()
+    }
+
+    fn focused_input(&mut self) -> &mut String {
+        match self.field {
+            Field::Name => &mut self.name,
+            Field::Address => &mut self.address,
+        }
+    }
+
+    /// Only joining needs an address.
+    fn cycle_field(&mut self) {
+        self.field = match (self.field, self.role) {
+            (Field::Name, Role::Join) => Field::Address,
+            _ => Field::Name,
+        };
+    }
+
+    fn render(&self, frame: &mut Frame) {
+        let main_chunks = create_common_layout(frame.area());
+        let body = main_chunks[1];
+        render_border(frame, body);
+        render_centered_text(frame, body, " LAN Multiplayer ");
+
+        let field_line = |label: &str, value: &str, field: Field| {
+            let focused = self.field == field;
+            let text = format!("{}: {}{}", label, value, if focused { "_" } else { "" });
+            if focused {
+                Line::from(format!("> {} <", text)).bold().fg(Color::Green)
+            } else {
+                Line::from(text)
+            }
+        };
+
+        let mut lines = vec![
+            Line::from(""),
+            Line::from(format!("< {} >", self.role)).bold(),
+            Line::from(""),
+            field_line("Name", &self.name, Field::Name),
+        ];
+        match self.role {
+            Role::Host => {
+                let address = self.local_ip.map_or("this machine's address".to_string(), |ip| ip.to_string());
+                lines.extend([
+                    Line::from(""),
+                    Line::from(format!("Other players join {}:{}", address, DEFAULT_PORT)),
+                ]);
+            }
+            Role::Join => {
+                lines.extend([
+                    Line::from(""),
+                    field_line("Host Address", &self.address, Field::Address),
+                ]);
+            }
+        }
+        lines.extend([
+            Line::from(""),
+            Line::from(self.error.clone()).fg(Color::Red),
+        ]);
+
+        let form = Paragraph::new(lines)
+            .alignment(Alignment::Center)
+            .block(Block::default());
+        frame.render_widget(form, body.inner(Margin { vertical: 2, horizontal: 2 }));
+
+        let footer_layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Length(5),
+                Constraint::Min(20),
+            ])
+            .split(main_chunks[2]);
+        let spans = vec![
+            " Esc ".to_string(), " Menu ".to_string(),
+            " Left/Right ".to_string(), " Host or Join ".to_string(),
+            " Tab ".to_string(), " Next Field ".to_string(),
+            " Enter ".to_string(), format!(" {} ", self.role),
+        ];
+        render_key_spans(frame, spans, footer_layout[1]);
+    }
+}
+
+// ---- Role ----
+#[derive(Clone, Copy, PartialEq)]
+enum Role {
+    Host,
+    Join,
+}
+
+impl fmt::Display for Role {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Role::Host => "Host a Table",
+            Role::Join => "Join a Table",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Field {
+    Name,
+    Address,
+}
+
+// ---- TRAIT IMPLEMENTATIONS ----
+impl Model for LanLobbyScreen {
+    fn update(&mut self) -> std::io::Result<ModelResponse> {
+        let lobby = match &mut self.stage {
+            Stage::Playing(table) => return table.update(),
+            Stage::Lobby(lobby) => lobby,
+        };
+        if let Event::Key(key) = event::read()? {
+            if key.kind == event::KeyEventKind::Release {
+                return Ok(ModelResponse::Refresh);
+            }
+            match key.code {
+                KeyCode::Esc => return Ok(ModelResponse::NavToMainMenu),
+                KeyCode::Left | KeyCode::Right => {
+                    lobby.role = match lobby.role {
+                        Role::Host => Role::Join,
+                        Role::Join => Role::Host,
+                    };
+                    lobby.field = Field::Name;
+                    lobby.error.clear();
+                }
+                KeyCode::Tab | KeyCode::Up | KeyCode::Down => lobby.cycle_field(),
+                KeyCode::Backspace => {
+                    lobby.focused_input().pop();
+                }
+                KeyCode::Char(c) => {
+                    let input = lobby.focused_input();
+                    if input.chars().count() < MAX_INPUT_LENGTH {
+                        input.push(c);
+                    }
+                }
+                KeyCode::Enter => {
+                    if let Some(table) = lobby.connect() {
+                        self.stage = Stage::Playing(Box::new(table));
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(ModelResponse::Refresh)
+    }
+
+    fn ui(&mut self, frame: &mut Frame) {
+        match &mut self.stage {
+            Stage::Lobby(lobby) => lobby.render(frame),
+            Stage::Playing(table) => table.ui(frame),
+        }
+    }
+
+    fn tick(&mut self) {
+        if let Stage::Playing(table) = &mut self.stage {
+            table.tick();
+        }
+    }
+}
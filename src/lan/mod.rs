@@ -0,0 +1 @@
+pub mod lan_lobby_screen;
\ No newline at end of file
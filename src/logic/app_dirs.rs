@@ -0,0 +1,114 @@
+use directories::ProjectDirs;
+use include_dir::Dir;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// The app's own directories, under XDG on Linux, Application Support on
+/// macOS and AppData on Windows. None without a home directory to put them in.
+fn project_dirs() -> Option<ProjectDirs> {
+    ProjectDirs::from("", "", "jacks-blackjack")
+}
+
+/// Where the config file is kept, or the working directory without a home.
+pub fn config_dir() -> PathBuf {
+    project_dirs().map_or_else(|| PathBuf::from("."), |dirs| dirs.config_dir().to_path_buf())
+}
+
+/// Where saved stats, history and settings are kept, or `data` in the
+/// working directory without a home.
+pub fn data_dir() -> PathBuf {
+    project_dirs().map_or_else(|| PathBuf::from("data"), |dirs| dirs.data_dir().to_path_buf())
+}
+
+/// Path of a file kept in the data directory, e.g. `data_file(STREAKS_FILE)`.
+pub fn data_file(name: &str) -> String {
+    data_dir().join(name).to_string_lossy().into_owned()
+}
+
+/// Strategy charts are kept with the data unless the config names another directory.
+pub fn default_strategies_dir() -> String {
+    data_dir().join("strategies").to_string_lossy().into_owned()
+}
+
+/// Index plays are kept with the data, loaded from every set in here.
+pub fn deviations_dir() -> String {
+    data_dir().join("deviations").to_string_lossy().into_owned()
+}
+
+/// Where charts, simulation results and the play history are exported to.
+pub fn exports_dir() -> PathBuf {
+    data_dir().join("exports")
+}
+
+/// Writes the files built into the app from `from` into `to` when `to`
+/// doesn't exist yet, so they're there to read and edit on first launch.
+/// Returns how many files were written.
+pub fn seed_bundled_dir(from: &Dir, to: &Path) -> io::Result<usize> {
+    if to.exists() {
+        return Ok(0);
+    }
+    fs::create_dir_all(to)?;
+    for file in from.files() {
+        fs::write(to.join(file.path()), file.contents())?;
+    }
+    Ok(from.files().count())
+}
+
+/// Copies the files in `from` into `to` when `to` doesn't exist yet, so the
+/// charts shipped with the app, or data saved beside it before, carry over
+/// on first launch. Returns how many files were copied.
+pub fn seed_dir(from: &Path, to: &Path) -> io::Result<usize> {
+    if to.exists() || !from.is_dir() {
+        return Ok(0);
+    }
+    fs::create_dir_all(to)?;
+    let mut copied = 0;
+    for entry in fs::read_dir(from)? {
+        let path = entry?.path();
+        if let Some(name) = path.file_name().filter(|_| path.is_file()) {
+            fs::copy(&path, to.join(name))?;
+            copied += 1;
+        }
+    }
+    Ok(copied)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seeding_only_fills_a_new_directory() {
+        let dir = std::env::temp_dir().join(format!("seed-{}", uuid::Uuid::new_v4()));
+        let (from, to) = (dir.join("from"), dir.join("to"));
+        fs::create_dir_all(from.join("nested")).unwrap();
+        fs::write(from.join("streaks.json"), "{}").unwrap();
+        fs::write(from.join("history.db"), "").unwrap();
+
+        // Only the files are copied, and only the first time
+        assert_eq!(seed_dir(&from, &to).unwrap(), 2);
+        assert!(to.join("streaks.json").is_file() && !to.join("nested").exists());
+        fs::write(from.join("bet_ramp.json"), "{}").unwrap();
+        assert_eq!(seed_dir(&from, &to).unwrap(), 0);
+        assert!(!to.join("bet_ramp.json").exists());
+
+        // Nothing to copy from is fine
+        assert_eq!(seed_dir(&dir.join("missing"), &dir.join("other")).unwrap(), 0);
+        assert!(!dir.join("other").exists());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_bundled_files_are_written_out_once() {
+        use crate::constants::BUNDLED_DEVIATIONS;
+        let dir = std::env::temp_dir().join(format!("seed-{}", uuid::Uuid::new_v4()));
+
+        assert_eq!(seed_bundled_dir(&BUNDLED_DEVIATIONS, &dir).unwrap(), 2);
+        assert_eq!(fs::read_to_string(dir.join("illustrious-18.json")).unwrap(), fs::read_to_string("resources/deviations/illustrious-18.json").unwrap());
+        fs::remove_file(dir.join("fab-4.json")).unwrap();
+        assert_eq!(seed_bundled_dir(&BUNDLED_DEVIATIONS, &dir).unwrap(), 0);
+        assert!(!dir.join("fab-4.json").exists());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,255 @@
+use crate::logic::counting::CountingSystem;
+use crate::logic::game::advisor::advise;
+use crate::logic::game::round::{PlayerAction, Round, RoundPhase};
+use crate::logic::game::shoe::Shoe;
+use crate::logic::game::table::TableConfig;
+use crate::logic::strategy_calculator_logic::{BlackjackStrategy, StrategyVariables};
+
+/// True counts to choose from for sitting down at the table
+pub const ENTRY_THRESHOLDS: [i32; 4] = [1, 2, 3, 4];
+pub const DEFAULT_ENTRY_THRESHOLD: usize = 1;
+
+/// Players at the watched table, besides the back counter once seated
+pub const OTHER_PLAYERS: usize = 2;
+
+/// A stretch of play, from sitting down at the table to leaving it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    /// True count when sitting down
+    pub true_count: f64,
+
+    /// The entry threshold in use when sitting down
+    pub threshold: i32,
+    pub rounds: u32,
+
+    /// Rounds played with the true count below the threshold
+    pub rounds_below: u32,
+
+    /// Won or lost, in betting units
+    pub net: f64,
+
+    /// True count when leaving, none while still seated
+    pub exit_true_count: Option<f64>,
+}
+
+impl Entry {
+    /// Whether the true count, floored, had reached the threshold.
+    pub fn is_well_timed(&self) -> bool {
+        self.true_count.floor() as i32 >= self.threshold
+    }
+}
+
+/// A table played by others while the count is kept from the side, sitting
+/// down when the count climbs to the entry threshold and leaving when it
+/// drops. Everyone plays basic strategy for one betting unit a hand.
+pub struct BackCounter {
+    rules: StrategyVariables,
+    table: TableConfig,
+    shoe: Shoe,
+
+    /// Chart every hand is played by, hands stand without one
+    strategy: Option<BlackjackStrategy>,
+    system: &'static dyn CountingSystem,
+    threshold: i32,
+    seated: bool,
+    round: Option<Round>,
+    rounds_watched: u32,
+    entries: Vec<Entry>,
+}
+
+impl BackCounter {
+    pub fn new(rules: StrategyVariables, table: TableConfig, strategy: Option<BlackjackStrategy>, system: &'static dyn CountingSystem, threshold: i32) -> Self {
+        let shoe = Shoe::from_rules(&rules, &table);
+        Self::with_shoe(rules, table, shoe, strategy, system, threshold)
+    }
+
+    fn with_shoe(rules: StrategyVariables, table: TableConfig, shoe: Shoe, strategy: Option<BlackjackStrategy>, system: &'static dyn CountingSystem, threshold: i32) -> Self {
+        Self {
+            rules,
+            table,
+            shoe,
+            strategy,
+            system,
+            threshold,
+            seated: false,
+            round: None,
+            rounds_watched: 0,
+            entries: Vec::new(),
+        }
+    }
+
+    pub fn system(&self) -> &'static dyn CountingSystem {
+        self.system
+    }
+
+    pub fn threshold(&self) -> i32 {
+        self.threshold
+    }
+
+    /// Changes the threshold future entries are judged against.
+    pub fn set_threshold(&mut self, threshold: i32) {
+        self.threshold = threshold;
+    }
+
+    pub fn is_seated(&self) -> bool {
+        self.seated
+    }
+
+    /// The last round dealt, none at the start of each shoe.
+    pub fn round(&self) -> Option<&Round> {
+        self.round.as_ref()
+    }
+
+    /// True count of the cards seen since the shuffle.
+    pub fn true_count(&self) -> f64 {
+        self.system.true_count(self.shoe.running_count(self.system), self.shoe.decks_remaining().max(0.5))
+    }
+
+    pub fn decks_remaining(&self) -> f64 {
+        self.shoe.decks_remaining()
+    }
+
+    pub fn needs_shuffle(&self) -> bool {
+        self.shoe.needs_shuffle()
+    }
+
+    /// Leaves the table, if seated, and starts a fresh shoe.
+    pub fn shuffle(&mut self) {
+        self.leave();
+        self.shoe.shuffle();
+        self.round = None;
+    }
+
+    /// Sits down at the current true count, playing from the next round.
+    pub fn enter(&mut self) -> Result<&Entry, String> {
+        if self.seated {
+            return Err("Already seated".to_string());
+        }
+        self.seated = true;
+        self.entries.push(Entry {
+            true_count: self.true_count(),
+            threshold: self.threshold,
+            rounds: 0,
+            rounds_below: 0,
+            net: 0.0,
+            exit_true_count: None,
+        });
+        Ok(&self.entries[self.entries.len() - 1])
+    }
+
+    /// Gets up from the table, returning how the stay went, or none when
+    /// not seated.
+    pub fn leave(&mut self) -> Option<&Entry> {
+        if !self.seated {
+            return None;
+        }
+        self.seated = false;
+        let true_count = self.true_count();
+        let entry = self.entries.last_mut()?;
+        entry.exit_true_count = Some(true_count);
+        Some(entry)
+    }
+
+    /// Deals the next round and plays it out, the back counter taking the
+    /// last spot when seated.
+    pub fn deal(&mut self) -> &Round {
+        let true_count = self.true_count();
+        let spots = OTHER_PLAYERS + usize::from(self.seated);
+        let mut round = Round::deal(&self.rules, &self.table, &vec![self.table.min_bet; spots], &mut self.shoe);
+        play_out(&mut round, &mut self.shoe, self.strategy.as_ref());
+
+        self.rounds_watched += 1;
+        if self.seated && let Some(entry) = self.entries.last_mut() {
+            entry.rounds += 1;
+            entry.net += round.spot_net(OTHER_PLAYERS) / self.table.min_bet;
+            if (true_count.floor() as i32) < entry.threshold {
+                entry.rounds_below += 1;
+            }
+        }
+        self.round.insert(round)
+    }
+
+    pub fn rounds_watched(&self) -> u32 {
+        self.rounds_watched
+    }
+
+    /// Every stretch of play, the last still going while seated.
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+
+    /// Rounds played and units won over the entries that were, or
+    /// weren't, well timed.
+    pub fn results(&self, well_timed: bool) -> (u32, f64) {
+        self.entries.iter()
+            .filter(|entry| entry.is_well_timed() == well_timed)
+            .fold((0, 0.0), |(rounds, net), entry| (rounds + entry.rounds, net + entry.net))
+    }
+}
+
+/// Plays every hand of a round by basic strategy, never taking insurance,
+/// standing when there's no chart or a play can't be made.
+fn play_out(round: &mut Round, shoe: &mut Shoe, strategy: Option<&BlackjackStrategy>) {
+    loop {
+        match round.phase() {
+            RoundPhase::Complete => break,
+            RoundPhase::Insurance => {
+                let _ = round.resolve_insurance(false, shoe);
+            }
+            RoundPhase::PlayerTurn => {
+                let action = strategy
+                    .and_then(|strategy| advise(strategy, round))
+                    .map_or(PlayerAction::Stand, |advice| advice.action);
+                if round.apply(action, shoe).is_err() {
+                    let _ = round.apply(PlayerAction::Stand, shoe);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logic::counting::COUNTING_SYSTEMS;
+    use crate::logic::game::card::{Card, Rank, Suit};
+
+    #[test]
+    fn test_entries_are_judged_against_the_threshold() {
+        let rules = StrategyVariables::single_deck();
+        // Two rounds of two players standing on small cards against a
+        // dealer's 17, then a round with the back counter in the last spot
+        // winning with 20 against 19
+        let ranks = [
+            Rank::Two, Rank::Three, Rank::Ten, Rank::Four, Rank::Five, Rank::Seven,
+            Rank::Six, Rank::Two, Rank::Ten, Rank::Three, Rank::Four, Rank::Seven,
+            Rank::Seven, Rank::Seven, Rank::Ten, Rank::Ten,
+            Rank::Seven, Rank::Seven, Rank::Ten, Rank::Nine,
+        ];
+        let mut cards: Vec<Card> = ranks.iter().map(|rank| Card::new(*rank, Suit::Hearts)).collect();
+        cards.extend(vec![Card::new(Rank::Seven, Suit::Clubs); 32]);
+        let mut counter = BackCounter::with_shoe(rules, TableConfig::default(), Shoe::stacked(cards), None, COUNTING_SYSTEMS[0], 2);
+
+        // Hi-Lo +6 after the first twelve cards, with 40 cards left
+        counter.deal();
+        counter.deal();
+        assert_eq!(counter.rounds_watched(), 2);
+        assert!((counter.true_count() - 6.0 / (40.0 / 52.0)).abs() < 1e-9);
+
+        assert!(counter.enter().unwrap().is_well_timed());
+        assert!(counter.enter().is_err());
+        let round = counter.deal();
+        assert_eq!(round.hands().len(), OTHER_PLAYERS + 1);
+
+        let entry = counter.leave().unwrap().clone();
+        assert_eq!((entry.rounds, entry.rounds_below, entry.net), (1, 0, 1.0));
+        assert!(entry.exit_true_count.is_some());
+        assert_eq!(counter.leave(), None);
+        assert_eq!(counter.results(true), (1, 1.0));
+        assert_eq!(counter.results(false), (0, 0.0));
+
+        // Raising the threshold past the count makes the next entry early
+        counter.set_threshold(10);
+        assert!(!counter.enter().unwrap().is_well_timed());
+    }
+}
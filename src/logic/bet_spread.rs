@@ -0,0 +1,213 @@
+use crate::logic::counting::CountingSystem;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+/// How far bets are spread from one betting unit, and how quickly they
+/// ramp up with the true count.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BetSpread {
+    /// Largest bet, in betting units
+    pub max_units: u32,
+
+    /// Units added for each true count above one
+    pub units_per_count: u32,
+}
+
+pub const BET_SPREADS: [BetSpread; 4] = [
+    BetSpread { max_units: 4, units_per_count: 1 },
+    BetSpread { max_units: 8, units_per_count: 2 },
+    BetSpread { max_units: 12, units_per_count: 3 },
+    BetSpread { max_units: 16, units_per_count: 4 },
+];
+pub const DEFAULT_BET_SPREAD: usize = 1;
+
+/// Bankroll sizes to choose from, in betting units. More units means a
+/// smaller unit and less risk of ruin.
+pub const BANKROLL_UNITS: [u32; 4] = [50, 100, 200, 400];
+pub const DEFAULT_BANKROLL_UNITS: usize = 2;
+
+impl BetSpread {
+    /// Units to bet at a true count: one unit up to a true count of one,
+    /// then `units_per_count` more for each whole count above it, up to the
+    /// top of the spread.
+    pub fn units(&self, true_count: f64) -> u32 {
+        let counts_above = (true_count.floor() as i64 - 1).clamp(0, self.max_units as i64) as u32;
+        (1 + counts_above * self.units_per_count).min(self.max_units)
+    }
+}
+
+impl fmt::Display for BetSpread {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "1-{}", self.max_units)
+    }
+}
+
+/// Whole true counts a custom ramp sets a bet at, from zero up
+pub const RAMP_STEPS: usize = 9;
+
+/// Bets worked out for a game rather than following a spread, in betting
+/// units at each whole true count: the first at zero and below, the last
+/// at `RAMP_STEPS - 1` and above.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CustomRamp {
+    /// Counting system the true counts are for, e.g. "Hi-Lo"
+    pub system: String,
+    pub units: [u32; RAMP_STEPS],
+}
+
+impl CustomRamp {
+    /// Position in `units` of the bet at a true count.
+    pub fn step(true_count: f64) -> usize {
+        (true_count.floor() as i64).clamp(0, RAMP_STEPS as i64 - 1) as usize
+    }
+
+    pub fn units(&self, true_count: f64) -> u32 {
+        self.units[Self::step(true_count)]
+    }
+
+    pub fn max_units(&self) -> u32 {
+        self.units.iter().copied().max().unwrap_or(1)
+    }
+
+    /// Whether the ramp's true counts are `system`'s.
+    pub fn is_for(&self, system: &dyn CountingSystem) -> bool {
+        self.system == system.name()
+    }
+
+    /// Loads a saved ramp, none if there isn't one or it cannot be read.
+    pub fn load(path: &str) -> Option<Self> {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|json| serde_json::from_str(&json).ok())
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(parent) = Path::new(path).parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+impl fmt::Display for CustomRamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Custom 1-{}", self.max_units())
+    }
+}
+
+/// How many units a ramp bets at each count: one of the spreads, or a
+/// custom ramp.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RampUnits {
+    Spread(BetSpread),
+    Custom(CustomRamp),
+}
+
+impl RampUnits {
+    /// A saved ramp when counting with the system it was worked out for,
+    /// otherwise `fallback`. True counts run differently from one system to
+    /// another, Omega II's about twice Hi-Lo's, so a ramp doesn't carry over.
+    pub fn custom_or(ramp: Option<CustomRamp>, system: &dyn CountingSystem, fallback: BetSpread) -> RampUnits {
+        match ramp {
+            Some(ramp) if ramp.is_for(system) => RampUnits::Custom(ramp),
+            _ => RampUnits::Spread(fallback),
+        }
+    }
+
+    pub fn units(&self, true_count: f64) -> u32 {
+        match self {
+            RampUnits::Spread(spread) => spread.units(true_count),
+            RampUnits::Custom(ramp) => ramp.units(true_count),
+        }
+    }
+}
+
+impl fmt::Display for RampUnits {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RampUnits::Spread(spread) => write!(f, "{}", spread),
+            RampUnits::Custom(ramp) => write!(f, "{}", ramp),
+        }
+    }
+}
+
+/// A bet spread sized to a bankroll at a table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BetRamp {
+    pub spread: RampUnits,
+
+    /// Smallest bet in the ramp, the bankroll split into units in whole
+    /// dollars, never below the table minimum
+    pub unit: f64,
+
+    /// The table maximum, which caps the top of the ramp
+    pub max_bet: f64,
+}
+
+impl BetRamp {
+    pub fn new(spread: RampUnits, balance: f64, bankroll_units: u32, min_bet: f64, max_bet: f64) -> Self {
+        Self {
+            spread,
+            unit: (balance / bankroll_units as f64).floor().max(min_bet),
+            max_bet,
+        }
+    }
+
+    /// What the ramp bets at a true count.
+    pub fn bet(&self, true_count: f64) -> f64 {
+        (self.unit * self.spread.units(true_count) as f64).min(self.max_bet)
+    }
+
+    /// Whether a bet is what the ramp calls for, to the cent.
+    pub fn is_on_ramp(&self, bet: f64, true_count: f64) -> bool {
+        (bet - self.bet(true_count)).abs() < 0.005
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logic::counting::{HI_LO, KO, OMEGA_II};
+
+    #[test]
+    fn test_ramp_follows_the_true_count() {
+        // $1,000 over 200 units bets $5 a unit, spread 1-8
+        let ramp = BetRamp::new(RampUnits::Spread(BET_SPREADS[1]), 1000.0, 200, 5.0, 500.0);
+        assert_eq!(ramp.unit, 5.0);
+        let bets: Vec<f64> = [-3.0, 0.5, 1.9, 2.0, 3.4, 4.0, 5.0, 12.0].iter().map(|count| ramp.bet(*count)).collect();
+        assert_eq!(bets, [5.0, 5.0, 5.0, 15.0, 25.0, 35.0, 40.0, 40.0]);
+        assert!(ramp.is_on_ramp(25.0, 3.4));
+        assert!(!ramp.is_on_ramp(10.0, 3.4));
+
+        // A short bankroll still bets the table minimum, and the maximum caps the top
+        let ramp = BetRamp::new(RampUnits::Spread(BET_SPREADS[3]), 300.0, 400, 10.0, 100.0);
+        assert_eq!(ramp.unit, 10.0);
+        assert_eq!(ramp.bet(10.0), 100.0);
+
+        // A custom ramp bets its first step at zero and below and its last past the top
+        let custom = CustomRamp { system: "Hi-Lo".to_string(), units: [1, 1, 2, 4, 6, 8, 10, 12, 12] };
+        let ramp = BetRamp::new(RampUnits::Custom(custom.clone()), 1000.0, 100, 5.0, 500.0);
+        let bets: Vec<f64> = [-4.0, 1.5, 2.0, 3.9, 20.0].iter().map(|count| ramp.bet(*count)).collect();
+        assert_eq!(bets, [10.0, 10.0, 20.0, 40.0, 120.0]);
+        assert_eq!((custom.max_units(), ramp.spread.to_string()), (12, "Custom 1-12".to_string()));
+    }
+
+    #[test]
+    fn test_custom_ramp_only_bets_its_own_systems_counts() {
+        let custom = CustomRamp { system: "Hi-Lo".to_string(), units: [1, 1, 2, 4, 6, 8, 10, 12, 12] };
+        let fallback = BET_SPREADS[DEFAULT_BET_SPREAD];
+        assert_eq!(RampUnits::custom_or(Some(custom.clone()), &HI_LO, fallback), RampUnits::Custom(custom.clone()));
+        assert_eq!(RampUnits::custom_or(None, &HI_LO, fallback), RampUnits::Spread(fallback));
+
+        // An Omega II count of 6 is about a Hi-Lo 3, so it doesn't get the ramp's top bet
+        for system in [&OMEGA_II, &KO] {
+            assert!(!custom.is_for(system));
+            let units = RampUnits::custom_or(Some(custom.clone()), system, fallback);
+            assert_eq!(units, RampUnits::Spread(fallback));
+            assert_eq!(units.units(6.0), fallback.units(6.0));
+        }
+    }
+}
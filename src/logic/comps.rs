@@ -0,0 +1,96 @@
+/// House edges a pit may assume for blackjack when rating play, whatever
+/// the rules and however well it's played
+pub const PIT_EDGES: [f64; 4] = [0.01, 0.015, 0.02, 0.025];
+pub const DEFAULT_PIT_EDGE: usize = 2;
+
+/// Rounds an hour a pit may credit a player with
+pub const PIT_ROUNDS_PER_HOUR: [u32; 4] = [50, 60, 70, 80];
+pub const DEFAULT_PIT_ROUNDS: usize = 1;
+
+/// Shares of the theoretical loss a casino may hand back as comps
+pub const COMP_RATES: [f64; 5] = [0.1, 0.2, 0.3, 0.4, 0.5];
+pub const DEFAULT_COMP_RATE: usize = 2;
+
+/// A stretch of play as the pit rates it and as it really goes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CompsConfig {
+    /// Average bet a round, in dollars
+    pub average_bet: f64,
+    pub hours: f64,
+
+    /// What the pit assumes about the game
+    pub pit_edge: f64,
+    pub pit_rounds_per_hour: f64,
+
+    /// Share of the theoretical loss returned as comps
+    pub comp_rate: f64,
+
+    /// The edge the house really has with the way the game is played,
+    /// negative when the player has the edge
+    pub house_edge: f64,
+    pub rounds_per_hour: f64,
+}
+
+/// What a stretch of play is rated at and what it really costs, in dollars.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CompsEstimate {
+    /// Average bet × hours × the pit's rounds an hour × the pit's edge
+    pub theoretical_loss: f64,
+
+    /// Comps the theoretical loss earns
+    pub comps: f64,
+
+    /// What the player can expect to lose, negative when they expect to win
+    pub expected_loss: f64,
+}
+
+impl CompsEstimate {
+    pub fn new(config: &CompsConfig) -> Self {
+        let theoretical_loss = config.average_bet * config.hours * config.pit_rounds_per_hour * config.pit_edge;
+        Self {
+            theoretical_loss,
+            comps: theoretical_loss * config.comp_rate,
+            expected_loss: config.average_bet * config.hours * config.rounds_per_hour * config.house_edge,
+        }
+    }
+
+    /// Comps less the expected loss, what the play is worth all told.
+    pub fn net(&self) -> f64 {
+        self.comps - self.expected_loss
+    }
+
+    /// Share of the theoretical loss the comps would need to be to cover
+    /// the expected loss, none when the player expects to win anyway.
+    pub fn break_even_rate(&self) -> Option<f64> {
+        (self.expected_loss > 0.0 && self.theoretical_loss > 0.0).then(|| self.expected_loss / self.theoretical_loss)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_comps_weighed_against_the_real_cost() {
+        let config = CompsConfig {
+            average_bet: 25.0,
+            hours: 4.0,
+            pit_edge: 0.02,
+            pit_rounds_per_hour: 60.0,
+            comp_rate: 0.3,
+            house_edge: 0.005,
+            rounds_per_hour: 80.0,
+        };
+        let estimate = CompsEstimate::new(&config);
+        assert!((estimate.theoretical_loss - 120.0).abs() < 1e-9);
+        assert!((estimate.comps - 36.0).abs() < 1e-9);
+        assert!((estimate.expected_loss - 40.0).abs() < 1e-9);
+        assert!((estimate.net() + 4.0).abs() < 1e-9);
+        assert!((estimate.break_even_rate().unwrap() - 1.0 / 3.0).abs() < 1e-9);
+
+        // A counter with the edge is paid comps on top of winning
+        let counting = CompsEstimate::new(&CompsConfig { house_edge: -0.01, ..config });
+        assert!((counting.net() - 116.0).abs() < 1e-9);
+        assert_eq!(counting.break_even_rate(), None);
+    }
+}
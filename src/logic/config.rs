@@ -0,0 +1,317 @@
+use crate::logic::app_dirs::{config_dir, default_strategies_dir};
+use crate::logic::game::table::TableConfig;
+use crate::logic::strategy_calculator_logic::{GameVariant, StrategyVariables, SurrenderRule, DECK_OPTIONS};
+use crate::ui::ActionPalette;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{OnceLock, RwLock};
+
+/// The config as loaded at launch and changed in the settings screen
+static CURRENT: OnceLock<RwLock<Config>> = OnceLock::new();
+
+/// Screens the app can open on instead of the menu.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StartupScreen {
+    #[default]
+    Menu,
+    Game,
+    #[serde(rename = "Strategy Calculator")]
+    StrategyCalculator,
+    #[serde(rename = "Count Trainer")]
+    CountTrainer,
+    #[serde(rename = "Strategy Quiz")]
+    StrategyQuiz,
+    #[serde(rename = "Table Math")]
+    TableMath,
+    Simulation,
+    Statistics,
+}
+
+pub const STARTUP_SCREENS: [StartupScreen; 8] = [
+    StartupScreen::Menu,
+    StartupScreen::Game,
+    StartupScreen::StrategyCalculator,
+    StartupScreen::CountTrainer,
+    StartupScreen::StrategyQuiz,
+    StartupScreen::TableMath,
+    StartupScreen::Simulation,
+    StartupScreen::Statistics,
+];
+
+impl fmt::Display for StartupScreen {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            StartupScreen::Menu => "Menu",
+            StartupScreen::Game => "Game",
+            StartupScreen::StrategyCalculator => "Strategy Calculator",
+            StartupScreen::CountTrainer => "Count Trainer",
+            StartupScreen::StrategyQuiz => "Strategy Quiz",
+            StartupScreen::TableMath => "Table Math",
+            StartupScreen::Simulation => "Simulation",
+            StartupScreen::Statistics => "Statistics",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+// ---- Key Bindings ----
+/// Keys shared by every screen that can be bound to other keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyAction {
+    Quit,
+    Menu,
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+pub const KEY_ACTIONS: [KeyAction; 6] = [
+    KeyAction::Quit,
+    KeyAction::Menu,
+    KeyAction::Up,
+    KeyAction::Down,
+    KeyAction::Left,
+    KeyAction::Right,
+];
+
+impl KeyAction {
+    /// The key the screens listen for.
+    pub fn default_key(self) -> char {
+        match self {
+            KeyAction::Quit => 'q',
+            KeyAction::Menu => 'm',
+            KeyAction::Up => 'k',
+            KeyAction::Down => 'j',
+            KeyAction::Left => 'h',
+            KeyAction::Right => 'l',
+        }
+    }
+}
+
+impl fmt::Display for KeyAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            KeyAction::Quit => "Quit",
+            KeyAction::Menu => "Menu",
+            KeyAction::Up => "Up",
+            KeyAction::Down => "Down",
+            KeyAction::Left => "Left",
+            KeyAction::Right => "Right",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// The key pressed for each of the shared keys.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(default)]
+pub struct KeyBindings {
+    pub quit: char,
+    pub menu: char,
+    pub up: char,
+    pub down: char,
+    pub left: char,
+    pub right: char,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self {
+            quit: KeyAction::Quit.default_key(),
+            menu: KeyAction::Menu.default_key(),
+            up: KeyAction::Up.default_key(),
+            down: KeyAction::Down.default_key(),
+            left: KeyAction::Left.default_key(),
+            right: KeyAction::Right.default_key(),
+        }
+    }
+}
+
+impl KeyBindings {
+    pub fn key(&self, action: KeyAction) -> char {
+        match action {
+            KeyAction::Quit => self.quit,
+            KeyAction::Menu => self.menu,
+            KeyAction::Up => self.up,
+            KeyAction::Down => self.down,
+            KeyAction::Left => self.left,
+            KeyAction::Right => self.right,
+        }
+    }
+
+    pub fn set(&mut self, action: KeyAction, key: char) {
+        match action {
+            KeyAction::Quit => self.quit = key,
+            KeyAction::Menu => self.menu = key,
+            KeyAction::Up => self.up = key,
+            KeyAction::Down => self.down = key,
+            KeyAction::Left => self.left = key,
+            KeyAction::Right => self.right = key,
+        }
+    }
+
+    /// The key the screens listen for in place of `key`. A bound key stands
+    /// for its action's usual key, any other key for itself, so the usual
+    /// keys still work where they aren't bound to something else.
+    pub fn translate(&self, key: char) -> char {
+        KEY_ACTIONS.into_iter()
+            .find(|action| self.key(*action) == key)
+            .map_or(key, KeyAction::default_key)
+    }
+
+    /// Why the bindings can't be used, when two actions share a key.
+    pub fn validate(&self) -> Result<(), String> {
+        for (index, action) in KEY_ACTIONS.iter().enumerate() {
+            if let Some(other) = KEY_ACTIONS[index + 1..].iter().find(|other| self.key(**other) == self.key(*action)) {
+                return Err(format!("{} and {} are both bound to {}", action, other, self.key(*action)));
+            }
+        }
+        Ok(())
+    }
+}
+
+// ---- Config ----
+/// Settings read from the config file at launch, every one optional.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct Config {
+    /// Screen shown at launch
+    pub startup_screen: StartupScreen,
+
+    /// How chart actions are colored
+    pub theme: ActionPalette,
+
+    /// Directory strategy charts are read from and written to
+    pub strategies_dir: String,
+
+    /// Rules the game starts with, and the strategy calculator until other
+    /// rules are picked in it
+    pub rules: StrategyVariables,
+
+    /// Limits, chips and house rules of the tables played at
+    pub table: TableConfig,
+    pub keys: KeyBindings,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            startup_screen: StartupScreen::default(),
+            theme: ActionPalette::default(),
+            strategies_dir: default_strategies_dir(),
+            rules: StrategyVariables {
+                decks: 6,
+                dealer_stands_on_soft_17: true,
+                double_after_split: true,
+                dealer_peak: true,
+                surrender_allowed: SurrenderRule::AnyUpcard,
+                variant: GameVariant::Classic,
+            },
+            table: TableConfig::default(),
+            keys: KeyBindings::default(),
+        }
+    }
+}
+
+impl Config {
+    /// Reads the config, the defaults when there's no file. A file that
+    /// can't be read, or deals a number of decks the app has no shoe or
+    /// charts for, is an error rather than quietly ignored.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let text = fs::read_to_string(path).map_err(|err| format!("Could not read {}: {}", path.display(), err))?;
+        let config: Config = toml::from_str(&text).map_err(|err| format!("Could not read {}: {}", path.display(), err))?;
+        config.keys.validate().map_err(|err| format!("Could not read {}: {}", path.display(), err))?;
+        config.table.validate().map_err(|err| format!("Could not read {}: {}", path.display(), err))?;
+        if !DECK_OPTIONS.contains(&config.rules.decks) {
+            return Err(format!("Could not read {}: {} decks isn't one of {:?}", path.display(), config.rules.decks, DECK_OPTIONS));
+        }
+        Ok(config)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, toml::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+/// Where the config file is kept: config.toml in the platform's config
+/// directory, e.g. `~/.config/jacks-blackjack` on Linux.
+pub fn config_path() -> PathBuf {
+    config_dir().join("config.toml")
+}
+
+/// The config in use.
+pub fn current() -> Config {
+    CURRENT.get_or_init(|| RwLock::new(Config::default())).read().map_or_else(|_| Config::default(), |config| config.clone())
+}
+
+/// Puts a config in use, read by every screen opened from now on.
+pub fn set_current(config: Config) {
+    if let Ok(mut current) = CURRENT.get_or_init(|| RwLock::new(Config::default())).write() {
+        *current = config;
+    }
+}
+
+/// Directory strategy charts are read from and written to.
+pub fn strategies_dir() -> String {
+    current().strategies_dir
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_reads_back_and_rebinds_keys() {
+        let path = std::env::temp_dir().join(format!("config-{}.toml", uuid::Uuid::new_v4()));
+        assert_eq!(Config::load(&path).unwrap(), Config::default());
+
+        // Anything left out keeps its default
+        fs::write(&path, "startup_screen = \"Strategy Quiz\"\ntheme = \"Colorblind Safe\"\n\n[rules]\ndecks = 2\ndealer_stands_on_soft_17 = false\ndouble_after_split = true\ndealer_peak = true\nsurrender_allowed = \"Not Allowed\"\n\n[keys]\nquit = \"x\"\nup = \"w\"\n").unwrap();
+        let config = Config::load(&path).unwrap();
+        assert_eq!((config.startup_screen, config.theme), (StartupScreen::StrategyQuiz, ActionPalette::ColorblindSafe));
+        assert_eq!((config.rules.decks, config.rules.surrender_allowed, config.rules.variant), (2, SurrenderRule::NotAllowed, GameVariant::Classic));
+        assert_eq!(config.strategies_dir, default_strategies_dir());
+        assert_eq!(config.table, TableConfig::default());
+        config.save(&path).unwrap();
+        assert_eq!(Config::load(&path).unwrap(), config);
+
+        // Bound keys stand for the usual ones, which keep working
+        assert_eq!(config.keys.translate('x'), 'q');
+        assert_eq!(config.keys.translate('w'), 'k');
+        assert_eq!(config.keys.translate('q'), 'q');
+        assert_eq!(config.keys.translate('s'), 's');
+        let mut swapped = KeyBindings::default();
+        swapped.set(KeyAction::Up, 'j');
+        swapped.set(KeyAction::Down, 'k');
+        assert_eq!((swapped.translate('j'), swapped.translate('k')), ('k', 'j'));
+
+        // Two actions can't share a key
+        fs::write(&path, "[keys]\nquit = \"j\"\n").unwrap();
+        assert!(Config::load(&path).unwrap_err().contains("Quit and Down are both bound to j"));
+
+        // Table limits and chips can be set, anything left out keeps its default
+        fs::write(&path, "[table]\nmin_bet = 25.0\nmax_bet = 1000.0\nchip_denominations = [5.0, 25.0, 100.0, 500.0]\n").unwrap();
+        let table = Config::load(&path).unwrap().table;
+        assert_eq!((table.min_bet, table.max_bet, table.chip_denominations.len()), (25.0, 1000.0, 4));
+        assert_eq!(table.max_split_hands, TableConfig::default().max_split_hands);
+        fs::write(&path, "[table]\nmin_bet = 50.0\nmax_bet = 10.0\n").unwrap();
+        assert!(Config::load(&path).unwrap_err().starts_with("Could not read"));
+
+        // Neither can a shoe with no decks, or more than the charts are for
+        for decks in [0, 3, 200] {
+            fs::write(&path, format!("[rules]\ndecks = {}\ndealer_stands_on_soft_17 = true\ndouble_after_split = true\ndealer_peak = true\nsurrender_allowed = \"Not Allowed\"\n", decks)).unwrap();
+            assert!(Config::load(&path).unwrap_err().starts_with("Could not read"));
+        }
+        fs::remove_file(&path).unwrap();
+    }
+}
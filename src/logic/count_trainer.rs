@@ -0,0 +1,864 @@
+use crate::logic::counting::CountingSystem;
+use crate::logic::deviations::{IndexPlay, IndexSet, IndexSituation};
+use crate::logic::game::card::Card;
+use crate::logic::game::shoe::Shoe;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::time::Duration;
+
+// ---- Drills ----
+/// What's being practiced.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum CountDrill {
+    /// Keeping the running count as a shoe is dealt
+    RunningCount,
+    /// Dividing a running count by the decks left
+    TrueCount,
+    /// Judging the decks left from the discard tray
+    DeckEstimation,
+    /// Counting down a deck or shoe flashed quicker and quicker
+    SpeedCount,
+    /// Deciding between basic strategy and an index play at a true count
+    Deviations,
+    /// Watching a table and only sitting down while the count is good
+    BackCounting,
+}
+
+impl CountDrill {
+    pub fn next(self) -> Self {
+        match self {
+            CountDrill::RunningCount => CountDrill::TrueCount,
+            CountDrill::TrueCount => CountDrill::DeckEstimation,
+            CountDrill::DeckEstimation => CountDrill::SpeedCount,
+            CountDrill::SpeedCount => CountDrill::Deviations,
+            CountDrill::Deviations => CountDrill::BackCounting,
+            CountDrill::BackCounting => CountDrill::RunningCount,
+        }
+    }
+
+    /// What the drill says when it's started.
+    pub fn instructions(self) -> &'static str {
+        match self {
+            CountDrill::RunningCount => "Press Space to start dealing",
+            CountDrill::TrueCount => "Divide the running count by the decks remaining",
+            CountDrill::DeckEstimation => "How many decks are left, to the half deck?",
+            CountDrill::SpeedCount => "Press Space to flash the cards, then give the count",
+            CountDrill::Deviations => "Basic strategy or the index play at this count?",
+            CountDrill::BackCounting => "Press Space to start the table, then W to sit down once the count is good",
+        }
+    }
+}
+
+impl fmt::Display for CountDrill {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            CountDrill::RunningCount => "Running Count",
+            CountDrill::TrueCount => "True Count",
+            CountDrill::DeckEstimation => "Deck Estimation",
+            CountDrill::SpeedCount => "Speed Count",
+            CountDrill::Deviations => "Deviation",
+            CountDrill::BackCounting => "Back Counting",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+// ---- Running Count Drill ----
+/// Cards dealt between each time the running count is asked for
+pub const CARDS_PER_CHECK: usize = 10;
+
+/// A running count given during training, and what it should have been.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CountCheck {
+    /// Cards dealt when the count was asked for
+    pub cards_seen: usize,
+    pub expected: i32,
+    pub answer: i32,
+}
+
+impl CountCheck {
+    pub fn is_correct(&self) -> bool {
+        self.answer == self.expected
+    }
+}
+
+/// Deals a shoe one card at a time for practicing a counting system,
+/// stopping every `CARDS_PER_CHECK` cards and once the cut card comes out to
+/// ask for the running count.
+pub struct CountTrainer {
+    shoe: Shoe,
+    system: &'static dyn CountingSystem,
+
+    /// Cards dealt so far, most recent last
+    dealt: Vec<Card>,
+
+    checks: Vec<CountCheck>,
+}
+
+impl CountTrainer {
+    pub fn new(shoe: Shoe, system: &'static dyn CountingSystem) -> Self {
+        Self { shoe, system, dealt: Vec::new(), checks: Vec::new() }
+    }
+
+    pub fn system(&self) -> &'static dyn CountingSystem {
+        self.system
+    }
+
+    /// Deals the next card, unless the count is being asked for or the shoe
+    /// is done.
+    pub fn deal(&mut self) -> Option<Card> {
+        if self.awaiting_count() || self.shoe.needs_shuffle() {
+            return None;
+        }
+        let card = self.shoe.draw();
+        self.dealt.push(card);
+        Some(card)
+    }
+
+    /// Whether dealing has stopped for the running count to be given.
+    pub fn awaiting_count(&self) -> bool {
+        let due = self.dealt.len().is_multiple_of(CARDS_PER_CHECK) || self.shoe.needs_shuffle();
+        let answered = self.checks.last().is_some_and(|check| check.cards_seen == self.dealt.len());
+        !self.dealt.is_empty() && due && !answered
+    }
+
+    /// Records the running count given, scored against the real one.
+    pub fn answer(&mut self, count: i32) -> CountCheck {
+        let check = CountCheck { cards_seen: self.dealt.len(), expected: self.running_count(), answer: count };
+        self.checks.push(check);
+        check
+    }
+
+    /// Whether the cut card is out and its count has been given.
+    pub fn is_finished(&self) -> bool {
+        self.shoe.needs_shuffle() && !self.awaiting_count()
+    }
+
+    /// Running count of the cards dealt, counted from the system's
+    /// starting count.
+    pub fn running_count(&self) -> i32 {
+        self.shoe.running_count(self.system)
+    }
+
+    pub fn dealt(&self) -> &[Card] {
+        &self.dealt
+    }
+
+    /// Cards left to deal before the cut card.
+    pub fn cards_left(&self) -> usize {
+        self.shoe.cards_to_cut()
+    }
+
+    pub fn checks(&self) -> &[CountCheck] {
+        &self.checks
+    }
+
+    /// Fraction of the counts given that were right.
+    pub fn accuracy(&self) -> f64 {
+        if self.checks.is_empty() {
+            return 0.0;
+        }
+        self.checks.iter().filter(|check| check.is_correct()).count() as f64 / self.checks.len() as f64
+    }
+
+    /// How far the counts given were off by, on average.
+    pub fn average_error(&self) -> f64 {
+        if self.checks.is_empty() {
+            return 0.0;
+        }
+        let total: i32 = self.checks.iter().map(|check| (check.answer - check.expected).abs()).sum();
+        total as f64 / self.checks.len() as f64
+    }
+}
+
+// ---- True Count Drill ----
+
+/// How a true count is brought to a whole number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TrueCountRounding {
+    /// Down to the next whole number, so +2.9 is +2 and -0.5 is -1
+    #[default]
+    Floor,
+    /// To the nearest whole number, halves away from zero
+    Round,
+}
+
+impl TrueCountRounding {
+    pub fn toggle(self) -> Self {
+        match self {
+            TrueCountRounding::Floor => TrueCountRounding::Round,
+            TrueCountRounding::Round => TrueCountRounding::Floor,
+        }
+    }
+
+    pub fn apply(self, true_count: f64) -> i32 {
+        match self {
+            TrueCountRounding::Floor => true_count.floor() as i32,
+            TrueCountRounding::Round => true_count.round() as i32,
+        }
+    }
+}
+
+impl fmt::Display for TrueCountRounding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            TrueCountRounding::Floor => "Floor",
+            TrueCountRounding::Round => "Round",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// A running count and the decks left to divide it by.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TrueCountQuestion {
+    pub running_count: i32,
+
+    /// Estimated to the half deck, the way it's judged from the discard tray
+    pub decks_remaining: f64,
+}
+
+impl TrueCountQuestion {
+    pub fn true_count(&self) -> f64 {
+        self.running_count as f64 / self.decks_remaining
+    }
+}
+
+/// A true count given in the drill, with how long it took.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TrueCountAttempt {
+    pub question: TrueCountQuestion,
+    pub expected: i32,
+    pub answer: i32,
+    pub time: Duration,
+}
+
+impl TrueCountAttempt {
+    pub fn is_correct(&self) -> bool {
+        self.answer == self.expected
+    }
+}
+
+/// Asks for the true count of one running count and decks remaining after
+/// another, scoring each answer under the chosen rounding.
+pub struct TrueCountDrill {
+    /// Largest shoe questions are drawn from
+    decks: u8,
+    rounding: TrueCountRounding,
+    question: TrueCountQuestion,
+    attempts: Vec<TrueCountAttempt>,
+    rng: StdRng,
+}
+
+impl TrueCountDrill {
+    pub fn new(decks: u8, rounding: TrueCountRounding) -> Self {
+        let mut rng = StdRng::from_os_rng();
+        let question = Self::ask(&mut rng, decks);
+        Self { decks, rounding, question, attempts: Vec::new(), rng }
+    }
+
+    /// A half deck to the whole shoe left, with a running count that makes
+    /// for true counts between about -6 and +6.
+    fn ask(rng: &mut StdRng, decks: u8) -> TrueCountQuestion {
+        let half_decks = rng.random_range(1..=decks.max(1) as i32 * 2);
+        let decks_remaining = half_decks as f64 / 2.0;
+        let widest = (decks_remaining * 6.0).ceil() as i32;
+        TrueCountQuestion { running_count: rng.random_range(-widest..=widest), decks_remaining }
+    }
+
+    pub fn question(&self) -> TrueCountQuestion {
+        self.question
+    }
+
+    pub fn rounding(&self) -> TrueCountRounding {
+        self.rounding
+    }
+
+    pub fn set_rounding(&mut self, rounding: TrueCountRounding) {
+        self.rounding = rounding;
+    }
+
+    /// Scores the true count given for the current question, taking `time`
+    /// to answer, and moves on to the next.
+    pub fn answer(&mut self, true_count: i32, time: Duration) -> TrueCountAttempt {
+        let attempt = TrueCountAttempt {
+            question: self.question,
+            expected: self.rounding.apply(self.question.true_count()),
+            answer: true_count,
+            time,
+        };
+        self.attempts.push(attempt);
+        self.question = Self::ask(&mut self.rng, self.decks);
+        attempt
+    }
+
+    pub fn attempts(&self) -> &[TrueCountAttempt] {
+        &self.attempts
+    }
+
+    /// Fraction of the true counts given that were right.
+    pub fn accuracy(&self) -> f64 {
+        if self.attempts.is_empty() {
+            return 0.0;
+        }
+        self.attempts.iter().filter(|attempt| attempt.is_correct()).count() as f64 / self.attempts.len() as f64
+    }
+
+    /// How long answers took on average.
+    pub fn average_time(&self) -> Duration {
+        if self.attempts.is_empty() {
+            return Duration::ZERO;
+        }
+        self.attempts.iter().map(|attempt| attempt.time).sum::<Duration>() / self.attempts.len() as u32
+    }
+}
+
+// ---- Deck Estimation Drill ----
+
+/// Most an estimate can be off by and still count, in decks. Estimates are
+/// made to the half deck, so either half deck either side of the real
+/// number counts when it's right between them.
+pub const ESTIMATE_TOLERANCE: f64 = 0.25;
+
+/// A shoe with some of its cards in the discard tray.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DeckQuestion {
+    pub decks: u8,
+    pub cards_discarded: usize,
+}
+
+impl DeckQuestion {
+    pub fn decks_discarded(&self) -> f64 {
+        self.cards_discarded as f64 / 52.0
+    }
+
+    pub fn decks_remaining(&self) -> f64 {
+        self.decks as f64 - self.decks_discarded()
+    }
+}
+
+/// Decks remaining guessed from the discard tray, with how long it took.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DeckEstimate {
+    pub question: DeckQuestion,
+    pub answer: f64,
+    pub time: Duration,
+}
+
+impl DeckEstimate {
+    pub fn error(&self) -> f64 {
+        (self.answer - self.question.decks_remaining()).abs()
+    }
+
+    pub fn is_correct(&self) -> bool {
+        self.error() <= ESTIMATE_TOLERANCE
+    }
+}
+
+/// Shows discard trays of different depths and asks how many decks are
+/// left in the shoe.
+pub struct DeckEstimationDrill {
+    decks: u8,
+    question: DeckQuestion,
+    estimates: Vec<DeckEstimate>,
+    rng: StdRng,
+}
+
+impl DeckEstimationDrill {
+    pub fn new(decks: u8) -> Self {
+        let mut rng = StdRng::from_os_rng();
+        let question = Self::ask(&mut rng, decks);
+        Self { decks, question, estimates: Vec::new(), rng }
+    }
+
+    /// Anything from a fresh shoe to half a deck left.
+    fn ask(rng: &mut StdRng, decks: u8) -> DeckQuestion {
+        let decks = decks.max(1);
+        let cards_discarded = rng.random_range(0..=decks as usize * 52 - 26);
+        DeckQuestion { decks, cards_discarded }
+    }
+
+    pub fn question(&self) -> DeckQuestion {
+        self.question
+    }
+
+    /// Scores the decks remaining given for the current tray, taking `time`
+    /// to answer, and moves on to the next.
+    pub fn answer(&mut self, decks_remaining: f64, time: Duration) -> DeckEstimate {
+        let estimate = DeckEstimate { question: self.question, answer: decks_remaining, time };
+        self.estimates.push(estimate);
+        self.question = Self::ask(&mut self.rng, self.decks);
+        estimate
+    }
+
+    pub fn estimates(&self) -> &[DeckEstimate] {
+        &self.estimates
+    }
+
+    /// Fraction of the estimates close enough to count.
+    pub fn accuracy(&self) -> f64 {
+        if self.estimates.is_empty() {
+            return 0.0;
+        }
+        self.estimates.iter().filter(|estimate| estimate.is_correct()).count() as f64 / self.estimates.len() as f64
+    }
+
+    /// How many decks the estimates were off by, on average.
+    pub fn average_error(&self) -> f64 {
+        if self.estimates.is_empty() {
+            return 0.0;
+        }
+        self.estimates.iter().map(|estimate| estimate.error()).sum::<f64>() / self.estimates.len() as f64
+    }
+
+    /// How long estimates took on average.
+    pub fn average_time(&self) -> Duration {
+        if self.estimates.is_empty() {
+            return Duration::ZERO;
+        }
+        self.estimates.iter().map(|estimate| estimate.time).sum::<Duration>() / self.estimates.len() as u32
+    }
+}
+
+// ---- Speed Count Drill ----
+
+/// Each card is flashed for this much of the time the last one was
+const SPEEDUP: f64 = 0.97;
+
+/// Quickest a card is ever flashed
+const MIN_FLASH: Duration = Duration::from_millis(100);
+
+/// Time allowed to give the count once the last card has gone
+pub const ANSWER_TIME: Duration = Duration::from_secs(10);
+
+/// A deck or shoe flashed a card at a time, each card quicker than the last,
+/// with the count asked for once it's through.
+pub struct SpeedRun {
+    cards: Vec<Card>,
+    decks: u8,
+    system: &'static dyn CountingSystem,
+
+    /// How long the first card is flashed for
+    first_flash: Duration,
+
+    /// Cards flashed so far, counting the one showing
+    shown: usize,
+
+    /// Time taken flashing the cards already gone
+    flash_time: Duration,
+}
+
+/// How a speed run went.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpeedResult {
+    pub decks: u8,
+
+    /// How long the first card was flashed for
+    pub first_flash: Duration,
+    pub expected: i32,
+
+    /// None when the time to answer ran out
+    pub answer: Option<i32>,
+
+    /// Flashing the cards and giving the count
+    pub time: Duration,
+}
+
+impl SpeedResult {
+    pub fn is_correct(&self) -> bool {
+        self.answer == Some(self.expected)
+    }
+}
+
+impl SpeedRun {
+    /// `cards` are flashed in order, starting at `first_flash` a card. A few
+    /// cards are usually held back so a balanced count doesn't end on zero.
+    pub fn new(cards: Vec<Card>, decks: u8, system: &'static dyn CountingSystem, first_flash: Duration) -> Self {
+        Self { cards, decks, system, first_flash, shown: 1, flash_time: Duration::ZERO }
+    }
+
+    /// The card showing, until they've all gone.
+    pub fn current(&self) -> Option<Card> {
+        self.cards.get(self.shown - 1).copied()
+    }
+
+    /// Which card is showing, from one, and how many there are.
+    pub fn progress(&self) -> (usize, usize) {
+        (self.shown.min(self.cards.len()), self.cards.len())
+    }
+
+    /// How long the card showing stays up.
+    pub fn flash_duration(&self) -> Duration {
+        self.first_flash.mul_f64(SPEEDUP.powi(self.shown as i32 - 1)).max(MIN_FLASH)
+    }
+
+    /// Moves on to the next card. False once the last has gone.
+    pub fn advance(&mut self) -> bool {
+        if self.current().is_some() {
+            self.flash_time += self.flash_duration();
+            self.shown += 1;
+        }
+        self.current().is_some()
+    }
+
+    /// Scores the count given, or none when time ran out, taking
+    /// `answer_time` after the last card.
+    pub fn finish(&self, answer: Option<i32>, answer_time: Duration) -> SpeedResult {
+        let answer = answer.filter(|_| answer_time <= ANSWER_TIME);
+        SpeedResult {
+            decks: self.decks,
+            first_flash: self.first_flash,
+            expected: self.system.running_count(self.decks, &self.cards),
+            answer,
+            time: self.flash_time + answer_time.min(ANSWER_TIME),
+        }
+    }
+}
+
+/// Quickest correct run through a shoe of `decks`.
+pub fn best_time(results: &[SpeedResult], decks: u8) -> Option<Duration> {
+    results.iter()
+        .filter(|result| result.decks == decks && result.is_correct())
+        .map(|result| result.time)
+        .min()
+}
+
+// ---- Deviation Drill ----
+/// Furthest a drilled true count strays from the play's index
+const DEVIATION_SPREAD: i32 = 3;
+
+/// A hand dealt against an upcard at a true count, for deciding whether
+/// an index play applies.
+#[derive(Debug, Clone)]
+pub struct DeviationQuestion {
+    pub play: IndexPlay,
+    pub true_count: i32,
+    pub hand: [Card; 2],
+    pub upcard: Card,
+}
+
+impl DeviationQuestion {
+    /// Whether the count calls for the index play over basic strategy.
+    pub fn deviates(&self) -> bool {
+        self.play.applies(self.true_count)
+    }
+}
+
+/// Whether the index play was chosen for a question, and how long it took.
+#[derive(Debug, Clone)]
+pub struct DeviationAnswer {
+    pub question: DeviationQuestion,
+    pub deviated: bool,
+    pub time: Duration,
+}
+
+impl DeviationAnswer {
+    pub fn is_correct(&self) -> bool {
+        self.deviated == self.question.deviates()
+    }
+}
+
+/// Deals the situations of an index set at true counts either side of
+/// their indices and scores the choice of basic strategy or index play.
+pub struct DeviationDrill {
+    set: IndexSet,
+    rng: StdRng,
+    question: DeviationQuestion,
+    answers: Vec<DeviationAnswer>,
+}
+
+impl DeviationDrill {
+    pub fn new(set: IndexSet) -> Result<Self, String> {
+        Self::with_rng(set, StdRng::from_os_rng())
+    }
+
+    fn with_rng(set: IndexSet, mut rng: StdRng) -> Result<Self, String> {
+        if set.plays.is_empty() {
+            return Err(format!("{} has no index plays", set.name));
+        }
+        let question = Self::ask(&set, &mut rng);
+        Ok(Self { set, rng, question, answers: Vec::new() })
+    }
+
+    /// Picks a play and a true count that calls for it half the time.
+    fn ask(set: &IndexSet, rng: &mut StdRng) -> DeviationQuestion {
+        let play = set.plays[rng.random_range(0..set.plays.len())].clone();
+        let offset = rng.random_range(0..DEVIATION_SPREAD);
+        let true_count = if rng.random_bool(0.5) != play.below {
+            play.index + offset
+        } else {
+            play.index - 1 - offset
+        };
+
+        // Deal the hand from a fresh deck, player first, then the upcard
+        let mut deck = Shoe::new(1, 1.0);
+        match &play.situation {
+            IndexSituation::Insurance => {
+                deck.bring_forward(1, Card::is_ace);
+            }
+            IndexSituation::Hand(scenario) => {
+                // A full deck always has the cards
+                let _ = scenario.stack(&mut deck, 1);
+            }
+        }
+        let (first, upcard, second) = (deck.draw(), deck.draw(), deck.draw());
+        DeviationQuestion { play, true_count, hand: [first, second], upcard }
+    }
+
+    pub fn set(&self) -> &IndexSet {
+        &self.set
+    }
+
+    pub fn question(&self) -> &DeviationQuestion {
+        &self.question
+    }
+
+    /// Scores choosing the index play, or basic strategy, and asks the next question.
+    pub fn answer(&mut self, deviated: bool, time: Duration) -> DeviationAnswer {
+        let next = Self::ask(&self.set, &mut self.rng);
+        let question = std::mem::replace(&mut self.question, next);
+        let answer = DeviationAnswer { question, deviated, time };
+        self.answers.push(answer.clone());
+        answer
+    }
+
+    pub fn answers(&self) -> &[DeviationAnswer] {
+        &self.answers
+    }
+
+    pub fn accuracy(&self) -> f64 {
+        if self.answers.is_empty() {
+            return 0.0;
+        }
+        self.answers.iter().filter(|answer| answer.is_correct()).count() as f64 / self.answers.len() as f64
+    }
+
+    pub fn average_time(&self) -> Duration {
+        if self.answers.is_empty() {
+            return Duration::ZERO;
+        }
+        self.answers.iter().map(|answer| answer.time).sum::<Duration>() / self.answers.len() as u32
+    }
+}
+
+// ---- In-Game Count Quiz ----
+/// Rounds dealt between each time the game asks for the count
+pub const QUIZ_EVERY: u32 = 3;
+
+/// Asks for the count every few rounds while playing, with the count kept
+/// hidden, and keeps score.
+#[derive(Debug, Clone, Default)]
+pub struct CountQuiz {
+    rounds_since: u32,
+    asked: u32,
+    right: u32,
+}
+
+impl CountQuiz {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn round_dealt(&mut self) {
+        self.rounds_since += 1;
+    }
+
+    /// Whether the count should be asked for before the next round is dealt.
+    pub fn is_due(&self) -> bool {
+        self.rounds_since >= QUIZ_EVERY
+    }
+
+    pub fn rounds_until_due(&self) -> u32 {
+        QUIZ_EVERY.saturating_sub(self.rounds_since)
+    }
+
+    /// Starts the wait for the next time the count is asked for, whether
+    /// or not this one gets answered.
+    pub fn ask(&mut self) {
+        self.rounds_since = 0;
+    }
+
+    /// Scores a running count, returning whether it was right.
+    pub fn answer_running_count(&mut self, answer: i32, expected: i32) -> bool {
+        self.score(answer == expected)
+    }
+
+    /// Scores a whole number true count, where flooring and rounding are
+    /// both right.
+    pub fn answer_true_count(&mut self, answer: i32, true_count: f64) -> bool {
+        self.score([TrueCountRounding::Floor, TrueCountRounding::Round].iter().any(|rounding| rounding.apply(true_count) == answer))
+    }
+
+    fn score(&mut self, right: bool) -> bool {
+        self.asked += 1;
+        if right {
+            self.right += 1;
+        }
+        right
+    }
+
+    pub fn asked(&self) -> u32 {
+        self.asked
+    }
+
+    pub fn right(&self) -> u32 {
+        self.right
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logic::counting::HI_LO;
+    use crate::logic::game::card::{Rank, Suit};
+
+    #[test]
+    fn test_asks_for_the_count_and_scores_the_shoe() {
+        // Twelve low cards then three tens, so checks come at 10 and at the end
+        let mut cards = vec![Card::new(Rank::Five, Suit::Hearts); 12];
+        cards.extend([Card::new(Rank::King, Suit::Spades); 3]);
+        let mut trainer = CountTrainer::new(Shoe::stacked(cards), &HI_LO);
+
+        for _ in 0..CARDS_PER_CHECK {
+            assert!(trainer.deal().is_some());
+        }
+        assert!(trainer.awaiting_count());
+        assert_eq!(trainer.deal(), None);
+        assert!(trainer.answer(10).is_correct());
+        assert!(!trainer.awaiting_count());
+
+        while trainer.deal().is_some() {}
+        assert!(trainer.awaiting_count());
+        assert!(!trainer.is_finished());
+        let check = trainer.answer(8);
+        assert_eq!((check.expected, check.cards_seen), (9, 15));
+        assert!(trainer.is_finished());
+
+        assert_eq!(trainer.accuracy(), 0.5);
+        assert_eq!(trainer.average_error(), 0.5);
+    }
+
+    #[test]
+    fn test_true_count_conventions_and_scoring() {
+        assert_eq!(TrueCountRounding::Floor.apply(2.9), 2);
+        assert_eq!(TrueCountRounding::Floor.apply(-0.5), -1);
+        assert_eq!(TrueCountRounding::Round.apply(2.5), 3);
+        assert_eq!(TrueCountRounding::Round.apply(-2.4), -2);
+
+        let mut drill = TrueCountDrill::new(6, TrueCountRounding::Floor);
+        for _ in 0..50 {
+            let question = drill.question();
+            assert!(question.decks_remaining >= 0.5 && question.decks_remaining <= 6.0);
+            assert!(question.true_count().abs() <= 6.0);
+            drill.answer(0, Duration::ZERO);
+        }
+
+        let mut drill = TrueCountDrill::new(6, TrueCountRounding::Floor);
+        let right = TrueCountRounding::Floor.apply(drill.question().true_count());
+        assert!(drill.answer(right, Duration::from_secs(1)).is_correct());
+        let right = TrueCountRounding::Floor.apply(drill.question().true_count());
+        assert!(!drill.answer(right + 1, Duration::from_secs(3)).is_correct());
+
+        assert_eq!(drill.accuracy(), 0.5);
+        assert_eq!(drill.average_time(), Duration::from_secs(2));
+    }
+
+    #[test]
+    fn test_deck_estimates_count_to_the_nearest_half_deck() {
+        // Two and a quarter decks left, so two and two and a half both count
+        let question = DeckQuestion { decks: 6, cards_discarded: 195 };
+        assert_eq!(question.decks_remaining(), 2.25);
+        let estimate = |answer| DeckEstimate { question, answer, time: Duration::ZERO };
+        assert!(estimate(2.0).is_correct());
+        assert!(estimate(2.5).is_correct());
+        assert!(!estimate(3.0).is_correct());
+
+        let mut drill = DeckEstimationDrill::new(1);
+        for _ in 0..50 {
+            assert!(drill.question().decks_remaining() >= 0.5);
+            let right = drill.question().decks_remaining();
+            assert!(drill.answer(right, Duration::from_secs(2)).is_correct());
+        }
+        assert_eq!(drill.accuracy(), 1.0);
+        assert_eq!(drill.average_error(), 0.0);
+        assert_eq!(drill.average_time(), Duration::from_secs(2));
+    }
+
+    #[test]
+    fn test_speed_run_speeds_up_and_times_the_count() {
+        let cards = vec![Card::new(Rank::Five, Suit::Hearts); 3];
+        let mut run = SpeedRun::new(cards, 1, &HI_LO, Duration::from_millis(1000));
+        assert_eq!(run.flash_duration(), Duration::from_millis(1000));
+        assert!(run.advance());
+        assert_eq!(run.flash_duration(), Duration::from_millis(970));
+        assert!(run.advance());
+        assert_eq!(run.progress(), (3, 3));
+        assert!(!run.advance());
+        assert_eq!(run.current(), None);
+
+        let result = run.finish(Some(3), Duration::from_secs(2));
+        assert!(result.is_correct());
+        assert_eq!(result.time, Duration::from_micros(1_000_000 + 970_000 + 940_900 + 2_000_000));
+
+        // Too late counts as no answer
+        let late = run.finish(Some(3), ANSWER_TIME + Duration::from_secs(1));
+        assert!(!late.is_correct());
+        assert_eq!(best_time(&[late, result], 1), Some(result.time));
+        assert_eq!(best_time(&[late, result], 6), None);
+    }
+
+    #[test]
+    fn test_quiz_asks_every_few_rounds() {
+        let mut quiz = CountQuiz::new();
+        for _ in 0..QUIZ_EVERY {
+            assert!(!quiz.is_due());
+            quiz.round_dealt();
+        }
+        assert!(quiz.is_due());
+        quiz.ask();
+        assert_eq!(quiz.rounds_until_due(), QUIZ_EVERY);
+
+        assert!(quiz.answer_running_count(5, 5));
+        assert!(!quiz.answer_running_count(4, 5));
+        assert!(quiz.answer_true_count(1, 1.6));
+        assert!(quiz.answer_true_count(2, 1.6));
+        assert!(!quiz.answer_true_count(1, -1.6));
+        assert_eq!((quiz.right(), quiz.asked()), (3, 5));
+    }
+
+    #[test]
+    fn test_deviation_questions_straddle_the_index() {
+        let set = IndexSet::from_json(r#"{ "name": "Test", "system": "Hi-Lo", "description": "",
+            "plays": [
+                { "situation": "16 vs 10", "play": "S", "index": 0 },
+                { "situation": "13 vs 2", "play": "H", "index": -1, "below": true },
+                { "situation": "Insurance", "play": "Insure", "index": 3 }
+            ] }"#).unwrap();
+        let mut drill = DeviationDrill::with_rng(set, StdRng::seed_from_u64(7)).unwrap();
+
+        let mut deviations = 0;
+        for _ in 0..60 {
+            let question = drill.question().clone();
+            assert!((question.true_count - question.play.index).abs() <= DEVIATION_SPREAD);
+            match &question.play.situation {
+                IndexSituation::Insurance => assert!(question.upcard.is_ace()),
+                IndexSituation::Hand(scenario) => {
+                    assert_eq!(question.upcard.value(), scenario.upcard);
+                    assert_eq!(question.hand.iter().map(|card| card.value()).sum::<u8>(), if scenario.upcard == 10 { 16 } else { 13 });
+                }
+            }
+            if question.deviates() {
+                deviations += 1;
+            }
+            let answer = drill.answer(question.deviates(), Duration::from_secs(1));
+            assert!(answer.is_correct());
+        }
+        assert!((15..=45).contains(&deviations), "{} of 60 deviated", deviations);
+        assert_eq!(drill.accuracy(), 1.0);
+
+        let empty = IndexSet::from_json(r#"{ "name": "Empty", "system": "Hi-Lo", "description": "", "plays": [] }"#).unwrap();
+        assert!(DeviationDrill::new(empty).is_err());
+    }
+}
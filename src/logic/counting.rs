@@ -0,0 +1,110 @@
+use crate::logic::game::card::{Card, Rank};
+
+/// A card counting system: what each card adds to the running count as it's
+/// seen, and where the count starts. Systems are shared with simulations
+/// running on other threads.
+pub trait CountingSystem: Sync {
+    fn name(&self) -> &str;
+
+    /// What a card of the rank adds to the running count.
+    fn tag(&self, rank: Rank) -> i32;
+
+    /// What the tags of a full deck add up to.
+    fn deck_total(&self) -> i32 {
+        Rank::ALL.iter().map(|rank| self.tag(*rank) * 4).sum()
+    }
+
+    /// Whether the tags of a full deck add up to zero. Only a balanced
+    /// count converts to a true count.
+    fn is_balanced(&self) -> bool {
+        self.deck_total() == 0
+    }
+
+    /// Running count before any card is seen. Balanced counts start at
+    /// zero, unbalanced ones start low enough that the shoe counts up to a
+    /// single deck's total, their pivot.
+    fn initial_running_count(&self, decks: u8) -> i32 {
+        -self.deck_total() * (decks as i32 - 1)
+    }
+
+    /// Running count per deck remaining. Unbalanced counts are converted
+    /// about their pivot, the running count where they already read as a
+    /// true count, which makes the conversion start from zero too.
+    fn true_count(&self, running_count: i32, decks_remaining: f64) -> f64 {
+        let pivot = self.deck_total() as f64;
+        pivot + (running_count as f64 - pivot) / decks_remaining
+    }
+
+    /// Running count once `cards` have been seen from a fresh shoe.
+    fn running_count(&self, decks: u8, cards: &[Card]) -> i32 {
+        self.initial_running_count(decks) + cards.iter().map(|card| self.tag(card.rank)).sum::<i32>()
+    }
+}
+
+/// A counting system given by a table of tags.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TagCount {
+    pub name: &'static str,
+
+    /// Tag for each card value, Aces first then 2 through 10
+    pub tags: [i8; 10],
+}
+
+impl CountingSystem for TagCount {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn tag(&self, rank: Rank) -> i32 {
+        self.tags[rank.value() as usize - 1] as i32
+    }
+}
+
+pub const HI_LO: TagCount = TagCount { name: "Hi-Lo", tags: [-1, 1, 1, 1, 1, 1, 0, 0, 0, -1] };
+/// Knock-Out, Hi-Lo with sevens counted, unbalanced so it's never converted
+pub const KO: TagCount = TagCount { name: "KO", tags: [-1, 1, 1, 1, 1, 1, 1, 0, 0, -1] };
+pub const HI_OPT_I: TagCount = TagCount { name: "Hi-Opt I", tags: [0, 0, 1, 1, 1, 1, 0, 0, 0, -1] };
+pub const HI_OPT_II: TagCount = TagCount { name: "Hi-Opt II", tags: [0, 1, 1, 2, 2, 1, 1, 0, 0, -2] };
+pub const OMEGA_II: TagCount = TagCount { name: "Omega II", tags: [0, 1, 1, 2, 2, 2, 1, 0, -1, -2] };
+pub const ZEN: TagCount = TagCount { name: "Zen Count", tags: [-1, 1, 1, 2, 2, 2, 1, 0, 0, -2] };
+
+/// The built in counting systems, Hi-Lo first.
+pub const COUNTING_SYSTEMS: [&dyn CountingSystem; 6] = [&HI_LO, &KO, &HI_OPT_I, &HI_OPT_II, &OMEGA_II, &ZEN];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logic::game::card::Suit;
+
+    #[test]
+    fn test_systems_are_balanced() {
+        for system in [HI_LO, HI_OPT_I, HI_OPT_II, OMEGA_II, ZEN] {
+            // Four of each card and sixteen tens
+            let deck_total: i32 = system.tags.iter().enumerate()
+                .map(|(i, tag)| *tag as i32 * if i == 9 { 16 } else { 4 })
+                .sum();
+            assert_eq!(deck_total, 0, "{} is unbalanced", system.name);
+            assert!(system.is_balanced());
+            assert_eq!(system.initial_running_count(6), 0);
+        }
+        assert!(!KO.is_balanced());
+    }
+
+    #[test]
+    fn test_ko_counts_up_to_its_pivot() {
+        assert_eq!(KO.initial_running_count(1), 0);
+        assert_eq!(KO.initial_running_count(6), -20);
+
+        let shoe: Vec<Card> = (0..6)
+            .flat_map(|_| Suit::ALL)
+            .flat_map(|suit| Rank::ALL.map(|rank| Card::new(rank, suit)))
+            .collect();
+        assert_eq!(KO.running_count(6, &shoe), 4);
+        assert_eq!(HI_LO.running_count(6, &shoe), 0);
+
+        // Both read zero off the top, and KO reads its pivot at any depth
+        assert_eq!(KO.true_count(KO.initial_running_count(6), 6.0), 0.0);
+        assert_eq!(KO.true_count(4, 2.5), 4.0);
+        assert_eq!(HI_LO.true_count(6, 2.0), 3.0);
+    }
+}
@@ -0,0 +1,229 @@
+use crate::logic::count_trainer::CountDrill;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub const SECONDS_PER_DAY: u64 = 86_400;
+
+/// One answer given in a counting drill, as kept between runs.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct DrillResult {
+    pub drill: CountDrill,
+
+    /// Counting system in use when the answer was given
+    pub system: String,
+    pub decks: u8,
+
+    /// Seconds each card was shown for, in the drills that deal on a clock
+    pub speed: Option<f64>,
+    pub correct: bool,
+
+    /// How far off the answer was, in points of count or in decks, or none
+    /// when no answer came in time
+    pub error: Option<f64>,
+
+    /// Seconds taken to answer, in the timed drills
+    pub time: Option<f64>,
+
+    /// When the answer was given, in seconds since the Unix epoch
+    pub at: u64,
+}
+
+impl DrillResult {
+    /// Day the answer was given on, in days since the Unix epoch (UTC).
+    pub fn day(&self) -> u64 {
+        self.at / SECONDS_PER_DAY
+    }
+}
+
+/// Totals over a set of drill answers.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DrillSummary {
+    pub answers: usize,
+    pub correct: usize,
+
+    /// Average error of the answers given, when the drill measures one
+    pub average_error: Option<f64>,
+
+    /// Average seconds taken to answer, when any answers were timed
+    pub average_time: Option<f64>,
+}
+
+impl DrillSummary {
+    /// Sums up the results, or none when there aren't any.
+    pub fn summarize<'a>(results: impl IntoIterator<Item = &'a DrillResult>) -> Option<Self> {
+        let results: Vec<&DrillResult> = results.into_iter().collect();
+        if results.is_empty() {
+            return None;
+        }
+        let errors: Vec<f64> = results.iter().filter_map(|result| result.error).collect();
+        let times: Vec<f64> = results.iter().filter_map(|result| result.time).collect();
+        Some(Self {
+            answers: results.len(),
+            correct: results.iter().filter(|result| result.correct).count(),
+            average_error: (!errors.is_empty()).then(|| errors.iter().sum::<f64>() / errors.len() as f64),
+            average_time: (!times.is_empty()).then(|| times.iter().sum::<f64>() / times.len() as f64),
+        })
+    }
+
+    /// Share of answers that were right.
+    pub fn accuracy(&self) -> f64 {
+        self.correct as f64 / self.answers as f64
+    }
+}
+
+/// Every counting drill answer ever given, saved between runs so progress
+/// can be followed over time.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+pub struct CountingStats {
+    results: Vec<DrillResult>,
+}
+
+impl CountingStats {
+    /// Loads saved stats, starting fresh if there are none or they cannot be read.
+    pub fn load(path: &str) -> Self {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(parent) = Path::new(path).parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    pub fn record(&mut self, result: DrillResult) {
+        self.results.push(result);
+    }
+
+    fn drill_results(&self, drill: CountDrill) -> impl Iterator<Item = &DrillResult> {
+        self.results.iter().filter(move |result| result.drill == drill)
+    }
+
+    pub fn summary(&self, drill: CountDrill) -> Option<DrillSummary> {
+        DrillSummary::summarize(self.drill_results(drill))
+    }
+
+    /// The drill's answers split by counting system, in the order first drilled.
+    pub fn by_system(&self, drill: CountDrill) -> Vec<(String, DrillSummary)> {
+        self.group(drill, |result| result.system.clone())
+    }
+
+    /// The drill's answers split by card speed, slowest first. Empty for
+    /// drills that aren't dealt on a clock.
+    pub fn by_speed(&self, drill: CountDrill) -> Vec<(f64, DrillSummary)> {
+        let mut speeds: Vec<(f64, DrillSummary)> = self.group(drill, |result| result.speed)
+            .into_iter()
+            .filter_map(|(speed, summary)| Some((speed?, summary)))
+            .collect();
+        speeds.sort_by(|a, b| b.0.total_cmp(&a.0));
+        speeds
+    }
+
+    /// The drill's answers split by the day they were given, oldest first.
+    /// Days without practice are left out.
+    pub fn by_day(&self, drill: CountDrill) -> Vec<(u64, DrillSummary)> {
+        let mut days = self.group(drill, DrillResult::day);
+        days.sort_by_key(|(day, _)| *day);
+        days
+    }
+
+    /// Summarizes the drill's answers for each key, in the order each key first appears.
+    fn group<K: PartialEq>(&self, drill: CountDrill, key: impl Fn(&DrillResult) -> K) -> Vec<(K, DrillSummary)> {
+        let mut keys: Vec<K> = Vec::new();
+        for result in self.drill_results(drill) {
+            let result_key = key(result);
+            if !keys.contains(&result_key) {
+                keys.push(result_key);
+            }
+        }
+        keys.into_iter()
+            .filter_map(|group_key| {
+                let summary = DrillSummary::summarize(self.drill_results(drill).filter(|result| key(result) == group_key))?;
+                Some((group_key, summary))
+            })
+            .collect()
+    }
+}
+
+/// Seconds since the Unix epoch.
+pub fn now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_secs())
+}
+
+/// Formats a day counted from the Unix epoch as a date, e.g. 2026-10-17.
+pub fn format_day(day: u64) -> String {
+    // Days to a civil date, counting in 400 year eras from March 1st, 0000
+    let days = day as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_from_march = (5 * day_of_year + 2) / 153;
+    let day_of_month = day_of_year - (153 * month_from_march + 2) / 5 + 1;
+    let month = if month_from_march < 10 { month_from_march + 3 } else { month_from_march - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    format!("{:04}-{:02}-{:02}", year, month, day_of_month)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(drill: CountDrill, system: &str, speed: Option<f64>, error: f64, day: u64) -> DrillResult {
+        DrillResult {
+            drill,
+            system: system.to_string(),
+            decks: 6,
+            speed,
+            correct: error == 0.0,
+            error: Some(error),
+            time: None,
+            at: day * SECONDS_PER_DAY + 3_600,
+        }
+    }
+
+    #[test]
+    fn test_stats_group_and_save() {
+        let mut stats = CountingStats::default();
+        stats.record(result(CountDrill::RunningCount, "Hi-Lo", Some(1.0), 2.0, 20_742));
+        stats.record(result(CountDrill::RunningCount, "KO", Some(0.5), 0.0, 20_743));
+        stats.record(result(CountDrill::RunningCount, "Hi-Lo", Some(0.5), 0.0, 20_743));
+        stats.record(result(CountDrill::TrueCount, "Hi-Lo", None, 1.0, 20_743));
+
+        let summary = stats.summary(CountDrill::RunningCount).unwrap();
+        assert_eq!((summary.answers, summary.correct), (3, 2));
+        assert!((summary.average_error.unwrap() - 2.0 / 3.0).abs() < 1e-9);
+        assert_eq!(summary.average_time, None);
+        assert_eq!(stats.summary(CountDrill::SpeedCount), None);
+
+        let systems: Vec<String> = stats.by_system(CountDrill::RunningCount).into_iter().map(|(name, _)| name).collect();
+        assert_eq!(systems, ["Hi-Lo", "KO"]);
+        let speeds: Vec<f64> = stats.by_speed(CountDrill::RunningCount).iter().map(|(speed, _)| *speed).collect();
+        assert_eq!(speeds, [1.0, 0.5]);
+        assert!(stats.by_speed(CountDrill::TrueCount).is_empty());
+
+        // Improving from none right to all right the next day
+        let days = stats.by_day(CountDrill::RunningCount);
+        assert_eq!(days.iter().map(|(day, summary)| (*day, summary.accuracy())).collect::<Vec<_>>(), [(20_742, 0.0), (20_743, 1.0)]);
+
+        let path = std::env::temp_dir().join(format!("counting-stats-{}.json", uuid::Uuid::new_v4()));
+        let path = path.to_str().unwrap();
+        stats.save(path).unwrap();
+        assert_eq!(CountingStats::load(path), stats);
+        fs::remove_file(path).unwrap();
+        assert_eq!(CountingStats::load(path), CountingStats::default());
+    }
+
+    #[test]
+    fn test_format_day() {
+        assert_eq!(format_day(0), "1970-01-01");
+        assert_eq!(format_day(11_016), "2000-02-29");
+        assert_eq!(format_day(20_743), "2026-10-17");
+    }
+}
@@ -0,0 +1,161 @@
+use crate::logic::ev::{card_label, ChartHand};
+use crate::logic::game::card::Card;
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+/// Right and wrong plays made in one chart cell.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct CellTally {
+    pub right: u32,
+    pub wrong: u32,
+}
+
+impl CellTally {
+    pub fn played(&self) -> u32 {
+        self.right + self.wrong
+    }
+
+    /// Share of plays that were wrong, none before any were made.
+    pub fn miss_rate(&self) -> Option<f64> {
+        (self.played() > 0).then(|| self.wrong as f64 / self.played() as f64)
+    }
+}
+
+/// The tally for a cell of a chart.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct CellRecord {
+    pub chart: String,
+    pub hand: ChartHand,
+
+    /// The upcard's column in the chart, from 0 for a 2 to 9 for an Ace
+    pub column: usize,
+    pub tally: CellTally,
+}
+
+impl CellRecord {
+    /// The cell as the heatmap labels it, e.g. "Hard 16 vs 10".
+    pub fn cell(&self) -> String {
+        format!("{} vs {}", self.hand, card_label(if self.column == 9 { 1 } else { self.column as u8 + 2 }))
+    }
+}
+
+/// How often each chart cell has been played right and wrong, at the table
+/// and in the strategy drills. The tallies are kept in the play history,
+/// this is them as they were read.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+pub struct DecisionAccuracy {
+    pub cells: Vec<CellRecord>,
+}
+
+impl DecisionAccuracy {
+    /// Loads tallies saved to a file by an older version, none if there are
+    /// none or they cannot be read.
+    pub fn load(path: &str) -> Self {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
+
+    /// Counts a play of the chart's row for `hand` against the upcard.
+    pub fn record(&mut self, chart: &str, hand: ChartHand, upcard: Card, correct: bool) {
+        self.record_cell(chart, hand, upcard.rank.strategy_column(), correct);
+    }
+
+    /// Counts a play of the chart's row for `hand` in the upcard's column.
+    pub fn record_cell(&mut self, chart: &str, hand: ChartHand, column: usize, correct: bool) {
+        let index = match self.cells.iter().position(|cell| cell.chart == chart && cell.hand == hand && cell.column == column) {
+            Some(index) => index,
+            None => {
+                self.cells.push(CellRecord { chart: chart.to_string(), hand, column, tally: CellTally::default() });
+                self.cells.len() - 1
+            }
+        };
+        let tally = &mut self.cells[index].tally;
+        if correct {
+            tally.right += 1;
+        } else {
+            tally.wrong += 1;
+        }
+    }
+
+    /// The plays made in a cell of a chart, none for cells never played.
+    pub fn tally(&self, chart: &str, hand: ChartHand, column: usize) -> CellTally {
+        self.cells.iter()
+            .find(|cell| cell.chart == chart && cell.hand == hand && cell.column == column)
+            .map_or_else(CellTally::default, |cell| cell.tally)
+    }
+
+    /// Cells of the chart that have ever been played wrong.
+    pub fn missed(&self, chart: &str) -> impl Iterator<Item = &CellRecord> {
+        self.cells.iter().filter(move |cell| cell.chart == chart && cell.tally.wrong > 0)
+    }
+
+    /// Cells over every chart played at least `min_played` times and missed
+    /// at least once, most often missed first.
+    pub fn weakest(&self, min_played: u32) -> Vec<&CellRecord> {
+        let mut cells: Vec<&CellRecord> = self.cells.iter()
+            .filter(|cell| cell.tally.wrong > 0 && cell.tally.played() >= min_played)
+            .collect();
+        cells.sort_by(|a, b| b.tally.miss_rate().unwrap_or(0.0).total_cmp(&a.tally.miss_rate().unwrap_or(0.0)).then(b.tally.wrong.cmp(&a.tally.wrong)));
+        cells
+    }
+
+    /// Plays made over every cell of each chart played, by chart name.
+    pub fn by_chart(&self) -> Vec<(String, CellTally)> {
+        let mut charts: Vec<(String, CellTally)> = vec![];
+        for cell in &self.cells {
+            let index = match charts.iter().position(|(chart, _)| *chart == cell.chart) {
+                Some(index) => index,
+                None => {
+                    charts.push((cell.chart.clone(), CellTally::default()));
+                    charts.len() - 1
+                }
+            };
+            charts[index].1.right += cell.tally.right;
+            charts[index].1.wrong += cell.tally.wrong;
+        }
+        charts.sort_by(|a, b| a.0.cmp(&b.0));
+        charts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logic::game::card::{Rank, Suit};
+
+    #[test]
+    fn test_plays_are_tallied_by_chart_and_cell() {
+        let mut accuracy = DecisionAccuracy::default();
+        let ten = Card::new(Rank::King, Suit::Spades);
+        for correct in [true, false, false, true] {
+            accuracy.record("chart", ChartHand::Hard(16), ten, correct);
+        }
+        accuracy.record("chart", ChartHand::Soft(18), Card::new(Rank::Ace, Suit::Hearts), true);
+
+        let sixteen = accuracy.tally("chart", ChartHand::Hard(16), 8);
+        assert_eq!((sixteen.played(), sixteen.miss_rate()), (4, Some(0.5)));
+        assert_eq!(accuracy.tally("chart", ChartHand::Soft(18), 9).miss_rate(), Some(0.0));
+        // Other charts and cells are counted apart
+        assert_eq!(accuracy.tally("other chart", ChartHand::Hard(16), 8).miss_rate(), None);
+        assert_eq!(accuracy.tally("chart", ChartHand::Pair(8), 8), CellTally::default());
+
+        // Only missed cells are weak, and cells played too little are left out
+        accuracy.record_cell("other chart", ChartHand::Pair(8), 8, false);
+        let weakest: Vec<String> = accuracy.weakest(1).iter().map(|cell| cell.cell()).collect();
+        assert_eq!(weakest, ["8,8 vs 10", "Hard 16 vs 10"]);
+        assert_eq!(accuracy.weakest(2).len(), 1);
+        assert_eq!(accuracy.missed("chart").count(), 1);
+        assert_eq!(accuracy.by_chart(), vec![
+            ("chart".to_string(), CellTally { right: 3, wrong: 2 }),
+            ("other chart".to_string(), CellTally { right: 0, wrong: 1 }),
+        ]);
+
+        let path = std::env::temp_dir().join(format!("decision-accuracy-{}.json", uuid::Uuid::new_v4()));
+        let path = path.to_str().unwrap();
+        fs::write(path, serde_json::to_string(&accuracy).unwrap()).unwrap();
+        assert_eq!(DecisionAccuracy::load(path), accuracy);
+        fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,213 @@
+use std::{fmt, fs};
+use serde::{Deserialize, Deserializer};
+use crate::logic::ev::ChartHand;
+use crate::logic::game::advisor::resolve_code;
+use crate::logic::game::round::PlayerAction;
+use crate::logic::game::scenario::Scenario;
+
+/// Every action, for reading a play's chart code without any ruled out.
+const ALL_ACTIONS: [PlayerAction; 5] = [
+    PlayerAction::Hit,
+    PlayerAction::Stand,
+    PlayerAction::Double,
+    PlayerAction::Split,
+    PlayerAction::Surrender,
+];
+
+/// A named set of count-based index plays, e.g. the Illustrious 18, for one
+/// counting system.
+#[derive(Debug, Deserialize, Clone)]
+pub struct IndexSet {
+    pub name: String,
+
+    /// Counting system the indices are true counts for, e.g. "Hi-Lo"
+    pub system: String,
+
+    pub description: String,
+
+    pub plays: Vec<IndexPlay>,
+}
+
+/// A departure from basic strategy once the true count reaches an index.
+#[derive(Debug, Deserialize, Clone)]
+pub struct IndexPlay {
+    pub situation: IndexSituation,
+
+    /// Chart code of the play to make instead, e.g. "S" or "Dh", or "Insure"
+    pub play: String,
+
+    /// True count the play changes at
+    pub index: i32,
+
+    /// Whether the play is made below the index rather than at or above it
+    #[serde(default)]
+    pub below: bool,
+}
+
+/// When an index play comes up: the insurance offer, or a hand against an upcard.
+#[derive(Debug, Clone, PartialEq)]
+pub enum IndexSituation {
+    Insurance,
+    Hand(Scenario),
+}
+
+// Written like a scenario, e.g. "16 vs 10" or "10,10 vs 5"
+impl<'de> Deserialize<'de> for IndexSituation {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        if s.trim().eq_ignore_ascii_case("insurance") {
+            return Ok(IndexSituation::Insurance);
+        }
+        Scenario::parse(&s).map(IndexSituation::Hand).map_err(serde::de::Error::custom)
+    }
+}
+
+impl fmt::Display for IndexSituation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IndexSituation::Insurance => write!(f, "Insurance"),
+            IndexSituation::Hand(scenario) => write!(f, "{}", scenario),
+        }
+    }
+}
+
+impl IndexPlay {
+    /// Name of the play, e.g. "Stand" for "S".
+    pub fn play_name(&self) -> String {
+        match self.situation {
+            IndexSituation::Insurance => "Insure".to_string(),
+            IndexSituation::Hand(_) => resolve_code(&self.play, &ALL_ACTIONS)
+                .map_or(self.play.clone(), |action| action.to_string()),
+        }
+    }
+
+    /// Whether the play is made at a true count, rather than basic strategy.
+    pub fn applies(&self, true_count: i32) -> bool {
+        if self.below {
+            true_count < self.index
+        } else {
+            true_count >= self.index
+        }
+    }
+
+    /// The true counts the play is made at, e.g. "+3 or more" or "below -1".
+    pub fn counts(&self) -> String {
+        let index = if self.index == 0 { "0".to_string() } else { format!("{:+}", self.index) };
+        if self.below {
+            format!("below {}", index)
+        } else {
+            format!("{} or more", index)
+        }
+    }
+}
+
+impl IndexSet {
+    /// Parse an IndexSet from a JSON string, checking every play can be made.
+    pub fn from_json(json_str: &str) -> Result<Self, String> {
+        let set: Self = serde_json::from_str(json_str).map_err(|err| err.to_string())?;
+        for play in &set.plays {
+            let valid = match play.situation {
+                IndexSituation::Insurance => play.play == "Insure",
+                IndexSituation::Hand(_) => resolve_code(&play.play, &ALL_ACTIONS).is_some(),
+            };
+            if !valid {
+                return Err(format!("Unknown play for {}: {}", play.situation, play.play));
+            }
+        }
+        Ok(set)
+    }
+
+    /// The index play for a hand played from a chart row against an
+    /// upcard (Aces as 1), when the true count calls for it.
+    pub fn play_for(&self, hand: ChartHand, upcard: u8, true_count: i32) -> Option<&IndexPlay> {
+        self.plays.iter().find(|play| match play.situation {
+            IndexSituation::Hand(scenario) => ChartHand::from(scenario.hand) == hand && scenario.upcard == upcard && play.applies(true_count),
+            IndexSituation::Insurance => false,
+        })
+    }
+
+    /// True count the set takes insurance at or above, if it has an index for it.
+    pub fn insurance_index(&self) -> Option<i32> {
+        self.plays.iter()
+            .find(|play| play.situation == IndexSituation::Insurance && !play.below)
+            .map(|play| play.index)
+    }
+
+    /// Whether the set takes insurance at the true count.
+    pub fn insures(&self, true_count: i32) -> bool {
+        self.plays.iter().any(|play| play.situation == IndexSituation::Insurance && play.applies(true_count))
+    }
+}
+
+/// Loads every `.json` index set in `deviations_dir`, sorted by name. Files
+/// that can't be read are left out.
+pub fn load_index_sets(deviations_dir: &str) -> Vec<IndexSet> {
+    let mut sets: Vec<IndexSet> = fs::read_dir(deviations_dir)
+        .map(|entries| {
+            entries.flatten()
+                .map(|entry| entry.path())
+                .filter(|path| path.is_file() && path.extension().and_then(|ext| ext.to_str()) == Some("json"))
+                .filter_map(|path| fs::read_to_string(path).ok())
+                .filter_map(|json| IndexSet::from_json(&json).ok())
+                .collect()
+        })
+        .unwrap_or_default();
+    sets.sort_by(|a, b| a.name.cmp(&b.name));
+    sets
+}
+
+/// Counting systems the sets have indices for, in the order first seen.
+pub fn counting_systems(sets: &[IndexSet]) -> Vec<String> {
+    let mut systems: Vec<String> = Vec::new();
+    for set in sets {
+        if !systems.contains(&set.system) {
+            systems.push(set.system.clone());
+        }
+    }
+    systems
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logic::game::scenario::ScenarioHand;
+
+    #[test]
+    fn test_index_set_from_json() {
+        let set = IndexSet::from_json(r#"{
+            "name": "Test Set",
+            "system": "Hi-Lo",
+            "description": "For Testing",
+            "plays": [
+                { "situation": "Insurance", "play": "Insure", "index": 3 },
+                { "situation": "16 vs 10", "play": "S", "index": 0 },
+                { "situation": "13 vs 2", "play": "H", "index": -1, "below": true }
+            ]
+        }"#).unwrap();
+
+        assert_eq!(set.plays[0].situation, IndexSituation::Insurance);
+        assert_eq!(set.plays[0].counts(), "+3 or more");
+        assert_eq!(set.plays[1].situation, IndexSituation::Hand(Scenario { hand: ScenarioHand::Hard(16), upcard: 10 }));
+        assert_eq!(set.plays[1].play_name(), "Stand");
+        assert_eq!(set.plays[1].counts(), "0 or more");
+        assert_eq!(set.plays[2].counts(), "below -1");
+        assert!(set.plays[1].applies(0) && !set.plays[1].applies(-1));
+        assert!(set.plays[2].applies(-2) && !set.plays[2].applies(-1));
+        assert!(set.insures(3) && !set.insures(2));
+        assert_eq!(set.insurance_index(), Some(3));
+        assert_eq!(set.play_for(ChartHand::Hard(16), 10, 1).map(|play| play.play.as_str()), Some("S"));
+        assert!(set.play_for(ChartHand::Hard(16), 10, -1).is_none());
+        assert!(set.play_for(ChartHand::Soft(16), 10, 1).is_none());
+        assert_eq!(set.play_for(ChartHand::Hard(13), 2, -3).map(|play| play.play.as_str()), Some("H"));
+
+        let unknown_play = r#"{ "name": "Bad", "system": "Hi-Lo", "description": "",
+            "plays": [{ "situation": "16 vs 10", "play": "X", "index": 0 }] }"#;
+        assert!(IndexSet::from_json(unknown_play).is_err());
+        let unknown_hand = r#"{ "name": "Bad", "system": "Hi-Lo", "description": "",
+            "plays": [{ "situation": "soft 30 vs 10", "play": "S", "index": 0 }] }"#;
+        assert!(IndexSet::from_json(unknown_hand).is_err());
+    }
+}
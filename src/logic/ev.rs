@@ -0,0 +1,1192 @@
+use std::collections::HashMap;
+use std::fmt;
+use crate::logic::counting::CountingSystem;
+use crate::logic::game::advisor::resolve_code;
+use crate::logic::game::card::{Card, Rank};
+use crate::logic::game::round::PlayerAction;
+use crate::logic::game::scenario::ScenarioHand;
+use crate::logic::game::table::TableConfig;
+use crate::logic::strategy_calculator_logic::{BlackjackStrategy, GameVariant, DECK_OPTIONS, HardHandRow, LegendEntry, PairRow, SoftHandRow, StrategyTables, StrategyVariables, SurrenderRule};
+use serde::{Deserialize, Serialize};
+
+/// Cards of each value left in the shoe, Aces first then 2 through 10.
+pub type Counts = [u16; 10];
+
+/// Dealer upcards in the order of a strategy chart's columns, 2-10 then Ace.
+pub const CHART_UPCARDS: [u8; 10] = [2, 3, 4, 5, 6, 7, 8, 9, 10, 1];
+
+/// Legend text for every code the engine writes into a chart.
+const LEGEND: [(&str, &str); 8] = [
+    ("H", "Hit"),
+    ("S", "Stand"),
+    ("P", "Split"),
+    ("Dh", "Double if allowed, else Hit"),
+    ("Ds", "Double if allowed, else Stand"),
+    ("Rh", "Surrender if allowed, else Hit"),
+    ("Rs", "Surrender if allowed, else Stand"),
+    ("Rp", "Surrender if allowed, else Split"),
+];
+
+/// A full shoe for the rules.
+pub fn full_shoe(rules: &StrategyVariables) -> Counts {
+    let decks = rules.decks as u16;
+    let mut counts = [4 * decks; 10];
+    counts[9] = 16 * decks;
+    counts
+}
+
+/// Counts of the values among `cards`, e.g. those the player hasn't seen yet.
+pub fn counts_of<'a>(cards: impl IntoIterator<Item = &'a Card>) -> Counts {
+    let mut counts = [0; 10];
+    for card in cards {
+        counts[index(card.value())] += 1;
+    }
+    counts
+}
+
+fn index(value: u8) -> usize {
+    value as usize - 1
+}
+
+/// Best total of a hand, counting one Ace as 11 when that doesn't bust it.
+fn soft_total(hard: u8, ace: bool) -> u8 {
+    if ace && hard + 10 <= 21 { hard + 10 } else { hard }
+}
+
+// ---- Dealer ----
+
+/// Chances of each way the dealer's hand can finish.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct DealerOutcomes {
+    /// Chance of finishing on 17 through 21
+    pub totals: [f64; 5],
+    pub blackjack: f64,
+    /// Chance of busting with exactly 22, which pushes in Free Bet
+    pub twenty_two: f64,
+    /// Chance of busting with 23 or more
+    pub bust: f64,
+}
+
+impl DealerOutcomes {
+    pub fn busts(&self) -> f64 {
+        self.twenty_two + self.bust
+    }
+
+    fn add(&mut self, other: &DealerOutcomes, weight: f64) {
+        for (total, chance) in self.totals.iter_mut().zip(other.totals) {
+            *total += chance * weight;
+        }
+        self.blackjack += other.blackjack * weight;
+        self.twenty_two += other.twenty_two * weight;
+        self.bust += other.bust * weight;
+    }
+}
+
+/// Chances of each dealer result for an upcard, with the cards still to come
+/// in `counts` (the upcard already taken out). When the dealer peeks, the hole
+/// card is known not to make a blackjack, so the chances are given the dealer
+/// doesn't have one.
+pub fn dealer_outcomes(counts: &Counts, upcard: u8, rules: &StrategyVariables) -> DealerOutcomes {
+    let mut counts = *counts;
+    dealer_draw(&mut counts, upcard, upcard == 1, 1, rules, &mut HashMap::new())
+}
+
+/// Plays out the dealer's hand card by card, taking each card out of the
+/// shoe as it's drawn. What's left in the shoe tells which cards were drawn,
+/// so it's enough to remember each position by.
+fn dealer_draw(
+    counts: &mut Counts,
+    hard: u8,
+    ace: bool,
+    cards: u8,
+    rules: &StrategyVariables,
+    memo: &mut HashMap<Counts, DealerOutcomes>,
+) -> DealerOutcomes {
+    let total = soft_total(hard, ace);
+    let hits_soft_17 = total == 17 && hard == 7 && !rules.dealer_stands_on_soft_17;
+    if cards >= 2 && total >= 17 && !hits_soft_17 {
+        let mut outcome = DealerOutcomes::default();
+        match total {
+            21 if cards == 2 => outcome.blackjack = 1.0,
+            17..=21 => outcome.totals[total as usize - 17] = 1.0,
+            22 => outcome.twenty_two = 1.0,
+            _ => outcome.bust = 1.0,
+        }
+        return outcome;
+    }
+    if let Some(outcome) = memo.get(counts) {
+        return *outcome;
+    }
+
+    // A peek has already ruled out the hole card that makes a blackjack
+    let ruled_out = match (cards, hard) {
+        (1, 1) if rules.dealer_peak => Some(10),
+        (1, 10) if rules.dealer_peak => Some(1),
+        _ => None,
+    };
+    let remaining: u16 = (1..=10u8)
+        .filter(|value| Some(*value) != ruled_out)
+        .map(|value| counts[index(value)])
+        .sum();
+
+    let mut outcome = DealerOutcomes::default();
+    for value in (1..=10u8).filter(|value| Some(*value) != ruled_out) {
+        let left = counts[index(value)];
+        if left == 0 {
+            continue;
+        }
+        counts[index(value)] -= 1;
+        let next = dealer_draw(counts, hard + value, ace || value == 1, cards + 1, rules, memo);
+        counts[index(value)] += 1;
+        outcome.add(&next, left as f64 / remaining as f64);
+    }
+    memo.insert(*counts, outcome);
+    outcome
+}
+
+/// How the dealer's hand finishes from an upcard dealt off a full shoe. The
+/// dealer's hand plays out the same whether or not they peek, so blackjacks
+/// are always included.
+pub fn dealer_final_totals(rules: &StrategyVariables, upcard: u8) -> DealerOutcomes {
+    let no_peek = StrategyVariables { dealer_peak: false, ..rules.clone() };
+    dealer_outcomes(&shoe_after_upcard(rules, upcard), upcard, &no_peek)
+}
+
+// ---- Player ----
+
+/// Chance that one more card busts a hand, drawn from `counts`. A soft hand
+/// can always count its Ace as 1 instead, so only hard totals can bust.
+pub fn bust_chance(counts: &Counts, total: u8, soft: bool) -> f64 {
+    let cards: u16 = counts.iter().sum();
+    if soft || cards == 0 {
+        return 0.0;
+    }
+    let busting: u16 = (1..=10u8)
+        .filter(|value| total + value > 21)
+        .map(|value| counts[index(value)])
+        .sum();
+    busting as f64 / cards as f64
+}
+
+/// Money riding on a hand in units of the original bet. `paid` leaves out
+/// Free Bet tokens, which are paid on a win but never lost.
+#[derive(Debug, Clone, Copy)]
+struct Stake {
+    bet: f64,
+    paid: f64,
+}
+
+const FULL_STAKE: Stake = Stake { bet: 1.0, paid: 1.0 };
+
+/// Everything the player's choice depends on once the first cards are out:
+/// the chance of each card being drawn next and how the dealer will finish.
+/// The player's own draws use the same chances throughout, which is what
+/// makes the result a total-dependent basic strategy.
+struct Situation<'a> {
+    rules: &'a StrategyVariables,
+    /// Split limits of the table the hand is played at
+    table: &'a TableConfig,
+    draws: [f64; 10],
+    dealer: DealerOutcomes,
+}
+
+impl<'a> Situation<'a> {
+    fn new(rules: &'a StrategyVariables, table: &'a TableConfig, counts: &Counts, upcard: u8) -> Self {
+        let remaining: u16 = counts.iter().sum();
+        let draws = counts.map(|left| left as f64 / remaining as f64);
+        Self { rules, table, draws, dealer: dealer_outcomes(counts, upcard, rules) }
+    }
+
+    fn free_bet(&self) -> bool {
+        self.rules.variant == GameVariant::FreeBet
+    }
+
+    /// Expected value of standing on `total`, where totals over 21 are busts.
+    fn stand_ev(&self, total: u8, stake: Stake) -> f64 {
+        if total > 21 {
+            return -stake.paid;
+        }
+        let dealer = &self.dealer;
+        let (mut win, mut lose) = (dealer.bust, dealer.blackjack);
+        // Free Bet pushes a dealer 22 rather than paying it
+        if !self.free_bet() {
+            win += dealer.twenty_two;
+        }
+        for (dealer_total, chance) in (17u8..).zip(dealer.totals) {
+            if dealer_total < total {
+                win += chance;
+            } else if dealer_total > total {
+                lose += chance;
+            }
+        }
+        stake.bet * win - stake.paid * lose
+    }
+
+    /// Value of playing a hand from each hard total and Ace holding by hitting
+    /// or standing, whichever is better. Totals only go up as cards are drawn,
+    /// so they are worked out from 21 down.
+    fn play_values(&self, stake: Stake) -> [[f64; 2]; 22] {
+        let mut values = [[0.0; 2]; 22];
+        for hard in (2..=21u8).rev() {
+            for ace in [false, true] {
+                let stand = self.stand_ev(soft_total(hard, ace), stake);
+                let hit = self.hit_ev(hard, ace, stake, &values);
+                values[hard as usize][ace as usize] = stand.max(hit);
+            }
+        }
+        values
+    }
+
+    /// Expected value of taking one card then playing on with `values`.
+    fn hit_ev(&self, hard: u8, ace: bool, stake: Stake, values: &[[f64; 2]; 22]) -> f64 {
+        (1..=10u8).map(|value| {
+            let next = hard + value;
+            let result = if next > 21 {
+                -stake.paid
+            } else {
+                values[next as usize][(ace || value == 1) as usize]
+            };
+            self.draws[index(value)] * result
+        }).sum()
+    }
+
+    /// Expected value of doubling, taking one card and standing. Free Bet
+    /// doubles hard 9-11 with tokens.
+    fn double_ev(&self, hard: u8, ace: bool, stake: Stake) -> f64 {
+        let free = self.free_bet() && !ace && (9..=11).contains(&hard);
+        let doubled = Stake {
+            bet: stake.bet * 2.0,
+            paid: if free { stake.paid } else { stake.paid + stake.bet },
+        };
+        (1..=10u8)
+            .map(|value| self.draws[index(value)] * self.stand_ev(soft_total(hard + value, ace || value == 1), doubled))
+            .sum()
+    }
+
+    /// Expected value of splitting a pair. Pairs dealt to the split hands are
+    /// split again up to the table's hand limit, and split Aces take a single
+    /// card each. Free Bet splits every pair but tens with tokens, so only one
+    /// of the hands is played with the player's own money.
+    fn split_ev(&self, value: u8) -> f64 {
+        let limit = if value == 1 && !self.table.resplit_aces { 2 } else { self.table.max_split_hands };
+        let repeat = self.draws[index(value)];
+        let (others, pairs) = split_hands(2, 2, limit, repeat);
+
+        // A hand's value when dealt any other card, and when it's left holding a pair
+        let hand_values = |stake: Stake| {
+            let values = self.play_values(stake);
+            let other: f64 = (1..=10u8)
+                .filter(|drawn| *drawn != value)
+                .map(|drawn| self.draws[index(drawn)] * self.split_hand_ev(value, drawn, stake, &values))
+                .sum();
+            (other / (1.0 - repeat), self.split_hand_ev(value, value, stake, &values))
+        };
+        let (paid_other, paid_pair) = hand_values(FULL_STAKE);
+        if !self.free_bet() || value == 10 {
+            return others * paid_other + pairs * paid_pair;
+        }
+
+        let (free_other, free_pair) = hand_values(Stake { bet: 1.0, paid: 0.0 });
+        let paid_other_share = others / (others + pairs);
+        let paid_pair_share = pairs / (others + pairs);
+        paid_other_share * paid_other + paid_pair_share * paid_pair
+            + (others - paid_other_share) * free_other + (pairs - paid_pair_share) * free_pair
+    }
+
+    /// Value of a split hand once it's been dealt its second card.
+    fn split_hand_ev(&self, value: u8, drawn: u8, stake: Stake, values: &[[f64; 2]; 22]) -> f64 {
+        let hard = value + drawn;
+        let ace = value == 1 || drawn == 1;
+        if value == 1 {
+            self.stand_ev(soft_total(hard, ace), stake)
+        } else if self.rules.double_after_split {
+            values[hard as usize][ace as usize].max(self.double_ev(hard, ace, stake))
+        } else {
+            values[hard as usize][ace as usize]
+        }
+    }
+
+    /// Late surrender, lost in full to a blackjack the dealer didn't peek for.
+    fn surrender_ev(&self) -> f64 {
+        -0.5 * (1.0 - self.dealer.blackjack) - self.dealer.blackjack
+    }
+}
+
+/// Expected number of split hands that end up with a card that doesn't pair
+/// them again, and of hands left holding a pair because `limit` hands are
+/// already in play. `waiting` hands are still to be dealt their second card,
+/// each pairing again with chance `repeat`.
+fn split_hands(hands: u8, waiting: u8, limit: u8, repeat: f64) -> (f64, f64) {
+    if waiting == 0 {
+        return (0.0, 0.0);
+    }
+    let (others, pairs) = split_hands(hands, waiting - 1, limit, repeat);
+    let (split_others, split_pairs) = if hands < limit {
+        split_hands(hands + 1, waiting + 1, limit, repeat)
+    } else {
+        (others, pairs + 1.0)
+    };
+    (
+        (1.0 - repeat) * (others + 1.0) + repeat * split_others,
+        (1.0 - repeat) * pairs + repeat * split_pairs,
+    )
+}
+
+// ---- Chart Cells ----
+
+/// A row of a strategy chart. Pairs are numbered as in the chart, 11 for Aces.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum ChartHand {
+    Hard(u8),
+    Soft(u8),
+    Pair(u8),
+}
+
+impl From<ScenarioHand> for ChartHand {
+    fn from(hand: ScenarioHand) -> Self {
+        match hand {
+            ScenarioHand::Hard(total) => ChartHand::Hard(total),
+            ScenarioHand::Soft(total) => ChartHand::Soft(total),
+            ScenarioHand::Pair(1) => ChartHand::Pair(11),
+            ScenarioHand::Pair(value) => ChartHand::Pair(value),
+        }
+    }
+}
+
+impl fmt::Display for ChartHand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChartHand::Hard(total) => write!(f, "Hard {}", total),
+            ChartHand::Soft(total) => write!(f, "Soft {}", total),
+            ChartHand::Pair(pair) => {
+                let card = card_label(if *pair == 11 { 1 } else { *pair });
+                write!(f, "{},{}", card, card)
+            }
+        }
+    }
+}
+
+/// Expected value of each play for a two card hand, per unit bet.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ActionEvs {
+    pub stand: f64,
+    pub hit: f64,
+    /// Only on the first two cards
+    pub double: Option<f64>,
+    /// Only for pairs
+    pub split: Option<f64>,
+    /// Only where the rules allow surrender against the upcard
+    pub surrender: Option<f64>,
+}
+
+impl ActionEvs {
+    fn add(&mut self, other: &ActionEvs, weight: f64) {
+        let add = |total: Option<f64>, ev: Option<f64>| ev.map(|ev| total.unwrap_or(0.0) + ev * weight);
+        self.stand += other.stand * weight;
+        self.hit += other.hit * weight;
+        self.double = add(self.double, other.double);
+        self.split = add(self.split, other.split);
+        self.surrender = add(self.surrender, other.surrender);
+    }
+
+    /// Expected value of the best play.
+    pub fn best(&self) -> f64 {
+        [self.double, self.split, self.surrender].into_iter()
+            .flatten()
+            .fold(self.hit.max(self.stand), f64::max)
+    }
+
+    /// Expected value of an action, if it can be taken.
+    fn of(&self, action: PlayerAction) -> Option<f64> {
+        match action {
+            PlayerAction::Stand => Some(self.stand),
+            PlayerAction::Hit => Some(self.hit),
+            PlayerAction::Double => self.double,
+            PlayerAction::Split => self.split,
+            PlayerAction::Surrender => self.surrender,
+        }
+    }
+
+    /// Actions that can be taken, to resolve chart codes against.
+    fn available(&self) -> Vec<PlayerAction> {
+        [PlayerAction::Hit, PlayerAction::Stand, PlayerAction::Double, PlayerAction::Split, PlayerAction::Surrender]
+            .into_iter()
+            .filter(|action| self.of(*action).is_some())
+            .collect()
+    }
+
+    /// Chart code for the best play, with the fallback the chart gives for
+    /// doubles and surrenders when they can't be made.
+    pub fn chart_code(&self) -> &'static str {
+        let hits = self.hit > self.stand;
+        let plain = self.hit.max(self.stand);
+        let double = self.double.unwrap_or(f64::NEG_INFINITY);
+        let split = self.split.unwrap_or(f64::NEG_INFINITY);
+        let surrender = self.surrender.unwrap_or(f64::NEG_INFINITY);
+        let best = plain.max(double).max(split).max(surrender);
+
+        if surrender == best {
+            if split > plain { "Rp" } else if hits { "Rh" } else { "Rs" }
+        } else if split == best {
+            "P"
+        } else if double == best {
+            if hits { "Dh" } else { "Ds" }
+        } else if hits {
+            "H"
+        } else {
+            "S"
+        }
+    }
+}
+
+/// Two card hands that make up a chart row, by card value with Aces as 1.
+/// Hard rows leave out pairs unless the total can only be made by one, hard
+/// 21 can't be made from two cards at all and is played without them.
+fn starting_cards(hand: ChartHand) -> Vec<Vec<u8>> {
+    match hand {
+        ChartHand::Hard(total) => {
+            let different: Vec<Vec<u8>> = (2..=10u8)
+                .filter_map(|first| total.checked_sub(first).map(|second| vec![first, second]))
+                .filter(|cards| cards[0] < cards[1] && cards[1] <= 10)
+                .collect();
+            if !different.is_empty() {
+                different
+            } else if total % 2 == 0 && (4..=20).contains(&total) {
+                vec![vec![total / 2, total / 2]]
+            } else {
+                vec![vec![]]
+            }
+        }
+        ChartHand::Soft(total) => vec![vec![1, total - 11]],
+        ChartHand::Pair(11) => vec![vec![1, 1]],
+        ChartHand::Pair(value) => vec![vec![value, value]],
+    }
+}
+
+/// Chance that hitting a hard chart row busts it, from a full shoe less the
+/// hand's own cards, averaged over the two card hands that make up the row.
+pub fn hard_bust_chance(rules: &StrategyVariables, total: u8) -> f64 {
+    let shoe = full_shoe(rules);
+    let mut chance = 0.0;
+    let mut total_weight = 0.0;
+    for cards in starting_cards(ChartHand::Hard(total)) {
+        let weight = deal_weight(&shoe, &cards);
+        let mut counts = shoe;
+        for value in &cards {
+            counts[index(*value)] -= 1;
+        }
+        chance += bust_chance(&counts, total, false) * weight;
+        total_weight += weight;
+    }
+    chance / total_weight
+}
+
+/// Expected value of each play for a chart cell, averaged over the two card
+/// hands that make up the row by how often each is dealt.
+pub fn action_evs(rules: &StrategyVariables, table: &TableConfig, hand: ChartHand, upcard: u8) -> ActionEvs {
+    let shoe = shoe_after_upcard(rules, upcard);
+    let mut total = ActionEvs::default();
+    let mut total_weight = 0.0;
+    for cards in starting_cards(hand) {
+        let hard = match hand {
+            ChartHand::Hard(total) => total,
+            _ => cards.iter().sum(),
+        };
+        let weight = deal_weight(&shoe, &cards);
+        let evs = hand_evs(rules, table, &shoe, &cards, hard, upcard, matches!(hand, ChartHand::Pair(_)));
+        total.add(&evs, weight);
+        total_weight += weight;
+    }
+
+    let mut average = ActionEvs::default();
+    average.add(&total, 1.0 / total_weight);
+    average
+}
+
+/// Why the chart plays a cell the way it does, from the EVs of its plays,
+/// e.g. "8,8 vs 10: split because it loses 47.6% of the bet on average,
+/// where surrender, the next best play, loses 50.0%". When the chart's play
+/// isn't the best under these rules, says so instead.
+pub fn explain_play(hand: ChartHand, upcard: u8, code: &str, evs: &ActionEvs) -> String {
+    let cell = format!("{} vs {}", hand, card_label(upcard));
+    let available = evs.available();
+    let Some(play) = resolve_code(code, &available) else {
+        return format!("{}: the chart's \"{}\" isn't a play that can be made here", cell, code.trim());
+    };
+    let outcome = |ev: f64| match ev {
+        ev if ev > 0.0 => format!("wins {:.1}%", ev * 100.0),
+        ev if ev < 0.0 => format!("loses {:.1}%", -ev * 100.0),
+        _ => "breaks even".to_string(),
+    };
+    let name = |action: PlayerAction| action.to_string().to_lowercase();
+    let play_ev = evs.of(play).unwrap_or(f64::NEG_INFINITY);
+    let mut others: Vec<(PlayerAction, f64)> = available.into_iter()
+        .filter(|action| *action != play)
+        .filter_map(|action| evs.of(action).map(|ev| (action, ev)))
+        .collect();
+    others.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+    match others.first() {
+        Some((best, best_ev)) if *best_ev > play_ev => format!(
+            "{}: the chart says {}, which {} of the bet on average, but {} {} under these rules",
+            cell, name(play), outcome(play_ev), name(*best), outcome(*best_ev),
+        ),
+        Some((next, next_ev)) => format!(
+            "{}: {} because it {} of the bet on average, where {}, the next best play, {}",
+            cell, name(play), outcome(play_ev), name(*next), outcome(*next_ev),
+        ),
+        None => format!("{}: {}, the only play there is", cell, name(play)),
+    }
+}
+
+fn shoe_after_upcard(rules: &StrategyVariables, upcard: u8) -> Counts {
+    let mut shoe = full_shoe(rules);
+    shoe[index(upcard)] -= 1;
+    shoe
+}
+
+/// How many ways `cards` can be dealt from the shoe, zero if they can't.
+fn deal_weight(shoe: &Counts, cards: &[u8]) -> f64 {
+    let mut counts = *shoe;
+    let mut weight = 1.0;
+    for value in cards {
+        weight *= counts[index(*value)] as f64;
+        counts[index(*value)] = counts[index(*value)].saturating_sub(1);
+    }
+    weight
+}
+
+/// Expected value of each play for a hand of `cards` totalling `hard`, Aces
+/// as 1, dealt from a shoe the upcard has already left. Doubles and
+/// surrenders are only offered on the first two cards.
+fn hand_evs(rules: &StrategyVariables, table: &TableConfig, shoe: &Counts, cards: &[u8], hard: u8, upcard: u8, can_split: bool) -> ActionEvs {
+    let mut counts = *shoe;
+    for value in cards {
+        counts[index(*value)] -= 1;
+    }
+    let situation = Situation::new(rules, table, &counts, upcard);
+    let ace = cards.contains(&1);
+    let first_cards = cards.len() <= 2;
+    let surrender_allowed = match rules.surrender_allowed {
+        SurrenderRule::NotAllowed => false,
+        SurrenderRule::AnyUpcard => true,
+        SurrenderRule::Dealer2Through10 => upcard != 1,
+    };
+
+    let values = situation.play_values(FULL_STAKE);
+    ActionEvs {
+        stand: situation.stand_ev(soft_total(hard, ace), FULL_STAKE),
+        hit: situation.hit_ev(hard, ace, FULL_STAKE, &values),
+        double: first_cards.then(|| situation.double_ev(hard, ace, FULL_STAKE)),
+        split: can_split.then(|| situation.split_ev(cards[0])),
+        surrender: (first_cards && surrender_allowed).then(|| situation.surrender_ev()),
+    }
+}
+
+/// Works out the basic strategy chart for any set of rules.
+pub fn generate_strategy(rules: &StrategyVariables, table: &TableConfig) -> BlackjackStrategy {
+    let actions = |hand: ChartHand| -> Vec<String> {
+        CHART_UPCARDS.iter()
+            .map(|upcard| action_evs(rules, table, hand, *upcard).chart_code().to_string())
+            .collect()
+    };
+    let tables = StrategyTables {
+        hard_hands: (5..=21).map(|total| HardHandRow { total, actions: actions(ChartHand::Hard(total)) }).collect(),
+        soft_hands: (13..=21).map(|total| SoftHandRow { total, actions: actions(ChartHand::Soft(total)) }).collect(),
+        pair_hands: (2..=11).map(|pair| PairRow { pair, actions: actions(ChartHand::Pair(pair)) }).collect(),
+    };
+
+    // Only explain the codes the chart uses
+    let used = |code: &str| {
+        tables.hard_hands.iter().map(|row| &row.actions)
+            .chain(tables.soft_hands.iter().map(|row| &row.actions))
+            .chain(tables.pair_hands.iter().map(|row| &row.actions))
+            .any(|actions| actions.iter().any(|action| action == code))
+    };
+    let action_legend = LEGEND.iter()
+        .filter(|(code, _)| used(code))
+        .map(|(code, description)| (code.to_string(), LegendEntry::from(*description)))
+        .collect();
+
+    BlackjackStrategy {
+        name: "Computed Strategy".to_string(),
+        description: format!(
+            "Basic strategy computed from the rules, splitting to {} hands{}",
+            table.max_split_hands,
+            if table.resplit_aces { " with Aces resplit" } else { "" },
+        ),
+        rules: rules.clone(),
+        tables,
+        action_legend,
+        ..BlackjackStrategy::new()
+    }
+}
+
+// ---- House Edge ----
+
+/// The house's edge over a player who plays every starting hand its best
+/// way and never takes insurance, as a share of the initial bet.
+pub fn house_edge(rules: &StrategyVariables, table: &TableConfig) -> f64 {
+    house_edge_paying(rules, table, 1.5)
+}
+
+/// House edge with naturals paying `blackjack_pays` to 1.
+fn house_edge_paying(rules: &StrategyVariables, table: &TableConfig, blackjack_pays: f64) -> f64 {
+    let full = full_shoe(rules);
+    let cards: u16 = full.iter().sum();
+    let mut player_ev = 0.0;
+    for upcard in 1..=10u8 {
+        let shoe = shoe_after_upcard(rules, upcard);
+        let mut upcard_ev = 0.0;
+        let mut total_weight = 0.0;
+        for first in 1..=10u8 {
+            for second in first..=10u8 {
+                // Two different cards can come in either order
+                let orders = if first == second { 1.0 } else { 2.0 };
+                let weight = deal_weight(&shoe, &[first, second]) * orders;
+                if weight > 0.0 {
+                    upcard_ev += weight * starting_hand_ev(rules, table, &shoe, [first, second], upcard, blackjack_pays);
+                    total_weight += weight;
+                }
+            }
+        }
+        player_ev += full[index(upcard)] as f64 / cards as f64 * upcard_ev / total_weight;
+    }
+    -player_ev
+}
+
+/// Expected value of a starting hand against the upcard, counting the
+/// dealer's chance of turning over a blackjack. With a peek that blackjack
+/// takes only the original bet, without one it's part of how the dealer
+/// finishes and can take doubles and splits too.
+fn starting_hand_ev(rules: &StrategyVariables, table: &TableConfig, shoe: &Counts, cards: [u8; 2], upcard: u8, blackjack_pays: f64) -> f64 {
+    let mut counts = *shoe;
+    for value in cards {
+        counts[index(value)] -= 1;
+    }
+    let remaining: u16 = counts.iter().sum();
+    let dealer_blackjack = match upcard {
+        1 => counts[index(10)] as f64 / remaining as f64,
+        10 => counts[index(1)] as f64 / remaining as f64,
+        _ => 0.0,
+    };
+
+    // A natural is paid unless the dealer has one too
+    if cards.contains(&1) && cards.contains(&10) {
+        return blackjack_pays * (1.0 - dealer_blackjack);
+    }
+    let best = hand_evs(rules, table, shoe, &cards, cards[0] + cards[1], upcard, cards[0] == cards[1]).best();
+    if rules.dealer_peak {
+        (1.0 - dealer_blackjack) * best - dealer_blackjack
+    } else {
+        best
+    }
+}
+
+// ---- Rule Impact ----
+
+/// How much changing one rule away from a baseline moves the house edge.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuleImpact {
+    /// The rule as the baseline has it, e.g. "Dealer Stands on Soft 17"
+    pub baseline: String,
+    /// The rule it's changed to, e.g. "Dealer Hits Soft 17"
+    pub change: String,
+    /// Change in house edge, positive when it's worse for the player
+    pub edge_change: f64,
+}
+
+/// The effect of changing each rule on its own, one row for every other
+/// setting it could have, from the soft 17 rule through to the number of decks.
+pub fn rule_impacts(rules: &StrategyVariables, table: &TableConfig) -> Vec<RuleImpact> {
+    let baseline = house_edge(rules, table);
+    let mut impacts = Vec::new();
+    let mut compare = |from: String, to: String, edge: f64| {
+        impacts.push(RuleImpact { baseline: from, change: to, edge_change: edge - baseline });
+    };
+    let soft_17 = |stands: bool| if stands { "Dealer Stands on Soft 17" } else { "Dealer Hits Soft 17" }.to_string();
+    let das = |allowed: bool| if allowed { "Double After Split" } else { "No Double After Split" }.to_string();
+    let peek = |peeks: bool| if peeks { "Dealer Peeks" } else { "No Hole Card (ENHC)" }.to_string();
+    let surrender = |rule: SurrenderRule| format!("Surrender: {}", rule.to_string());
+    let decks = |decks: u8| format!("{} Deck{}", decks, if decks == 1 { "" } else { "s" });
+
+    let changed = StrategyVariables { dealer_stands_on_soft_17: !rules.dealer_stands_on_soft_17, ..rules.clone() };
+    compare(soft_17(rules.dealer_stands_on_soft_17), soft_17(changed.dealer_stands_on_soft_17), house_edge(&changed, table));
+
+    let changed = StrategyVariables { double_after_split: !rules.double_after_split, ..rules.clone() };
+    compare(das(rules.double_after_split), das(changed.double_after_split), house_edge(&changed, table));
+
+    let changed = StrategyVariables { dealer_peak: !rules.dealer_peak, ..rules.clone() };
+    compare(peek(rules.dealer_peak), peek(changed.dealer_peak), house_edge(&changed, table));
+
+    for rule in [SurrenderRule::NotAllowed, SurrenderRule::AnyUpcard, SurrenderRule::Dealer2Through10] {
+        if rule != rules.surrender_allowed {
+            let changed = StrategyVariables { surrender_allowed: rule, ..rules.clone() };
+            compare(surrender(rules.surrender_allowed), surrender(rule), house_edge(&changed, table));
+        }
+    }
+
+    compare("Blackjack Pays 3:2".to_string(), "Blackjack Pays 6:5".to_string(), house_edge_paying(rules, table, 1.2));
+
+    for count in DECK_OPTIONS {
+        if count != rules.decks {
+            let changed = StrategyVariables { decks: count, ..rules.clone() };
+            compare(decks(rules.decks), decks(count), house_edge(&changed, table));
+        }
+    }
+    impacts
+}
+
+// ---- Strategy Comparison ----
+
+/// A chart cell two strategies play differently.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CellDifference {
+    pub hand: ChartHand,
+    pub upcard: u8,
+    pub first: PlayerAction,
+    pub second: PlayerAction,
+    /// How much more the first strategy's play is worth when the hand comes up
+    pub ev_difference: f64,
+    /// Chance of the hand being dealt against the upcard
+    pub frequency: f64,
+}
+
+/// How two strategies compare under one set of rules.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StrategyComparison {
+    /// Cells that play differently, those that matter most first
+    pub differences: Vec<CellDifference>,
+    /// How much more the first strategy is worth per hand dealt
+    pub ev_difference: f64,
+}
+
+/// Compares two strategies cell by cell. Only the play each makes on its
+/// first two cards is compared, with both playing on as well as they can
+/// from there, so charts that only differ past the first decision compare
+/// the same.
+pub fn compare_strategies(rules: &StrategyVariables, table: &TableConfig, first: &BlackjackStrategy, second: &BlackjackStrategy) -> StrategyComparison {
+    let (first, second) = (&first.tables, &second.tables);
+    let mut cells: Vec<(ChartHand, &Vec<String>, &Vec<String>)> = Vec::new();
+    for row in &first.hard_hands {
+        if let Some(other) = second.hard_hands.iter().find(|other| other.total == row.total) {
+            cells.push((ChartHand::Hard(row.total), &row.actions, &other.actions));
+        }
+    }
+    for row in &first.soft_hands {
+        if let Some(other) = second.soft_hands.iter().find(|other| other.total == row.total) {
+            cells.push((ChartHand::Soft(row.total), &row.actions, &other.actions));
+        }
+    }
+    for row in &first.pair_hands {
+        if let Some(other) = second.pair_hands.iter().find(|other| other.pair == row.pair) {
+            cells.push((ChartHand::Pair(row.pair), &row.actions, &other.actions));
+        }
+    }
+
+    let mut differences = Vec::new();
+    for (hand, first_codes, second_codes) in cells {
+        for (column, upcard) in CHART_UPCARDS.iter().enumerate() {
+            let (Some(first_code), Some(second_code)) = (first_codes.get(column), second_codes.get(column)) else {
+                continue;
+            };
+            let frequency = first_decision_frequency(rules, hand, *upcard);
+            if first_code.trim() == second_code.trim() || frequency == 0.0 {
+                continue;
+            }
+
+            // Codes that fall back to the same play under these rules don't differ
+            let evs = action_evs(rules, table, hand, *upcard);
+            let available = evs.available();
+            let plays = (resolve_code(first_code, &available), resolve_code(second_code, &available));
+            if let (Some(first), Some(second)) = plays
+                && first != second
+                && let (Some(first_ev), Some(second_ev)) = (evs.of(first), evs.of(second))
+            {
+                differences.push(CellDifference { hand, upcard: *upcard, first, second, ev_difference: first_ev - second_ev, frequency });
+            }
+        }
+    }
+
+    let weighted = |difference: &CellDifference| difference.ev_difference * difference.frequency;
+    differences.sort_by(|a, b| weighted(b).abs().total_cmp(&weighted(a).abs()));
+    StrategyComparison { ev_difference: differences.iter().map(weighted).sum(), differences }
+}
+
+/// Chance of a round starting with the hand against the upcard. Hard rows
+/// only count hands that aren't pairs, which are played from the pair table,
+/// and a soft 21 is a natural with nothing to decide.
+fn first_decision_frequency(rules: &StrategyVariables, hand: ChartHand, upcard: u8) -> f64 {
+    let full = full_shoe(rules);
+    let cards = full.iter().sum::<u16>() as f64;
+    let shoe = shoe_after_upcard(rules, upcard);
+    let hands: Vec<Vec<u8>> = match hand {
+        ChartHand::Soft(21) => Vec::new(),
+        _ => starting_cards(hand),
+    };
+    let dealt: f64 = hands.iter()
+        .filter(|cards| cards.len() == 2 && (cards[0] != cards[1] || matches!(hand, ChartHand::Pair(_))))
+        .map(|cards| deal_weight(&shoe, cards) * if cards[0] == cards[1] { 1.0 } else { 2.0 })
+        .sum();
+    full[index(upcard)] as f64 / cards * dealt / ((cards - 1.0) * (cards - 2.0))
+}
+
+// ---- Insurance ----
+
+/// Cards of each value in a single deck.
+const ONE_DECK: [f64; 10] = [4.0, 4.0, 4.0, 4.0, 4.0, 4.0, 4.0, 4.0, 4.0, 16.0];
+
+/// How far the cards of a deck move the count between them, each card's tag squared.
+fn tag_spread(system: &dyn CountingSystem) -> f64 {
+    Rank::ALL.iter().map(|rank| (system.tag(*rank) as f64).powi(2) * 4.0).sum()
+}
+
+/// Share of tens left in the shoe at a true count for a counting system. The
+/// count is taken to have come from each card value in proportion to its tag,
+/// so a card that moves the count twice as far is twice as over or under
+/// represented in what's left.
+pub fn ten_share(system: &dyn CountingSystem, true_count: f64) -> f64 {
+    let ten_tag = system.tag(Rank::Ten) as f64;
+    ONE_DECK[index(10)] * (1.0 - true_count * ten_tag / tag_spread(system)) / 52.0
+}
+
+/// Expected value of an insurance bet per unit staked at a true count. It
+/// pays 2:1 when the dealer's hole card is a ten.
+pub fn insurance_ev(system: &dyn CountingSystem, true_count: f64) -> f64 {
+    3.0 * ten_share(system, true_count) - 1.0
+}
+
+/// True count from which insurance is worth taking, where a third of the
+/// shoe is tens. None for a system that doesn't count tens.
+pub fn insurance_break_even(system: &dyn CountingSystem) -> Option<f64> {
+    let ten_tag = system.tag(Rank::Ten) as f64;
+    (ten_tag != 0.0).then(|| -tag_spread(system) / (12.0 * ten_tag))
+}
+
+// ---- Composition Exceptions ----
+
+/// A hand whose exact cards call for a different play than its chart row.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompositionException {
+    pub hand: ExceptionHand,
+    pub upcard: u8,
+    /// What the chart row says to do with the hand
+    pub chart: &'static str,
+    /// What the cards in the hand say to do
+    pub play: &'static str,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExceptionHand {
+    /// A particular hand, by card value with Aces as 1
+    Cards(Vec<u8>),
+    /// Hands of `cards` or more cards making a hard total
+    MultiCard { total: u8, cards: usize },
+}
+
+/// Hard totals where hands of three or more cards are checked, lower totals
+/// always hit and higher ones always stand.
+const MULTI_CARD_TOTALS: std::ops::RangeInclusive<u8> = 12..=16;
+
+/// Finds the hard hands that play differently from their chart row once the
+/// exact cards are taken into account: two card hands with a different play
+/// than the rest of their row, and totals that should hit or stand against
+/// the chart once they're made of three (or failing that four) cards. Soft
+/// hands and pairs are left out, each of their rows is a single two card hand.
+pub fn composition_exceptions(rules: &StrategyVariables, table: &TableConfig) -> Vec<CompositionException> {
+    let mut exceptions = Vec::new();
+    for upcard in CHART_UPCARDS {
+        let shoe = shoe_after_upcard(rules, upcard);
+        for total in 5..=20 {
+            let row = action_evs(rules, table, ChartHand::Hard(total), upcard).chart_code();
+            let mut exception = |hand: ExceptionHand, chart: &'static str, play: &'static str| {
+                exceptions.push(CompositionException { hand, upcard, chart, play });
+            };
+
+            let two_cards = starting_cards(ChartHand::Hard(total));
+            if two_cards.len() > 1 {
+                for cards in two_cards {
+                    let play = action_name(hand_evs(rules, table, &shoe, &cards, total, upcard, false).chart_code());
+                    if play != action_name(row) {
+                        exception(ExceptionHand::Cards(cards), action_name(row), play);
+                    }
+                }
+            }
+
+            if MULTI_CARD_TOTALS.contains(&total) {
+                // Later cards can only hit or stand, so compare with the chart's fallback
+                let chart = if code_hits(row) { "Hit" } else { "Stand" };
+                let differing = [3, 4].into_iter()
+                    .map(|cards| (cards, multi_card_play(rules, table, &shoe, total, cards, upcard)))
+                    .find(|(_, play)| *play != chart);
+                if let Some((cards, play)) = differing {
+                    exception(ExceptionHand::MultiCard { total, cards }, chart, play);
+                }
+            }
+        }
+    }
+    exceptions
+}
+
+/// Whether hands of `count` cards making a hard total should hit or stand,
+/// taking each way of making the total by how likely it is to be dealt.
+fn multi_card_play(rules: &StrategyVariables, table: &TableConfig, shoe: &Counts, total: u8, count: usize, upcard: u8) -> &'static str {
+    let gain: f64 = hands_of(count, total, 1).iter()
+        .map(|cards| (cards, deal_weight(shoe, cards)))
+        .filter(|(_, weight)| *weight > 0.0)
+        .map(|(cards, weight)| {
+            let evs = hand_evs(rules, table, shoe, cards, total, upcard, false);
+            (evs.hit - evs.stand) * weight
+        })
+        .sum();
+    if gain > 0.0 { "Hit" } else { "Stand" }
+}
+
+/// Whether a chart code hits once its preferred play is no longer allowed.
+fn code_hits(code: &str) -> bool {
+    matches!(code, "H" | "Dh" | "Rh")
+}
+
+/// Every hand of `count` cards, Aces as 1 and no lower than `lowest`, that
+/// adds up to `total`, each listed once with its cards in order.
+fn hands_of(count: usize, total: u8, lowest: u8) -> Vec<Vec<u8>> {
+    if count == 0 {
+        return if total == 0 { vec![vec![]] } else { vec![] };
+    }
+    (lowest..=10u8.min(total))
+        .flat_map(|card| {
+            hands_of(count - 1, total - card, card).into_iter().map(move |mut rest| {
+                rest.insert(0, card);
+                rest
+            })
+        })
+        .collect()
+}
+
+/// The play a chart code stands for, ignoring its fallback.
+fn action_name(code: &str) -> &'static str {
+    match code {
+        "H" => "Hit",
+        "S" => "Stand",
+        "D" | "Dh" | "Ds" => "Double",
+        "P" => "Split",
+        _ => "Surrender",
+    }
+}
+
+pub fn card_label(value: u8) -> String {
+    if value == 1 { "A".to_string() } else { value.to_string() }
+}
+
+impl fmt::Display for CompositionException {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.hand {
+            ExceptionHand::Cards(cards) => {
+                let cards: Vec<String> = cards.iter().map(|card| card_label(*card)).collect();
+                write!(f, "{}", cards.join(","))?;
+            }
+            ExceptionHand::MultiCard { total, cards } => write!(f, "{}+ card {}", cards, total)?,
+        }
+        write!(f, " vs {}: {}, not {}", card_label(self.upcard), self.play, self.chart)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logic::counting::HI_LO;
+    use crate::logic::game::card::{Rank, Suit};
+
+    fn rules() -> StrategyVariables {
+        StrategyVariables {
+            decks: 6,
+            dealer_stands_on_soft_17: true,
+            double_after_split: true,
+            dealer_peak: true,
+            surrender_allowed: SurrenderRule::NotAllowed,
+            variant: GameVariant::Classic,
+        }
+    }
+
+    fn table() -> TableConfig {
+        TableConfig::default()
+    }
+
+    #[test]
+    fn test_dealer_outcomes() {
+        let rules = rules();
+        let mut counts = full_shoe(&rules);
+        counts[index(6)] -= 1;
+        let six = dealer_outcomes(&counts, 6, &rules);
+        let total = six.totals.iter().sum::<f64>() + six.blackjack + six.twenty_two + six.bust;
+        assert!((total - 1.0).abs() < 1e-9);
+        assert!((six.busts() - 0.42).abs() < 0.01);
+
+        // A peek rules out blackjack, without one it's about a third of Aces
+        let mut counts = full_shoe(&rules);
+        counts[index(1)] -= 1;
+        assert_eq!(dealer_outcomes(&counts, 1, &rules).blackjack, 0.0);
+        let no_peek = StrategyVariables { dealer_peak: false, ..rules.clone() };
+        assert!((dealer_outcomes(&counts, 1, &no_peek).blackjack - 0.31).abs() < 0.01);
+
+        // Final totals include the blackjacks a peek would have ended early
+        let ace = dealer_final_totals(&rules, 1);
+        assert_eq!(ace, dealer_outcomes(&counts, 1, &no_peek));
+        let hits_soft_17 = StrategyVariables { dealer_stands_on_soft_17: false, ..rules.clone() };
+        assert!(dealer_final_totals(&hits_soft_17, 1).totals[0] < ace.totals[0]);
+        assert!((dealer_final_totals(&rules, 6).busts() - six.busts()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_bust_chance() {
+        let shoe = full_shoe(&rules());
+        assert_eq!(bust_chance(&shoe, 11, false), 0.0);
+        assert!((bust_chance(&shoe, 12, false) - 4.0 / 13.0).abs() < 1e-9);
+        assert!((bust_chance(&shoe, 16, false) - 8.0 / 13.0).abs() < 1e-9);
+        assert_eq!(bust_chance(&shoe, 16, true), 0.0);
+
+        // Most hard 12s are made without a ten, leaving a single deck richer in them
+        let one_deck = StrategyVariables { decks: 1, ..rules() };
+        assert!(hard_bust_chance(&one_deck, 12) > hard_bust_chance(&rules(), 12));
+        assert_eq!(hard_bust_chance(&one_deck, 21), 1.0);
+
+        // Only tens left to come
+        let tens = counts_of(&[Card::new(Rank::King, Suit::Clubs), Card::new(Rank::Ten, Suit::Hearts)]);
+        assert_eq!(bust_chance(&tens, 12, false), 1.0);
+    }
+
+    #[test]
+    fn test_house_edge() {
+        // Six decks, S17, DAS, no surrender is about 0.40%
+        let edge = house_edge(&rules(), &table());
+        assert!((edge - 0.0040).abs() < 0.0005);
+        let hits_soft_17 = StrategyVariables { dealer_stands_on_soft_17: false, ..rules() };
+        assert!(house_edge(&hits_soft_17, &table()) > edge);
+
+        // 6:5 blackjack costs the player about 1.4%
+        let impacts = rule_impacts(&rules(), &table());
+        let payout = impacts.iter().find(|impact| impact.change == "Blackjack Pays 6:5").unwrap();
+        assert!((payout.edge_change - 0.014).abs() < 0.001);
+        let soft_17 = &impacts[0];
+        assert_eq!(soft_17.change, "Dealer Hits Soft 17");
+        assert!((soft_17.edge_change - (house_edge(&hits_soft_17, &table()) - edge)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_insurance_break_even() {
+        let hi_lo = &HI_LO;
+        assert!((insurance_ev(hi_lo, 0.0) + 1.0 / 13.0).abs() < 1e-9);
+        let break_even = insurance_break_even(hi_lo).unwrap();
+        assert!((break_even - 10.0 / 3.0).abs() < 1e-9);
+        assert!(insurance_ev(hi_lo, break_even).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_compare_strategies() {
+        let rules = rules();
+        let computed = generate_strategy(&rules, &table());
+        let same = compare_strategies(&rules, &table(), &computed, &computed);
+        assert!(same.differences.is_empty());
+        assert_eq!(same.ev_difference, 0.0);
+
+        // Standing on 16 vs 10 instead of hitting costs a little per hand dealt
+        let mut stands = computed.clone();
+        stands.tables.hard_hands.iter_mut().find(|row| row.total == 16).unwrap().actions[8] = "S".to_string();
+        let comparison = compare_strategies(&rules, &table(), &computed, &stands);
+        assert_eq!(comparison.differences.len(), 1);
+        let difference = &comparison.differences[0];
+        assert_eq!((difference.hand, difference.upcard), (ChartHand::Hard(16), 10));
+        assert_eq!((difference.first, difference.second), (PlayerAction::Hit, PlayerAction::Stand));
+        assert!(comparison.ev_difference > 0.0 && comparison.ev_difference < 0.001);
+
+        // Every first decision, plus the naturals, covers every round
+        let mut total = 0.0;
+        for upcard in CHART_UPCARDS {
+            let rows = (5..=21).map(ChartHand::Hard)
+                .chain((13..=21).map(ChartHand::Soft))
+                .chain((2..=11).map(ChartHand::Pair));
+            total += rows.map(|hand| first_decision_frequency(&rules, hand, upcard)).sum::<f64>();
+            let shoe = shoe_after_upcard(&rules, upcard);
+            let cards = full_shoe(&rules).iter().sum::<u16>() as f64;
+            let upcard_chance = full_shoe(&rules)[index(upcard)] as f64 / cards;
+            total += upcard_chance * 2.0 * deal_weight(&shoe, &[1, 10]) / ((cards - 1.0) * (cards - 2.0));
+        }
+        assert!((total - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_explain_play() {
+        let rules = rules();
+        let evs = action_evs(&rules, &table(), ChartHand::Pair(8), 10);
+        let explanation = explain_play(ChartHand::Pair(8), 10, "P", &evs);
+        assert!(explanation.starts_with("8,8 vs 10: split because it loses"), "{}", explanation);
+        assert!(explanation.contains("where hit, the next best play, loses"), "{}", explanation);
+
+        // A chart that stands on 16 vs 10 is told hitting does better
+        let evs = action_evs(&rules, &table(), ChartHand::Hard(16), 10);
+        let explanation = explain_play(ChartHand::Hard(16), 10, "S", &evs);
+        assert!(explanation.starts_with("Hard 16 vs 10: the chart says stand"), "{}", explanation);
+        assert!(explanation.ends_with("under these rules") && explanation.contains("but hit loses"), "{}", explanation);
+    }
+
+    #[test]
+    fn test_split_ev_follows_the_tables_split_rules() {
+        let split = |table: &TableConfig, pair: u8| action_evs(&rules(), table, ChartHand::Pair(pair), 6).split.unwrap();
+        let one_split = TableConfig { max_split_hands: 2, ..table() };
+        let resplit_aces = TableConfig { resplit_aces: true, ..table() };
+
+        // Resplitting is worth something, more so for Aces where the table allows it
+        assert!(split(&table(), 8) > split(&one_split, 8));
+        assert!(split(&resplit_aces, 1) > split(&table(), 1));
+        assert_eq!(split(&one_split, 1), split(&table(), 1));
+    }
+
+    #[test]
+    fn test_generated_strategy_plays() {
+        let code = |strategy: &BlackjackStrategy, hand: ChartHand, upcard: u8| {
+            let column = CHART_UPCARDS.iter().position(|card| *card == upcard).unwrap();
+            let tables = &strategy.tables;
+            match hand {
+                ChartHand::Hard(total) => tables.hard_hands.iter().find(|row| row.total == total).unwrap().actions[column].clone(),
+                ChartHand::Soft(total) => tables.soft_hands.iter().find(|row| row.total == total).unwrap().actions[column].clone(),
+                ChartHand::Pair(pair) => tables.pair_hands.iter().find(|row| row.pair == pair).unwrap().actions[column].clone(),
+            }
+        };
+
+        let strategy = generate_strategy(&rules(), &table());
+        assert_eq!(code(&strategy, ChartHand::Hard(16), 10), "H");
+        assert_eq!(code(&strategy, ChartHand::Hard(12), 4), "S");
+        assert_eq!(code(&strategy, ChartHand::Hard(11), 6), "Dh");
+        assert_eq!(code(&strategy, ChartHand::Soft(18), 3), "Ds");
+        assert_eq!(code(&strategy, ChartHand::Soft(18), 9), "H");
+        assert_eq!(code(&strategy, ChartHand::Pair(8), 10), "P");
+        assert_eq!(code(&strategy, ChartHand::Pair(10), 6), "S");
+        assert_eq!(code(&strategy, ChartHand::Pair(11), 1), "P");
+        assert_eq!(strategy.action_legend["Dh"].description, "Double if allowed, else Hit");
+        assert!(!strategy.action_legend.contains_key("Rh"));
+
+        let surrender = generate_strategy(&StrategyVariables { surrender_allowed: SurrenderRule::AnyUpcard, ..rules() }, &table());
+        assert_eq!(code(&surrender, ChartHand::Hard(16), 10), "Rh");
+
+        // Free Bet's tokens make every hard 9-11 a double
+        let free_bet = generate_strategy(&StrategyVariables { variant: GameVariant::FreeBet, ..rules() }, &table());
+        assert_eq!(code(&free_bet, ChartHand::Hard(9), 10), "Dh");
+        assert_eq!(code(&free_bet, ChartHand::Pair(8), 10), "P");
+    }
+
+    #[test]
+    fn test_composition_exceptions() {
+        // The best known exception: a 16 of three or more cards stands on a 10
+        let exceptions: Vec<String> = composition_exceptions(&rules(), &table()).iter().map(|exception| exception.to_string()).collect();
+        assert_eq!(exceptions, vec!["3+ card 16 vs 10: Stand, not Hit"]);
+
+        // A single deck has more, e.g. 10,2 hits a 4 where 12 otherwise stands
+        let single_deck = composition_exceptions(&StrategyVariables { decks: 1, ..rules() }, &table());
+        assert!(single_deck.contains(&CompositionException {
+            hand: ExceptionHand::Cards(vec![2, 10]),
+            upcard: 4,
+            chart: "Stand",
+            play: "Hit",
+        }));
+    }
+}
@@ -0,0 +1,450 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::logic::ev::{card_label, ChartHand, CHART_UPCARDS};
+use crate::logic::strategy_calculator_logic::BlackjackStrategy;
+use crate::ui::get_action_color;
+use ratatui::style::Color;
+use resvg::{tiny_skia, usvg};
+use svg::node::element::{Rectangle, Text};
+use svg::Document;
+
+/// A hand in a chart and its chart code against each upcard.
+type ChartRow<'a> = (ChartHand, &'a [String]);
+
+/// Each of a strategy's tables by name, as its rows of hands and chart codes.
+fn chart_tables(strategy: &BlackjackStrategy) -> [(&'static str, Vec<ChartRow<'_>>); 3] {
+    let tables = &strategy.tables;
+    [
+        ("hard", tables.hard_hands.iter().map(|row| (ChartHand::Hard(row.total), row.actions.as_slice())).collect()),
+        ("soft", tables.soft_hands.iter().map(|row| (ChartHand::Soft(row.total), row.actions.as_slice())).collect()),
+        ("pairs", tables.pair_hands.iter().map(|row| (ChartHand::Pair(row.pair), row.actions.as_slice())).collect()),
+    ]
+}
+
+/// Table titles, as the calculator shows them.
+fn table_title(table: &str) -> &'static str {
+    match table {
+        "hard" => "Hard Hands",
+        "soft" => "Soft Hands",
+        _ => "Pairs",
+    }
+}
+
+/// Turns a strategy name into something safe to use in a file name.
+pub fn file_stem(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '+' { c } else { '-' })
+        .collect()
+}
+
+// ---- CSV ----
+
+/// Quotes a field when it would otherwise split into two, e.g. "A,A".
+pub fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Each table as CSV, a row per hand and a column per dealer upcard.
+pub fn chart_csv(strategy: &BlackjackStrategy) -> Vec<(&'static str, String)> {
+    let header = std::iter::once("Hand".to_string())
+        .chain(CHART_UPCARDS.iter().map(|upcard| card_label(*upcard)))
+        .collect::<Vec<_>>()
+        .join(",");
+
+    chart_tables(strategy).into_iter().map(|(table, rows)| {
+        let mut csv = header.clone();
+        csv.push('\n');
+        for (hand, actions) in rows {
+            let fields = std::iter::once(hand.to_string())
+                .chain(actions.iter().cloned())
+                .map(|field| csv_field(&field))
+                .collect::<Vec<_>>();
+            csv.push_str(&fields.join(","));
+            csv.push('\n');
+        }
+        (table, csv)
+    }).collect()
+}
+
+/// Writes the strategy's tables to `<name>_hard.csv`, `<name>_soft.csv` and
+/// `<name>_pairs.csv` in `dir`, returning the files written.
+pub fn export_csv(strategy: &BlackjackStrategy, name: &str, dir: &Path) -> Result<Vec<PathBuf>, String> {
+    fs::create_dir_all(dir).map_err(|err| format!("Couldn't create {}: {}", dir.display(), err))?;
+    chart_csv(strategy).into_iter().map(|(table, csv)| {
+        let path = dir.join(format!("{}_{}.csv", file_stem(name), table));
+        fs::write(&path, csv).map_err(|err| format!("Couldn't write {}: {}", path.display(), err))?;
+        Ok(path)
+    }).collect()
+}
+
+// ---- Text ----
+
+/// A chart row on one line for pasting into notes, e.g. "Hard 16: 2 S, 3 S, ..., A H".
+pub fn row_text(hand: ChartHand, actions: &[String]) -> String {
+    let cells: Vec<String> = CHART_UPCARDS.iter().zip(actions)
+        .map(|(upcard, code)| format!("{} {}", card_label(*upcard), code.trim()))
+        .collect();
+    format!("{}: {}", hand, cells.join(", "))
+}
+
+/// One cell with what its code means, e.g. "Hard 16 vs 10: H (Hit)".
+pub fn cell_text(strategy: &BlackjackStrategy, hand: ChartHand, upcard: u8, code: &str) -> String {
+    let code = code.trim();
+    match strategy.action_legend.get(code) {
+        Some(entry) => format!("{} vs {}: {} ({})", hand, card_label(upcard), code, entry.description),
+        None => format!("{} vs {}: {}", hand, card_label(upcard), code),
+    }
+}
+
+/// The whole chart as plain text laid out like the calculator's tables, one
+/// table under another, with the rules above and the legend below.
+pub fn chart_text(strategy: &BlackjackStrategy) -> String {
+    let mut text = format!("{}\n{}\n", strategy.name, strategy.rules);
+    for (table, rows) in chart_tables(strategy) {
+        let width = rows.iter().map(|(hand, _)| hand.to_string().len()).max().unwrap_or(0);
+        text.push_str(&format!("\n{}\n{:width$}", table_title(table), ""));
+        for upcard in CHART_UPCARDS {
+            text.push_str(&format!(" {:<3}", card_label(upcard)));
+        }
+        for (hand, actions) in rows {
+            text.push_str(&format!("\n{:width$}", hand.to_string()));
+            for code in actions {
+                text.push_str(&format!(" {:<3}", code.trim()));
+            }
+        }
+        text.push('\n');
+    }
+
+    let mut legend: Vec<(&String, &String)> = strategy.action_legend.iter().map(|(code, entry)| (code, &entry.description)).collect();
+    legend.sort_by(|a, b| a.0.cmp(b.0));
+    text.push('\n');
+    for (code, description) in legend {
+        text.push_str(&format!("{}: {}\n", code, description));
+    }
+    // Trailing spaces from the last column would only get in the way when pasted
+    text.lines().map(str::trim_end).collect::<Vec<_>>().join("\n") + "\n"
+}
+
+// ---- HTML ----
+
+/// The terminal colours the calculator draws chart codes in, for a web page.
+fn css_color(color: Color) -> String {
+    let css = match color {
+        Color::Rgb(red, green, blue) => return format!("#{:02x}{:02x}{:02x}", red, green, blue),
+        Color::Black => "#000000",
+        Color::Red => "#cd3131",
+        Color::Green => "#0dbc79",
+        Color::Yellow => "#e5e510",
+        Color::Blue => "#2472c8",
+        Color::Magenta => "#bc3fbc",
+        Color::Cyan => "#11a8cd",
+        Color::Gray => "#e5e5e5",
+        Color::DarkGray => "#666666",
+        Color::LightRed => "#f14c4c",
+        Color::LightGreen => "#23d18b",
+        Color::LightYellow => "#f5f543",
+        Color::LightBlue => "#3b8eea",
+        Color::LightMagenta => "#d670d6",
+        Color::LightCyan => "#29b8db",
+        _ => "#e5e5e5",
+    };
+    css.to_string()
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+const HTML_STYLE: &str = "\
+body { font-family: monospace; background: #1e1e1e; color: #e5e5e5; margin: 2em; }
+h1, p { text-align: center; }
+.tables { display: flex; flex-wrap: wrap; justify-content: center; gap: 2em; }
+table { border-collapse: collapse; }
+caption { font-weight: bold; padding: 0.5em; }
+th, td { padding: 0.2em 0.5em; text-align: center; }
+td.code { color: #1e1e1e; font-weight: bold; border: 1px solid #1e1e1e; }
+.legend { display: flex; flex-wrap: wrap; justify-content: center; gap: 0.5em 2em; margin-top: 2em; }
+@media print { body { margin: 0; } * { print-color-adjust: exact; -webkit-print-color-adjust: exact; } }
+";
+
+/// The strategy as a web page that stands on its own: its tables side by
+/// side and the action legend, each code coloured the way the calculator
+/// shows it.
+pub fn chart_html(strategy: &BlackjackStrategy) -> String {
+    let cell = |code: &str| format!(
+        "<td class=\"code\" style=\"background: {}\">{}</td>",
+        css_color(get_action_color(&strategy.action_legend, code)),
+        escape_html(code),
+    );
+
+    let mut html = String::from("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    html.push_str(&format!("<title>{}</title>\n", escape_html(&strategy.name)));
+    html.push_str(&format!("<style>\n{}</style>\n</head>\n<body>\n", HTML_STYLE));
+    html.push_str(&format!("<h1>{}</h1>\n", escape_html(&strategy.name)));
+    html.push_str(&format!("<p>{}</p>\n", escape_html(&strategy.rules.to_string())));
+
+    html.push_str("<div class=\"tables\">\n");
+    for (table, rows) in chart_tables(strategy) {
+        html.push_str(&format!("<table>\n<caption>{}</caption>\n<tr><th></th>", table_title(table)));
+        for upcard in CHART_UPCARDS {
+            html.push_str(&format!("<th>{}</th>", card_label(upcard)));
+        }
+        html.push_str("</tr>\n");
+        for (hand, actions) in rows {
+            html.push_str(&format!("<tr><th>{}</th>", escape_html(&hand.to_string())));
+            for action in actions {
+                html.push_str(&cell(action));
+            }
+            html.push_str("</tr>\n");
+        }
+        html.push_str("</table>\n");
+    }
+    html.push_str("</div>\n");
+
+    let mut legend: Vec<(&String, &String)> = strategy.action_legend.iter().map(|(code, entry)| (code, &entry.description)).collect();
+    legend.sort_by(|a, b| a.0.cmp(b.0));
+    html.push_str("<div class=\"legend\">\n");
+    for (code, description) in legend {
+        html.push_str(&format!(
+            "<table><tr>{}<td>{}</td></tr></table>\n",
+            cell(code),
+            escape_html(description),
+        ));
+    }
+    html.push_str("</div>\n</body>\n</html>\n");
+    html
+}
+
+/// Writes the strategy's chart to `<name>.html` in `dir`, returning the file written.
+pub fn export_html(strategy: &BlackjackStrategy, name: &str, dir: &Path) -> Result<PathBuf, String> {
+    fs::create_dir_all(dir).map_err(|err| format!("Couldn't create {}: {}", dir.display(), err))?;
+    let path = dir.join(format!("{}.html", file_stem(name)));
+    fs::write(&path, chart_html(strategy)).map_err(|err| format!("Couldn't write {}: {}", path.display(), err))?;
+    Ok(path)
+}
+
+// ---- Image ----
+
+/// Sizes on the wallet card, in points
+const CARD_MARGIN: f64 = 8.0;
+const CARD_CELL: f64 = 13.0;
+const CARD_LABEL: f64 = 22.0;
+const CARD_GAP: f64 = 8.0;
+const CARD_TITLE: f64 = 22.0;
+const CARD_LEGEND_LINE: f64 = 10.0;
+const CARD_LEGEND_GAP: f64 = 4.0;
+const CARD_LEGEND_COLUMNS: usize = 2;
+
+/// How many pixels a point becomes in a PNG, enough to print sharply
+const PNG_SCALE: f32 = 4.0;
+
+/// Short hand labels that fit a wallet card, e.g. "16", "A,7" or "8,8".
+fn card_hand_label(hand: ChartHand) -> String {
+    match hand {
+        ChartHand::Hard(total) => total.to_string(),
+        ChartHand::Soft(total) => format!("A,{}", card_label(total - 11)),
+        pair => pair.to_string(),
+    }
+}
+
+fn card_text(content: impl Into<String>, x: f64, y: f64, size: f64) -> Text {
+    Text::new(content)
+        .set("x", x)
+        .set("y", y)
+        .set("font-family", "monospace")
+        .set("font-size", size)
+}
+
+/// The strategy as a wallet card sized SVG: the three tables side by side
+/// under the rules they're for, with the action legend beneath.
+pub fn chart_svg(strategy: &BlackjackStrategy) -> String {
+    let tables = chart_tables(strategy);
+    let table_width = CARD_LABEL + CARD_CELL * CHART_UPCARDS.len() as f64;
+    let longest = tables.iter().map(|(_, rows)| rows.len()).max().unwrap_or(0);
+    let mut legend: Vec<(&String, &String)> = strategy.action_legend.iter().map(|(code, entry)| (code, &entry.description)).collect();
+    legend.sort_by(|a, b| a.0.cmp(b.0));
+    let legend_rows = legend.len().div_ceil(CARD_LEGEND_COLUMNS);
+
+    let width = CARD_MARGIN * 2.0 + table_width * tables.len() as f64 + CARD_GAP * (tables.len() - 1) as f64;
+    let tables_height = CARD_CELL * (longest + 2) as f64;
+    let height = CARD_MARGIN * 2.0 + CARD_TITLE + tables_height + CARD_LEGEND_GAP + CARD_LEGEND_LINE * legend_rows as f64;
+
+    let mut document = Document::new()
+        .set("xmlns", "http://www.w3.org/2000/svg")
+        .set("width", width)
+        .set("height", height)
+        .set("viewBox", (0.0, 0.0, width, height))
+        .add(Rectangle::new().set("width", "100%").set("height", "100%").set("fill", "white"))
+        .add(card_text(strategy.name.clone(), CARD_MARGIN, CARD_MARGIN + 8.0, 9.0).set("font-weight", "bold"))
+        .add(card_text(strategy.rules.to_string(), CARD_MARGIN, CARD_MARGIN + 17.0, 6.0));
+
+    let top = CARD_MARGIN + CARD_TITLE;
+    for (i, (table, rows)) in tables.iter().enumerate() {
+        let left = CARD_MARGIN + (table_width + CARD_GAP) * i as f64;
+        document = document.add(card_text(table_title(table), left, top + CARD_CELL * 0.7, 7.0).set("font-weight", "bold"));
+        for (column, upcard) in CHART_UPCARDS.iter().enumerate() {
+            let x = left + CARD_LABEL + CARD_CELL * (column as f64 + 0.5);
+            document = document.add(card_text(card_label(*upcard), x, top + CARD_CELL * 1.7, 7.0)
+                .set("text-anchor", "middle")
+                .set("font-weight", "bold"));
+        }
+
+        for (row, (hand, actions)) in rows.iter().enumerate() {
+            let y = top + CARD_CELL * (row + 2) as f64;
+            document = document.add(card_text(card_hand_label(*hand), left, y + CARD_CELL * 0.7, 7.0).set("font-weight", "bold"));
+            for (column, action) in actions.iter().enumerate() {
+                let x = left + CARD_LABEL + CARD_CELL * column as f64;
+                document = document
+                    .add(Rectangle::new()
+                        .set("x", x)
+                        .set("y", y)
+                        .set("width", CARD_CELL)
+                        .set("height", CARD_CELL)
+                        .set("fill", css_color(get_action_color(&strategy.action_legend, action)))
+                        .set("stroke", "white")
+                        .set("stroke-width", 0.5))
+                    .add(card_text(action.clone(), x + CARD_CELL / 2.0, y + CARD_CELL * 0.7, 6.5).set("text-anchor", "middle"));
+            }
+        }
+    }
+
+    // Each code in the legend on a square coloured like the chart's
+    let legend_top = top + tables_height + CARD_LEGEND_GAP;
+    let legend_width = (width - CARD_MARGIN * 2.0) / CARD_LEGEND_COLUMNS as f64;
+    for (i, (code, description)) in legend.iter().enumerate() {
+        let x = CARD_MARGIN + legend_width * (i / legend_rows) as f64;
+        let y = legend_top + CARD_LEGEND_LINE * (i % legend_rows) as f64;
+        document = document
+            .add(Rectangle::new()
+                .set("x", x)
+                .set("y", y + 1.0)
+                .set("width", 12.0)
+                .set("height", CARD_LEGEND_LINE - 2.0)
+                .set("fill", css_color(get_action_color(&strategy.action_legend, code))))
+            .add(card_text(code.to_string(), x + 6.0, y + 7.0, 5.5).set("text-anchor", "middle"))
+            .add(card_text(description.to_string(), x + 15.0, y + 7.0, 5.5));
+    }
+    document.to_string()
+}
+
+/// Writes the strategy's wallet card to `<name>.svg` in `dir`, returning the file written.
+pub fn export_svg(strategy: &BlackjackStrategy, name: &str, dir: &Path) -> Result<PathBuf, String> {
+    fs::create_dir_all(dir).map_err(|err| format!("Couldn't create {}: {}", dir.display(), err))?;
+    let path = dir.join(format!("{}.svg", file_stem(name)));
+    fs::write(&path, chart_svg(strategy)).map_err(|err| format!("Couldn't write {}: {}", path.display(), err))?;
+    Ok(path)
+}
+
+/// Writes the strategy's wallet card to `<name>.png` in `dir`, drawn with
+/// the system's fonts, returning the file written.
+pub fn export_png(strategy: &BlackjackStrategy, name: &str, dir: &Path) -> Result<PathBuf, String> {
+    let mut options = usvg::Options::default();
+    let fonts = options.fontdb_mut();
+    fonts.load_system_fonts();
+    // The card asks for monospace, so point that at whichever one is installed
+    let monospace = fonts.faces()
+        .find(|face| face.monospaced)
+        .or_else(|| fonts.faces().next())
+        .and_then(|face| face.families.first())
+        .map(|(family, _)| family.clone())
+        .ok_or("No fonts were found to draw the chart with")?;
+    fonts.set_monospace_family(monospace);
+
+    let tree = usvg::Tree::from_str(&chart_svg(strategy), &options).map_err(|err| err.to_string())?;
+    let size = tree.size().to_int_size().scale_by(PNG_SCALE).ok_or("The chart is too large to draw")?;
+    let mut pixmap = tiny_skia::Pixmap::new(size.width(), size.height()).ok_or("The chart is too large to draw")?;
+    resvg::render(&tree, tiny_skia::Transform::from_scale(PNG_SCALE, PNG_SCALE), &mut pixmap.as_mut());
+
+    fs::create_dir_all(dir).map_err(|err| format!("Couldn't create {}: {}", dir.display(), err))?;
+    let path = dir.join(format!("{}.png", file_stem(name)));
+    pixmap.save_png(&path).map_err(|err| format!("Couldn't write {}: {}", path.display(), err))?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logic::ev::generate_strategy;
+    use crate::logic::game::table::TableConfig;
+    use crate::logic::strategy_calculator_logic::{GameVariant, StrategyVariables, SurrenderRule};
+
+    fn rules() -> StrategyVariables {
+        StrategyVariables {
+            decks: 6,
+            dealer_stands_on_soft_17: true,
+            double_after_split: true,
+            dealer_peak: true,
+            surrender_allowed: SurrenderRule::NotAllowed,
+            variant: GameVariant::Classic,
+        }
+    }
+
+    #[test]
+    fn test_export_csv() {
+        let strategy = generate_strategy(&rules(), &TableConfig::default());
+        let dir = std::env::temp_dir().join(format!("export-{}", uuid::Uuid::new_v4()));
+        let paths = export_csv(&strategy, &strategy.name, &dir).unwrap();
+        assert_eq!(paths.len(), 3);
+        assert!(paths[0].ends_with("Computed-Strategy_hard.csv"));
+
+        let hard = fs::read_to_string(&paths[0]).unwrap();
+        let lines: Vec<&str> = hard.lines().collect();
+        assert_eq!(lines[0], "Hand,2,3,4,5,6,7,8,9,10,A");
+        assert_eq!(lines.len(), strategy.tables.hard_hands.len() + 1);
+        assert!(lines.contains(&"Hard 16,S,S,S,S,S,H,H,H,H,H"));
+
+        // Pairs are written as their two cards, which needs quoting
+        let pairs = fs::read_to_string(&paths[2]).unwrap();
+        assert!(pairs.lines().last().unwrap().starts_with("\"A,A\",P,"));
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_chart_text() {
+        let strategy = generate_strategy(&rules(), &TableConfig::default());
+        let text = chart_text(&strategy);
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines[0], "Computed Strategy");
+        assert!(lines.contains(&"        2   3   4   5   6   7   8   9   10  A"));
+        assert!(lines.contains(&"Hard 16 S   S   S   S   S   H   H   H   H   H"));
+        assert!(lines.contains(&"A,A   P   P   P   P   P   P   P   P   P   P"));
+        assert!(lines.contains(&"H: Hit"));
+
+        let row = &strategy.tables.hard_hands.iter().find(|row| row.total == 16).unwrap().actions;
+        assert_eq!(row_text(ChartHand::Hard(16), row), "Hard 16: 2 S, 3 S, 4 S, 5 S, 6 S, 7 H, 8 H, 9 H, 10 H, A H");
+        assert_eq!(cell_text(&strategy, ChartHand::Hard(16), 10, "H"), "Hard 16 vs 10: H (Hit)");
+    }
+
+    #[test]
+    fn test_chart_html() {
+        let mut strategy = generate_strategy(&rules(), &TableConfig::default());
+        strategy.name = "Hit & <Run>".to_string();
+        let html = chart_html(&strategy);
+        assert!(html.contains("<h1>Hit &amp; &lt;Run&gt;</h1>"));
+        assert!(html.contains("Classic, 6 Decks, S17, DAS, Surrender: Not Allowed, Dealer Peeks"));
+        assert_eq!(html.matches("<caption>").count(), 3);
+        assert!(html.contains("<tr><th>A,A</th>"));
+        // Coloured the same as the calculator, so hits are red
+        assert!(html.contains(&format!("<td class=\"code\" style=\"background: {}\">H</td>", css_color(Color::Red))));
+    }
+
+    #[test]
+    fn test_chart_svg() {
+        let strategy = generate_strategy(&rules(), &TableConfig::default());
+        let svg = chart_svg(&strategy);
+        let cells: usize = chart_tables(&strategy).iter()
+            .map(|(_, rows)| rows.iter().map(|(_, actions)| actions.len()).sum::<usize>())
+            .sum();
+        // A background, then a coloured square per chart code and legend entry
+        assert_eq!(svg.matches("<rect").count(), 1 + cells + strategy.action_legend.len());
+        assert!(svg.contains("A,A"));
+        assert!(usvg::Tree::from_str(&svg, &usvg::Options::default()).is_ok());
+    }
+}
@@ -0,0 +1,187 @@
+use std::fmt;
+use crate::logic::ev::ChartHand;
+use crate::logic::game::card::Card;
+use crate::logic::game::hand::Hand;
+use crate::logic::game::round::{Decision, PlayerAction, Round};
+use crate::logic::strategy_calculator_logic::BlackjackStrategy;
+
+/// A basic strategy recommendation for the hand being played.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Advice {
+    /// Chart code the recommendation came from, e.g. "Ds"
+    pub code: String,
+
+    /// The action to take, after falling back for actions that aren't allowed
+    pub action: PlayerAction,
+}
+
+/// The chart row a hand is played from. Pairs use the pair table only
+/// while the hand can still be split, soft hands the soft table when the
+/// chart has a row for the total, and everything else the hard table.
+pub fn chart_hand(strategy: &BlackjackStrategy, hand: &Hand, split_allowed: bool) -> ChartHand {
+    let tables = &strategy.tables;
+    if split_allowed && hand.is_pair() {
+        let first = hand.cards()[0];
+        let pair = if first.is_ace() { 11 } else { first.value() };
+        if tables.pair_hands.iter().any(|row| row.pair == pair) {
+            return ChartHand::Pair(pair);
+        }
+    }
+
+    let total = hand.total();
+    if hand.is_soft() && tables.soft_hands.iter().any(|row| row.total == total) {
+        return ChartHand::Soft(total);
+    }
+
+    // Charts start at hard 5, smaller totals play the same as the lowest row
+    let row = tables.hard_hands.iter().map(|row| row.total).filter(|row| *row >= total).min();
+    ChartHand::Hard(row.unwrap_or(total))
+}
+
+/// Looks up the chart code for a hand against the dealer upcard, from the
+/// row `chart_hand` picks.
+pub fn chart_code<'a>(strategy: &'a BlackjackStrategy, hand: &Hand, upcard: Card, split_allowed: bool) -> Option<&'a str> {
+    let tables = &strategy.tables;
+    let actions = match chart_hand(strategy, hand, split_allowed) {
+        ChartHand::Pair(pair) => &tables.pair_hands.iter().find(|row| row.pair == pair)?.actions,
+        ChartHand::Soft(total) => &tables.soft_hands.iter().find(|row| row.total == total)?.actions,
+        ChartHand::Hard(total) => &tables.hard_hands.iter().find(|row| row.total == total)?.actions,
+    };
+    actions.get(upcard.rank.strategy_column()).map(|code| code.as_str())
+}
+
+/// Turns a chart code into an action, taking the chart's fallback when the
+/// preferred action isn't available (e.g. "Ds" means double, otherwise stand).
+pub fn resolve_code(code: &str, available: &[PlayerAction]) -> Option<PlayerAction> {
+    let (preferred, fallback) = match code.trim() {
+        "H" => (PlayerAction::Hit, None),
+        "S" => (PlayerAction::Stand, None),
+        "D" | "Dh" => (PlayerAction::Double, Some(PlayerAction::Hit)),
+        "Ds" => (PlayerAction::Double, Some(PlayerAction::Stand)),
+        "P" => (PlayerAction::Split, None),
+        "Rh" => (PlayerAction::Surrender, Some(PlayerAction::Hit)),
+        "Rs" => (PlayerAction::Surrender, Some(PlayerAction::Stand)),
+        "Rp" => (PlayerAction::Surrender, Some(PlayerAction::Split)),
+        _ => return None,
+    };
+
+    if available.contains(&preferred) {
+        Some(preferred)
+    } else {
+        fallback.filter(|action| available.contains(action))
+    }
+}
+
+/// Recommends one of the available actions for a hand against the dealer upcard.
+pub fn recommend(strategy: &BlackjackStrategy, hand: &Hand, upcard: Card, available: &[PlayerAction]) -> Option<Advice> {
+    let code = chart_code(strategy, hand, upcard, available.contains(&PlayerAction::Split))?;
+    let action = resolve_code(code, available)?;
+    Some(Advice { code: code.to_string(), action })
+}
+
+/// Recommends an action for the round's active hand, if the player is acting.
+pub fn advise(strategy: &BlackjackStrategy, round: &Round) -> Option<Advice> {
+    let index = round.active_hand_index()?;
+    let player = &round.hands()[index];
+    let upcard = *round.dealer().cards().first()?;
+    recommend(strategy, &player.hand, upcard, &round.available_actions())
+}
+
+// ---- Decision Review ----
+
+/// A decision graded against the strategy chart.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Review {
+    pub decision: Decision,
+    pub recommended: PlayerAction,
+}
+
+impl Review {
+    pub fn is_correct(&self) -> bool {
+        self.decision.action == self.recommended
+    }
+}
+
+impl fmt::Display for Review {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "you {}, ", self.decision)?;
+        if self.is_correct() {
+            write!(f, "correct")
+        } else {
+            write!(f, "should have {}", self.recommended.past_tense())
+        }
+    }
+}
+
+/// Grades a decision, or `None` when the chart has no answer for it.
+pub fn review(strategy: &BlackjackStrategy, decision: &Decision) -> Option<Review> {
+    let advice = recommend(strategy, &decision.hand, decision.upcard, &decision.available)?;
+    Some(Review { decision: decision.clone(), recommended: advice.action })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logic::game::card::{Rank, Suit};
+
+    fn strategy() -> BlackjackStrategy {
+        BlackjackStrategy::from_file("resources/strategies/default-strategy.json").unwrap()
+    }
+
+    fn hand_of(ranks: &[Rank]) -> Hand {
+        let mut hand = Hand::new();
+        for rank in ranks {
+            hand.add(Card::new(*rank, Suit::Spades));
+        }
+        hand
+    }
+
+    #[test]
+    fn test_chart_code_picks_the_right_table() {
+        let strategy = strategy();
+        let ten = Card::new(Rank::King, Suit::Hearts);
+        let six = Card::new(Rank::Six, Suit::Hearts);
+
+        assert_eq!(chart_code(&strategy, &hand_of(&[Rank::Ten, Rank::Six]), ten, true), Some("H"));
+        assert_eq!(chart_code(&strategy, &hand_of(&[Rank::Ten, Rank::Six]), six, true), Some("S"));
+        assert_eq!(chart_code(&strategy, &hand_of(&[Rank::Ace, Rank::Seven]), six, true), Some("D"));
+        assert_eq!(chart_code(&strategy, &hand_of(&[Rank::Eight, Rank::Eight]), ten, true), Some("P"));
+
+        // A pair that can't be split again plays as a hard total
+        assert_eq!(chart_code(&strategy, &hand_of(&[Rank::Eight, Rank::Eight]), ten, false), Some("H"));
+        assert_eq!(chart_code(&strategy, &hand_of(&[Rank::Two, Rank::Two]), six, false), Some("H"));
+        assert_eq!(chart_code(&strategy, &hand_of(&[Rank::Ace, Rank::Ace]), six, false), Some("S"));
+    }
+
+    #[test]
+    fn test_resolve_code_falls_back() {
+        let all = [PlayerAction::Hit, PlayerAction::Stand, PlayerAction::Double, PlayerAction::Split, PlayerAction::Surrender];
+        let no_extras = [PlayerAction::Hit, PlayerAction::Stand];
+
+        assert_eq!(resolve_code("Ds", &all), Some(PlayerAction::Double));
+        assert_eq!(resolve_code("Ds", &no_extras), Some(PlayerAction::Stand));
+        assert_eq!(resolve_code("D", &no_extras), Some(PlayerAction::Hit));
+        assert_eq!(resolve_code("Rh", &no_extras), Some(PlayerAction::Hit));
+        assert_eq!(resolve_code("Rp", &all), Some(PlayerAction::Surrender));
+        assert_eq!(resolve_code("?", &all), None);
+    }
+
+    #[test]
+    fn test_review_describes_decisions() {
+        let strategy = strategy();
+        let decision = |ranks: &[Rank], upcard: Rank, action: PlayerAction| Decision {
+            hand: hand_of(ranks),
+            upcard: Card::new(upcard, Suit::Hearts),
+            action,
+            available: vec![PlayerAction::Hit, PlayerAction::Stand, PlayerAction::Double],
+        };
+
+        let hit = review(&strategy, &decision(&[Rank::Ten, Rank::Six], Rank::Ten, PlayerAction::Hit)).unwrap();
+        assert!(hit.is_correct());
+        assert_eq!(hit.to_string(), "you hit 16 vs 10, correct");
+
+        let stood = review(&strategy, &decision(&[Rank::Ace, Rank::Seven], Rank::Nine, PlayerAction::Stand)).unwrap();
+        assert!(!stood.is_correct());
+        assert_eq!(stood.to_string(), "you stood on A,7 vs 9, should have hit");
+    }
+}
@@ -0,0 +1,215 @@
+use crate::logic::game::card::Card;
+use crate::logic::game::round::{Round, RoundPhase};
+
+/// Tracks how much of a round has been shown so cards can be revealed one at
+/// a time, in the order they were dealt, rather than all at once.
+///
+/// The round itself is always fully resolved, this only decides what is
+/// visible. Each `step` reveals one more card or flips the hole card.
+#[derive(Debug, Clone, Default)]
+pub struct DealAnimation {
+    /// Cards shown for each player hand, by hand index
+    hands_shown: Vec<usize>,
+    dealer_shown: usize,
+    hole_revealed: bool,
+    peek: PeekState,
+}
+
+/// Progress of the dealer checking the hole card for blackjack.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PeekState {
+    #[default]
+    Pending,
+    Peeking,
+    Done,
+}
+
+enum Reveal {
+    Player(usize),
+    Dealer,
+}
+
+impl DealAnimation {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts over for a freshly dealt round with nothing on the table.
+    pub fn start(&mut self, round: &Round) {
+        self.hands_shown = vec![0; round.hands().len()];
+        self.dealer_shown = 0;
+        self.hole_revealed = false;
+        self.peek = PeekState::Pending;
+    }
+
+    /// Keeps the shown cards lined up with the hands after the hand at
+    /// `index` was split: each half keeps one card and receives a new one.
+    pub fn split(&mut self, index: usize) {
+        if index < self.hands_shown.len() {
+            self.hands_shown[index] = 1;
+            self.hands_shown.insert(index + 1, 1);
+        }
+    }
+
+    /// Shows everything straight away.
+    pub fn finish(&mut self, round: &Round) {
+        self.hands_shown = round.hands().iter().map(|player| player.hand.len()).collect();
+        self.dealer_shown = round.dealer().len();
+        self.hole_revealed = round.phase() == RoundPhase::Complete;
+        self.peek = PeekState::Done;
+    }
+
+    pub fn is_done(&self, round: &Round) -> bool {
+        let mut finished = self.clone();
+        !finished.step(round)
+    }
+
+    /// Reveals the next card. Player hands and the dealer are dealt round
+    /// robin, and a dealer showing an Ace or ten then peeks (after any
+    /// insurance). Once the round is over the hole card is flipped and the
+    /// dealer's draws follow. Returns false when there is nothing left to show.
+    pub fn step(&mut self, round: &Round) -> bool {
+        self.hands_shown.resize(round.hands().len(), 0);
+
+        // Cards dealt to the dealer before the players act
+        let dealt_to_dealer = if round.dealer_has_hole_card() { 2 } else { 1 };
+        let mut next: Option<(usize, Reveal)> = None;
+        for (index, player) in round.hands().iter().enumerate() {
+            let shown = self.hands_shown[index];
+            if shown < player.hand.len() && next.as_ref().is_none_or(|(least, _)| shown < *least) {
+                next = Some((shown, Reveal::Player(index)));
+            }
+        }
+        if self.dealer_shown < dealt_to_dealer.min(round.dealer().len())
+            && next.as_ref().is_none_or(|(least, _)| self.dealer_shown < *least) {
+            next = Some((self.dealer_shown, Reveal::Dealer));
+        }
+
+        match next {
+            Some((_, Reveal::Player(index))) => self.hands_shown[index] += 1,
+            Some((_, Reveal::Dealer)) => self.dealer_shown += 1,
+            None if self.peek != PeekState::Done
+                && round.dealer_checks_for_blackjack()
+                && round.phase() != RoundPhase::Insurance => {
+                self.peek = match self.peek {
+                    PeekState::Pending => PeekState::Peeking,
+                    PeekState::Peeking | PeekState::Done => PeekState::Done,
+                };
+            }
+            None if round.phase() != RoundPhase::Complete => return false,
+            None if !self.hole_revealed && round.dealer_has_hole_card() => self.hole_revealed = true,
+            None if self.dealer_shown < round.dealer().len() => {
+                self.hole_revealed = true;
+                self.dealer_shown += 1;
+            }
+            None => {
+                self.hole_revealed = true;
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Number of cards of the player hand at `index` to show.
+    pub fn hand_shown(&self, index: usize) -> usize {
+        self.hands_shown.get(index).copied().unwrap_or(0)
+    }
+
+    pub fn dealer_shown(&self) -> usize {
+        self.dealer_shown
+    }
+
+    pub fn hole_revealed(&self) -> bool {
+        self.hole_revealed
+    }
+
+    pub fn peek(&self) -> PeekState {
+        self.peek
+    }
+
+    /// Cards of the round drawn from the shoe but not shown yet, the hole
+    /// card included while it's face down.
+    pub fn hidden_cards(&self, round: &Round) -> Vec<Card> {
+        let mut hidden: Vec<Card> = round.hands().iter().enumerate()
+            .flat_map(|(index, player)| player.hand.cards().iter().skip(self.hand_shown(index)).copied())
+            .collect();
+        let dealer = round.dealer().cards();
+        hidden.extend(dealer.iter().skip(self.dealer_shown).copied());
+        if round.dealer_has_hole_card() && !self.hole_revealed && self.dealer_shown >= 2 {
+            hidden.push(dealer[1]);
+        }
+        hidden
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logic::game::card::{Card, Rank, Suit};
+    use crate::logic::game::round::PlayerAction;
+    use crate::logic::game::shoe::Shoe;
+    use crate::logic::game::table::TableConfig;
+    use crate::logic::strategy_calculator_logic::StrategyVariables;
+
+    fn shoe_of(ranks: &[Rank]) -> Shoe {
+        Shoe::stacked(ranks.iter().map(|rank| Card::new(*rank, Suit::Clubs)).collect())
+    }
+
+    #[test]
+    fn test_cards_are_revealed_in_dealing_order() {
+        let rules = StrategyVariables::single_deck();
+        // Two spots: 10,9 and 9,8 against a dealer 6 that draws a 4 then a 9
+        let mut shoe = shoe_of(&[Rank::Ten, Rank::Nine, Rank::Six, Rank::Nine, Rank::Eight, Rank::Four, Rank::Nine]);
+        let mut round = Round::deal(&rules, &TableConfig::default(), &[10.0, 10.0], &mut shoe);
+        let mut animation = DealAnimation::new();
+        animation.start(&round);
+        assert_eq!(animation.hidden_cards(&round).len(), 6);
+
+        // Spot 1, spot 2, dealer, twice over
+        let expected = [(1, 0, 0), (1, 1, 0), (1, 1, 1), (2, 1, 1), (2, 2, 1), (2, 2, 2)];
+        for (first, second, dealer) in expected {
+            assert!(animation.step(&round));
+            assert_eq!((animation.hand_shown(0), animation.hand_shown(1), animation.dealer_shown()), (first, second, dealer));
+        }
+        assert!(animation.is_done(&round));
+        assert!(!animation.hole_revealed());
+        assert_eq!(animation.hidden_cards(&round), [Card::new(Rank::Four, Suit::Clubs)]);
+
+        round.apply(PlayerAction::Stand, &mut shoe).unwrap();
+        round.apply(PlayerAction::Stand, &mut shoe).unwrap();
+
+        // The hole card flips before the dealer draws
+        assert!(animation.step(&round));
+        assert!(animation.hole_revealed());
+        assert_eq!(animation.dealer_shown(), 2);
+        assert!(animation.step(&round));
+        assert_eq!(animation.dealer_shown(), 3);
+        assert!(!animation.step(&round));
+        assert!(animation.hidden_cards(&round).is_empty());
+    }
+
+    #[test]
+    fn test_peek_before_revealing_dealer_blackjack() {
+        let rules = StrategyVariables::single_deck();
+        let mut shoe = shoe_of(&[Rank::Nine, Rank::King, Rank::Eight, Rank::Ace]);
+        let round = Round::deal(&rules, &TableConfig::default(), &[10.0], &mut shoe);
+        assert_eq!(round.phase(), RoundPhase::Complete);
+
+        let mut animation = DealAnimation::new();
+        animation.start(&round);
+        for _ in 0..4 {
+            animation.step(&round);
+        }
+        assert_eq!(animation.peek(), PeekState::Pending);
+
+        assert!(animation.step(&round));
+        assert_eq!(animation.peek(), PeekState::Peeking);
+        assert!(animation.step(&round));
+        assert_eq!(animation.peek(), PeekState::Done);
+        assert!(!animation.hole_revealed());
+
+        assert!(animation.step(&round));
+        assert!(animation.hole_revealed());
+        assert!(!animation.step(&round));
+    }
+}
@@ -0,0 +1,86 @@
+use std::fs;
+use std::path::Path;
+use serde::{Deserialize, Serialize};
+
+pub const DEFAULT_BANKROLL: f64 = 1000.0;
+
+/// The player's money, carried from round to round and saved between runs.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Bankroll {
+    /// Money currently available
+    pub balance: f64,
+
+    /// Balance at the last buy in, used to show the overall result
+    pub buy_in: f64,
+}
+
+impl Bankroll {
+    pub fn new(buy_in: f64) -> Self {
+        Self {
+            balance: buy_in,
+            buy_in,
+        }
+    }
+
+    /// Whether `amount` can be wagered while `committed` is already on the table.
+    pub fn can_cover(&self, committed: f64, amount: f64) -> bool {
+        committed + amount <= self.balance
+    }
+
+    /// Credits (or debits, when negative) the net result of a round.
+    pub fn settle(&mut self, net: f64) {
+        self.balance += net;
+    }
+
+    /// Net won or lost since the last buy in.
+    pub fn profit(&self) -> f64 {
+        self.balance - self.buy_in
+    }
+
+    /// Loads a saved bankroll, starting fresh if there is none or it cannot be read.
+    pub fn load(path: &str) -> Self {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_else(|| Self::new(DEFAULT_BANKROLL))
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(parent) = Path::new(path).parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cover_and_settle() {
+        let mut bankroll = Bankroll::new(100.0);
+        assert!(bankroll.can_cover(50.0, 50.0));
+        assert!(!bankroll.can_cover(60.0, 50.0));
+
+        bankroll.settle(-75.0);
+        bankroll.settle(15.0);
+        assert_eq!(bankroll.balance, 40.0);
+        assert_eq!(bankroll.profit(), -60.0);
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let path = std::env::temp_dir().join(format!("bankroll-{}.json", uuid::Uuid::new_v4()));
+        let path = path.to_str().unwrap();
+
+        let mut bankroll = Bankroll::new(500.0);
+        bankroll.settle(42.5);
+        bankroll.save(path).unwrap();
+        assert_eq!(Bankroll::load(path), bankroll);
+
+        fs::remove_file(path).unwrap();
+        assert_eq!(Bankroll::load(path).balance, DEFAULT_BANKROLL);
+    }
+}
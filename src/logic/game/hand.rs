@@ -0,0 +1,124 @@
+use std::fmt;
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use crate::logic::game::card::Card;
+
+/// An ordered collection of cards held by the player or the dealer.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct Hand {
+    cards: Vec<Card>,
+}
+
+impl Hand {
+    pub fn new() -> Self {
+        Self { cards: Vec::new() }
+    }
+
+    pub fn add(&mut self, card: Card) {
+        self.cards.push(card);
+    }
+
+    pub fn cards(&self) -> &[Card] {
+        &self.cards
+    }
+
+    pub fn len(&self) -> usize {
+        self.cards.len()
+    }
+
+    /// Removes and returns the last card, used when splitting a pair.
+    pub fn take_last(&mut self) -> Option<Card> {
+        self.cards.pop()
+    }
+
+    /// Total counting every Ace as 1.
+    fn hard_total(&self) -> u8 {
+        self.cards.iter().map(|card| card.value()).sum()
+    }
+
+    /// Best total of the hand, counting one Ace as 11 when that does not bust.
+    pub fn total(&self) -> u8 {
+        let hard = self.hard_total();
+        if self.cards.iter().any(|card| card.is_ace()) && hard + 10 <= 21 {
+            hard + 10
+        } else {
+            hard
+        }
+    }
+
+    /// A hand is soft when an Ace is currently being counted as 11.
+    pub fn is_soft(&self) -> bool {
+        self.total() != self.hard_total()
+    }
+
+    pub fn is_bust(&self) -> bool {
+        self.total() > 21
+    }
+
+    /// Two card 21. Whether it pays as a blackjack (e.g. not after a split)
+    /// is decided by the round.
+    pub fn is_natural(&self) -> bool {
+        self.cards.len() == 2 && self.total() == 21
+    }
+
+    /// Two cards of equal value, e.g. 8,8 or K,10.
+    pub fn is_pair(&self) -> bool {
+        self.cards.len() == 2 && self.cards[0].value() == self.cards[1].value()
+    }
+
+    /// Describes the hand the way it is read off a chart: "8,8" for pairs,
+    /// "A,7" for two card soft hands and the total otherwise.
+    pub fn chart_label(&self) -> String {
+        if self.len() == 2 && (self.is_pair() || self.is_soft()) {
+            self.cards.iter().map(|card| card.chart_label()).join(",")
+        } else if self.is_soft() {
+            format!("soft {}", self.total())
+        } else {
+            self.total().to_string()
+        }
+    }
+}
+
+impl fmt::Display for Hand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.cards.iter().join(" "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logic::game::card::{Rank, Suit};
+
+    fn hand_of(ranks: &[Rank]) -> Hand {
+        let mut hand = Hand::new();
+        for rank in ranks {
+            hand.add(Card::new(*rank, Suit::Spades));
+        }
+        hand
+    }
+
+    #[test]
+    fn test_soft_and_hard_totals() {
+        let soft = hand_of(&[Rank::Ace, Rank::Six]);
+        assert_eq!(soft.total(), 17);
+        assert!(soft.is_soft());
+
+        let hardened = hand_of(&[Rank::Ace, Rank::Six, Rank::Nine]);
+        assert_eq!(hardened.total(), 16);
+        assert!(!hardened.is_soft());
+
+        let two_aces = hand_of(&[Rank::Ace, Rank::Ace]);
+        assert_eq!(two_aces.total(), 12);
+        assert!(two_aces.is_soft());
+    }
+
+    #[test]
+    fn test_natural_bust_and_pair() {
+        assert!(hand_of(&[Rank::Ace, Rank::King]).is_natural());
+        assert!(!hand_of(&[Rank::Seven, Rank::Four, Rank::King]).is_natural());
+        assert!(hand_of(&[Rank::King, Rank::Queen, Rank::Two]).is_bust());
+        assert!(hand_of(&[Rank::King, Rank::Ten]).is_pair());
+        assert!(!hand_of(&[Rank::Nine, Rank::Ten]).is_pair());
+    }
+}
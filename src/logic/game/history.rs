@@ -0,0 +1,140 @@
+use crate::logic::game::hand::Hand;
+use crate::logic::game::round::{Decision, InsuranceDecision, PlayerHand, Round};
+use crate::logic::strategy_calculator_logic::StrategyVariables;
+
+/// Everything that happened in one finished round.
+#[derive(Debug, Clone)]
+pub struct HandRecord {
+    /// 1-based position in the session
+    pub number: usize,
+    /// Rules the round was dealt under
+    pub rules: StrategyVariables,
+    pub dealer: Hand,
+    pub hands: Vec<PlayerHand>,
+    pub decisions: Vec<Decision>,
+    pub insurance: Option<InsuranceDecision>,
+    pub insurance_net: f64,
+    pub net: f64,
+
+    /// Hi-Lo counts when the bets were placed
+    pub running_count: i32,
+    pub true_count: f64,
+}
+
+impl HandRecord {
+    pub fn total_bet(&self) -> f64 {
+        self.hands.iter().map(|player| player.bet).sum()
+    }
+
+    /// Steps when replaying the hand: one per decision, then the result.
+    pub fn replay_steps(&self) -> usize {
+        self.decisions.len() + 1
+    }
+}
+
+/// A point in a session replay: a hand, and a step within that hand.
+/// Steps before `decisions.len()` show a decision, the last step the result.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReplayPosition {
+    pub hand: usize,
+    pub step: usize,
+}
+
+/// Rounds played during the current session, oldest first.
+#[derive(Debug, Default)]
+pub struct HandHistory {
+    records: Vec<HandRecord>,
+}
+
+impl HandHistory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a finished round along with the count it was bet at.
+    pub fn record(&mut self, round: &Round, running_count: i32, true_count: f64) {
+        self.records.push(HandRecord {
+            number: self.records.len() + 1,
+            rules: round.rules().clone(),
+            dealer: round.dealer().clone(),
+            hands: round.hands().to_vec(),
+            decisions: round.decisions().to_vec(),
+            insurance: round.insurance(),
+            insurance_net: round.insurance_net(),
+            net: round.net_result(),
+            running_count,
+            true_count,
+        });
+    }
+
+    pub fn records(&self) -> &[HandRecord] {
+        &self.records
+    }
+
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    /// The next step of the replay, moving on to the following hand after
+    /// a hand's result. Stays put at the end of the session.
+    pub fn step_forward(&self, position: ReplayPosition) -> ReplayPosition {
+        let Some(record) = self.records.get(position.hand) else {
+            return position;
+        };
+        if position.step + 1 < record.replay_steps() {
+            ReplayPosition { hand: position.hand, step: position.step + 1 }
+        } else if position.hand + 1 < self.records.len() {
+            ReplayPosition { hand: position.hand + 1, step: 0 }
+        } else {
+            position
+        }
+    }
+
+    /// The previous step of the replay, back into the prior hand's result.
+    pub fn step_back(&self, position: ReplayPosition) -> ReplayPosition {
+        if position.step > 0 {
+            ReplayPosition { hand: position.hand, step: position.step - 1 }
+        } else if position.hand > 0 {
+            let hand = position.hand - 1;
+            ReplayPosition { hand, step: self.records[hand].replay_steps() - 1 }
+        } else {
+            position
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_round() {
+        let round = Round::sample();
+
+        let mut history = HandHistory::new();
+        history.record(&round, 2, 1.5);
+
+        let record = &history.records()[0];
+        assert_eq!(record.number, 1);
+        assert_eq!(record.decisions.len(), 2);
+        assert_eq!(record.hands[0].hand.total(), 20);
+        assert_eq!(record.total_bet(), 10.0);
+        assert_eq!(record.net, 10.0);
+        assert_eq!(record.running_count, 2);
+
+        // Replay walks both decisions, then the result, then stops
+        let mut position = ReplayPosition { hand: 0, step: 0 };
+        for step in 1..3 {
+            position = history.step_forward(position);
+            assert_eq!(position, ReplayPosition { hand: 0, step });
+        }
+        assert_eq!(history.step_forward(position), position);
+        assert_eq!(history.step_back(position), ReplayPosition { hand: 0, step: 1 });
+
+        // Crossing into the next hand and back again
+        history.record(&round, 0, 0.0);
+        position = history.step_forward(position);
+        assert_eq!(position, ReplayPosition { hand: 1, step: 0 });
+        assert_eq!(history.step_back(position), ReplayPosition { hand: 0, step: 2 });
+    }
+}
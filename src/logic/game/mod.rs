@@ -0,0 +1,13 @@
+pub mod advisor;
+pub mod animation;
+pub mod bankroll;
+pub mod card;
+pub mod hand;
+pub mod history;
+pub mod practice;
+pub mod round;
+pub mod scenario;
+pub mod seat;
+pub mod shared_table;
+pub mod shoe;
+pub mod table;
\ No newline at end of file
@@ -0,0 +1,84 @@
+use crate::logic::game::round::Round;
+use crate::logic::game::shoe::Shoe;
+
+/// The round and shoe as they stood before each decision of a practice
+/// round, so a decision can be taken back and played differently.
+///
+/// The shoe is kept with the round so a retried decision draws the same
+/// cards the original one did.
+#[derive(Default)]
+pub struct PracticeHistory {
+    snapshots: Vec<(Round, Shoe)>,
+}
+
+impl PracticeHistory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Forgets the previous round, called when a new one is dealt.
+    pub fn clear(&mut self) {
+        self.snapshots.clear();
+    }
+
+    /// Remembers the table before a decision is made.
+    pub fn record(&mut self, round: &Round, shoe: &Shoe) {
+        self.snapshots.push((round.clone(), shoe.clone()));
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.snapshots.is_empty()
+    }
+
+    /// The table before the most recent decision.
+    pub fn undo(&mut self) -> Option<(Round, Shoe)> {
+        self.snapshots.pop()
+    }
+
+    /// The table as it was first dealt, before any decision.
+    pub fn redeal(&mut self) -> Option<(Round, Shoe)> {
+        self.snapshots.truncate(1);
+        self.snapshots.pop()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logic::game::card::{Card, Rank, Suit};
+    use crate::logic::game::round::PlayerAction;
+    use crate::logic::game::table::TableConfig;
+    use crate::logic::strategy_calculator_logic::StrategyVariables;
+
+    #[test]
+    fn test_undo_and_redeal_replay_the_same_cards() {
+        let rules = StrategyVariables::single_deck();
+        // Player 5,7 against a dealer 10,7, then a 2 and a 9 to draw
+        let cards = [Rank::Five, Rank::Ten, Rank::Seven, Rank::Seven, Rank::Two, Rank::Nine]
+            .iter()
+            .map(|rank| Card::new(*rank, Suit::Clubs))
+            .collect();
+        let mut shoe = Shoe::stacked(cards);
+        let mut round = Round::deal(&rules, &TableConfig::default(), &[10.0], &mut shoe);
+        let mut history = PracticeHistory::new();
+        assert!(!history.can_undo());
+
+        history.record(&round, &shoe);
+        round.apply(PlayerAction::Hit, &mut shoe).unwrap();
+        history.record(&round, &shoe);
+        round.apply(PlayerAction::Hit, &mut shoe).unwrap();
+        assert_eq!(round.hands()[0].hand.total(), 23);
+
+        // Taking back the second hit leaves the 9 on top of the shoe
+        (round, shoe) = history.undo().unwrap();
+        assert_eq!(round.hands()[0].hand.total(), 14);
+        round.apply(PlayerAction::Stand, &mut shoe).unwrap();
+        assert_eq!(round.net_result(), -10.0);
+
+        (round, shoe) = history.redeal().unwrap();
+        assert_eq!(round.hands()[0].hand.total(), 12);
+        assert!(!history.can_undo());
+        round.apply(PlayerAction::Hit, &mut shoe).unwrap();
+        assert_eq!(round.hands()[0].hand.total(), 14);
+    }
+}
@@ -0,0 +1,1106 @@
+use std::fmt;
+use serde::{Deserialize, Serialize};
+use crate::logic::game::card::Card;
+use crate::logic::game::hand::Hand;
+use crate::logic::game::shoe::Shoe;
+use crate::logic::game::table::TableConfig;
+use crate::logic::strategy_calculator_logic::{GameVariant, StrategyVariables, SurrenderRule};
+
+/// Decisions the player can make on their active hand.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PlayerAction {
+    Hit,
+    Stand,
+    Double,
+    Split,
+    Surrender,
+}
+
+impl fmt::Display for PlayerAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            PlayerAction::Hit => "Hit",
+            PlayerAction::Stand => "Stand",
+            PlayerAction::Double => "Double",
+            PlayerAction::Split => "Split",
+            PlayerAction::Surrender => "Surrender",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl PlayerAction {
+    /// Past tense of the action, for describing decisions already made.
+    pub fn past_tense(self) -> &'static str {
+        match self {
+            PlayerAction::Hit => "hit",
+            PlayerAction::Stand => "stood",
+            PlayerAction::Double => "doubled",
+            PlayerAction::Split => "split",
+            PlayerAction::Surrender => "surrendered",
+        }
+    }
+}
+
+/// Where a single player hand is in its lifecycle.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum HandStatus {
+    /// Still waiting on decisions
+    Playing,
+    Stood,
+    Doubled,
+    Busted,
+    Blackjack,
+    Surrendered,
+}
+
+/// Final result of a player hand once the dealer has been resolved.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum HandOutcome {
+    /// Natural 21, paid 3:2
+    Blackjack,
+    Win,
+    Push,
+    Lose,
+    /// Half the bet returned
+    Surrender,
+}
+
+impl HandOutcome {
+    /// Amount won or lost per unit bet.
+    pub fn multiplier(self) -> f64 {
+        match self {
+            HandOutcome::Blackjack => 1.5,
+            HandOutcome::Win => 1.0,
+            HandOutcome::Push => 0.0,
+            HandOutcome::Lose => -1.0,
+            HandOutcome::Surrender => -0.5,
+        }
+    }
+}
+
+impl fmt::Display for HandOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            HandOutcome::Blackjack => "Blackjack",
+            HandOutcome::Win => "Win",
+            HandOutcome::Push => "Push",
+            HandOutcome::Lose => "Lose",
+            HandOutcome::Surrender => "Surrender",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// A player hand together with the money riding on it.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PlayerHand {
+    pub hand: Hand,
+
+    /// Betting spot the hand belongs to, split hands share their spot
+    pub spot: usize,
+
+    /// Total wager on this hand, including any double
+    pub bet: f64,
+
+    /// Part of `bet` placed with Free Bet tokens, paid on a win but never lost
+    pub free_bet: f64,
+
+    pub status: HandStatus,
+
+    /// Whether this hand was created by splitting a pair
+    pub is_split: bool,
+
+    /// Split aces receive a single card and may only be split again
+    pub split_aces: bool,
+
+    /// Set once the round has been settled
+    pub outcome: Option<HandOutcome>,
+}
+
+impl PlayerHand {
+    fn new(spot: usize, bet: f64, is_split: bool) -> Self {
+        Self {
+            hand: Hand::new(),
+            spot,
+            bet,
+            free_bet: 0.0,
+            status: HandStatus::Playing,
+            is_split,
+            split_aces: false,
+            outcome: None,
+        }
+    }
+
+    /// Net money won (positive) or lost (negative) on this hand.
+    pub fn net_result(&self) -> f64 {
+        self.outcome.map_or(0.0, |outcome| {
+            let multiplier = outcome.multiplier();
+            if multiplier < 0.0 {
+                multiplier * self.paid_bet()
+            } else {
+                multiplier * self.bet
+            }
+        })
+    }
+
+    /// The part of the bet paid for with the player's own money.
+    pub fn paid_bet(&self) -> f64 {
+        self.bet - self.free_bet
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum RoundPhase {
+    /// The dealer shows an Ace and the player must accept or decline insurance
+    Insurance,
+    /// The player is acting on their hands
+    PlayerTurn,
+    /// All hands are settled
+    Complete,
+}
+
+/// The player's answer to an insurance offer.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub struct InsuranceDecision {
+    pub taken: bool,
+
+    /// Whether the decision matches the recommended play
+    pub correct: bool,
+}
+
+/// A decision the player made, along with the situation it was made in.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Decision {
+    /// The hand as it was before acting
+    pub hand: Hand,
+    pub upcard: Card,
+    pub action: PlayerAction,
+
+    /// Every action that was legal at the time
+    pub available: Vec<PlayerAction>,
+}
+
+impl fmt::Display for Decision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let verb = match self.action {
+            PlayerAction::Stand => "stood on",
+            action => action.past_tense(),
+        };
+        write!(f, "{} {} vs {}", verb, self.hand.chart_label(), self.upcard.chart_label())
+    }
+}
+
+/// A single round of blackjack: one dealer hand against the player's hand(s),
+/// which may be spread over several betting spots.
+///
+/// The round only knows about the rules it was dealt with. Cards are drawn
+/// from a `Shoe` owned by the caller so the same shoe carries across rounds.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Round {
+    rules: StrategyVariables,
+    table: TableConfig,
+    dealer: Hand,
+    hands: Vec<PlayerHand>,
+    active_hand: usize,
+    phase: RoundPhase,
+
+    /// Insurance side bet placed on each spot, zero where it was declined
+    insurance_bets: Vec<f64>,
+    insurance: Option<InsuranceDecision>,
+    /// The count's insurance index and the true count when it was offered,
+    /// when the player is counting
+    #[serde(default)]
+    insurance_index: Option<(i32, f64)>,
+
+    /// Every decision applied this round, in order
+    decisions: Vec<Decision>,
+}
+
+impl Round {
+    /// Deals a new round with one hand per bet and resolves any immediate
+    /// blackjacks. Spots are dealt and played in the order given.
+    pub fn deal(rules: &StrategyVariables, table: &TableConfig, bets: &[f64], shoe: &mut Shoe) -> Self {
+        let mut hands: Vec<PlayerHand> = bets.iter()
+            .enumerate()
+            .map(|(spot, bet)| PlayerHand::new(spot, *bet, false))
+            .collect();
+        let mut dealer = Hand::new();
+
+        // One card to each spot then the dealer, twice. Without a peek the
+        // dealer plays European no hole card rules and takes a single card.
+        for round in 0..2 {
+            for player in hands.iter_mut() {
+                player.hand.add(shoe.draw());
+            }
+            if round == 0 || rules.dealer_peak {
+                dealer.add(shoe.draw());
+            }
+        }
+
+        for player in hands.iter_mut() {
+            if player.hand.is_natural() {
+                player.status = HandStatus::Blackjack;
+            }
+        }
+
+        let mut round = Self {
+            rules: rules.clone(),
+            table: table.clone(),
+            dealer,
+            hands,
+            active_hand: 0,
+            phase: RoundPhase::PlayerTurn,
+            insurance_bets: vec![0.0; bets.len()],
+            insurance: None,
+            insurance_index: None,
+            decisions: Vec::new(),
+        };
+
+        if round.dealer.cards()[0].is_ace() {
+            round.phase = RoundPhase::Insurance;
+        } else {
+            round.start_player_turn(shoe);
+        }
+
+        round
+    }
+
+    /// Basic strategy never takes insurance, it only pays when the remaining
+    /// cards are rich enough in tens, which requires a count. With a count's
+    /// index it's taken once the true count reaches the index.
+    pub fn insurance_recommended(&self) -> bool {
+        self.insurance_index.is_some_and(|(index, true_count)| true_count >= index as f64)
+    }
+
+    /// Grades the insurance offer against a count's index, e.g. +3 for
+    /// Hi-Lo, at the true count the player saw, rather than basic strategy.
+    pub fn set_insurance_index(&mut self, index: i32, true_count: f64) {
+        self.insurance_index = Some((index, true_count));
+    }
+
+    /// Accepts or declines the insurance offer for every spot. Insurance costs
+    /// half of each original bet and pays 2:1 if the dealer has blackjack.
+    pub fn resolve_insurance(&mut self, take: bool, shoe: &mut Shoe) -> Result<(), String> {
+        self.resolve_insurance_by_spot(&vec![take; self.insurance_bets.len()], shoe)
+    }
+
+    /// Answers the insurance offer separately for each spot, for tables where
+    /// the spots belong to different players.
+    pub fn resolve_insurance_by_spot(&mut self, takes: &[bool], shoe: &mut Shoe) -> Result<(), String> {
+        if self.phase != RoundPhase::Insurance {
+            return Err("Insurance is not being offered".to_string());
+        }
+        if takes.len() != self.insurance_bets.len() {
+            return Err(format!("Expected {} insurance answers, got {}", self.insurance_bets.len(), takes.len()));
+        }
+
+        for player in &self.hands {
+            if takes[player.spot] {
+                self.insurance_bets[player.spot] = player.bet / 2.0;
+            }
+        }
+        let recommended = self.insurance_recommended();
+        self.insurance = Some(InsuranceDecision {
+            taken: takes.iter().any(|take| *take),
+            correct: takes.iter().all(|take| *take == recommended),
+        });
+
+        self.phase = RoundPhase::PlayerTurn;
+        self.start_player_turn(shoe);
+        Ok(())
+    }
+
+    pub fn insurance(&self) -> Option<InsuranceDecision> {
+        self.insurance
+    }
+
+    /// With peek, a dealer blackjack ends the round before the player acts.
+    /// Player blackjacks need no decisions, so play starts on the first other hand.
+    fn start_player_turn(&mut self, shoe: &mut Shoe) {
+        let dealer_peeks = self.rules.dealer_peak && self.dealer_upcard_may_be_blackjack();
+        if dealer_peeks && self.dealer.is_natural() {
+            self.settle();
+            return;
+        }
+
+        match self.hands.iter().position(|player| player.status == HandStatus::Playing) {
+            Some(index) => self.active_hand = index,
+            None => {
+                self.play_dealer(shoe);
+                self.settle();
+            }
+        }
+    }
+
+    /// Whether the dealer took a hole card, false under European no hole card rules.
+    pub fn dealer_has_hole_card(&self) -> bool {
+        self.rules.dealer_peak
+    }
+
+    /// Whether the dealer peeks under the hole card for blackjack before the
+    /// player acts, which only happens with an Ace or ten showing.
+    pub fn dealer_checks_for_blackjack(&self) -> bool {
+        self.rules.dealer_peak && self.dealer_upcard_may_be_blackjack()
+    }
+
+    pub fn rules(&self) -> &StrategyVariables {
+        &self.rules
+    }
+
+    pub fn phase(&self) -> RoundPhase {
+        self.phase
+    }
+
+    pub fn dealer(&self) -> &Hand {
+        &self.dealer
+    }
+
+    pub fn hands(&self) -> &[PlayerHand] {
+        &self.hands
+    }
+
+    pub fn decisions(&self) -> &[Decision] {
+        &self.decisions
+    }
+
+    /// Index of the hand currently being played, if the player is still acting.
+    pub fn active_hand_index(&self) -> Option<usize> {
+        match self.phase {
+            RoundPhase::PlayerTurn => Some(self.active_hand),
+            RoundPhase::Insurance | RoundPhase::Complete => None,
+        }
+    }
+
+    fn dealer_upcard_may_be_blackjack(&self) -> bool {
+        let upcard = self.dealer.cards()[0];
+        upcard.is_ace() || upcard.value() == 10
+    }
+
+    /// Whether `action` is legal for the active hand under the round's rules.
+    pub fn can(&self, action: PlayerAction) -> bool {
+        let Some(index) = self.active_hand_index() else {
+            return false;
+        };
+        let player = &self.hands[index];
+        if player.status != HandStatus::Playing {
+            return false;
+        }
+
+        match action {
+            PlayerAction::Stand => true,
+            PlayerAction::Hit => !player.split_aces,
+            PlayerAction::Double => {
+                !player.split_aces
+                    && player.hand.len() == 2
+                    && (!player.is_split || self.rules.double_after_split)
+            }
+            PlayerAction::Split => self.can_split(player),
+            PlayerAction::Surrender => {
+                self.hands_in_spot(player.spot) == 1 && player.hand.len() == 2 && self.surrender_allowed()
+            }
+        }
+    }
+
+    /// Late surrender against the dealer upcard under the round's surrender rule.
+    fn surrender_allowed(&self) -> bool {
+        match self.rules.surrender_allowed {
+            SurrenderRule::NotAllowed => false,
+            SurrenderRule::AnyUpcard => true,
+            SurrenderRule::Dealer2Through10 => !self.dealer.cards()[0].is_ace(),
+        }
+    }
+
+    fn hands_in_spot(&self, spot: usize) -> usize {
+        self.hands.iter().filter(|player| player.spot == spot).count()
+    }
+
+    fn can_split(&self, player: &PlayerHand) -> bool {
+        if !player.hand.is_pair() || self.hands_in_spot(player.spot) >= self.table.max_split_hands as usize {
+            return false;
+        }
+        let aces = player.hand.cards()[0].is_ace();
+        !(aces && player.is_split && !self.table.resplit_aces)
+    }
+
+    /// Actions that are currently legal, in the order they are usually offered.
+    pub fn available_actions(&self) -> Vec<PlayerAction> {
+        [PlayerAction::Hit, PlayerAction::Stand, PlayerAction::Double, PlayerAction::Split, PlayerAction::Surrender]
+            .into_iter()
+            .filter(|action| self.can(*action))
+            .collect()
+    }
+
+    /// Applies a player decision to the active hand.
+    pub fn apply(&mut self, action: PlayerAction, shoe: &mut Shoe) -> Result<(), String> {
+        if !self.can(action) {
+            return Err(format!("{} is not allowed right now", action));
+        }
+
+        let index = self.active_hand;
+        self.decisions.push(Decision {
+            hand: self.hands[index].hand.clone(),
+            upcard: self.dealer.cards()[0],
+            action,
+            available: self.available_actions(),
+        });
+        match action {
+            PlayerAction::Hit => {
+                let player = &mut self.hands[index];
+                player.hand.add(shoe.draw());
+                if player.hand.is_bust() {
+                    player.status = HandStatus::Busted;
+                } else if player.hand.total() == 21 {
+                    player.status = HandStatus::Stood;
+                }
+            }
+            PlayerAction::Stand => {
+                self.hands[index].status = HandStatus::Stood;
+            }
+            PlayerAction::Double => {
+                let free = self.is_free(action);
+                let player = &mut self.hands[index];
+                if free {
+                    player.free_bet += player.bet;
+                }
+                player.bet *= 2.0;
+                player.hand.add(shoe.draw());
+                player.status = if player.hand.is_bust() {
+                    HandStatus::Busted
+                } else {
+                    HandStatus::Doubled
+                };
+            }
+            PlayerAction::Split => self.split(shoe),
+            PlayerAction::Surrender => {
+                self.hands[index].status = HandStatus::Surrendered;
+            }
+        }
+
+        self.advance(shoe);
+        Ok(())
+    }
+
+    fn split(&mut self, shoe: &mut Shoe) {
+        let index = self.active_hand;
+        let free = self.is_free(PlayerAction::Split);
+        let bet = self.hands[index].bet;
+        let spot = self.hands[index].spot;
+        let moved = self.hands[index].hand.take_last().expect("split requires a pair");
+        let splitting_aces = moved.is_ace();
+
+        let first = &mut self.hands[index];
+        first.is_split = true;
+        first.hand.add(shoe.draw());
+
+        let mut second = PlayerHand::new(spot, bet, true);
+        if free {
+            second.free_bet = bet;
+        }
+        second.hand.add(moved);
+        second.hand.add(shoe.draw());
+        self.hands.insert(index + 1, second);
+
+        // Split aces receive one card each and stand unless they can be split again
+        if splitting_aces {
+            for i in [index, index + 1] {
+                self.hands[i].split_aces = true;
+                if !self.can_split(&self.hands[i]) {
+                    self.hands[i].status = HandStatus::Stood;
+                }
+            }
+        }
+    }
+
+    /// Moves to the next unfinished hand, or plays the dealer and settles
+    /// once every hand is finished.
+    fn advance(&mut self, shoe: &mut Shoe) {
+        while self.active_hand < self.hands.len() {
+            let player = &mut self.hands[self.active_hand];
+            if player.status == HandStatus::Playing {
+                if player.hand.total() == 21 {
+                    player.status = HandStatus::Stood;
+                } else {
+                    return;
+                }
+            }
+            self.active_hand += 1;
+        }
+
+        self.play_dealer(shoe);
+        self.settle();
+    }
+
+    fn dealer_must_hit(&self) -> bool {
+        let total = self.dealer.total();
+        total < 17 || (total == 17 && self.dealer.is_soft() && !self.rules.dealer_stands_on_soft_17)
+    }
+
+    fn play_dealer(&mut self, shoe: &mut Shoe) {
+        // Under ENHC the dealer's second card is only dealt now, it is always
+        // needed to settle blackjacks and insurance
+        if self.dealer.len() == 1 {
+            self.dealer.add(shoe.draw());
+        }
+
+        // The dealer only draws further if some hand could still beat them
+        let any_live_hand = self.hands.iter().any(|player| {
+            !matches!(player.status, HandStatus::Busted | HandStatus::Blackjack | HandStatus::Surrendered)
+        });
+        if !any_live_hand || self.dealer.is_natural() {
+            return;
+        }
+
+        while self.dealer_must_hit() {
+            self.dealer.add(shoe.draw());
+        }
+    }
+
+    fn settle(&mut self) {
+        let dealer_total = self.dealer.total();
+        let dealer_blackjack = self.dealer.is_natural();
+        // Free Bet pays for its free doubles and splits by pushing a dealer 22
+        let dealer_pushes = self.rules.variant == GameVariant::FreeBet && dealer_total == 22;
+
+        for player in self.hands.iter_mut() {
+            let outcome = if player.status == HandStatus::Busted {
+                HandOutcome::Lose
+            } else if player.status == HandStatus::Surrendered {
+                // Late surrender is only honoured once the dealer is known not to have
+                // blackjack, without a peek the whole bet is lost to a dealer blackjack
+                if dealer_blackjack { HandOutcome::Lose } else { HandOutcome::Surrender }
+            } else if player.status == HandStatus::Blackjack {
+                if dealer_blackjack { HandOutcome::Push } else { HandOutcome::Blackjack }
+            } else if dealer_blackjack {
+                // Without a peek the player loses every wager, including doubles and splits
+                HandOutcome::Lose
+            } else if dealer_pushes {
+                HandOutcome::Push
+            } else if self.dealer.is_bust() || player.hand.total() > dealer_total {
+                HandOutcome::Win
+            } else if player.hand.total() == dealer_total {
+                HandOutcome::Push
+            } else {
+                HandOutcome::Lose
+            };
+            player.outcome = Some(outcome);
+        }
+
+        self.phase = RoundPhase::Complete;
+    }
+
+    /// Total money on the table across every hand and the insurance bet.
+    /// Free Bet tokens aren't the player's money and don't count.
+    pub fn total_wagered(&self) -> f64 {
+        self.hands.iter().map(|player| player.paid_bet()).sum::<f64>() + self.insurance_bets.iter().sum::<f64>()
+    }
+
+    /// Money on the table for a single spot, including its insurance.
+    pub fn spot_wagered(&self, spot: usize) -> f64 {
+        self.hands.iter()
+            .filter(|player| player.spot == spot)
+            .map(|player| player.paid_bet())
+            .sum::<f64>() + self.insurance_bets.get(spot).copied().unwrap_or(0.0)
+    }
+
+    /// Whether doubling or splitting the active hand would be on the house.
+    /// Free Bet gives free doubles on hard 9-11 and free splits on every pair but tens.
+    pub fn is_free(&self, action: PlayerAction) -> bool {
+        let Some(index) = self.active_hand_index() else {
+            return false;
+        };
+        if self.rules.variant != GameVariant::FreeBet {
+            return false;
+        }
+
+        let hand = &self.hands[index].hand;
+        match action {
+            PlayerAction::Double => hand.len() == 2 && !hand.is_soft() && (9..=11).contains(&hand.total()),
+            PlayerAction::Split => hand.is_pair() && hand.cards()[0].value() != 10,
+            _ => false,
+        }
+    }
+
+    /// Money a double or split on the active hand would add, zero when it's free.
+    pub fn action_cost(&self, action: PlayerAction) -> Option<f64> {
+        let index = self.active_hand_index()?;
+        if self.is_free(action) {
+            Some(0.0)
+        } else {
+            Some(self.hands[index].bet)
+        }
+    }
+
+    /// Net money won or lost on the insurance side bet.
+    pub fn insurance_net(&self) -> f64 {
+        (0..self.insurance_bets.len()).map(|spot| self.spot_insurance_net(spot)).sum()
+    }
+
+    fn spot_insurance_net(&self, spot: usize) -> f64 {
+        let bet = self.insurance_bets.get(spot).copied().unwrap_or(0.0);
+        if self.phase != RoundPhase::Complete {
+            0.0
+        } else if self.dealer.is_natural() {
+            bet * 2.0
+        } else {
+            -bet
+        }
+    }
+
+    /// Net money won or lost across every hand in the round, including insurance.
+    pub fn net_result(&self) -> f64 {
+        self.hands.iter().map(|player| player.net_result()).sum::<f64>() + self.insurance_net()
+    }
+
+    /// Net money won or lost on a single spot, including its insurance.
+    pub fn spot_net(&self, spot: usize) -> f64 {
+        self.hands.iter()
+            .filter(|player| player.spot == spot)
+            .map(|player| player.net_result())
+            .sum::<f64>() + self.spot_insurance_net(spot)
+    }
+
+    /// A copy of the round that is safe to show other players: while the
+    /// round is being played the hole card is swapped for a copy of the
+    /// upcard, it is face down either way but can't be read off the wire.
+    pub fn without_hole_card(&self) -> Self {
+        let mut round = self.clone();
+        if round.phase != RoundPhase::Complete && round.dealer.len() == 2 {
+            let upcard = round.dealer.cards()[0];
+            round.dealer = Hand::new();
+            round.dealer.add(upcard);
+            round.dealer.add(upcard);
+        }
+        round
+    }
+
+    /// Spot of the hand currently being played.
+    pub fn active_spot(&self) -> Option<usize> {
+        self.active_hand_index().map(|index| self.hands[index].spot)
+    }
+}
+
+#[cfg(test)]
+impl Round {
+    /// A finished $10 round on a single deck: 10,6 hit to 20 and stood on
+    /// against the dealer's 17.
+    pub fn sample() -> Self {
+        use crate::logic::game::card::{Rank, Suit};
+
+        let mut shoe = Shoe::stacked(
+            [Rank::Ten, Rank::Ten, Rank::Six, Rank::Seven, Rank::Four]
+                .iter()
+                .map(|rank| Card::new(*rank, Suit::Hearts))
+                .collect(),
+        );
+        let mut round = Self::deal(&StrategyVariables::single_deck(), &TableConfig::default(), &[10.0], &mut shoe);
+        round.apply(PlayerAction::Hit, &mut shoe).unwrap();
+        round.apply(PlayerAction::Stand, &mut shoe).unwrap();
+        round
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logic::game::card::{Card, Rank, Suit};
+    use crate::logic::strategy_calculator_logic::SurrenderRule;
+
+    /// Builds a shoe dealing the given ranks in order: player, dealer, player, dealer, ...
+    fn shoe_of(ranks: &[Rank]) -> Shoe {
+        Shoe::stacked(ranks.iter().map(|rank| Card::new(*rank, Suit::Hearts)).collect())
+    }
+
+    #[test]
+    fn test_stand_and_dealer_draws_to_17() {
+        let mut shoe = shoe_of(&[Rank::Ten, Rank::Ten, Rank::Nine, Rank::Six, Rank::Two]);
+        let mut round = Round::deal(&StrategyVariables::single_deck(), &TableConfig::default(), &[10.0], &mut shoe);
+        round.apply(PlayerAction::Stand, &mut shoe).unwrap();
+
+        assert_eq!(round.phase(), RoundPhase::Complete);
+        assert_eq!(round.dealer().total(), 18);
+        assert_eq!(round.hands()[0].outcome, Some(HandOutcome::Win));
+        assert_eq!(round.net_result(), 10.0);
+    }
+
+    #[test]
+    fn test_dealer_hits_soft_17_when_h17() {
+        let mut h17 = StrategyVariables::single_deck();
+        h17.dealer_stands_on_soft_17 = false;
+        let mut shoe = shoe_of(&[Rank::Ten, Rank::Ace, Rank::Eight, Rank::Six, Rank::Five]);
+        let mut round = Round::deal(&h17, &TableConfig::default(), &[10.0], &mut shoe);
+        round.resolve_insurance(false, &mut shoe).unwrap();
+        round.apply(PlayerAction::Stand, &mut shoe).unwrap();
+
+        // Soft 17 + 5 = hard 12, which must be hit again from the reshuffled shoe
+        assert!(round.dealer().len() > 3);
+    }
+
+    #[test]
+    fn test_double_doubles_bet_and_takes_one_card() {
+        let mut shoe = shoe_of(&[Rank::Six, Rank::Ten, Rank::Five, Rank::Seven, Rank::Ten]);
+        let mut round = Round::deal(&StrategyVariables::single_deck(), &TableConfig::default(), &[10.0], &mut shoe);
+        round.apply(PlayerAction::Double, &mut shoe).unwrap();
+
+        assert_eq!(round.hands()[0].hand.total(), 21);
+        assert_eq!(round.hands()[0].bet, 20.0);
+        assert_eq!(round.net_result(), 20.0);
+    }
+
+    #[test]
+    fn test_player_blackjack_pays_three_to_two() {
+        let mut shoe = shoe_of(&[Rank::Ace, Rank::Nine, Rank::King, Rank::Seven]);
+        let round = Round::deal(&StrategyVariables::single_deck(), &TableConfig::default(), &[10.0], &mut shoe);
+
+        assert_eq!(round.phase(), RoundPhase::Complete);
+        assert_eq!(round.net_result(), 15.0);
+    }
+
+    #[test]
+    fn test_dealer_peek_ends_round() {
+        let mut shoe = shoe_of(&[Rank::Ten, Rank::Ace, Rank::Nine, Rank::King]);
+        let mut round = Round::deal(&StrategyVariables::single_deck(), &TableConfig::default(), &[10.0], &mut shoe);
+        assert_eq!(round.phase(), RoundPhase::Insurance);
+        round.resolve_insurance(false, &mut shoe).unwrap();
+
+        assert_eq!(round.phase(), RoundPhase::Complete);
+        assert_eq!(round.net_result(), -10.0);
+    }
+
+    #[test]
+    fn test_no_peek_loses_double_to_dealer_blackjack() {
+        let mut no_peek = StrategyVariables::single_deck();
+        no_peek.dealer_peak = false;
+        let mut shoe = shoe_of(&[Rank::Six, Rank::Ace, Rank::Five, Rank::Ten, Rank::King]);
+        let mut round = Round::deal(&no_peek, &TableConfig::default(), &[10.0], &mut shoe);
+        assert!(!round.dealer_has_hole_card());
+        assert_eq!(round.dealer().len(), 1);
+        round.resolve_insurance(false, &mut shoe).unwrap();
+        assert_eq!(round.phase(), RoundPhase::PlayerTurn);
+
+        round.apply(PlayerAction::Double, &mut shoe).unwrap();
+        assert_eq!(round.net_result(), -20.0);
+    }
+
+    #[test]
+    fn test_split_plays_each_hand() {
+        let mut shoe = shoe_of(&[
+            Rank::Eight, Rank::Ten, Rank::Eight, Rank::Seven,
+            Rank::Three, Rank::Ten, Rank::Ten,
+        ]);
+        let mut round = Round::deal(&StrategyVariables::single_deck(), &TableConfig::default(), &[10.0], &mut shoe);
+        round.apply(PlayerAction::Split, &mut shoe).unwrap();
+        assert_eq!(round.hands().len(), 2);
+        assert!(!round.can(PlayerAction::Split));
+
+        // First hand: 8,3 doubles into 8,3,10
+        round.apply(PlayerAction::Double, &mut shoe).unwrap();
+        assert_eq!(round.active_hand_index(), Some(1));
+
+        // Second hand: 8,10 stands
+        round.apply(PlayerAction::Stand, &mut shoe).unwrap();
+        assert_eq!(round.phase(), RoundPhase::Complete);
+        assert_eq!(round.hands()[0].outcome, Some(HandOutcome::Win));
+        assert_eq!(round.hands()[1].outcome, Some(HandOutcome::Win));
+        assert_eq!(round.net_result(), 30.0);
+    }
+
+    #[test]
+    fn test_split_twenty_one_is_not_blackjack() {
+        let mut shoe = shoe_of(&[
+            Rank::Ace, Rank::Ten, Rank::Ace, Rank::Eight,
+            Rank::King, Rank::Five,
+        ]);
+        let mut round = Round::deal(&StrategyVariables::single_deck(), &TableConfig::default(), &[10.0], &mut shoe);
+        round.apply(PlayerAction::Split, &mut shoe).unwrap();
+
+        assert_eq!(round.phase(), RoundPhase::Complete);
+        assert_eq!(round.hands()[0].outcome, Some(HandOutcome::Win));
+        assert_eq!(round.hands()[1].outcome, Some(HandOutcome::Lose));
+        assert_eq!(round.net_result(), 0.0);
+    }
+
+    #[test]
+    fn test_resplit_up_to_table_limit() {
+        let table = TableConfig { max_split_hands: 3, resplit_aces: false, ..TableConfig::default() };
+        let mut shoe = shoe_of(&[
+            Rank::Eight, Rank::Ten, Rank::Eight, Rank::Seven,
+            Rank::Eight, Rank::Eight, Rank::Eight, Rank::Two,
+        ]);
+        let mut round = Round::deal(&StrategyVariables::single_deck(), &table, &[10.0], &mut shoe);
+        round.apply(PlayerAction::Split, &mut shoe).unwrap();
+        round.apply(PlayerAction::Split, &mut shoe).unwrap();
+        assert_eq!(round.hands().len(), 3);
+        assert!(round.hands().iter().all(|player| player.bet == 10.0));
+
+        // 8,8 again but the table limit has been reached
+        assert!(round.hands()[0].hand.is_pair());
+        assert!(!round.can(PlayerAction::Split));
+    }
+
+    #[test]
+    fn test_split_aces_only_resplit_when_allowed() {
+        let deal = [
+            Rank::Ace, Rank::Nine, Rank::Ace, Rank::Seven,
+            Rank::Ace, Rank::Five,
+        ];
+
+        let mut shoe = shoe_of(&deal);
+        let mut round = Round::deal(&StrategyVariables::single_deck(), &TableConfig::default(), &[10.0], &mut shoe);
+        round.apply(PlayerAction::Split, &mut shoe).unwrap();
+        assert_eq!(round.phase(), RoundPhase::Complete);
+
+        let table = TableConfig { max_split_hands: 4, resplit_aces: true, ..TableConfig::default() };
+        let mut shoe = shoe_of(&deal);
+        let mut round = Round::deal(&StrategyVariables::single_deck(), &table, &[10.0], &mut shoe);
+        round.apply(PlayerAction::Split, &mut shoe).unwrap();
+        assert_eq!(round.active_hand_index(), Some(0));
+        assert_eq!(round.available_actions(), vec![PlayerAction::Stand, PlayerAction::Split]);
+    }
+
+    #[test]
+    fn test_insurance_pays_two_to_one_on_dealer_blackjack() {
+        let mut shoe = shoe_of(&[Rank::Ten, Rank::Ace, Rank::Nine, Rank::King]);
+        let mut round = Round::deal(&StrategyVariables::single_deck(), &TableConfig::default(), &[10.0], &mut shoe);
+        round.resolve_insurance(true, &mut shoe).unwrap();
+
+        // Lose the 10 main bet, win 2:1 on the 5 insurance bet
+        assert_eq!(round.phase(), RoundPhase::Complete);
+        assert_eq!(round.insurance_net(), 10.0);
+        assert_eq!(round.net_result(), 0.0);
+        assert_eq!(round.insurance(), Some(InsuranceDecision { taken: true, correct: false }));
+    }
+
+    #[test]
+    fn test_insurance_lost_when_dealer_has_no_blackjack() {
+        let mut shoe = shoe_of(&[Rank::Ten, Rank::Ace, Rank::Nine, Rank::Seven]);
+        let mut round = Round::deal(&StrategyVariables::single_deck(), &TableConfig::default(), &[10.0], &mut shoe);
+        assert!(round.available_actions().is_empty());
+        round.resolve_insurance(true, &mut shoe).unwrap();
+        round.apply(PlayerAction::Stand, &mut shoe).unwrap();
+
+        // 19 beats the dealer's soft 18, but the 5 insurance bet is lost
+        assert_eq!(round.insurance_net(), -5.0);
+        assert_eq!(round.net_result(), 5.0);
+    }
+
+    #[test]
+    fn test_insured_blackjack_is_even_money() {
+        let mut shoe = shoe_of(&[Rank::Ace, Rank::Ace, Rank::King, Rank::Six]);
+        let mut round = Round::deal(&StrategyVariables::single_deck(), &TableConfig::default(), &[10.0], &mut shoe);
+        round.resolve_insurance(true, &mut shoe).unwrap();
+
+        assert_eq!(round.net_result(), 10.0);
+    }
+
+    #[test]
+    fn test_surrender_returns_half_the_bet() {
+        let mut surrender = StrategyVariables::single_deck();
+        surrender.surrender_allowed = SurrenderRule::AnyUpcard;
+        let mut shoe = shoe_of(&[Rank::Ten, Rank::Ten, Rank::Six, Rank::Seven]);
+        let mut round = Round::deal(&surrender, &TableConfig::default(), &[10.0], &mut shoe);
+        round.apply(PlayerAction::Surrender, &mut shoe).unwrap();
+
+        assert_eq!(round.phase(), RoundPhase::Complete);
+        assert_eq!(round.hands()[0].outcome, Some(HandOutcome::Surrender));
+        assert_eq!(round.dealer().len(), 2);
+        assert_eq!(round.net_result(), -5.0);
+    }
+
+    #[test]
+    fn test_surrender_respects_rule() {
+        let mut shoe = shoe_of(&[Rank::Ten, Rank::Ten, Rank::Six, Rank::Seven]);
+        let round = Round::deal(&StrategyVariables::single_deck(), &TableConfig::default(), &[10.0], &mut shoe);
+        assert!(!round.can(PlayerAction::Surrender));
+
+        let mut two_through_ten = StrategyVariables::single_deck();
+        two_through_ten.surrender_allowed = SurrenderRule::Dealer2Through10;
+        let mut shoe = shoe_of(&[Rank::Ten, Rank::Ace, Rank::Six, Rank::Seven]);
+        let mut round = Round::deal(&two_through_ten, &TableConfig::default(), &[10.0], &mut shoe);
+        round.resolve_insurance(false, &mut shoe).unwrap();
+        assert!(!round.can(PlayerAction::Surrender));
+
+        let mut shoe = shoe_of(&[Rank::Ten, Rank::Ten, Rank::Six, Rank::Seven]);
+        let mut round = Round::deal(&two_through_ten, &TableConfig::default(), &[10.0], &mut shoe);
+        assert!(round.can(PlayerAction::Surrender));
+        round.apply(PlayerAction::Hit, &mut shoe).unwrap();
+        assert!(!round.can(PlayerAction::Surrender));
+    }
+
+    #[test]
+    fn test_surrender_without_peek_loses_to_dealer_blackjack() {
+        let mut no_peek = StrategyVariables::single_deck();
+        no_peek.dealer_peak = false;
+        no_peek.surrender_allowed = SurrenderRule::AnyUpcard;
+        let mut shoe = shoe_of(&[Rank::Ten, Rank::Ten, Rank::Six, Rank::Ace]);
+        let mut round = Round::deal(&no_peek, &TableConfig::default(), &[10.0], &mut shoe);
+        round.apply(PlayerAction::Surrender, &mut shoe).unwrap();
+
+        assert_eq!(round.hands()[0].outcome, Some(HandOutcome::Lose));
+        assert_eq!(round.net_result(), -10.0);
+    }
+
+    #[test]
+    fn test_multiple_spots_are_played_in_order() {
+        let mut shoe = shoe_of(&[
+            Rank::Ten, Rank::Ace, Rank::Seven,
+            Rank::Six, Rank::King, Rank::Ten,
+            Rank::Three,
+        ]);
+        let mut round = Round::deal(&StrategyVariables::single_deck(), &TableConfig::default(), &[10.0, 25.0], &mut shoe);
+
+        // Spot 1 has 10,6 and spot 2 has a blackjack, so play starts and ends on spot 1
+        assert_eq!(round.hands()[1].status, HandStatus::Blackjack);
+        assert_eq!(round.active_hand_index(), Some(0));
+        round.apply(PlayerAction::Hit, &mut shoe).unwrap();
+        assert_eq!(round.active_hand_index(), Some(0));
+        round.apply(PlayerAction::Stand, &mut shoe).unwrap();
+
+        // 19 beats the dealer's 17, and the blackjack pays 3:2
+        assert_eq!(round.phase(), RoundPhase::Complete);
+        assert_eq!(round.hands()[0].net_result(), 10.0);
+        assert_eq!(round.hands()[1].net_result(), 37.5);
+    }
+
+    #[test]
+    fn test_split_limit_applies_per_spot() {
+        let table = TableConfig { max_split_hands: 2, resplit_aces: false, ..TableConfig::default() };
+        let mut shoe = shoe_of(&[
+            Rank::Eight, Rank::Eight, Rank::Ten,
+            Rank::Eight, Rank::Eight, Rank::Seven,
+            Rank::Eight, Rank::Eight,
+        ]);
+        let mut round = Round::deal(&StrategyVariables::single_deck(), &table, &[10.0, 10.0], &mut shoe);
+        round.apply(PlayerAction::Split, &mut shoe).unwrap();
+        assert!(!round.can(PlayerAction::Split));
+        round.apply(PlayerAction::Stand, &mut shoe).unwrap();
+        round.apply(PlayerAction::Stand, &mut shoe).unwrap();
+
+        // The second spot may still split its own pair
+        assert_eq!(round.active_hand_index(), Some(2));
+        assert!(round.can(PlayerAction::Split));
+    }
+
+    #[test]
+    fn test_enhc_loses_splits_to_dealer_blackjack() {
+        let mut enhc = StrategyVariables::single_deck();
+        enhc.dealer_peak = false;
+        let mut shoe = shoe_of(&[
+            Rank::Eight, Rank::Ten, Rank::Eight,
+            Rank::Two, Rank::Three, Rank::Ace,
+        ]);
+        let mut round = Round::deal(&enhc, &TableConfig::default(), &[10.0], &mut shoe);
+        round.apply(PlayerAction::Split, &mut shoe).unwrap();
+        round.apply(PlayerAction::Stand, &mut shoe).unwrap();
+        round.apply(PlayerAction::Stand, &mut shoe).unwrap();
+
+        // The hole card is only dealt once the player has finished
+        assert_eq!(round.dealer().len(), 2);
+        assert!(round.dealer().is_natural());
+        assert_eq!(round.net_result(), -20.0);
+    }
+
+    #[test]
+    fn test_enhc_player_blackjack_waits_for_dealer_card() {
+        let mut enhc = StrategyVariables::single_deck();
+        enhc.dealer_peak = false;
+        let mut shoe = shoe_of(&[Rank::Ace, Rank::Ten, Rank::King, Rank::Ace]);
+        let round = Round::deal(&enhc, &TableConfig::default(), &[10.0], &mut shoe);
+
+        assert_eq!(round.phase(), RoundPhase::Complete);
+        assert_eq!(round.hands()[0].outcome, Some(HandOutcome::Push));
+    }
+
+    fn free_bet() -> StrategyVariables {
+        StrategyVariables { variant: GameVariant::FreeBet, ..StrategyVariables::single_deck() }
+    }
+
+    #[test]
+    fn test_free_double_only_risks_the_original_bet() {
+        let mut shoe = shoe_of(&[Rank::Six, Rank::Ten, Rank::Five, Rank::Seven, Rank::Two]);
+        let mut round = Round::deal(&free_bet(), &TableConfig::default(), &[10.0], &mut shoe);
+        assert!(round.is_free(PlayerAction::Double));
+        assert_eq!(round.action_cost(PlayerAction::Double), Some(0.0));
+        round.apply(PlayerAction::Double, &mut shoe).unwrap();
+
+        assert_eq!(round.hands()[0].bet, 20.0);
+        assert_eq!(round.hands()[0].free_bet, 10.0);
+        assert_eq!(round.hands()[0].outcome, Some(HandOutcome::Lose));
+        assert_eq!(round.net_result(), -10.0);
+    }
+
+    #[test]
+    fn test_free_split_pays_but_tens_cost() {
+        let mut shoe = shoe_of(&[Rank::Eight, Rank::Ten, Rank::Eight, Rank::Seven, Rank::Ten, Rank::Ten]);
+        let mut round = Round::deal(&free_bet(), &TableConfig::default(), &[10.0], &mut shoe);
+        round.apply(PlayerAction::Split, &mut shoe).unwrap();
+        assert_eq!(round.hands()[1].free_bet, 10.0);
+        assert_eq!(round.total_wagered(), 10.0);
+        round.apply(PlayerAction::Stand, &mut shoe).unwrap();
+        round.apply(PlayerAction::Stand, &mut shoe).unwrap();
+        assert_eq!(round.net_result(), 20.0);
+
+        let mut shoe = shoe_of(&[Rank::King, Rank::Ten, Rank::Ten, Rank::Seven]);
+        let round = Round::deal(&free_bet(), &TableConfig::default(), &[10.0], &mut shoe);
+        assert!(round.can(PlayerAction::Split));
+        assert!(!round.is_free(PlayerAction::Split));
+        assert_eq!(round.action_cost(PlayerAction::Split), Some(10.0));
+    }
+
+    #[test]
+    fn test_free_bet_dealer_22_pushes() {
+        let ranks = [Rank::Ten, Rank::Ten, Rank::Eight, Rank::Six, Rank::Six];
+        let mut shoe = shoe_of(&ranks);
+        let mut round = Round::deal(&free_bet(), &TableConfig::default(), &[10.0], &mut shoe);
+        round.apply(PlayerAction::Stand, &mut shoe).unwrap();
+        assert_eq!(round.dealer().total(), 22);
+        assert_eq!(round.hands()[0].outcome, Some(HandOutcome::Push));
+
+        // The same hand wins at a classic table
+        let mut shoe = shoe_of(&ranks);
+        let mut round = Round::deal(&StrategyVariables::single_deck(), &TableConfig::default(), &[10.0], &mut shoe);
+        round.apply(PlayerAction::Stand, &mut shoe).unwrap();
+        assert_eq!(round.hands()[0].outcome, Some(HandOutcome::Win));
+    }
+
+    #[test]
+    fn test_insurance_by_spot() {
+        let mut shoe = shoe_of(&[Rank::Ten, Rank::Nine, Rank::Ace, Rank::Eight, Rank::Nine, Rank::King]);
+        let mut round = Round::deal(&StrategyVariables::single_deck(), &TableConfig::default(), &[10.0, 20.0], &mut shoe);
+        assert!(round.resolve_insurance_by_spot(&[true], &mut shoe).is_err());
+        round.resolve_insurance_by_spot(&[false, true], &mut shoe).unwrap();
+
+        // The dealer has blackjack, only the second spot is covered
+        assert_eq!(round.phase(), RoundPhase::Complete);
+        assert_eq!(round.spot_net(0), -10.0);
+        assert_eq!(round.spot_net(1), 0.0);
+        assert!(!round.insurance().unwrap().correct);
+    }
+
+    #[test]
+    fn test_insurance_graded_against_the_count_index() {
+        // Below the index insurance is still declined
+        let mut shoe = shoe_of(&[Rank::Ten, Rank::Ace, Rank::Nine, Rank::Seven]);
+        let mut round = Round::deal(&StrategyVariables::single_deck(), &TableConfig::default(), &[10.0], &mut shoe);
+        round.set_insurance_index(3, 2.5);
+        assert!(!round.insurance_recommended());
+        round.resolve_insurance(true, &mut shoe).unwrap();
+        assert_eq!(round.insurance(), Some(InsuranceDecision { taken: true, correct: false }));
+
+        // At or above it, taking insurance is the right play
+        for true_count in [3.0, 4.5] {
+            let mut shoe = shoe_of(&[Rank::Ten, Rank::Ace, Rank::Nine, Rank::Seven]);
+            let mut round = Round::deal(&StrategyVariables::single_deck(), &TableConfig::default(), &[10.0], &mut shoe);
+            round.set_insurance_index(3, true_count);
+            assert!(round.insurance_recommended());
+            round.resolve_insurance(true, &mut shoe).unwrap();
+            assert_eq!(round.insurance(), Some(InsuranceDecision { taken: true, correct: true }));
+        }
+
+        let mut shoe = shoe_of(&[Rank::Ten, Rank::Ace, Rank::Nine, Rank::Seven]);
+        let mut round = Round::deal(&StrategyVariables::single_deck(), &TableConfig::default(), &[10.0], &mut shoe);
+        round.set_insurance_index(3, 3.0);
+        round.resolve_insurance(false, &mut shoe).unwrap();
+        assert!(!round.insurance().unwrap().correct);
+    }
+}
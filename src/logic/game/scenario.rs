@@ -0,0 +1,241 @@
+use crate::logic::game::card::Card;
+use crate::logic::game::shoe::Shoe;
+use std::fmt;
+
+/// The two cards a scenario deals the player, by card value with Aces as 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScenarioHand {
+    /// Two different cards without an Ace
+    Hard(u8),
+    /// An Ace and another card, by total
+    Soft(u8),
+    Pair(u8),
+}
+
+/// A situation to practice: the player's starting hand against a dealer upcard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Scenario {
+    pub hand: ScenarioHand,
+    /// Value of the dealer's upcard, Aces as 1
+    pub upcard: u8,
+}
+
+impl Scenario {
+    /// Reads a scenario the way it would be said or read off a chart:
+    /// "soft 18 vs 9", "hard 16 vs 10", "pair of 8s vs A", "A,7 vs 9", "A7 v 9" or "12 vs 4".
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let text = text.trim().to_lowercase();
+        let (hand, upcard) = text.split_once(" vs ")
+            .or_else(|| text.split_once(" v "))
+            .or_else(|| text.split_once(" against "))
+            .ok_or("Describe the hand and the dealer's card, e.g. soft 18 vs 9")?;
+        let upcard = parse_card_value(upcard)
+            .ok_or_else(|| format!("Unknown dealer card: {}", upcard.trim()))?;
+        Ok(Self { hand: parse_hand(hand.trim())?, upcard })
+    }
+
+    /// Card values that can make up the player's hand, either way round.
+    fn combinations(&self) -> Vec<(u8, u8)> {
+        match self.hand {
+            ScenarioHand::Hard(total) => (2..=10)
+                .filter_map(|first| total.checked_sub(first).map(|second| (first, second)))
+                .filter(|(first, second)| first < second && *second <= 10)
+                .collect(),
+            ScenarioHand::Soft(total) => vec![(1, total - 11)],
+            ScenarioHand::Pair(value) => vec![(value, value)],
+        }
+    }
+
+    /// Arranges the front of the shoe so the next round deals this scenario
+    /// to the first of `spots` spots. The cards are taken from further back
+    /// in the shoe, so what's left to come is unchanged for counting. Fails
+    /// if the cards needed have all been dealt.
+    pub fn stack(&self, shoe: &mut Shoe, spots: usize) -> Result<(), String> {
+        let (first, second) = self.choose_cards(shoe.upcoming())
+            .ok_or("Not enough cards left for the scenario")?;
+
+        // A card to each spot, then the dealer's upcard, then the second round
+        let stacked = shoe.bring_forward(0, |card| card.value() == first)
+            && shoe.bring_forward(spots, |card| card.value() == self.upcard)
+            && shoe.bring_forward(spots + 1, |card| card.value() == second);
+        if !stacked {
+            return Err("Not enough cards left for the scenario".to_string());
+        }
+        Ok(())
+    }
+
+    /// Picks whichever way of making the hand comes up first in the shoe, so
+    /// each is dealt about as often as it would be naturally.
+    fn choose_cards(&self, upcoming: &[Card]) -> Option<(u8, u8)> {
+        let remaining = |value: u8| upcoming.iter().filter(|card| card.value() == value).count();
+        // The dealer's upcard comes out of the same cards
+        let available = |value: u8, needed: usize| {
+            remaining(value) >= needed + usize::from(value == self.upcard)
+        };
+        if !available(self.upcard, 0) {
+            return None;
+        }
+
+        let combinations = self.combinations();
+        upcoming.iter().find_map(|card| {
+            combinations.iter()
+                .filter_map(|(first, second)| match card.value() {
+                    value if value == *first => Some((*first, *second)),
+                    value if value == *second => Some((*second, *first)),
+                    _ => None,
+                })
+                .find(|(first, second)| {
+                    if first == second {
+                        available(*first, 2)
+                    } else {
+                        available(*first, 1) && available(*second, 1)
+                    }
+                })
+        })
+    }
+}
+
+/// The hand half of a scenario.
+fn parse_hand(text: &str) -> Result<ScenarioHand, String> {
+    // Two cards written out, e.g. 8,8 or A,7
+    if let Some((first, second)) = text.split_once(',') {
+        let (Some(first), Some(second)) = (parse_card_value(first), parse_card_value(second)) else {
+            return Err(format!("Unknown cards: {}", text));
+        };
+        return two_cards(first, second);
+    }
+
+    // Or run together the way charts label them, e.g. A7, 88 or TT. Totals
+    // like 16 come first, no two cards are written that way.
+    let chars: Vec<char> = text.chars().collect();
+    if let [first, second] = chars[..]
+        && !text.parse::<u8>().is_ok_and(|total| (5..=19).contains(&total))
+        && let (Some(first), Some(second)) = (parse_card_value(&first.to_string()), parse_card_value(&second.to_string()))
+    {
+        return two_cards(first, second);
+    }
+
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let total = |word: &str| word.parse::<u8>().map_err(|_| format!("Unknown total: {}", word));
+    match words.as_slice() {
+        ["soft", value] => soft(total(value)?),
+        ["hard", value] => hard(total(value)?),
+        [value] if value.parse::<u8>().is_ok() => hard(total(value)?),
+        ["pair", "of", value] | ["pair", value] => parse_card_value(value)
+            .map(ScenarioHand::Pair)
+            .ok_or_else(|| format!("Unknown hand: {}", text)),
+        // A plural on its own is a pair, e.g. "8s" or "aces"
+        [value] if value.ends_with('s') => parse_card_value(value)
+            .map(ScenarioHand::Pair)
+            .ok_or_else(|| format!("Unknown hand: {}", text)),
+        _ => Err(format!("Unknown hand: {}", text)),
+    }
+}
+
+fn two_cards(first: u8, second: u8) -> Result<ScenarioHand, String> {
+    match (first, second) {
+        _ if first == second => Ok(ScenarioHand::Pair(first)),
+        (1, other) | (other, 1) => soft(other + 11),
+        _ => hard(first + second),
+    }
+}
+
+fn soft(total: u8) -> Result<ScenarioHand, String> {
+    match total {
+        13..=20 => Ok(ScenarioHand::Soft(total)),
+        _ => Err("Soft totals run from 13 (A,2) to 20 (A,9)".to_string()),
+    }
+}
+
+fn hard(total: u8) -> Result<ScenarioHand, String> {
+    match total {
+        5..=19 => Ok(ScenarioHand::Hard(total)),
+        _ => Err("Hard totals run from 5 to 19, ask for 2,2 or 10,10 as a pair".to_string()),
+    }
+}
+
+/// A single card by value: "A", "ace", "10", "K", "8" or plurals like "8s" and "aces".
+fn parse_card_value(text: &str) -> Option<u8> {
+    let text = match text.trim() {
+        "aces" => "ace",
+        text => text.strip_suffix('s').unwrap_or(text),
+    };
+    match text {
+        "a" | "ace" => Some(1),
+        "t" | "j" | "q" | "k" | "ten" | "jack" | "queen" | "king" => Some(10),
+        number => number.parse().ok().filter(|value| (2..=10).contains(value)),
+    }
+}
+
+fn value_label(value: u8) -> String {
+    if value == 1 { "A".to_string() } else { value.to_string() }
+}
+
+impl fmt::Display for Scenario {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.hand {
+            ScenarioHand::Hard(total) => write!(f, "Hard {}", total)?,
+            ScenarioHand::Soft(total) => write!(f, "Soft {}", total)?,
+            ScenarioHand::Pair(value) => write!(f, "{},{}", value_label(value), value_label(value))?,
+        }
+        write!(f, " vs {}", value_label(self.upcard))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logic::game::round::Round;
+    use crate::logic::game::table::TableConfig;
+    use crate::logic::strategy_calculator_logic::{GameVariant, StrategyVariables, SurrenderRule};
+
+    #[test]
+    fn test_parse_scenarios() {
+        let parsed = |text: &str| Scenario::parse(text).unwrap();
+        assert_eq!(parsed("soft 18 vs 9"), Scenario { hand: ScenarioHand::Soft(18), upcard: 9 });
+        assert_eq!(parsed("A,7 vs 9"), parsed("soft 18 vs 9"));
+        assert_eq!(parsed("Pair of 8s vs 10"), Scenario { hand: ScenarioHand::Pair(8), upcard: 10 });
+        assert_eq!(parsed("8,8 vs K"), parsed("pair of 8s vs 10"));
+        assert_eq!(parsed("aces vs 6"), Scenario { hand: ScenarioHand::Pair(1), upcard: 6 });
+        assert_eq!(parsed("16 vs A"), Scenario { hand: ScenarioHand::Hard(16), upcard: 1 });
+        assert_eq!(parsed("10,6 v ace"), parsed("hard 16 vs A"));
+        assert_eq!(parsed("soft 18 vs 9").to_string(), "Soft 18 vs 9");
+        assert_eq!(parsed("A7 v 9"), parsed("soft 18 vs 9"));
+        assert_eq!(parsed("88 v T"), parsed("pair of 8s vs 10"));
+        assert_eq!(parsed("T6 v 2"), parsed("16 vs 2"));
+        assert_eq!(parsed("16 v T"), Scenario { hand: ScenarioHand::Hard(16), upcard: 10 });
+
+        assert!(Scenario::parse("soft 18").is_err());
+        assert!(Scenario::parse("soft 21 vs 9").is_err());
+        assert!(Scenario::parse("hard 20 vs 9").is_err());
+        assert!(Scenario::parse("16 vs 11").is_err());
+    }
+
+    #[test]
+    fn test_stacked_shoe_deals_the_scenario() {
+        let rules = StrategyVariables {
+            decks: 6,
+            dealer_stands_on_soft_17: true,
+            double_after_split: true,
+            dealer_peak: true,
+            surrender_allowed: SurrenderRule::NotAllowed,
+            variant: GameVariant::Classic,
+        };
+        let mut shoe = Shoe::new(6, 0.75);
+        for (text, spots) in [("soft 18 vs 9", 1), ("8,8 vs 10", 2), ("hard 16 vs A", 3), ("12 vs 4", 1)] {
+            let scenario = Scenario::parse(text).unwrap();
+            let remaining = shoe.remaining();
+            scenario.stack(&mut shoe, spots).unwrap();
+            assert_eq!(shoe.remaining(), remaining);
+
+            let round = Round::deal(&rules, &TableConfig::default(), &vec![10.0; spots], &mut shoe);
+            let hand = &round.hands()[0].hand;
+            assert_eq!(round.dealer().cards()[0].value(), scenario.upcard);
+            match scenario.hand {
+                ScenarioHand::Hard(total) => assert!(!hand.is_soft() && !hand.is_pair() && hand.total() == total),
+                ScenarioHand::Soft(total) => assert!(hand.is_soft() && hand.total() == total),
+                ScenarioHand::Pair(value) => assert!(hand.is_pair() && hand.cards()[0].value() == value),
+            }
+        }
+    }
+}
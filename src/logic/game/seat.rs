@@ -0,0 +1,128 @@
+use serde::{Deserialize, Serialize};
+use crate::logic::game::bankroll::{Bankroll, DEFAULT_BANKROLL};
+use crate::logic::game::round::{HandOutcome, Round};
+
+/// Results for one player over the session.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct SeatStats {
+    /// Rounds the player had a bet in
+    pub rounds: u32,
+    pub hands: u32,
+    pub wins: u32,
+    pub losses: u32,
+    pub pushes: u32,
+    pub blackjacks: u32,
+
+    /// Money put on the table, counting doubles, splits and insurance
+    pub wagered: f64,
+
+    /// Net won or lost across every round, including insurance
+    pub net: f64,
+
+    /// Largest single round win
+    pub best_round: f64,
+}
+
+impl SeatStats {
+    /// Share of decided hands won, pushes and surrenders aside.
+    pub fn win_rate(&self) -> Option<f64> {
+        let decided = self.wins + self.losses;
+        (decided > 0).then(|| self.wins as f64 / decided as f64)
+    }
+}
+
+/// A player sitting at a shared table with their own money and results.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Seat {
+    pub name: String,
+    pub bankroll: Bankroll,
+
+    /// Wager placed each round
+    pub bet: f64,
+
+    pub stats: SeatStats,
+
+    /// A player who has left the table sits out until they return
+    pub away: bool,
+}
+
+impl Seat {
+    pub fn new(name: &str, bet: f64) -> Self {
+        Self {
+            name: name.to_string(),
+            bankroll: Bankroll::new(DEFAULT_BANKROLL),
+            bet,
+            stats: SeatStats::default(),
+            away: false,
+        }
+    }
+
+    /// Whether the player is at the table and can cover their bet for the next round.
+    pub fn can_play(&self) -> bool {
+        !self.away && self.bankroll.can_cover(0.0, self.bet)
+    }
+
+    /// Pays out a settled round for the hands played on `spot`.
+    pub fn settle(&mut self, round: &Round, spot: usize) {
+        let net = round.spot_net(spot);
+        self.bankroll.settle(net);
+
+        let stats = &mut self.stats;
+        stats.rounds += 1;
+        stats.wagered += round.spot_wagered(spot);
+        stats.net += net;
+        stats.best_round = stats.best_round.max(net);
+        for player in round.hands().iter().filter(|player| player.spot == spot) {
+            stats.hands += 1;
+            match player.outcome {
+                Some(HandOutcome::Blackjack) => {
+                    stats.blackjacks += 1;
+                    stats.wins += 1;
+                }
+                Some(HandOutcome::Win) => stats.wins += 1,
+                Some(HandOutcome::Lose) => stats.losses += 1,
+                Some(HandOutcome::Push) => stats.pushes += 1,
+                Some(HandOutcome::Surrender) | None => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logic::game::card::{Card, Rank, Suit};
+    use crate::logic::game::round::PlayerAction;
+    use crate::logic::game::shoe::Shoe;
+    use crate::logic::game::table::TableConfig;
+    use crate::logic::strategy_calculator_logic::StrategyVariables;
+
+    #[test]
+    fn test_seats_settle_their_own_spot() {
+        let rules = StrategyVariables::single_deck();
+        // Spot 1 gets 10,9, spot 2 gets 10,6 and the dealer 10,8
+        let cards = [Rank::Ten, Rank::Ten, Rank::Ten, Rank::Nine, Rank::Six, Rank::Eight]
+            .iter()
+            .map(|rank| Card::new(*rank, Suit::Diamonds))
+            .collect();
+        let mut shoe = Shoe::stacked(cards);
+        let mut alice = Seat::new("Alice", 10.0);
+        let mut bob = Seat::new("Bob", 25.0);
+
+        let mut round = Round::deal(&rules, &TableConfig::default(), &[alice.bet, bob.bet], &mut shoe);
+        assert_eq!(round.active_spot(), Some(0));
+        round.apply(PlayerAction::Stand, &mut shoe).unwrap();
+        assert_eq!(round.active_spot(), Some(1));
+        assert_eq!(round.spot_wagered(1), 25.0);
+        round.apply(PlayerAction::Stand, &mut shoe).unwrap();
+
+        alice.settle(&round, 0);
+        bob.settle(&round, 1);
+        assert_eq!(alice.bankroll.profit(), 10.0);
+        assert_eq!(bob.bankroll.profit(), -25.0);
+        assert_eq!(alice.stats.wins, 1);
+        assert_eq!(bob.stats.losses, 1);
+        assert_eq!(alice.stats.win_rate(), Some(1.0));
+        assert_eq!(bob.stats.best_round, 0.0);
+    }
+}
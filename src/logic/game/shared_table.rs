@@ -0,0 +1,375 @@
+use serde::{Deserialize, Serialize};
+use crate::logic::game::bankroll::{Bankroll, DEFAULT_BANKROLL};
+use crate::logic::game::round::{PlayerAction, Round, RoundPhase};
+use crate::logic::game::seat::Seat;
+use crate::logic::game::shoe::Shoe;
+use crate::logic::game::table::TableConfig;
+use crate::logic::strategy_calculator_logic::StrategyVariables;
+
+pub const MIN_PLAYERS: usize = 2;
+pub const MAX_PLAYERS: usize = 4;
+const DEFAULT_BET: f64 = 10.0;
+
+/// A table shared by several players, each with their own seat, taking
+/// turns against one dealer.
+///
+/// Like `Round`, the table doesn't own the shoe so that the whole table can
+/// be sent to other players without giving away the cards still to come.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SharedTable {
+    pub rules: StrategyVariables,
+    pub table: TableConfig,
+    seats: Vec<Seat>,
+    round: Option<Round>,
+
+    /// Rounds dealt so far, so a new deal can be told apart from an update
+    round_number: u32,
+
+    /// Seat playing each spot of the current round, seats that couldn't
+    /// cover their bet sit the round out
+    seated: Vec<usize>,
+
+    /// Insurance answers so far, in spot order
+    insurance_answers: Vec<bool>,
+
+    /// What just happened at the table, for every player to see
+    pub announcement: String,
+}
+
+impl SharedTable {
+    pub fn new(rules: &StrategyVariables, table: &TableConfig) -> Self {
+        Self {
+            rules: rules.clone(),
+            table: table.clone(),
+            seats: Vec::new(),
+            round: None,
+            round_number: 0,
+            seated: Vec::new(),
+            insurance_answers: Vec::new(),
+            announcement: String::new(),
+        }
+    }
+
+    pub fn seats(&self) -> &[Seat] {
+        &self.seats
+    }
+
+    pub fn round(&self) -> Option<&Round> {
+        self.round.as_ref()
+    }
+
+    pub fn round_number(&self) -> u32 {
+        self.round_number
+    }
+
+    /// Seat playing `spot` in the current round.
+    pub fn seat_at(&self, spot: usize) -> Option<usize> {
+        self.seated.get(spot).copied()
+    }
+
+    pub fn round_in_progress(&self) -> bool {
+        matches!(&self.round, Some(round) if round.phase() != RoundPhase::Complete)
+    }
+
+    pub fn insurance_offered(&self) -> bool {
+        matches!(&self.round, Some(round) if round.phase() == RoundPhase::Insurance)
+    }
+
+    /// Seat whose turn it is: the next to answer insurance, or the owner of the active hand.
+    pub fn acting_seat(&self) -> Option<usize> {
+        let round = self.round.as_ref()?;
+        let spot = if round.phase() == RoundPhase::Insurance {
+            self.insurance_answers.len()
+        } else {
+            round.active_spot()?
+        };
+        self.seat_at(spot)
+    }
+
+    /// Sits a new player down, returning their seat.
+    pub fn join(&mut self, name: &str) -> Result<usize, String> {
+        if self.seats.len() >= MAX_PLAYERS {
+            return Err(format!("The table is full, it seats {} players", MAX_PLAYERS));
+        }
+        let name = if name.trim().is_empty() {
+            format!("Player {}", self.seats.len() + 1)
+        } else {
+            name.trim().to_string()
+        };
+        self.seats.push(Seat::new(&name, self.table.clamp_bet(DEFAULT_BET)));
+        Ok(self.seats.len() - 1)
+    }
+
+    /// Renames a player, names have to be unique so returning network
+    /// players find their seat.
+    pub fn rename(&mut self, seat: usize, name: &str) -> Result<(), String> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err("Enter a name".to_string());
+        }
+        if self.seats.iter().enumerate().any(|(other, player)| other != seat && player.name == name) {
+            return Err(format!("{} is already at the table", name));
+        }
+        let player = self.seats.get_mut(seat).ok_or("No such seat")?;
+        player.name = name.to_string();
+        Ok(())
+    }
+
+    /// Adds or removes players so `players` are seated, only between rounds.
+    pub fn set_players(&mut self, players: usize) -> Result<(), String> {
+        if self.round_in_progress() {
+            return Err("Players can only join or leave between hands".to_string());
+        }
+        let players = players.clamp(MIN_PLAYERS, MAX_PLAYERS);
+        while self.seats.len() < players {
+            self.join("")?;
+        }
+        self.seats.truncate(players);
+        Ok(())
+    }
+
+    /// Marks a player as having left or come back. A player who leaves mid
+    /// round has their remaining decisions played for them.
+    pub fn set_away(&mut self, seat: usize, away: bool, shoe: &mut Shoe) {
+        if let Some(player) = self.seats.get_mut(seat) {
+            player.away = away;
+        }
+        self.play_for_away_seats(shoe);
+    }
+
+    /// Deals a round to every player who can cover their bet.
+    pub fn deal(&mut self, shoe: &mut Shoe) -> Result<(), String> {
+        if self.round_in_progress() {
+            return Err("Finish the current hand first".to_string());
+        }
+        let seated: Vec<usize> = (0..self.seats.len()).filter(|seat| self.seats[*seat].can_play()).collect();
+        if seated.is_empty() {
+            return Err("Nobody can cover their bet, buy a player back in".to_string());
+        }
+
+        let shuffled = shoe.needs_shuffle();
+        if shuffled {
+            shoe.shuffle();
+        }
+
+        let bets: Vec<f64> = seated.iter().map(|seat| self.seats[*seat].bet).collect();
+        self.round = Some(Round::deal(&self.rules, &self.table, &bets, shoe));
+        self.round_number += 1;
+        self.seated = seated;
+        self.insurance_answers.clear();
+
+        let sitting_out: Vec<&str> = (0..self.seats.len())
+            .filter(|seat| !self.seated.contains(seat))
+            .map(|seat| self.seats[seat].name.as_str())
+            .collect();
+        self.announcement = if !sitting_out.is_empty() {
+            format!("{} sitting out", sitting_out.join(", "))
+        } else if shuffled {
+            "Cut card reached, the shoe has been shuffled".to_string()
+        } else {
+            String::new()
+        };
+        self.on_round_updated();
+        Ok(())
+    }
+
+    /// Records a player's insurance answer, the offer is resolved once
+    /// every player has answered.
+    pub fn decide_insurance(&mut self, seat: usize, take: bool, shoe: &mut Shoe) -> Result<(), String> {
+        if self.acting_seat() != Some(seat) {
+            return Err(self.not_your_turn());
+        }
+        let Some(round) = &self.round else {
+            return Err("Insurance is not being offered".to_string());
+        };
+        let spot = self.insurance_answers.len();
+        let player = &self.seats[seat];
+        if take && !player.bankroll.can_cover(round.spot_wagered(spot), player.bet / 2.0) {
+            return Err(format!("{} can't cover the insurance bet", player.name));
+        }
+
+        self.insurance_answers.push(take);
+        if self.insurance_answers.len() == self.seated.len()
+            && let Some(round) = self.round.as_mut() {
+            round.resolve_insurance_by_spot(&self.insurance_answers, shoe)?;
+        }
+        self.on_round_updated();
+        Ok(())
+    }
+
+    /// Applies a decision to the active hand for the player whose turn it is.
+    pub fn act(&mut self, seat: usize, action: PlayerAction, shoe: &mut Shoe) -> Result<(), String> {
+        if self.acting_seat() != Some(seat) {
+            return Err(self.not_your_turn());
+        }
+        if self.insurance_offered() {
+            return Err("Answer the insurance offer first".to_string());
+        }
+        let Some(round) = self.round.as_mut() else {
+            return Err("Nothing to play, wait for the deal".to_string());
+        };
+
+        // Doubles and splits come out of the acting player's own bankroll
+        let player = &self.seats[seat];
+        if matches!(action, PlayerAction::Double | PlayerAction::Split)
+            && round.can(action)
+            && let (Some(bet), Some(spot)) = (round.action_cost(action), round.active_spot())
+            && !player.bankroll.can_cover(round.spot_wagered(spot), bet) {
+            return Err(format!("{} can't cover another ${:.2} to {}", player.name, bet, action.to_string().to_lowercase()));
+        }
+
+        round.apply(action, shoe)?;
+        self.on_round_updated();
+        Ok(())
+    }
+
+    fn not_your_turn(&self) -> String {
+        match self.acting_seat() {
+            Some(seat) => format!("Waiting on {}", self.seats[seat].name),
+            None => "Nothing to play, wait for the deal".to_string(),
+        }
+    }
+
+    /// Declines insurance and stands for players who have left the table.
+    fn play_for_away_seats(&mut self, shoe: &mut Shoe) {
+        while let Some(seat) = self.acting_seat().filter(|seat| self.seats[*seat].away) {
+            let result = if self.insurance_offered() {
+                self.decide_insurance(seat, false, shoe)
+            } else {
+                self.act(seat, PlayerAction::Stand, shoe)
+            };
+            if result.is_err() {
+                break;
+            }
+        }
+    }
+
+    /// Pays every player once the round has been settled.
+    fn on_round_updated(&mut self) {
+        let Some(round) = &self.round else {
+            return;
+        };
+        if round.phase() != RoundPhase::Complete {
+            return;
+        }
+
+        let mut results = Vec::new();
+        for (spot, seat) in self.seated.iter().enumerate() {
+            let player = &mut self.seats[*seat];
+            player.settle(round, spot);
+            let net = round.spot_net(spot);
+            results.push(format!("{} {}{:.2}", player.name, if net < 0.0 { "-$" } else { "+$" }, net.abs()));
+        }
+        self.announcement = results.join(", ");
+    }
+
+    /// What each seat won or lost in the last round once it's settled,
+    /// for the dealer to pay out and collect.
+    pub fn settlements(&self) -> Vec<(usize, f64)> {
+        match &self.round {
+            Some(round) if round.phase() == RoundPhase::Complete => self.seated.iter()
+                .enumerate()
+                .map(|(spot, seat)| (*seat, round.spot_net(spot)))
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// The house's result across every round, the other side of the players' nets.
+    pub fn house_net(&self) -> f64 {
+        -self.seats.iter().map(|seat| seat.stats.net).sum::<f64>()
+    }
+
+    /// Sets a player's bet for the next round, within the table limits.
+    pub fn set_bet(&mut self, seat: usize, bet: f64) -> Result<(), String> {
+        if self.round_in_progress() {
+            return Err("Bets can only change between hands".to_string());
+        }
+        let player = self.seats.get_mut(seat).ok_or("No such seat")?;
+        player.bet = self.table.clamp_bet(bet);
+        Ok(())
+    }
+
+    /// Gives a player a fresh bankroll, only between rounds.
+    pub fn rebuy(&mut self, seat: usize) -> Result<(), String> {
+        if self.round_in_progress() {
+            return Err("Players can only buy in between hands".to_string());
+        }
+        let player = self.seats.get_mut(seat).ok_or("No such seat")?;
+        player.bankroll = Bankroll::new(DEFAULT_BANKROLL);
+        self.announcement = format!("{} bought in for ${:.2}", player.name, DEFAULT_BANKROLL);
+        Ok(())
+    }
+
+    /// The table as other players may see it, without the dealer's hole card.
+    pub fn public_view(&self) -> Self {
+        let mut table = self.clone();
+        table.round = self.round.as_ref().map(|round| round.without_hole_card());
+        table
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logic::game::card::{Card, Rank, Suit};
+
+    fn shared_table() -> SharedTable {
+        let rules = StrategyVariables::single_deck();
+        let mut table = SharedTable::new(&rules, &TableConfig::default());
+        table.set_players(3).unwrap();
+        table
+    }
+
+    fn shoe_of(ranks: &[Rank]) -> Shoe {
+        Shoe::stacked(ranks.iter().map(|rank| Card::new(*rank, Suit::Spades)).collect())
+    }
+
+    #[test]
+    fn test_players_take_turns() {
+        let mut table = shared_table();
+        table.seats[1].bankroll = Bankroll::new(0.0);
+        let mut shoe = shoe_of(&[Rank::Ten, Rank::Nine, Rank::Seven, Rank::Eight, Rank::Six, Rank::Ten]);
+        table.deal(&mut shoe).unwrap();
+
+        // Player 2 is broke and sits out, so player 3 plays the second spot
+        assert_eq!(table.seat_at(1), Some(2));
+        assert_eq!(table.acting_seat(), Some(0));
+        assert!(table.act(2, PlayerAction::Stand, &mut shoe).is_err());
+        table.act(0, PlayerAction::Stand, &mut shoe).unwrap();
+        assert_eq!(table.acting_seat(), Some(2));
+        table.act(2, PlayerAction::Stand, &mut shoe).unwrap();
+
+        assert!(!table.round_in_progress());
+        assert_eq!(table.seats()[0].bankroll.profit(), 10.0);
+        assert_eq!(table.seats()[2].bankroll.profit(), -10.0);
+        assert_eq!(table.seats()[1].stats.rounds, 0);
+        assert_eq!(table.settlements(), vec![(0, 10.0), (2, -10.0)]);
+        assert_eq!(table.house_net(), 0.0);
+    }
+
+    #[test]
+    fn test_names_are_unique() {
+        let mut table = shared_table();
+        table.rename(0, " Alice ").unwrap();
+        assert_eq!(table.seats()[0].name, "Alice");
+        assert!(table.rename(1, "Alice").is_err());
+        assert!(table.rename(1, "  ").is_err());
+        table.rename(0, "Alice").unwrap();
+    }
+
+    #[test]
+    fn test_away_players_are_stood_and_hole_card_hidden() {
+        let mut table = shared_table();
+        let mut shoe = shoe_of(&[Rank::Ten, Rank::Nine, Rank::Eight, Rank::Five, Rank::Six, Rank::Four, Rank::Jack, Rank::Queen]);
+        table.deal(&mut shoe).unwrap();
+        assert_eq!(table.public_view().round().unwrap().dealer().cards()[1].rank, Rank::Five);
+
+        table.set_away(0, true, &mut shoe);
+        assert_eq!(table.acting_seat(), Some(1));
+        table.act(1, PlayerAction::Stand, &mut shoe).unwrap();
+        table.set_away(2, true, &mut shoe);
+        assert!(!table.round_in_progress());
+        assert!(!table.seats()[0].can_play());
+    }
+}
@@ -0,0 +1,385 @@
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::ops::RangeInclusive;
+use crate::logic::counting::CountingSystem;
+use crate::logic::game::card::{Card, Rank, Suit};
+use crate::logic::game::table::TableConfig;
+use crate::logic::strategy_calculator_logic::StrategyVariables;
+
+pub const MIN_DECKS: u8 = 1;
+pub const MAX_DECKS: u8 = 8;
+const CARDS_PER_DECK: usize = 52;
+/// Where a cut may be made, as a fraction of the shoe. Cuts too close to
+/// either end are refused at the table.
+pub const CUT_RANGE: RangeInclusive<f64> = 0.2..=0.8;
+/// Cards taken off the top at a time during a strip cut
+const STRIP_PACKET: RangeInclusive<usize> = 5..=15;
+
+/// How the cards are mixed between shoes.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ShuffleProcedure {
+    /// Every order equally likely, as a shuffling machine aims for
+    #[default]
+    Random,
+    /// A dealer's hand shuffle: riffle, riffle, strip, riffle. Runs of cards
+    /// from the last shoe survive it, as they do at a real table.
+    Riffle,
+}
+
+impl fmt::Display for ShuffleProcedure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ShuffleProcedure::Random => "Random",
+            ShuffleProcedure::Riffle => "Riffle",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// One or more shuffled decks that cards are dealt from, with a cut card
+/// marking how deep the shoe is dealt before the next shuffle.
+#[derive(Clone)]
+pub struct Shoe {
+    /// Cards in dealing order
+    cards: Vec<Card>,
+
+    /// Index of the next card to be dealt
+    position: usize,
+
+    /// Fraction of the shoe dealt before the cut card comes out (0.0 - 1.0)
+    penetration: f64,
+
+    /// Index of the cut card, once dealt past it the shoe should be shuffled
+    cut_card: usize,
+
+    procedure: ShuffleProcedure,
+
+    /// Cards burned after each shuffle
+    burn_cards: usize,
+
+    /// Cards at the front of the shoe burned since the last shuffle, never seen
+    burned: usize,
+
+    rng: StdRng,
+}
+
+impl Shoe {
+    /// Creates a freshly shuffled shoe of 1-8 standard 52 card decks with the
+    /// cut card placed at the given penetration.
+    pub fn new(decks: u8, penetration: f64) -> Self {
+        Self::with_rng(decks, penetration, StdRng::from_os_rng())
+    }
+
+    fn with_rng(decks: u8, penetration: f64, rng: StdRng) -> Self {
+        let decks = decks.clamp(MIN_DECKS, MAX_DECKS);
+        let mut cards = Vec::with_capacity(decks as usize * CARDS_PER_DECK);
+        for _ in 0..decks {
+            for suit in Suit::ALL {
+                for rank in Rank::ALL {
+                    cards.push(Card::new(rank, suit));
+                }
+            }
+        }
+
+        let mut shoe = Self {
+            cards,
+            position: 0,
+            penetration: 0.0,
+            cut_card: 0,
+            procedure: ShuffleProcedure::Random,
+            burn_cards: 0,
+            burned: 0,
+            rng,
+        };
+        shoe.set_penetration(penetration);
+        shoe.shuffle();
+        shoe
+    }
+
+    /// Creates a shoe sized for the given rules.
+    pub fn from_rules(rules: &StrategyVariables, table: &TableConfig) -> Self {
+        Self::for_table(Self::new(rules.decks, table.penetration), table)
+    }
+
+    /// Creates a shoe sized for the given rules that shuffles the same way
+    /// every time it's given the same seed, so runs can be repeated.
+    pub fn seeded_from_rules(rules: &StrategyVariables, table: &TableConfig, seed: u64) -> Self {
+        Self::for_table(Self::with_rng(rules.decks, table.penetration, StdRng::seed_from_u64(seed)), table)
+    }
+
+    fn for_table(mut shoe: Self, table: &TableConfig) -> Self {
+        shoe.set_procedure(table.shuffle);
+        shoe.set_burn_cards(table.burn_cards);
+        shoe.shuffle();
+        shoe
+    }
+
+    /// Creates a shoe that deals the given cards in order, used to set up
+    /// specific situations.
+    #[cfg(test)]
+    pub fn stacked(cards: Vec<Card>) -> Self {
+        let cut_card = cards.len();
+        Self {
+            cards,
+            position: 0,
+            penetration: 1.0,
+            cut_card,
+            procedure: ShuffleProcedure::Random,
+            burn_cards: 0,
+            burned: 0,
+            rng: StdRng::seed_from_u64(0),
+        }
+    }
+
+    /// Moves the cut card. Always leaves at least one card in front of it.
+    pub fn set_penetration(&mut self, penetration: f64) {
+        self.penetration = penetration.clamp(0.0, 1.0);
+        self.cut_card = ((self.cards.len() as f64 * self.penetration).round() as usize).max(1);
+    }
+
+    pub fn penetration(&self) -> f64 {
+        self.penetration
+    }
+
+    /// Takes effect from the next shuffle.
+    pub fn set_procedure(&mut self, procedure: ShuffleProcedure) {
+        self.procedure = procedure;
+    }
+
+    /// Takes effect from the next shuffle.
+    pub fn set_burn_cards(&mut self, burn_cards: u8) {
+        self.burn_cards = burn_cards as usize;
+    }
+
+    /// Collects every card back into the shoe, shuffles, has the dealer cut
+    /// and burns the top cards.
+    pub fn shuffle(&mut self) {
+        let cut = self.rng.random_range(CUT_RANGE);
+        self.shuffle_and_cut(cut);
+    }
+
+    /// Shuffles with the cut made `cut` of the way into the shoe, for a
+    /// player cutting the cards.
+    pub fn shuffle_and_cut(&mut self, cut: f64) {
+        match self.procedure {
+            ShuffleProcedure::Random => self.cards.shuffle(&mut self.rng),
+            ShuffleProcedure::Riffle => {
+                self.riffle();
+                self.riffle();
+                self.strip();
+                self.riffle();
+            }
+        }
+
+        self.cut(cut);
+
+        // Never burn the whole shoe
+        self.burned = self.burn_cards.min(self.cards.len() - 1);
+        self.position = self.burned;
+    }
+
+    /// Moves the cards from `at` of the way into the shoe to the front.
+    fn cut(&mut self, at: f64) {
+        let at = (self.cards.len() as f64 * at.clamp(*CUT_RANGE.start(), *CUT_RANGE.end())).round() as usize;
+        self.cards.rotate_left(at);
+    }
+
+    /// Splits the cards about in half and lets them fall together, each
+    /// card more likely to come from the larger pile (Gilbert-Shannon-Reeds).
+    fn riffle(&mut self) {
+        let total = self.cards.len();
+        let split = (0..total).filter(|_| self.rng.random_bool(0.5)).count();
+        let (mut left, mut right) = (0, split);
+
+        let mut mixed = Vec::with_capacity(total);
+        while mixed.len() < total {
+            let (left_pile, right_pile) = (split - left, total - right);
+            if self.rng.random_range(0..left_pile + right_pile) < left_pile {
+                mixed.push(self.cards[left]);
+                left += 1;
+            } else {
+                mixed.push(self.cards[right]);
+                right += 1;
+            }
+        }
+        self.cards = mixed;
+    }
+
+    /// Takes small packets off the top, each landing on the last, reversing
+    /// their order without mixing the cards within them.
+    fn strip(&mut self) {
+        let mut stripped = Vec::with_capacity(self.cards.len());
+        let mut rest = self.cards.as_slice();
+        while !rest.is_empty() {
+            let packet = self.rng.random_range(STRIP_PACKET).min(rest.len());
+            let (top, remaining) = rest.split_at(packet);
+            stripped.splice(0..0, top.iter().copied());
+            rest = remaining;
+        }
+        self.cards = stripped;
+    }
+
+    /// Whether the cut card has come out. The shoe is shuffled between rounds,
+    /// never in the middle of one.
+    pub fn needs_shuffle(&self) -> bool {
+        self.position >= self.cut_card
+    }
+
+    /// Deals the next card. If the shoe is dealt out completely mid round the
+    /// cards are reshuffled rather than running dry.
+    pub fn draw(&mut self) -> Card {
+        if self.position >= self.cards.len() {
+            self.shuffle();
+        }
+        let card = self.cards[self.position];
+        self.position += 1;
+        card
+    }
+
+    /// Cards still to be dealt, in order.
+    pub fn upcoming(&self) -> &[Card] {
+        &self.cards[self.position..]
+    }
+
+    /// Swaps the first upcoming card matching `wanted` at or after `offset`
+    /// into place to be dealt `offset` cards from now. Returns false if no
+    /// such card is left.
+    pub fn bring_forward(&mut self, offset: usize, wanted: impl Fn(&Card) -> bool) -> bool {
+        let target = self.position + offset;
+        let Some(found) = self.cards.iter().skip(target).position(wanted) else {
+            return false;
+        };
+        self.cards.swap(target, target + found);
+        true
+    }
+
+    /// Number of cards left before the shoe must be reshuffled.
+    pub fn remaining(&self) -> usize {
+        self.cards.len() - self.position
+    }
+
+    /// Number of cards left to deal before the cut card comes out.
+    pub fn cards_to_cut(&self) -> usize {
+        self.cut_card.saturating_sub(self.position)
+    }
+
+    /// Running count of every card seen since the last shuffle in a
+    /// counting system, burned cards are never shown.
+    pub fn running_count(&self, system: &dyn CountingSystem) -> i32 {
+        let decks = (self.total_decks().round() as u8).max(1);
+        system.running_count(decks, &self.cards[self.burned..self.position])
+    }
+
+    /// Running count per deck remaining, for balanced counting systems.
+    pub fn true_count(&self, system: &dyn CountingSystem) -> f64 {
+        self.running_count(system) as f64 / self.decks_remaining().max(0.5)
+    }
+
+    pub fn total_decks(&self) -> f64 {
+        self.cards.len() as f64 / CARDS_PER_DECK as f64
+    }
+
+    /// Decks left to be dealt, as used for true count conversion.
+    pub fn decks_remaining(&self) -> f64 {
+        self.remaining() as f64 / CARDS_PER_DECK as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logic::counting::HI_LO;
+
+    #[test]
+    fn test_new_shoe_contains_every_card() {
+        let mut shoe = Shoe::new(2, 1.0);
+        assert_eq!(shoe.remaining(), 104);
+
+        let drawn: Vec<Card> = (0..104).map(|_| shoe.draw()).collect();
+        assert_eq!(shoe.remaining(), 0);
+        for rank in Rank::ALL {
+            assert_eq!(drawn.iter().filter(|card| card.rank == rank).count(), 8);
+        }
+
+        // An empty shoe reshuffles rather than running dry
+        shoe.draw();
+        assert_eq!(shoe.remaining(), 103);
+    }
+
+    #[test]
+    fn test_cut_card_triggers_shuffle() {
+        let mut shoe = Shoe::new(1, 0.75);
+        for _ in 0..38 {
+            shoe.draw();
+        }
+        assert!(!shoe.needs_shuffle());
+
+        shoe.draw();
+        assert!(shoe.needs_shuffle());
+
+        shoe.shuffle();
+        assert!(!shoe.needs_shuffle());
+        assert_eq!(shoe.remaining(), 52);
+    }
+
+    #[test]
+    fn test_deck_count_and_estimates() {
+        assert_eq!(Shoe::new(0, 0.75).total_decks(), 1.0);
+        assert_eq!(Shoe::new(12, 0.75).total_decks(), 8.0);
+
+        let mut shoe = Shoe::new(6, 0.75);
+        for _ in 0..78 {
+            shoe.draw();
+        }
+        assert_eq!(shoe.decks_remaining(), 4.5);
+    }
+
+    #[test]
+    fn test_hi_lo_count() {
+        let mut shoe = Shoe::stacked(
+            [Rank::Two, Rank::Five, Rank::Seven, Rank::King, Rank::Ace, Rank::Six]
+                .iter()
+                .map(|rank| Card::new(*rank, Suit::Clubs))
+                .collect(),
+        );
+        assert_eq!(shoe.running_count(&HI_LO), 0);
+
+        for _ in 0..3 {
+            shoe.draw();
+        }
+        assert_eq!(shoe.running_count(&HI_LO), 2);
+
+        shoe.draw();
+        shoe.draw();
+        assert_eq!(shoe.running_count(&HI_LO), 0);
+
+        shoe.shuffle();
+        assert_eq!(shoe.running_count(&HI_LO), 0);
+    }
+
+    #[test]
+    fn test_riffle_burn_and_cut() {
+        let mut shoe = Shoe::new(2, 0.75);
+        shoe.set_procedure(ShuffleProcedure::Riffle);
+        shoe.set_burn_cards(3);
+        shoe.shuffle_and_cut(0.5);
+
+        // Nothing is lost or duplicated, and the burned cards are out of play unseen
+        for rank in Rank::ALL {
+            assert_eq!(shoe.cards.iter().filter(|card| card.rank == rank).count(), 8);
+        }
+        assert_eq!(shoe.remaining(), 101);
+        assert_eq!(shoe.running_count(&HI_LO), 0);
+
+        // Cutting 40% in brings the fifth of ten cards to the front, and cuts
+        // too near either end are moved back within reach
+        let mut stacked = Shoe::stacked(Rank::ALL[..10].iter().map(|rank| Card::new(*rank, Suit::Hearts)).collect());
+        stacked.cut(0.4);
+        assert_eq!(stacked.draw().rank, Rank::Six);
+        stacked.cut(0.95);
+        assert_eq!(stacked.cards[0].rank, Rank::Four);
+    }
+}
@@ -0,0 +1,102 @@
+use serde::{Deserialize, Serialize};
+use crate::logic::game::shoe::ShuffleProcedure;
+
+/// Penetrations a table can be set to deal to
+pub const PENETRATION_OPTIONS: [f64; 6] = [0.5, 0.6, 0.7, 0.75, 0.8, 0.9];
+
+/// House rules for the table that are not part of a strategy's `StrategyVariables`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct TableConfig {
+    /// Maximum number of hands a player may split into, e.g. 4 allows three splits
+    pub max_split_hands: u8,
+
+    /// Whether split aces may be split again when dealt another ace
+    pub resplit_aces: bool,
+
+    /// Fraction of the shoe dealt before the cut card, e.g. 0.75 deals 4.5 of 6 decks
+    pub penetration: f64,
+
+    /// How the dealer mixes the cards between shoes
+    pub shuffle: ShuffleProcedure,
+
+    /// Cards dealt face down to the discard tray after each shuffle
+    pub burn_cards: u8,
+
+    /// Whether the player cuts the shuffled shoe rather than the dealer
+    pub player_cuts: bool,
+
+    /// Smallest wager accepted on a spot
+    pub min_bet: f64,
+
+    /// Largest wager accepted on a spot
+    pub max_bet: f64,
+
+    /// Chip values available when sizing a bet, smallest first
+    pub chip_denominations: Vec<f64>,
+}
+
+impl Default for TableConfig {
+    fn default() -> Self {
+        Self {
+            max_split_hands: 4,
+            resplit_aces: false,
+            penetration: 0.75,
+            shuffle: ShuffleProcedure::Random,
+            burn_cards: 1,
+            player_cuts: false,
+            min_bet: 5.0,
+            max_bet: 500.0,
+            chip_denominations: vec![1.0, 5.0, 25.0, 100.0],
+        }
+    }
+}
+
+impl TableConfig {
+    /// Adds (or removes, when negative) `amount` to a bet, keeping it within the table limits.
+    pub fn adjust_bet(&self, bet: f64, amount: f64) -> f64 {
+        self.clamp_bet(bet + amount)
+    }
+
+    pub fn clamp_bet(&self, bet: f64) -> f64 {
+        bet.clamp(self.min_bet, self.max_bet)
+    }
+
+    /// Checks the limits and chips can be bet with: a minimum above zero
+    /// and no more than the maximum, and chips above zero, smallest first.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.min_bet <= 0.0 || self.min_bet > self.max_bet {
+            return Err(format!("The table limits ${} to ${} can't be bet within", self.min_bet, self.max_bet));
+        }
+        if self.chip_denominations.is_empty() {
+            return Err("The table needs at least one chip".to_string());
+        }
+        if self.chip_denominations.iter().any(|chip| *chip <= 0.0) || !self.chip_denominations.is_sorted() {
+            return Err(format!("The chips {:?} must be above zero, smallest first", self.chip_denominations));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_adjust_bet_stays_within_limits() {
+        let table = TableConfig::default();
+        assert_eq!(table.adjust_bet(10.0, 25.0), 35.0);
+        assert_eq!(table.adjust_bet(10.0, -25.0), 5.0);
+        assert_eq!(table.adjust_bet(450.0, 100.0), 500.0);
+    }
+
+    #[test]
+    fn test_limits_and_chips_must_be_bettable() {
+        assert!(TableConfig::default().validate().is_ok());
+        assert!(TableConfig { min_bet: 0.0, ..TableConfig::default() }.validate().is_err());
+        assert!(TableConfig { min_bet: 100.0, max_bet: 50.0, ..TableConfig::default() }.validate().is_err());
+        assert!(TableConfig { chip_denominations: vec![], ..TableConfig::default() }.validate().is_err());
+        assert!(TableConfig { chip_denominations: vec![25.0, 5.0], ..TableConfig::default() }.validate().is_err());
+        assert!(TableConfig { min_bet: 25.0, max_bet: 25.0, chip_denominations: vec![25.0], ..TableConfig::default() }.validate().is_ok());
+    }
+}
@@ -0,0 +1,486 @@
+use crate::logic::counting_stats::now;
+use crate::logic::decision_accuracy::{CellRecord, CellTally, DecisionAccuracy};
+use crate::logic::export::csv_field;
+use crate::logic::game::history::HandRecord;
+use rusqlite::types::ValueRef;
+use rusqlite::{params, Connection};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Tables kept in the database, made when it's first opened.
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS sessions (
+    id INTEGER PRIMARY KEY,
+    activity TEXT NOT NULL,
+    started_at INTEGER NOT NULL,
+    ended_at INTEGER NOT NULL,
+    hands INTEGER NOT NULL DEFAULT 0,
+    net REAL NOT NULL DEFAULT 0,
+    answered INTEGER NOT NULL DEFAULT 0,
+    correct INTEGER NOT NULL DEFAULT 0
+);
+CREATE TABLE IF NOT EXISTS hands (
+    id INTEGER PRIMARY KEY,
+    session_id INTEGER NOT NULL REFERENCES sessions(id),
+    played_at INTEGER NOT NULL,
+    rules TEXT NOT NULL,
+    variant TEXT NOT NULL,
+    dealer TEXT NOT NULL,
+    player TEXT NOT NULL,
+    bet REAL NOT NULL,
+    net REAL NOT NULL,
+    insurance_net REAL NOT NULL,
+    decisions INTEGER NOT NULL,
+    correct_decisions INTEGER,
+    running_count INTEGER NOT NULL,
+    true_count REAL NOT NULL
+);
+CREATE TABLE IF NOT EXISTS answers (
+    id INTEGER PRIMARY KEY,
+    session_id INTEGER NOT NULL REFERENCES sessions(id),
+    answered_at INTEGER NOT NULL,
+    drill TEXT NOT NULL,
+    correct INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS cells (
+    chart TEXT NOT NULL,
+    hand TEXT NOT NULL,
+    upcard_column INTEGER NOT NULL,
+    correct INTEGER NOT NULL DEFAULT 0,
+    wrong INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (chart, hand, upcard_column)
+);
+";
+
+/// Hands played and drill answers given, kept in a SQLite database between
+/// runs so statistics can be worked out over everything ever played. Each
+/// screen keeps its own store, and its hands and answers go into one
+/// session, started with the first of them.
+pub struct HistoryStore {
+    connection: Connection,
+
+    /// What the session is of, e.g. "Game" or a drill's name
+    activity: String,
+    session: Option<i64>,
+}
+
+/// Answers given, or decisions made, and how many of them were right.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Tally {
+    pub total: u32,
+    pub correct: u32,
+}
+
+impl Tally {
+    /// Share of them that were right, none when there weren't any.
+    pub fn accuracy(&self) -> Option<f64> {
+        (self.total > 0).then(|| self.correct as f64 / self.total as f64)
+    }
+}
+
+impl std::ops::Add for Tally {
+    type Output = Tally;
+
+    fn add(self, other: Tally) -> Tally {
+        Tally { total: self.total + other.total, correct: self.correct + other.correct }
+    }
+}
+
+/// Hands played at the table, of one variant or all of them.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HandTotals {
+    pub hands: u32,
+    pub net: f64,
+    pub wagered: f64,
+
+    /// Decisions graded against the chart
+    pub decisions: Tally,
+}
+
+/// How one session went, for following results and accuracy over time.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SessionSummary {
+    pub activity: String,
+
+    /// When the session started, in seconds since the Unix epoch
+    pub started_at: u64,
+    pub hands: u32,
+    pub net: f64,
+
+    /// Decisions graded at the table and answers in the Strategy Quiz's drills
+    pub strategy: Tally,
+
+    /// Answers in the Count Trainer's drills
+    pub counting: Tally,
+}
+
+/// Totals over everything ever kept in the database.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LifetimeStats {
+    pub sessions: u32,
+    pub hands: HandTotals,
+
+    /// Hands by the variant they were played under, in name order
+    pub variants: Vec<(String, HandTotals)>,
+
+    /// Answers by the drill they were given in, in name order, and by the
+    /// screen the drill is on
+    pub drills: Vec<(String, Tally)>,
+    pub activities: Vec<(String, Tally)>,
+}
+
+impl LifetimeStats {
+    /// Decisions at the table and answers in the Strategy Quiz's drills.
+    pub fn strategy_accuracy(&self) -> Tally {
+        self.hands.decisions + self.activity("Strategy Quiz")
+    }
+
+    /// Answers in the Count Trainer's drills.
+    pub fn counting_accuracy(&self) -> Tally {
+        self.activity("Count Trainer")
+    }
+
+    fn activity(&self, activity: &str) -> Tally {
+        self.activities.iter().find(|(name, _)| name == activity).map_or(Tally::default(), |(_, tally)| *tally)
+    }
+}
+
+impl HistoryStore {
+    /// Opens the database at `path`, making it and its tables if need be.
+    pub fn open(path: &str, activity: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        if let Some(parent) = Path::new(path).parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let connection = Connection::open(path)?;
+        connection.execute_batch("PRAGMA journal_mode = WAL; PRAGMA synchronous = NORMAL;")?;
+        connection.execute_batch(SCHEMA)?;
+        Ok(Self { connection, activity: activity.to_string(), session: None })
+    }
+
+    /// The session being recorded to, starting it if this is its first record.
+    fn session(&mut self) -> Result<i64, Box<dyn std::error::Error>> {
+        if let Some(session) = self.session {
+            return Ok(session);
+        }
+        let started_at = now() as i64;
+        self.connection.execute(
+            "INSERT INTO sessions (activity, started_at, ended_at) VALUES (?1, ?2, ?2)",
+            params![self.activity, started_at],
+        )?;
+        let session = self.connection.last_insert_rowid();
+        self.session = Some(session);
+        Ok(session)
+    }
+
+    /// Records a finished round, with how many of its decisions were right
+    /// when they were graded.
+    pub fn record_hand(&mut self, record: &HandRecord, correct_decisions: Option<u32>) -> Result<(), Box<dyn std::error::Error>> {
+        let session = self.session()?;
+        let played_at = now() as i64;
+        let player = record.hands.iter().map(|player| player.hand.to_string()).collect::<Vec<String>>().join(" | ");
+        self.connection.execute(
+            "INSERT INTO hands (session_id, played_at, rules, variant, dealer, player, bet, net, insurance_net, decisions, correct_decisions, running_count, true_count)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
+            params![
+                session,
+                played_at,
+                record.rules.to_string(),
+                record.rules.variant.to_string(),
+                record.dealer.to_string(),
+                player,
+                record.total_bet(),
+                record.net,
+                record.insurance_net,
+                record.decisions.len() as i64,
+                correct_decisions,
+                record.running_count,
+                record.true_count,
+            ],
+        )?;
+        self.connection.execute(
+            "UPDATE sessions SET ended_at = ?2, hands = hands + 1, net = net + ?3 WHERE id = ?1",
+            params![session, played_at, record.net],
+        )?;
+        Ok(())
+    }
+
+    /// Records an answer given in a drill.
+    pub fn record_answer(&mut self, drill: &str, correct: bool) -> Result<(), Box<dyn std::error::Error>> {
+        let session = self.session()?;
+        let answered_at = now() as i64;
+        self.connection.execute(
+            "INSERT INTO answers (session_id, answered_at, drill, correct) VALUES (?1, ?2, ?3, ?4)",
+            params![session, answered_at, drill, correct],
+        )?;
+        self.connection.execute(
+            "UPDATE sessions SET ended_at = ?2, answered = answered + 1, correct = correct + ?3 WHERE id = ?1",
+            params![session, answered_at, correct as i64],
+        )?;
+        Ok(())
+    }
+
+    /// Adds plays tallied by chart cell to the cells kept, in place so
+    /// plays recorded from another screen in the meantime are kept too.
+    pub fn add_cells(&mut self, plays: &DecisionAccuracy) -> Result<(), Box<dyn std::error::Error>> {
+        let transaction = self.connection.transaction()?;
+        for cell in &plays.cells {
+            add_to_cell(&transaction, cell)?;
+        }
+        transaction.commit()?;
+        Ok(())
+    }
+
+    /// Right and wrong plays in every chart cell played, as they are now.
+    pub fn decision_accuracy(&self) -> Result<DecisionAccuracy, Box<dyn std::error::Error>> {
+        let cells = self.connection
+            .prepare("SELECT chart, hand, upcard_column, correct, wrong FROM cells ORDER BY rowid")?
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, CellTally { right: row.get(3)?, wrong: row.get(4)? })))?
+            .map(|row| {
+                let (chart, hand, column, tally): (String, String, i64, CellTally) = row?;
+                Ok(CellRecord { chart, hand: serde_json::from_str(&hand)?, column: column as usize, tally })
+            })
+            .collect::<Result<_, Box<dyn std::error::Error>>>()?;
+        Ok(DecisionAccuracy { cells })
+    }
+
+    /// Adds up every session, hand and answer kept.
+    pub fn lifetime_stats(&self) -> Result<LifetimeStats, Box<dyn std::error::Error>> {
+        let sessions = self.connection.query_row("SELECT COUNT(*) FROM sessions WHERE hands > 0 OR answered > 0", [], |row| row.get(0))?;
+        let variants: Vec<(String, HandTotals)> = self.connection
+            .prepare(
+                "SELECT variant, COUNT(*), SUM(net), SUM(bet),
+                        SUM(CASE WHEN correct_decisions IS NULL THEN 0 ELSE decisions END), SUM(COALESCE(correct_decisions, 0))
+                 FROM hands GROUP BY variant ORDER BY variant",
+            )?
+            .query_map([], |row| {
+                let totals = HandTotals {
+                    hands: row.get(1)?,
+                    net: row.get(2)?,
+                    wagered: row.get(3)?,
+                    decisions: Tally { total: row.get(4)?, correct: row.get(5)? },
+                };
+                Ok((row.get(0)?, totals))
+            })?
+            .collect::<Result<_, _>>()?;
+        let hands = variants.iter().fold(HandTotals::default(), |all, (_, totals)| HandTotals {
+            hands: all.hands + totals.hands,
+            net: all.net + totals.net,
+            wagered: all.wagered + totals.wagered,
+            decisions: all.decisions + totals.decisions,
+        });
+        Ok(LifetimeStats {
+            sessions,
+            hands,
+            variants,
+            drills: self.answer_tallies("drill")?,
+            activities: self.answer_tallies("activity")?,
+        })
+    }
+
+    /// Every session with a hand or an answer in it, oldest first.
+    pub fn sessions(&self) -> Result<Vec<SessionSummary>, Box<dyn std::error::Error>> {
+        let sessions = self.connection
+            .prepare(
+                "SELECT activity, started_at, sessions.hands, sessions.net, answered, correct,
+                        COALESCE(SUM(CASE WHEN correct_decisions IS NULL THEN 0 ELSE decisions END), 0), COALESCE(SUM(correct_decisions), 0)
+                 FROM sessions LEFT JOIN hands ON hands.session_id = sessions.id
+                 WHERE sessions.hands > 0 OR answered > 0
+                 GROUP BY sessions.id ORDER BY started_at, sessions.id",
+            )?
+            .query_map([], |row| {
+                let activity: String = row.get(0)?;
+                let answers = Tally { total: row.get(4)?, correct: row.get(5)? };
+                let decisions = Tally { total: row.get(6)?, correct: row.get(7)? };
+                Ok(SessionSummary {
+                    started_at: row.get::<_, i64>(1)? as u64,
+                    hands: row.get(2)?,
+                    net: row.get(3)?,
+                    strategy: if activity == "Strategy Quiz" { decisions + answers } else { decisions },
+                    counting: if activity == "Count Trainer" { answers } else { Tally::default() },
+                    activity,
+                })
+            })?
+            .collect::<Result<_, _>>()?;
+        Ok(sessions)
+    }
+
+    /// Every session, hand and drill answer kept as CSV, a table each named
+    /// for the file it's written to. Times are UTC, e.g. 2026-10-17 21:04:13.
+    pub fn to_csv(&self) -> Result<Vec<(&'static str, String)>, Box<dyn std::error::Error>> {
+        Ok(vec![
+            ("sessions", self.query_csv(
+                "SELECT sessions.id AS session, activity, datetime(started_at, 'unixepoch') AS started_at, datetime(ended_at, 'unixepoch') AS ended_at,
+                        sessions.hands, sessions.net, answered, correct,
+                        COALESCE(SUM(CASE WHEN correct_decisions IS NULL THEN 0 ELSE decisions END), 0) AS graded_decisions,
+                        COALESCE(SUM(correct_decisions), 0) AS correct_decisions
+                 FROM sessions LEFT JOIN hands ON hands.session_id = sessions.id
+                 WHERE sessions.hands > 0 OR answered > 0
+                 GROUP BY sessions.id ORDER BY started_at, sessions.id",
+            )?),
+            ("hands", self.query_csv(
+                "SELECT id AS hand, session_id AS session, datetime(played_at, 'unixepoch') AS played_at, rules, variant, dealer, player,
+                        bet, net, insurance_net, decisions, correct_decisions, running_count, true_count
+                 FROM hands ORDER BY played_at, id",
+            )?),
+            ("answers", self.query_csv(
+                "SELECT answers.id AS answer, session_id AS session, datetime(answered_at, 'unixepoch') AS answered_at, activity, drill, answers.correct
+                 FROM answers JOIN sessions ON sessions.id = answers.session_id
+                 ORDER BY answered_at, answers.id",
+            )?),
+        ])
+    }
+
+    /// A query's rows as CSV, headed by its column names. A hand that
+    /// wasn't graded leaves its correct decisions empty.
+    fn query_csv(&self, sql: &str) -> Result<String, Box<dyn std::error::Error>> {
+        let mut statement = self.connection.prepare(sql)?;
+        let mut csv = statement.column_names().join(",");
+        csv.push('\n');
+        let mut rows = statement.query([])?;
+        while let Some(row) = rows.next()? {
+            let fields = (0..row.as_ref().column_count())
+                .map(|index| Ok(match row.get_ref(index)? {
+                    ValueRef::Null => String::new(),
+                    ValueRef::Integer(value) => value.to_string(),
+                    ValueRef::Real(value) => value.to_string(),
+                    ValueRef::Text(text) | ValueRef::Blob(text) => csv_field(&String::from_utf8_lossy(text)),
+                }))
+                .collect::<Result<Vec<String>, rusqlite::Error>>()?;
+            csv.push_str(&fields.join(","));
+            csv.push('\n');
+        }
+        Ok(csv)
+    }
+
+    /// Answers grouped by `column`, either the drill or the session's activity.
+    fn answer_tallies(&self, column: &str) -> Result<Vec<(String, Tally)>, Box<dyn std::error::Error>> {
+        let tallies = self.connection
+            .prepare(&format!(
+                "SELECT {column}, COUNT(*), SUM(answers.correct)
+                 FROM answers JOIN sessions ON sessions.id = answers.session_id
+                 GROUP BY {column} ORDER BY {column}"
+            ))?
+            .query_map([], |row| Ok((row.get(0)?, Tally { total: row.get(1)?, correct: row.get(2)? })))?
+            .collect::<Result<_, _>>()?;
+        Ok(tallies)
+    }
+}
+
+/// Adds a cell's right and wrong plays to its row. Hands are kept as JSON,
+/// e.g. {"Hard":16}.
+fn add_to_cell(connection: &Connection, cell: &CellRecord) -> Result<(), Box<dyn std::error::Error>> {
+    connection.execute(
+        "INSERT INTO cells (chart, hand, upcard_column, correct, wrong) VALUES (?1, ?2, ?3, ?4, ?5)
+         ON CONFLICT (chart, hand, upcard_column) DO UPDATE SET correct = correct + excluded.correct, wrong = wrong + excluded.wrong",
+        params![cell.chart, serde_json::to_string(&cell.hand)?, cell.column as i64, cell.tally.right, cell.tally.wrong],
+    )?;
+    Ok(())
+}
+
+/// Writes the play history to history_sessions.csv, history_hands.csv and
+/// history_answers.csv in `dir`, returning the files written.
+pub fn export_history_csv(store: &HistoryStore, dir: &Path) -> Result<Vec<PathBuf>, String> {
+    let tables = store.to_csv().map_err(|err| format!("Couldn't read the play history: {}", err))?;
+    fs::create_dir_all(dir).map_err(|err| format!("Couldn't create {}: {}", dir.display(), err))?;
+    tables.into_iter().map(|(table, csv)| {
+        let path = dir.join(format!("history_{}.csv", table));
+        fs::write(&path, csv).map_err(|err| format!("Couldn't write {}: {}", path.display(), err))?;
+        Ok(path)
+    }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logic::game::card::{Card, Rank, Suit};
+    use crate::logic::game::history::HandHistory;
+    use crate::logic::game::round::Round;
+    use crate::logic::ev::ChartHand;
+
+    #[test]
+    fn test_hands_and_answers_are_kept_by_session() {
+        let round = Round::sample();
+        let mut history = HandHistory::new();
+        history.record(&round, 2, 1.5);
+
+        let path = std::env::temp_dir().join(format!("history-{}.db", uuid::Uuid::new_v4()));
+        let path = path.to_str().unwrap();
+        let mut game = HistoryStore::open(path, "Game").unwrap();
+        game.record_hand(&history.records()[0], Some(2)).unwrap();
+        game.record_hand(&history.records()[0], None).unwrap();
+        let mut drill = HistoryStore::open(path, "Strategy Quiz").unwrap();
+        drill.record_answer("Strategy Quiz", true).unwrap();
+        drill.record_answer("Strategy Quiz", false).unwrap();
+
+        // Reopened, everything's still there
+        let store = HistoryStore::open(path, "Game").unwrap();
+        let sessions: Vec<(String, i64, f64, i64, i64)> = store.connection
+            .prepare("SELECT activity, hands, net, answered, correct FROM sessions ORDER BY id").unwrap()
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?))).unwrap()
+            .collect::<Result<_, _>>().unwrap();
+        assert_eq!(sessions, vec![("Game".to_string(), 2, 20.0, 0, 0), ("Strategy Quiz".to_string(), 0, 0.0, 2, 1)]);
+        let (player, bet, graded): (String, f64, Option<i64>) = store.connection
+            .query_row("SELECT player, bet, correct_decisions FROM hands ORDER BY id LIMIT 1", [], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?))).unwrap();
+        assert_eq!((player, bet, graded), (history.records()[0].hands[0].hand.to_string(), 10.0, Some(2)));
+
+        // Only the graded hand's decisions count towards accuracy, and the
+        // quiz answers count as strategy
+        let stats = store.lifetime_stats().unwrap();
+        assert_eq!(stats.sessions, 2);
+        assert_eq!(stats.hands, HandTotals { hands: 2, net: 20.0, wagered: 20.0, decisions: Tally { total: 2, correct: 2 } });
+        assert_eq!(stats.variants, vec![("Classic".to_string(), stats.hands.clone())]);
+        assert_eq!(stats.drills, vec![("Strategy Quiz".to_string(), Tally { total: 2, correct: 1 })]);
+        assert_eq!(stats.strategy_accuracy(), Tally { total: 4, correct: 3 });
+        assert_eq!(stats.counting_accuracy().accuracy(), None);
+
+        let sessions = store.sessions().unwrap();
+        assert_eq!(sessions.iter().map(|session| (session.hands, session.net)).collect::<Vec<_>>(), vec![(2, 20.0), (0, 0.0)]);
+        assert_eq!(sessions.iter().map(|session| session.strategy).collect::<Vec<_>>(), vec![Tally { total: 2, correct: 2 }, Tally { total: 2, correct: 1 }]);
+
+        // Exported a row each, the ungraded hand's correct decisions left empty
+        let dir = std::env::temp_dir().join(format!("history-export-{}", uuid::Uuid::new_v4()));
+        let paths = export_history_csv(&store, &dir).unwrap();
+        assert_eq!(paths.iter().map(|path| path.file_name().unwrap().to_str().unwrap()).collect::<Vec<_>>(), vec!["history_sessions.csv", "history_hands.csv", "history_answers.csv"]);
+        let csv: Vec<String> = paths.iter().map(|path| fs::read_to_string(path).unwrap()).collect();
+        assert_eq!(csv[0].lines().next(), Some("session,activity,started_at,ended_at,hands,net,answered,correct,graded_decisions,correct_decisions"));
+        assert_eq!(csv[0].lines().nth(1).unwrap().split(',').skip(4).collect::<Vec<_>>(), vec!["2", "20", "0", "0", "2", "2"]);
+        assert_eq!(csv[0].lines().count(), 3);
+        let hands: Vec<&str> = csv[1].lines().collect();
+        assert_eq!(hands.len(), 3);
+        assert!(hands[1].contains(&format!(",{},10,10,0,2,2,", csv_field(&history.records()[0].hands[0].hand.to_string()))));
+        assert!(hands[2].contains(",10,10,0,2,,"));
+        assert_eq!(csv[2].lines().skip(1).map(|line| line.split(',').skip(3).collect::<Vec<_>>().join(",")).collect::<Vec<_>>(), vec!["Strategy Quiz,Strategy Quiz,1", "Strategy Quiz,Strategy Quiz,0"]);
+        fs::remove_dir_all(&dir).unwrap();
+
+        drop((game, drill, store));
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_chart_cell_plays_add_up_across_screens() {
+        let path = std::env::temp_dir().join(format!("history-{}.db", uuid::Uuid::new_v4()));
+        let path = path.to_str().unwrap();
+        let ten = Card::new(Rank::King, Suit::Spades);
+        // Both screens open the database before either records a play
+        let mut game = HistoryStore::open(path, "Game").unwrap();
+        let mut quiz = HistoryStore::open(path, "Strategy Quiz").unwrap();
+
+        let mut plays = DecisionAccuracy::default();
+        plays.record("chart", ChartHand::Hard(16), ten, true);
+        plays.record("chart", ChartHand::Pair(11), ten, true);
+        game.add_cells(&plays).unwrap();
+        let mut answer = DecisionAccuracy::default();
+        answer.record("chart", ChartHand::Hard(16), ten, false);
+        quiz.add_cells(&answer).unwrap();
+        game.add_cells(&answer).unwrap();
+
+        // Nothing either screen recorded is lost
+        let accuracy = HistoryStore::open(path, "Statistics").unwrap().decision_accuracy().unwrap();
+        assert_eq!(accuracy.tally("chart", ChartHand::Hard(16), 8), CellTally { right: 1, wrong: 2 });
+        assert_eq!(accuracy.tally("chart", ChartHand::Pair(11), 8), CellTally { right: 1, wrong: 0 });
+        assert_eq!(accuracy.cells.len(), 2);
+
+        drop((game, quiz));
+        fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,336 @@
+use std::io::{self, ErrorKind, Read, Write};
+use std::net::{IpAddr, TcpListener, TcpStream, ToSocketAddrs, UdpSocket};
+use std::time::Duration;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use crate::logic::game::round::PlayerAction;
+use crate::logic::game::shared_table::SharedTable;
+use crate::logic::game::shoe::Shoe;
+
+pub const DEFAULT_PORT: u16 = 7777;
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+/// Longest line a peer may send, far more than a full table takes
+const MAX_LINE_LENGTH: usize = 1 << 20;
+/// Most output left waiting for a peer that has stopped reading before it's given up on
+const MAX_PENDING: usize = 4 << 20;
+
+// ---- Protocol ----
+// Every message is a single line of JSON. Players send what they want to do,
+// the host applies it to its table and sends everyone the table as it now
+// stands, so players never need to agree on anything themselves.
+
+/// Messages a player sends to the host.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub enum ClientMessage {
+    /// Take a seat, or return to the seat of the same name after leaving
+    Join { name: String },
+    Bet(f64),
+    Rebuy,
+    Insurance(bool),
+    Action(PlayerAction),
+}
+
+/// Messages the host sends to players.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub enum ServerMessage {
+    /// The seat the player was given
+    Welcome { seat: usize },
+    Table(Box<SharedTable>),
+    /// Something the player asked for wasn't allowed
+    Rejected(String),
+}
+
+// ---- Connection ----
+
+/// A TCP stream carrying one message per line, read and written without
+/// blocking so it can be polled from the main loop.
+pub struct Connection {
+    stream: TcpStream,
+
+    /// Bytes received that don't yet make up a whole line
+    buffer: Vec<u8>,
+    /// Bytes sent that the socket hasn't taken yet
+    outgoing: Vec<u8>,
+    closed: bool,
+}
+
+impl Connection {
+    pub fn connect(address: &str) -> io::Result<Self> {
+        let address = address.to_socket_addrs()?
+            .next()
+            .ok_or_else(|| io::Error::new(ErrorKind::InvalidInput, format!("Unknown address: {}", address)))?;
+        Self::from_stream(TcpStream::connect_timeout(&address, CONNECT_TIMEOUT)?)
+    }
+
+    fn from_stream(stream: TcpStream) -> io::Result<Self> {
+        stream.set_nonblocking(true)?;
+        stream.set_nodelay(true)?;
+        Ok(Self {
+            stream,
+            buffer: Vec::new(),
+            outgoing: Vec::new(),
+            closed: false,
+        })
+    }
+
+    /// Queues a message and writes what the socket will take, the rest goes
+    /// out with later sends and receives. Fails once the other end has left
+    /// too much unread, so a peer that stops reading can't hold up the table.
+    pub fn send<T: Serialize>(&mut self, message: &T) -> io::Result<()> {
+        self.outgoing.extend(serde_json::to_vec(message)?);
+        self.outgoing.push(b'\n');
+        self.flush()?;
+        if self.outgoing.len() > MAX_PENDING {
+            return Err(io::Error::new(ErrorKind::TimedOut, "The other end stopped reading"));
+        }
+        Ok(())
+    }
+
+    /// Writes queued output until the socket would block.
+    fn flush(&mut self) -> io::Result<()> {
+        while !self.outgoing.is_empty() {
+            match self.stream.write(&self.outgoing) {
+                Ok(0) => return Err(io::Error::new(ErrorKind::WriteZero, "The connection was closed")),
+                Ok(written) => {
+                    self.outgoing.drain(..written);
+                }
+                Err(err) if err.kind() == ErrorKind::WouldBlock => break,
+                Err(err) if err.kind() == ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(())
+    }
+
+    /// Every complete message received since the last call. Fails once the
+    /// other end has hung up and everything it sent has been read, or sends
+    /// a line too long to be a message.
+    pub fn receive<T: DeserializeOwned>(&mut self) -> io::Result<Vec<T>> {
+        self.flush()?;
+        let mut chunk = [0; 4096];
+        while !self.closed && self.buffer.len() <= MAX_LINE_LENGTH {
+            match self.stream.read(&mut chunk) {
+                Ok(0) => self.closed = true,
+                Ok(read) => self.buffer.extend_from_slice(&chunk[..read]),
+                Err(err) if err.kind() == ErrorKind::WouldBlock => break,
+                Err(err) if err.kind() == ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err),
+            }
+        }
+
+        let mut messages = Vec::new();
+        while let Some(end) = self.buffer.iter().position(|byte| *byte == b'\n') {
+            let line: Vec<u8> = self.buffer.drain(..=end).collect();
+            messages.push(serde_json::from_slice(&line[..end])?);
+        }
+        if self.buffer.len() > MAX_LINE_LENGTH {
+            return Err(io::Error::new(ErrorKind::InvalidData, "The other end sent a line too long to be a message"));
+        }
+        if messages.is_empty() && self.closed {
+            return Err(io::Error::new(ErrorKind::ConnectionAborted, "The connection was closed"));
+        }
+        Ok(messages)
+    }
+}
+
+// ---- Table Server ----
+
+struct RemotePlayer {
+    connection: Connection,
+
+    /// Set once the player has joined
+    seat: Option<usize>,
+    connected: bool,
+}
+
+/// Runs a `SharedTable` for players connecting over TCP. The host's own
+/// seats are played locally, everyone else sends `ClientMessage`s.
+pub struct TableServer {
+    listener: TcpListener,
+    players: Vec<RemotePlayer>,
+}
+
+impl TableServer {
+    /// Listens for players on every interface.
+    pub fn host(port: u16) -> io::Result<Self> {
+        let listener = TcpListener::bind(("0.0.0.0", port))?;
+        listener.set_nonblocking(true)?;
+        Ok(Self {
+            listener,
+            players: Vec::new(),
+        })
+    }
+
+    pub fn port(&self) -> u16 {
+        self.listener.local_addr().map_or(0, |address| address.port())
+    }
+
+    /// Accepts new players and applies everything players have sent since
+    /// the last poll. Returns whether the table changed.
+    pub fn poll(&mut self, table: &mut SharedTable, shoe: &mut Shoe) -> bool {
+        while let Ok((stream, _)) = self.listener.accept() {
+            if let Ok(connection) = Connection::from_stream(stream) {
+                self.players.push(RemotePlayer { connection, seat: None, connected: true });
+            }
+        }
+
+        let mut changed = false;
+        for player in self.players.iter_mut() {
+            match player.connection.receive::<ClientMessage>() {
+                Ok(messages) => {
+                    for message in messages {
+                        let result = Self::apply(table, shoe, player, message);
+                        if let Err(err) = result {
+                            let _ = player.connection.send(&ServerMessage::Rejected(err));
+                        } else {
+                            changed = true;
+                        }
+                    }
+                }
+                Err(_) => {
+                    // The seat is kept so the player can pick up where they left off
+                    player.connected = false;
+                    if let Some(seat) = player.seat {
+                        table.set_away(seat, true, shoe);
+                        changed = true;
+                    }
+                }
+            }
+        }
+        self.players.retain(|player| player.connected);
+        changed
+    }
+
+    fn apply(table: &mut SharedTable, shoe: &mut Shoe, player: &mut RemotePlayer, message: ClientMessage) -> Result<(), String> {
+        let Some(seat) = player.seat else {
+            let ClientMessage::Join { name } = message else {
+                return Err("Join the table first".to_string());
+            };
+            let returning = table.seats().iter().position(|seat| seat.away && seat.name == name.trim());
+            let seat = match returning {
+                Some(seat) => seat,
+                None => table.join(&name)?,
+            };
+            table.set_away(seat, false, shoe);
+            player.seat = Some(seat);
+            return player.connection.send(&ServerMessage::Welcome { seat }).map_err(|err| err.to_string());
+        };
+
+        match message {
+            ClientMessage::Join { .. } => Err("Already seated".to_string()),
+            ClientMessage::Bet(bet) => table.set_bet(seat, bet),
+            ClientMessage::Rebuy => table.rebuy(seat),
+            ClientMessage::Insurance(take) => table.decide_insurance(seat, take, shoe),
+            ClientMessage::Action(action) => table.act(seat, action, shoe),
+        }
+    }
+
+    /// Sends every seated player the table, without the dealer's hole card.
+    pub fn broadcast(&mut self, table: &SharedTable) {
+        let message = ServerMessage::Table(Box::new(table.public_view()));
+        for player in self.players.iter_mut().filter(|player| player.seat.is_some()) {
+            if player.connection.send(&message).is_err() {
+                player.connected = false;
+            }
+        }
+    }
+}
+
+/// This machine's address on the local network, for telling other players
+/// where to connect. Connecting a UDP socket picks the outgoing interface
+/// without sending anything.
+pub fn local_ip() -> Option<IpAddr> {
+    let socket = UdpSocket::bind(("0.0.0.0", 0)).ok()?;
+    socket.connect(("8.8.8.8", 80)).ok()?;
+    socket.local_addr().ok().map(|address| address.ip())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+    use crate::logic::game::table::TableConfig;
+    use crate::logic::strategy_calculator_logic::StrategyVariables;
+
+    /// Polls until something arrives, the other end runs on another thread.
+    fn receive_one<T: DeserializeOwned>(connection: &mut Connection) -> T {
+        for _ in 0..200 {
+            if let Some(message) = connection.receive::<T>().unwrap().into_iter().next() {
+                return message;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        panic!("Nothing was received");
+    }
+
+    #[test]
+    fn test_player_joins_and_bets_over_tcp() {
+        let rules = StrategyVariables::single_deck();
+        let mut table = SharedTable::new(&rules, &TableConfig::default());
+        table.join("Host").unwrap();
+        let mut shoe = Shoe::new(1, 0.75);
+        let mut server = TableServer::host(0).unwrap();
+        let port = server.port();
+
+        let client = thread::spawn(move || {
+            let mut connection = Connection::connect(&format!("127.0.0.1:{}", port)).unwrap();
+            connection.send(&ClientMessage::Join { name: "Guest".to_string() }).unwrap();
+            let welcome: ServerMessage = receive_one(&mut connection);
+            connection.send(&ClientMessage::Bet(25.0)).unwrap();
+            connection.send(&ClientMessage::Action(PlayerAction::Hit)).unwrap();
+            let rejected: ServerMessage = receive_one(&mut connection);
+            (welcome, rejected)
+        });
+
+        for _ in 0..200 {
+            server.poll(&mut table, &mut shoe);
+            if client.is_finished() {
+                break;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        let (welcome, rejected) = client.join().unwrap();
+
+        assert!(matches!(welcome, ServerMessage::Welcome { seat: 1 }));
+        assert!(matches!(rejected, ServerMessage::Rejected(_)));
+        assert_eq!(table.seats()[1].name, "Guest");
+        assert_eq!(table.seats()[1].bet, 25.0);
+
+        // Everything the host sends has to read back on the other end
+        table.deal(&mut shoe).unwrap();
+        let sent = serde_json::to_vec(&ServerMessage::Table(Box::new(table.public_view()))).unwrap();
+        assert!(serde_json::from_slice::<ServerMessage>(&sent).is_ok());
+    }
+
+    /// A connection and the raw stream at its other end.
+    fn connected_pair() -> (TcpStream, Connection) {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+        let peer = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        (peer, Connection::from_stream(listener.accept().unwrap().0).unwrap())
+    }
+
+    #[test]
+    fn test_peers_that_stop_reading_or_never_end_a_line_are_dropped() {
+        // Sending to a peer that reads nothing gives up rather than blocking
+        let (_peer, mut connection) = connected_pair();
+        let message = ServerMessage::Rejected("x".repeat(1 << 16));
+        assert!((0..1000).any(|_| connection.send(&message).is_err()));
+
+        // A line that never ends isn't kept growing
+        let (mut peer, mut connection) = connected_pair();
+        let writer = thread::spawn(move || {
+            let _ = peer.write_all(&vec![b'x'; MAX_LINE_LENGTH + 1]);
+            peer
+        });
+        let mut result = Ok(vec![]);
+        for _ in 0..200 {
+            result = connection.receive::<ClientMessage>();
+            if result.is_err() {
+                break;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        assert_eq!(result.unwrap_err().kind(), ErrorKind::InvalidData);
+        writer.join().unwrap();
+    }
+}
@@ -1 +1,24 @@
-pub mod strategy_calculator_logic;
\ No newline at end of file
+pub mod strategy_calculator_logic;
+pub mod game;
+pub mod lan;
+pub mod ev;
+pub mod deviations;
+pub mod counting;
+pub mod count_trainer;
+pub mod export;
+pub mod registry;
+pub mod counting_stats;
+pub mod bet_spread;
+pub mod back_counting;
+pub mod strategy_quiz;
+pub mod time_attack;
+pub mod spaced_repetition;
+pub mod streaks;
+pub mod table_math;
+pub mod surrender_drill;
+pub mod decision_accuracy;
+pub mod simulator;
+pub mod comps;
+pub mod history_store;
+pub mod config;
+pub mod app_dirs;
\ No newline at end of file
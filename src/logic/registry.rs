@@ -0,0 +1,136 @@
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+use serde::Deserialize;
+use crate::logic::strategy_calculator_logic::BlackjackStrategy;
+
+/// How long to wait on the registry before giving up
+const FETCH_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A curated set of strategy charts listed in the registry index.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct StrategyPack {
+    pub name: String,
+
+    pub author: String,
+
+    pub description: String,
+
+    /// Where each of the pack's strategy files is, as a URL, a path, or
+    /// relative to the index
+    pub strategies: Vec<String>,
+}
+
+/// The registry's index of strategy packs.
+#[derive(Debug, Deserialize)]
+struct RegistryIndex {
+    packs: Vec<StrategyPack>,
+}
+
+/// Reads a registry file from a web address or a local path.
+fn fetch(location: &str) -> Result<String, String> {
+    if location.starts_with("http://") || location.starts_with("https://") {
+        let agent = ureq::AgentBuilder::new().timeout(FETCH_TIMEOUT).build();
+        // Without the URL, which the caller already reports
+        agent.get(location).call()
+            .map_err(|err| match err {
+                ureq::Error::Status(code, _) => format!("the server answered {}", code),
+                ureq::Error::Transport(transport) => match transport.message() {
+                    Some(message) => format!("{}: {}", transport.kind(), message),
+                    None => transport.kind().to_string(),
+                },
+            })?
+            .into_string()
+            .map_err(|err| err.to_string())
+    } else {
+        fs::read_to_string(location).map_err(|err| err.to_string())
+    }
+}
+
+/// Where `location` is when it's written relative to the index.
+fn resolve(index: &str, location: &str) -> String {
+    let absolute = location.contains("://") || Path::new(location).is_absolute();
+    match index.rfind('/') {
+        Some(end) if !absolute => format!("{}/{}", &index[..end], location),
+        _ => location.to_string(),
+    }
+}
+
+/// The packs listed in the registry index at `index`.
+pub fn fetch_packs(index: &str) -> Result<Vec<StrategyPack>, String> {
+    let json = fetch(index).map_err(|err| format!("Couldn't read the registry at {}: {}", index, err))?;
+    let index: RegistryIndex = serde_json::from_str(&json)
+        .map_err(|err| format!("The registry index isn't valid: {}", err))?;
+    Ok(index.packs)
+}
+
+/// Downloads each of the pack's strategies and saves them to
+/// `strategies_dir`, returning the files written. Nothing is written unless
+/// every strategy in the pack downloads and is a valid chart.
+pub fn install_pack(index: &str, pack: &StrategyPack, strategies_dir: &str) -> Result<Vec<String>, String> {
+    let mut downloads = Vec::new();
+    for location in &pack.strategies {
+        let location = resolve(index, location);
+        let file = location.rsplit(['/', '\\']).next()
+            .filter(|file| file.ends_with(".json") && !file.starts_with('.'))
+            .ok_or_else(|| format!("{} isn't a strategy file", location))?;
+        let json = fetch(&location).map_err(|err| format!("Couldn't download {}: {}", location, err))?;
+
+        let problems = match BlackjackStrategy::from_json(&json) {
+            Ok(strategy) => strategy.validate(),
+            Err(err) => vec![err.to_string()],
+        };
+        if !problems.is_empty() {
+            return Err(format!("{}: {}", file, problems.join("; ")));
+        }
+        downloads.push((file.to_string(), json));
+    }
+
+    for (file, json) in &downloads {
+        let path = Path::new(strategies_dir).join(file);
+        fs::write(&path, json).map_err(|err| format!("Couldn't write {}: {}", path.display(), err))?;
+    }
+    Ok(downloads.into_iter().map(|(file, _)| file).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logic::strategy_calculator_logic::load_strategies;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_install_pack_from_local_registry() {
+        let registry = std::env::temp_dir().join(format!("registry-{}", Uuid::new_v4()));
+        let strategies = registry.join("strategies");
+        fs::create_dir_all(registry.join("packs")).unwrap();
+        fs::create_dir_all(&strategies).unwrap();
+        fs::copy("resources/strategies/default-strategy.json", registry.join("packs/shared.json")).unwrap();
+        fs::write(registry.join("packs/broken.json"), r#"{ "name": "Broken""#).unwrap();
+        fs::write(registry.join("index.json"), r#"{ "packs": [
+            { "name": "Good", "author": "Tester", "description": "Loads", "strategies": ["packs/shared.json"] },
+            { "name": "Bad", "author": "Tester", "description": "Doesn't", "strategies": ["packs/shared.json", "packs/broken.json"] }
+        ] }"#).unwrap();
+
+        let index = registry.join("index.json").to_string_lossy().into_owned();
+        let packs = fetch_packs(&index).unwrap();
+        assert_eq!(packs.iter().map(|pack| pack.name.as_str()).collect::<Vec<_>>(), vec!["Good", "Bad"]);
+
+        let strategies_dir = strategies.to_str().unwrap();
+        // A pack with a broken file installs none of it
+        assert!(install_pack(&index, &packs[1], strategies_dir).unwrap_err().starts_with("broken.json"));
+        assert!(load_strategies(strategies_dir).is_empty());
+
+        assert_eq!(install_pack(&index, &packs[0], strategies_dir).unwrap(), vec!["shared.json"]);
+        assert!(load_strategies(strategies_dir).contains_key("shared"));
+        fs::remove_dir_all(registry).unwrap();
+    }
+
+    #[test]
+    fn test_resolve_relative_to_index() {
+        let index = "https://example.com/registry/index.json";
+        assert_eq!(resolve(index, "packs/a.json"), "https://example.com/registry/packs/a.json");
+        assert_eq!(resolve(index, "https://other.org/b.json"), "https://other.org/b.json");
+        assert_eq!(resolve("registry/index.json", "/charts/c.json"), "/charts/c.json");
+    }
+}
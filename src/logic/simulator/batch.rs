@@ -0,0 +1,239 @@
+use crate::logic::bet_spread::BET_SPREADS;
+use crate::logic::counting::COUNTING_SYSTEMS;
+use crate::logic::deviations::IndexSet;
+use crate::logic::simulator::engine::{CountingPlay, SimulationConfig, SimulationResult};
+use crate::logic::strategy_calculator_logic::BlackjackStrategy;
+use serde::Deserialize;
+use std::fs;
+
+/// Name standing for betting flat with no count in a matrix's systems
+pub const FLAT: &str = "Flat";
+
+/// Games to simulate one after another, every combination of the lists
+/// played for the same rounds off the same seed. Each strategy brings its
+/// own rule set, and the counting systems are each played at every spread
+/// and index set made for them, e.g.
+///
+/// ```json
+/// { "rounds": 1000000, "strategies": ["strategy_6D_S17_DAS_NS_P"], "systems": ["Flat", "Hi-Lo"], "spreads": [8, 16] }
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct BatchMatrix {
+    pub rounds: u64,
+
+    #[serde(default)]
+    pub seed: Option<u64>,
+
+    /// Names of charts in the strategies directory, or paths to them
+    pub strategies: Vec<String>,
+
+    /// Fractions of the shoe dealt before the cut card
+    #[serde(default = "default_penetrations")]
+    pub penetrations: Vec<f64>,
+
+    /// Counting systems by name, with `FLAT` for no count at all
+    #[serde(default = "default_systems")]
+    pub systems: Vec<String>,
+
+    /// Tops of the bet spreads counters play, in betting units, e.g. 8 for 1-8
+    #[serde(default = "default_spreads")]
+    pub spreads: Vec<u32>,
+
+    /// Index sets to play by name, each only with its own counting system.
+    /// Counters also play without one when this is left empty.
+    #[serde(default)]
+    pub deviations: Vec<String>,
+}
+
+fn default_penetrations() -> Vec<f64> {
+    vec![0.75]
+}
+
+fn default_systems() -> Vec<String> {
+    vec![FLAT.to_string()]
+}
+
+fn default_spreads() -> Vec<u32> {
+    vec![8]
+}
+
+/// One game of a batch and what to call it in the summary.
+#[derive(Debug, Clone)]
+pub struct BatchRun {
+    pub strategy: String,
+    pub counting: String,
+    pub config: SimulationConfig,
+}
+
+/// A game of a batch and how it went.
+#[derive(Debug, Clone)]
+pub struct BatchRow {
+    pub strategy: String,
+    pub penetration: f64,
+    pub counting: String,
+    pub result: SimulationResult,
+}
+
+impl BatchMatrix {
+    pub fn from_json(json_str: &str) -> Result<Self, String> {
+        serde_json::from_str(json_str).map_err(|err| format!("Invalid batch file: {}", err))
+    }
+
+    pub fn from_file(path: &str) -> Result<Self, String> {
+        let json = fs::read_to_string(path).map_err(|err| format!("Couldn't read {}: {}", path, err))?;
+        Self::from_json(&json)
+    }
+
+    /// Every combination in the matrix, strategies outermost. `find` loads a
+    /// chart by the name given for it.
+    pub fn runs(&self, find: impl Fn(&str) -> Result<(String, BlackjackStrategy), String>, index_sets: &[IndexSet]) -> Result<Vec<BatchRun>, String> {
+        if self.rounds == 0 {
+            return Err("A batch needs at least one round".to_string());
+        }
+        if let Some(penetration) = self.penetrations.iter().find(|penetration| !(0.1..=0.95).contains(*penetration)) {
+            return Err(format!("Penetration {} is outside 0.1 to 0.95", penetration));
+        }
+        let spreads = self.spreads.iter()
+            .map(|units| BET_SPREADS.iter().find(|spread| spread.max_units == *units).copied()
+                .ok_or_else(|| format!("No 1-{} bet spread, try one of {}", units, BET_SPREADS.map(|spread| spread.max_units.to_string()).join(", "))))
+            .collect::<Result<Vec<_>, String>>()?;
+        let deviations = self.deviations.iter()
+            .map(|name| index_sets.iter().find(|set| set.name == *name).ok_or_else(|| format!("No index set called {}", name)))
+            .collect::<Result<Vec<_>, String>>()?;
+
+        // How each system is played, the same for every strategy
+        let mut counting: Vec<(String, Option<CountingPlay>)> = vec![];
+        for name in &self.systems {
+            if name == FLAT {
+                counting.push((FLAT.to_string(), None));
+                continue;
+            }
+            let system = *COUNTING_SYSTEMS.iter().find(|system| system.name() == name).ok_or_else(|| format!("No counting system called {}", name))?;
+            let sets: Vec<Option<&IndexSet>> = if deviations.is_empty() {
+                vec![None]
+            } else {
+                deviations.iter().filter(|set| set.system == system.name()).map(|set| Some(*set)).collect()
+            };
+            for spread in &spreads {
+                for set in &sets {
+                    let play = CountingPlay { system, spread: *spread, deviations: set.cloned() };
+                    let label = match set {
+                        Some(set) => format!("{} {} with the {}", system.name(), spread, set.name),
+                        None => format!("{} {}", system.name(), spread),
+                    };
+                    counting.push((label, Some(play)));
+                }
+            }
+        }
+        if counting.is_empty() {
+            return Err("No system in the batch has an index set to play".to_string());
+        }
+
+        let mut runs = vec![];
+        for name in &self.strategies {
+            let (strategy_name, strategy) = find(name)?;
+            for penetration in &self.penetrations {
+                for (label, play) in &counting {
+                    let mut config = SimulationConfig::for_strategy(strategy.clone(), self.rounds, self.seed);
+                    config.table.penetration = *penetration;
+                    config.counting = play.clone();
+                    runs.push(BatchRun { strategy: strategy_name.clone(), counting: label.clone(), config });
+                }
+            }
+        }
+        Ok(runs)
+    }
+}
+
+/// The games of a batch side by side, one line each under a header, with
+/// columns as wide as their longest entry.
+pub fn summary_table(rows: &[BatchRow]) -> String {
+    let header = ["Strategy", "Pen", "Counting", "EV", "± 95%", "Avg Bet", "Advantage", "SD / 100", "Units / Hour"];
+    let lines: Vec<[String; 9]> = rows.iter().map(|row| {
+        let result = &row.result;
+        let (_, high) = result.ev_interval();
+        [
+            row.strategy.clone(),
+            format!("{:.0}%", row.penetration * 100.0),
+            row.counting.clone(),
+            format!("{:+.3}%", result.ev() * 100.0),
+            format!("{:.3}%", (high - result.ev()) * 100.0),
+            format!("{:.2}", result.average_bet()),
+            format!("{:+.3}%", result.advantage() * 100.0),
+            format!("{:.2}", result.std_dev_per_100()),
+            format!("{:+.2}", result.per_hour()),
+        ]
+    }).collect();
+    let widths: Vec<usize> = (0..header.len())
+        .map(|column| lines.iter().map(|line| line[column].chars().count()).chain([header[column].chars().count()]).max().unwrap_or(0))
+        .collect();
+
+    // Names read left to right, numbers line up on the right
+    let format_line = |cells: Vec<&str>| -> String {
+        cells.iter().enumerate()
+            .map(|(column, cell)| if column == 0 || column == 2 { format!("{:<width$}", cell, width = widths[column]) } else { format!("{:>width$}", cell, width = widths[column]) })
+            .collect::<Vec<String>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+    let mut table = vec![format_line(header.to_vec())];
+    table.extend(lines.iter().map(|line| format_line(line.iter().map(String::as_str).collect())));
+    table.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logic::deviations::load_index_sets;
+
+    fn find(name: &str) -> Result<(String, BlackjackStrategy), String> {
+        BlackjackStrategy::from_file(&format!("resources/strategies/{}.json", name)).map(|strategy| (name.to_string(), strategy)).map_err(|err| format!("{:?}", err))
+    }
+
+    #[test]
+    fn test_matrix_expands_to_every_combination() {
+        let matrix = BatchMatrix::from_json(r#"{
+            "rounds": 2000,
+            "seed": 3,
+            "strategies": ["strategy_6D_S17_DAS_NS_P", "strategy_6D_H17_DAS_NS_P"],
+            "penetrations": [0.7, 0.85],
+            "systems": ["Flat", "Hi-Lo", "KO"],
+            "spreads": [8, 16],
+            "deviations": ["Illustrious 18"]
+        }"#).unwrap();
+        let index_sets = load_index_sets("resources/deviations");
+        let runs = matrix.runs(find, &index_sets).unwrap();
+
+        // Flat, then Hi-Lo at both spreads with its index set. KO has no index set here.
+        assert_eq!(runs.len(), 2 * 2 * 3);
+        assert_eq!(runs.iter().take(3).map(|run| run.counting.as_str()).collect::<Vec<&str>>(), ["Flat", "Hi-Lo 1-8 with the Illustrious 18", "Hi-Lo 1-16 with the Illustrious 18"]);
+        assert_eq!(runs[3].config.table.penetration, 0.85);
+        assert_eq!(runs[6].strategy, "strategy_6D_H17_DAS_NS_P");
+        assert!(!runs[6].config.rules.dealer_stands_on_soft_17);
+        assert!(runs.iter().all(|run| run.config.rounds == 2000 && run.config.seed == Some(3)));
+
+        let defaults = BatchMatrix::from_json(r#"{ "rounds": 10, "strategies": ["default-strategy"] }"#).unwrap();
+        assert_eq!(defaults.runs(find, &index_sets).unwrap().len(), 1);
+        assert!(BatchMatrix::from_json(r#"{ "rounds": 10, "strategies": ["default-strategy"], "spreads": [5], "systems": ["Hi-Lo"] }"#).unwrap().runs(find, &index_sets).is_err());
+        assert!(BatchMatrix::from_json(r#"{ "rounds": 10, "strategies": ["default-strategy"], "systems": ["Red Seven"] }"#).unwrap().runs(find, &index_sets).is_err());
+        assert!(BatchMatrix::from_json(r#"{ "rounds": 10 }"#).is_err());
+    }
+
+    #[test]
+    fn test_summary_table_lines_up() {
+        let result = SimulationResult { rounds: 100, wins: 45, pushes: 10, losses: 45, net: -1.0, net_squared: 130.0, wagered: 110.0, bet: 100.0 };
+        let rows = vec![
+            BatchRow { strategy: "default-strategy".to_string(), penetration: 0.75, counting: FLAT.to_string(), result: result.clone() },
+            BatchRow { strategy: "six-deck".to_string(), penetration: 0.9, counting: "Hi-Lo 1-16".to_string(), result },
+        ];
+        let table = summary_table(&rows);
+        let lines: Vec<&str> = table.lines().collect();
+
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("Strategy          Pen  Counting"));
+        assert!(lines[1].starts_with("default-strategy  75%  Flat        -1.000%"));
+        assert!(lines[2].starts_with("six-deck          90%  Hi-Lo 1-16  -1.000%"));
+        assert_eq!(lines[1].chars().count(), lines[2].chars().count());
+    }
+}
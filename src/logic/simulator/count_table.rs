@@ -0,0 +1,65 @@
+use crate::logic::simulator::engine::SimulationResult;
+use std::collections::BTreeMap;
+
+/// Furthest true count kept apart either side of zero, counts beyond it
+/// are folded into the ends
+pub const COUNT_TABLE_LIMIT: i32 = 10;
+
+/// How rounds went at each true count before the deal, floored to whole
+/// counts, for runs that count the cards.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct CountTable {
+    pub counts: BTreeMap<i32, SimulationResult>,
+}
+
+impl CountTable {
+    pub fn record(&mut self, true_count: i32, net: f64, wagered: f64, bet: f64) {
+        self.counts.entry(true_count.clamp(-COUNT_TABLE_LIMIT, COUNT_TABLE_LIMIT)).or_default().record(net, wagered, bet);
+    }
+
+    /// Adds in the rounds of another run.
+    pub fn merge(&mut self, other: &CountTable) {
+        for (true_count, result) in &other.counts {
+            self.counts.entry(*true_count).or_default().merge(result);
+        }
+    }
+
+    pub fn rounds(&self) -> u64 {
+        self.counts.values().map(|result| result.rounds).sum()
+    }
+
+    /// Share of rounds dealt at `true_count`.
+    pub fn frequency(&self, true_count: i32) -> f64 {
+        let rounds = self.rounds();
+        if rounds == 0 {
+            return 0.0;
+        }
+        self.counts.get(&true_count).map_or(0, |result| result.rounds) as f64 / rounds as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rounds_are_kept_by_count() {
+        let mut table = CountTable::default();
+        table.record(0, -1.0, 1.0, 1.0);
+        table.record(0, 1.0, 1.0, 1.0);
+        table.record(3, 8.0, 8.0, 4.0);
+        table.record(14, 2.0, 2.0, 2.0);
+
+        let mut other = CountTable::default();
+        other.record(-12, -1.0, 1.0, 1.0);
+        other.record(3, -4.0, 4.0, 4.0);
+        table.merge(&other);
+
+        assert_eq!(table.counts.keys().copied().collect::<Vec<i32>>(), vec![-COUNT_TABLE_LIMIT, 0, 3, COUNT_TABLE_LIMIT]);
+        assert_eq!(table.rounds(), 6);
+        assert_eq!(table.frequency(0), 2.0 / 6.0);
+        assert_eq!(table.frequency(5), 0.0);
+        assert_eq!((table.counts[&3].ev(), table.counts[&3].advantage()), (2.0, 0.5));
+        assert_eq!(CountTable::default().frequency(0), 0.0);
+    }
+}
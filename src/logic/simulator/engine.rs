@@ -0,0 +1,618 @@
+use crate::logic::bet_spread::BetSpread;
+use crate::logic::counting::CountingSystem;
+use crate::logic::deviations::IndexSet;
+use crate::logic::game::advisor::{advise, chart_hand, resolve_code};
+use crate::logic::game::round::{PlayerAction, Round, RoundPhase};
+use crate::logic::game::shoe::Shoe;
+use crate::logic::game::table::TableConfig;
+use crate::logic::simulator::count_table::CountTable;
+use crate::logic::simulator::sessions::{Sessions, ROUNDS_PER_HOUR};
+use crate::logic::simulator::trajectory::{Trajectories, TRAJECTORY_POINTS};
+use crate::logic::strategy_calculator_logic::{BlackjackStrategy, StrategyVariables};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rayon::prelude::*;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+/// Rounds played off each shoe when a run is spread over threads. Streams
+/// are the same size whatever the number of cores, so a seeded run comes
+/// out the same on any machine.
+const STREAM_ROUNDS: u64 = 100_000;
+
+/// Rounds a stream plays between reporting progress and checking whether
+/// the run has been cancelled
+pub const PROGRESS_ROUNDS: u64 = 5_000;
+
+/// Paths a run is cut into for following the bankroll, more for runs too
+/// long for each path to fit in a stream
+const TRAJECTORY_PATHS: u64 = 100;
+
+/// Standard errors either side of the EV that hold the true EV 95% of the time
+pub const CONFIDENCE_Z: f64 = 1.96;
+
+/// What to simulate: a strategy chart played under a set of rules.
+#[derive(Debug, Clone)]
+pub struct SimulationConfig {
+    pub rules: StrategyVariables,
+    pub table: TableConfig,
+    pub strategy: BlackjackStrategy,
+
+    /// Rounds to play, one hand a round before any splits
+    pub rounds: u64,
+
+    /// Shuffles the same way for the same seed, random when none
+    pub seed: Option<u64>,
+
+    /// Counts the cards to size bets and change plays, betting one unit
+    /// flat on basic strategy when none
+    pub counting: Option<CountingPlay>,
+}
+
+/// How a counter plays: bets spread by the true count of a counting system,
+/// and the chart departed from at the indices of an index set. Both go by
+/// the true count before the round is dealt.
+#[derive(Clone)]
+pub struct CountingPlay {
+    pub system: &'static dyn CountingSystem,
+    pub spread: BetSpread,
+
+    /// Index plays for the system, basic strategy throughout when none
+    pub deviations: Option<IndexSet>,
+}
+
+impl CountingPlay {
+    pub fn new(system: &'static dyn CountingSystem, spread: BetSpread) -> Self {
+        Self { system, spread, deviations: None }
+    }
+
+    /// The index play's action for the round's active hand, when the count
+    /// calls for one and it can be made.
+    fn deviation(&self, strategy: &BlackjackStrategy, round: &Round, true_count: i32) -> Option<PlayerAction> {
+        let deviations = self.deviations.as_ref()?;
+        let hand = &round.hands()[round.active_hand_index()?].hand;
+        let upcard = round.dealer().cards().first()?.value();
+        let available = round.available_actions();
+        let row = chart_hand(strategy, hand, available.contains(&PlayerAction::Split));
+        resolve_code(&deviations.play_for(row, upcard, true_count)?.play, &available)
+    }
+
+    fn insures(&self, true_count: i32) -> bool {
+        self.deviations.as_ref().is_some_and(|deviations| deviations.insures(true_count))
+    }
+}
+
+impl fmt::Debug for CountingPlay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CountingPlay")
+            .field("system", &self.system.name())
+            .field("spread", &self.spread)
+            .field("deviations", &self.deviations.as_ref().map(|deviations| &deviations.name))
+            .finish()
+    }
+}
+
+impl SimulationConfig {
+    /// Plays the chart under its own rules at a standard table.
+    pub fn for_strategy(strategy: BlackjackStrategy, rounds: u64, seed: Option<u64>) -> Self {
+        Self { rules: strategy.rules.clone(), table: TableConfig::default(), strategy, rounds, seed, counting: None }
+    }
+}
+
+/// Totals over the rounds played, in betting units.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct SimulationResult {
+    pub rounds: u64,
+
+    /// Rounds that finished ahead, level and behind, splits counted together
+    pub wins: u64,
+    pub pushes: u64,
+    pub losses: u64,
+
+    /// Sum of each round's result, and of its square for the spread
+    pub net: f64,
+    pub net_squared: f64,
+
+    /// Money put on the table, counting doubles and splits
+    pub wagered: f64,
+
+    /// Bets placed before the cards were dealt
+    pub bet: f64,
+}
+
+impl SimulationResult {
+    pub fn record(&mut self, net: f64, wagered: f64, bet: f64) {
+        self.rounds += 1;
+        if net > 0.0 {
+            self.wins += 1;
+        } else if net < 0.0 {
+            self.losses += 1;
+        } else {
+            self.pushes += 1;
+        }
+        self.net += net;
+        self.net_squared += net * net;
+        self.wagered += wagered;
+        self.bet += bet;
+    }
+
+    /// Average result of a round in betting units.
+    pub fn ev(&self) -> f64 {
+        if self.rounds == 0 {
+            return 0.0;
+        }
+        self.net / self.rounds as f64
+    }
+
+    /// Standard deviation of a round's result in betting units.
+    pub fn std_dev(&self) -> f64 {
+        if self.rounds < 2 {
+            return 0.0;
+        }
+        let rounds = self.rounds as f64;
+        let variance = (self.net_squared - self.net * self.net / rounds) / (rounds - 1.0);
+        variance.max(0.0).sqrt()
+    }
+
+    /// Standard deviation of the result over 100 rounds in betting units.
+    pub fn std_dev_per_100(&self) -> f64 {
+        self.std_dev() * 10.0
+    }
+
+    /// How far the EV is likely off the true EV, one standard deviation of
+    /// a round shrunk by the rounds played.
+    pub fn standard_error(&self) -> f64 {
+        if self.rounds == 0 {
+            return 0.0;
+        }
+        self.std_dev() / (self.rounds as f64).sqrt()
+    }
+
+    /// Range the true EV falls in with 95% confidence.
+    pub fn ev_interval(&self) -> (f64, f64) {
+        let margin = CONFIDENCE_Z * self.standard_error();
+        (self.ev() - margin, self.ev() + margin)
+    }
+
+    /// Adds in the rounds of another run.
+    pub fn merge(&mut self, other: &SimulationResult) {
+        self.rounds += other.rounds;
+        self.wins += other.wins;
+        self.pushes += other.pushes;
+        self.losses += other.losses;
+        self.net += other.net;
+        self.net_squared += other.net_squared;
+        self.wagered += other.wagered;
+        self.bet += other.bet;
+    }
+
+    /// Average bet before the cards were dealt, in betting units.
+    pub fn average_bet(&self) -> f64 {
+        if self.rounds == 0 {
+            return 0.0;
+        }
+        self.bet / self.rounds as f64
+    }
+
+    /// Result as a share of the bets placed, the player's edge over the house.
+    pub fn advantage(&self) -> f64 {
+        if self.bet == 0.0 {
+            return 0.0;
+        }
+        self.net / self.bet
+    }
+
+    /// Average result over an hour at `ROUNDS_PER_HOUR`, in betting units.
+    pub fn per_hour(&self) -> f64 {
+        self.ev() * ROUNDS_PER_HOUR as f64
+    }
+
+    fn rate(&self, count: u64) -> f64 {
+        if self.rounds == 0 {
+            return 0.0;
+        }
+        count as f64 / self.rounds as f64
+    }
+
+    pub fn win_rate(&self) -> f64 {
+        self.rate(self.wins)
+    }
+
+    pub fn push_rate(&self) -> f64 {
+        self.rate(self.pushes)
+    }
+
+    pub fn loss_rate(&self) -> f64 {
+        self.rate(self.losses)
+    }
+}
+
+/// How a round went, in betting units.
+pub struct PlayedRound {
+    pub net: f64,
+    pub wagered: f64,
+    pub bet: f64,
+
+    /// True count before the deal, floored, when counting
+    pub true_count: Option<i32>,
+}
+
+/// A shoe for the rules and table, shuffled from the seed when there is one.
+pub fn shoe_for(config: &SimulationConfig) -> Shoe {
+    match config.seed {
+        Some(seed) => Shoe::seeded_from_rules(&config.rules, &config.table, seed),
+        None => Shoe::from_rules(&config.rules, &config.table),
+    }
+}
+
+/// Plays one round of `config` off the shoe as it stands. A counter's bet,
+/// insurance and index plays go by the true count before the deal.
+pub fn play_round_off(config: &SimulationConfig, shoe: &mut Shoe) -> Result<PlayedRound, String> {
+    let counting = config.counting.as_ref();
+    let true_count = counting.map(|counting| shoe.true_count(counting.system));
+    let bet = counting.zip(true_count).map_or(1, |(counting, true_count)| counting.spread.units(true_count)) as f64;
+    let true_count = true_count.map(|true_count| true_count.floor() as i32);
+    let mut round = Round::deal(&config.rules, &config.table, &[bet], shoe);
+    if round.phase() == RoundPhase::Insurance {
+        let insure = counting.zip(true_count).is_some_and(|(counting, true_count)| counting.insures(true_count));
+        round.resolve_insurance(insure, shoe)?;
+    }
+    while round.phase() == RoundPhase::PlayerTurn {
+        let advice = advise(&config.strategy, &round).ok_or_else(|| {
+            let hand = &round.hands()[round.active_hand_index().unwrap_or_default()].hand;
+            format!("The chart has no play for {} vs {}", hand.chart_label(), round.dealer().cards()[0].chart_label())
+        })?;
+        let deviation = counting.zip(true_count).and_then(|(counting, true_count)| counting.deviation(&config.strategy, &round, true_count));
+        round.apply(deviation.unwrap_or(advice.action), shoe)?;
+    }
+    Ok(PlayedRound { net: round.net_result(), wagered: round.total_wagered(), bet, true_count })
+}
+
+/// Plays rounds of one hand at a time off a shoe that carries across
+/// rounds, every decision taken from the chart and insurance always
+/// declined, the way basic strategy plays. A counter sizes bets from the
+/// count before each round, and takes insurance and departs from the
+/// chart where their index plays say to.
+pub struct Simulator {
+    config: SimulationConfig,
+    shoe: Shoe,
+    result: SimulationResult,
+
+    /// Rounds played by the true count they were dealt at, when counting
+    by_count: CountTable,
+}
+
+impl Simulator {
+    pub fn new(config: SimulationConfig) -> Self {
+        let shoe = shoe_for(&config);
+        Self { config, shoe, result: SimulationResult::default(), by_count: CountTable::default() }
+    }
+
+    /// Plays a round, shuffling first once the cut card has come out, and
+    /// counts it in `played`.
+    fn play_round(&mut self, played: &mut SimulationResult) -> Result<(), String> {
+        if self.shoe.needs_shuffle() {
+            self.shoe.shuffle();
+        }
+        let round = play_round_off(&self.config, &mut self.shoe)?;
+        played.record(round.net, round.wagered, round.bet);
+        if let Some(true_count) = round.true_count {
+            self.by_count.record(true_count, round.net, round.wagered, round.bet);
+        }
+        Ok(())
+    }
+
+    /// Plays up to `rounds` more rounds without going past the configured
+    /// total, so a long run can be played a piece at a time, and returns
+    /// how those rounds went.
+    pub fn play(&mut self, rounds: u64) -> Result<SimulationResult, String> {
+        let mut played = SimulationResult::default();
+        for _ in 0..rounds.min(self.remaining()) {
+            self.play_round(&mut played)?;
+        }
+        self.result.merge(&played);
+        Ok(played)
+    }
+
+    /// Rounds still to play.
+    pub fn remaining(&self) -> u64 {
+        self.config.rounds.saturating_sub(self.result.rounds)
+    }
+
+    /// Every round played so far.
+    pub fn result(&self) -> &SimulationResult {
+        &self.result
+    }
+}
+
+/// How far a run on another thread has got, and a way to stop it.
+#[derive(Debug, Default)]
+pub struct SimulationProgress {
+    /// Rounds played so far across every stream
+    played: Mutex<SimulationResult>,
+    cancelled: AtomicBool,
+}
+
+impl SimulationProgress {
+    /// The rounds played so far, added up in whatever order the streams
+    /// reported them.
+    pub fn so_far(&self) -> SimulationResult {
+        self.played.lock().map(|played| played.clone()).unwrap_or_default()
+    }
+
+    /// Stops the run after the rounds being played, keeping those already played.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// Adds rounds just played to the run's progress.
+    pub fn report(&self, played: &SimulationResult) {
+        if let Ok(mut so_far) = self.played.lock() {
+            so_far.merge(played);
+        }
+    }
+}
+
+/// A run played on another thread, so the screen that started it keeps
+/// drawing while it plays.
+pub struct BackgroundRun<T> {
+    progress: Arc<SimulationProgress>,
+    handle: JoinHandle<Result<T, String>>,
+    started: Instant,
+}
+
+impl<T: Send + 'static> BackgroundRun<T> {
+    /// Starts `run` on another thread, handing it the progress to report to.
+    pub fn start(run: impl FnOnce(&SimulationProgress) -> Result<T, String> + Send + 'static) -> Self {
+        let progress = Arc::new(SimulationProgress::default());
+        let shared = progress.clone();
+        let handle = std::thread::spawn(move || run(&shared));
+        Self { progress, handle, started: Instant::now() }
+    }
+
+    pub fn progress(&self) -> &SimulationProgress {
+        &self.progress
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.started.elapsed()
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.handle.is_finished()
+    }
+
+    /// Waits for the run to end and takes its result.
+    pub fn join(self) -> Result<T, String> {
+        self.handle.join().unwrap_or_else(|_| Err("The simulation stopped unexpectedly".to_string()))
+    }
+}
+
+/// Seeds for `count` streams of a run, each drawn in turn from the run's
+/// seed, or none for a run shuffled at random.
+pub fn stream_seeds(seed: Option<u64>, count: usize) -> Vec<Option<u64>> {
+    let mut seeds = seed.map(StdRng::seed_from_u64);
+    (0..count).map(|_| seeds.as_mut().map(|seeds| seeds.random())).collect()
+}
+
+/// Splits a run into streams of `STREAM_ROUNDS`, each dealt off its own shoe.
+pub fn streams(config: &SimulationConfig) -> Vec<SimulationConfig> {
+    let count = config.rounds.div_ceil(STREAM_ROUNDS);
+    stream_seeds(config.seed, count as usize).into_iter()
+        .enumerate()
+        .map(|(index, seed)| {
+            let rounds = (config.rounds - index as u64 * STREAM_ROUNDS).min(STREAM_ROUNDS);
+            SimulationConfig { rounds, seed, ..config.clone() }
+        })
+        .collect()
+}
+
+/// Plays every round of a simulation, the streams spread over every core
+/// and their results added up in order.
+pub fn simulate(config: SimulationConfig) -> Result<SimulationResult, String> {
+    simulate_with_progress(config, &SimulationProgress::default())
+}
+
+/// Plays a simulation, reporting to `progress` as it goes. A cancelled run
+/// stops early with the rounds played up to then.
+pub fn simulate_with_progress(config: SimulationConfig, progress: &SimulationProgress) -> Result<SimulationResult, String> {
+    simulate_in_detail(config, ROUNDS_PER_HOUR, progress).map(|detail| detail.result)
+}
+
+/// Rounds between the points of a path, sized so a run cuts into about
+/// `TRAJECTORY_PATHS` paths that each fit in a stream.
+fn path_step(rounds: u64) -> u64 {
+    (rounds / TRAJECTORY_PATHS / TRAJECTORY_POINTS as u64).clamp(2, STREAM_ROUNDS / TRAJECTORY_POINTS as u64)
+}
+
+/// A run's totals, with the bankroll followed along stretches of it, the
+/// results of the sessions it cuts into, and its rounds by true count.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct SimulationDetail {
+    pub result: SimulationResult,
+    pub trajectories: Trajectories,
+    pub sessions: Sessions,
+    pub by_count: CountTable,
+}
+
+/// Plays a simulation like `simulate_with_progress`, also cutting each
+/// stream into paths of equal length to follow the bankroll along, and
+/// into sessions of `session_rounds`. Rounds left over at the end of a
+/// stream don't make a path or a session.
+pub fn simulate_in_detail(config: SimulationConfig, session_rounds: u64, progress: &SimulationProgress) -> Result<SimulationDetail, String> {
+    let step = path_step(config.rounds);
+    let path_rounds = step * TRAJECTORY_POINTS as u64;
+    let results: Vec<Result<SimulationDetail, String>> = streams(&config)
+        .into_par_iter()
+        .map(|stream| {
+            let mut simulator = Simulator::new(stream);
+            let mut detail = SimulationDetail {
+                trajectories: Trajectories { path_rounds, paths: vec![] },
+                sessions: Sessions { session_rounds, results: vec![] },
+                ..SimulationDetail::default()
+            };
+            let mut path = vec![0.0];
+            let mut into_step = SimulationResult::default();
+            let mut into_session = SimulationResult::default();
+            let mut unreported = SimulationResult::default();
+            while simulator.remaining() > 0 && !progress.is_cancelled() {
+                let played = simulator.play((step - into_step.rounds).min(session_rounds - into_session.rounds))?;
+                into_step.merge(&played);
+                into_session.merge(&played);
+                if into_step.rounds == step {
+                    path.push(path[path.len() - 1] + std::mem::take(&mut into_step).net);
+                }
+                if path.len() > TRAJECTORY_POINTS {
+                    detail.trajectories.paths.push(std::mem::replace(&mut path, vec![0.0]));
+                }
+                if into_session.rounds == session_rounds {
+                    detail.sessions.results.push(std::mem::take(&mut into_session).net);
+                }
+                unreported.merge(&played);
+                if unreported.rounds >= PROGRESS_ROUNDS {
+                    progress.report(&std::mem::take(&mut unreported));
+                }
+            }
+            progress.report(&unreported);
+            detail.result = simulator.result;
+            detail.by_count = simulator.by_count;
+            Ok(detail)
+        })
+        .collect();
+
+    let mut total = SimulationDetail {
+        trajectories: Trajectories { path_rounds, paths: vec![] },
+        sessions: Sessions { session_rounds, results: vec![] },
+        ..SimulationDetail::default()
+    };
+    for result in results {
+        let stream = result?;
+        total.result.merge(&stream.result);
+        total.trajectories.paths.extend(stream.trajectories.paths);
+        total.sessions.results.extend(stream.sessions.results);
+        total.by_count.merge(&stream.by_count);
+    }
+    Ok(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logic::bet_spread::BET_SPREADS;
+    use crate::logic::counting::HI_LO;
+    use crate::logic::deviations::load_index_sets;
+    use crate::logic::ev::house_edge;
+    use crate::logic::game::card::{Card, Rank, Suit};
+
+    fn config(rounds: u64, seed: u64) -> SimulationConfig {
+        let strategy = BlackjackStrategy::from_file("resources/strategies/default-strategy.json").unwrap();
+        SimulationConfig::for_strategy(strategy, rounds, Some(seed))
+    }
+
+    #[test]
+    fn test_simulated_ev_matches_the_house_edge() {
+        let config = config(200_000, 7);
+        let edge = house_edge(&config.rules, &config.table);
+        let result = simulate(config).unwrap();
+
+        assert_eq!(result.rounds, 200_000);
+        assert_eq!(result.wins + result.pushes + result.losses, result.rounds);
+        assert!((result.win_rate() + result.push_rate() + result.loss_rate() - 1.0).abs() < 1e-9);
+        // Doubles and splits put more than the initial bet on the table
+        assert!(result.wagered > result.rounds as f64);
+        // A round swings a little over one bet either way, which leaves the
+        // EV of 200,000 rounds within about a percent of the worked out edge
+        assert!((1.0..1.3).contains(&result.std_dev()), "{}", result.std_dev());
+        assert!((result.ev() + edge).abs() < 0.015, "simulated {} against an edge of {}", result.ev(), edge);
+
+        // The interval is a couple of tenths of a percent either side, and
+        // 100 rounds swing ten times as far as one
+        let (low, high) = result.ev_interval();
+        assert!(low < result.ev() && result.ev() < high);
+        assert!((high - low - 2.0 * CONFIDENCE_Z * result.std_dev() / 200_000f64.sqrt()).abs() < 1e-12);
+        assert!((0.002..0.003).contains(&result.standard_error()), "{}", result.standard_error());
+        assert!((result.std_dev_per_100() - 10.0 * result.std_dev()).abs() < 1e-12);
+        assert_eq!(SimulationResult::default().ev_interval(), (0.0, 0.0));
+    }
+
+    #[test]
+    fn test_seeded_runs_repeat_and_stop_at_the_total() {
+        assert_eq!(simulate(config(2_000, 3)).unwrap(), simulate(config(2_000, 3)).unwrap());
+
+        // Each stream gets its own seed, and the streams add up to the run
+        let streams = streams(&config(2 * STREAM_ROUNDS + 1, 3));
+        assert_eq!(streams.iter().map(|stream| stream.rounds).collect::<Vec<u64>>(), vec![STREAM_ROUNDS, STREAM_ROUNDS, 1]);
+        assert!(streams[0].seed != streams[1].seed && streams[1].seed != streams[2].seed);
+
+        let mut simulator = Simulator::new(config(1_500, 3));
+        simulator.play(1_000).unwrap();
+        assert_eq!(simulator.remaining(), 500);
+        assert_eq!(simulator.play(1_000).unwrap().rounds, 500);
+        assert_eq!((simulator.result.rounds, simulator.remaining()), (1_500, 0));
+
+        // A cancelled run stops before playing every round
+        let progress = SimulationProgress::default();
+        progress.cancel();
+        assert_eq!(simulate_with_progress(config(STREAM_ROUNDS, 3), &progress).unwrap().rounds, 0);
+    }
+
+    #[test]
+    fn test_counting_play() {
+        let illustrious_18 = load_index_sets("resources/deviations").into_iter().find(|set| set.name == "Illustrious 18").unwrap();
+        let counting = CountingPlay { deviations: Some(illustrious_18), ..CountingPlay::new(&HI_LO, BET_SPREADS[3]) };
+        let strategy = config(1, 1).strategy;
+
+        // 16 against a 10 stands from a true count of zero, and insurance is taken from +3
+        let cards = [Rank::Ten, Rank::Ten, Rank::Six, Rank::Seven].map(|rank| Card::new(rank, Suit::Hearts));
+        let round = Round::deal(&strategy.rules, &TableConfig::default(), &[1.0], &mut Shoe::stacked(cards.to_vec()));
+        assert_eq!(counting.deviation(&strategy, &round, 0), Some(PlayerAction::Stand));
+        assert_eq!(counting.deviation(&strategy, &round, -1), None);
+        assert!(counting.insures(3) && !counting.insures(2));
+        assert!(!CountingPlay::new(&HI_LO, BET_SPREADS[3]).insures(10));
+
+        // A counter raises bets when the count is up, never past the top of the spread
+        let mut counter = config(20_000, 6);
+        counter.counting = Some(counting);
+        let SimulationDetail { result, by_count, .. } = simulate_in_detail(counter, ROUNDS_PER_HOUR, &SimulationProgress::default()).unwrap();
+        assert_eq!(result.rounds, 20_000);
+        assert!((1.2..16.0).contains(&result.average_bet()), "{}", result.average_bet());
+
+        // Every round is kept by the count it was dealt at, one unit bet at zero
+        assert_eq!(by_count.rounds(), result.rounds);
+        assert_eq!(by_count.counts[&0].average_bet(), 1.0);
+        assert!(by_count.counts[&4].average_bet() > 1.0);
+        assert_eq!(simulate(config(2_000, 6)).unwrap().average_bet(), 1.0);
+    }
+
+    #[test]
+    fn test_runs_cut_into_bankroll_paths() {
+        let progress = SimulationProgress::default();
+        let SimulationDetail { result, trajectories, sessions, by_count } = simulate_in_detail(config(20_000, 4), 250, &progress).unwrap();
+
+        // Progress hears about every round, and tracking paths doesn't change the run
+        let so_far = progress.so_far();
+        assert_eq!((so_far.rounds, so_far.wins, so_far.losses), (result.rounds, result.wins, result.losses));
+        assert_eq!(result, simulate(config(20_000, 4)).unwrap());
+        assert!(by_count.counts.is_empty());
+        assert_eq!((trajectories.path_rounds, trajectories.paths.len()), (200, 100));
+        assert!(trajectories.paths.iter().all(|path| path.len() == TRAJECTORY_POINTS + 1 && path[0] == 0.0));
+        let net: f64 = trajectories.paths.iter().map(|path| path[TRAJECTORY_POINTS]).sum();
+        assert!((net - result.net).abs() < 1e-6);
+
+        // Sessions don't have to line up with the points of a path
+        assert_eq!((sessions.session_rounds, sessions.results.len()), (250, 80));
+        let net: f64 = sessions.results.iter().sum();
+        assert!((net - result.net).abs() < 1e-6);
+
+        // Long runs make longer paths rather than more of them, up to a stream each
+        assert_eq!(path_step(1_000_000) * TRAJECTORY_POINTS as u64, 10_000);
+        assert_eq!(path_step(100_000_000) * TRAJECTORY_POINTS as u64, STREAM_ROUNDS);
+    }
+}
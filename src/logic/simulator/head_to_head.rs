@@ -0,0 +1,120 @@
+use crate::logic::simulator::engine::{play_round_off, shoe_for, streams, SimulationConfig, SimulationProgress, SimulationResult, PROGRESS_ROUNDS};
+use crate::logic::strategy_calculator_logic::BlackjackStrategy;
+use rayon::prelude::*;
+
+/// Two charts played round for round against the same cards.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct HeadToHead {
+    pub first: SimulationResult,
+    pub second: SimulationResult,
+
+    /// The second chart's result less the first's each round, won when the
+    /// second did better and pushed when they came out the same
+    pub difference: SimulationResult,
+}
+
+impl HeadToHead {
+    pub fn merge(&mut self, other: &HeadToHead) {
+        self.first.merge(&other.first);
+        self.second.merge(&other.second);
+        self.difference.merge(&other.difference);
+    }
+
+    /// How much more the second chart wins a round than the first, in betting units.
+    pub fn ev_delta(&self) -> f64 {
+        self.difference.ev()
+    }
+
+    /// Range the true difference falls in with 95% confidence, from the
+    /// spread of the paired differences.
+    pub fn delta_interval(&self) -> (f64, f64) {
+        self.difference.ev_interval()
+    }
+
+    /// Standard error the delta would have with each chart dealt its own cards.
+    pub fn unpaired_error(&self) -> f64 {
+        self.first.standard_error().hypot(self.second.standard_error())
+    }
+
+    /// Times as many rounds the charts would need on their own shoes to pin
+    /// the difference down as closely.
+    pub fn variance_reduction(&self) -> f64 {
+        let paired = self.difference.standard_error();
+        if paired == 0.0 {
+            return 0.0;
+        }
+        (self.unpaired_error() / paired).powi(2)
+    }
+}
+
+/// Plays `second` against the first config's chart, both under its rules,
+/// table and counting. Each round is dealt to the two charts from copies
+/// of one shoe, so they see the same cards until their plays part ways,
+/// and the shoe carries on as the first chart left it. Runs are spread
+/// over threads in streams like `simulate`, and a cancelled run keeps the
+/// rounds played.
+pub fn head_to_head(first: SimulationConfig, second: BlackjackStrategy, progress: &SimulationProgress) -> Result<HeadToHead, String> {
+    let results: Vec<Result<HeadToHead, String>> = streams(&first)
+        .into_par_iter()
+        .map(|stream| {
+            let other = SimulationConfig { strategy: second.clone(), ..stream.clone() };
+            let mut shoe = shoe_for(&stream);
+            let mut total = HeadToHead::default();
+            let mut unreported = SimulationResult::default();
+            while total.first.rounds < stream.rounds && !progress.is_cancelled() {
+                if shoe.needs_shuffle() {
+                    shoe.shuffle();
+                }
+                let mut copy = shoe.clone();
+                let played = play_round_off(&stream, &mut shoe)?;
+                let against = play_round_off(&other, &mut copy)?;
+                total.first.record(played.net, played.wagered, played.bet);
+                total.second.record(against.net, against.wagered, against.bet);
+                total.difference.record(against.net - played.net, 0.0, 0.0);
+                unreported.record(played.net, played.wagered, played.bet);
+                if unreported.rounds >= PROGRESS_ROUNDS {
+                    progress.report(&std::mem::take(&mut unreported));
+                }
+            }
+            progress.report(&unreported);
+            Ok(total)
+        })
+        .collect();
+
+    let mut total = HeadToHead::default();
+    for result in results {
+        total.merge(&result?);
+    }
+    Ok(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strategy(name: &str) -> BlackjackStrategy {
+        BlackjackStrategy::from_file(&format!("resources/strategies/{}.json", name)).unwrap()
+    }
+
+    #[test]
+    fn test_charts_play_the_same_cards() {
+        let first = SimulationConfig::for_strategy(strategy("strategy_6D_S17_DAS_NS_P"), 20_000, Some(8));
+
+        // A chart against itself never differs
+        let same = head_to_head(first.clone(), strategy("strategy_6D_S17_DAS_NS_P"), &SimulationProgress::default()).unwrap();
+        assert_eq!(same.first, same.second);
+        assert_eq!((same.difference.rounds, same.difference.pushes, same.ev_delta()), (20_000, 20_000, 0.0));
+        assert_eq!(same.variance_reduction(), 0.0);
+
+        // A chart for other rules plays most hands the same way, so pairing
+        // the rounds narrows the difference far more than playing them apart
+        let progress = SimulationProgress::default();
+        let other = head_to_head(first, strategy("strategy_1D_H17_NDAS_AUC_NP"), &progress).unwrap();
+        assert_eq!(progress.so_far().rounds, 20_000);
+        assert!(other.difference.pushes > 15_000, "{}", other.difference.pushes);
+        assert!((other.ev_delta() - (other.second.ev() - other.first.ev())).abs() < 1e-9);
+        assert!(other.variance_reduction() > 10.0, "{}", other.variance_reduction());
+        let (low, high) = other.delta_interval();
+        assert!(low < other.ev_delta() && other.ev_delta() < high);
+    }
+}
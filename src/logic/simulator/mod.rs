@@ -0,0 +1,11 @@
+pub mod engine;
+pub mod risk_of_ruin;
+pub mod trajectory;
+pub mod sessions;
+pub mod penetration;
+pub mod batch;
+pub mod count_table;
+pub mod report;
+pub mod presets;
+pub mod head_to_head;
+pub mod optimal_ramp;
\ No newline at end of file
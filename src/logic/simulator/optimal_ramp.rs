@@ -0,0 +1,236 @@
+use crate::logic::bet_spread::{BetSpread, CustomRamp, RAMP_STEPS};
+use crate::logic::simulator::count_table::CountTable;
+use crate::logic::simulator::engine::{simulate_in_detail, SimulationConfig, SimulationProgress, SimulationResult};
+use crate::logic::simulator::risk_of_ruin::risk_of_ruin;
+use crate::logic::simulator::sessions::ROUNDS_PER_HOUR;
+use std::fmt;
+
+/// Chances of going broke a ramp may carry, for picking how hard to bet
+pub const RUIN_TOLERANCES: [f64; 5] = [0.01, 0.02, 0.05, 0.1, 0.135];
+
+/// Most units a ramp may bet, for picking the table's spread
+pub const RAMP_MAX_UNITS: [u32; 5] = [4, 8, 12, 16, 24];
+
+/// Bankroll a ramp's SCORE is worked out for, in dollars
+const SCORE_BANKROLL: f64 = 10_000.0;
+
+/// What a ramp is picked to do best.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RampGoal {
+    /// Win the most for the risk taken, however big the bankroll
+    Score,
+
+    /// Grow the bankroll fastest
+    Growth,
+}
+
+impl fmt::Display for RampGoal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RampGoal::Score => write!(f, "SCORE"),
+            RampGoal::Growth => write!(f, "Growth"),
+        }
+    }
+}
+
+/// What to find a ramp for: a counting game played with a bankroll, and
+/// the most it may risk and bet.
+#[derive(Debug, Clone)]
+pub struct RampSearch {
+    /// The game the ramp is for, which must count cards. Its bet spread is
+    /// ignored, the game is played flat to see how each count does.
+    pub simulation: SimulationConfig,
+
+    /// Betting units in the bankroll
+    pub bankroll: f64,
+
+    /// Chance of going broke the ramp may carry
+    pub max_ruin: f64,
+    pub max_units: u32,
+    pub goal: RampGoal,
+}
+
+/// A bet ramp and how it plays.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FoundRamp {
+    pub ramp: CustomRamp,
+
+    /// Rounds played flat at each step of the ramp
+    pub steps: Vec<SimulationResult>,
+
+    /// Share of rounds dealt at each step
+    pub frequencies: Vec<f64>,
+    pub stats: RampStats,
+}
+
+/// How a ramp does a round, in betting units.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct RampStats {
+    pub ev: f64,
+    pub std_dev: f64,
+    pub average_bet: f64,
+
+    /// What a $10,000 bankroll bet at full Kelly wins in 100 rounds
+    pub score: f64,
+
+    /// How much the bankroll grows in 100 rounds, as a share of it
+    pub growth: f64,
+    pub ruin: f64,
+}
+
+impl RampStats {
+    /// Result per bet, e.g. 0.01 for a 1% edge.
+    pub fn advantage(&self) -> f64 {
+        if self.average_bet == 0.0 {
+            return 0.0;
+        }
+        self.ev / self.average_bet
+    }
+}
+
+/// Folds rounds by true count into the steps of a ramp.
+pub fn ramp_steps(table: &CountTable) -> Vec<SimulationResult> {
+    let mut steps = vec![SimulationResult::default(); RAMP_STEPS];
+    for (true_count, result) in &table.counts {
+        steps[CustomRamp::step(*true_count as f64)].merge(result);
+    }
+    steps
+}
+
+/// How betting `units` at each step does, from rounds played flat there.
+pub fn ramp_stats(units: &[u32; RAMP_STEPS], steps: &[SimulationResult], bankroll: f64) -> RampStats {
+    let rounds: u64 = steps.iter().map(|step| step.rounds).sum();
+    if rounds == 0 {
+        return RampStats::default();
+    }
+    let mut stats = RampStats::default();
+    let mut second_moment = 0.0;
+    for (bet, step) in units.iter().map(|units| *units as f64).zip(steps) {
+        let frequency = step.rounds as f64 / rounds as f64;
+        let squared = if step.rounds == 0 { 0.0 } else { step.net_squared / step.rounds as f64 };
+        stats.ev += frequency * bet * step.ev();
+        stats.average_bet += frequency * bet;
+        second_moment += frequency * bet * bet * squared;
+        stats.growth += frequency * (bet * step.ev() / bankroll - bet * bet * squared / (2.0 * bankroll * bankroll));
+    }
+    let variance = (second_moment - stats.ev * stats.ev).max(0.0);
+    stats.std_dev = variance.sqrt();
+    stats.growth *= ROUNDS_PER_HOUR as f64;
+    if stats.ev > 0.0 && variance > 0.0 {
+        stats.score = SCORE_BANKROLL * ROUNDS_PER_HOUR as f64 * stats.ev * stats.ev / variance;
+    }
+    stats.ruin = risk_of_ruin(stats.ev, stats.std_dev, bankroll);
+    stats
+}
+
+/// The ramp betting `scale` times each step's edge over its variance, the
+/// Kelly bet, rounded to whole units between one and `max_units` and never
+/// dropping as the count climbs.
+fn scaled_ramp(scale: f64, kelly: &[f64], max_units: u32) -> [u32; RAMP_STEPS] {
+    let mut units = [1; RAMP_STEPS];
+    let mut floor = 1;
+    for (step, kelly) in kelly.iter().enumerate() {
+        floor = ((scale * kelly).round().clamp(1.0, max_units as f64) as u32).max(floor);
+        units[step] = floor;
+    }
+    units
+}
+
+/// Picks the ramp that does best at `goal` without carrying more than
+/// `max_ruin`, from rounds played flat at each step. Bets are kept in
+/// proportion to each step's Kelly bet, which SCORE and growth both favour,
+/// and every scale that changes a bet is tried. None when no ramp has an
+/// edge with little enough risk.
+pub fn best_ramp(steps: &[SimulationResult], bankroll: f64, max_ruin: f64, max_units: u32, goal: RampGoal) -> Option<[u32; RAMP_STEPS]> {
+    let kelly: Vec<f64> = steps.iter()
+        .map(|step| {
+            let squared = if step.rounds == 0 { 0.0 } else { step.net_squared / step.rounds as f64 };
+            if step.ev() > 0.0 && squared > 0.0 { step.ev() / squared } else { 0.0 }
+        })
+        .collect();
+
+    // Each step's bet goes up a unit where the scale passes a half way point
+    let mut scales = vec![0.0];
+    for kelly in kelly.iter().filter(|kelly| **kelly > 0.0) {
+        scales.extend((1..max_units).map(|units| (units as f64 + 0.5) / kelly * (1.0 + 1e-9)));
+    }
+
+    let mut best: Option<([u32; RAMP_STEPS], f64)> = None;
+    for scale in scales {
+        let units = scaled_ramp(scale, &kelly, max_units);
+        let stats = ramp_stats(&units, steps, bankroll);
+        if stats.ev <= 0.0 || stats.ruin > max_ruin {
+            continue;
+        }
+        let value = match goal {
+            RampGoal::Score => stats.score,
+            RampGoal::Growth => stats.growth,
+        };
+        if best.is_none_or(|(_, best)| value > best) {
+            best = Some((units, value));
+        }
+    }
+    best.map(|(units, _)| units)
+}
+
+/// Plays the search's game flat while counting to see how each true count
+/// does, then finds the best ramp for it.
+pub fn find_ramp(search: &RampSearch, progress: &SimulationProgress) -> Result<FoundRamp, String> {
+    let mut simulation = search.simulation.clone();
+    let Some(counting) = simulation.counting.as_mut() else {
+        return Err("Finding a ramp needs a counting system".to_string());
+    };
+    counting.spread = BetSpread { max_units: 1, units_per_count: 1 };
+    let system = counting.system.name().to_string();
+    let played = simulate_in_detail(simulation, ROUNDS_PER_HOUR, progress)?;
+
+    let steps = ramp_steps(&played.by_count);
+    let units = best_ramp(&steps, search.bankroll, search.max_ruin, search.max_units, search.goal).ok_or_else(|| format!(
+        "No ramp up to 1-{} wins with under a {:.1}% risk of ruin on {:.0} units, try a bigger bankroll, more risk or a higher max bet",
+        search.max_units,
+        search.max_ruin * 100.0,
+        search.bankroll,
+    ))?;
+    let frequencies = steps.iter().map(|step| step.rounds as f64 / played.result.rounds.max(1) as f64).collect();
+    Ok(FoundRamp {
+        stats: ramp_stats(&units, &steps, search.bankroll),
+        ramp: CustomRamp { system, units },
+        steps,
+        frequencies,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Rounds at a step, losing `edge` of them net and winning the rest.
+    fn step(rounds: u64, edge: f64) -> SimulationResult {
+        SimulationResult { rounds, net: edge * rounds as f64, net_squared: 1.3 * rounds as f64, bet: rounds as f64, ..SimulationResult::default() }
+    }
+
+    #[test]
+    fn test_ramp_bets_up_with_the_edge_within_the_risk() {
+        let edges = [-0.01, -0.002, 0.003, 0.008, 0.013, 0.018, 0.023, 0.028, 0.035];
+        let rounds = [600_000, 150_000, 90_000, 60_000, 40_000, 25_000, 15_000, 10_000, 10_000];
+        let steps: Vec<SimulationResult> = rounds.iter().zip(edges).map(|(rounds, edge)| step(*rounds, edge)).collect();
+
+        let growth = best_ramp(&steps, 2000.0, 0.135, 16, RampGoal::Growth).unwrap();
+        assert_eq!(growth[..2], [1, 1]);
+        assert!(growth.windows(2).all(|pair| pair[0] <= pair[1]), "{:?}", growth);
+        let stats = ramp_stats(&growth, &steps, 2000.0);
+        assert!(stats.ev > 0.0 && stats.ruin <= 0.135, "{:?}", stats);
+
+        // Less risk bets less, and SCORE spreads at least as wide as growth
+        let careful = best_ramp(&steps, 2000.0, 0.02, 16, RampGoal::Growth).unwrap();
+        assert!(careful.iter().zip(growth).all(|(careful, growth)| *careful <= growth));
+        assert!(ramp_stats(&careful, &steps, 2000.0).ruin <= 0.02);
+        let score = best_ramp(&steps, 2000.0, 0.135, 16, RampGoal::Score).unwrap();
+        assert!(ramp_stats(&score, &steps, 2000.0).score >= stats.score);
+
+        // Flat betting has no edge here, and too small a bankroll can't afford a spread
+        assert_eq!(best_ramp(&steps, 2000.0, 0.135, 1, RampGoal::Growth), None);
+        assert_eq!(best_ramp(&steps, 100.0, 0.01, 16, RampGoal::Score), None);
+        assert_eq!(ramp_stats(&[1; RAMP_STEPS], &[], 100.0), RampStats::default());
+    }
+}
@@ -0,0 +1,52 @@
+use crate::logic::simulator::engine::{simulate_with_progress, SimulationConfig, SimulationProgress, SimulationResult};
+
+/// Penetrations a sweep plays, from half the shoe to nine tenths
+pub const SWEEP_PENETRATIONS: [f64; 9] = [0.5, 0.55, 0.6, 0.65, 0.7, 0.75, 0.8, 0.85, 0.9];
+
+/// How the game went dealt to one penetration.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SweepPoint {
+    pub penetration: f64,
+    pub result: SimulationResult,
+}
+
+/// Plays the same game at each of `SWEEP_PENETRATIONS`, every point its
+/// configured rounds off the same seed. A cancelled sweep keeps the points
+/// it finished.
+pub fn sweep_penetration(config: &SimulationConfig, progress: &SimulationProgress) -> Result<Vec<SweepPoint>, String> {
+    let mut points = vec![];
+    for penetration in SWEEP_PENETRATIONS {
+        let mut point = config.clone();
+        point.table.penetration = penetration;
+        let result = simulate_with_progress(point, progress)?;
+        if result.rounds < config.rounds {
+            break;
+        }
+        points.push(SweepPoint { penetration, result });
+    }
+    Ok(points)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logic::strategy_calculator_logic::BlackjackStrategy;
+
+    #[test]
+    fn test_sweep_plays_every_penetration() {
+        let strategy = BlackjackStrategy::from_file("resources/strategies/default-strategy.json").unwrap();
+        let config = SimulationConfig::for_strategy(strategy, 1_000, Some(2));
+        let progress = SimulationProgress::default();
+        let points = sweep_penetration(&config, &progress).unwrap();
+
+        assert_eq!(points.iter().map(|point| point.penetration).collect::<Vec<f64>>(), SWEEP_PENETRATIONS.to_vec());
+        assert!(points.iter().all(|point| point.result.rounds == 1_000));
+        assert_eq!(progress.so_far().rounds, 9_000);
+        // Dealing deeper changes where the shuffles fall, and so the cards
+        assert!(points[0].result != points[8].result);
+
+        let cancelled = SimulationProgress::default();
+        cancelled.cancel();
+        assert!(sweep_penetration(&config, &cancelled).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,119 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// Longest name a preset can be saved under
+pub const MAX_PRESET_NAME_LENGTH: usize = 32;
+
+/// Settings of a simulation saved under a name to run again later. The
+/// strategy brings its rules, and everything is kept by name or value
+/// rather than by position so presets survive charts being added.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct SimulationPreset {
+    pub name: String,
+
+    /// Name of the chart played
+    pub strategy: String,
+
+    /// Name of the chart played head to head against it
+    #[serde(default)]
+    pub versus: Option<String>,
+
+    pub rounds: u64,
+    pub session_hours: u64,
+    pub seed: Option<u64>,
+
+    /// Name of the counting system, betting flat when none
+    pub counting: Option<String>,
+
+    /// Top of the bet spread in betting units, e.g. 8 for 1-8
+    pub spread: u32,
+
+    /// Name of the index set played, basic strategy when none
+    pub deviations: Option<String>,
+}
+
+/// Every saved preset, kept in order of name.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+pub struct SimulationPresets {
+    presets: Vec<SimulationPreset>,
+}
+
+impl SimulationPresets {
+    /// Loads saved presets, starting with none if there are none or they cannot be read.
+    pub fn load(path: &str) -> Self {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(parent) = Path::new(path).parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    pub fn presets(&self) -> &[SimulationPreset] {
+        &self.presets
+    }
+
+    pub fn position(&self, name: &str) -> Option<usize> {
+        self.presets.iter().position(|preset| preset.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Adds the preset, replacing one with the same name whatever its case,
+    /// and returns where it went.
+    pub fn insert(&mut self, preset: SimulationPreset) -> usize {
+        if let Some(position) = self.position(&preset.name) {
+            self.presets.remove(position);
+        }
+        let position = self.presets.partition_point(|saved| saved.name.to_lowercase() < preset.name.to_lowercase());
+        self.presets.insert(position, preset);
+        position
+    }
+
+    pub fn remove(&mut self, position: usize) -> Option<SimulationPreset> {
+        (position < self.presets.len()).then(|| self.presets.remove(position))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn preset(name: &str, rounds: u64) -> SimulationPreset {
+        SimulationPreset {
+            name: name.to_string(),
+            strategy: "default-strategy".to_string(),
+            versus: None,
+            rounds,
+            session_hours: 4,
+            seed: Some(7),
+            counting: Some("Hi-Lo".to_string()),
+            spread: 8,
+            deviations: Some("Illustrious 18".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_presets_stay_in_order_and_replace_by_name() {
+        let mut presets = SimulationPresets::default();
+        assert_eq!(presets.insert(preset("Six deck", 1_000_000)), 0);
+        assert_eq!(presets.insert(preset("double deck", 100_000)), 0);
+        assert_eq!(presets.insert(preset("Shoe game", 10_000)), 1);
+        assert_eq!(presets.insert(preset("SIX DECK", 10_000_000)), 2);
+
+        assert_eq!(presets.presets().iter().map(|preset| preset.name.as_str()).collect::<Vec<&str>>(), vec!["double deck", "Shoe game", "SIX DECK"]);
+        assert_eq!(presets.presets()[2].rounds, 10_000_000);
+        assert_eq!(presets.position("shoe GAME"), Some(1));
+        assert_eq!(presets.remove(0).map(|preset| preset.name), Some("double deck".to_string()));
+        assert_eq!(presets.remove(5), None);
+
+        let json = serde_json::to_string(&presets).unwrap();
+        assert_eq!(serde_json::from_str::<SimulationPresets>(&json).unwrap(), presets);
+        assert_eq!(SimulationPresets::load("data/no_such_presets.json"), SimulationPresets::default());
+    }
+}
@@ -0,0 +1,278 @@
+use crate::logic::export::{csv_field, file_stem};
+use crate::logic::simulator::engine::{SimulationConfig, SimulationDetail, SimulationResult};
+use crate::logic::simulator::sessions::ROUNDS_PER_HOUR;
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Bins the session results are counted into
+pub const REPORT_HISTOGRAM_BINS: usize = 20;
+
+/// Percentiles of the bankroll followed along each point of the paths
+pub const REPORT_PERCENTILES: [f64; 5] = [5.0, 25.0, 50.0, 75.0, 95.0];
+
+/// A finished run written out in full for looking at in other tools.
+#[derive(Debug, Clone, Serialize)]
+pub struct SimulationReport {
+    pub name: String,
+    pub rules: String,
+    pub penetration: f64,
+    pub seed: Option<u64>,
+
+    /// Counting system, spread and index set, none when betting flat
+    pub counting: Option<String>,
+    pub summary: Summary,
+
+    /// The chart played flat, when the run counted cards
+    pub baseline: Option<Summary>,
+    pub sessions: SessionReport,
+
+    /// The bankroll at each point along the paths, at each of `REPORT_PERCENTILES`
+    pub bankroll: Vec<BankrollPoint>,
+
+    /// How rounds went at each true count, empty when not counting
+    pub by_count: Vec<CountRow>,
+}
+
+/// A run's headline numbers, in betting units and shares of one.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Summary {
+    pub rounds: u64,
+    pub ev: f64,
+    pub ev_low: f64,
+    pub ev_high: f64,
+    pub std_dev: f64,
+    pub std_dev_per_100: f64,
+    pub win_rate: f64,
+    pub push_rate: f64,
+    pub loss_rate: f64,
+    pub average_bet: f64,
+    pub advantage: f64,
+    pub per_hour: f64,
+    pub net: f64,
+    pub wagered: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionReport {
+    pub hours: f64,
+    pub sessions: usize,
+    pub ahead: f64,
+    pub mean: f64,
+    pub histogram: Vec<HistogramBin>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HistogramBin {
+    pub low: f64,
+    pub high: f64,
+    pub sessions: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BankrollPoint {
+    pub rounds: u64,
+
+    /// Net result at each of `REPORT_PERCENTILES`, in order
+    pub percentiles: Vec<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CountRow {
+    pub true_count: i32,
+    pub rounds: u64,
+    pub frequency: f64,
+    pub ev: f64,
+    pub average_bet: f64,
+    pub advantage: f64,
+}
+
+impl Summary {
+    pub fn new(result: &SimulationResult) -> Self {
+        let (ev_low, ev_high) = result.ev_interval();
+        Self {
+            rounds: result.rounds,
+            ev: result.ev(),
+            ev_low,
+            ev_high,
+            std_dev: result.std_dev(),
+            std_dev_per_100: result.std_dev_per_100(),
+            win_rate: result.win_rate(),
+            push_rate: result.push_rate(),
+            loss_rate: result.loss_rate(),
+            average_bet: result.average_bet(),
+            advantage: result.advantage(),
+            per_hour: result.per_hour(),
+            net: result.net,
+            wagered: result.wagered,
+        }
+    }
+
+    /// Each figure by name, in the order they're written out.
+    fn fields(&self) -> [(&'static str, f64); 14] {
+        [
+            ("rounds", self.rounds as f64),
+            ("ev", self.ev),
+            ("ev_low", self.ev_low),
+            ("ev_high", self.ev_high),
+            ("std_dev", self.std_dev),
+            ("std_dev_per_100", self.std_dev_per_100),
+            ("win_rate", self.win_rate),
+            ("push_rate", self.push_rate),
+            ("loss_rate", self.loss_rate),
+            ("average_bet", self.average_bet),
+            ("advantage", self.advantage),
+            ("per_hour", self.per_hour),
+            ("net", self.net),
+            ("wagered", self.wagered),
+        ]
+    }
+}
+
+impl SimulationReport {
+    pub fn new(name: &str, config: &SimulationConfig, detail: &SimulationDetail, baseline: Option<&SimulationResult>) -> Self {
+        let counting = config.counting.as_ref().map(|counting| match &counting.deviations {
+            Some(deviations) => format!("{} {} with the {}", counting.system.name(), counting.spread, deviations.name),
+            None => format!("{} {}", counting.system.name(), counting.spread),
+        });
+        let sessions = &detail.sessions;
+        let trajectories = &detail.trajectories;
+        let percentiles: Vec<Vec<f64>> = REPORT_PERCENTILES.iter().map(|percentile| trajectories.percentile(*percentile)).collect();
+        let bankroll = if trajectories.paths.is_empty() {
+            vec![]
+        } else {
+            trajectories.rounds_at().into_iter().enumerate()
+                .map(|(point, rounds)| BankrollPoint { rounds, percentiles: percentiles.iter().map(|percentile| percentile[point]).collect() })
+                .collect()
+        };
+        let by_count = detail.by_count.counts.iter()
+            .map(|(true_count, result)| CountRow {
+                true_count: *true_count,
+                rounds: result.rounds,
+                frequency: detail.by_count.frequency(*true_count),
+                ev: result.ev(),
+                average_bet: result.average_bet(),
+                advantage: result.advantage(),
+            })
+            .collect();
+        Self {
+            name: name.to_string(),
+            rules: config.rules.to_string(),
+            penetration: config.table.penetration,
+            seed: config.seed,
+            counting,
+            summary: Summary::new(&detail.result),
+            baseline: baseline.map(Summary::new),
+            sessions: SessionReport {
+                hours: sessions.session_rounds as f64 / ROUNDS_PER_HOUR as f64,
+                sessions: sessions.results.len(),
+                ahead: sessions.ahead(),
+                mean: sessions.mean(),
+                histogram: sessions.histogram(REPORT_HISTOGRAM_BINS).into_iter().map(|bin| HistogramBin { low: bin.low, high: bin.high, sessions: bin.count }).collect(),
+            },
+            bankroll,
+            by_count,
+        }
+    }
+
+    pub fn to_json(&self) -> Result<String, String> {
+        serde_json::to_string_pretty(self).map_err(|err| format!("Couldn't write the report: {}", err))
+    }
+
+    /// The report as CSV tables: the summary with the flat baseline beside
+    /// it, the session histogram, the bankroll percentiles and the rounds by
+    /// true count.
+    pub fn to_csv(&self) -> Vec<(&'static str, String)> {
+        let mut summary = "statistic,value,flat_baseline\n".to_string();
+        for (field, value) in [("name", &self.name), ("rules", &self.rules), ("counting", &self.counting.clone().unwrap_or_default())] {
+            summary.push_str(&format!("{},{},\n", field, csv_field(value)));
+        }
+        summary.push_str(&format!("penetration,{},\n", self.penetration));
+        summary.push_str(&format!("seed,{},\n", self.seed.map(|seed| seed.to_string()).unwrap_or_default()));
+        let baseline = self.baseline.as_ref().map(Summary::fields);
+        for (index, (field, value)) in self.summary.fields().into_iter().enumerate() {
+            let flat = baseline.map(|baseline| baseline[index].1.to_string()).unwrap_or_default();
+            summary.push_str(&format!("{},{},{}\n", field, value, flat));
+        }
+
+        let mut histogram = "low,high,sessions\n".to_string();
+        for bin in &self.sessions.histogram {
+            histogram.push_str(&format!("{},{},{}\n", bin.low, bin.high, bin.sessions));
+        }
+
+        let mut bankroll = std::iter::once("rounds".to_string())
+            .chain(REPORT_PERCENTILES.iter().map(|percentile| format!("p{}", percentile)))
+            .collect::<Vec<String>>()
+            .join(",");
+        bankroll.push('\n');
+        for point in &self.bankroll {
+            let fields = std::iter::once(point.rounds.to_string()).chain(point.percentiles.iter().map(f64::to_string)).collect::<Vec<String>>();
+            bankroll.push_str(&fields.join(","));
+            bankroll.push('\n');
+        }
+
+        let mut by_count = "true_count,rounds,frequency,ev,average_bet,advantage\n".to_string();
+        for row in &self.by_count {
+            by_count.push_str(&format!("{},{},{},{},{},{}\n", row.true_count, row.rounds, row.frequency, row.ev, row.average_bet, row.advantage));
+        }
+
+        vec![("summary", summary), ("histogram", histogram), ("bankroll", bankroll), ("counts", by_count)]
+    }
+}
+
+/// Writes the report to `<name>_simulation.json` in `dir`.
+pub fn export_report_json(report: &SimulationReport, dir: &Path) -> Result<PathBuf, String> {
+    fs::create_dir_all(dir).map_err(|err| format!("Couldn't create {}: {}", dir.display(), err))?;
+    let path = dir.join(format!("{}_simulation.json", file_stem(&report.name)));
+    fs::write(&path, report.to_json()?).map_err(|err| format!("Couldn't write {}: {}", path.display(), err))?;
+    Ok(path)
+}
+
+/// Writes each of the report's tables to `<name>_simulation_<table>.csv` in
+/// `dir`, returning the files written.
+pub fn export_report_csv(report: &SimulationReport, dir: &Path) -> Result<Vec<PathBuf>, String> {
+    fs::create_dir_all(dir).map_err(|err| format!("Couldn't create {}: {}", dir.display(), err))?;
+    report.to_csv().into_iter().map(|(table, csv)| {
+        let path = dir.join(format!("{}_simulation_{}.csv", file_stem(&report.name), table));
+        fs::write(&path, csv).map_err(|err| format!("Couldn't write {}: {}", path.display(), err))?;
+        Ok(path)
+    }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logic::bet_spread::BET_SPREADS;
+    use crate::logic::counting::HI_LO;
+    use crate::logic::simulator::engine::{simulate_in_detail, CountingPlay, SimulationProgress};
+    use crate::logic::strategy_calculator_logic::BlackjackStrategy;
+
+    #[test]
+    fn test_report_writes_every_table() {
+        let strategy = BlackjackStrategy::from_file("resources/strategies/default-strategy.json").unwrap();
+        let mut config = SimulationConfig::for_strategy(strategy, 10_000, Some(5));
+        config.counting = Some(CountingPlay::new(&HI_LO, BET_SPREADS[1]));
+        let detail = simulate_in_detail(config.clone(), 200, &SimulationProgress::default()).unwrap();
+        let baseline = SimulationResult { rounds: 10, net: -1.0, bet: 10.0, ..SimulationResult::default() };
+        let report = SimulationReport::new("default, Hi-Lo", &config, &detail, Some(&baseline));
+
+        assert_eq!(report.counting.as_deref(), Some("Hi-Lo 1-8"));
+        assert_eq!(report.summary, Summary::new(&detail.result));
+        assert_eq!(report.sessions.histogram.iter().map(|bin| bin.sessions).sum::<usize>(), 50);
+        assert_eq!(report.bankroll.len(), detail.trajectories.rounds_at().len());
+        assert_eq!(report.by_count.iter().map(|row| row.rounds).sum::<u64>(), 10_000);
+        assert!((report.by_count.iter().map(|row| row.frequency).sum::<f64>() - 1.0).abs() < 1e-9);
+
+        let json: serde_json::Value = serde_json::from_str(&report.to_json().unwrap()).unwrap();
+        assert_eq!(json["summary"]["rounds"], 10_000);
+        assert_eq!(json["baseline"]["advantage"], -0.1);
+
+        let csv = report.to_csv();
+        assert_eq!(csv.iter().map(|(table, _)| *table).collect::<Vec<&str>>(), vec!["summary", "histogram", "bankroll", "counts"]);
+        assert!(csv[0].1.contains("name,\"default, Hi-Lo\",\n"));
+        assert!(csv[0].1.contains("advantage,") && csv[0].1.contains(",-0.1\n"));
+        assert_eq!(csv[1].1.lines().count(), REPORT_HISTOGRAM_BINS + 1);
+        assert_eq!(csv[2].1.lines().next(), Some("rounds,p5,p25,p50,p75,p95"));
+        assert_eq!(csv[3].1.lines().count(), report.by_count.len() + 1);
+    }
+}
@@ -0,0 +1,179 @@
+use crate::logic::simulator::engine::{stream_seeds, SimulationConfig, SimulationProgress, SimulationResult, Simulator};
+use rayon::prelude::*;
+
+/// Rounds a trial plays between reporting progress and checking whether
+/// the run has been cancelled
+const PROGRESS_ROUNDS: u64 = 1_000;
+
+/// What to work out the risk of ruin for: a game and bet spread played
+/// with a bankroll for a stretch of rounds, many times over.
+#[derive(Debug, Clone)]
+pub struct RuinConfig {
+    /// The game each trial plays, its rounds the length of a trial
+    pub simulation: SimulationConfig,
+
+    /// Betting units each trial starts with
+    pub bankroll: f64,
+    pub trials: u32,
+}
+
+/// How the trials went, alongside what the formulas make of the same game.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct RuinResult {
+    pub bankroll: f64,
+
+    /// Rounds in each trial
+    pub rounds: u64,
+
+    /// Trials played to the end, and those that lost the whole bankroll
+    pub trials: u32,
+    pub ruined: u32,
+
+    /// Every round of every trial, for the EV and spread the formulas use
+    pub played: SimulationResult,
+}
+
+impl RuinResult {
+    /// Share of the trials that went broke.
+    pub fn simulated(&self) -> f64 {
+        if self.trials == 0 {
+            return 0.0;
+        }
+        self.ruined as f64 / self.trials as f64
+    }
+
+    /// Risk of ruin playing forever at the EV and spread the trials found.
+    pub fn formula(&self) -> f64 {
+        risk_of_ruin(self.played.ev(), self.played.std_dev(), self.bankroll)
+    }
+
+    /// Risk of ruin within a trial's rounds at the EV and spread the trials found.
+    pub fn formula_within(&self) -> f64 {
+        risk_of_ruin_within(self.played.ev(), self.played.std_dev(), self.bankroll, self.rounds)
+    }
+}
+
+/// Chance of losing `bankroll` units playing forever with a round's EV and
+/// standard deviation in units. A game without an edge goes broke in the end.
+pub fn risk_of_ruin(ev: f64, std_dev: f64, bankroll: f64) -> f64 {
+    if ev <= 0.0 {
+        return 1.0;
+    }
+    if std_dev <= 0.0 {
+        return 0.0;
+    }
+    (-2.0 * ev * bankroll / (std_dev * std_dev)).exp().min(1.0)
+}
+
+/// Chance of losing `bankroll` units some time in the next `rounds` rounds,
+/// treating the bankroll as drifting by the EV with the spread of a round.
+pub fn risk_of_ruin_within(ev: f64, std_dev: f64, bankroll: f64, rounds: u64) -> f64 {
+    if rounds == 0 || bankroll <= 0.0 {
+        return if bankroll <= 0.0 { 1.0 } else { 0.0 };
+    }
+    if std_dev <= 0.0 {
+        return if ev * rounds as f64 <= -bankroll { 1.0 } else { 0.0 };
+    }
+    let rounds = rounds as f64;
+    let spread = std_dev * rounds.sqrt();
+    let drift = ev * rounds;
+    let broke_at_the_end = normal_cdf((-bankroll - drift) / spread);
+    let reflection = (-2.0 * ev * bankroll / (std_dev * std_dev)).exp() * normal_cdf((-bankroll + drift) / spread);
+    let risk = broke_at_the_end + reflection;
+    if risk.is_finite() { risk.clamp(0.0, 1.0) } else { 1.0 }
+}
+
+/// Standard normal distribution function, from the Abramowitz and Stegun
+/// approximation of the error function.
+fn normal_cdf(x: f64) -> f64 {
+    let z = x.abs() / std::f64::consts::SQRT_2;
+    let t = 1.0 / (1.0 + 0.3275911 * z);
+    let poly = t * (0.254829592 + t * (-0.284496736 + t * (1.421413741 + t * (-1.453152027 + t * 1.061405429))));
+    let erf = 1.0 - poly * (-z * z).exp();
+    if x >= 0.0 { 0.5 * (1.0 + erf) } else { 0.5 * (1.0 - erf) }
+}
+
+/// Plays a trial, returning how its rounds went and whether the bankroll
+/// ran out, or none when the run was cancelled first. A ruined trial plays
+/// on so every trial adds the same rounds to the EV and spread.
+fn play_trial(config: SimulationConfig, bankroll: f64, progress: &SimulationProgress) -> Result<Option<(SimulationResult, bool)>, String> {
+    let mut simulator = Simulator::new(config);
+    let mut balance = bankroll;
+    let mut ruined = false;
+    while simulator.remaining() > 0 {
+        if progress.is_cancelled() {
+            return Ok(None);
+        }
+        let mut chunk = SimulationResult::default();
+        for _ in 0..PROGRESS_ROUNDS.min(simulator.remaining()) {
+            let round = simulator.play(1)?;
+            balance += round.net;
+            ruined |= balance <= 0.0;
+            chunk.merge(&round);
+        }
+        progress.report(&chunk);
+    }
+    Ok(Some((simulator.result().clone(), ruined)))
+}
+
+/// Plays every trial, spread over every core, and counts how many went
+/// broke. A cancelled run counts only the trials it finished.
+pub fn simulate_ruin(config: &RuinConfig, progress: &SimulationProgress) -> Result<RuinResult, String> {
+    let trials: Vec<Result<Option<(SimulationResult, bool)>, String>> = stream_seeds(config.simulation.seed, config.trials as usize)
+        .into_par_iter()
+        .map(|seed| play_trial(SimulationConfig { seed, ..config.simulation.clone() }, config.bankroll, progress))
+        .collect();
+
+    let mut result = RuinResult { bankroll: config.bankroll, rounds: config.simulation.rounds, ..RuinResult::default() };
+    for trial in trials {
+        if let Some((played, ruined)) = trial? {
+            result.trials += 1;
+            result.ruined += ruined as u32;
+            result.played.merge(&played);
+        }
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logic::strategy_calculator_logic::BlackjackStrategy;
+
+    #[test]
+    fn test_formulas() {
+        assert_eq!(risk_of_ruin(-0.005, 1.15, 100.0), 1.0);
+        assert_eq!(risk_of_ruin(0.0, 1.15, 100.0), 1.0);
+        // 1% edge, 1.15 units of spread and 100 units: e^(-2 * 0.01 * 100 / 1.3225)
+        assert!((risk_of_ruin(0.01, 1.15, 100.0) - 0.2203).abs() < 1e-3);
+        assert!(risk_of_ruin(0.01, 1.15, 200.0) < risk_of_ruin(0.01, 1.15, 100.0));
+
+        // Going broke within a stretch grows with its length towards playing forever
+        let short = risk_of_ruin_within(0.01, 1.15, 100.0, 1_000);
+        let long = risk_of_ruin_within(0.01, 1.15, 100.0, 100_000);
+        assert!(short < long && long <= risk_of_ruin(0.01, 1.15, 100.0) + 1e-6, "{} {}", short, long);
+        assert!((long - risk_of_ruin(0.01, 1.15, 100.0)).abs() < 0.01);
+        // A losing game doesn't always go broke in a short stretch
+        assert!(risk_of_ruin_within(-0.005, 1.15, 100.0, 1_000) < 0.01);
+        assert!((normal_cdf(0.0) - 0.5).abs() < 1e-7 && (normal_cdf(1.96) - 0.975).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_simulated_ruin() {
+        let strategy = BlackjackStrategy::from_file("resources/strategies/default-strategy.json").unwrap();
+        let simulation = SimulationConfig::for_strategy(strategy, 2_000, Some(5));
+        let config = RuinConfig { simulation, bankroll: 20.0, trials: 40 };
+        let result = simulate_ruin(&config, &SimulationProgress::default()).unwrap();
+
+        assert_eq!(result, simulate_ruin(&config, &SimulationProgress::default()).unwrap());
+        assert_eq!((result.trials, result.played.rounds), (40, 80_000));
+        // 20 units against the house over 2,000 rounds goes broke more often than not
+        assert!(result.simulated() > 0.5, "{}", result.simulated());
+        assert!((result.formula_within() - result.simulated()).abs() < 0.3, "{} {}", result.formula_within(), result.simulated());
+        assert_eq!(result.formula(), 1.0);
+
+        let progress = SimulationProgress::default();
+        progress.cancel();
+        assert_eq!(simulate_ruin(&config, &progress).unwrap().trials, 0);
+    }
+}
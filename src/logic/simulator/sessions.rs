@@ -0,0 +1,91 @@
+/// Rounds a player gets through in an hour at a busy table
+pub const ROUNDS_PER_HOUR: u64 = 100;
+
+/// Net results of sessions of the same length cut from a run, in betting units.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Sessions {
+    /// Rounds in each session
+    pub session_rounds: u64,
+    pub results: Vec<f64>,
+}
+
+/// Sessions that ended between `low` and `high` units.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bin {
+    pub low: f64,
+    pub high: f64,
+    pub count: usize,
+}
+
+impl Sessions {
+    pub fn hours(&self) -> f64 {
+        self.session_rounds as f64 / ROUNDS_PER_HOUR as f64
+    }
+
+    pub fn mean(&self) -> f64 {
+        if self.results.is_empty() {
+            return 0.0;
+        }
+        self.results.iter().sum::<f64>() / self.results.len() as f64
+    }
+
+    /// Share of sessions that finished ahead.
+    pub fn ahead(&self) -> f64 {
+        if self.results.is_empty() {
+            return 0.0;
+        }
+        self.results.iter().filter(|net| **net > 0.0).count() as f64 / self.results.len() as f64
+    }
+
+    /// The result `percentile` percent of sessions finished at or below.
+    pub fn percentile(&self, percentile: f64) -> f64 {
+        if self.results.is_empty() {
+            return 0.0;
+        }
+        let mut results = self.results.clone();
+        results.sort_by(f64::total_cmp);
+        let rank = ((percentile / 100.0) * (results.len() - 1) as f64).round() as usize;
+        results[rank.min(results.len() - 1)]
+    }
+
+    /// Counts sessions into `bins` bins of the same whole number of units,
+    /// starting from the worst result and reaching the best.
+    pub fn histogram(&self, bins: usize) -> Vec<Bin> {
+        if self.results.is_empty() || bins == 0 {
+            return vec![];
+        }
+        let low = self.results.iter().copied().fold(f64::INFINITY, f64::min).floor();
+        let high = self.results.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let width = ((high - low) / bins as f64).ceil().max(1.0);
+        let mut histogram: Vec<Bin> = (0..bins)
+            .map(|bin| Bin { low: low + bin as f64 * width, high: low + (bin + 1) as f64 * width, count: 0 })
+            .collect();
+        for net in &self.results {
+            let bin = (((net - low) / width) as usize).min(bins - 1);
+            histogram[bin].count += 1;
+        }
+        histogram
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_summary_and_histogram() {
+        let sessions = Sessions { session_rounds: 400, results: vec![-12.0, -3.5, 0.0, 1.0, 4.0, 8.5] };
+
+        assert_eq!(sessions.hours(), 4.0);
+        assert_eq!(sessions.mean(), -0.3333333333333333);
+        assert_eq!(sessions.ahead(), 0.5);
+        assert_eq!((sessions.percentile(0.0), sessions.percentile(50.0), sessions.percentile(100.0)), (-12.0, 1.0, 8.5));
+
+        // 20.5 units across 4 bins rounds up to 6 units a bin
+        let histogram = sessions.histogram(4);
+        assert_eq!(histogram.iter().map(|bin| bin.count).collect::<Vec<usize>>(), vec![1, 1, 3, 1]);
+        assert_eq!((histogram[0].low, histogram[0].high, histogram[3].high), (-12.0, -6.0, 12.0));
+        assert_eq!(histogram.iter().map(|bin| bin.count).sum::<usize>(), sessions.results.len());
+        assert!(Sessions::default().histogram(4).is_empty());
+    }
+}
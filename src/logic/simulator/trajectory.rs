@@ -0,0 +1,62 @@
+/// Points along each path after the start, evenly spaced in rounds
+pub const TRAJECTORY_POINTS: usize = 50;
+
+/// How the bankroll moved over stretches of a run, each path starting at
+/// nothing and stepping through its net result in betting units.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Trajectories {
+    /// Rounds in each path
+    pub path_rounds: u64,
+
+    /// Net result at the start of each path and after every step of
+    /// `path_rounds / TRAJECTORY_POINTS` rounds
+    pub paths: Vec<Vec<f64>>,
+}
+
+impl Trajectories {
+    /// Rounds played by each point along a path.
+    pub fn rounds_at(&self) -> Vec<u64> {
+        let step = self.path_rounds / TRAJECTORY_POINTS as u64;
+        (0..=TRAJECTORY_POINTS as u64).map(|point| point * step).collect()
+    }
+
+    /// The result at each point that `percentile` percent of paths are at
+    /// or below, taking the nearest path rather than blending two.
+    pub fn percentile(&self, percentile: f64) -> Vec<f64> {
+        if self.paths.is_empty() {
+            return vec![];
+        }
+        let rank = ((percentile / 100.0) * (self.paths.len() - 1) as f64).round() as usize;
+        (0..=TRAJECTORY_POINTS)
+            .map(|point| {
+                let mut results: Vec<f64> = self.paths.iter().map(|path| path[point]).collect();
+                results.sort_by(f64::total_cmp);
+                results[rank.min(results.len() - 1)]
+            })
+            .collect()
+    }
+
+    /// The lowest and highest result any path reached.
+    pub fn range(&self) -> (f64, f64) {
+        self.paths.iter().flatten().fold((0.0, 0.0), |(low, high), net| (net.min(low), net.max(high)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percentiles_and_range() {
+        let paths: Vec<Vec<f64>> = (0..5).map(|path| (0..=TRAJECTORY_POINTS).map(|point| (path * point) as f64 - 10.0 * (point > 0) as u8 as f64).collect()).collect();
+        let trajectories = Trajectories { path_rounds: 500, paths };
+
+        assert_eq!(trajectories.rounds_at()[..3], [0, 10, 20]);
+        assert_eq!(*trajectories.rounds_at().last().unwrap(), 500);
+        assert_eq!(trajectories.percentile(0.0)[1], -10.0);
+        assert_eq!(trajectories.percentile(50.0)[1], -8.0);
+        assert_eq!(trajectories.percentile(100.0)[2], -2.0);
+        assert_eq!(trajectories.range(), (-10.0, 4.0 * TRAJECTORY_POINTS as f64 - 10.0));
+        assert!(Trajectories::default().percentile(50.0).is_empty());
+    }
+}
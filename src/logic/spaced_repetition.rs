@@ -0,0 +1,243 @@
+use crate::logic::decision_accuracy::DecisionAccuracy;
+use crate::logic::strategy_quiz::QuizQuestion;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// Answers that make up one step of a review interval
+pub const STEP_ANSWERS: u64 = 10;
+
+/// Chance of asking a due cell in place of a dealt hand
+pub const REVIEW_SHARE: f64 = 0.5;
+
+/// Review intervals after the first and second answers in a row that are
+/// right, in steps. Later intervals grow by the cell's ease.
+const FIRST_INTERVAL: u32 = 1;
+const SECOND_INTERVAL: u32 = 6;
+
+const STARTING_EASE: f64 = 2.5;
+const MIN_EASE: f64 = 1.3;
+
+/// How well an answer was recalled, on SM-2's scale of 0 to 5
+const RIGHT_QUALITY: u8 = 4;
+const WRONG_QUALITY: u8 = 1;
+
+/// A chart cell that has been missed, scheduled for review.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Review {
+    /// Chart the cell belongs to
+    pub chart: String,
+
+    /// The cell, e.g. "A,7 vs 6"
+    pub cell: String,
+
+    /// The hand last asked from the cell, asked again when it's due
+    pub question: QuizQuestion,
+
+    /// How quickly the interval grows, lower for cells that are missed often
+    pub ease: f64,
+
+    /// Right answers in a row
+    pub repetitions: u32,
+
+    /// Steps until the next review
+    pub interval: u32,
+
+    /// Answers given, over every chart, when the cell comes due
+    pub due: u64,
+
+    /// Times the cell has been missed
+    pub lapses: u32,
+}
+
+impl Review {
+    /// A cell not yet reviewed, due straight away.
+    fn new(chart: &str, question: QuizQuestion) -> Self {
+        Self {
+            chart: chart.to_string(),
+            cell: question.cell(),
+            question,
+            ease: STARTING_EASE,
+            repetitions: 0,
+            interval: 0,
+            due: 0,
+            lapses: 0,
+        }
+    }
+
+    /// SM-2's update for an answer of the given quality, made once
+    /// `answered` answers have been given.
+    fn grade(&mut self, quality: u8, answered: u64) {
+        if quality < 3 {
+            self.repetitions = 0;
+            self.interval = FIRST_INTERVAL;
+            self.lapses += 1;
+        } else {
+            self.interval = match self.repetitions {
+                0 => FIRST_INTERVAL,
+                1 => SECOND_INTERVAL,
+                _ => (self.interval as f64 * self.ease).round() as u32,
+            };
+            self.repetitions += 1;
+        }
+        let shortfall = (5 - quality) as f64;
+        self.ease = (self.ease + 0.1 - shortfall * (0.08 + shortfall * 0.02)).max(MIN_EASE);
+        self.due = answered + self.interval as u64 * STEP_ANSWERS;
+    }
+}
+
+/// Chart cells that have been missed, brought back into the quiz on an
+/// SM-2 style schedule counted in answers rather than days, so practice
+/// keeps coming back to weaknesses. Saved between runs.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ReviewSchedule {
+    /// Answers given over every chart, the clock reviews are due by
+    answered: u64,
+    reviews: Vec<Review>,
+}
+
+impl ReviewSchedule {
+    /// Loads the saved schedule, starting fresh if there is none or it cannot be read.
+    pub fn load(path: &str) -> Self {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(parent) = Path::new(path).parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Grades an answer for its cell. Cells are only scheduled once missed,
+    /// after which every answer for them counts as a review.
+    pub fn record(&mut self, chart: &str, question: &QuizQuestion, correct: bool) {
+        self.answered += 1;
+        let cell = question.cell();
+        let index = match self.reviews.iter().position(|review| review.chart == chart && review.cell == cell) {
+            Some(index) => index,
+            None if correct => return,
+            None => {
+                self.reviews.push(Review::new(chart, question.clone()));
+                self.reviews.len() - 1
+            }
+        };
+        let review = &mut self.reviews[index];
+        review.question = question.clone();
+        review.grade(if correct { RIGHT_QUALITY } else { WRONG_QUALITY }, self.answered);
+    }
+
+    /// Schedules the chart's cells that have been missed anywhere, at the
+    /// table as well as in the drills, and aren't scheduled yet, due
+    /// straight away.
+    pub fn schedule_missed(&mut self, chart: &str, accuracy: &DecisionAccuracy) {
+        for missed in accuracy.missed(chart) {
+            let Some(question) = QuizQuestion::for_cell(missed.hand, missed.column) else {
+                continue;
+            };
+            if self.reviews.iter().any(|review| review.chart == chart && review.cell == question.cell()) {
+                continue;
+            }
+            let mut review = Review::new(chart, question);
+            review.lapses = missed.tally.wrong;
+            review.due = self.answered;
+            self.reviews.push(review);
+        }
+    }
+
+    /// The chart's cells that are due, most overdue first.
+    pub fn due(&self, chart: &str) -> Vec<&Review> {
+        let mut due: Vec<&Review> = self.reviews.iter()
+            .filter(|review| review.chart == chart && review.due <= self.answered)
+            .collect();
+        due.sort_by_key(|review| review.due);
+        due
+    }
+
+    /// A due cell to ask in place of the next dealt hand, some of the time,
+    /// passing over cells whose hands aren't `allowed`.
+    pub fn next_review(&self, chart: &str, allowed: impl Fn(&QuizQuestion) -> bool) -> Option<QuizQuestion> {
+        if !rand::rng().random_bool(REVIEW_SHARE) {
+            return None;
+        }
+        self.due(chart).into_iter()
+            .find(|review| allowed(&review.question))
+            .map(|review| review.question.clone())
+    }
+
+    /// The chart's scheduled cells, hardest first.
+    pub fn weakest(&self, chart: &str) -> Vec<&Review> {
+        let mut reviews: Vec<&Review> = self.reviews.iter().filter(|review| review.chart == chart).collect();
+        reviews.sort_by(|a, b| a.ease.total_cmp(&b.ease).then(b.lapses.cmp(&a.lapses)));
+        reviews
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logic::game::card::{Card, Rank, Suit};
+    use crate::logic::ev::ChartHand;
+    use crate::logic::game::hand::Hand;
+
+    fn question(first: Rank, second: Rank, upcard: Rank) -> QuizQuestion {
+        let mut hand = Hand::new();
+        hand.add(Card::new(first, Suit::Spades));
+        hand.add(Card::new(second, Suit::Hearts));
+        QuizQuestion { hand, upcard: Card::new(upcard, Suit::Clubs) }
+    }
+
+    #[test]
+    fn test_missed_cells_come_back_less_often_as_they_are_learned() {
+        let mut schedule = ReviewSchedule::default();
+        let sixteen = question(Rank::Ten, Rank::Six, Rank::Ten);
+
+        // Right answers for a cell never missed aren't scheduled
+        schedule.record("chart", &sixteen, true);
+        assert!(schedule.weakest("chart").is_empty());
+
+        // A miss is due again a step later, for any hand from its cell
+        schedule.record("chart", &sixteen, false);
+        let review = schedule.weakest("chart")[0].clone();
+        assert_eq!((review.cell.as_str(), review.lapses, review.due), ("16 vs 10", 1, 2 + STEP_ANSWERS));
+        assert!(review.ease < STARTING_EASE);
+        for _ in 0..STEP_ANSWERS {
+            assert!(schedule.due("chart").is_empty());
+            schedule.record("chart", &question(Rank::Two, Rank::Three, Rank::Four), true);
+        }
+        assert_eq!(schedule.due("chart").len(), 1);
+        assert!(schedule.due("other chart").is_empty());
+
+        // Right answers push the next review further out each time
+        let nine_seven = question(Rank::Nine, Rank::Seven, Rank::King);
+        let mut intervals = Vec::new();
+        for _ in 0..3 {
+            schedule.record("chart", &nine_seven, true);
+            intervals.push(schedule.weakest("chart")[0].interval);
+        }
+        assert_eq!(intervals, [FIRST_INTERVAL, SECOND_INTERVAL, (SECOND_INTERVAL as f64 * review.ease).round() as u32]);
+        assert_eq!(schedule.weakest("chart")[0].question, nine_seven);
+        assert!(schedule.due("chart").is_empty());
+
+        // Cells missed at the table come due at once, those already
+        // scheduled are left as they are
+        let mut accuracy = DecisionAccuracy::default();
+        accuracy.record("chart", ChartHand::Soft(18), Card::new(Rank::Nine, Suit::Hearts), false);
+        accuracy.record("chart", ChartHand::Hard(16), Card::new(Rank::King, Suit::Hearts), false);
+        schedule.schedule_missed("chart", &accuracy);
+        let due: Vec<&str> = schedule.due("chart").iter().map(|review| review.cell.as_str()).collect();
+        assert_eq!(due, ["A,7 vs 9"]);
+        assert_eq!(schedule.weakest("chart").len(), 2);
+
+        let path = std::env::temp_dir().join(format!("review-schedule-{}.json", uuid::Uuid::new_v4()));
+        let path = path.to_str().unwrap();
+        schedule.save(path).unwrap();
+        assert_eq!(ReviewSchedule::load(path), schedule);
+        fs::remove_file(path).unwrap();
+    }
+}
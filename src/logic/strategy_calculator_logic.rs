@@ -1,11 +1,32 @@
 use std::collections::HashMap;
+use std::{fmt, fs};
+use std::time::SystemTime;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
+use crate::logic::game::advisor::resolve_code;
+use crate::logic::game::round::PlayerAction;
 use crate::logic::strategy_calculator_logic::SurrenderRule::{AnyUpcard, Dealer2Through10, NotAllowed};
 
+/// Version of the strategy file format written by this build. Files from
+/// before it was recorded are version 1.
+pub const STRATEGY_FORMAT_VERSION: u64 = 3;
+
+/// Upgrades a strategy file's JSON from one format version to the next, the
+/// first from version 1 to 2.
+const MIGRATIONS: [fn(&mut serde_json::Map<String, serde_json::Value>); 2] = [
+    add_game_variant,
+    count_decks_exactly,
+];
+
+/// Deck counts the rules can be set to
+pub const DECK_OPTIONS: [u8; 6] = [1, 2, 4, 5, 6, 8];
+
 /// Represents a complete "basic" blackjack strategy without deviations.
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct BlackjackStrategy {
+    /// Version of the file format the strategy was read from or is written in
+    pub format_version: u64,
+
     /// Unique identifier of the strategy
     pub id: Uuid,
 
@@ -22,12 +43,55 @@ pub struct BlackjackStrategy {
     pub tables: StrategyTables,
 
     /// Legend explaining the tables symbols and their corresponding actions
-    pub action_legend: HashMap<String, String>,
+    pub action_legend: HashMap<String, LegendEntry>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+/// What a chart code means, and optionally the color to draw it in. In a
+/// file it's just the description, e.g. `"H": "Hit"`, unless it has a color,
+/// e.g. `"Ph": { "description": "Split if DAS, otherwise Hit", "color": "#29b8db" }`.
+/// Colors are names like "lightmagenta" or hex like "#cc79a7".
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(from = "LegendValue", into = "LegendValue")]
+pub struct LegendEntry {
+    pub description: String,
+    pub color: Option<String>,
+}
+
+/// How a legend entry is written in a strategy file.
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum LegendValue {
+    Description(String),
+    Styled { description: String, color: Option<String> },
+}
+
+impl From<LegendValue> for LegendEntry {
+    fn from(value: LegendValue) -> Self {
+        match value {
+            LegendValue::Description(description) => Self { description, color: None },
+            LegendValue::Styled { description, color } => Self { description, color },
+        }
+    }
+}
+
+impl From<LegendEntry> for LegendValue {
+    fn from(entry: LegendEntry) -> Self {
+        match entry.color {
+            None => LegendValue::Description(entry.description),
+            color => LegendValue::Styled { description: entry.description, color },
+        }
+    }
+}
+
+impl From<&str> for LegendEntry {
+    fn from(description: &str) -> Self {
+        Self { description: description.to_string(), color: None }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct StrategyVariables {
-    /// Number of decks
+    /// Number of decks, one of DECK_OPTIONS
     pub decks: u8,
 
     /// Whether dealer stands on soft 17
@@ -36,11 +100,17 @@ pub struct StrategyVariables {
     /// Whether play may double after splitting
     pub double_after_split: bool,
 
-    /// Whether dealer peaks for blackjack
+    /// Whether dealer peaks for blackjack. Without a peek the dealer plays
+    /// European no hole card (ENHC) rules and doubles and splits are lost to a
+    /// dealer blackjack, so "no peek" charts are ENHC charts.
     pub dealer_peak: bool,
 
     /// Type of surrender allowed
     pub surrender_allowed: SurrenderRule,
+
+    /// Which flavour of blackjack the chart is for, charts without one are Classic
+    #[serde(default)]
+    pub variant: GameVariant,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -82,13 +152,23 @@ pub struct PairRow {
     pub actions: Vec<String>,
 }
 
-#[derive(Serialize, Hash, Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Hash, Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SurrenderRule {
     NotAllowed,
     AnyUpcard,
     Dealer2Through10,
 }
 
+// Written the same way the strategy files spell it, so rules read back
+impl Serialize for SurrenderRule {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str((*self).to_string())
+    }
+}
+
 // Add this implementation to your SurrenderRule enum
 impl<'de> Deserialize<'de> for SurrenderRule {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
@@ -100,6 +180,78 @@ impl<'de> Deserialize<'de> for SurrenderRule {
     }
 }
 
+/// Blackjack variants with their own payouts and so their own basic strategy.
+#[derive(Serialize, Deserialize, Hash, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GameVariant {
+    #[default]
+    Classic,
+    /// Free doubles on hard 9-11, free splits on every pair but tens, and a
+    /// dealer 22 pushes every hand still standing
+    #[serde(rename = "Free Bet")]
+    FreeBet,
+}
+
+impl fmt::Display for GameVariant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            GameVariant::Classic => "Classic",
+            GameVariant::FreeBet => "Free Bet",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+// The rules in one line, e.g. "Classic, 6 Decks, S17, DAS, Surrender: Not Allowed, Dealer Peeks"
+impl fmt::Display for StrategyVariables {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}, {}, {}, {}, Surrender: {}, {}",
+            self.variant,
+            if self.decks == 1 { "1 Deck".to_string() } else { format!("{} Decks", self.decks) },
+            if self.dealer_stands_on_soft_17 { "S17" } else { "H17" },
+            if self.double_after_split { "DAS" } else { "No DAS" },
+            self.surrender_allowed.to_string(),
+            if self.dealer_peak { "Dealer Peeks" } else { "No Peek (ENHC)" },
+        )
+    }
+}
+
+impl StrategyVariables {
+    /// Rules saved to `path`, if there are any that can be read and deal
+    /// one of `DECK_OPTIONS`.
+    pub fn load(path: &str) -> Option<Self> {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|json| serde_json::from_str::<Self>(&json).ok())
+            .filter(|rules| DECK_OPTIONS.contains(&rules.decks))
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(parent) = std::path::Path::new(path).parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+impl StrategyVariables {
+    /// Single deck, S17, DAS, peek and no surrender: the table the game
+    /// tests deal at.
+    pub fn single_deck() -> Self {
+        Self {
+            decks: 1,
+            dealer_stands_on_soft_17: true,
+            double_after_split: true,
+            dealer_peak: true,
+            surrender_allowed: NotAllowed,
+            variant: GameVariant::Classic,
+        }
+    }
+}
+
 impl SurrenderRule {
     pub fn from_string(str_value: &str) -> Result<Self, String> {
         match str_value {
@@ -110,7 +262,7 @@ impl SurrenderRule {
         }
     }
 
-    pub fn to_string(&self) -> &'static str {
+    pub fn to_string(self) -> &'static str {
         match self {
             NotAllowed => "Not Allowed",
             AnyUpcard => "Any Dealer Upcard",
@@ -123,6 +275,7 @@ impl BlackjackStrategy {
     /// Create a new BlackjackStrategy with default values
     pub fn new() -> Self {
         Self {
+            format_version: STRATEGY_FORMAT_VERSION,
             id: Uuid::new_v4(),
             name: "Default Strategy".to_string(),
             description: "Default Basic Strategy".to_string(),
@@ -131,7 +284,8 @@ impl BlackjackStrategy {
                 dealer_stands_on_soft_17: true,
                 double_after_split: true,
                 dealer_peak: true,
-                surrender_allowed: NotAllowed
+                surrender_allowed: NotAllowed,
+                variant: GameVariant::Classic,
             },
             tables: StrategyTables {
                 hard_hands: Vec::new(),
@@ -142,27 +296,431 @@ impl BlackjackStrategy {
         }
     }
 
-    /// Parse a BlackjackStrategy from a JSON string
+    /// Parse a BlackjackStrategy from a JSON string, bringing files written in
+    /// an older format up to date
     pub fn from_json(json_str: &str) -> Result<Self, serde_json::Error> {
-        let mut strategy: Self = serde_json::from_str(json_str)?;
+        let mut json: serde_json::Value = serde_json::from_str(json_str)?;
+        if let Some(fields) = json.as_object_mut() {
+            migrate(fields).map_err(serde::de::Error::custom)?;
+        }
+        let mut strategy: Self = serde_json::from_value(json)?;
         if strategy.id == Uuid::nil() {
             strategy.id = Uuid::new_v4();
         }
         Ok(strategy)
     }
 
+    /// Problems that would leave the chart broken: rows without an action
+    /// for every upcard, totals no hand can have, repeated rows, and codes
+    /// that aren't known or aren't in the legend.
+    pub fn validate(&self) -> Vec<String> {
+        let tables = &self.tables;
+        let rows = tables.hard_hands.iter().map(|row| ("Hard", row.total, &row.actions, 4..=21))
+            .chain(tables.soft_hands.iter().map(|row| ("Soft", row.total, &row.actions, 12..=21)))
+            .chain(tables.pair_hands.iter().map(|row| ("Pair", row.pair, &row.actions, 2..=11)));
+        let all_actions = [
+            PlayerAction::Hit,
+            PlayerAction::Stand,
+            PlayerAction::Double,
+            PlayerAction::Split,
+            PlayerAction::Surrender,
+        ];
+
+        let mut problems = Vec::new();
+        let mut seen = Vec::new();
+        let mut unexplained: Vec<&String> = Vec::new();
+        for (kind, total, actions, range) in rows {
+            let row = format!("{} {}", kind, total);
+            if !range.contains(&total) {
+                problems.push(format!("{} is out of range, {} rows run from {} to {}", row, kind.to_lowercase(), range.start(), range.end()));
+            }
+            if seen.contains(&row) {
+                problems.push(format!("{} appears more than once", row));
+            }
+            if actions.len() != 10 {
+                problems.push(format!("{} has {} actions, it needs one for each upcard from 2 to A (10)", row, actions.len()));
+            }
+            for code in actions {
+                if resolve_code(code, &all_actions).is_none() {
+                    problems.push(format!("{} uses an unknown code: {}", row, code));
+                } else if !self.action_legend.contains_key(code) && !unexplained.contains(&code) {
+                    unexplained.push(code);
+                }
+            }
+            seen.push(row);
+        }
+
+        // Each missing legend entry once, rather than for every cell it's in
+        for code in unexplained {
+            problems.push(format!("{} is used in the tables but isn't in the action legend", code));
+        }
+        let mut colors: Vec<(&String, &String)> = self.action_legend.iter()
+            .filter_map(|(code, entry)| entry.color.as_ref().map(|color| (code, color)))
+            .collect();
+        colors.sort();
+        for (code, color) in colors {
+            if color.parse::<ratatui::style::Color>().is_err() {
+                problems.push(format!("{}'s color isn't a color name or #rrggbb: {}", code, color));
+            }
+        }
+        problems
+    }
+
     /// Load a BlackjackStrategy from a file
     pub fn from_file(path: &str) -> Result<Self, Box<dyn std::error::Error>> {
         let file_content = std::fs::read_to_string(path)?;
         let strategy = Self::from_json(&file_content)?;
         Ok(strategy)
     }
+
+    /// Save the BlackjackStrategy to a file, e.g. in the strategies directory
+    pub fn save(&self, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Write the id, name and description back to the strategy's file at
+    /// `path`, leaving the rest of the file as it was written
+    pub fn save_metadata(&self, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let mut json: serde_json::Value = serde_json::from_str(&fs::read_to_string(path)?)?;
+        let fields = json.as_object_mut().ok_or("Strategy file isn't a JSON object")?;
+        fields.insert("id".to_string(), serde_json::to_value(self.id)?);
+        fields.insert("name".to_string(), self.name.clone().into());
+        fields.insert("description".to_string(), self.description.clone().into());
+        fs::write(path, serde_json::to_string_pretty(&json)?)?;
+        Ok(())
+    }
+}
+
+// ---- File Format Migrations ----
+
+/// Runs every migration from the file's format version on, so it reads as
+/// the current version. Files from a newer build than this one are refused
+/// rather than read with fields missing.
+fn migrate(fields: &mut serde_json::Map<String, serde_json::Value>) -> Result<(), String> {
+    let version = match fields.get("format_version") {
+        None => 1,
+        Some(version) => version.as_u64()
+            .filter(|version| *version >= 1)
+            .ok_or_else(|| format!("Unknown format version: {}", version))?,
+    };
+    if version > STRATEGY_FORMAT_VERSION {
+        return Err(format!(
+            "Written in format version {}, this version of the app reads up to {}",
+            version, STRATEGY_FORMAT_VERSION
+        ));
+    }
+
+    for migration in &MIGRATIONS[version as usize - 1..] {
+        migration(fields);
+    }
+    fields.insert("format_version".to_string(), STRATEGY_FORMAT_VERSION.into());
+    Ok(())
+}
+
+/// Version 2 added game variants, every chart before them was Classic.
+fn add_game_variant(fields: &mut serde_json::Map<String, serde_json::Value>) {
+    if let Some(rules) = fields.get_mut("rules").and_then(|rules| rules.as_object_mut()) {
+        rules.entry("variant").or_insert_with(|| GameVariant::Classic.to_string().into());
+    }
+}
+
+/// Version 3 counts decks as they are. Before it 3 stood for four or more
+/// decks, charts that play the same as a six deck shoe.
+fn count_decks_exactly(fields: &mut serde_json::Map<String, serde_json::Value>) {
+    if let Some(decks) = fields.get_mut("rules").and_then(|rules| rules.get_mut("decks"))
+        && decks.as_u64() == Some(3)
+    {
+        *decks = 6.into();
+    }
+}
+
+/// A strategy file that couldn't be loaded, and why: the reason it
+/// couldn't be read, or everything wrong with the chart in it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StrategyLoadError {
+    /// Name of the file within the strategies directory
+    pub file: String,
+
+    pub problems: Vec<String>,
+}
+
+impl fmt::Display for StrategyLoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.file, self.problems.join("; "))
+    }
+}
+
+/// Loads every `.json` strategy in `strategies_dir`, keyed by file stem.
+/// Files that can't be read or don't validate are left out.
+pub fn load_strategies(strategies_dir: &str) -> HashMap<String, BlackjackStrategy> {
+    load_strategies_reporting_errors(strategies_dir).0
+}
+
+/// Loads every `.json` strategy in `strategies_dir`, keyed by file stem,
+/// along with why any that couldn't be read or don't validate were left
+/// out, by file name.
+pub fn load_strategies_reporting_errors(strategies_dir: &str) -> (HashMap<String, BlackjackStrategy>, Vec<StrategyLoadError>) {
+    let mut strategy_cache = HashMap::new();
+    let mut errors = Vec::new();
+
+    // Attempt to read directory and load all .json files
+    if let Ok(entries) = fs::read_dir(strategies_dir) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_file() && path.extension().and_then(|ext| ext.to_str()) == Some("json")
+                && let Some(filename) = path.file_stem().and_then(|name| name.to_str()) {
+                let problems = match BlackjackStrategy::from_file(&path.to_string_lossy()) {
+                    Ok(strategy) => {
+                        let problems = strategy.validate();
+                        if problems.is_empty() {
+                            // Cache the strategy
+                            strategy_cache.insert(filename.to_string(), strategy);
+                        }
+                        problems
+                    },
+                    Err(err) => vec![err.to_string()],
+                };
+                if !problems.is_empty() {
+                    errors.push(StrategyLoadError {
+                        file: entry.file_name().to_string_lossy().into_owned(),
+                        problems,
+                    });
+                }
+            }
+        }
+    }
+
+    errors.sort_by(|a, b| a.file.cmp(&b.file));
+    (strategy_cache, errors)
+}
+
+/// Each `.json` file in `strategies_dir` with when it was last modified and
+/// its size, sorted by name. It changes whenever a strategy is added,
+/// removed or edited, so the directory can be watched by comparing them.
+pub fn strategy_files_signature(strategies_dir: &str) -> Vec<(String, Option<SystemTime>, u64)> {
+    let mut files: Vec<_> = fs::read_dir(strategies_dir)
+        .map(|entries| {
+            entries.flatten()
+                .filter(|entry| entry.path().extension().and_then(|ext| ext.to_str()) == Some("json"))
+                .map(|entry| {
+                    let metadata = entry.metadata().ok();
+                    (
+                        entry.file_name().to_string_lossy().into_owned(),
+                        metadata.as_ref().and_then(|metadata| metadata.modified().ok()),
+                        metadata.map_or(0, |metadata| metadata.len()),
+                    )
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    files.sort();
+    files
+}
+
+/// Finds a cached strategy designed for exactly the given rules.
+pub fn find_matching_strategy<'a>(
+    strategy_cache: &'a HashMap<String, BlackjackStrategy>,
+    rules: &StrategyVariables,
+) -> Option<(String, &'a BlackjackStrategy)> {
+    strategy_cache.iter()
+        .find(|(_, strategy)| strategy.rules == *rules)
+        .map(|(name, strategy)| (name.clone(), strategy))
+}
+
+/// The name a chart for the rules is filed under in the strategies
+/// directory, e.g. "strategy_6D_S17_DAS_AUC_P", with "_FB" for Free Bet.
+pub fn strategy_key(rules: &StrategyVariables) -> String {
+    let surrender = match rules.surrender_allowed {
+        NotAllowed => "NS",
+        AnyUpcard => "AUC",
+        Dealer2Through10 => "D2T10",
+    };
+    let mut key = format!(
+        "strategy_{}D_{}_{}_{}_{}",
+        rules.decks,
+        if rules.dealer_stands_on_soft_17 { "S17" } else { "H17" },
+        if rules.double_after_split { "DAS" } else { "NDAS" },
+        surrender,
+        if rules.dealer_peak { "P" } else { "NP" },
+    );
+    if rules.variant == GameVariant::FreeBet {
+        key.push_str("_FB");
+    }
+    key
+}
+
+/// The rules two rule sets differ on, by short name, e.g. ["Soft 17", "DAS"].
+pub fn rule_differences(first: &StrategyVariables, second: &StrategyVariables) -> Vec<&'static str> {
+    [
+        ("Decks", first.decks != second.decks),
+        ("Soft 17", first.dealer_stands_on_soft_17 != second.dealer_stands_on_soft_17),
+        ("DAS", first.double_after_split != second.double_after_split),
+        ("Peek", first.dealer_peak != second.dealer_peak),
+        ("Surrender", first.surrender_allowed != second.surrender_allowed),
+        ("Variant", first.variant != second.variant),
+    ]
+        .into_iter()
+        .filter(|(_, differs)| *differs)
+        .map(|(rule, _)| rule)
+        .collect()
+}
+
+/// Finds the cached strategy whose rules differ from the given ones on the
+/// fewest, along with the rules it differs on. An exact match differs on none.
+pub fn find_nearest_strategy<'a>(
+    strategy_cache: &'a HashMap<String, BlackjackStrategy>,
+    rules: &StrategyVariables,
+) -> Option<(String, &'a BlackjackStrategy, Vec<&'static str>)> {
+    strategy_cache.iter()
+        .map(|(name, strategy)| (name, strategy, rule_differences(&strategy.rules, rules)))
+        // Ties go by name so the same chart is picked every time
+        .min_by(|a, b| a.2.len().cmp(&b.2.len()).then_with(|| a.0.cmp(b.0)))
+        .map(|(name, strategy, differences)| (name.clone(), strategy, differences))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_rules_read_back() {
+        let path = std::env::temp_dir().join(format!("rules-{}", Uuid::new_v4())).join("last_rules.json");
+        let path = path.to_str().unwrap();
+        assert_eq!(StrategyVariables::load(path), None);
+
+        let rules = StrategyVariables {
+            decks: 2,
+            dealer_stands_on_soft_17: false,
+            double_after_split: false,
+            dealer_peak: false,
+            surrender_allowed: Dealer2Through10,
+            variant: GameVariant::FreeBet,
+        };
+        rules.save(path).unwrap();
+        assert_eq!(StrategyVariables::load(path), Some(rules.clone()));
+
+        // Rules dealing decks there's no shoe or chart for aren't used
+        for decks in [0, 3] {
+            StrategyVariables { decks, ..rules.clone() }.save(path).unwrap();
+            assert_eq!(StrategyVariables::load(path), None);
+        }
+        fs::remove_dir_all(std::path::Path::new(path).parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_malformed_strategy_files_are_reported() {
+        let dir = std::env::temp_dir().join(format!("strategies-{}", Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::copy("resources/strategies/default-strategy.json", dir.join("good.json")).unwrap();
+        fs::write(dir.join("truncated.json"), r#"{ "name": "Truncated""#).unwrap();
+        fs::write(dir.join("notes.txt"), "not a strategy").unwrap();
+
+        let (strategies, errors) = load_strategies_reporting_errors(dir.to_str().unwrap());
+        assert_eq!(strategies.keys().collect::<Vec<_>>(), vec!["good"]);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].file, "truncated.json");
+        assert!(errors[0].to_string().starts_with("truncated.json: EOF while parsing"));
+
+        // Fixing the file changes what's in the directory
+        let signature = strategy_files_signature(dir.to_str().unwrap());
+        assert_eq!(signature.len(), 2);
+        fs::copy(dir.join("good.json"), dir.join("truncated.json")).unwrap();
+        assert_ne!(strategy_files_signature(dir.to_str().unwrap()), signature);
+        assert!(load_strategies_reporting_errors(dir.to_str().unwrap()).1.is_empty());
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_save_metadata() {
+        let path = std::env::temp_dir().join(format!("strategy-{}.json", Uuid::new_v4()));
+        let path = path.to_str().unwrap();
+        fs::copy("resources/strategies/default-strategy.json", path).unwrap();
+
+        let mut strategy = BlackjackStrategy::from_file(path).unwrap();
+        strategy.name = "Renamed".to_string();
+        strategy.description = "Edited in the app".to_string();
+        strategy.id = Uuid::new_v4();
+        strategy.save_metadata(path).unwrap();
+
+        let saved = BlackjackStrategy::from_file(path).unwrap();
+        assert_eq!((saved.id, saved.name.as_str(), saved.description.as_str()), (strategy.id, "Renamed", "Edited in the app"));
+        assert_eq!(saved.rules, strategy.rules);
+        assert_eq!(saved.tables.hard_hands.len(), strategy.tables.hard_hands.len());
+        // Rules left out of the file stay left out
+        let json: serde_json::Value = serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap();
+        assert!(json["rules"].get("variant").is_none());
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_older_formats_are_migrated() {
+        let version_1 = fs::read_to_string("resources/strategies/default-strategy.json").unwrap();
+        assert!(!version_1.contains("format_version") && !version_1.contains("variant"));
+        let strategy = BlackjackStrategy::from_json(&version_1).unwrap();
+        assert_eq!(strategy.format_version, STRATEGY_FORMAT_VERSION);
+        assert_eq!(strategy.rules.variant, GameVariant::Classic);
+        // 3 decks stood for four or more, which play as six
+        let four_or_more = version_1.replace("\"decks\": 1", "\"decks\": 3");
+        assert_eq!(BlackjackStrategy::from_json(&four_or_more).unwrap().rules.decks, 6);
+
+        // Written back out it's the current version, and reads the same
+        let saved = serde_json::to_string(&strategy).unwrap();
+        assert!(saved.contains(&format!("\"format_version\":{}", STRATEGY_FORMAT_VERSION)));
+        assert_eq!(BlackjackStrategy::from_json(&saved).unwrap().rules, strategy.rules);
+
+        let newer = version_1.replacen('{', &format!("{{ \"format_version\": {},", STRATEGY_FORMAT_VERSION + 1), 1);
+        let err = BlackjackStrategy::from_json(&newer).unwrap_err().to_string();
+        assert!(err.contains("this version of the app reads up to"));
+    }
+
+    #[test]
+    fn test_strategy_key_matches_file_names() {
+        for (name, strategy) in load_strategies("resources/strategies") {
+            if name.starts_with("strategy_") {
+                assert_eq!(strategy_key(&strategy.rules), name);
+            }
+        }
+    }
+
+    #[test]
+    fn test_find_nearest_strategy() {
+        let strategies = load_strategies("resources/strategies");
+        let rules = strategies["strategy_1D_S17_DAS_NS_P"].rules.clone();
+        let (name, _, differences) = find_nearest_strategy(&strategies, &rules).unwrap();
+        assert_eq!(name, "strategy_1D_S17_DAS_NS_P");
+        assert!(differences.is_empty());
+
+        // The only Free Bet charts are for 6 decks, so this is one rule off either way
+        let free_bet = StrategyVariables { variant: GameVariant::FreeBet, ..rules };
+        let (name, _, differences) = find_nearest_strategy(&strategies, &free_bet).unwrap();
+        assert_eq!(name, "strategy_1D_S17_DAS_NS_P");
+        assert_eq!(differences, vec!["Variant"]);
+    }
+
+    #[test]
+    fn test_validate() {
+        for (name, strategy) in load_strategies_reporting_errors("resources/strategies").0 {
+            assert_eq!(strategy.validate(), Vec::<String>::new(), "{}", name);
+        }
+
+        let mut strategy = BlackjackStrategy::from_file("resources/strategies/default-strategy.json").unwrap();
+        strategy.tables.hard_hands[0].actions.pop();
+        strategy.tables.soft_hands[0].total = 22;
+        strategy.tables.pair_hands[1].pair = strategy.tables.pair_hands[0].pair;
+        strategy.tables.pair_hands[2].actions[0] = "X".to_string();
+        strategy.action_legend.remove("S");
+        strategy.action_legend.get_mut("H").unwrap().color = Some("reddish".to_string());
+        let problems = strategy.validate();
+        assert!(problems.contains(&"Hard 5 has 9 actions, it needs one for each upcard from 2 to A (10)".to_string()));
+        assert!(problems.contains(&"Soft 22 is out of range, soft rows run from 12 to 21".to_string()));
+        assert!(problems.contains(&"Pair 2 appears more than once".to_string()));
+        assert!(problems.contains(&"Pair 4 uses an unknown code: X".to_string()));
+        assert!(problems.contains(&"S is used in the tables but isn't in the action legend".to_string()));
+        assert!(problems.contains(&"H's color isn't a color name or #rrggbb: reddish".to_string()));
+        assert_eq!(problems.len(), 6);
+    }
+
     #[test]
     fn test_from_json_minimal() {
         let json_str = r#"{
@@ -191,7 +749,7 @@ mod tests {
         assert_eq!(strategy.name, "Test Strategy");
         assert_eq!(strategy.description, "For Testing");
         assert_eq!(strategy.rules.decks, 1);
-        assert_eq!(strategy.rules.dealer_stands_on_soft_17, true);
+        assert!(strategy.rules.dealer_stands_on_soft_17);
         assert_eq!(strategy.tables.hard_hands.len(), 0);
         assert!(!strategy.id.is_nil()); // Should have generated a UUID
         println!("{:#?}", strategy)
@@ -199,7 +757,7 @@ mod tests {
 
     #[test]
     fn test_from_json_with_tables() {
-        let json_str = r#"{
+        let json_str = r##"{
             "id": "00000000-0000-0000-0000-000000000000",
             "name": "Test Strategy",
             "description": "For Testing",
@@ -225,9 +783,9 @@ mod tests {
                 "H": "Hit",
                 "S": "Stand",
                 "D": "Double",
-                "P": "Split"
+                "P": { "description": "Split", "color": "#00ff00" }
             }
-        }"#;
+        }"##;
 
         let result = BlackjackStrategy::from_json(json_str);
         assert!(result.is_ok());
@@ -235,7 +793,7 @@ mod tests {
         let strategy = result.unwrap();
         assert_eq!(strategy.name, "Test Strategy");
         assert_eq!(strategy.rules.decks, 2);
-        assert_eq!(strategy.rules.dealer_stands_on_soft_17, false);
+        assert!(!strategy.rules.dealer_stands_on_soft_17);
 
         // Verify tables were parsed correctly
         assert_eq!(strategy.tables.hard_hands.len(), 1);
@@ -251,6 +809,12 @@ mod tests {
 
         // Verify action legend was parsed
         assert_eq!(strategy.action_legend.len(), 4);
-        assert_eq!(strategy.action_legend.get("H").unwrap(), "Hit");
+        assert_eq!(strategy.action_legend["H"], LegendEntry::from("Hit"));
+        assert_eq!(strategy.action_legend["P"].color.as_deref(), Some("#00ff00"));
+
+        // Entries without a color are written back as just their description
+        let json = serde_json::to_value(&strategy).unwrap();
+        assert_eq!(json["action_legend"]["H"], "Hit");
+        assert_eq!(json["action_legend"]["P"]["color"], "#00ff00");
     }
 }
\ No newline at end of file
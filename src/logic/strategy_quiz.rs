@@ -0,0 +1,644 @@
+use crate::logic::ev::ChartHand;
+use crate::logic::game::advisor::{chart_code, recommend};
+use crate::logic::game::card::{Card, Rank, Suit};
+use crate::logic::game::hand::Hand;
+use crate::logic::game::round::PlayerAction;
+use crate::logic::game::shoe::Shoe;
+use crate::logic::strategy_calculator_logic::{BlackjackStrategy, SurrenderRule};
+use rand::seq::SliceRandom;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::fmt;
+
+/// Questions asked in each quiz
+pub const QUIZ_LENGTH: usize = 20;
+
+/// Cards a missed flashcard goes behind before it comes up again
+pub const MISSED_GAP: usize = 5;
+
+/// Hands dealt looking for one the drill filter allows before settling
+/// for any hand, for filters that nothing on the chart matches
+const MAX_DEALS: usize = 10_000;
+
+/// Actions in the order they're offered
+const ACTIONS: [PlayerAction; 5] = [
+    PlayerAction::Hit,
+    PlayerAction::Stand,
+    PlayerAction::Double,
+    PlayerAction::Split,
+    PlayerAction::Surrender,
+];
+
+/// The table of the chart a hand is played from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HandCategory {
+    Hard,
+    Soft,
+    Pair,
+}
+
+impl HandCategory {
+    pub const ALL: [HandCategory; 3] = [HandCategory::Hard, HandCategory::Soft, HandCategory::Pair];
+
+    pub fn of(hand: &Hand) -> Self {
+        if hand.is_pair() {
+            HandCategory::Pair
+        } else if hand.is_soft() {
+            HandCategory::Soft
+        } else {
+            HandCategory::Hard
+        }
+    }
+}
+
+impl fmt::Display for HandCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            HandCategory::Hard => "Hard Totals",
+            HandCategory::Soft => "Soft Totals",
+            HandCategory::Pair => "Pairs",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// A starting hand against a dealer upcard.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct QuizQuestion {
+    pub hand: Hand,
+    pub upcard: Card,
+}
+
+impl QuizQuestion {
+    pub fn category(&self) -> HandCategory {
+        HandCategory::of(&self.hand)
+    }
+
+    /// A two card hand from a chart row against the column's upcard, none
+    /// for rows no two card hand is played from, like hard 20, always a
+    /// pair, or soft 21, a natural.
+    pub fn for_cell(hand: ChartHand, column: usize) -> Option<Self> {
+        let card = |value: u8, suit: Suit| Rank::ALL.into_iter().find(|rank| rank.value() == value).map(|rank| Card::new(rank, suit));
+        let (first, second) = match hand {
+            ChartHand::Hard(total) if (5..=19).contains(&total) => {
+                let first = total.saturating_sub(10).max(2);
+                (first, total - first)
+            }
+            ChartHand::Soft(total) if (13..=20).contains(&total) => (1, total - 11),
+            ChartHand::Pair(11) => (1, 1),
+            ChartHand::Pair(pair) => (pair, pair),
+            _ => return None,
+        };
+        let mut cards = Hand::new();
+        cards.add(card(first, Suit::Spades)?);
+        cards.add(card(second, Suit::Hearts)?);
+        let upcard = card(if column == 9 { 1 } else { column as u8 + 2 }, Suit::Clubs)?;
+        Some(Self { hand: cards, upcard })
+    }
+
+    /// The hand as its chart row is labelled, e.g. "A,7" whichever order
+    /// the cards came in.
+    fn row_label(&self) -> String {
+        match self.category() {
+            HandCategory::Pair => self.hand.chart_label(),
+            HandCategory::Soft => format!("A,{}", self.hand.total() - 11),
+            HandCategory::Hard => self.hand.total().to_string(),
+        }
+    }
+
+    /// The chart cell the hand is played from, labelled as on a flashcard,
+    /// e.g. "A,7 vs 6".
+    pub fn cell(&self) -> String {
+        format!("{} vs {}", self.row_label(), self.upcard.chart_label())
+    }
+
+    /// The chart cell the hand is played from, none when the chart has no
+    /// row for it.
+    pub fn flashcard(&self, strategy: &BlackjackStrategy) -> Option<Flashcard> {
+        let code = chart_code(strategy, &self.hand, self.upcard, true)?;
+        Some(Flashcard {
+            category: self.category(),
+            hand: self.row_label(),
+            total: self.hand.total(),
+            upcard: self.upcard.chart_label(),
+            column: self.upcard.rank.strategy_column(),
+            code: code.trim().to_string(),
+        })
+    }
+
+    /// What a fresh two card hand may do under the chart's rules.
+    pub fn available(&self, strategy: &BlackjackStrategy) -> Vec<PlayerAction> {
+        ACTIONS.into_iter()
+            .filter(|action| match action {
+                PlayerAction::Split => self.hand.is_pair(),
+                PlayerAction::Surrender => match strategy.rules.surrender_allowed {
+                    SurrenderRule::NotAllowed => false,
+                    SurrenderRule::AnyUpcard => true,
+                    SurrenderRule::Dealer2Through10 => !self.upcard.is_ace(),
+                },
+                _ => true,
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for QuizQuestion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} vs {}", self.hand.chart_label(), self.upcard.chart_label())
+    }
+}
+
+/// A play chosen in the quiz, with the one the chart calls for.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuizAnswer {
+    pub question: QuizQuestion,
+    pub answer: PlayerAction,
+    pub expected: PlayerAction,
+}
+
+impl QuizAnswer {
+    pub fn is_correct(&self) -> bool {
+        self.answer == self.expected
+    }
+}
+
+/// Deals starting hands against dealer upcards, no money involved, and
+/// scores each play against a strategy chart.
+pub struct StrategyQuiz {
+    strategy: BlackjackStrategy,
+
+    /// The hands dealt, every hand when it's empty
+    filter: DrillFilter,
+    shoe: Shoe,
+    question: QuizQuestion,
+    answers: Vec<QuizAnswer>,
+    length: usize,
+}
+
+impl StrategyQuiz {
+    pub fn new(strategy: BlackjackStrategy, length: usize, filter: DrillFilter) -> Self {
+        let mut shoe = Shoe::new(strategy.rules.decks, 0.75);
+        let question = Self::ask(&mut shoe, &strategy, &filter);
+        Self {
+            strategy,
+            filter,
+            shoe,
+            question,
+            answers: Vec::new(),
+            length,
+        }
+    }
+
+    /// Deals the next hand the filter allows, passing over blackjacks as
+    /// there's nothing to decide, and shuffling once the cut card is out.
+    fn ask(shoe: &mut Shoe, strategy: &BlackjackStrategy, filter: &DrillFilter) -> QuizQuestion {
+        let mut deals = 0;
+        loop {
+            if shoe.needs_shuffle() {
+                shoe.shuffle();
+            }
+            let (first, upcard, second) = (shoe.draw(), shoe.draw(), shoe.draw());
+            let mut hand = Hand::new();
+            hand.add(first);
+            hand.add(second);
+            if hand.is_natural() {
+                continue;
+            }
+            let question = QuizQuestion { hand, upcard };
+            deals += 1;
+            if deals >= MAX_DEALS || filter.allows_question(strategy, &question) {
+                return question;
+            }
+        }
+    }
+
+    pub fn question(&self) -> &QuizQuestion {
+        &self.question
+    }
+
+    pub fn available(&self) -> Vec<PlayerAction> {
+        self.question.available(&self.strategy)
+    }
+
+    /// Asks a hand of the caller's choosing in place of the one dealt.
+    pub fn ask_instead(&mut self, question: QuizQuestion) {
+        self.question = question;
+    }
+
+    /// Scores a play for the current hand and deals the next one.
+    pub fn answer(&mut self, action: PlayerAction) -> Result<&QuizAnswer, String> {
+        if self.is_finished() {
+            return Err("The quiz is over".to_string());
+        }
+        let available = self.available();
+        if !available.contains(&action) {
+            return Err(format!("Can't {} on {}", action.to_string().to_lowercase(), self.question));
+        }
+        let expected = recommend(&self.strategy, &self.question.hand, self.question.upcard, &available)
+            .ok_or_else(|| format!("The chart has no play for {}", self.question))?
+            .action;
+
+        let next = Self::ask(&mut self.shoe, &self.strategy, &self.filter);
+        let question = std::mem::replace(&mut self.question, next);
+        self.answers.push(QuizAnswer { question, answer: action, expected });
+        Ok(&self.answers[self.answers.len() - 1])
+    }
+
+    pub fn answers(&self) -> &[QuizAnswer] {
+        &self.answers
+    }
+
+    pub fn length(&self) -> usize {
+        self.length
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.answers.len() >= self.length
+    }
+
+    pub fn correct(&self) -> usize {
+        self.answers.iter().filter(|answer| answer.is_correct()).count()
+    }
+
+    /// Right and asked for each table of the chart that came up.
+    pub fn by_category(&self) -> Vec<(HandCategory, usize, usize)> {
+        self.tally(&HandCategory::ALL, |answer| answer.question.category())
+    }
+
+    /// Right and asked for each play the chart called for.
+    pub fn by_play(&self) -> Vec<(PlayerAction, usize, usize)> {
+        self.tally(&ACTIONS, |answer| answer.expected)
+    }
+
+    /// Right and asked for each key, leaving out keys never asked.
+    fn tally<K: Copy + PartialEq>(&self, keys: &[K], key: impl Fn(&QuizAnswer) -> K) -> Vec<(K, usize, usize)> {
+        keys.iter()
+            .map(|group| {
+                let answers: Vec<&QuizAnswer> = self.answers.iter().filter(|answer| key(answer) == *group).collect();
+                (*group, answers.iter().filter(|answer| answer.is_correct()).count(), answers.len())
+            })
+            .filter(|(_, _, asked)| *asked > 0)
+            .collect()
+    }
+}
+
+// ---- Flashcards ----
+/// One cell of a strategy chart.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Flashcard {
+    pub category: HandCategory,
+
+    /// The hand as it's read off the chart, e.g. "9,9", "A,7" or "16"
+    pub hand: String,
+
+    /// The hand's total, both cards counted for pairs
+    pub total: u8,
+
+    /// The dealer upcard, "A" for an Ace
+    pub upcard: String,
+
+    /// The upcard's column in the chart, from 0 for a 2 to 9 for an Ace
+    pub column: usize,
+
+    /// The chart's code for the cell, e.g. "Ds"
+    pub code: String,
+}
+
+impl Flashcard {
+    /// The chart row the card is from.
+    pub fn chart_hand(&self) -> ChartHand {
+        match self.category {
+            HandCategory::Hard => ChartHand::Hard(self.total),
+            HandCategory::Soft => ChartHand::Soft(self.total),
+            HandCategory::Pair if self.hand.starts_with('A') => ChartHand::Pair(11),
+            HandCategory::Pair => ChartHand::Pair(self.total / 2),
+        }
+    }
+}
+
+impl fmt::Display for Flashcard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} vs {}", self.hand, self.upcard)
+    }
+}
+
+/// Every cell of a chart the filter allows in a shuffled deck, a lighter way to study than
+/// the quiz: the play is revealed on request and the player grades
+/// themselves. Known cards go to the back of the deck, missed cards come
+/// back after a few others.
+pub struct FlashcardDeck {
+    cards: VecDeque<Flashcard>,
+    revealed: bool,
+    known: u32,
+    missed: u32,
+}
+
+impl FlashcardDeck {
+    pub fn new(strategy: &BlackjackStrategy, filter: &DrillFilter) -> Self {
+        let mut cards = chart_cells(strategy);
+        cards.retain(|card| filter.allows(card));
+        cards.shuffle(&mut rand::rng());
+        Self {
+            cards: cards.into(),
+            revealed: false,
+            known: 0,
+            missed: 0,
+        }
+    }
+
+    /// The card being studied, none when the chart had no cells.
+    pub fn current(&self) -> Option<&Flashcard> {
+        self.cards.front()
+    }
+
+    pub fn is_revealed(&self) -> bool {
+        self.revealed
+    }
+
+    pub fn reveal(&mut self) {
+        self.revealed = true;
+    }
+
+    /// Takes the player's word on whether they knew the revealed play, and
+    /// moves on to the next card.
+    pub fn grade(&mut self, knew: bool) -> Result<(), String> {
+        if !self.revealed {
+            return Err("Reveal the play before grading it".to_string());
+        }
+        let card = self.cards.pop_front().ok_or("There are no cards to study")?;
+        if knew {
+            self.known += 1;
+            self.cards.push_back(card);
+        } else {
+            self.missed += 1;
+            self.cards.insert(MISSED_GAP.min(self.cards.len()), card);
+        }
+        self.revealed = false;
+        Ok(())
+    }
+
+    pub fn len(&self) -> usize {
+        self.cards.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cards.is_empty()
+    }
+
+    pub fn known(&self) -> u32 {
+        self.known
+    }
+
+    pub fn missed(&self) -> u32 {
+        self.missed
+    }
+}
+
+/// Every cell of a chart, row by row.
+pub fn chart_cells(strategy: &BlackjackStrategy) -> Vec<Flashcard> {
+    let tables = &strategy.tables;
+    let rows = tables.hard_hands.iter()
+        .map(|row| (HandCategory::Hard, row.total.to_string(), row.total, &row.actions))
+        .chain(tables.soft_hands.iter().map(|row| (HandCategory::Soft, format!("A,{}", row.total - 11), row.total, &row.actions)))
+        .chain(tables.pair_hands.iter().map(|row| {
+            let card = if row.pair == 11 { "A".to_string() } else { row.pair.to_string() };
+            // A pair of Aces counts one as 1
+            let total = if row.pair == 11 { 12 } else { row.pair * 2 };
+            (HandCategory::Pair, format!("{},{}", card, card), total, &row.actions)
+        }));
+
+    let mut cells = Vec::new();
+    for (category, hand, total, actions) in rows {
+        for (column, code) in actions.iter().enumerate() {
+            let upcard = if column == 9 { "A".to_string() } else { (column + 2).to_string() };
+            cells.push(Flashcard { category, hand: hand.clone(), total, upcard, column, code: code.trim().to_string() });
+        }
+    }
+    cells
+}
+
+// ---- Drill Filters ----
+/// Kinds of hands a quiz can be limited to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HandClass {
+    Hard,
+    Soft,
+    Pairs,
+
+    /// Hard 12 through 16 against a 7 through an Ace
+    StiffVsHigh,
+
+    /// Cells the chart surrenders when it can
+    Surrender,
+}
+
+impl HandClass {
+    pub const ALL: [HandClass; 5] = [HandClass::Hard, HandClass::Soft, HandClass::Pairs, HandClass::StiffVsHigh, HandClass::Surrender];
+
+    pub fn contains(&self, cell: &Flashcard) -> bool {
+        match self {
+            HandClass::Hard => cell.category == HandCategory::Hard,
+            HandClass::Soft => cell.category == HandCategory::Soft,
+            HandClass::Pairs => cell.category == HandCategory::Pair,
+            // A 7 sits in the sixth column
+            HandClass::StiffVsHigh => cell.category == HandCategory::Hard && (12..=16).contains(&cell.total) && cell.column >= 5,
+            HandClass::Surrender => cell.code.starts_with('R'),
+        }
+    }
+}
+
+impl fmt::Display for HandClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            HandClass::Hard => "Hard Totals",
+            HandClass::Soft => "Soft Totals",
+            HandClass::Pairs => "Pairs",
+            HandClass::StiffVsHigh => "Stiff Hands vs 7-A",
+            HandClass::Surrender => "Surrender Decisions",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// The hand classes a quiz is limited to. Hands in any of them are asked,
+/// or every hand when none are picked.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DrillFilter {
+    classes: Vec<HandClass>,
+}
+
+impl DrillFilter {
+    pub fn is_empty(&self) -> bool {
+        self.classes.is_empty()
+    }
+
+    pub fn includes(&self, class: HandClass) -> bool {
+        self.classes.contains(&class)
+    }
+
+    /// Picks a class, or drops it when it's already picked.
+    pub fn toggle(&mut self, class: HandClass) {
+        if let Some(index) = self.classes.iter().position(|picked| *picked == class) {
+            self.classes.remove(index);
+        } else {
+            self.classes.push(class);
+        }
+    }
+
+    pub fn allows(&self, cell: &Flashcard) -> bool {
+        self.is_empty() || self.classes.iter().any(|class| class.contains(cell))
+    }
+
+    /// Whether a dealt hand is one to ask, going by its cell in the chart.
+    pub fn allows_question(&self, strategy: &BlackjackStrategy, question: &QuizQuestion) -> bool {
+        self.is_empty() || question.flashcard(strategy).is_some_and(|cell| self.allows(&cell))
+    }
+}
+
+impl fmt::Display for DrillFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return write!(f, "All Hands");
+        }
+        let names: Vec<String> = self.classes.iter().map(HandClass::to_string).collect();
+        write!(f, "{}", names.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logic::game::card::{Rank, Suit};
+
+    fn strategy() -> BlackjackStrategy {
+        BlackjackStrategy::from_file("resources/strategies/default-strategy.json").unwrap()
+    }
+
+    #[test]
+    fn test_quiz_scores_against_the_chart() {
+        let chart = strategy();
+        let mut quiz = StrategyQuiz::new(chart.clone(), 12, DrillFilter::default());
+
+        // Following the chart for half the quiz, then always standing
+        for _ in 0..6 {
+            let question = quiz.question().clone();
+            let expected = recommend(&chart, &question.hand, question.upcard, &quiz.available()).unwrap().action;
+            assert!(!question.hand.is_natural());
+            assert!(quiz.answer(expected).unwrap().is_correct());
+        }
+        for _ in 0..6 {
+            if !quiz.question().hand.is_pair() {
+                assert!(quiz.answer(PlayerAction::Split).is_err());
+            }
+            quiz.answer(PlayerAction::Stand).unwrap();
+        }
+        assert!(quiz.is_finished());
+        assert!(quiz.answer(PlayerAction::Stand).is_err());
+
+        let stands = quiz.answers()[6..].iter().filter(|answer| answer.expected == PlayerAction::Stand).count();
+        assert_eq!(quiz.correct(), 6 + stands);
+        let by_category = quiz.by_category();
+        assert_eq!(by_category.iter().map(|(_, right, _)| right).sum::<usize>(), quiz.correct());
+        assert_eq!(by_category.iter().map(|(_, _, asked)| asked).sum::<usize>(), 12);
+        assert_eq!(quiz.by_play().iter().map(|(_, _, asked)| asked).sum::<usize>(), 12);
+    }
+
+    #[test]
+    fn test_question_offers_split_and_surrender_by_the_rules() {
+        let card = |rank| Card::new(rank, Suit::Clubs);
+        let mut hand = Hand::new();
+        hand.add(card(Rank::Eight));
+        hand.add(card(Rank::Eight));
+        let question = QuizQuestion { hand, upcard: card(Rank::Ace) };
+        assert_eq!(question.category(), HandCategory::Pair);
+        assert_eq!(question.to_string(), "8,8 vs A");
+        assert_eq!(question.cell(), "8,8 vs A");
+
+        let mut hand = Hand::new();
+        hand.add(card(Rank::Seven));
+        hand.add(card(Rank::Ace));
+        assert_eq!(QuizQuestion { hand, upcard: card(Rank::King) }.cell(), "A,7 vs 10");
+
+        let mut strategy = strategy();
+        strategy.rules.surrender_allowed = SurrenderRule::Dealer2Through10;
+        assert_eq!(question.available(&strategy), [PlayerAction::Hit, PlayerAction::Stand, PlayerAction::Double, PlayerAction::Split]);
+        strategy.rules.surrender_allowed = SurrenderRule::AnyUpcard;
+        assert!(question.available(&strategy).contains(&PlayerAction::Surrender));
+    }
+
+    #[test]
+    fn test_flashcards_cover_the_chart_and_bring_misses_back() {
+        let chart = strategy();
+        let tables = &chart.tables;
+        let mut deck = FlashcardDeck::new(&chart, &DrillFilter::default());
+        let size = deck.len();
+        assert_eq!(size, (tables.hard_hands.len() + tables.soft_hands.len() + tables.pair_hands.len()) * 10);
+
+        // Going through the whole deck once, every cell reads off its row
+        let mut studied = Vec::new();
+        for _ in 0..size {
+            studied.push(deck.current().unwrap().clone());
+            deck.reveal();
+            deck.grade(true).unwrap();
+        }
+        assert_eq!(studied.iter().filter(|card| card.category == HandCategory::Pair).count(), tables.pair_hands.len() * 10);
+        let eights = studied.iter().find(|card| card.hand == "8,8" && card.upcard == "A").unwrap();
+        let row = tables.pair_hands.iter().find(|row| row.pair == 8).unwrap();
+        assert_eq!(eights.code, row.actions[9].trim());
+
+        // Every card can be asked as a hand from its cell, but for rows two
+        // cards only make as a pair or a natural
+        for card in &studied {
+            match QuizQuestion::for_cell(card.chart_hand(), card.column) {
+                Some(question) => assert_eq!(question.cell(), card.to_string()),
+                None => assert!(matches!(card.chart_hand(), ChartHand::Hard(20) | ChartHand::Hard(21) | ChartHand::Soft(21)), "{}", card),
+            }
+        }
+
+        // A missed card comes back after a few others
+        let card = deck.current().unwrap().clone();
+        assert!(deck.grade(false).is_err());
+        deck.reveal();
+        deck.grade(false).unwrap();
+        assert_eq!((deck.known(), deck.missed()), (size as u32, 1));
+        for _ in 0..MISSED_GAP {
+            assert_ne!(deck.current(), Some(&card));
+            deck.reveal();
+            deck.grade(true).unwrap();
+        }
+        assert_eq!(deck.current(), Some(&card));
+    }
+
+    #[test]
+    fn test_filters_limit_the_hands_asked() {
+        let chart = strategy();
+        let mut filter = DrillFilter::default();
+        filter.toggle(HandClass::Pairs);
+        filter.toggle(HandClass::StiffVsHigh);
+        assert_eq!(filter.to_string(), "Pairs, Stiff Hands vs 7-A");
+
+        let mut quiz = StrategyQuiz::new(chart.clone(), 30, filter.clone());
+        for _ in 0..30 {
+            let cell = quiz.question().flashcard(&chart).unwrap();
+            assert!(cell.category == HandCategory::Pair || (cell.category == HandCategory::Hard && cell.total >= 12 && cell.total <= 16));
+            assert!(cell.category == HandCategory::Pair || !["2", "3", "4", "5", "6"].contains(&cell.upcard.as_str()));
+            let action = quiz.available()[0];
+            quiz.answer(action).unwrap();
+        }
+
+        // Five stiff totals against five upcards, plus every pair cell
+        let deck = FlashcardDeck::new(&chart, &filter);
+        assert_eq!(deck.len(), 25 + chart.tables.pair_hands.len() * 10);
+
+        // Only charts for games with surrender have surrender decisions
+        filter.toggle(HandClass::Pairs);
+        filter.toggle(HandClass::StiffVsHigh);
+        filter.toggle(HandClass::Surrender);
+        assert!(FlashcardDeck::new(&chart, &filter).is_empty());
+        let chart = BlackjackStrategy::from_file("resources/strategies/strategy_6D_S17_DAS_AUC_P.json").unwrap();
+        let surrenders: Vec<Flashcard> = chart_cells(&chart).into_iter().filter(|cell| filter.allows(cell)).collect();
+        assert!(!surrenders.is_empty());
+        assert!(surrenders.iter().all(|cell| cell.code.starts_with('R')));
+        assert!(surrenders.iter().any(|cell| cell.to_string() == "16 vs 10"));
+    }
+}
@@ -0,0 +1,93 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// Right answers in a row in a drill.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct Streak {
+    /// Right answers since the last mistake
+    pub current: u32,
+    pub best: u32,
+}
+
+impl Streak {
+    /// Adds a right answer to the streak, or starts it over on a mistake.
+    pub fn record(&mut self, correct: bool) {
+        if correct {
+            self.current += 1;
+            self.best = self.best.max(self.current);
+        } else {
+            self.current = 0;
+        }
+    }
+
+    /// Whether the streak going is the best yet.
+    pub fn is_best(&self) -> bool {
+        self.current > 0 && self.current == self.best
+    }
+}
+
+/// The streak for each drill, saved between sessions so a streak carries
+/// on from one session to the next.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Streaks {
+    streaks: HashMap<String, Streak>,
+}
+
+impl Streaks {
+    /// Loads saved streaks, starting fresh if there are none or they cannot be read.
+    pub fn load(path: &str) -> Self {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(parent) = Path::new(path).parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// The drill's streak, nothing yet for drills never answered.
+    pub fn get(&self, drill: &str) -> Streak {
+        self.streaks.get(drill).copied().unwrap_or_default()
+    }
+
+    /// Records an answer in the drill, returning its streak.
+    pub fn record(&mut self, drill: &str, correct: bool) -> Streak {
+        let streak = self.streaks.entry(drill.to_string()).or_default();
+        streak.record(correct);
+        *streak
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_streaks_reset_on_mistakes_and_keep_the_best() {
+        let mut streaks = Streaks::default();
+        for correct in [true, true, true, false, true] {
+            streaks.record("Running Count", correct);
+        }
+        assert_eq!(streaks.get("Running Count"), Streak { current: 1, best: 3 });
+        assert!(!streaks.get("Running Count").is_best());
+        assert_eq!(streaks.get("True Count"), Streak::default());
+
+        // Catching up with the best streak carries it on
+        let streak = (0..3).map(|_| streaks.record("Running Count", true)).last().unwrap();
+        assert_eq!(streak, Streak { current: 4, best: 4 });
+        assert!(streak.is_best());
+
+        let path = std::env::temp_dir().join(format!("streaks-{}.json", uuid::Uuid::new_v4()));
+        let path = path.to_str().unwrap();
+        streaks.save(path).unwrap();
+        assert_eq!(Streaks::load(path), streaks);
+        fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,172 @@
+use crate::logic::game::advisor::recommend;
+use crate::logic::game::hand::Hand;
+use crate::logic::game::round::PlayerAction;
+use crate::logic::game::shoe::Shoe;
+use crate::logic::strategy_calculator_logic::{BlackjackStrategy, GameVariant, SurrenderRule};
+use crate::logic::strategy_quiz::{chart_cells, HandClass, QuizAnswer, QuizQuestion};
+use rand::Rng;
+
+/// Surrender rules the drill switches between
+pub const SURRENDER_RULES: [SurrenderRule; 2] = [SurrenderRule::AnyUpcard, SurrenderRule::Dealer2Through10];
+
+/// Decks in the shoe hands are dealt from
+const DRILL_DECKS: u8 = 6;
+
+/// A play in the drill, with the chart it was scored against.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SurrenderAnswer {
+    pub chart: String,
+    pub rule: SurrenderRule,
+    pub answer: QuizAnswer,
+}
+
+/// Deals only the hands some chart surrenders, each under the rules of a
+/// chart picked at random from those that allow surrender, so the same
+/// hand comes up where it's surrendered and where it's played out. Plays
+/// are scored against the chart the hand was dealt under.
+pub struct SurrenderDrill {
+    /// Charts that allow surrender, by name
+    charts: Vec<(String, BlackjackStrategy)>,
+
+    /// Cells any of the charts surrender, e.g. "16 vs 10"
+    cells: Vec<String>,
+    shoe: Shoe,
+
+    /// Position in `charts` of the chart the hand is dealt under
+    chart: usize,
+    question: QuizQuestion,
+    answers: Vec<SurrenderAnswer>,
+}
+
+impl SurrenderDrill {
+    /// A drill over the classic charts that allow surrender, failing when
+    /// none of them surrender anything.
+    pub fn new(strategies: &[(String, BlackjackStrategy)]) -> Result<Self, String> {
+        let charts: Vec<(String, BlackjackStrategy)> = strategies.iter()
+            .filter(|(_, strategy)| strategy.rules.variant == GameVariant::Classic && SURRENDER_RULES.contains(&strategy.rules.surrender_allowed))
+            .cloned()
+            .collect();
+        let mut cells: Vec<String> = charts.iter()
+            .flat_map(|(_, strategy)| chart_cells(strategy))
+            .filter(|cell| HandClass::Surrender.contains(cell))
+            .map(|cell| cell.to_string())
+            .collect();
+        cells.sort();
+        cells.dedup();
+        if cells.is_empty() {
+            return Err("None of the charts surrender any hands".to_string());
+        }
+
+        let mut shoe = Shoe::new(DRILL_DECKS, 0.75);
+        let question = Self::ask(&mut shoe, &cells);
+        let chart = rand::rng().random_range(0..charts.len());
+        Ok(Self { charts, cells, shoe, chart, question, answers: Vec::new() })
+    }
+
+    /// Deals until a hand lands in one of the cells, shuffling once the
+    /// cut card is out.
+    fn ask(shoe: &mut Shoe, cells: &[String]) -> QuizQuestion {
+        loop {
+            if shoe.needs_shuffle() {
+                shoe.shuffle();
+            }
+            let (first, upcard, second) = (shoe.draw(), shoe.draw(), shoe.draw());
+            let mut hand = Hand::new();
+            hand.add(first);
+            hand.add(second);
+            let question = QuizQuestion { hand, upcard };
+            if cells.contains(&question.cell()) {
+                return question;
+            }
+        }
+    }
+
+    /// The chart the hand is dealt under, with its name.
+    pub fn chart(&self) -> (&str, &BlackjackStrategy) {
+        let (name, strategy) = &self.charts[self.chart];
+        (name, strategy)
+    }
+
+    pub fn question(&self) -> &QuizQuestion {
+        &self.question
+    }
+
+    pub fn available(&self) -> Vec<PlayerAction> {
+        self.question.available(self.chart().1)
+    }
+
+    /// Scores a play for the current hand under its chart, then deals the
+    /// next hand under a chart picked afresh.
+    pub fn answer(&mut self, action: PlayerAction) -> Result<&SurrenderAnswer, String> {
+        let available = self.available();
+        if !available.contains(&action) {
+            return Err(format!("Can't {} on {}", action.to_string().to_lowercase(), self.question));
+        }
+        let (chart, strategy) = self.chart();
+        let expected = recommend(strategy, &self.question.hand, self.question.upcard, &available)
+            .ok_or_else(|| format!("The chart has no play for {}", self.question))?
+            .action;
+        let (chart, rule) = (chart.to_string(), strategy.rules.surrender_allowed);
+
+        let next = Self::ask(&mut self.shoe, &self.cells);
+        let question = std::mem::replace(&mut self.question, next);
+        self.chart = rand::rng().random_range(0..self.charts.len());
+        self.answers.push(SurrenderAnswer { chart, rule, answer: QuizAnswer { question, answer: action, expected } });
+        Ok(&self.answers[self.answers.len() - 1])
+    }
+
+    pub fn answers(&self) -> &[SurrenderAnswer] {
+        &self.answers
+    }
+
+    pub fn correct(&self) -> usize {
+        self.answers.iter().filter(|answer| answer.answer.is_correct()).count()
+    }
+
+    /// Right and asked under each surrender rule, leaving out rules never asked.
+    pub fn by_rule(&self) -> Vec<(SurrenderRule, usize, usize)> {
+        SURRENDER_RULES.iter()
+            .map(|rule| {
+                let answers: Vec<&SurrenderAnswer> = self.answers.iter().filter(|answer| answer.rule == *rule).collect();
+                (*rule, answers.iter().filter(|answer| answer.answer.is_correct()).count(), answers.len())
+            })
+            .filter(|(_, _, asked)| *asked > 0)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logic::strategy_calculator_logic::load_strategies;
+
+    #[test]
+    fn test_only_hands_some_chart_surrenders_are_dealt() {
+        let mut strategies: Vec<(String, BlackjackStrategy)> = load_strategies("resources/strategies").into_iter().collect();
+        strategies.sort_by(|a, b| a.0.cmp(&b.0));
+        let mut drill = SurrenderDrill::new(&strategies).unwrap();
+        assert!(drill.cells.contains(&"16 vs 10".to_string()));
+        assert!(!drill.cells.contains(&"12 vs 10".to_string()));
+
+        for _ in 0..200 {
+            let (_, strategy) = drill.chart();
+            assert_ne!(strategy.rules.surrender_allowed, SurrenderRule::NotAllowed);
+            assert_eq!(strategy.rules.variant, GameVariant::Classic);
+            let question = drill.question().clone();
+            assert!(drill.cells.contains(&question.cell()));
+            // Surrender is only offered against an Ace when any upcard may be surrendered
+            let offered = drill.available().contains(&PlayerAction::Surrender);
+            assert_eq!(offered, !question.upcard.is_ace() || strategy.rules.surrender_allowed == SurrenderRule::AnyUpcard);
+            let action = if offered { PlayerAction::Surrender } else { PlayerAction::Hit };
+            assert_eq!(drill.answer(action).unwrap().answer.question, question);
+        }
+        let asked: usize = drill.by_rule().iter().map(|(_, _, asked)| asked).sum();
+        assert_eq!((asked, drill.answers().len()), (200, 200));
+
+        // Without a chart that surrenders there's nothing to drill
+        let no_surrender: Vec<(String, BlackjackStrategy)> = strategies.into_iter()
+            .filter(|(_, strategy)| strategy.rules.surrender_allowed == SurrenderRule::NotAllowed)
+            .collect();
+        assert!(SurrenderDrill::new(&no_surrender).is_err());
+    }
+}
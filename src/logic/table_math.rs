@@ -0,0 +1,304 @@
+use crate::logic::game::hand::Hand;
+use crate::logic::game::shoe::Shoe;
+use rand::Rng;
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::time::Duration;
+
+/// How long a hand is shown for, slowest first
+pub const FLASH_TIMES: [Duration; 5] = [
+    Duration::from_millis(2000),
+    Duration::from_millis(1500),
+    Duration::from_millis(1000),
+    Duration::from_millis(700),
+    Duration::from_millis(400),
+];
+pub const DEFAULT_FLASH_TIME: usize = 1;
+
+/// Time to call the total once the hand is hidden, after which it's late
+pub const TOTAL_ANSWER_TIME: Duration = Duration::from_secs(5);
+
+/// Cards in the dealer hands flashed
+const DEALER_HAND_SIZES: RangeInclusive<usize> = 2..=5;
+
+/// Cards in the player hands flashed, fewer when they bust first
+const PLAYER_HAND_SIZES: RangeInclusive<usize> = 3..=5;
+
+/// Decks in the shoe hands are dealt from
+const DRILL_DECKS: u8 = 6;
+
+/// The table math drills.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MathDrill {
+    /// Totalling dealer hands played out to a stand or a bust
+    DealerTotals,
+    /// Naming player hands soft or hard along with their totals
+    HandTotals,
+}
+
+impl MathDrill {
+    pub fn next(self) -> Self {
+        match self {
+            MathDrill::DealerTotals => MathDrill::HandTotals,
+            MathDrill::HandTotals => MathDrill::DealerTotals,
+        }
+    }
+
+    /// What the drill says when it's started.
+    pub fn instructions(self) -> &'static str {
+        match self {
+            MathDrill::DealerTotals => "Press Space to flash a dealer hand",
+            MathDrill::HandTotals => "Press Space to flash a player hand",
+        }
+    }
+}
+
+impl fmt::Display for MathDrill {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MathDrill::DealerTotals => write!(f, "Dealer Totals"),
+            MathDrill::HandTotals => write!(f, "Hand Totals"),
+        }
+    }
+}
+
+/// What a hand adds up to, or that it's gone over 21. Soft totals are only
+/// called out as soft where the drill asks for it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TotalCall {
+    Total(u8),
+    Soft(u8),
+    Bust,
+}
+
+impl TotalCall {
+    pub fn of(hand: &Hand) -> Self {
+        if hand.is_bust() {
+            TotalCall::Bust
+        } else {
+            TotalCall::Total(hand.total())
+        }
+    }
+
+    /// The hand's total, named soft when an ace is still counting 11.
+    pub fn naming_soft(hand: &Hand) -> Self {
+        match TotalCall::of(hand) {
+            TotalCall::Total(total) if hand.is_soft() => TotalCall::Soft(total),
+            call => call,
+        }
+    }
+}
+
+impl fmt::Display for TotalCall {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TotalCall::Total(total) => write!(f, "{}", total),
+            TotalCall::Soft(total) => write!(f, "Soft {}", total),
+            TotalCall::Bust => write!(f, "Bust"),
+        }
+    }
+}
+
+/// A total called for a hand, with the right one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TotalAttempt {
+    pub hand: Hand,
+    pub expected: TotalCall,
+
+    /// None when the time to answer ran out
+    pub answer: Option<TotalCall>,
+    pub time: Duration,
+}
+
+impl TotalAttempt {
+    pub fn is_correct(&self) -> bool {
+        self.answer == Some(self.expected)
+    }
+}
+
+/// The cards of a hand as they're read off a chart, e.g. "A,6,10".
+pub fn describe_cards(hand: &Hand) -> String {
+    hand.cards().iter().map(|card| card.chart_label()).collect::<Vec<String>>().join(",")
+}
+
+/// Flashes hands and asks for each total or whether it's a bust, to build
+/// up the arithmetic needed at table speed. Dealer hands of two to five
+/// cards are drawn the way the house plays them. Player hands of three to
+/// five cards always hold an ace, and are named soft or hard as well, since
+/// soft totals are the ones most often miscounted.
+pub struct TotalDrill {
+    drill: MathDrill,
+    shoe: Shoe,
+    hits_soft_17: bool,
+    hand: Hand,
+    attempts: Vec<TotalAttempt>,
+}
+
+impl TotalDrill {
+    pub fn new(drill: MathDrill, hits_soft_17: bool) -> Self {
+        let mut shoe = Shoe::new(DRILL_DECKS, 0.75);
+        let hand = Self::deal(drill, &mut shoe, hits_soft_17);
+        Self { drill, shoe, hits_soft_17, hand, attempts: Vec::new() }
+    }
+
+    fn deal(drill: MathDrill, shoe: &mut Shoe, hits_soft_17: bool) -> Hand {
+        match drill {
+            MathDrill::DealerTotals => Self::deal_dealer(shoe, hits_soft_17),
+            MathDrill::HandTotals => Self::deal_player(shoe),
+        }
+    }
+
+    /// Draws for the dealer until the house rules say stand, dealing again
+    /// when that takes more cards than the drill flashes.
+    fn deal_dealer(shoe: &mut Shoe, hits_soft_17: bool) -> Hand {
+        loop {
+            if shoe.needs_shuffle() {
+                shoe.shuffle();
+            }
+            let mut hand = Hand::new();
+            hand.add(shoe.draw());
+            hand.add(shoe.draw());
+            while hand.total() < 17 || (hits_soft_17 && hand.total() == 17 && hand.is_soft()) {
+                hand.add(shoe.draw());
+            }
+            if DEALER_HAND_SIZES.contains(&hand.len()) {
+                return hand;
+            }
+        }
+    }
+
+    /// Hits a player hand up to a random number of cards, or until it
+    /// busts, dealing again until a hand holds an ace.
+    fn deal_player(shoe: &mut Shoe) -> Hand {
+        let size = rand::rng().random_range(PLAYER_HAND_SIZES);
+        loop {
+            if shoe.needs_shuffle() {
+                shoe.shuffle();
+            }
+            let mut hand = Hand::new();
+            while hand.len() < size && !hand.is_bust() {
+                hand.add(shoe.draw());
+            }
+            if hand.cards().iter().any(|card| card.is_ace()) {
+                return hand;
+            }
+        }
+    }
+
+    /// The hand being flashed.
+    pub fn hand(&self) -> &Hand {
+        &self.hand
+    }
+
+    pub fn hits_soft_17(&self) -> bool {
+        self.hits_soft_17
+    }
+
+    /// Changes how the dealer plays soft 17, from the next hand.
+    pub fn set_hits_soft_17(&mut self, hits_soft_17: bool) {
+        self.hits_soft_17 = hits_soft_17;
+        self.hand = Self::deal(self.drill, &mut self.shoe, hits_soft_17);
+    }
+
+    /// The call the drill wants for a hand.
+    pub fn expected(&self, hand: &Hand) -> TotalCall {
+        match self.drill {
+            MathDrill::DealerTotals => TotalCall::of(hand),
+            MathDrill::HandTotals => TotalCall::naming_soft(hand),
+        }
+    }
+
+    /// Scores the total called for the hand, or none when time ran out,
+    /// taking `time` to answer, and deals the next. Answers given after
+    /// `TOTAL_ANSWER_TIME` count as late.
+    pub fn answer(&mut self, answer: Option<TotalCall>, time: Duration) -> &TotalAttempt {
+        let next = Self::deal(self.drill, &mut self.shoe, self.hits_soft_17);
+        let hand = std::mem::replace(&mut self.hand, next);
+        self.attempts.push(TotalAttempt {
+            expected: self.expected(&hand),
+            hand,
+            answer: answer.filter(|_| time <= TOTAL_ANSWER_TIME),
+            time: time.min(TOTAL_ANSWER_TIME),
+        });
+        &self.attempts[self.attempts.len() - 1]
+    }
+
+    pub fn attempts(&self) -> &[TotalAttempt] {
+        &self.attempts
+    }
+
+    /// Fraction of the totals called that were right.
+    pub fn accuracy(&self) -> f64 {
+        if self.attempts.is_empty() {
+            return 0.0;
+        }
+        self.attempts.iter().filter(|attempt| attempt.is_correct()).count() as f64 / self.attempts.len() as f64
+    }
+
+    /// How long answers took on average.
+    pub fn average_time(&self) -> Duration {
+        if self.attempts.is_empty() {
+            return Duration::ZERO;
+        }
+        self.attempts.iter().map(|attempt| attempt.time).sum::<Duration>() / self.attempts.len() as u32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logic::game::card::{Card, Rank, Suit};
+
+    #[test]
+    fn test_dealer_hands_are_played_out_and_scored() {
+        let mut drill = TotalDrill::new(MathDrill::DealerTotals, true);
+        for _ in 0..200 {
+            let hand = drill.hand().clone();
+            assert!(DEALER_HAND_SIZES.contains(&hand.len()));
+            // Hitting soft 17, the dealer stands on hard 17 or soft 18 and up
+            assert!(hand.total() >= 17 && !(hand.total() == 17 && hand.is_soft()));
+            let attempt = drill.answer(Some(TotalCall::of(&hand)), Duration::from_secs(1));
+            assert!(attempt.is_correct());
+        }
+
+        let hand = drill.hand().clone();
+        assert!(!drill.answer(Some(TotalCall::Total(hand.total() + 1)), Duration::from_secs(1)).is_correct());
+        // Right but too late
+        let right = TotalCall::of(drill.hand());
+        let late = drill.answer(Some(right), TOTAL_ANSWER_TIME * 2).clone();
+        assert_eq!((late.answer, late.time), (None, TOTAL_ANSWER_TIME));
+        assert!((drill.accuracy() - 200.0 / 202.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_total_call_of_a_hand() {
+        let mut hand = Hand::new();
+        for rank in [Rank::Ace, Rank::Six, Rank::Ten] {
+            hand.add(Card::new(rank, Suit::Hearts));
+        }
+        assert_eq!((TotalCall::of(&hand), describe_cards(&hand)), (TotalCall::Total(17), "A,6,10".to_string()));
+        hand.add(Card::new(Rank::Nine, Suit::Clubs));
+        assert_eq!(TotalCall::of(&hand).to_string(), "Bust");
+    }
+
+    #[test]
+    fn test_player_hands_are_named_soft_or_hard() {
+        let mut hand = Hand::new();
+        for rank in [Rank::Ace, Rank::Three, Rank::Four, Rank::Ace] {
+            hand.add(Card::new(rank, Suit::Spades));
+        }
+        assert_eq!(TotalCall::naming_soft(&hand), TotalCall::Soft(19));
+        hand.add(Card::new(Rank::Five, Suit::Spades));
+        assert_eq!(TotalCall::naming_soft(&hand).to_string(), "14");
+
+        let mut drill = TotalDrill::new(MathDrill::HandTotals, false);
+        for _ in 0..200 {
+            let hand = drill.hand().clone();
+            assert!(hand.cards().iter().any(|card| card.is_ace()));
+            assert!(PLAYER_HAND_SIZES.contains(&hand.len()));
+            // A soft total called without naming it soft is wrong
+            let attempt = drill.answer(Some(TotalCall::of(&hand)), Duration::from_secs(1));
+            assert_eq!(attempt.is_correct(), !hand.is_soft() || hand.is_bust());
+        }
+    }
+}
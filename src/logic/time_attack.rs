@@ -0,0 +1,234 @@
+use crate::logic::counting_stats::SECONDS_PER_DAY;
+use crate::logic::game::round::PlayerAction;
+use crate::logic::strategy_calculator_logic::BlackjackStrategy;
+use crate::logic::strategy_quiz::{DrillFilter, QuizAnswer, QuizQuestion, StrategyQuiz};
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+/// Lengths of a time attack to choose from, in seconds
+pub const TIME_LIMITS: [u64; 2] = [60, 120];
+pub const DEFAULT_TIME_LIMIT: usize = 0;
+
+/// High scores kept for each time limit
+pub const HIGH_SCORES_KEPT: usize = 10;
+
+/// Points for a right answer, however long it took
+const RIGHT_POINTS: i32 = 100;
+
+/// Most points a right answer can add for speed, all of them when it comes
+/// within `QUICK_ANSWER` and fewer the longer it takes after that, down to
+/// none at `SLOW_ANSWER`
+const SPEED_POINTS: i32 = 100;
+const QUICK_ANSWER: Duration = Duration::from_secs(1);
+const SLOW_ANSWER: Duration = Duration::from_secs(5);
+
+/// Points taken off for a wrong answer
+const WRONG_POINTS: i32 = 50;
+
+/// Points an answer is worth, right ones earning more the quicker they came.
+pub fn points(correct: bool, time: Duration) -> i32 {
+    if !correct {
+        return -WRONG_POINTS;
+    }
+    let slowness = time.saturating_sub(QUICK_ANSWER).as_secs_f64() / (SLOW_ANSWER - QUICK_ANSWER).as_secs_f64();
+    RIGHT_POINTS + (SPEED_POINTS as f64 * (1.0 - slowness.min(1.0))).round() as i32
+}
+
+/// As many strategy decisions as can be made against the clock, scored for
+/// both speed and accuracy. The clock is kept by the caller.
+pub struct TimeAttack {
+    quiz: StrategyQuiz,
+    seconds: u64,
+    score: u32,
+}
+
+impl TimeAttack {
+    pub fn new(strategy: BlackjackStrategy, seconds: u64, filter: DrillFilter) -> Self {
+        Self {
+            quiz: StrategyQuiz::new(strategy, usize::MAX, filter),
+            seconds,
+            score: 0,
+        }
+    }
+
+    /// How long the run lasts, in seconds.
+    pub fn seconds(&self) -> u64 {
+        self.seconds
+    }
+
+    pub fn question(&self) -> &QuizQuestion {
+        self.quiz.question()
+    }
+
+    pub fn available(&self) -> Vec<PlayerAction> {
+        self.quiz.available()
+    }
+
+    /// Scores a play made `time` after the hand was dealt, returning the
+    /// answer and the points it was worth. The score never drops below zero.
+    pub fn answer(&mut self, action: PlayerAction, time: Duration) -> Result<(QuizAnswer, i32), String> {
+        let answer = self.quiz.answer(action)?.clone();
+        let points = points(answer.is_correct(), time);
+        self.score = self.score.saturating_add_signed(points);
+        Ok((answer, points))
+    }
+
+    pub fn score(&self) -> u32 {
+        self.score
+    }
+
+    pub fn answered(&self) -> usize {
+        self.quiz.answers().len()
+    }
+
+    pub fn correct(&self) -> usize {
+        self.quiz.correct()
+    }
+
+    /// How the run went, as kept in the high score table.
+    pub fn finish(&self, chart: &str, at: u64) -> HighScore {
+        HighScore {
+            score: self.score,
+            seconds: self.seconds,
+            answered: self.answered(),
+            correct: self.correct(),
+            chart: chart.to_string(),
+            at,
+        }
+    }
+}
+
+// ---- High Scores ----
+/// A finished time attack, as kept between runs.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct HighScore {
+    pub score: u32,
+
+    /// Length of the run, in seconds
+    pub seconds: u64,
+    pub answered: usize,
+    pub correct: usize,
+
+    /// Chart the answers were scored against
+    pub chart: String,
+
+    /// When the run finished, in seconds since the Unix epoch
+    pub at: u64,
+}
+
+impl HighScore {
+    /// Day the run finished on, in days since the Unix epoch (UTC).
+    pub fn day(&self) -> u64 {
+        self.at / SECONDS_PER_DAY
+    }
+
+    /// Share of answers that were right, none when nothing was answered.
+    pub fn accuracy(&self) -> Option<f64> {
+        (self.answered > 0).then(|| self.correct as f64 / self.answered as f64)
+    }
+}
+
+/// The best time attack runs for each time limit, saved between runs.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+pub struct HighScores {
+    scores: Vec<HighScore>,
+}
+
+impl HighScores {
+    /// Loads saved high scores, starting fresh if there are none or they cannot be read.
+    pub fn load(path: &str) -> Self {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(parent) = Path::new(path).parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// The best runs of a length, highest score first, earlier runs ahead
+    /// on a tie.
+    pub fn table(&self, seconds: u64) -> Vec<&HighScore> {
+        let mut table: Vec<&HighScore> = self.scores.iter().filter(|score| score.seconds == seconds).collect();
+        table.sort_by_key(|score| Reverse(score.score));
+        table.truncate(HIGH_SCORES_KEPT);
+        table
+    }
+
+    /// Adds a run to its table, returning where it placed from zero, or
+    /// none when it didn't make the table.
+    pub fn record(&mut self, high_score: HighScore) -> Option<usize> {
+        let seconds = high_score.seconds;
+        let rank = self.table(seconds).iter().take_while(|kept| kept.score >= high_score.score).count();
+        if rank >= HIGH_SCORES_KEPT {
+            return None;
+        }
+        self.scores.push(high_score);
+        // Drop whatever was pushed off the bottom of the table
+        let table: Vec<HighScore> = self.table(seconds).into_iter().cloned().collect();
+        self.scores.retain(|score| score.seconds != seconds);
+        self.scores.extend(table);
+        Some(rank)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn high_score(score: u32, seconds: u64, at: u64) -> HighScore {
+        HighScore { score, seconds, answered: 10, correct: 8, chart: "default".to_string(), at }
+    }
+
+    #[test]
+    fn test_quick_right_answers_score_most() {
+        assert_eq!(points(true, Duration::from_millis(800)), 200);
+        assert_eq!(points(true, Duration::from_secs(3)), 150);
+        assert_eq!(points(true, Duration::from_secs(9)), 100);
+        assert_eq!(points(false, Duration::from_millis(500)), -50);
+
+        let strategy = BlackjackStrategy::from_file("resources/strategies/default-strategy.json").unwrap();
+        let mut run = TimeAttack::new(strategy, TIME_LIMITS[0], DrillFilter::default());
+        let action = run.available()[0];
+        let (answer, earned) = run.answer(action, Duration::from_secs(2)).unwrap();
+        assert_eq!(earned, points(answer.is_correct(), Duration::from_secs(2)));
+        assert_eq!(run.score(), earned.max(0) as u32);
+
+        let finished = run.finish("default", 1_000);
+        assert_eq!((finished.answered, finished.seconds), (1, 60));
+        assert_eq!(finished.accuracy(), Some(run.correct() as f64));
+    }
+
+    #[test]
+    fn test_high_scores_keep_the_best_of_each_length() {
+        let mut scores = HighScores::default();
+        for at in 0..HIGH_SCORES_KEPT as u64 {
+            assert_eq!(scores.record(high_score(1_000 + at as u32 * 100, 60, at)), Some(0));
+        }
+        assert_eq!(scores.record(high_score(500, 60, 20)), None);
+        // Ties place behind the earlier run, and the lowest drops off
+        assert_eq!(scores.record(high_score(1_500, 60, 21)), Some(5));
+        let table = scores.table(60);
+        assert_eq!(table.len(), HIGH_SCORES_KEPT);
+        assert_eq!((table[4].at, table[5].at), (5, 21));
+        assert_eq!(table[HIGH_SCORES_KEPT - 1].score, 1_100);
+
+        // Each length has a table of its own
+        assert_eq!(scores.record(high_score(500, 120, 22)), Some(0));
+        assert_eq!(scores.table(120).len(), 1);
+
+        let path = std::env::temp_dir().join(format!("high-scores-{}.json", uuid::Uuid::new_v4()));
+        let path = path.to_str().unwrap();
+        scores.save(path).unwrap();
+        assert_eq!(HighScores::load(path), scores);
+        fs::remove_file(path).unwrap();
+    }
+}
@@ -1,29 +1,93 @@
 mod app;
+mod cli;
+mod clipboard;
 mod menu;
 mod ui;
 mod model;
 mod about;
 mod constants;
 mod strategy_calculator;
+mod count_trainer;
+mod strategy_quiz;
+mod table_math;
+mod simulation;
+mod comps;
+mod statistics;
+mod settings;
 mod logic;
+mod game;
+mod hand_history;
+mod hotseat;
+mod lan;
+mod widgets;
 
 use crate::app::App;
 use color_eyre::Result;
 use ratatui::backend::{Backend, CrosstermBackend};
+use ratatui::crossterm::event;
 use ratatui::crossterm::event::{DisableMouseCapture, EnableMouseCapture};
 use ratatui::crossterm::execute;
 use ratatui::crossterm::terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen};
 use ratatui::{Terminal};
 use std::error::Error;
 use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 use crate::about::about_us_screen::AboutUsScreen;
+use crate::count_trainer::count_trainer_screen::CountTrainerScreen;
+use crate::game::game_screen::GameScreen;
+use crate::hand_history::hand_history_screen::HandHistoryScreen;
+use crate::hotseat::hotseat_screen::HotseatScreen;
+use crate::lan::lan_lobby_screen::LanLobbyScreen;
 use crate::menu::menu_screen::MenuScreen;
 use crate::model::{Model, ModelResponse};
 use crate::strategy_calculator::strategy_calculator_screen::StrategyCalculatorScreen;
+use crate::strategy_quiz::strategy_quiz_screen::StrategyQuizScreen;
+use crate::table_math::table_math_screen::TableMathScreen;
+use crate::simulation::risk_of_ruin_screen::RiskOfRuinScreen;
+use crate::simulation::bet_ramp_screen::BetRampScreen;
+use crate::comps::comps_screen::CompsScreen;
+use crate::constants::{BUNDLED_DEVIATIONS, BUNDLED_STRATEGIES, DECISION_ACCURACY_FILE, HISTORY_DATABASE_FILE};
+use crate::logic::app_dirs::{data_dir, data_file, default_strategies_dir, deviations_dir, seed_bundled_dir, seed_dir};
+use crate::logic::decision_accuracy::DecisionAccuracy;
+use crate::logic::history_store::HistoryStore;
+use crate::logic::config::{self, config_path, Config, StartupScreen};
+use crate::statistics::statistics_screen::StatisticsScreen;
+use crate::settings::settings_screen::SettingsScreen;
+use crate::simulation::simulation_screen::SimulationScreen;
+
+/// How long to wait for input before ticking the screen
+const TICK_RATE: Duration = Duration::from_millis(50);
 
 fn main() -> Result<(), Box<dyn Error>> {
     color_eyre::install()?;
 
+    // A config that can't be read is reported rather than quietly replaced by the defaults
+    match Config::load(&config_path()) {
+        Ok(loaded) => config::set_current(loaded),
+        Err(err) => {
+            eprintln!("{err}");
+            std::process::exit(1);
+        }
+    }
+
+    if let Err(err) = seed_directories() {
+        eprintln!("Could not set up {}: {err}", data_dir().display());
+    }
+    if let Err(err) = import_decision_accuracy() {
+        eprintln!("Could not move the decision accuracy into the play history: {err}");
+    }
+
+    // Commands like exports run without starting the game
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if !args.is_empty() {
+        if let Err(err) = cli::run(&args) {
+            eprintln!("{err}");
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
     // Setup Terminal
     let mut terminal = setup_terminal()?;
 
@@ -40,32 +104,126 @@ fn main() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
-fn run_app<B: Backend>(terminal: &mut Terminal<B>, _app: &mut App) -> io::Result<()> {
+/// Fills the data directory the first time it's made: anything an older
+/// version saved to `data` beside the app, or in the directory it was run
+/// from, comes along. The charts built into the app go into the strategies
+/// directory unless the config names another, and the index plays into
+/// the deviations directory.
+fn seed_directories() -> io::Result<()> {
+    let beside_app = std::env::current_exe().ok().and_then(|exe| exe.parent().map(|dir| dir.join("data")));
+    for saved in beside_app.into_iter().chain([PathBuf::from("data")]) {
+        seed_dir(&saved, &data_dir())?;
+    }
+    if config::strategies_dir() == default_strategies_dir() {
+        seed_bundled_dir(&BUNDLED_STRATEGIES, Path::new(&default_strategies_dir()))?;
+    }
+    seed_bundled_dir(&BUNDLED_DEVIATIONS, Path::new(&deviations_dir()))?;
+    Ok(())
+}
+
+/// Chart cell tallies saved to their own file before the play history kept
+/// them are added to it once, and the file is removed.
+fn import_decision_accuracy() -> Result<(), Box<dyn Error>> {
+    let path = data_file(DECISION_ACCURACY_FILE);
+    if !Path::new(&path).is_file() {
+        return Ok(());
+    }
+    let mut store = HistoryStore::open(&data_file(HISTORY_DATABASE_FILE), "Statistics")?;
+    store.add_cells(&DecisionAccuracy::load(&path))?;
+    std::fs::remove_file(&path)?;
+    Ok(())
+}
+
+fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> io::Result<()> {
     let mut screen: Box<dyn Model> = Box::new(MenuScreen::new());
+    // The startup screen opens as if it had been picked from the menu
+    let mut response = Ok(startup_response(config::current().startup_screen));
     loop {
-        terminal.draw(|f| screen.ui(f))?;
-
-        loop {
-            // Nested loop prevents rerender of UI when not necessary
-            let response = screen.update();
-            match response {
-                Ok(ModelResponse::Refresh) => break, // break into parent look to rerender
-                Ok(ModelResponse::Exit) => return Ok(()),
-                Ok(ModelResponse::NavToMainMenu) => {
-                    screen = Box::new(MenuScreen::new());
-                    break;
-                }
-                Ok(ModelResponse::NavToStrategyCalculator) => {
-                    screen = Box::new(StrategyCalculatorScreen::new());
-                    break;
-                }
-                Ok(ModelResponse::NavToAboutUs) => {
-                    screen = Box::new(AboutUsScreen::new());
-                    break;
-                }
-                _ => break,
+        match response {
+            Ok(ModelResponse::Refresh) => {} // fall through to rerender
+            Ok(ModelResponse::Exit) => return Ok(()),
+            Ok(ModelResponse::NavToMainMenu) => {
+                screen = Box::new(MenuScreen::new());
+            }
+            Ok(ModelResponse::NavToGame) => {
+                // Pick the game back up if it was only set aside
+                screen = match app.suspended_game.take() {
+                    Some(game) => game,
+                    None => Box::new(GameScreen::new(app.hand_history.clone(), app.palette.clone())),
+                };
             }
+            Ok(ModelResponse::NavToHotseat) => {
+                screen = Box::new(HotseatScreen::new());
+            }
+            Ok(ModelResponse::NavToDealer) => {
+                screen = Box::new(HotseatScreen::dealer());
+            }
+            Ok(ModelResponse::NavToLan) => {
+                screen = Box::new(LanLobbyScreen::new());
+            }
+            Ok(ModelResponse::NavToHandHistory) => {
+                let history = Box::new(HandHistoryScreen::new(app.hand_history.clone(), app.palette.clone()));
+                app.suspended_game = Some(std::mem::replace(&mut screen, history));
+            }
+            Ok(ModelResponse::NavToStrategyCalculator) => {
+                screen = Box::new(StrategyCalculatorScreen::new(app.palette.clone()));
+            }
+            Ok(ModelResponse::NavToCountTrainer) => {
+                screen = Box::new(CountTrainerScreen::new());
+            }
+            Ok(ModelResponse::NavToStrategyQuiz) => {
+                screen = Box::new(StrategyQuizScreen::new(app.palette.clone()));
+            }
+            Ok(ModelResponse::NavToTableMath) => {
+                screen = Box::new(TableMathScreen::new());
+            }
+            Ok(ModelResponse::NavToSimulation) => {
+                screen = Box::new(SimulationScreen::new());
+            }
+            Ok(ModelResponse::NavToRiskOfRuin) => {
+                screen = Box::new(RiskOfRuinScreen::new());
+            }
+            Ok(ModelResponse::NavToBetRamp) => {
+                screen = Box::new(BetRampScreen::new());
+            }
+            Ok(ModelResponse::NavToComps) => {
+                screen = Box::new(CompsScreen::new());
+            }
+            Ok(ModelResponse::NavToStatistics) => {
+                screen = Box::new(StatisticsScreen::new());
+            }
+            Ok(ModelResponse::NavToSettings) => {
+                screen = Box::new(SettingsScreen::new(app.palette.clone()));
+            }
+            Ok(ModelResponse::NavToAboutUs) => {
+                screen = Box::new(AboutUsScreen::new());
+            }
+            Err(err) => return Err(err),
         }
+
+        terminal.draw(|f| screen.ui(f))?;
+
+        // Screens redraw on input, and on every tick so they can animate
+        response = if event::poll(TICK_RATE)? {
+            screen.update()
+        } else {
+            screen.tick();
+            Ok(ModelResponse::Refresh)
+        };
+    }
+}
+
+/// The navigation that opens a startup screen.
+fn startup_response(startup_screen: StartupScreen) -> ModelResponse {
+    match startup_screen {
+        StartupScreen::Menu => ModelResponse::NavToMainMenu,
+        StartupScreen::Game => ModelResponse::NavToGame,
+        StartupScreen::StrategyCalculator => ModelResponse::NavToStrategyCalculator,
+        StartupScreen::CountTrainer => ModelResponse::NavToCountTrainer,
+        StartupScreen::StrategyQuiz => ModelResponse::NavToStrategyQuiz,
+        StartupScreen::TableMath => ModelResponse::NavToTableMath,
+        StartupScreen::Simulation => ModelResponse::NavToSimulation,
+        StartupScreen::Statistics => ModelResponse::NavToStatistics,
     }
 }
 
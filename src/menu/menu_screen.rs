@@ -4,9 +4,10 @@ use ratatui::Frame;
 use ratatui::layout::{Alignment, Constraint, Direction, Layout, Rect};
 use ratatui::prelude::{Color, Line, Stylize};
 use ratatui::widgets::{Block, Paragraph};
-use crate::menu::menu_screen::MenuOption::{AboutUs, StrategyCalculator};
+use std::fmt;
+use crate::menu::menu_screen::MenuOption::{AboutUs, CountTrainer, Dealer, Hotseat, Lan, PlayBlackjack, StrategyCalculator, StrategyQuiz, TableMath, Simulation, RiskOfRuin, BetRamp, Comps, Statistics, Settings};
 use crate::model::{Model, ModelResponse};
-use crate::ui::{render_border, render_sub_title_block, render_title_block, MenuNavigation};
+use crate::ui::{render_border, render_sub_title_block, render_title_block, MenuNavigation, read_event};
 
 // ---- Menu Screen ----
 pub struct MenuScreen {
@@ -23,8 +24,13 @@ impl MenuScreen {
     fn render_menu_body(&self, frame: &mut Frame, rect: Rect) {
         let mut menu_body: Vec<Line<'_>> = vec![];
 
+        // Options go one under another without the blank lines between
+        // them when the terminal is too short to fit both
+        let spaced = rect.height as usize >= MENU_ITEMS.len() * 2;
         for (i, item) in MENU_ITEMS.iter().enumerate() {
-            menu_body.push(Line::from(""));
+            if spaced {
+                menu_body.push(Line::from(""));
+            }
             let mut text = if self.active_menu_index == i as i8 {
                 "> ".to_string()
             } else {
@@ -49,7 +55,20 @@ impl MenuScreen {
     fn return_navigation_target(&self) -> ModelResponse {
         let selected_option = MENU_ITEMS.get(self.active_menu_index as usize).unwrap();
         match selected_option {
+            PlayBlackjack => ModelResponse::NavToGame,
+            Hotseat => ModelResponse::NavToHotseat,
+            Lan => ModelResponse::NavToLan,
+            Dealer => ModelResponse::NavToDealer,
             StrategyCalculator => ModelResponse::NavToStrategyCalculator,
+            CountTrainer => ModelResponse::NavToCountTrainer,
+            StrategyQuiz => ModelResponse::NavToStrategyQuiz,
+            TableMath => ModelResponse::NavToTableMath,
+            Simulation => ModelResponse::NavToSimulation,
+            RiskOfRuin => ModelResponse::NavToRiskOfRuin,
+            BetRamp => ModelResponse::NavToBetRamp,
+            Comps => ModelResponse::NavToComps,
+            Statistics => ModelResponse::NavToStatistics,
+            Settings => ModelResponse::NavToSettings,
             AboutUs => ModelResponse::NavToAboutUs,
         }
     }
@@ -57,30 +76,70 @@ impl MenuScreen {
 
 // ---- Menu Option ----
 enum MenuOption {
+    PlayBlackjack,
+    Hotseat,
+    Lan,
+    Dealer,
     StrategyCalculator,
+    CountTrainer,
+    StrategyQuiz,
+    TableMath,
+    Simulation,
+    RiskOfRuin,
+    BetRamp,
+    Comps,
+    Statistics,
+    Settings,
     AboutUs
 }
 
-impl MenuOption {
-    pub fn to_string(&self) -> String {
-        match self {
-            StrategyCalculator => "Strategy Calculator".to_string(),
-            AboutUs => "About Us".to_string()
-        }
+impl fmt::Display for MenuOption {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            PlayBlackjack => "Play Blackjack",
+            Hotseat => "Hotseat Multiplayer",
+            Lan => "LAN Multiplayer",
+            Dealer => "Dealer Mode",
+            StrategyCalculator => "Strategy Calculator",
+            CountTrainer => "Count Trainer",
+            StrategyQuiz => "Strategy Quiz",
+            TableMath => "Table Math",
+            Simulation => "Simulation",
+            RiskOfRuin => "Risk of Ruin",
+            BetRamp => "Bet Ramp Finder",
+            Comps => "Comps Calculator",
+            Statistics => "Statistics",
+            Settings => "Settings",
+            AboutUs => "About Us",
+        };
+        write!(f, "{}", name)
     }
 }
 
 
 // ---- CONSTANTS ----
-const MENU_ITEMS: [MenuOption; 2] = [
+const MENU_ITEMS: [MenuOption; 15] = [
+    PlayBlackjack,
+    Hotseat,
+    Lan,
+    Dealer,
     StrategyCalculator,
+    CountTrainer,
+    StrategyQuiz,
+    TableMath,
+    Simulation,
+    RiskOfRuin,
+    BetRamp,
+    Comps,
+    Statistics,
+    Settings,
     AboutUs,
 ];
 
 // ---- TRAIT IMPLEMENTATIONS ----
 impl Model for MenuScreen {
     fn update(&mut self) -> std::io::Result<ModelResponse> {
-        if let Event::Key(key) = event::read()? {
+        if let Event::Key(key) = read_event(false)? {
             if key.kind == event::KeyEventKind::Release {
                 return Ok(ModelResponse::Refresh);
             }
@@ -116,9 +175,10 @@ impl Model for MenuScreen {
             .constraints([
                 Constraint::Length(14),
                 Constraint::Length(1),
-                Constraint::Length(10),
-                Constraint::Ratio(2,5),
-                Constraint::Ratio(1,5),
+                Constraint::Length(4),
+                // Every option plus the blank line above it, when there's room
+                Constraint::Max(MENU_ITEMS.len() as u16 * 2),
+                Constraint::Min(0),
             ])
             .split(screen);
 
@@ -11,7 +11,21 @@ pub enum ModelResponse {
     Exit,
     /// Navigate to a different screen
     NavToMainMenu,
+    NavToGame,
+    NavToHotseat,
+    NavToLan,
+    NavToDealer,
+    NavToHandHistory,
     NavToStrategyCalculator,
+    NavToCountTrainer,
+    NavToStrategyQuiz,
+    NavToTableMath,
+    NavToSimulation,
+    NavToRiskOfRuin,
+    NavToBetRamp,
+    NavToComps,
+    NavToStatistics,
+    NavToSettings,
     NavToAboutUs,
 }
 
@@ -23,9 +37,14 @@ pub enum ModelResponse {
 // This allows different screens to be developed in isolation, then quickly added
 // to the main application flow when ready.
 pub trait Model {
-    /// Called by main program loop to update internal state
+    /// Called by main program loop to update internal state once an input
+    /// event is waiting to be read
     fn update(&mut self) -> io::Result<ModelResponse>;
 
     /// Called by main program loop to refresh/redraw the current screen
     fn ui(&mut self, frame: &mut Frame);
+
+    /// Called by main program loop whenever a tick passes without input,
+    /// for screens that animate or otherwise change on their own
+    fn tick(&mut self) {}
 }
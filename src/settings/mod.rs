@@ -0,0 +1 @@
+pub mod settings_screen;
\ No newline at end of file
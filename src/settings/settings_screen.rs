@@ -0,0 +1,345 @@
+use crate::logic::app_dirs::default_strategies_dir;
+use crate::logic::config::{self, config_path, Config, KeyAction, STARTUP_SCREENS};
+use crate::logic::strategy_calculator_logic::{GameVariant, SurrenderRule, DECK_OPTIONS};
+use crate::model::{Model, ModelResponse};
+use crate::ui::{create_common_layout, read_event, render_border, render_centered_text, render_key_spans, step_through, ActionPalette, ACTION_PALETTES};
+use ratatui::crossterm::event;
+use ratatui::crossterm::event::{Event, KeyCode};
+use ratatui::layout::{Alignment, Constraint, Direction, Layout, Margin, Rect};
+use ratatui::prelude::{Line, Stylize};
+use ratatui::style::Color;
+use ratatui::widgets::{Block, Paragraph, Wrap};
+use ratatui::Frame;
+use std::cell::Cell;
+use std::fmt;
+use std::rc::Rc;
+
+// ---- Settings ----
+/// What the config file holds, in the order they're listed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SettingsOption {
+    StartupScreen,
+    Theme,
+    StrategiesDir,
+    Decks,
+    Soft17,
+    DoubleAfterSplit,
+    Surrender,
+    DealerPeek,
+    Variant,
+    Key(KeyAction),
+}
+
+impl fmt::Display for SettingsOption {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SettingsOption::StartupScreen => write!(f, "Startup Screen"),
+            SettingsOption::Theme => write!(f, "Action Colors"),
+            SettingsOption::StrategiesDir => write!(f, "Strategies Directory"),
+            SettingsOption::Decks => write!(f, "Number of Decks"),
+            SettingsOption::Soft17 => write!(f, "Soft 17"),
+            SettingsOption::DoubleAfterSplit => write!(f, "Double After Split"),
+            SettingsOption::Surrender => write!(f, "Surrender"),
+            SettingsOption::DealerPeek => write!(f, "Dealer Peeks"),
+            SettingsOption::Variant => write!(f, "Game Variant"),
+            SettingsOption::Key(action) => write!(f, "{} Key", action),
+        }
+    }
+}
+
+const SETTINGS_OPTIONS: [SettingsOption; 15] = [
+    SettingsOption::StartupScreen,
+    SettingsOption::Theme,
+    SettingsOption::StrategiesDir,
+    SettingsOption::Decks,
+    SettingsOption::Soft17,
+    SettingsOption::DoubleAfterSplit,
+    SettingsOption::Surrender,
+    SettingsOption::DealerPeek,
+    SettingsOption::Variant,
+    SettingsOption::Key(KeyAction::Quit),
+    SettingsOption::Key(KeyAction::Menu),
+    SettingsOption::Key(KeyAction::Up),
+    SettingsOption::Key(KeyAction::Down),
+    SettingsOption::Key(KeyAction::Left),
+    SettingsOption::Key(KeyAction::Right),
+];
+
+const SURRENDER_RULES: [SurrenderRule; 3] = [SurrenderRule::NotAllowed, SurrenderRule::AnyUpcard, SurrenderRule::Dealer2Through10];
+
+/// The option `step` away from `current` in `options`.
+fn step_option<T: Copy + PartialEq>(options: &[T], current: T, step: isize) -> T {
+    let position = options.iter().position(|option| *option == current).unwrap_or(0);
+    options[step_through(position, step, options.len())]
+}
+
+// ---- Settings Screen ----
+/// Edits the config file: the screen shown at launch, the action colors,
+/// where strategy charts are kept, the rules new games start with, and the
+/// keys shared by every screen.
+pub struct SettingsScreen {
+    /// The config as edited, written out when saved
+    config: Config,
+
+    /// Shared with the screens that color chart actions
+    palette: Rc<Cell<ActionPalette>>,
+
+    /// Position in `SETTINGS_OPTIONS`
+    active_option: usize,
+
+    /// Strategies directory being typed in
+    dir_input: Option<String>,
+
+    /// Waiting for the key to bind to the highlighted action
+    rebinding: bool,
+    message: String,
+}
+
+impl SettingsScreen {
+    pub fn new(palette: Rc<Cell<ActionPalette>>) -> Self {
+        Self {
+            config: config::current(),
+            palette,
+            active_option: 0,
+            dir_input: None,
+            rebinding: false,
+            message: String::new(),
+        }
+    }
+
+    fn change_option(&mut self, step: isize) {
+        let rules = &mut self.config.rules;
+        match SETTINGS_OPTIONS[self.active_option] {
+            SettingsOption::StartupScreen => self.config.startup_screen = step_option(&STARTUP_SCREENS, self.config.startup_screen, step),
+            SettingsOption::Theme => {
+                // Shown straight away, like picking colors in the strategy calculator
+                self.config.theme = step_option(&ACTION_PALETTES, self.config.theme, step);
+                self.palette.set(self.config.theme);
+            }
+            SettingsOption::StrategiesDir | SettingsOption::Key(_) => {}
+            SettingsOption::Decks => rules.decks = step_option(&DECK_OPTIONS, rules.decks, step),
+            SettingsOption::Soft17 => rules.dealer_stands_on_soft_17 = !rules.dealer_stands_on_soft_17,
+            SettingsOption::DoubleAfterSplit => rules.double_after_split = !rules.double_after_split,
+            SettingsOption::Surrender => rules.surrender_allowed = step_option(&SURRENDER_RULES, rules.surrender_allowed, step),
+            SettingsOption::DealerPeek => rules.dealer_peak = !rules.dealer_peak,
+            SettingsOption::Variant => rules.variant = step_option(&[GameVariant::Classic, GameVariant::FreeBet], rules.variant, step),
+        }
+    }
+
+    /// Enter types a new strategies directory or waits for a key to bind.
+    fn start_editing(&mut self) {
+        match SETTINGS_OPTIONS[self.active_option] {
+            SettingsOption::StrategiesDir => {
+                self.dir_input = Some(self.config.strategies_dir.clone());
+                self.message = "Type a directory and press Enter, Esc to cancel".to_string();
+            }
+            SettingsOption::Key(action) => {
+                self.rebinding = true;
+                self.message = format!("Press the key for {}, Esc to cancel", action);
+            }
+            _ => {}
+        }
+    }
+
+    /// Typing the strategies directory takes every key until it's done or cancelled.
+    fn update_dir_input(&mut self, key: KeyCode) {
+        let Some(input) = self.dir_input.as_mut() else {
+            return;
+        };
+        match key {
+            KeyCode::Enter => {
+                let dir = input.trim();
+                self.config.strategies_dir = if dir.is_empty() { default_strategies_dir() } else { dir.to_string() };
+                self.dir_input = None;
+                self.message.clear();
+            }
+            KeyCode::Esc => {
+                self.dir_input = None;
+                self.message.clear();
+            }
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Char(c) => input.push(c),
+            _ => {}
+        }
+    }
+
+    /// Binds the key pressed to the highlighted action, unless another
+    /// action already has it.
+    fn rebind(&mut self, key: KeyCode) {
+        let SettingsOption::Key(action) = SETTINGS_OPTIONS[self.active_option] else {
+            return;
+        };
+        self.rebinding = false;
+        let KeyCode::Char(c) = key else {
+            self.message.clear();
+            return;
+        };
+        let mut keys = self.config.keys;
+        keys.set(action, c);
+        self.message = match keys.validate() {
+            Ok(()) => {
+                self.config.keys = keys;
+                format!("{} is now {}", action, c)
+            }
+            Err(err) => err,
+        };
+    }
+
+    /// Writes the config out and puts it in use for every screen opened after.
+    fn save(&mut self) {
+        let path = config_path();
+        self.message = match self.config.save(&path) {
+            Ok(()) => {
+                config::set_current(self.config.clone());
+                format!("Saved to {}", path.display())
+            }
+            Err(err) => format!("Couldn't save the config: {}", err),
+        };
+    }
+
+    fn render_settings(&self, frame: &mut Frame, rect: Rect) {
+        let rules = &self.config.rules;
+        let mut lines: Vec<Line> = vec![];
+        for (index, option) in SETTINGS_OPTIONS.iter().enumerate() {
+            let selected = index == self.active_option;
+            let value = match option {
+                SettingsOption::StartupScreen => self.config.startup_screen.to_string(),
+                SettingsOption::Theme => self.config.theme.to_string(),
+                SettingsOption::StrategiesDir => match &self.dir_input {
+                    Some(input) => format!("{}_", input),
+                    None => self.config.strategies_dir.clone(),
+                },
+                SettingsOption::Decks => rules.decks.to_string(),
+                SettingsOption::Soft17 => (if rules.dealer_stands_on_soft_17 { "Dealer Stands" } else { "Dealer Hits" }).to_string(),
+                SettingsOption::DoubleAfterSplit => (if rules.double_after_split { "Allowed" } else { "Not Allowed" }).to_string(),
+                SettingsOption::Surrender => String::from(rules.surrender_allowed.to_string()),
+                SettingsOption::DealerPeek => (if rules.dealer_peak { "Yes" } else { "No (ENHC)" }).to_string(),
+                SettingsOption::Variant => rules.variant.to_string(),
+                SettingsOption::Key(_) if selected && self.rebinding => "_".to_string(),
+                SettingsOption::Key(action) => self.config.keys.key(*action).to_string(),
+            };
+            let text = match option {
+                SettingsOption::StrategiesDir | SettingsOption::Key(_) => format!("{}{}: {}", if selected { "> " } else { "" }, option, value),
+                _ => format!("{}{}: < {} >", if selected { "> " } else { "" }, option, value),
+            };
+            if matches!(option, SettingsOption::Decks | SettingsOption::Key(KeyAction::Quit)) {
+                lines.push(Line::from(""));
+            }
+            lines.push(if selected { Line::from(text).fg(Color::Green) } else { Line::from(text) });
+        }
+
+        lines.push(Line::from(""));
+        lines.push(Line::from(self.message.as_str()).fg(Color::Yellow));
+
+        let settings = Paragraph::new(lines)
+            .bold()
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true })
+            .block(Block::default());
+        frame.render_widget(settings, rect);
+    }
+
+    /// The file as it'll be written, and whether it differs from the one in use.
+    fn render_file(&self, frame: &mut Frame, rect: Rect) {
+        let mut lines = vec![
+            Line::from(config_path().display().to_string()).fg(Color::Cyan),
+            Line::from(if self.config == config::current() { "Saved" } else { "Not saved yet, press S to save" }).fg(Color::Yellow),
+            Line::from(""),
+        ];
+        let text = toml::to_string_pretty(&self.config).unwrap_or_else(|err| err.to_string());
+        lines.extend(text.lines().map(|line| Line::from(line.to_string())));
+        lines.push(Line::from(""));
+        lines.push(Line::from("Rules and the strategies directory apply to screens opened after saving. Bound keys stand in for the usual ones, which keep working").fg(Color::Yellow));
+
+        let file = Paragraph::new(lines)
+            .wrap(Wrap { trim: false })
+            .block(Block::default());
+        frame.render_widget(file, rect.inner(Margin { vertical: 0, horizontal: 2 }));
+    }
+
+    fn footer_spans(&self) -> Vec<String> {
+        if self.dir_input.is_some() || self.rebinding {
+            return vec![" Enter ".to_string(), " Done ".to_string(), " Esc ".to_string(), " Cancel ".to_string()];
+        }
+        let mut spans = vec![
+            " Q ".to_string(), " Quit ".to_string(),
+            " M ".to_string(), " Menu ".to_string(),
+            " J/↓ ".to_string(), " Down ".to_string(),
+            " K/↑ ".to_string(), " Up ".to_string(),
+        ];
+        match SETTINGS_OPTIONS[self.active_option] {
+            SettingsOption::StrategiesDir => spans.extend([" Enter ".to_string(), " Edit ".to_string()]),
+            SettingsOption::Key(_) => spans.extend([" Enter ".to_string(), " Rebind ".to_string()]),
+            _ => spans.extend([" H/← L/→ ".to_string(), " Change ".to_string()]),
+        }
+        spans.extend([" S ".to_string(), " Save ".to_string()]);
+        spans
+    }
+}
+
+// ---- TRAIT IMPLEMENTATIONS ----
+impl Model for SettingsScreen {
+    fn update(&mut self) -> std::io::Result<ModelResponse> {
+        // Keys being bound are taken as pressed, not as what they're bound to
+        if let Event::Key(key) = read_event(self.dir_input.is_some() || self.rebinding)? {
+            if key.kind == event::KeyEventKind::Release {
+                return Ok(ModelResponse::Refresh);
+            }
+            if self.dir_input.is_some() {
+                self.update_dir_input(key.code);
+                return Ok(ModelResponse::Refresh);
+            }
+            if self.rebinding {
+                self.rebind(key.code);
+                return Ok(ModelResponse::Refresh);
+            }
+            match key.code {
+                KeyCode::Char('q') => return Ok(ModelResponse::Exit),
+                KeyCode::Char('m') => return Ok(ModelResponse::NavToMainMenu),
+                KeyCode::Char('j') | KeyCode::Down => {
+                    self.active_option = (self.active_option + 1) % SETTINGS_OPTIONS.len();
+                }
+                KeyCode::Char('k') | KeyCode::Up => {
+                    self.active_option = (self.active_option + SETTINGS_OPTIONS.len() - 1) % SETTINGS_OPTIONS.len();
+                }
+                KeyCode::Char('h') | KeyCode::Left => self.change_option(-1),
+                KeyCode::Char('l') | KeyCode::Right => self.change_option(1),
+                KeyCode::Enter => self.start_editing(),
+                KeyCode::Char('s') => self.save(),
+                _ => {}
+            }
+        }
+        Ok(ModelResponse::Refresh)
+    }
+
+    fn ui(&mut self, frame: &mut Frame) {
+        let main_chunks = create_common_layout(frame.area());
+        // Wider than most screens' settings, the directory and the file read as long lines
+        let horizontal_chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+            .split(main_chunks[1]);
+
+        let left_section = horizontal_chunks[0];
+        render_border(frame, left_section);
+        render_centered_text(frame, left_section, " Settings ");
+        self.render_settings(frame, left_section.inner(Margin { vertical: 1, horizontal: 1 }));
+
+        let right_section = horizontal_chunks[1];
+        render_border(frame, right_section);
+        render_centered_text(frame, right_section, " Config File ");
+        self.render_file(frame, right_section.inner(Margin { vertical: 1, horizontal: 1 }));
+
+        // Render Footer
+        let footer_layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Length(5),
+                Constraint::Min(20),
+            ])
+            .split(main_chunks[2]);
+        render_key_spans(frame, self.footer_spans(), footer_layout[1]);
+    }
+}
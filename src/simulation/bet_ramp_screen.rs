@@ -0,0 +1,461 @@
+use crate::constants::BET_RAMP_FILE;
+use crate::logic::app_dirs::{data_file, deviations_dir};
+use crate::logic::bet_spread::{BET_SPREADS, RAMP_STEPS};
+use crate::logic::config::strategies_dir;
+use crate::logic::counting::COUNTING_SYSTEMS;
+use crate::logic::deviations::{load_index_sets, IndexSet};
+use crate::logic::game::table::PENETRATION_OPTIONS;
+use crate::logic::simulator::engine::{BackgroundRun, CountingPlay, SimulationConfig};
+use crate::logic::simulator::optimal_ramp::{find_ramp, FoundRamp, RampGoal, RampSearch, RAMP_MAX_UNITS, RUIN_TOLERANCES};
+use crate::logic::strategy_calculator_logic::{load_strategies, BlackjackStrategy};
+use crate::model::{Model, ModelResponse};
+use crate::ui::{create_common_layout, format_count, render_border, render_centered_text, render_key_spans, split_content_horizontally, read_event, step_through};
+use ratatui::crossterm::event;
+use ratatui::crossterm::event::{Event, KeyCode};
+use ratatui::layout::{Alignment, Constraint, Direction, Layout, Margin, Rect};
+use ratatui::prelude::{Line, Stylize};
+use ratatui::style::Color;
+use ratatui::widgets::{Block, Gauge, Paragraph, Wrap};
+use ratatui::Frame;
+use std::fmt;
+use std::time::Duration;
+
+/// Bankrolls a ramp can be found for, in betting units
+const BANKROLL_OPTIONS: [u32; 5] = [200, 400, 800, 1_600, 3_200];
+const DEFAULT_BANKROLL: usize = 3;
+
+/// Rounds played flat to see how each count does
+const ROUND_OPTIONS: [u64; 3] = [100_000, 1_000_000, 10_000_000];
+const DEFAULT_ROUNDS: usize = 1;
+
+/// Positions in `RUIN_TOLERANCES`, `RAMP_MAX_UNITS` and `PENETRATION_OPTIONS`
+const DEFAULT_RUIN: usize = 4;
+const DEFAULT_MAX_UNITS: usize = 3;
+const DEFAULT_PENETRATION: usize = 3;
+
+const GOALS: [RampGoal; 2] = [RampGoal::Score, RampGoal::Growth];
+
+// ---- Settings ----
+/// What a ramp is found for, in the order they're listed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RampOption {
+    Strategy,
+    Penetration,
+    Counting,
+    Deviations,
+    Bankroll,
+    RiskOfRuin,
+    MaxBet,
+    Goal,
+    Rounds,
+}
+
+impl fmt::Display for RampOption {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            RampOption::Strategy => "Strategy",
+            RampOption::Penetration => "Penetration",
+            RampOption::Counting => "Counting",
+            RampOption::Deviations => "Deviations",
+            RampOption::Bankroll => "Bankroll",
+            RampOption::RiskOfRuin => "Risk of Ruin",
+            RampOption::MaxBet => "Max Bet",
+            RampOption::Goal => "Goal",
+            RampOption::Rounds => "Rounds",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+const RAMP_OPTIONS: [RampOption; 9] = [
+    RampOption::Strategy,
+    RampOption::Penetration,
+    RampOption::Counting,
+    RampOption::Deviations,
+    RampOption::Bankroll,
+    RampOption::RiskOfRuin,
+    RampOption::MaxBet,
+    RampOption::Goal,
+    RampOption::Rounds,
+];
+
+// ---- Runs ----
+/// The game being played flat on another thread.
+struct Run {
+    run: BackgroundRun<FoundRamp>,
+    label: String,
+    rounds: u64,
+}
+
+/// The ramp a run found.
+struct Finished {
+    found: FoundRamp,
+    label: String,
+    bankroll: u32,
+    took: Duration,
+}
+
+/// The true counts a step of a ramp covers.
+fn step_label(step: usize) -> String {
+    match step {
+        0 => "0 and below".to_string(),
+        step if step == RAMP_STEPS - 1 => format!("+{} and up", step),
+        step => format!("+{}", step),
+    }
+}
+
+// ---- Bet Ramp Screen ----
+/// Finds the bets to make at each true count that do best for a bankroll
+/// and the risk it can take, and saves them for the game's bet advisor.
+pub struct BetRampScreen {
+    /// Charts that can be played, by name
+    strategies: Vec<(String, BlackjackStrategy)>,
+
+    /// Position in `strategies`
+    strategy: usize,
+
+    /// Positions in `PENETRATION_OPTIONS` and `COUNTING_SYSTEMS`
+    penetration: usize,
+    counting: usize,
+
+    /// Every index set, and the position among those for the counting
+    /// system of the one played, basic strategy when none
+    index_sets: Vec<IndexSet>,
+    deviations: Option<usize>,
+
+    /// Positions in `BANKROLL_OPTIONS`, `RUIN_TOLERANCES`, `RAMP_MAX_UNITS`,
+    /// `GOALS` and `ROUND_OPTIONS`
+    bankroll: usize,
+    ruin: usize,
+    max_units: usize,
+    goal: usize,
+    rounds: usize,
+
+    /// Position in `RAMP_OPTIONS`
+    active_option: usize,
+    run: Option<Run>,
+    finished: Option<Finished>,
+    message: String,
+}
+
+impl BetRampScreen {
+    pub fn new() -> Self {
+        let mut strategies: Vec<(String, BlackjackStrategy)> = load_strategies(&strategies_dir()).into_iter().collect();
+        strategies.sort_by(|a, b| a.0.cmp(&b.0));
+        let strategy = strategies.iter().position(|(name, _)| name == "default-strategy").unwrap_or(0);
+        let message = if strategies.is_empty() {
+            format!("No strategies found in {}", &strategies_dir())
+        } else {
+            "Press Enter to find a ramp".to_string()
+        };
+        Self {
+            strategies,
+            strategy,
+            penetration: DEFAULT_PENETRATION,
+            counting: 0,
+            index_sets: load_index_sets(&deviations_dir()),
+            deviations: None,
+            bankroll: DEFAULT_BANKROLL,
+            ruin: DEFAULT_RUIN,
+            max_units: DEFAULT_MAX_UNITS,
+            goal: 0,
+            rounds: DEFAULT_ROUNDS,
+            active_option: 0,
+            run: None,
+            finished: None,
+            message,
+        }
+    }
+
+    fn is_running(&self) -> bool {
+        self.run.is_some()
+    }
+
+    /// Index sets for the counting system being played.
+    fn available_sets(&self) -> Vec<&IndexSet> {
+        let system = COUNTING_SYSTEMS[self.counting].name();
+        self.index_sets.iter().filter(|set| set.system == system).collect()
+    }
+
+    fn index_set(&self) -> Option<&IndexSet> {
+        self.deviations.and_then(|position| self.available_sets().get(position).copied())
+    }
+
+    fn change_option(&mut self, step: isize) {
+        match RAMP_OPTIONS[self.active_option] {
+            RampOption::Strategy if !self.strategies.is_empty() => {
+                self.strategy = step_through(self.strategy, step, self.strategies.len());
+            }
+            RampOption::Strategy => {}
+            RampOption::Penetration => self.penetration = step_through(self.penetration, step, PENETRATION_OPTIONS.len()),
+            RampOption::Counting => {
+                self.counting = step_through(self.counting, step, COUNTING_SYSTEMS.len());
+                self.deviations = None;
+            }
+            // None comes before the first index set
+            RampOption::Deviations => {
+                let stepped = step_through(self.deviations.map_or(0, |position| position + 1), step, self.available_sets().len() + 1);
+                self.deviations = stepped.checked_sub(1);
+            }
+            RampOption::Bankroll => self.bankroll = step_through(self.bankroll, step, BANKROLL_OPTIONS.len()),
+            RampOption::RiskOfRuin => self.ruin = step_through(self.ruin, step, RUIN_TOLERANCES.len()),
+            RampOption::MaxBet => self.max_units = step_through(self.max_units, step, RAMP_MAX_UNITS.len()),
+            RampOption::Goal => self.goal = step_through(self.goal, step, GOALS.len()),
+            RampOption::Rounds => self.rounds = step_through(self.rounds, step, ROUND_OPTIONS.len()),
+        }
+    }
+
+    /// Starts playing the game flat on another thread to find the ramp.
+    fn start(&mut self) {
+        let Some((name, strategy)) = self.strategies.get(self.strategy) else {
+            return;
+        };
+        let rounds = ROUND_OPTIONS[self.rounds];
+        let system = COUNTING_SYSTEMS[self.counting];
+        let mut simulation = SimulationConfig::for_strategy(strategy.clone(), rounds, None);
+        simulation.table.penetration = PENETRATION_OPTIONS[self.penetration];
+        let mut play = CountingPlay::new(system, BET_SPREADS[0]);
+        play.deviations = self.index_set().cloned();
+        simulation.counting = Some(play);
+        let search = RampSearch {
+            simulation,
+            bankroll: BANKROLL_OPTIONS[self.bankroll] as f64,
+            max_ruin: RUIN_TOLERANCES[self.ruin],
+            max_units: RAMP_MAX_UNITS[self.max_units],
+            goal: GOALS[self.goal],
+        };
+        let label = match self.index_set() {
+            Some(set) => format!("{}, {} with the {}", name, system.name(), set.name),
+            None => format!("{}, {}", name, system.name()),
+        };
+        self.run = Some(Run {
+            run: BackgroundRun::start(move |progress| find_ramp(&search, progress)),
+            label,
+            rounds,
+        });
+        self.finished = None;
+        self.message = "Press C to cancel".to_string();
+    }
+
+    fn cancel(&mut self) {
+        if let Some(run) = &self.run {
+            run.run.progress().cancel();
+            self.message = "Cancelling...".to_string();
+        }
+    }
+
+    /// Picks up the ramp once the run's thread is done.
+    fn check_run(&mut self) {
+        if !self.run.as_ref().is_some_and(|run| run.run.is_finished()) {
+            return;
+        }
+        let Some(Run { run, label, .. }) = self.run.take() else {
+            return;
+        };
+        let took = run.elapsed();
+        match run.join() {
+            Ok(found) => {
+                self.message = "Press S to save the ramp for the game's bet advisor".to_string();
+                self.finished = Some(Finished { found, label, bankroll: BANKROLL_OPTIONS[self.bankroll], took });
+            }
+            Err(err) => self.message = err,
+        }
+    }
+
+    /// Saves the ramp found for the game screen to offer.
+    fn save(&mut self) {
+        let Some(finished) = &self.finished else {
+            return;
+        };
+        let ramp = &finished.found.ramp;
+        self.message = match ramp.save(&data_file(BET_RAMP_FILE)) {
+            Ok(()) => format!("Saved the {} ramp, pick it with Shift+J in the game", ramp),
+            Err(err) => format!("Couldn't save the ramp: {}", err),
+        };
+    }
+
+    fn render_settings(&self, frame: &mut Frame, rect: Rect) {
+        let mut lines: Vec<Line> = vec![];
+        for (index, option) in RAMP_OPTIONS.iter().enumerate() {
+            let value = match option {
+                RampOption::Strategy => self.strategies.get(self.strategy).map_or("None".to_string(), |(name, _)| name.clone()),
+                RampOption::Penetration => format!("{:.0}%", PENETRATION_OPTIONS[self.penetration] * 100.0),
+                RampOption::Counting => COUNTING_SYSTEMS[self.counting].name().to_string(),
+                RampOption::Deviations => self.index_set().map_or("None".to_string(), |set| set.name.clone()),
+                RampOption::Bankroll => format!("{} units", format_count(BANKROLL_OPTIONS[self.bankroll] as u64)),
+                RampOption::RiskOfRuin => format!("{:.1}%", RUIN_TOLERANCES[self.ruin] * 100.0),
+                RampOption::MaxBet => format!("{} units", RAMP_MAX_UNITS[self.max_units]),
+                RampOption::Goal => GOALS[self.goal].to_string(),
+                RampOption::Rounds => format_count(ROUND_OPTIONS[self.rounds]),
+            };
+            let selected = index == self.active_option && !self.is_running();
+            let text = format!("{}{}: < {} >", if selected { "> " } else { "" }, option, value);
+            lines.push(Line::from(""));
+            lines.push(if selected { Line::from(text).fg(Color::Green) } else { Line::from(text) });
+        }
+
+        if let Some((_, strategy)) = self.strategies.get(self.strategy) {
+            lines.push(Line::from(""));
+            lines.push(Line::from(strategy.rules.to_string()).fg(Color::Cyan));
+        }
+        lines.push(Line::from(""));
+        lines.push(Line::from(match GOALS[self.goal] {
+            RampGoal::Score => "Wins the most for the risk, no more than the risk of ruin allows",
+            RampGoal::Growth => "Grows the bankroll fastest, no more than the risk of ruin allows",
+        }).fg(Color::Yellow));
+
+        let settings = Paragraph::new(lines)
+            .bold()
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true })
+            .block(Block::default());
+        frame.render_widget(settings, rect);
+    }
+
+    /// The run's progress bar, or the ramp found with how it plays.
+    fn render_run(&self, frame: &mut Frame, rect: Rect) {
+        let layout = Layout::vertical([
+            Constraint::Length(2),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Min(0),
+            Constraint::Length(2),
+        ]).split(rect.inner(Margin { vertical: 0, horizontal: 2 }));
+
+        if let Some(run) = &self.run {
+            let so_far = run.run.progress().so_far();
+            frame.render_widget(Paragraph::new(run.label.as_str()).bold().alignment(Alignment::Center), layout[1]);
+            let ratio = (so_far.rounds as f64 / run.rounds as f64).clamp(0.0, 1.0);
+            let gauge = Gauge::default()
+                .gauge_style(Color::Green)
+                .ratio(ratio)
+                .label(format!("{} of {} rounds", format_count(so_far.rounds), format_count(run.rounds)));
+            frame.render_widget(gauge, layout[3]);
+            let lines = vec![
+                Line::from(""),
+                Line::from("Playing flat to see how each true count does"),
+                Line::from(format!("Elapsed: {:.1}s", run.run.elapsed().as_secs_f64())),
+            ];
+            frame.render_widget(Paragraph::new(lines).alignment(Alignment::Center), layout[4]);
+        } else if let Some(finished) = &self.finished {
+            let found = &finished.found;
+            let stats = &found.stats;
+            frame.render_widget(Paragraph::new(finished.label.as_str()).bold().alignment(Alignment::Center), layout[1]);
+            let mut lines = vec![
+                Line::from(format!("{:<12}{:>9}{:>11}{:>7}", "True Count", "Rounds", "Edge", "Bet")).bold(),
+            ];
+            for (step, (result, frequency)) in found.steps.iter().zip(&found.frequencies).enumerate() {
+                lines.push(Line::from(format!(
+                    "{:<12}{:>8.1}%{:>+10.2}%{:>7}",
+                    step_label(step),
+                    frequency * 100.0,
+                    result.ev() * 100.0,
+                    found.ramp.units[step],
+                )));
+            }
+            lines.extend([
+                Line::from(""),
+                Line::from(format!("{}, betting units of 1/{} of the bankroll", found.ramp, format_count(finished.bankroll as u64))).bold().fg(Color::Green),
+                Line::from(""),
+                Line::from(format!("EV Per Round: {:+.4} units", stats.ev)),
+                Line::from(format!("Average Bet: {:.2} units, a {:+.3}% edge", stats.average_bet, stats.advantage() * 100.0)),
+                Line::from(format!("Standard Deviation: {:.3} units a round", stats.std_dev)),
+                Line::from(format!("SCORE: {:.2}", stats.score)),
+                Line::from(format!("Growth: {:+.3}% of the bankroll per 100 rounds", stats.growth * 100.0)),
+                Line::from(format!("Risk of Ruin: {:.2}%", stats.ruin * 100.0)).bold().fg(Color::Yellow),
+                Line::from(""),
+                Line::from(format!("Took {:.1}s", finished.took.as_secs_f64())),
+            ]);
+            frame.render_widget(Paragraph::new(lines).alignment(Alignment::Center), layout[3].union(layout[4]));
+        }
+
+        frame.render_widget(Paragraph::new(self.message.as_str()).bold().fg(Color::Yellow).alignment(Alignment::Center).wrap(Wrap { trim: true }), layout[5]);
+    }
+
+    fn footer_spans(&self) -> Vec<String> {
+        let mut spans = vec![
+            " Q ".to_string(), " Quit ".to_string(),
+            " M ".to_string(), " Menu ".to_string(),
+        ];
+        if self.is_running() {
+            spans.extend([" C ".to_string(), " Cancel ".to_string()]);
+        } else {
+            spans.extend([
+                " J/↓ ".to_string(), " Down ".to_string(),
+                " K/↑ ".to_string(), " Up ".to_string(),
+                " H/← L/→ ".to_string(), " Change ".to_string(),
+                " Enter ".to_string(), " Find ".to_string(),
+            ]);
+            if self.finished.is_some() {
+                spans.extend([" S ".to_string(), " Save Ramp ".to_string()]);
+            }
+        }
+        spans
+    }
+}
+
+// ---- TRAIT IMPLEMENTATIONS ----
+/// Leaving the screen cancels any run still going.
+impl Drop for BetRampScreen {
+    fn drop(&mut self) {
+        self.cancel();
+    }
+}
+
+impl Model for BetRampScreen {
+    fn update(&mut self) -> std::io::Result<ModelResponse> {
+        if let Event::Key(key) = read_event(false)? {
+            if key.kind == event::KeyEventKind::Release {
+                return Ok(ModelResponse::Refresh);
+            }
+            match key.code {
+                KeyCode::Char('q') => return Ok(ModelResponse::Exit),
+                KeyCode::Char('m') => return Ok(ModelResponse::NavToMainMenu),
+                KeyCode::Char('c') | KeyCode::Esc if self.is_running() => self.cancel(),
+                _ if self.is_running() => {}
+                KeyCode::Char('j') | KeyCode::Down => {
+                    self.active_option = (self.active_option + 1) % RAMP_OPTIONS.len();
+                }
+                KeyCode::Char('k') | KeyCode::Up => {
+                    self.active_option = (self.active_option + RAMP_OPTIONS.len() - 1) % RAMP_OPTIONS.len();
+                }
+                KeyCode::Char('h') | KeyCode::Left => self.change_option(-1),
+                KeyCode::Char('l') | KeyCode::Right => self.change_option(1),
+                KeyCode::Char('s') => self.save(),
+                KeyCode::Enter => self.start(),
+                _ => {}
+            }
+        }
+        Ok(ModelResponse::Refresh)
+    }
+
+    fn ui(&mut self, frame: &mut Frame) {
+        let main_chunks = create_common_layout(frame.area());
+        let horizontal_chunks = split_content_horizontally(main_chunks[1]);
+
+        let left_section = horizontal_chunks[0];
+        render_border(frame, left_section);
+        render_centered_text(frame, left_section, " Game Conditions ");
+        self.render_settings(frame, left_section.inner(Margin { vertical: 1, horizontal: 1 }));
+
+        let right_section = horizontal_chunks[1];
+        render_border(frame, right_section);
+        render_centered_text(frame, right_section, " Bet Ramp ");
+        self.render_run(frame, right_section.inner(Margin { vertical: 1, horizontal: 1 }));
+
+        // Render Footer
+        let footer_layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Length(5),
+                Constraint::Min(20),
+            ])
+            .split(main_chunks[2]);
+        render_key_spans(frame, self.footer_spans(), footer_layout[1]);
+    }
+
+    fn tick(&mut self) {
+        self.check_run();
+    }
+}
@@ -0,0 +1,3 @@
+pub mod simulation_screen;
+pub mod risk_of_ruin_screen;
+pub mod bet_ramp_screen;
\ No newline at end of file
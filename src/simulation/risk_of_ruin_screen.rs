@@ -0,0 +1,389 @@
+use crate::logic::bet_spread::{BetSpread, BET_SPREADS};
+use crate::logic::config::strategies_dir;
+use crate::logic::counting::HI_LO;
+use crate::logic::game::table::PENETRATION_OPTIONS;
+use crate::logic::simulator::engine::{BackgroundRun, CountingPlay, SimulationConfig};
+use crate::logic::simulator::risk_of_ruin::{simulate_ruin, RuinConfig, RuinResult};
+use crate::logic::strategy_calculator_logic::{load_strategies, BlackjackStrategy};
+use crate::model::{Model, ModelResponse};
+use crate::ui::{create_common_layout, format_count, render_border, render_centered_text, render_key_spans, split_content_horizontally, read_event, step_through};
+use ratatui::crossterm::event;
+use ratatui::crossterm::event::{Event, KeyCode};
+use ratatui::layout::{Alignment, Constraint, Direction, Layout, Margin, Rect};
+use ratatui::prelude::{Line, Stylize};
+use ratatui::style::Color;
+use ratatui::widgets::{Block, Gauge, Paragraph, Wrap};
+use ratatui::Frame;
+use std::fmt;
+use std::time::Duration;
+
+/// Bankrolls a trial can start with, in betting units
+const BANKROLL_OPTIONS: [u32; 6] = [50, 100, 200, 400, 800, 1_600];
+const DEFAULT_BANKROLL: usize = 1;
+
+/// Rounds each trial can be set to play
+const TRIAL_LENGTH_OPTIONS: [u64; 3] = [1_000, 10_000, 100_000];
+const DEFAULT_TRIAL_LENGTH: usize = 1;
+
+/// How many trials a run can be set to play
+const TRIALS_OPTIONS: [u32; 3] = [100, 1_000, 10_000];
+const DEFAULT_TRIALS: usize = 1;
+
+/// Position in the spreads on offer, flat betting first then `BET_SPREADS`
+const DEFAULT_SPREAD: usize = 2;
+const DEFAULT_PENETRATION: usize = 3;
+
+// ---- Settings ----
+/// The game conditions of a run, in the order they're listed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RuinOption {
+    Strategy,
+    Penetration,
+    BetSpread,
+    Bankroll,
+    TrialLength,
+    Trials,
+}
+
+impl fmt::Display for RuinOption {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            RuinOption::Strategy => "Strategy",
+            RuinOption::Penetration => "Penetration",
+            RuinOption::BetSpread => "Bet Spread",
+            RuinOption::Bankroll => "Bankroll",
+            RuinOption::TrialLength => "Trial Length",
+            RuinOption::Trials => "Trials",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+const RUIN_OPTIONS: [RuinOption; 6] = [
+    RuinOption::Strategy,
+    RuinOption::Penetration,
+    RuinOption::BetSpread,
+    RuinOption::Bankroll,
+    RuinOption::TrialLength,
+    RuinOption::Trials,
+];
+
+// ---- Runs ----
+/// Trials being played on another thread.
+struct Run {
+    run: BackgroundRun<RuinResult>,
+    strategy: String,
+
+    /// Rounds across every trial
+    rounds: u64,
+    trials: u32,
+}
+
+/// How a run went once it's over.
+struct Finished {
+    result: RuinResult,
+    strategy: String,
+    trials: u32,
+    took: Duration,
+}
+
+impl Finished {
+    fn cancelled(&self) -> bool {
+        self.result.trials < self.trials
+    }
+}
+
+// ---- Risk of Ruin Screen ----
+/// Works out the chance of losing a bankroll with a chart and bet spread,
+/// from the formulas and from trials played on a background thread.
+pub struct RiskOfRuinScreen {
+    /// Charts that can be played, by name
+    strategies: Vec<(String, BlackjackStrategy)>,
+
+    /// Position in `strategies`
+    strategy: usize,
+
+    /// Position in `PENETRATION_OPTIONS`
+    penetration: usize,
+
+    /// Flat betting at 0, otherwise one past the position in `BET_SPREADS`
+    spread: usize,
+
+    /// Positions in `BANKROLL_OPTIONS`, `TRIAL_LENGTH_OPTIONS` and `TRIALS_OPTIONS`
+    bankroll: usize,
+    trial_length: usize,
+    trials: usize,
+
+    /// Position in `RUIN_OPTIONS`
+    active_option: usize,
+    run: Option<Run>,
+    finished: Option<Finished>,
+    message: String,
+}
+
+impl RiskOfRuinScreen {
+    pub fn new() -> Self {
+        let mut strategies: Vec<(String, BlackjackStrategy)> = load_strategies(&strategies_dir()).into_iter().collect();
+        strategies.sort_by(|a, b| a.0.cmp(&b.0));
+        let strategy = strategies.iter().position(|(name, _)| name == "default-strategy").unwrap_or(0);
+        let message = if strategies.is_empty() {
+            format!("No strategies found in {}", &strategies_dir())
+        } else {
+            "Press Enter to start the trials".to_string()
+        };
+        Self {
+            strategies,
+            strategy,
+            penetration: DEFAULT_PENETRATION,
+            spread: DEFAULT_SPREAD,
+            bankroll: DEFAULT_BANKROLL,
+            trial_length: DEFAULT_TRIAL_LENGTH,
+            trials: DEFAULT_TRIALS,
+            active_option: 0,
+            run: None,
+            finished: None,
+            message,
+        }
+    }
+
+    fn bet_spread(&self) -> Option<BetSpread> {
+        self.spread.checked_sub(1).map(|position| BET_SPREADS[position])
+    }
+
+    fn is_running(&self) -> bool {
+        self.run.is_some()
+    }
+
+    fn change_option(&mut self, step: isize) {
+        match RUIN_OPTIONS[self.active_option] {
+            RuinOption::Strategy if !self.strategies.is_empty() => {
+                self.strategy = step_through(self.strategy, step, self.strategies.len());
+            }
+            RuinOption::Strategy => {}
+            RuinOption::Penetration => self.penetration = step_through(self.penetration, step, PENETRATION_OPTIONS.len()),
+            RuinOption::BetSpread => self.spread = step_through(self.spread, step, BET_SPREADS.len() + 1),
+            RuinOption::Bankroll => self.bankroll = step_through(self.bankroll, step, BANKROLL_OPTIONS.len()),
+            RuinOption::TrialLength => self.trial_length = step_through(self.trial_length, step, TRIAL_LENGTH_OPTIONS.len()),
+            RuinOption::Trials => self.trials = step_through(self.trials, step, TRIALS_OPTIONS.len()),
+        }
+    }
+
+    /// Starts playing the trials on another thread.
+    fn start(&mut self) {
+        let Some((name, strategy)) = self.strategies.get(self.strategy) else {
+            return;
+        };
+        let mut simulation = SimulationConfig::for_strategy(strategy.clone(), TRIAL_LENGTH_OPTIONS[self.trial_length], None);
+        simulation.table.penetration = PENETRATION_OPTIONS[self.penetration];
+        simulation.counting = self.bet_spread().map(|spread| CountingPlay::new(&HI_LO, spread));
+        let config = RuinConfig { simulation, bankroll: BANKROLL_OPTIONS[self.bankroll] as f64, trials: TRIALS_OPTIONS[self.trials] };
+        self.run = Some(Run {
+            run: BackgroundRun::start(move |progress| simulate_ruin(&config, progress)),
+            strategy: name.clone(),
+            rounds: TRIAL_LENGTH_OPTIONS[self.trial_length] * TRIALS_OPTIONS[self.trials] as u64,
+            trials: TRIALS_OPTIONS[self.trials],
+        });
+        self.message = "Press C to cancel".to_string();
+    }
+
+    fn cancel(&mut self) {
+        if let Some(run) = &self.run {
+            run.run.progress().cancel();
+            self.message = "Cancelling...".to_string();
+        }
+    }
+
+    /// Picks up the result once the run's thread is done.
+    fn check_run(&mut self) {
+        if !self.run.as_ref().is_some_and(|run| run.run.is_finished()) {
+            return;
+        }
+        let Some(Run { run, strategy, trials, .. }) = self.run.take() else {
+            return;
+        };
+        let took = run.elapsed();
+        match run.join() {
+            Ok(result) => {
+                let finished = Finished { result, strategy, trials, took };
+                self.message = if finished.cancelled() { "Cancelled" } else { "Finished" }.to_string();
+                self.finished = Some(finished);
+            }
+            Err(err) => self.message = err,
+        }
+    }
+
+    fn render_settings(&self, frame: &mut Frame, rect: Rect) {
+        let mut lines: Vec<Line> = vec![];
+        for (index, option) in RUIN_OPTIONS.iter().enumerate() {
+            let value = match option {
+                RuinOption::Strategy => self.strategies.get(self.strategy).map_or("None".to_string(), |(name, _)| name.clone()),
+                RuinOption::Penetration => format!("{:.0}%", PENETRATION_OPTIONS[self.penetration] * 100.0),
+                RuinOption::BetSpread => self.bet_spread().map_or("Flat".to_string(), |spread| spread.to_string()),
+                RuinOption::Bankroll => format!("{} units", format_count(BANKROLL_OPTIONS[self.bankroll] as u64)),
+                RuinOption::TrialLength => format!("{} rounds", format_count(TRIAL_LENGTH_OPTIONS[self.trial_length])),
+                RuinOption::Trials => format_count(TRIALS_OPTIONS[self.trials] as u64),
+            };
+            let selected = index == self.active_option && !self.is_running();
+            let text = format!("{}{}: < {} >", if selected { "> " } else { "" }, option, value);
+            lines.push(Line::from(""));
+            lines.push(if selected { Line::from(text).fg(Color::Green) } else { Line::from(text) });
+        }
+
+        if let Some((_, strategy)) = self.strategies.get(self.strategy) {
+            lines.push(Line::from(""));
+            lines.push(Line::from(strategy.rules.to_string()).fg(Color::Cyan));
+        }
+        lines.push(Line::from(""));
+        lines.push(Line::from(match self.bet_spread() {
+            Some(spread) => format!("Bets {} units by the Hi-Lo true count", spread),
+            None => "Bets one unit every round".to_string(),
+        }).fg(Color::Yellow));
+
+        let settings = Paragraph::new(lines)
+            .bold()
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true })
+            .block(Block::default());
+        frame.render_widget(settings, rect);
+    }
+
+    /// The run's progress bar, or the formula and simulated risk of ruin
+    /// from the last run.
+    fn render_run(&self, frame: &mut Frame, rect: Rect) {
+        let layout = Layout::vertical([
+            Constraint::Length(2),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Min(0),
+            Constraint::Length(2),
+        ]).split(rect.inner(Margin { vertical: 0, horizontal: 2 }));
+
+        if let Some(run) = &self.run {
+            let so_far = run.run.progress().so_far();
+            frame.render_widget(Paragraph::new(run.strategy.as_str()).bold().alignment(Alignment::Center), layout[1]);
+            let ratio = (so_far.rounds as f64 / run.rounds as f64).clamp(0.0, 1.0);
+            let gauge = Gauge::default()
+                .gauge_style(Color::Green)
+                .ratio(ratio)
+                .label(format!("{} of {} rounds", format_count(so_far.rounds), format_count(run.rounds)));
+            frame.render_widget(gauge, layout[3]);
+            let lines = vec![
+                Line::from(""),
+                Line::from(format!("Trials: {}", format_count(run.trials as u64))),
+                Line::from(format!("EV So Far: {:+.4} units a round", so_far.ev())),
+                Line::from(format!("Elapsed: {:.1}s", run.run.elapsed().as_secs_f64())),
+            ];
+            frame.render_widget(Paragraph::new(lines).alignment(Alignment::Center), layout[4]);
+        } else if let Some(finished) = &self.finished {
+            let result = &finished.result;
+            let (_, high) = result.played.ev_interval();
+            frame.render_widget(Paragraph::new(finished.strategy.as_str()).bold().alignment(Alignment::Center), layout[1]);
+            let mut lines = vec![
+                Line::from(format!("{} units over {} rounds", format_count(result.bankroll as u64), format_count(result.rounds))),
+                Line::from(""),
+                Line::from(format!("EV Per Round: {:+.4} ± {:.4} units", result.played.ev(), high - result.played.ev())),
+                Line::from(format!("Standard Deviation: {:.3} units a round, {:.2} per 100", result.played.std_dev(), result.played.std_dev_per_100())),
+                Line::from(""),
+                Line::from("Formula Risk of Ruin").bold(),
+                Line::from(format!("Playing Forever: {:.2}%", result.formula() * 100.0)),
+                Line::from(format!("Within {} Rounds: {:.2}%", format_count(result.rounds), result.formula_within() * 100.0)),
+                Line::from(""),
+                Line::from("Simulated Risk of Ruin").bold(),
+                Line::from(format!("{:.2}%", result.simulated() * 100.0)).bold().fg(Color::Yellow),
+                Line::from(format!("{} of {} trials went broke", format_count(result.ruined as u64), format_count(result.trials as u64))),
+                Line::from(""),
+                Line::from(format!("Took {:.1}s", finished.took.as_secs_f64())),
+            ];
+            if finished.cancelled() {
+                lines.push(Line::from(""));
+                lines.push(Line::from(format!("Cancelled after {} of {} trials", format_count(result.trials as u64), format_count(finished.trials as u64))).fg(Color::Yellow));
+            }
+            frame.render_widget(Paragraph::new(lines).alignment(Alignment::Center), layout[3].union(layout[4]));
+        }
+
+        frame.render_widget(Paragraph::new(self.message.as_str()).bold().fg(Color::Yellow).alignment(Alignment::Center), layout[5]);
+    }
+
+    fn footer_spans(&self) -> Vec<String> {
+        let mut spans = vec![
+            " Q ".to_string(), " Quit ".to_string(),
+            " M ".to_string(), " Menu ".to_string(),
+        ];
+        if self.is_running() {
+            spans.extend([" C ".to_string(), " Cancel ".to_string()]);
+        } else {
+            spans.extend([
+                " J/↓ ".to_string(), " Down ".to_string(),
+                " K/↑ ".to_string(), " Up ".to_string(),
+                " H/← L/→ ".to_string(), " Change ".to_string(),
+                " Enter ".to_string(), " Run ".to_string(),
+            ]);
+        }
+        spans
+    }
+}
+
+// ---- TRAIT IMPLEMENTATIONS ----
+/// Leaving the screen cancels any run still going.
+impl Drop for RiskOfRuinScreen {
+    fn drop(&mut self) {
+        self.cancel();
+    }
+}
+
+impl Model for RiskOfRuinScreen {
+    fn update(&mut self) -> std::io::Result<ModelResponse> {
+        if let Event::Key(key) = read_event(false)? {
+            if key.kind == event::KeyEventKind::Release {
+                return Ok(ModelResponse::Refresh);
+            }
+            match key.code {
+                KeyCode::Char('q') => return Ok(ModelResponse::Exit),
+                KeyCode::Char('m') => return Ok(ModelResponse::NavToMainMenu),
+                KeyCode::Char('c') | KeyCode::Esc if self.is_running() => self.cancel(),
+                _ if self.is_running() => {}
+                KeyCode::Char('j') | KeyCode::Down => {
+                    self.active_option = (self.active_option + 1) % RUIN_OPTIONS.len();
+                }
+                KeyCode::Char('k') | KeyCode::Up => {
+                    self.active_option = (self.active_option + RUIN_OPTIONS.len() - 1) % RUIN_OPTIONS.len();
+                }
+                KeyCode::Char('h') | KeyCode::Left => self.change_option(-1),
+                KeyCode::Char('l') | KeyCode::Right => self.change_option(1),
+                KeyCode::Enter => self.start(),
+                _ => {}
+            }
+        }
+        Ok(ModelResponse::Refresh)
+    }
+
+    fn ui(&mut self, frame: &mut Frame) {
+        let main_chunks = create_common_layout(frame.area());
+        let horizontal_chunks = split_content_horizontally(main_chunks[1]);
+
+        let left_section = horizontal_chunks[0];
+        render_border(frame, left_section);
+        render_centered_text(frame, left_section, " Game Conditions ");
+        self.render_settings(frame, left_section.inner(Margin { vertical: 1, horizontal: 1 }));
+
+        let right_section = horizontal_chunks[1];
+        render_border(frame, right_section);
+        render_centered_text(frame, right_section, " Risk of Ruin ");
+        self.render_run(frame, right_section.inner(Margin { vertical: 1, horizontal: 1 }));
+
+        // Render Footer
+        let footer_layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Length(5),
+                Constraint::Min(20),
+            ])
+            .split(main_chunks[2]);
+        render_key_spans(frame, self.footer_spans(), footer_layout[1]);
+    }
+
+    fn tick(&mut self) {
+        self.check_run();
+    }
+}
@@ -0,0 +1,998 @@
+use crate::constants::SIMULATION_PRESETS_FILE;
+use crate::logic::app_dirs::{data_file, deviations_dir, exports_dir};
+use crate::logic::bet_spread::{BET_SPREADS, DEFAULT_BET_SPREAD};
+use crate::logic::config::strategies_dir;
+use crate::logic::counting::COUNTING_SYSTEMS;
+use crate::logic::deviations::{load_index_sets, IndexSet};
+use crate::logic::ev::house_edge;
+use crate::logic::game::table::TableConfig;
+use crate::logic::simulator::engine::{simulate_in_detail, simulate_with_progress, BackgroundRun, CountingPlay, SimulationConfig, SimulationDetail, SimulationResult, CONFIDENCE_Z};
+use crate::logic::simulator::presets::{SimulationPreset, SimulationPresets, MAX_PRESET_NAME_LENGTH};
+use crate::logic::simulator::report::{export_report_csv, export_report_json, SimulationReport};
+use crate::logic::simulator::head_to_head::{head_to_head, HeadToHead};
+use crate::logic::simulator::penetration::{sweep_penetration, SweepPoint, SWEEP_PENETRATIONS};
+use crate::logic::simulator::sessions::{Sessions, ROUNDS_PER_HOUR};
+use crate::logic::simulator::trajectory::Trajectories;
+use crate::logic::strategy_calculator_logic::{load_strategies, BlackjackStrategy};
+use crate::model::{Model, ModelResponse};
+use crate::ui::{create_common_layout, format_count, render_border, render_centered_text, render_key_spans, split_content_horizontally, read_event};
+use ratatui::crossterm::event;
+use ratatui::crossterm::event::{Event, KeyCode};
+use ratatui::layout::{Alignment, Constraint, Direction, Layout, Margin, Rect};
+use ratatui::prelude::{Line, Stylize};
+use ratatui::style::{Color, Style};
+use ratatui::symbols::Marker;
+use ratatui::widgets::{Axis, Bar, BarChart, BarGroup, Block, Chart, Dataset, Gauge, GraphType, LegendPosition, Paragraph};
+use ratatui::Frame;
+use std::fmt;
+use std::time::Duration;
+
+/// Rounds a run can be set to play
+const ROUND_OPTIONS: [u64; 5] = [10_000, 100_000, 1_000_000, 10_000_000, 100_000_000];
+const DEFAULT_ROUNDS: usize = 2;
+
+/// Hours of play a session can be set to last
+const SESSION_HOURS: [u64; 4] = [1, 2, 4, 8];
+const DEFAULT_SESSION_HOURS: usize = 2;
+
+/// Bars in the histogram of session results
+const HISTOGRAM_BINS: usize = 12;
+
+/// Paths drawn as they went, behind the percentile bands
+const SAMPLE_PATHS: usize = 5;
+
+/// Percentiles drawn across every path, with their colours
+const TRAJECTORY_BANDS: [(f64, &str, Color); 5] = [
+    (5.0, "5th", Color::Red),
+    (25.0, "25th", Color::Yellow),
+    (50.0, "Median", Color::Green),
+    (75.0, "75th", Color::Yellow),
+    (95.0, "95th", Color::Red),
+];
+
+// ---- Settings ----
+/// The settings of a run, in the order they're listed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SimulationOption {
+    Preset,
+    Strategy,
+    Versus,
+    Rounds,
+    Session,
+    Seed,
+    Counting,
+    BetSpread,
+    Deviations,
+}
+
+impl fmt::Display for SimulationOption {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            SimulationOption::Preset => "Preset",
+            SimulationOption::Strategy => "Strategy",
+            SimulationOption::Versus => "Versus",
+            SimulationOption::Rounds => "Rounds",
+            SimulationOption::Session => "Session",
+            SimulationOption::Seed => "Seed",
+            SimulationOption::Counting => "Counting",
+            SimulationOption::BetSpread => "Bet Spread",
+            SimulationOption::Deviations => "Deviations",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+const SIMULATION_OPTIONS: [SimulationOption; 9] = [
+    SimulationOption::Preset,
+    SimulationOption::Strategy,
+    SimulationOption::Versus,
+    SimulationOption::Rounds,
+    SimulationOption::Session,
+    SimulationOption::Seed,
+    SimulationOption::Counting,
+    SimulationOption::BetSpread,
+    SimulationOption::Deviations,
+];
+
+/// What the results panel charts once a run is over.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ResultView {
+    Paths,
+    Sessions,
+}
+
+impl ResultView {
+    fn other(self) -> Self {
+        match self {
+            ResultView::Paths => ResultView::Sessions,
+            ResultView::Sessions => ResultView::Paths,
+        }
+    }
+}
+
+impl fmt::Display for ResultView {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ResultView::Paths => "Bankroll Paths",
+            ResultView::Sessions => "Session Results",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+// ---- Runs ----
+/// What a run on another thread comes back with.
+enum Outcome {
+    /// A simulation, and the same chart betting flat when it counts cards
+    Single(SimulationDetail, Option<SimulationResult>),
+
+    /// The game played at each penetration of a sweep
+    Sweep(Vec<SweepPoint>),
+
+    /// Two charts played against the same cards, by name
+    HeadToHead { result: HeadToHead, first: String, second: String },
+}
+
+/// A simulation or sweep being played on another thread.
+struct Run {
+    run: BackgroundRun<Outcome>,
+    config: SimulationConfig,
+    strategy: String,
+    rounds: u64,
+
+    /// Rounds across every simulation the run plays
+    total: u64,
+}
+
+/// How a run went once it's over.
+struct Finished {
+    detail: SimulationDetail,
+    config: SimulationConfig,
+
+    /// The chart played flat, to set a counter's results against
+    baseline: Option<SimulationResult>,
+    strategy: String,
+    rounds: u64,
+    took: Duration,
+}
+
+impl Finished {
+    fn cancelled(&self) -> bool {
+        self.detail.result.rounds < self.rounds
+    }
+}
+
+/// How a penetration sweep went once it's over.
+struct Swept {
+    points: Vec<SweepPoint>,
+    strategy: String,
+
+    /// Rounds played at each penetration
+    rounds: u64,
+    took: Duration,
+}
+
+/// How two charts played against the same cards came out.
+struct Compared {
+    result: HeadToHead,
+    first: String,
+    second: String,
+    rounds: u64,
+    took: Duration,
+}
+
+/// Rounds played each second over `took`.
+fn throughput(rounds: u64, took: Duration) -> f64 {
+    if took.is_zero() {
+        return 0.0;
+    }
+    rounds as f64 / took.as_secs_f64()
+}
+
+/// The EV as a percentage of a unit, give or take its 95% margin.
+fn format_ev(result: &SimulationResult) -> String {
+    let (_, high) = result.ev_interval();
+    format!("{:+.3}% ± {:.3}%", result.ev() * 100.0, (high - result.ev()) * 100.0)
+}
+
+/// Sample paths of the bankroll through a run and the percentile bands
+/// across every path, left out when there's no room or no complete path.
+fn render_trajectories(frame: &mut Frame, trajectories: &Trajectories, rect: Rect) {
+    if trajectories.paths.is_empty() || rect.height < 8 {
+        return;
+    }
+    let rounds = trajectories.rounds_at();
+    let points = |values: &[f64]| -> Vec<(f64, f64)> {
+        rounds.iter().zip(values).map(|(rounds, net)| (*rounds as f64, *net)).collect()
+    };
+    let samples: Vec<Vec<(f64, f64)>> = trajectories.paths.iter().take(SAMPLE_PATHS).map(|path| points(path)).collect();
+    let bands: Vec<Vec<(f64, f64)>> = TRAJECTORY_BANDS.iter().map(|(percentile, _, _)| points(&trajectories.percentile(*percentile))).collect();
+
+    let mut datasets: Vec<Dataset> = samples.iter()
+        .map(|sample| Dataset::default().marker(Marker::Braille).graph_type(GraphType::Line).fg(Color::DarkGray).data(sample))
+        .collect();
+    datasets.extend(TRAJECTORY_BANDS.iter().zip(&bands).map(|((_, name, color), band)| {
+        Dataset::default().name(*name).marker(Marker::Braille).graph_type(GraphType::Line).fg(*color).data(band)
+    }));
+
+    let (low, high) = trajectories.range();
+    let path_rounds = trajectories.path_rounds;
+    let chart = Chart::new(datasets)
+        .legend_position(Some(LegendPosition::TopLeft))
+        .hidden_legend_constraints((Constraint::Ratio(1, 4), Constraint::Ratio(1, 2)))
+        .x_axis(Axis::default()
+            .title("Rounds")
+            .bounds([0.0, path_rounds as f64])
+            .labels(["0".to_string(), format_count(path_rounds / 2), format_count(path_rounds)]))
+        .y_axis(Axis::default()
+            .title("Units")
+            .bounds([low, high])
+            .labels([format!("{:+.0}", low), format!("{:+.0}", (low + high) / 2.0), format!("{:+.0}", high)]));
+    frame.render_widget(chart, rect);
+}
+
+/// How sessions of the chosen length finished, the spread of results as
+/// bars from the worst session to the best.
+fn render_sessions(frame: &mut Frame, sessions: &Sessions, rect: Rect) {
+    if sessions.results.is_empty() || rect.height < 8 {
+        return;
+    }
+    let layout = Layout::vertical([Constraint::Length(2), Constraint::Min(0)]).split(rect);
+    let summary = vec![
+        Line::from(format!("{} sessions of {} hours: {:.0}% finished ahead", format_count(sessions.results.len() as u64), sessions.hours(), sessions.ahead() * 100.0)),
+        Line::from(format!("Average {:+.1} units, 5th to 95th percentile {:+.0} to {:+.0}", sessions.mean(), sessions.percentile(5.0), sessions.percentile(95.0))),
+    ];
+    frame.render_widget(Paragraph::new(summary).alignment(Alignment::Center), layout[0]);
+
+    let histogram = sessions.histogram(HISTOGRAM_BINS);
+    let bar_width = (layout[1].width / HISTOGRAM_BINS as u16).saturating_sub(1).max(1);
+    let bars = histogram.iter().map(|bin| {
+        let color = if bin.high <= 0.0 { Color::Red } else if bin.low >= 0.0 { Color::Green } else { Color::Yellow };
+        Bar::default()
+            .value(bin.count as u64)
+            .text_value(format_count(bin.count as u64))
+            .label(Line::from(format!("{:+.0}", bin.low)))
+            .style(Style::new().fg(color))
+            .value_style(Style::new().black().bg(color))
+    }).collect::<Vec<_>>();
+    let chart = BarChart::default()
+        .data(BarGroup::default().bars(&bars))
+        .bar_width(bar_width)
+        .bar_gap(1);
+    let chart_width = HISTOGRAM_BINS as u16 * (bar_width + 1);
+    frame.render_widget(chart, layout[1].inner(Margin {
+        vertical: 0,
+        horizontal: layout[1].width.saturating_sub(chart_width) / 2,
+    }));
+}
+
+/// The advantage played at each penetration of a sweep, as a table and a
+/// line rising as the cut moves deeper, the chart left out when there's no room.
+fn render_sweep(frame: &mut Frame, points: &[SweepPoint], rect: Rect) {
+    let mut lines = vec![Line::from(format!("{:>11}  {:>10}  {:>11}  {:>14}", "Penetration", "Avg Bet", "Advantage", "Units / Hour")).bold()];
+    lines.extend(points.iter().map(|point| {
+        let result = &point.result;
+        Line::from(format!("{:>10.0}%  {:>10.2}  {:>+10.3}%  {:>+14.2}", point.penetration * 100.0, result.average_bet(), result.advantage() * 100.0, result.per_hour()))
+    }));
+    let layout = Layout::vertical([
+        Constraint::Length(lines.len() as u16),
+        Constraint::Length(1),
+        Constraint::Min(0),
+    ]).split(rect);
+    frame.render_widget(Paragraph::new(lines).alignment(Alignment::Center), layout[0]);
+
+    if points.len() < 2 || layout[2].height < 8 {
+        return;
+    }
+    let advantages: Vec<(f64, f64)> = points.iter().map(|point| (point.penetration * 100.0, point.result.advantage() * 100.0)).collect();
+    let (low, high) = advantages.iter().fold((f64::INFINITY, f64::NEG_INFINITY), |(low, high), (_, advantage)| (low.min(*advantage), high.max(*advantage)));
+    let margin = ((high - low) / 10.0).max(0.05);
+    let (low, high) = (low - margin, high + margin);
+    let (first, last) = (advantages[0].0, advantages[advantages.len() - 1].0);
+    let datasets = vec![
+        Dataset::default().marker(Marker::Braille).graph_type(GraphType::Line).fg(Color::Green).data(&advantages),
+        Dataset::default().marker(Marker::Dot).graph_type(GraphType::Scatter).fg(Color::Yellow).data(&advantages),
+    ];
+    let chart = Chart::new(datasets)
+        .x_axis(Axis::default()
+            .title("Penetration")
+            .bounds([first, last])
+            .labels([format!("{:.0}%", first), format!("{:.0}%", (first + last) / 2.0), format!("{:.0}%", last)]))
+        .y_axis(Axis::default()
+            .title("Advantage")
+            .bounds([low, high])
+            .labels([format!("{:+.2}%", low), format!("{:+.2}%", (low + high) / 2.0), format!("{:+.2}%", high)]));
+    frame.render_widget(chart, layout[2]);
+}
+
+// ---- Simulation Screen ----
+/// Plays a strategy chart for many rounds on a background thread, showing
+/// how far it's got and what it's found as it goes.
+pub struct SimulationScreen {
+    /// Charts that can be simulated, by name
+    strategies: Vec<(String, BlackjackStrategy)>,
+
+    /// Position in `strategies`
+    strategy: usize,
+
+    /// Position in `strategies` of a chart to play head to head against it
+    versus: Option<usize>,
+
+    /// Position in `ROUND_OPTIONS`
+    rounds: usize,
+
+    /// Position in `SESSION_HOURS`
+    session_hours: usize,
+
+    /// Shuffles the same way every run when set
+    seed: Option<u64>,
+
+    /// Position in `COUNTING_SYSTEMS`, betting flat on basic strategy when none
+    counting: Option<usize>,
+
+    /// Position in `BET_SPREADS`
+    spread: usize,
+
+    /// Index sets to choose from, and the position among those for the
+    /// counting system of the one played, basic strategy when none
+    index_sets: Vec<IndexSet>,
+    deviations: Option<usize>,
+
+    /// Saved settings, and the position of the one last loaded or saved
+    presets: SimulationPresets,
+    preset: Option<usize>,
+
+    /// Name being typed to save the settings under
+    preset_input: Option<String>,
+
+    /// Position in `SIMULATION_OPTIONS`
+    active_option: usize,
+
+    /// Worked out house edge under the chart's rules, to check runs against
+    house_edge: f64,
+    run: Option<Run>,
+    finished: Option<Finished>,
+    swept: Option<Swept>,
+    compared: Option<Compared>,
+    view: ResultView,
+    message: String,
+}
+
+impl SimulationScreen {
+    pub fn new() -> Self {
+        let mut strategies: Vec<(String, BlackjackStrategy)> = load_strategies(&strategies_dir()).into_iter().collect();
+        strategies.sort_by(|a, b| a.0.cmp(&b.0));
+        let strategy = strategies.iter().position(|(name, _)| name == "default-strategy").unwrap_or(0);
+        let message = if strategies.is_empty() {
+            format!("No strategies found in {}", &strategies_dir())
+        } else {
+            "Press Enter to start a run".to_string()
+        };
+        let mut screen = Self {
+            strategies,
+            strategy,
+            versus: None,
+            rounds: DEFAULT_ROUNDS,
+            session_hours: DEFAULT_SESSION_HOURS,
+            seed: None,
+            counting: None,
+            spread: DEFAULT_BET_SPREAD,
+            index_sets: load_index_sets(&deviations_dir()),
+            deviations: None,
+            presets: SimulationPresets::load(&data_file(SIMULATION_PRESETS_FILE)),
+            preset: None,
+            preset_input: None,
+            active_option: 0,
+            house_edge: 0.0,
+            run: None,
+            finished: None,
+            swept: None,
+            compared: None,
+            view: ResultView::Paths,
+            message,
+        };
+        screen.update_house_edge();
+        screen
+    }
+
+    fn update_house_edge(&mut self) {
+        if let Some((_, strategy)) = self.strategies.get(self.strategy) {
+            // Simulations are played at a standard table
+            self.house_edge = house_edge(&strategy.rules, &TableConfig::default());
+        }
+    }
+
+    fn is_running(&self) -> bool {
+        self.run.is_some()
+    }
+
+    /// Index sets for the counting system being played.
+    fn available_sets(&self) -> Vec<&IndexSet> {
+        let Some(counting) = self.counting else {
+            return vec![];
+        };
+        let system = COUNTING_SYSTEMS[counting].name();
+        self.index_sets.iter().filter(|set| set.system == system).collect()
+    }
+
+    fn counting_play(&self) -> Option<CountingPlay> {
+        let mut play = CountingPlay::new(COUNTING_SYSTEMS[self.counting?], BET_SPREADS[self.spread]);
+        play.deviations = self.deviations.and_then(|position| self.available_sets().get(position).map(|set| (*set).clone()));
+        Some(play)
+    }
+
+    /// Steps through `len` choices and none, none coming before the first.
+    fn step_optional(position: Option<usize>, step: isize, len: usize) -> Option<usize> {
+        let stepped = (position.map_or(0, |position| position as isize + 1) + step).rem_euclid(len as isize + 1);
+        stepped.checked_sub(1).map(|position| position as usize)
+    }
+
+    fn change_option(&mut self, step: isize) {
+        match SIMULATION_OPTIONS[self.active_option] {
+            SimulationOption::Preset => {
+                self.preset = Self::step_optional(self.preset, step, self.presets.presets().len());
+                if let Some(position) = self.preset {
+                    self.load_preset(position);
+                }
+            }
+            SimulationOption::Strategy if !self.strategies.is_empty() => {
+                self.strategy = (self.strategy as isize + step).rem_euclid(self.strategies.len() as isize) as usize;
+                self.update_house_edge();
+            }
+            SimulationOption::Strategy => {}
+            SimulationOption::Versus => {
+                self.versus = Self::step_optional(self.versus, step, self.strategies.len());
+            }
+            SimulationOption::Rounds => {
+                self.rounds = self.rounds.saturating_add_signed(step).min(ROUND_OPTIONS.len() - 1);
+            }
+            SimulationOption::Session => {
+                self.session_hours = self.session_hours.saturating_add_signed(step).min(SESSION_HOURS.len() - 1);
+            }
+            SimulationOption::Counting => {
+                self.counting = Self::step_optional(self.counting, step, COUNTING_SYSTEMS.len());
+                self.deviations = None;
+            }
+            SimulationOption::BetSpread => {
+                self.spread = (self.spread as isize + step).rem_euclid(BET_SPREADS.len() as isize) as usize;
+            }
+            SimulationOption::Deviations => {
+                self.deviations = Self::step_optional(self.deviations, step, self.available_sets().len());
+            }
+            // Stepping down from the first seed goes back to random shuffles
+            SimulationOption::Seed => {
+                self.seed = match self.seed {
+                    None if step > 0 => Some(1),
+                    None => None,
+                    Some(seed) => seed.checked_add_signed(step as i64).filter(|seed| *seed > 0),
+                };
+            }
+        }
+    }
+
+    // ---- Presets ----
+    /// The settings as a preset saved under `name`.
+    fn preset_named(&self, name: &str) -> Option<SimulationPreset> {
+        let (strategy, _) = self.strategies.get(self.strategy)?;
+        let play = self.counting_play();
+        Some(SimulationPreset {
+            name: name.to_string(),
+            strategy: strategy.clone(),
+            versus: self.versus.and_then(|versus| self.strategies.get(versus)).map(|(name, _)| name.clone()),
+            rounds: ROUND_OPTIONS[self.rounds],
+            session_hours: SESSION_HOURS[self.session_hours],
+            seed: self.seed,
+            counting: play.as_ref().map(|play| play.system.name().to_string()),
+            spread: BET_SPREADS[self.spread].max_units,
+            deviations: play.and_then(|play| play.deviations).map(|deviations| deviations.name),
+        })
+    }
+
+    /// Sets everything to the preset's settings, leaving them as they were
+    /// when one of them can't be found any more.
+    fn load_preset(&mut self, position: usize) {
+        let Some(preset) = self.presets.presets().get(position) else {
+            return;
+        };
+        let missing = |what: &str, value: String| format!("Preset {} uses {} {}, which isn't available", preset.name, what, value);
+        let Some(strategy) = self.strategies.iter().position(|(name, _)| *name == preset.strategy) else {
+            self.message = missing("the strategy", preset.strategy.clone());
+            return;
+        };
+        let Some(rounds) = ROUND_OPTIONS.iter().position(|rounds| *rounds == preset.rounds) else {
+            self.message = missing("a run of", format_count(preset.rounds));
+            return;
+        };
+        let Some(session_hours) = SESSION_HOURS.iter().position(|hours| *hours == preset.session_hours) else {
+            self.message = missing("sessions of", format!("{} hours", preset.session_hours));
+            return;
+        };
+        let Some(spread) = BET_SPREADS.iter().position(|spread| spread.max_units == preset.spread) else {
+            self.message = missing("a spread of", format!("1-{}", preset.spread));
+            return;
+        };
+        let counting = match &preset.counting {
+            Some(system) => match COUNTING_SYSTEMS.iter().position(|counting| counting.name() == system) {
+                Some(counting) => Some(counting),
+                None => {
+                    self.message = missing("the counting system", system.clone());
+                    return;
+                }
+            },
+            None => None,
+        };
+        let versus = match &preset.versus {
+            Some(versus) => match self.strategies.iter().position(|(name, _)| name == versus) {
+                Some(versus) => Some(versus),
+                None => {
+                    self.message = missing("the strategy", versus.clone());
+                    return;
+                }
+            },
+            None => None,
+        };
+        let name = preset.name.clone();
+        let deviations = preset.deviations.clone();
+
+        self.strategy = strategy;
+        self.versus = versus;
+        self.rounds = rounds;
+        self.session_hours = session_hours;
+        self.seed = preset.seed;
+        self.counting = counting;
+        self.spread = spread;
+        self.deviations = None;
+        self.update_house_edge();
+        if let Some(deviations) = deviations {
+            match self.available_sets().iter().position(|set| set.name == deviations) {
+                Some(position) => self.deviations = Some(position),
+                None => {
+                    self.message = format!("Loaded {}, but the {} index set isn't available", name, deviations);
+                    return;
+                }
+            }
+        }
+        self.message = format!("Loaded {}", name);
+    }
+
+    fn start_preset_input(&mut self) {
+        let name = self.preset.and_then(|position| self.presets.presets().get(position)).map(|preset| preset.name.clone());
+        self.preset_input = Some(name.unwrap_or_default());
+        self.message = "Type a name and press Enter to save, Esc to cancel".to_string();
+    }
+
+    /// Typing a preset's name takes every key until it's saved or cancelled.
+    fn update_preset_input(&mut self, key: KeyCode) {
+        let Some(input) = self.preset_input.as_mut() else {
+            return;
+        };
+        match key {
+            KeyCode::Enter if input.trim().is_empty() => self.message = "Type a name to save the settings under".to_string(),
+            KeyCode::Enter => {
+                let name = input.trim().to_string();
+                self.preset_input = None;
+                let Some(preset) = self.preset_named(&name) else {
+                    return;
+                };
+                self.preset = Some(self.presets.insert(preset));
+                self.message = match self.presets.save(&data_file(SIMULATION_PRESETS_FILE)) {
+                    Ok(()) => format!("Saved {}", name),
+                    Err(err) => format!("Couldn't save presets: {}", err),
+                };
+            }
+            KeyCode::Esc => {
+                self.preset_input = None;
+                self.message.clear();
+            }
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Char(c) if input.chars().count() < MAX_PRESET_NAME_LENGTH => input.push(c),
+            _ => {}
+        }
+    }
+
+    fn delete_preset(&mut self) {
+        let Some(preset) = self.preset.and_then(|position| self.presets.remove(position)) else {
+            return;
+        };
+        self.preset = None;
+        self.message = match self.presets.save(&data_file(SIMULATION_PRESETS_FILE)) {
+            Ok(()) => format!("Deleted {}", preset.name),
+            Err(err) => format!("Couldn't save presets: {}", err),
+        };
+    }
+
+    /// The game the settings describe, with a name for it like
+    /// "default-strategy, Hi-Lo 1-8".
+    fn config(&self) -> Option<(String, SimulationConfig)> {
+        let (name, strategy) = self.strategies.get(self.strategy)?;
+        let mut config = SimulationConfig::for_strategy(strategy.clone(), ROUND_OPTIONS[self.rounds], self.seed);
+        config.counting = self.counting_play();
+        let label = match &config.counting {
+            Some(CountingPlay { system, spread, deviations: Some(deviations) }) => format!("{}, {} {} with the {}", name, system.name(), spread, deviations.name),
+            Some(CountingPlay { system, spread, deviations: None }) => format!("{}, {} {}", name, system.name(), spread),
+            None => name.clone(),
+        };
+        Some((label, config))
+    }
+
+    /// Starts playing the chosen chart on another thread.
+    fn start(&mut self) {
+        let Some((label, config)) = self.config() else {
+            return;
+        };
+        let baseline = config.counting.is_some().then(|| SimulationConfig { counting: None, ..config.clone() });
+        let session_rounds = SESSION_HOURS[self.session_hours] * ROUNDS_PER_HOUR;
+        let rounds = ROUND_OPTIONS[self.rounds];
+        let played = config.clone();
+        self.run = Some(Run {
+            run: BackgroundRun::start(move |progress| {
+                let detail = simulate_in_detail(played, session_rounds, progress)?;
+                let baseline = baseline.map(|baseline| simulate_with_progress(baseline, progress)).transpose()?;
+                Ok(Outcome::Single(detail, baseline))
+            }),
+            config,
+            strategy: label,
+            rounds,
+            total: if self.counting.is_some() { rounds * 2 } else { rounds },
+        });
+        self.message = "Press C to cancel".to_string();
+    }
+
+    /// Starts playing the chosen game at every penetration of a sweep.
+    fn start_sweep(&mut self) {
+        let Some((label, config)) = self.config() else {
+            return;
+        };
+        let rounds = ROUND_OPTIONS[self.rounds];
+        let played = config.clone();
+        self.run = Some(Run {
+            run: BackgroundRun::start(move |progress| sweep_penetration(&played, progress).map(Outcome::Sweep)),
+            config,
+            strategy: label,
+            rounds,
+            total: rounds * SWEEP_PENETRATIONS.len() as u64,
+        });
+        self.message = "Press C to cancel".to_string();
+    }
+
+    /// Writes the last run's full results to JSON and CSV files in the exports directory.
+    fn export(&mut self) {
+        let Some(finished) = &self.finished else {
+            return;
+        };
+        let report = SimulationReport::new(&finished.strategy, &finished.config, &finished.detail, finished.baseline.as_ref());
+        let dir = &exports_dir();
+        self.message = match export_report_json(&report, dir).and_then(|json| Ok((json, export_report_csv(&report, dir)?))) {
+            Ok((json, csv)) => format!("Wrote {} and {} CSV files", json.display(), csv.len()),
+            Err(err) => err,
+        };
+    }
+
+    /// Starts playing the chart set under Versus against the chosen one on
+    /// the same cards, under the chosen chart's rules.
+    fn start_head_to_head(&mut self) {
+        let Some((first, config)) = self.config() else {
+            return;
+        };
+        let Some((second, strategy)) = self.versus.and_then(|versus| self.strategies.get(versus)).cloned() else {
+            self.message = "Choose a strategy under Versus to play head to head".to_string();
+            return;
+        };
+        let rounds = ROUND_OPTIONS[self.rounds];
+        let played = config.clone();
+        let label = format!("{} against {}", second, first);
+        self.run = Some(Run {
+            run: BackgroundRun::start(move |progress| {
+                let result = head_to_head(played, strategy, progress)?;
+                Ok(Outcome::HeadToHead { result, first, second })
+            }),
+            config,
+            strategy: label,
+            rounds,
+            total: rounds,
+        });
+        self.message = "Press C to cancel".to_string();
+    }
+
+    fn cancel(&mut self) {
+        if let Some(run) = &self.run {
+            run.run.progress().cancel();
+            self.message = "Cancelling...".to_string();
+        }
+    }
+
+    /// Picks up the result once the run's thread is done.
+    fn check_run(&mut self) {
+        if !self.run.as_ref().is_some_and(|run| run.run.is_finished()) {
+            return;
+        }
+        let Some(Run { run, config, strategy, rounds, .. }) = self.run.take() else {
+            return;
+        };
+        let took = run.elapsed();
+        match run.join() {
+            Ok(Outcome::Single(detail, baseline)) => {
+                let finished = Finished { detail, config, baseline, strategy, rounds, took };
+                self.message = if finished.cancelled() { "Cancelled" } else { "Finished" }.to_string();
+                self.finished = Some(finished);
+                self.swept = None;
+                self.compared = None;
+            }
+            Ok(Outcome::Sweep(points)) => {
+                self.message = if points.len() < SWEEP_PENETRATIONS.len() { "Cancelled" } else { "Finished" }.to_string();
+                self.swept = Some(Swept { points, strategy, rounds, took });
+                self.finished = None;
+                self.compared = None;
+            }
+            Ok(Outcome::HeadToHead { result, first, second }) => {
+                self.message = if result.first.rounds < rounds { "Cancelled" } else { "Finished" }.to_string();
+                self.compared = Some(Compared { result, first, second, rounds, took });
+                self.finished = None;
+                self.swept = None;
+            }
+            Err(err) => self.message = err,
+        }
+    }
+
+    fn render_settings(&self, frame: &mut Frame, rect: Rect) {
+        let mut lines: Vec<Line> = vec![];
+        for (index, option) in SIMULATION_OPTIONS.iter().enumerate() {
+            let value = match option {
+                SimulationOption::Preset => self.preset
+                    .and_then(|position| self.presets.presets().get(position).map(|preset| preset.name.clone()))
+                    .unwrap_or("None".to_string()),
+                SimulationOption::Strategy => self.strategies.get(self.strategy).map_or("None".to_string(), |(name, _)| name.clone()),
+                SimulationOption::Versus => self.versus.and_then(|versus| self.strategies.get(versus)).map_or("None".to_string(), |(name, _)| name.clone()),
+                SimulationOption::Rounds => format_count(ROUND_OPTIONS[self.rounds]),
+                SimulationOption::Session => format!("{} hours", SESSION_HOURS[self.session_hours]),
+                SimulationOption::Seed => self.seed.map_or("Random".to_string(), |seed| seed.to_string()),
+                SimulationOption::Counting => self.counting.map_or("Off".to_string(), |counting| COUNTING_SYSTEMS[counting].name().to_string()),
+                SimulationOption::BetSpread if self.counting.is_none() => "Flat".to_string(),
+                SimulationOption::BetSpread => BET_SPREADS[self.spread].to_string(),
+                SimulationOption::Deviations => self.deviations
+                    .and_then(|position| self.available_sets().get(position).map(|set| set.name.clone()))
+                    .unwrap_or("None".to_string()),
+            };
+            let selected = index == self.active_option && !self.is_running();
+            let text = format!("{}{}: < {} >", if selected { "> " } else { "" }, option, value);
+            lines.push(Line::from(""));
+            lines.push(if selected { Line::from(text).fg(Color::Green) } else { Line::from(text) });
+        }
+
+        if let Some((_, strategy)) = self.strategies.get(self.strategy) {
+            lines.push(Line::from(""));
+            lines.push(Line::from(strategy.rules.to_string()).fg(Color::Cyan));
+            lines.push(Line::from(""));
+            lines.push(Line::from(format!("House Edge: {:.2}%", self.house_edge * 100.0)).fg(Color::Yellow));
+        }
+
+        let settings = Paragraph::new(lines)
+            .bold()
+            .alignment(Alignment::Center)
+            .block(Block::default());
+        frame.render_widget(settings, rect);
+    }
+
+    /// The run's progress bar and what it's found so far, or how the last
+    /// run went.
+    fn render_run(&self, frame: &mut Frame, rect: Rect) {
+        let layout = Layout::vertical([
+            Constraint::Length(2),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Min(0),
+            Constraint::Length(2),
+        ]).split(rect.inner(Margin { vertical: 0, horizontal: 2 }));
+
+        if let Some(run) = &self.run {
+            let so_far = run.run.progress().so_far();
+            frame.render_widget(Paragraph::new(run.strategy.as_str()).bold().alignment(Alignment::Center), layout[1]);
+            let ratio = (so_far.rounds as f64 / run.total as f64).clamp(0.0, 1.0);
+            let gauge = Gauge::default()
+                .gauge_style(Color::Green)
+                .ratio(ratio)
+                .label(format!("{} of {} rounds", format_count(so_far.rounds), format_count(run.total)));
+            frame.render_widget(gauge, layout[3]);
+            let lines = vec![
+                Line::from(""),
+                Line::from(format!("EV So Far: {}", format_ev(&so_far))),
+                Line::from(format!("Rounds / Second: {}", format_count(throughput(so_far.rounds, run.run.elapsed()) as u64))),
+                Line::from(format!("Elapsed: {:.1}s", run.run.elapsed().as_secs_f64())),
+            ];
+            frame.render_widget(Paragraph::new(lines).alignment(Alignment::Center), layout[4]);
+        } else if let Some(finished) = &self.finished {
+            let result = &finished.detail.result;
+            let (low, high) = result.ev_interval();
+            frame.render_widget(Paragraph::new(finished.strategy.as_str()).bold().alignment(Alignment::Center), layout[1]);
+            let mut lines = vec![
+                Line::from(format!("Rounds Played: {} of {}", format_count(result.rounds), format_count(finished.rounds))),
+                Line::from(""),
+                Line::from(format!("EV Per Round: {}", format_ev(result))).bold(),
+                Line::from(format!("95% Confidence: {:+.3}% to {:+.3}%", low * 100.0, high * 100.0)),
+                Line::from(format!("Standard Deviation: {:.3} units a round", result.std_dev())),
+                Line::from(format!("Per 100 Rounds: {:.2} units", result.std_dev_per_100())),
+                Line::from(format!("Won: {:.2}%  Pushed: {:.2}%  Lost: {:.2}%", result.win_rate() * 100.0, result.push_rate() * 100.0, result.loss_rate() * 100.0)),
+                Line::from(format!("Per Hour: {:+.2} units at {} rounds an hour", result.per_hour(), ROUNDS_PER_HOUR)),
+            ];
+            if let Some(baseline) = finished.baseline.as_ref().filter(|baseline| baseline.rounds > 0) {
+                lines.extend([
+                    Line::from(""),
+                    Line::from(format!("Average Bet: {:.2} units  Advantage: {:+.3}%", result.average_bet(), result.advantage() * 100.0)).bold(),
+                    Line::from(format!("Flat Basic Strategy: {:+.3}% a round, {:+.2} units an hour", baseline.ev() * 100.0, baseline.per_hour())),
+                    Line::from(format!("Counting Gains: {:+.2} units an hour", result.per_hour() - baseline.per_hour())).fg(Color::Green),
+                ]);
+            }
+            lines.extend([
+                Line::from(""),
+                Line::from(format!("Took {:.1}s at {} rounds a second", finished.took.as_secs_f64(), format_count(throughput(result.rounds, finished.took) as u64))),
+            ]);
+            if finished.cancelled() {
+                lines.push(Line::from(""));
+                lines.push(Line::from("Cancelled before every round was played").fg(Color::Yellow));
+            }
+            let sections = Layout::vertical([
+                Constraint::Length(lines.len() as u16),
+                Constraint::Length(1),
+                Constraint::Min(0),
+            ]).split(layout[3].union(layout[4]));
+            frame.render_widget(Paragraph::new(lines).alignment(Alignment::Center), sections[0]);
+            match self.view {
+                ResultView::Paths => render_trajectories(frame, &finished.detail.trajectories, sections[2]),
+                ResultView::Sessions => render_sessions(frame, &finished.detail.sessions, sections[2]),
+            }
+        } else if let Some(swept) = &self.swept {
+            let rounds = swept.points.iter().map(|point| point.result.rounds).sum::<u64>();
+            frame.render_widget(Paragraph::new(swept.strategy.as_str()).bold().alignment(Alignment::Center), layout[1]);
+            let mut lines = vec![
+                Line::from(format!("{} rounds at each of {} penetrations", format_count(swept.rounds), swept.points.len())),
+                Line::from(format!("Took {:.1}s at {} rounds a second", swept.took.as_secs_f64(), format_count(throughput(rounds, swept.took) as u64))),
+            ];
+            if swept.points.len() < SWEEP_PENETRATIONS.len() {
+                lines.push(Line::from("Cancelled before every penetration was played").fg(Color::Yellow));
+            }
+            let sections = Layout::vertical([
+                Constraint::Length(lines.len() as u16),
+                Constraint::Length(1),
+                Constraint::Min(0),
+            ]).split(layout[3].union(layout[4]));
+            frame.render_widget(Paragraph::new(lines).alignment(Alignment::Center), sections[0]);
+            render_sweep(frame, &swept.points, sections[2]);
+        } else if let Some(compared) = &self.compared {
+            let result = &compared.result;
+            let (low, high) = result.delta_interval();
+            let title = format!("{} against {}", compared.second, compared.first);
+            frame.render_widget(Paragraph::new(title).bold().alignment(Alignment::Center), layout[1]);
+            let mut lines = vec![
+                Line::from(format!("Rounds Played: {} of {}, the same cards to both", format_count(result.first.rounds), format_count(compared.rounds))),
+                Line::from(""),
+                Line::from(format!("{}: {}", compared.first, format_ev(&result.first))),
+                Line::from(format!("{}: {}", compared.second, format_ev(&result.second))),
+                Line::from(""),
+                Line::from(format!("EV Difference: {:+.3}% ± {:.3}%", result.ev_delta() * 100.0, (high - result.ev_delta()) * 100.0)).bold(),
+                Line::from(format!("95% Confidence: {:+.3}% to {:+.3}%", low * 100.0, high * 100.0)),
+                Line::from(format!("On Their Own Shoes: ± {:.3}%, {:.0} times the rounds for the same margin", CONFIDENCE_Z * result.unpaired_error() * 100.0, result.variance_reduction())),
+                Line::from(format!("Better: {:.2}%  Same: {:.2}%  Worse: {:.2}%", result.difference.win_rate() * 100.0, result.difference.push_rate() * 100.0, result.difference.loss_rate() * 100.0)),
+                Line::from(""),
+                Line::from(format!("Took {:.1}s at {} rounds a second", compared.took.as_secs_f64(), format_count(throughput(result.first.rounds, compared.took) as u64))),
+            ];
+            if result.first.rounds < compared.rounds {
+                lines.push(Line::from(""));
+                lines.push(Line::from("Cancelled before every round was played").fg(Color::Yellow));
+            }
+            frame.render_widget(Paragraph::new(lines).alignment(Alignment::Center), layout[3].union(layout[4]));
+        }
+
+        let message = match &self.preset_input {
+            Some(input) => vec![Line::from(format!("Save Preset As: {}_", input)), Line::from(self.message.as_str())],
+            None => vec![Line::from(self.message.as_str())],
+        };
+        frame.render_widget(Paragraph::new(message).bold().fg(Color::Yellow).alignment(Alignment::Center), layout[5]);
+    }
+
+    fn footer_spans(&self) -> Vec<String> {
+        let mut spans = vec![
+            " Q ".to_string(), " Quit ".to_string(),
+            " M ".to_string(), " Menu ".to_string(),
+        ];
+        if self.is_running() {
+            spans.extend([" C ".to_string(), " Cancel ".to_string()]);
+        } else {
+            spans.extend([
+                " J/↓ ".to_string(), " Down ".to_string(),
+                " K/↑ ".to_string(), " Up ".to_string(),
+                " H/← L/→ ".to_string(), " Change ".to_string(),
+                " Enter ".to_string(), " Run ".to_string(),
+                " P ".to_string(), " Sweep Penetration ".to_string(),
+                " S ".to_string(), " Save Preset ".to_string(),
+            ]);
+            if self.versus.is_some() {
+                spans.extend([" T ".to_string(), " Head to Head ".to_string()]);
+            }
+            if self.preset.is_some() && SIMULATION_OPTIONS[self.active_option] == SimulationOption::Preset {
+                spans.extend([" D ".to_string(), " Delete Preset ".to_string()]);
+            }
+            if self.finished.is_some() {
+                spans.extend([" V ".to_string(), format!(" {} ", self.view.other())]);
+                spans.extend([" E ".to_string(), " Export ".to_string()]);
+            }
+        }
+        spans
+    }
+}
+
+// ---- TRAIT IMPLEMENTATIONS ----
+/// Leaving the screen cancels any run still going.
+impl Drop for SimulationScreen {
+    fn drop(&mut self) {
+        self.cancel();
+    }
+}
+
+impl Model for SimulationScreen {
+    fn update(&mut self) -> std::io::Result<ModelResponse> {
+        if let Event::Key(key) = read_event(self.preset_input.is_some())? {
+            if key.kind == event::KeyEventKind::Release {
+                return Ok(ModelResponse::Refresh);
+            }
+            if self.preset_input.is_some() {
+                self.update_preset_input(key.code);
+                return Ok(ModelResponse::Refresh);
+            }
+            match key.code {
+                KeyCode::Char('q') => return Ok(ModelResponse::Exit),
+                KeyCode::Char('m') => return Ok(ModelResponse::NavToMainMenu),
+                KeyCode::Char('c') | KeyCode::Esc if self.is_running() => self.cancel(),
+                _ if self.is_running() => {}
+                KeyCode::Char('j') | KeyCode::Down => {
+                    self.active_option = (self.active_option + 1) % SIMULATION_OPTIONS.len();
+                }
+                KeyCode::Char('k') | KeyCode::Up => {
+                    self.active_option = (self.active_option + SIMULATION_OPTIONS.len() - 1) % SIMULATION_OPTIONS.len();
+                }
+                KeyCode::Char('h') | KeyCode::Left => self.change_option(-1),
+                KeyCode::Char('l') | KeyCode::Right => self.change_option(1),
+                KeyCode::Char('v') => self.view = self.view.other(),
+                KeyCode::Enter => self.start(),
+                KeyCode::Char('p') => self.start_sweep(),
+                KeyCode::Char('e') => self.export(),
+                KeyCode::Char('t') => self.start_head_to_head(),
+                KeyCode::Char('s') => self.start_preset_input(),
+                KeyCode::Char('d') if SIMULATION_OPTIONS[self.active_option] == SimulationOption::Preset => self.delete_preset(),
+                _ => {}
+            }
+        }
+        Ok(ModelResponse::Refresh)
+    }
+
+    fn ui(&mut self, frame: &mut Frame) {
+        let main_chunks = create_common_layout(frame.area());
+        let horizontal_chunks = split_content_horizontally(main_chunks[1]);
+
+        let left_section = horizontal_chunks[0];
+        render_border(frame, left_section);
+        render_centered_text(frame, left_section, " Simulation Settings ");
+        self.render_settings(frame, left_section.inner(Margin { vertical: 1, horizontal: 1 }));
+
+        let right_section = horizontal_chunks[1];
+        render_border(frame, right_section);
+        render_centered_text(frame, right_section, " Simulation ");
+        self.render_run(frame, right_section.inner(Margin { vertical: 1, horizontal: 1 }));
+
+        // Render Footer
+        let footer_layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Length(5),
+                Constraint::Min(20),
+            ])
+            .split(main_chunks[2]);
+        render_key_spans(frame, self.footer_spans(), footer_layout[1]);
+    }
+
+    fn tick(&mut self) {
+        self.check_run();
+    }
+}
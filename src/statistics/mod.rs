@@ -0,0 +1 @@
+pub mod statistics_screen;
\ No newline at end of file
@@ -0,0 +1,380 @@
+use crate::constants::HISTORY_DATABASE_FILE;
+use crate::logic::app_dirs::{data_file, exports_dir};
+use crate::logic::counting_stats::{format_day, SECONDS_PER_DAY};
+use crate::logic::decision_accuracy::DecisionAccuracy;
+use crate::logic::history_store::{export_history_csv, HandTotals, HistoryStore, LifetimeStats, SessionSummary, Tally};
+use crate::model::{Model, ModelResponse};
+use crate::ui::{create_common_layout, dollars, render_border, render_centered_text, render_key_spans, read_event};
+use ratatui::crossterm::event;
+use ratatui::crossterm::event::{Event, KeyCode};
+use ratatui::layout::{Alignment, Constraint, Direction, Layout, Margin, Rect};
+use ratatui::prelude::{Line, Stylize};
+use ratatui::style::Color;
+use ratatui::symbols::Marker;
+use ratatui::widgets::{Axis, Block, Chart, Dataset, GraphType, LegendPosition, Paragraph, Row, Table, Wrap};
+use ratatui::Frame;
+use std::fmt;
+
+/// Plays a chart cell needs before it's listed among the weakest
+const MIN_CELL_PLAYS: u32 = 3;
+
+/// What the screen is showing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum StatisticsView {
+    Totals,
+    Graphs,
+    Cells,
+}
+
+impl StatisticsView {
+    fn next(self) -> Self {
+        match self {
+            StatisticsView::Totals => StatisticsView::Graphs,
+            StatisticsView::Graphs => StatisticsView::Cells,
+            StatisticsView::Cells => StatisticsView::Totals,
+        }
+    }
+}
+
+impl fmt::Display for StatisticsView {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            StatisticsView::Totals => "Totals",
+            StatisticsView::Graphs => "Graphs",
+            StatisticsView::Cells => "Chart Cells",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// How many were right out of how many, and the share of them, e.g.
+/// "92.5% (37 / 40)", or a dash when there weren't any.
+fn accuracy(tally: Tally) -> String {
+    match tally.accuracy() {
+        Some(accuracy) => format!("{:.1}% ({} / {})", accuracy * 100.0, tally.correct, tally.total),
+        None => "-".to_string(),
+    }
+}
+
+/// Net result coloured by whether it's a win or a loss.
+fn net_line(label: &str, net: f64) -> Line<'static> {
+    Line::from(format!("{}: {}", label, dollars(net))).fg(if net >= 0.0 { Color::Green } else { Color::Red })
+}
+
+/// The lines for the hands played under one variant or all of them.
+fn hand_lines(totals: &HandTotals) -> Vec<Line<'static>> {
+    let mut lines = vec![
+        Line::from(format!("Hands Played: {}", totals.hands)),
+        net_line("Net Result", totals.net),
+        Line::from(format!("Wagered: {}", dollars(totals.wagered))),
+    ];
+    if totals.wagered > 0.0 {
+        lines.push(Line::from(format!("Result per $100 Wagered: {}", dollars(totals.net / totals.wagered * 100.0))));
+    }
+    lines.push(Line::from(format!("Decisions Right: {}", accuracy(totals.decisions))));
+    lines
+}
+
+/// The net result summed session by session against the hands played so
+/// far, left out when there's no room or no hands.
+fn render_cumulative_result(frame: &mut Frame, sessions: &[SessionSummary], rect: Rect) {
+    let mut points = vec![(0.0, 0.0)];
+    for session in sessions.iter().filter(|session| session.hands > 0) {
+        let (hands, net) = points[points.len() - 1];
+        points.push((hands + session.hands as f64, net + session.net));
+    }
+    if points.len() < 2 || rect.height < 8 {
+        frame.render_widget(Paragraph::new("No hands played yet").alignment(Alignment::Center), rect);
+        return;
+    }
+    let (hands, net) = points[points.len() - 1];
+    let (low, high) = points.iter().fold((0.0_f64, 0.0_f64), |(low, high), (_, net)| (low.min(*net), high.max(*net)));
+    let margin = ((high - low) / 10.0).max(1.0);
+    let (low, high) = (low - margin, high + margin);
+    let datasets = vec![
+        Dataset::default().marker(Marker::Braille).graph_type(GraphType::Line).fg(if net >= 0.0 { Color::Green } else { Color::Red }).data(&points),
+    ];
+    let chart = Chart::new(datasets)
+        .x_axis(Axis::default()
+            .title("Hands")
+            .bounds([0.0, hands])
+            .labels(["0".to_string(), format!("{:.0}", hands / 2.0), format!("{:.0}", hands)]))
+        .y_axis(Axis::default()
+            .title("Net")
+            .bounds([low, high])
+            .labels([dollars(low), dollars((low + high) / 2.0), dollars(high)]));
+    frame.render_widget(chart, rect);
+}
+
+/// Basic strategy and counting accuracy in each session, oldest first,
+/// left out when there's no room or nothing has been graded.
+fn render_accuracy(frame: &mut Frame, sessions: &[SessionSummary], rect: Rect) {
+    let points = |tally: fn(&SessionSummary) -> Tally| -> Vec<(f64, f64)> {
+        sessions.iter().enumerate()
+            .filter_map(|(index, session)| tally(session).accuracy().map(|accuracy| (index as f64 + 1.0, accuracy * 100.0)))
+            .collect()
+    };
+    let strategy = points(|session| session.strategy);
+    let counting = points(|session| session.counting);
+    if (strategy.is_empty() && counting.is_empty()) || rect.height < 8 {
+        frame.render_widget(Paragraph::new("No decisions graded or drills answered yet").alignment(Alignment::Center), rect);
+        return;
+    }
+    let datasets = vec![
+        Dataset::default().name("Basic Strategy").marker(Marker::Braille).graph_type(GraphType::Line).fg(Color::Cyan).data(&strategy),
+        Dataset::default().marker(Marker::Dot).graph_type(GraphType::Scatter).fg(Color::Cyan).data(&strategy),
+        Dataset::default().name("Counting").marker(Marker::Braille).graph_type(GraphType::Line).fg(Color::Yellow).data(&counting),
+        Dataset::default().marker(Marker::Dot).graph_type(GraphType::Scatter).fg(Color::Yellow).data(&counting),
+    ];
+    let day = |session: &SessionSummary| format_day(session.started_at / SECONDS_PER_DAY);
+    let chart = Chart::new(datasets)
+        .legend_position(Some(LegendPosition::BottomRight))
+        .hidden_legend_constraints((Constraint::Ratio(1, 3), Constraint::Ratio(1, 2)))
+        .x_axis(Axis::default()
+            .title("Sessions")
+            .bounds([0.5, sessions.len() as f64 + 0.5])
+            .labels([day(&sessions[0]), day(&sessions[sessions.len() - 1])]))
+        .y_axis(Axis::default()
+            .title("Right")
+            .bounds([0.0, 100.0])
+            .labels(["0%", "50%", "100%"]));
+    frame.render_widget(chart, rect);
+}
+
+// ---- Statistics Screen ----
+/// Totals over every session kept in the history database: hands played,
+/// how they came out and how well they were played, and how the drills
+/// have gone.
+pub struct StatisticsScreen {
+    /// What was added up when the screen opened, or why it couldn't be
+    stats: Result<LifetimeStats, String>,
+
+    /// Every session played, oldest first, for the graphs
+    sessions: Vec<SessionSummary>,
+
+    /// Right and wrong plays in every chart cell, at the table and in the drills
+    accuracy: DecisionAccuracy,
+
+    view: StatisticsView,
+    message: String,
+}
+
+impl StatisticsScreen {
+    pub fn new() -> Self {
+        let read = HistoryStore::open(&data_file(HISTORY_DATABASE_FILE), "Statistics")
+            .and_then(|store| Ok((store.lifetime_stats()?, store.sessions()?, store.decision_accuracy()?)))
+            .map_err(|err| format!("Could not read the play history: {}", err));
+        let (stats, sessions, accuracy) = match read {
+            Ok((stats, sessions, accuracy)) => (Ok(stats), sessions, accuracy),
+            Err(err) => (Err(err), vec![], DecisionAccuracy::default()),
+        };
+        Self { stats, sessions, accuracy, view: StatisticsView::Totals, message: String::new() }
+    }
+
+    /// Writes every session, hand and drill answer to CSV files in the exports directory.
+    fn export(&mut self) {
+        let exported = HistoryStore::open(&data_file(HISTORY_DATABASE_FILE), "Statistics")
+            .map_err(|err| format!("Couldn't open the play history: {}", err))
+            .and_then(|store| export_history_csv(&store, &exports_dir()));
+        self.message = match exported {
+            Ok(paths) => format!("Wrote {} CSV files to {}", paths.len(), exports_dir().display()),
+            Err(err) => err,
+        };
+    }
+
+    /// Hands played and accuracy over everything, then the drills one by one.
+    fn overview_lines(stats: &LifetimeStats) -> Vec<Line<'static>> {
+        let mut lines = vec![
+            Line::from(""),
+            Line::from(format!("Sessions: {}", stats.sessions)),
+            Line::from(""),
+            Line::from("At the Table").bold(),
+        ];
+        lines.extend(hand_lines(&stats.hands));
+        lines.push(Line::from(""));
+        lines.push(Line::from("Accuracy").bold());
+        lines.push(Line::from(format!("Basic Strategy: {}", accuracy(stats.strategy_accuracy()))).fg(Color::Cyan));
+        lines.push(Line::from(format!("Counting: {}", accuracy(stats.counting_accuracy()))).fg(Color::Cyan));
+        if !stats.drills.is_empty() {
+            lines.push(Line::from(""));
+            lines.push(Line::from("Drills").bold());
+            for (drill, tally) in &stats.drills {
+                lines.push(Line::from(format!("{}: {}", drill, accuracy(*tally))));
+            }
+        }
+        lines
+    }
+
+    /// Hands played under each variant.
+    fn variant_lines(stats: &LifetimeStats) -> Vec<Line<'static>> {
+        if stats.variants.is_empty() {
+            return vec![Line::from(""), Line::from("No hands played yet")];
+        }
+        let mut lines = vec![];
+        for (variant, totals) in &stats.variants {
+            lines.push(Line::from(""));
+            lines.push(Line::from(variant.clone()).bold());
+            lines.extend(hand_lines(totals));
+        }
+        lines
+    }
+
+    /// The totals over everything beside the hands played under each variant.
+    fn render_totals(&self, frame: &mut Frame, rect: Rect) {
+        // Half each, the totals read as long lines
+        let horizontal_chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(rect);
+
+        let (overview, variants) = match &self.stats {
+            Ok(stats) => (Self::overview_lines(stats), Self::variant_lines(stats)),
+            Err(err) => (vec![Line::from(""), Line::from(err.clone()).fg(Color::Red)], vec![]),
+        };
+
+        let left_section = horizontal_chunks[0];
+        render_border(frame, left_section);
+        render_centered_text(frame, left_section, " Lifetime Statistics ");
+        let overview = Paragraph::new(overview)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true })
+            .block(Block::default());
+        frame.render_widget(overview, left_section.inner(Margin { vertical: 1, horizontal: 2 }));
+
+        let right_section = horizontal_chunks[1];
+        render_border(frame, right_section);
+        render_centered_text(frame, right_section, " By Variant ");
+        let variants = Paragraph::new(variants)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true })
+            .block(Block::default());
+        frame.render_widget(variants, right_section.inner(Margin { vertical: 1, horizontal: 2 }));
+    }
+
+    /// Results and accuracy over time, one graph above the other.
+    fn render_graphs(&self, frame: &mut Frame, rect: Rect) {
+        let layout = Layout::vertical([Constraint::Percentage(50), Constraint::Percentage(50)]).split(rect);
+
+        render_border(frame, layout[0]);
+        render_centered_text(frame, layout[0], " Cumulative Result ");
+        render_cumulative_result(frame, &self.sessions, layout[0].inner(Margin { vertical: 1, horizontal: 2 }));
+
+        render_border(frame, layout[1]);
+        render_centered_text(frame, layout[1], " Accuracy by Session ");
+        render_accuracy(frame, &self.sessions, layout[1].inner(Margin { vertical: 1, horizontal: 2 }));
+    }
+
+    /// The cells missed most often over every chart beside how often each
+    /// chart has been played right.
+    fn render_cells(&self, frame: &mut Frame, rect: Rect) {
+        let horizontal_chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(65), Constraint::Percentage(35)])
+            .split(rect);
+
+        let left_section = horizontal_chunks[0];
+        render_border(frame, left_section);
+        render_centered_text(frame, left_section, " Most Missed Cells ");
+        let weakest = self.accuracy.weakest(MIN_CELL_PLAYS);
+        let area = left_section.inner(Margin { vertical: 1, horizontal: 2 });
+        if weakest.is_empty() {
+            let message = format!("No cell played {} times or more has been missed", MIN_CELL_PLAYS);
+            frame.render_widget(Paragraph::new(vec![Line::from(""), Line::from(message)]).alignment(Alignment::Center), area);
+        } else {
+            let rows = weakest.iter().map(|cell| {
+                Row::new(vec![
+                    cell.chart.clone(),
+                    cell.cell(),
+                    cell.tally.played().to_string(),
+                    format!("{:.0}%", cell.tally.miss_rate().unwrap_or(0.0) * 100.0),
+                ])
+            });
+            let table = Table::new(rows, [
+                Constraint::Min(16),
+                Constraint::Length(15),
+                Constraint::Length(6),
+                Constraint::Length(6),
+            ])
+                .header(Row::new(vec!["Chart", "Cell", "Played", "Missed"]).bold().underlined())
+                .column_spacing(2);
+            frame.render_widget(table, area);
+        }
+
+        let right_section = horizontal_chunks[1];
+        render_border(frame, right_section);
+        render_centered_text(frame, right_section, " By Chart ");
+        let charts = self.accuracy.by_chart();
+        let mut lines = vec![];
+        if charts.is_empty() {
+            lines.push(Line::from(""));
+            lines.push(Line::from("No plays graded yet"));
+        }
+        for (chart, tally) in charts {
+            lines.push(Line::from(""));
+            lines.push(Line::from(chart).bold());
+            lines.push(Line::from(format!("Right: {}", accuracy(Tally { total: tally.played(), correct: tally.right }))));
+        }
+        let charts = Paragraph::new(lines)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true })
+            .block(Block::default());
+        frame.render_widget(charts, right_section.inner(Margin { vertical: 1, horizontal: 2 }));
+    }
+
+    fn footer_spans(&self) -> Vec<String> {
+        vec![
+            " Q ".to_string(), " Quit ".to_string(),
+            " M ".to_string(), " Menu ".to_string(),
+            " Tab ".to_string(), format!(" {} ", self.view.next()),
+            " X ".to_string(), " Export CSV ".to_string(),
+        ]
+    }
+}
+
+// ---- TRAIT IMPLEMENTATIONS ----
+impl Model for StatisticsScreen {
+    fn update(&mut self) -> std::io::Result<ModelResponse> {
+        if let Event::Key(key) = read_event(false)? {
+            if key.kind == event::KeyEventKind::Release {
+                return Ok(ModelResponse::Refresh);
+            }
+            match key.code {
+                KeyCode::Char('q') => return Ok(ModelResponse::Exit),
+                KeyCode::Char('m') => return Ok(ModelResponse::NavToMainMenu),
+                KeyCode::Tab => self.view = self.view.next(),
+                KeyCode::Char('x') => self.export(),
+                _ => {}
+            }
+        }
+        Ok(ModelResponse::Refresh)
+    }
+
+    fn ui(&mut self, frame: &mut Frame) {
+        let main_chunks = create_common_layout(frame.area());
+        // A line under the view for what the export did
+        let (content, message) = if self.message.is_empty() {
+            (main_chunks[1], None)
+        } else {
+            let layout = Layout::vertical([Constraint::Min(5), Constraint::Length(1)]).split(main_chunks[1]);
+            (layout[0], Some(layout[1]))
+        };
+        match self.view {
+            StatisticsView::Totals => self.render_totals(frame, content),
+            StatisticsView::Graphs => self.render_graphs(frame, content),
+            StatisticsView::Cells => self.render_cells(frame, content),
+        }
+        if let Some(rect) = message {
+            frame.render_widget(Paragraph::new(self.message.as_str()).bold().fg(Color::Yellow).alignment(Alignment::Center), rect);
+        }
+
+        // Render Footer
+        let footer_layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Length(5),
+                Constraint::Min(20),
+            ])
+            .split(main_chunks[2]);
+        render_key_spans(frame, self.footer_spans(), footer_layout[1]);
+    }
+}
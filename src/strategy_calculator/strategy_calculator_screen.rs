@@ -1,16 +1,34 @@
-use crate::logic::strategy_calculator_logic::{BlackjackStrategy, SurrenderRule};
+use crate::clipboard::copy_to_clipboard;
+use crate::constants::{HISTORY_DATABASE_FILE, LAST_RULES_FILE, REGISTRY_INDEX_URL};
+use crate::logic::app_dirs::{data_file, deviations_dir, exports_dir};
+use crate::logic::config::{self, strategies_dir};
+use crate::logic::counting::{CountingSystem, COUNTING_SYSTEMS};
+use crate::logic::decision_accuracy::DecisionAccuracy;
+use crate::logic::deviations::{counting_systems, load_index_sets, IndexSet, IndexSituation};
+use crate::logic::ev::{action_evs, card_label, compare_strategies, composition_exceptions, dealer_final_totals, explain_play, generate_strategy, hard_bust_chance, house_edge, insurance_break_even, insurance_ev, rule_impacts, ChartHand, CompositionException, DealerOutcomes, RuleImpact, StrategyComparison, CHART_UPCARDS};
+use crate::logic::history_store::HistoryStore;
+use crate::logic::export::{cell_text, chart_text, export_csv, export_html, export_png, export_svg, row_text};
+use crate::logic::game::advisor::resolve_code;
+use crate::logic::game::round::PlayerAction;
+use crate::logic::game::scenario::Scenario;
+use crate::logic::game::table::TableConfig;
+use crate::logic::registry::{fetch_packs, install_pack, StrategyPack};
+use crate::logic::strategy_calculator_logic::{find_matching_strategy, load_strategies_reporting_errors, strategy_files_signature, strategy_key, BlackjackStrategy, GameVariant, DECK_OPTIONS, LegendEntry, StrategyLoadError, StrategyVariables, SurrenderRule};
 use crate::model::{Model, ModelResponse};
-use crate::ui::{create_common_layout, create_header_main_footer_layout, render_border, render_centered_text, render_footer_spans, split_content_horizontally, MenuNavigation};
+use crate::ui::{create_common_layout, create_header_main_footer_layout, render_border, render_centered_text, render_footer_spans, split_content_horizontally, ActionPalette, MenuNavigation, ACTION_PALETTES, read_event};
+use crate::widgets::scroll_view::ScrollView;
 use ratatui::crossterm::event;
 use ratatui::crossterm::event::{Event, KeyCode};
 use ratatui::layout::{Alignment, Constraint, Direction, Layout, Margin, Rect};
 use ratatui::prelude::{Line, Stylize};
 use ratatui::style::{Color, Style};
-use ratatui::widgets::{Block, Cell, Paragraph, Row, Table};
+use ratatui::widgets::{Bar, BarChart, BarGroup, Block, Borders, Cell, Clear, Paragraph, Row, Table, TableState};
 use ratatui::Frame;
 use std::collections::HashMap;
-use std::fs;
+use std::fmt;
 use std::rc::Rc;
+use std::time::{Duration, Instant, SystemTime};
+use uuid::Uuid;
 
 // ---- Adjustable Settings ----
 enum AdjustableOption {
@@ -19,28 +37,101 @@ enum AdjustableOption {
     AllowDoubleAfterSplit,
     AllowSurrender,
     DealerPeak,
+    GameVariant,
+    ActionColors,
 }
 
-impl AdjustableOption {
-    pub fn to_string(&self) -> String {
-        match self {
-            AdjustableOption::NumberOfDecks => "Number of Decks".to_string(),
-            AdjustableOption::Soft17DealerAction => "Soft 17 Dealer Action".to_string(),
-            AdjustableOption::AllowDoubleAfterSplit => "Allow Double After Split".to_string(),
-            AdjustableOption::AllowSurrender => "Allow Surrender".to_string(),
-            AdjustableOption::DealerPeak => "Dealer Peak".to_string(),
-        }
+impl fmt::Display for AdjustableOption {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            AdjustableOption::NumberOfDecks => "Number of Decks",
+            AdjustableOption::Soft17DealerAction => "Soft 17 Dealer Action",
+            AdjustableOption::AllowDoubleAfterSplit => "Allow Double After Split",
+            AdjustableOption::AllowSurrender => "Allow Surrender",
+            AdjustableOption::DealerPeak => "Dealer Peak",
+            AdjustableOption::GameVariant => "Game Variant",
+            AdjustableOption::ActionColors => "Action Colors",
+        };
+        write!(f, "{}", name)
     }
 }
 
-const ADJUSTABLE_OPTIONS: [AdjustableOption; 5] = [
+const ADJUSTABLE_OPTIONS: [AdjustableOption; 7] = [
     AdjustableOption::NumberOfDecks,
     AdjustableOption::Soft17DealerAction,
     AdjustableOption::AllowDoubleAfterSplit,
     AdjustableOption::AllowSurrender,
     AdjustableOption::DealerPeak,
+    AdjustableOption::GameVariant,
+    AdjustableOption::ActionColors,
 ];
 
+// ---- Chart Views ----
+/// What the right hand side of the screen shows.
+#[derive(Clone, Copy, PartialEq)]
+enum ChartView {
+    Strategy,
+    IndexPlays,
+    DealerOutcomes,
+    BustChances,
+    RuleImpact,
+    Insurance,
+    Comparison,
+    FileProblems,
+    MissingCharts,
+    StrategyPicker,
+    StrategyPacks,
+}
+
+/// The strategy table cell being inspected, by table (hard, soft then pairs),
+/// row and upcard column.
+#[derive(Clone, Copy, PartialEq)]
+struct ChartCursor {
+    table: usize,
+    row: usize,
+    column: usize,
+}
+
+/// The sections of a chart, in the order the cursor and comparison step through them
+const CHART_SECTIONS: [&str; 3] = ["Hard Hands", "Soft Hands", "Pairs"];
+
+/// Fewest lines kept above and below the strategy tables, for the EVs and insurance
+const MIN_CHART_MARGIN: u16 = 3;
+
+/// Longest hand that can be typed in to look up
+const MAX_LOOKUP_LENGTH: usize = 24;
+
+/// Rows the strategy tables move by with Page Up and Page Down
+const CHART_SCROLL_LINES: isize = 5;
+
+/// How often the strategies directory is checked for changes
+const STRATEGY_RELOAD_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Red behind the cells of the mistake heatmap, from cells never missed to
+/// cells always missed
+const LEAST_MISSED_RED: u8 = 50;
+const MOST_MISSED_RED: u8 = 220;
+
+// ---- Strategy Metadata ----
+/// The part of a strategy file being edited from the picker.
+#[derive(Clone, Copy, PartialEq)]
+enum MetadataField {
+    Name,
+    Description,
+}
+
+impl fmt::Display for MetadataField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            MetadataField::Name => "Name",
+            MetadataField::Description => "Description",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+const MAX_METADATA_LENGTH: usize = 80;
+
 // ---- Strategy Calculator Screen ----
 pub struct StrategyCalculatorScreen {
     active_menu_index: i8,
@@ -49,124 +140,200 @@ pub struct StrategyCalculatorScreen {
     allow_double_after_split: bool,
     surrender_rule: SurrenderRule,
     dealer_peak: bool,
+    variant: GameVariant,
     strategy: BlackjackStrategy,
     strategy_cache: HashMap<String, BlackjackStrategy>,
     active_strategy_name: String,
+
+    /// Table whose split rules computed charts and EVs are worked out for
+    table: TableConfig,
+
+    /// House edge under the chosen rules, worked out again whenever they change
+    house_edge: f64,
+
+    /// Effect of changing each rule on its own, while shown
+    rule_impacts: Option<Vec<RuleImpact>>,
+
+    /// The chart compared with another strategy under the chosen rules, while
+    /// shown, with that strategy. The other strategy is picked by position in
+    /// `comparison_names`.
+    comparison: Option<(BlackjackStrategy, StrategyComparison)>,
+    compare_with: usize,
+    /// The chart section shown next to the other strategy's, by position in
+    /// `CHART_SECTIONS`, in place of the list of differences
+    side_by_side: Option<usize>,
+
+    /// Hands whose exact cards play differently from the chart, while shown
+    exceptions: Option<Vec<CompositionException>>,
+
+    /// Cell whose action EVs are shown, while inspecting the chart
+    cursor: Option<ChartCursor>,
+
+    /// Whether the cursor's cell is explained in a popup over the chart
+    cell_detail: bool,
+
+    /// A hand being typed in to look up, e.g. "A7 v 9"
+    lookup_input: Option<String>,
+
+    /// The chart's play for the hand last looked up, shown in the footer
+    /// until the cursor moves on
+    lookup_result: Option<String>,
+
+    /// Whether the chart has upcards down the side and hands across the top
+    transposed: bool,
+
+    /// Right and wrong plays in each cell, loaded afresh each time the
+    /// mistake heatmap is turned on, while it's shown over the chart
+    mistakes: Option<DecisionAccuracy>,
+
+    /// How chart actions are colored, shared with the other screens
+    palette: Rc<std::cell::Cell<ActionPalette>>,
+
+    /// How far the tables are scrolled when the terminal is too short for them
+    chart_scroll: ScrollView,
+
+    view: ChartView,
+
+    /// Loaded index plays, shown in place of the chart for one counting system
+    index_sets: Vec<IndexSet>,
+    counting_system: usize,
+
+    /// Position in CHART_UPCARDS of the upcard the dealer outcomes are charted for
+    dealer_upcard: usize,
+
+    /// Where the chart was last exported to, or why it couldn't be, or that
+    /// the strategies were reloaded
+    notice: Option<String>,
+
+    /// Strategy files that couldn't be loaded, and whether the warning
+    /// about them is still up
+    load_errors: Vec<StrategyLoadError>,
+    show_load_errors: bool,
+
+    /// Whether the chart was picked by hand rather than matched to the rules,
+    /// until the rules are changed
+    picked_strategy: bool,
+
+    /// Position in `strategy_names` of the strategy highlighted in the picker
+    picker_selection: usize,
+
+    /// The highlighted strategy's name or description as it's being typed
+    metadata_input: Option<(MetadataField, String)>,
+
+    /// Strategy packs listed in the registry, or why they couldn't be,
+    /// fetched each time they're shown, and the one highlighted
+    strategy_packs: Option<Result<Vec<StrategyPack>, String>>,
+    pack_selection: usize,
+
+    /// Rules chosen this session that no loaded chart is for, in the order chosen
+    missing_charts: Vec<StrategyVariables>,
+
+    /// The strategies directory as last loaded, to notice when it changes
+    strategy_files: Vec<(String, Option<SystemTime>, u64)>,
+    last_reload_check: Instant,
 }
 
 impl StrategyCalculatorScreen {
-    pub fn new() -> Self {
-        // Initialize with default game settings
-        let default_decks = 3;
-        let default_dealer_stands_on_soft_17 = true;
-        let default_double_after_split = true;
-        let default_surrender = SurrenderRule::AnyUpcard;
-        let default_dealer_peak = true;
-
-        // Load all strategies from the strategies directory
-        let strategies_dir = "resources/strategies";
-        let mut strategy_cache = HashMap::new();
-
-        // Default strategy to load if we can't find any
-        let mut default_strategy = BlackjackStrategy::new();
-        let mut active_strategy_name = "Default".to_string();
-
-        // Attempt to read directory and load all .json files
-        if let Ok(entries) = fs::read_dir(strategies_dir) {
-            for entry in entries.flatten() {
-                let path = entry.path();
-                if path.is_file() && path.extension().and_then(|ext| ext.to_str()) == Some("json") {
-                    if let Some(filename) = path.file_stem().and_then(|name| name.to_str()) {
-                        match BlackjackStrategy::from_file(path.to_str().unwrap()) {
-                            Ok(strategy) => {
-                                // Cache the strategy
-                                strategy_cache.insert(filename.to_string(), strategy);
-                            },
-                            Err(_) => {
-                                panic!()
-                            }
-                        }
-                    }
-                }
+    pub fn new(palette: Rc<std::cell::Cell<ActionPalette>>) -> Self {
+        // Load all strategies from the strategies directory, warning about any that can't be read
+        let (strategy_cache, load_errors) = load_strategies_reporting_errors(&strategies_dir());
+
+        let table = config::current().table;
+        // Reopen on the rules last chosen, or the config's the first time
+        let default_rules = StrategyVariables::load(&data_file(LAST_RULES_FILE)).unwrap_or_else(|| config::current().rules);
+        // Work the chart out when none was loaded for these rules
+        let mut missing_charts = Vec::new();
+        let (active_strategy_name, default_strategy) = match find_matching_strategy(&strategy_cache, &default_rules) {
+            Some((name, strategy)) => (name, strategy.clone()),
+            None => {
+                missing_charts.push(default_rules.clone());
+                let strategy = generate_strategy(&default_rules, &table);
+                (strategy.name.clone(), strategy)
             }
-        }
-
-        // Find a matching strategy using our static method
-        if let Some((name, strategy)) = Self::find_matching_strategy(
-            &strategy_cache,
-            default_decks,
-            default_dealer_stands_on_soft_17,  // Note the inversion for dealer_stands_on_soft_17
-            default_double_after_split,
-            default_dealer_peak,
-            default_surrender
-        ) {
-            active_strategy_name = name;
-            default_strategy = strategy.clone();  // Assuming your strategy type implements Clone
-        }
+        };
 
         Self {
             active_menu_index: 0,
-            number_of_decks: default_decks as i8,
-            dealer_stands_on_soft_17: default_dealer_stands_on_soft_17,
-            allow_double_after_split: default_double_after_split,
-            surrender_rule: default_surrender,
-            dealer_peak: default_dealer_peak,
+            number_of_decks: default_rules.decks as i8,
+            dealer_stands_on_soft_17: default_rules.dealer_stands_on_soft_17,
+            allow_double_after_split: default_rules.double_after_split,
+            surrender_rule: default_rules.surrender_allowed,
+            dealer_peak: default_rules.dealer_peak,
+            variant: default_rules.variant,
             strategy: default_strategy,
             strategy_cache,
             active_strategy_name,
+            house_edge: house_edge(&default_rules, &table),
+            table,
+            rule_impacts: None,
+            comparison: None,
+            compare_with: 0,
+            side_by_side: None,
+            exceptions: None,
+            cursor: None,
+            cell_detail: false,
+            lookup_input: None,
+            lookup_result: None,
+            transposed: false,
+            mistakes: None,
+            palette,
+            chart_scroll: ScrollView::new(),
+            view: ChartView::Strategy,
+            index_sets: load_index_sets(&deviations_dir()),
+            counting_system: 0,
+            dealer_upcard: 0,
+            notice: None,
+            show_load_errors: !load_errors.is_empty(),
+            load_errors,
+            picked_strategy: false,
+            picker_selection: 0,
+            metadata_input: None,
+            strategy_packs: None,
+            pack_selection: 0,
+            missing_charts,
+            strategy_files: strategy_files_signature(&strategies_dir()),
+            last_reload_check: Instant::now(),
         }
     }
 
-    fn get_action_color(&self, action: &str) -> Color {
-        match action.trim() {
-            "H" => Color::Red,
-            "D" | "Dh" => Color::Blue,
-            "Ds" => Color::LightBlue,
-            "S" => Color::Yellow,
-            "P" => Color::LightCyan,
-            "Su" | "Rs" | "Rp" => Color::LightMagenta,
-            "Rh" => Color::Magenta,
-             _ => Color::Red
-        }
-    }
-
-    fn create_colored_row<'a>(&self, row_data: Vec<String>) -> Row<'a> {
+    /// A row of the chart, its label then its actions, each styled by
+    /// `style` from its position among the actions.
+    fn create_colored_row<'a>(&self, row_data: Vec<String>, style: impl Fn(usize, &str) -> Style) -> Row<'a> {
         let first_cell = Cell::new(row_data[0].clone());
 
         let mut cells = vec![first_cell];
-        for action in row_data.iter().skip(1) {
-            let color = self.get_action_color(action);
-            cells.push(Cell::new(action.clone()).style(Style::new().fg(color)));
+        for (index, action) in row_data.iter().skip(1).enumerate() {
+            cells.push(Cell::new(action.clone()).style(style(index, action)));
         }
 
         Row::new(cells)
     }
 
-    // Static method that doesn't require &self
-    pub fn find_matching_strategy(
-        strategy_cache: &HashMap<String, BlackjackStrategy>,
-        decks: u8,
-        dealer_stands_on_soft_17: bool,
-        double_after_split: bool,
-        dealer_peak: bool,
-        surrender_rule: SurrenderRule
-    ) -> Option<(String, &BlackjackStrategy)> {
-        // Iterate through all cached strategies
-        for (name, strategy) in strategy_cache {
-            // Check for exact match on all variables
-            if strategy.rules.decks == decks &&
-                strategy.rules.dealer_stands_on_soft_17 == dealer_stands_on_soft_17 &&
-                strategy.rules.double_after_split == double_after_split &&
-                strategy.rules.dealer_peak == dealer_peak &&
-                strategy.rules.surrender_allowed == surrender_rule {
-
-                // Return the name and reference to the matching strategy
-                return Some((name.clone(), strategy));
+    /// How a chart cell's action is drawn, in its palette color, or shaded
+    /// by how often it's been played wrong while the mistake heatmap is
+    /// shown. `table` is the cell's section, none for the surrender marks.
+    fn cell_style(&self, table: Option<usize>, row: usize, column: usize, action: &str) -> Style {
+        let (Some(mistakes), Some(table)) = (&self.mistakes, table) else {
+            return self.palette.get().style(&self.strategy.action_legend, action);
+        };
+        let tally = mistakes.tally(&self.active_strategy_name, self.row_hand(table, row), column);
+        match tally.miss_rate() {
+            Some(rate) => {
+                let red = LEAST_MISSED_RED as f64 + (MOST_MISSED_RED - LEAST_MISSED_RED) as f64 * rate;
+                Style::new().fg(Color::White).bg(Color::Rgb(red.round() as u8, 0, 0))
             }
+            None => Style::new().fg(Color::DarkGray),
         }
+    }
 
-        // No exact match found
-        None
+    /// Shows or hides how often each cell has been played wrong.
+    fn toggle_mistakes(&mut self) {
+        self.mistakes = match self.mistakes {
+            Some(_) => None,
+            None => Some(HistoryStore::open(&data_file(HISTORY_DATABASE_FILE), "Strategy Calculator")
+                .and_then(|store| store.decision_accuracy())
+                .unwrap_or_default()),
+        };
     }
 
     // Add a method to switch active strategy
@@ -180,30 +347,279 @@ impl StrategyCalculatorScreen {
         }
     }
 
-    pub fn update_strategy_based_on_settings(&mut self) {
-        // Convert the UI settings to strategy variables
-        let decks = self.number_of_decks as u8;
-        let dealer_stands_on_soft_17 = self.dealer_stands_on_soft_17;
-        let double_after_split = self.allow_double_after_split;
-        let dealer_peak = self.dealer_peak;
-        let surrender_allowed =self.surrender_rule;
+    /// Converts the UI settings to strategy variables
+    fn current_rules(&self) -> StrategyVariables {
+        StrategyVariables {
+            decks: self.number_of_decks as u8,
+            dealer_stands_on_soft_17: self.dealer_stands_on_soft_17,
+            double_after_split: self.allow_double_after_split,
+            dealer_peak: self.dealer_peak,
+            surrender_allowed: self.surrender_rule,
+            variant: self.variant,
+        }
+    }
 
+    pub fn update_strategy_based_on_settings(&mut self) {
+        self.picked_strategy = false;
         // Find an exact matching strategy
-        if let Some((name, _)) = Self::find_matching_strategy(
-            &self.strategy_cache,
-            decks,
-            dealer_stands_on_soft_17,
-            double_after_split,
-            dealer_peak,
-            surrender_allowed
-        ) {
+        if let Some((name, _)) = find_matching_strategy(&self.strategy_cache, &self.current_rules()) {
             // Update the active strategy if we found a match
-            self.switch_strategy(&*name);
+            self.switch_strategy(&name);
         } else {
-            if self.strategy_cache.contains_key("default-strategy.json") {
-                self.switch_strategy("default-strategy");
+            // Otherwise work the chart out from the rules, and note there's no chart for them
+            self.strategy = generate_strategy(&self.current_rules(), &self.table);
+            self.active_strategy_name = self.strategy.name.clone();
+            if !self.missing_charts.contains(&self.current_rules()) {
+                self.missing_charts.push(self.current_rules());
             }
         }
+
+        self.house_edge = house_edge(&self.current_rules(), &self.table);
+        self.notice = None;
+        if self.exceptions.is_some() {
+            self.exceptions = Some(composition_exceptions(&self.current_rules(), &self.table));
+        }
+        if self.rule_impacts.is_some() {
+            self.rule_impacts = Some(rule_impacts(&self.current_rules(), &self.table));
+        }
+        if self.comparison.is_some() {
+            self.comparison = Some(self.compare());
+        }
+    }
+
+    /// Saves the rules chosen so the calculator reopens on them.
+    fn remember_rules(&mut self) {
+        if let Err(err) = self.current_rules().save(&data_file(LAST_RULES_FILE)) {
+            self.notice = Some(format!("Couldn't save the rules: {}", err));
+        }
+    }
+
+    /// Shows `view` in place of the strategy chart, or goes back to the chart.
+    fn toggle_view(&mut self, view: ChartView) {
+        self.view = if self.view == view { ChartView::Strategy } else { view };
+        // Every rule change is a full house edge calculation, so only keep them up to date while shown
+        self.rule_impacts = (self.view == ChartView::RuleImpact).then(|| rule_impacts(&self.current_rules(), &self.table));
+        self.comparison = (self.view == ChartView::Comparison).then(|| self.compare());
+    }
+
+    /// Writes the chart to the exports directory as CSV files, a web page and
+    /// a wallet card image.
+    fn export_chart(&mut self) {
+        let dir = &exports_dir();
+        let exported = export_csv(&self.strategy, &self.active_strategy_name, dir)
+            .and_then(|_| export_html(&self.strategy, &self.active_strategy_name, dir))
+            .and_then(|_| export_svg(&self.strategy, &self.active_strategy_name, dir))
+            .and_then(|_| export_png(&self.strategy, &self.active_strategy_name, dir));
+        let notice = match exported {
+            Ok(_) => format!("Exported the chart as CSV, HTML, SVG and PNG to {}", dir.display()),
+            Err(err) => err,
+        };
+        self.notice = Some(notice);
+    }
+
+    /// Loads the strategies again when a file in the directory has been
+    /// added, removed or edited since they were last loaded.
+    fn reload_changed_strategies(&mut self) {
+        let strategy_files = strategy_files_signature(&strategies_dir());
+        if strategy_files == self.strategy_files {
+            return;
+        }
+        self.strategy_files = strategy_files;
+
+        let (strategy_cache, load_errors) = load_strategies_reporting_errors(&strategies_dir());
+        // Only warn again about problems that weren't there before
+        self.show_load_errors = load_errors.iter().any(|error| !self.load_errors.contains(error));
+        if load_errors.is_empty() && self.view == ChartView::FileProblems {
+            self.view = ChartView::Strategy;
+        }
+        self.strategy_cache = strategy_cache;
+        self.load_errors = load_errors;
+        let strategy_cache = &self.strategy_cache;
+        self.missing_charts.retain(|rules| find_matching_strategy(strategy_cache, rules).is_none());
+
+        // Keep a chart that was picked by hand, if it's still there
+        let picked = self.picked_strategy.then(|| self.active_strategy_name.clone());
+        self.update_strategy_based_on_settings();
+        if let Some(name) = picked {
+            self.picked_strategy = self.switch_strategy(&name);
+        }
+        self.notice = Some(format!("Reloaded the strategies in {}", &strategies_dir()));
+    }
+
+    /// Saves a computed chart for each of the missing rule combinations to
+    /// the strategies directory, then loads them.
+    fn generate_missing_charts(&mut self) {
+        let written = self.missing_charts.iter().try_fold(0, |written, rules| {
+            let path = format!("{}/{}.json", strategies_dir(), strategy_key(rules));
+            generate_strategy(rules, &self.table).save(&path)
+                .map(|_| written + 1)
+                .map_err(|err| format!("Couldn't write {}: {}", path, err))
+        });
+        self.reload_changed_strategies();
+        self.notice = Some(match written {
+            Ok(written) => format!("Wrote {} computed charts to {}", written, &strategies_dir()),
+            Err(err) => err,
+        });
+    }
+
+    /// Every loaded strategy's name, sorted.
+    fn strategy_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.strategy_cache.keys().cloned().collect();
+        names.sort();
+        names
+    }
+
+    /// Opens the picker on the chart being shown, or closes it.
+    fn toggle_strategy_picker(&mut self) {
+        self.toggle_view(ChartView::StrategyPicker);
+        self.picker_selection = self.strategy_names().iter()
+            .position(|name| *name == self.active_strategy_name)
+            .unwrap_or(0);
+    }
+
+    fn move_picker_selection(&mut self, increment: isize) {
+        let count = self.strategy_cache.len().max(1) as isize;
+        self.picker_selection = (self.picker_selection as isize + increment).rem_euclid(count) as usize;
+    }
+
+    /// Shows the highlighted strategy's chart whatever rules are chosen.
+    fn pick_strategy(&mut self) {
+        if let Some(name) = self.strategy_names().get(self.picker_selection)
+            && self.switch_strategy(name)
+        {
+            self.picked_strategy = true;
+            self.view = ChartView::Strategy;
+        }
+    }
+
+    /// Starts editing the highlighted strategy's name or description.
+    fn start_metadata_input(&mut self, field: MetadataField) {
+        let Some(strategy) = self.strategy_names().get(self.picker_selection).map(|name| &self.strategy_cache[name]) else {
+            return;
+        };
+        let value = match field {
+            MetadataField::Name => strategy.name.clone(),
+            MetadataField::Description => strategy.description.clone(),
+        };
+        self.metadata_input = Some((field, value));
+        self.notice = None;
+    }
+
+    /// Typing a name or description takes every key until it's saved or cancelled.
+    fn update_metadata_input(&mut self, key: KeyCode) {
+        let Some((field, input)) = self.metadata_input.as_mut() else {
+            return;
+        };
+        match key {
+            KeyCode::Enter if *field == MetadataField::Name && input.trim().is_empty() => {
+                self.notice = Some("A strategy needs a name".to_string());
+            }
+            KeyCode::Enter => {
+                let (field, input) = (*field, input.trim().to_string());
+                self.metadata_input = None;
+                self.save_picked_metadata(|strategy| match field {
+                    MetadataField::Name => strategy.name = input,
+                    MetadataField::Description => strategy.description = input,
+                });
+            }
+            KeyCode::Esc => self.metadata_input = None,
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Char(c) if input.chars().count() < MAX_METADATA_LENGTH => input.push(c),
+            _ => {}
+        }
+    }
+
+    /// Applies `edit` to the highlighted strategy and writes its name,
+    /// description and id back to its file.
+    fn save_picked_metadata(&mut self, edit: impl FnOnce(&mut BlackjackStrategy)) {
+        let Some(name) = self.strategy_names().get(self.picker_selection).cloned() else {
+            return;
+        };
+        let mut strategy = self.strategy_cache[&name].clone();
+        edit(&mut strategy);
+
+        let path = format!("{}/{}.json", strategies_dir(), name);
+        if let Err(err) = strategy.save_metadata(&path) {
+            self.notice = Some(format!("Couldn't write {}: {}", path, err));
+            return;
+        }
+        // Saved here, so there's nothing for the reload to pick up
+        self.strategy_files = strategy_files_signature(&strategies_dir());
+        if name == self.active_strategy_name {
+            self.strategy = strategy.clone();
+        }
+        self.strategy_cache.insert(name, strategy);
+        self.notice = Some(format!("Saved {}", path));
+    }
+
+    /// Opens the list of strategy packs in the registry, or closes it.
+    fn toggle_strategy_packs(&mut self) {
+        self.toggle_view(ChartView::StrategyPacks);
+        self.pack_selection = 0;
+        self.strategy_packs = (self.view == ChartView::StrategyPacks).then(|| fetch_packs(REGISTRY_INDEX_URL));
+    }
+
+    fn move_pack_selection(&mut self, increment: isize) {
+        if let Some(Ok(packs)) = &self.strategy_packs {
+            let count = packs.len().max(1) as isize;
+            self.pack_selection = (self.pack_selection as isize + increment).rem_euclid(count) as usize;
+        }
+    }
+
+    /// Downloads the highlighted pack's strategies into the strategies
+    /// directory, then loads them.
+    fn install_selected_pack(&mut self) {
+        let Some(Ok(packs)) = &self.strategy_packs else {
+            return;
+        };
+        let Some(pack) = packs.get(self.pack_selection).cloned() else {
+            return;
+        };
+        let installed = install_pack(REGISTRY_INDEX_URL, &pack, &strategies_dir());
+        self.reload_changed_strategies();
+        self.notice = Some(match installed {
+            Ok(files) => format!("Installed {} charts from {} to {}", files.len(), pack.name, &strategies_dir()),
+            Err(err) => format!("Couldn't install {}: {}", pack.name, err),
+        });
+    }
+
+    /// Strategies the chart can be compared with: every loaded strategy by
+    /// name, then the one worked out for the chosen rules.
+    fn comparison_names(&self) -> Vec<String> {
+        let mut names = self.strategy_names();
+        names.push(generate_strategy(&self.current_rules(), &self.table).name);
+        names
+    }
+
+    fn compare_with_name(&self) -> String {
+        let names = self.comparison_names();
+        names[self.compare_with % names.len()].clone()
+    }
+
+    fn compare(&self) -> (BlackjackStrategy, StrategyComparison) {
+        let rules = self.current_rules();
+        let other = match self.strategy_cache.get(&self.compare_with_name()) {
+            Some(strategy) => strategy.clone(),
+            None => generate_strategy(&rules, &self.table),
+        };
+        let comparison = compare_strategies(&rules, &self.table, &self.strategy, &other);
+        (other, comparison)
+    }
+
+    fn cycle_comparison(&mut self, increment: isize) {
+        let count = self.strategy_cache.len() as isize + 1;
+        self.compare_with = (self.compare_with as isize + increment).rem_euclid(count) as usize;
+        self.comparison = Some(self.compare());
+    }
+
+    /// Shows or hides the composition-dependent exceptions to the chart.
+    fn toggle_exceptions(&mut self) {
+        self.exceptions = match self.exceptions {
+            Some(_) => None,
+            None => Some(composition_exceptions(&self.current_rules(), &self.table)),
+        };
     }
 
     fn render_menu_body(&self, frame: &mut Frame, rect: Rect) {
@@ -220,16 +636,13 @@ impl StrategyCalculatorScreen {
             text.push_str(item.to_string().as_str());
 
             match i {
-                0 => text.push_str(&format!(": < {} >", match self.number_of_decks {
-                    1 => "1",
-                    2 => "2",
-                    3 => "4+",
-                    _ => "Unknown"
-                })),
+                0 => text.push_str(&format!(": < {} >", self.number_of_decks)),
                 1 => text.push_str(&format!(": < {} >", if self.dealer_stands_on_soft_17 { "Dealer Stands" } else { "Dealer Hits" })),
                 2 => text.push_str(&format!(": < {} >", if self.allow_double_after_split { "Allowed" } else { "Not Allowed" })),
                 3 => text.push_str(&format!(": < {} >", self.surrender_rule.to_string())),
-                4 => text.push_str(&format!(": < {} >", if self.dealer_peak { "Yes" } else { "No" })),
+                4 => text.push_str(&format!(": < {} >", if self.dealer_peak { "Yes" } else { "No (ENHC)" })),
+                5 => text.push_str(&format!(": < {} >", self.variant)),
+                6 => text.push_str(&format!(": < {} >", self.palette.get())),
                 _ => {}, // Handle any other case
             }
 
@@ -241,6 +654,11 @@ impl StrategyCalculatorScreen {
 
         }
 
+        // A negative edge means the player has the advantage
+        menu_body.push(Line::from(""));
+        let edge = Line::from(format!("House Edge: {:.2}%", self.house_edge * 100.0));
+        menu_body.push(if self.house_edge < 0.0 { edge.fg(Color::Green) } else { edge.fg(Color::Yellow) });
+
         let menu_options = Paragraph::new(menu_body)
             .bold()
             .alignment(Alignment::Center)
@@ -264,7 +682,7 @@ impl StrategyCalculatorScreen {
 
         // Render key header
         let header_sect = vert_split[0];
-        let header = Paragraph::new("Action Legend")
+        let header = Paragraph::new(if self.mistakes.is_some() { "Mistake Heatmap" } else { "Action Legend" })
             .bold()
             .alignment(Alignment::Center)
             .block(Block::default());
@@ -274,18 +692,32 @@ impl StrategyCalculatorScreen {
         // Render key body
         let body_sect = vert_split[1];
 
+        // While the heatmap is shown, the shades stand in for the actions
+        if self.mistakes.is_some() {
+            let key = Paragraph::new(vec![
+                Line::from(" Never missed ").style(Style::new().fg(Color::White).bg(Color::Rgb(LEAST_MISSED_RED, 0, 0))),
+                Line::from(" Always missed ").style(Style::new().fg(Color::White).bg(Color::Rgb(MOST_MISSED_RED, 0, 0))),
+                Line::from(" Never played ").style(Style::new().fg(Color::DarkGray)),
+            ])
+                .bold()
+                .alignment(Alignment::Left)
+                .block(Block::default());
+            frame.render_widget(key, body_sect);
+            return;
+        }
+
         // Create a sorted collection of the action legend items
         // First, collect into a Vec to allow sorting
-        let mut sorted_legend: Vec<(&String, &String)> = self.strategy.action_legend.iter().collect();
+        let mut sorted_legend: Vec<(&String, &LegendEntry)> = self.strategy.action_legend.iter().collect();
 
         // Sort by the action code alphabetically
         sorted_legend.sort_by(|a, b| a.0.cmp(b.0));
 
         // Now create the styled lines in alphabetical order
-        for (code, description) in sorted_legend {
+        for (code, entry) in sorted_legend {
             strat_key_lines.push(
                 Line::from(
-                    format!("{}: {}", code, description)).fg(self.get_action_color(code)));
+                    format!("{}: {}", code, entry.description)).style(self.palette.get().style(&self.strategy.action_legend, code)));
         }
 
         // Render
@@ -296,20 +728,17 @@ impl StrategyCalculatorScreen {
         frame.render_widget(key, body_sect);
     }
 
-    fn increment_current_menu_item(&mut self, increment: i8) {
+    /// Changes the highlighted setting, returning whether it was one of the rules.
+    fn increment_current_menu_item(&mut self, increment: i8) -> bool {
         let menu_item = ADJUSTABLE_OPTIONS.get(self.active_menu_index as usize).unwrap();
         match menu_item {
             AdjustableOption::NumberOfDecks => {
-                // Boundaries for wrapping
-                let min_value = 1;
-                let max_value = 3;
-
-                // Convert to `isize` for safe handling of negatives
-                let new_value = (self.number_of_decks as isize + increment as isize - min_value)
-                    .rem_euclid(max_value - min_value + 1)
-                    + min_value;
-
-                self.number_of_decks = new_value as i8;
+                // Step through the deck counts there are, wrapping at either end
+                let position = DECK_OPTIONS.iter()
+                    .position(|decks| *decks as i8 == self.number_of_decks)
+                    .unwrap_or(0) as isize;
+                let new_position = (position + increment as isize).rem_euclid(DECK_OPTIONS.len() as isize);
+                self.number_of_decks = DECK_OPTIONS[new_position as usize] as i8;
             }
             AdjustableOption::Soft17DealerAction => {
                 // Simple boolean toggle
@@ -333,76 +762,1317 @@ impl StrategyCalculatorScreen {
             AdjustableOption::DealerPeak => {
                 self.dealer_peak = !self.dealer_peak;
             }
+            AdjustableOption::GameVariant => {
+                self.variant = match self.variant {
+                    GameVariant::Classic => GameVariant::FreeBet,
+                    GameVariant::FreeBet => GameVariant::Classic,
+                };
+            }
+            AdjustableOption::ActionColors => {
+                let position = ACTION_PALETTES.iter().position(|palette| *palette == self.palette.get()).unwrap_or(0);
+                let new_position = (position as isize + increment as isize).rem_euclid(ACTION_PALETTES.len() as isize);
+                self.palette.set(ACTION_PALETTES[new_position as usize]);
+                return false;
+            }
         }
+        true
     }
 
     // Modified table rendering methods
     pub fn render_hard_hands_table(&mut self, frame: &mut Frame, rect: Rect) {
-        // Create rows from hard hands data with conditional coloring
-        let rows = self.strategy.tables.hard_hands.iter().map(|row| {
-            let mut row_cells = vec![row.total.to_string()];
-            row_cells.extend(row.actions.iter().cloned());
-            self.create_colored_row(row_cells)
+        let hands = self.strategy.tables.hard_hands.iter()
+            .map(|row| (row.total.to_string(), row.actions.clone()))
+            .collect();
+        self.render_chart_table(frame, rect, CHART_SECTIONS[0], hands, Some(0));
+    }
+
+    pub fn render_soft_hands_table(&mut self, frame: &mut Frame, rect: Rect) {
+        // Hands are shown as the Ace and the other card, e.g. A7
+        let hands: Vec<(String, Vec<String>)> = self.strategy.tables.soft_hands.iter()
+            .map(|row| (format!("A{}", row.total - 11), row.actions.clone()))
+            .collect();
+        let rows = if self.transposed { CHART_UPCARDS.len() } else { hands.len() };
+        let [soft_rect, surrender_rect] = Layout::vertical([
+            Constraint::Length(rows as u16 + 5),
+            Constraint::Min(0),
+        ]).areas(rect);
+        self.render_chart_table(frame, soft_rect, CHART_SECTIONS[1], hands, Some(1));
+
+        // Surrender gets its own section under the soft hands, the way printed charts show it
+        self.render_surrender_table(frame, surrender_rect);
+    }
+
+    /// Marks each hand and upcard the chart surrenders with "Su", for rules
+    /// that allow surrender. Only hands that surrender against some upcard
+    /// get a row, and the section is left out when there's no room for it.
+    fn render_surrender_table(&self, frame: &mut Frame, rect: Rect) {
+        if self.strategy.rules.surrender_allowed == SurrenderRule::NotAllowed {
+            return;
+        }
+        let tables = &self.strategy.tables;
+        let hands = tables.hard_hands.iter().map(|row| (row.total.to_string(), &row.actions))
+            .chain(tables.soft_hands.iter().map(|row| (format!("A{}", row.total - 11), &row.actions)))
+            .chain(tables.pair_hands.iter().map(|row| {
+                let card = if row.pair == 11 { "A".to_string() } else { row.pair.to_string() };
+                (format!("{},{}", card, card), &row.actions)
+            }));
+        let hands: Vec<(String, Vec<String>)> = hands
+            .filter(|(_, actions)| actions.iter().any(|action| action.starts_with('R')))
+            .map(|(hand, actions)| {
+                let marks = actions.iter().map(|action| {
+                    if action.starts_with('R') { "Su" } else { "" }.to_string()
+                });
+                (hand, marks.collect())
+            })
+            .collect();
+        let rows = if self.transposed { CHART_UPCARDS.len() } else { hands.len() };
+        if hands.is_empty() || (rect.height as usize) < rows + 4 {
+            return;
+        }
+        self.render_chart_table(frame, rect, "Surrender", hands, None);
+    }
+
+    pub fn render_pair_hands_table(&mut self, frame: &mut Frame, rect: Rect) {
+        let hands = self.strategy.tables.pair_hands.iter()
+            .map(|row| (row.pair.to_string(), row.actions.clone()))
+            .collect();
+        self.render_chart_table(frame, rect, CHART_SECTIONS[2], hands, Some(2));
+    }
+
+    /// Draws one section of the chart, each hand's actions against the
+    /// upcards. Hands run down the side, or across the top when transposed.
+    /// `table` is the section's position for the cell cursor, if it can have it.
+    fn render_chart_table(&self, frame: &mut Frame, rect: Rect, title: &str, hands: Vec<(String, Vec<String>)>, table: Option<usize>) {
+        let label_width = hands.iter().map(|(hand, _)| hand.len()).max().unwrap_or(0).max(2) as u16;
+        let row_count = if self.transposed { CHART_UPCARDS.len() } else { hands.len() };
+        let (chart, width) = if self.transposed {
+            let rows = CHART_UPCARDS.iter().enumerate().map(|(column, upcard)| {
+                let mut row_cells = vec![card_label(*upcard)];
+                row_cells.extend(hands.iter().map(|(_, actions)| actions.get(column).cloned().unwrap_or_default()));
+                self.create_colored_row(row_cells, |hand, action| self.cell_style(table, hand, column, action))
+            }).collect::<Vec<_>>();
+            let mut widths = vec![Constraint::Length(3)];
+            widths.extend(vec![Constraint::Length(label_width); hands.len()]);
+            let header = self.create_table_header_from(hands.iter().map(|(hand, _)| hand.clone()));
+            let width = 3 + hands.len() as u16 * (label_width + 1);
+            (self.create_strategy_table(rows, widths, header, title), width)
+        } else {
+            let rows = hands.into_iter().enumerate().map(|(row, (hand, actions))| {
+                let mut row_cells = vec![hand];
+                row_cells.extend(actions);
+                self.create_colored_row(row_cells, |column, action| self.cell_style(table, row, column, action))
+            }).collect::<Vec<_>>();
+            let widths = self.create_table_column_constraints(3);
+            (self.create_strategy_table(rows, widths, self.create_table_header(), title), 24 + label_width)
+        };
+
+        // Render in a centered area, scrolled as far as this table's rows need
+        let inner_rect = self.create_centered_table_area(rect, width);
+        let mut state = table.map_or_else(TableState::default, |table| self.table_state(table));
+        let visible_rows = rect.height.saturating_sub(4) as usize;
+        *state.offset_mut() = self.chart_scroll.offset().min(row_count.saturating_sub(visible_rows));
+        frame.render_stateful_widget(chart, inner_rect, &mut state);
+    }
+
+    /// Selects the cursor's cell when it's on `table`.
+    fn table_state(&self, table: usize) -> TableState {
+        match self.cursor {
+            Some(cursor) if cursor.table == table && self.transposed => TableState::default()
+                .with_selected(Some(cursor.column))
+                .with_selected_column(Some(cursor.row + 1)),
+            Some(cursor) if cursor.table == table => TableState::default()
+                .with_selected(Some(cursor.row))
+                .with_selected_column(Some(cursor.column + 1)),
+            _ => TableState::default(),
+        }
+    }
+
+    fn table_rows(&self, table: usize) -> usize {
+        let tables = &self.strategy.tables;
+        [tables.hard_hands.len(), tables.soft_hands.len(), tables.pair_hands.len()][table]
+    }
+
+    /// Starts or stops inspecting the chart's cells.
+    fn toggle_cursor(&mut self) {
+        self.cell_detail = false;
+        self.lookup_result = None;
+        self.cursor = match self.cursor {
+            Some(_) => None,
+            None => Some(ChartCursor { table: 0, row: 0, column: 0 }),
+        };
+    }
+
+    /// Moves the cursor by tables, and by rows and columns as they're shown,
+    /// keeping it inside its table. Moving to another table keeps the row
+    /// where that table has one.
+    fn move_cursor(&mut self, tables: isize, rows: isize, columns: isize) {
+        let Some(cursor) = self.cursor else {
+            return;
+        };
+        self.lookup_result = None;
+        // Transposed, the rows on screen are upcards and the columns hands
+        let (rows, columns) = if self.transposed { (columns, rows) } else { (rows, columns) };
+        let table = (cursor.table as isize + tables).rem_euclid(3) as usize;
+        let last_row = self.table_rows(table).saturating_sub(1);
+        self.cursor = Some(ChartCursor {
+            table,
+            row: cursor.row.saturating_add_signed(rows).min(last_row),
+            column: cursor.column.saturating_add_signed(columns).min(CHART_UPCARDS.len() - 1),
+        });
+    }
+
+    /// Typing a hand to look up takes every key until it's found or cancelled.
+    fn update_lookup_input(&mut self, key: KeyCode) {
+        let Some(input) = self.lookup_input.as_mut() else {
+            return;
+        };
+        match key {
+            KeyCode::Enter => match Scenario::parse(input) {
+                Ok(scenario) => {
+                    self.lookup_input = None;
+                    self.look_up(scenario);
+                }
+                Err(err) => self.notice = Some(err),
+            },
+            KeyCode::Esc => self.lookup_input = None,
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Char(c) if input.chars().count() < MAX_LOOKUP_LENGTH => input.push(c),
+            _ => {}
+        }
+    }
+
+    /// Puts the cursor on the chart's cell for a hand and upcard, and notes
+    /// the play there for the footer.
+    fn look_up(&mut self, scenario: Scenario) {
+        let hand = ChartHand::from(scenario.hand);
+        let tables = &self.strategy.tables;
+        let found = match hand {
+            ChartHand::Hard(total) => tables.hard_hands.iter().position(|row| row.total == total).map(|row| (0, row)),
+            ChartHand::Soft(total) => tables.soft_hands.iter().position(|row| row.total == total).map(|row| (1, row)),
+            ChartHand::Pair(pair) => tables.pair_hands.iter().position(|row| row.pair == pair).map(|row| (2, row)),
+        };
+        let column = CHART_UPCARDS.iter().position(|upcard| *upcard == scenario.upcard);
+        let (Some((table, row)), Some(column)) = (found, column) else {
+            self.notice = Some(format!("This chart has no row for {}", hand));
+            return;
+        };
+
+        self.view = ChartView::Strategy;
+        self.cursor = Some(ChartCursor { table, row, column });
+        if let Some((_, _, code)) = self.cursor_cell() {
+            let code = code.trim();
+            let play = self.strategy.action_legend.get(code).map_or(code, |entry| entry.description.as_str());
+            self.lookup_result = Some(format!("{} vs {}: {}", hand, card_label(scenario.upcard), play));
+        }
+    }
+
+    /// The hand a row of one of the chart's sections is for.
+    fn row_hand(&self, table: usize, row: usize) -> ChartHand {
+        let tables = &self.strategy.tables;
+        match table {
+            0 => ChartHand::Hard(tables.hard_hands[row].total),
+            1 => ChartHand::Soft(tables.soft_hands[row].total),
+            _ => ChartHand::Pair(tables.pair_hands[row].pair),
+        }
+    }
+
+    /// The hand and chart codes of the row under the cursor.
+    fn cursor_row(&self) -> Option<(ChartHand, &[String])> {
+        let cursor = self.cursor?;
+        let tables = &self.strategy.tables;
+        let actions = match cursor.table {
+            0 => &tables.hard_hands[cursor.row].actions,
+            1 => &tables.soft_hands[cursor.row].actions,
+            _ => &tables.pair_hands[cursor.row].actions,
+        };
+        Some((self.row_hand(cursor.table, cursor.row), actions))
+    }
+
+    /// The hand, upcard and chart code of the cell under the cursor.
+    fn cursor_cell(&self) -> Option<(ChartHand, u8, String)> {
+        let cursor = self.cursor?;
+        let (hand, actions) = self.cursor_row()?;
+        let code = actions.get(cursor.column).cloned().unwrap_or_default();
+        Some((hand, CHART_UPCARDS[cursor.column], code))
+    }
+
+    /// Copies the cursor's cell, or its whole row, as text. Without the
+    /// cursor, copies the whole chart.
+    fn copy_chart(&mut self, whole_row: bool) {
+        let (text, copied) = match (self.cursor_row(), self.cursor_cell()) {
+            (Some((hand, actions)), _) if whole_row => (row_text(hand, actions), hand.to_string()),
+            (_, Some((hand, upcard, code))) => {
+                (cell_text(&self.strategy, hand, upcard, &code), format!("{} vs {}", hand, card_label(upcard)))
+            }
+            _ => (chart_text(&self.strategy), "the chart".to_string()),
+        };
+        self.notice = Some(match copy_to_clipboard(&text) {
+            Ok(()) => format!("Copied {} to the clipboard", copied),
+            Err(err) => format!("Couldn't copy to the clipboard: {}", err),
+        });
+    }
+
+    /// The EV of every play open to the hand under the cursor, best first
+    /// in green, above the tables.
+    fn render_cell_evs(&self, frame: &mut Frame, rect: Rect) {
+        let Some((hand, upcard, _)) = self.cursor_cell() else {
+            return;
+        };
+        let evs = action_evs(&self.current_rules(), &self.table, hand, upcard);
+
+        let plays = [
+            ("Stand", Some(evs.stand)),
+            ("Hit", Some(evs.hit)),
+            ("Double", evs.double),
+            ("Split", evs.split),
+            ("Surrender", evs.surrender),
+        ];
+        let mut spans = Vec::new();
+        for (name, ev) in plays {
+            let Some(ev) = ev else {
+                continue;
+            };
+            let text = format!(" {} {:+.1}% ", name, ev * 100.0);
+            spans.push(if ev == evs.best() { text.bold().fg(Color::Green) } else { text.into() });
+        }
+        let lines = vec![
+            Line::from(format!("{} vs {}", hand, card_label(upcard))).bold(),
+            Line::from(spans),
+        ];
+        let area = Layout::vertical([Constraint::Min(0), Constraint::Length(3)]).split(rect)[1];
+        frame.render_widget(Paragraph::new(lines).alignment(Alignment::Center), area);
+    }
+
+    /// Explains the cursor's cell over the chart: why the chart plays it that
+    /// way, what each play is worth, what to do when the chart's first choice
+    /// isn't allowed, and how often the dealer busts from the upcard.
+    fn render_cell_detail(&self, frame: &mut Frame, rect: Rect) {
+        let Some((hand, upcard, code)) = self.cursor_cell() else {
+            return;
+        };
+        let rules = self.current_rules();
+        let evs = action_evs(&rules, &self.table, hand, upcard);
+
+        let mut lines = vec![Line::from(explain_play(hand, upcard, &code, &evs)), Line::from("")];
+        let plays = [
+            (PlayerAction::Stand, Some(evs.stand)),
+            (PlayerAction::Hit, Some(evs.hit)),
+            (PlayerAction::Double, evs.double),
+            (PlayerAction::Split, evs.split),
+            (PlayerAction::Surrender, evs.surrender),
+        ];
+        for (play, ev) in plays {
+            let Some(ev) = ev else {
+                continue;
+            };
+            let text = format!("{:<10} {:+6.1}%", play.to_string(), ev * 100.0);
+            lines.push(if ev == evs.best() { Line::from(text).bold().fg(Color::Green) } else { Line::from(text) });
+        }
+        lines.push(Line::from(""));
+
+        // Doubles and surrenders say what to do when they can't be made
+        let every_play = [PlayerAction::Hit, PlayerAction::Stand, PlayerAction::Double, PlayerAction::Split, PlayerAction::Surrender];
+        let preferred = resolve_code(&code, &every_play);
+        let fallback = resolve_code(&code, &[PlayerAction::Hit, PlayerAction::Stand, PlayerAction::Split]);
+        if let (Some(preferred), Some(fallback)) = (preferred, fallback)
+            && preferred != fallback
+        {
+            lines.push(Line::from(format!(
+                "When {} isn't allowed, {} instead",
+                preferred.to_string().to_lowercase(),
+                fallback.to_string().to_lowercase(),
+            )));
+        }
+        let busts = dealer_final_totals(&rules, upcard).busts();
+        lines.push(Line::from(format!("The dealer busts {:.1}% of the time with {} showing", busts * 100.0, card_label(upcard))));
+
+        let width = rect.width.saturating_sub(8).min(72);
+        // The explanation wraps onto as many lines as it needs
+        let text_width = width.saturating_sub(4).max(1) as usize;
+        let wrapped = lines.iter().map(|line| line.width().max(1).div_ceil(text_width) as u16).sum::<u16>();
+        let height = (wrapped + 2).min(rect.height);
+        let area = Rect {
+            x: rect.x + (rect.width - width) / 2,
+            y: rect.y + (rect.height - height) / 2,
+            width,
+            height,
+        };
+        let detail = Paragraph::new(lines)
+            .wrap(ratatui::widgets::Wrap { trim: false })
+            .block(Block::default()
+                .borders(Borders::all())
+                .title(format!(" {} vs {}: {} ", hand, card_label(upcard), code.trim()))
+                .title_alignment(Alignment::Center)
+                .fg(Color::Cyan)
+                .padding(ratatui::widgets::Padding::horizontal(1)));
+        frame.render_widget(Clear, area);
+        frame.render_widget(detail, area);
+    }
+
+    fn render_strategy_chart(&mut self, frame: &mut Frame, right_section: Rect) {
+        // Say whose chart it is when it wasn't matched to the rules
+        let title = if self.picked_strategy {
+            format!(" Strategy Chart: {} ", self.active_strategy_name)
+        } else {
+            " Strategy Chart ".to_string()
+        };
+        render_centered_text(frame, right_section, &title);
+
+        // The tables take a title, a header with a line either side, then the rows
+        let tables = &self.strategy.tables;
+        let rows = if self.transposed {
+            CHART_UPCARDS.len()
+        } else {
+            tables.hard_hands.len().max(tables.soft_hands.len()).max(tables.pair_hands.len())
+        };
+
+        // On a short terminal the space above and below the tables shrinks first,
+        // then the tables scroll together
+        let spare = right_section.height.saturating_sub(rows as u16 + 4) / 2;
+        let margin = spare.clamp(MIN_CHART_MARGIN, 10);
+        let right_section_chunks = create_header_main_footer_layout(right_section, margin, 0, margin);
+        if let Some(cursor) = self.cursor {
+            let row = if self.transposed { cursor.column } else { cursor.row };
+            self.chart_scroll.show(row, right_section_chunks[1].height.saturating_sub(6) as usize);
+        }
+        let tables_rect = self.chart_scroll.render(frame, right_section_chunks[1], rows + 4);
+
+        let right_layout = if self.transposed {
+            // Each table is as wide as it has hands, soft hands up to A10 take three characters
+            let tables = &self.strategy.tables;
+            let soft_width = if tables.soft_hands.iter().any(|row| row.total > 20) { 4 } else { 3 };
+            Layout::horizontal([
+                Constraint::Length(2),
+                Constraint::Fill(tables.hard_hands.len() as u16 * 3 + 4),
+                Constraint::Fill(tables.soft_hands.len() as u16 * soft_width + 4),
+                Constraint::Fill(tables.pair_hands.len() as u16 * 3 + 4),
+                Constraint::Length(2),
+            ])
+        } else {
+            Layout::horizontal([
+                Constraint::Length(4),         // Small buffer space
+                Constraint::Ratio(1, 3),       // Equal chunk 1
+                Constraint::Ratio(1, 3),       // Equal chunk 2
+                Constraint::Ratio(1, 3),       // Equal chunk 3
+                Constraint::Length(4),         // Small buffer space
+            ])
+        };
+        let right_chunks = right_layout.split(tables_rect);
+
+        self.render_hard_hands_table(frame, right_chunks[1]);
+        self.render_soft_hands_table(frame, right_chunks[2]);
+        self.render_pair_hands_table(frame, right_chunks[3]);
+        self.render_cell_evs(frame, right_section_chunks[0]);
+
+        let [insurance_rect, exceptions_rect] = Layout::vertical([
+            Constraint::Length(2),
+            Constraint::Min(0),
+        ]).areas(right_section_chunks[2]);
+        self.render_insurance_row(frame, insurance_rect);
+
+        if let Some(exceptions) = &self.exceptions {
+            let exceptions_rect = right_layout.split(exceptions_rect);
+            Self::render_exceptions(frame, exceptions, &exceptions_rect[1..4]);
+        }
+
+        if self.cell_detail {
+            self.render_cell_detail(frame, right_section);
+        }
+    }
+
+    /// Insurance and even money under the tables: never at basic strategy,
+    /// and the index to take it at for the counting system picked in the
+    /// index plays, when it has one.
+    fn render_insurance_row(&self, frame: &mut Frame, rect: Rect) {
+        let mut spans = vec![
+            "Insurance / Even Money: ".bold(),
+            "Never".bold().fg(Color::Red),
+            " at basic strategy".into(),
+        ];
+        let systems = counting_systems(&self.index_sets);
+        let index = systems.get(self.counting_system).and_then(|system| {
+            self.index_sets.iter()
+                .filter(|set| &set.system == system)
+                .flat_map(|set| &set.plays)
+                .find(|play| play.situation == IndexSituation::Insurance)
+                .map(|play| (system, play))
+        });
+        if let Some((system, play)) = index {
+            spans.extend([
+                "; with a ".into(),
+                system.clone().bold(),
+                " count, take it at ".into(),
+                play.counts().bold().fg(Color::Green),
+            ]);
+        }
+        let line = Paragraph::new(Line::from(spans)).alignment(Alignment::Center);
+        frame.render_widget(line, rect.inner(Margin { vertical: 0, horizontal: 2 }));
+    }
+
+    /// Lists the index plays for the chosen counting system, set by set.
+    fn render_index_plays(&self, frame: &mut Frame, right_section: Rect) {
+        let systems = counting_systems(&self.index_sets);
+        let Some(system) = systems.get(self.counting_system) else {
+            render_centered_text(frame, right_section, " Index Plays ");
+            let message = Paragraph::new(format!("No index plays were loaded from {}", deviations_dir()))
+                .alignment(Alignment::Center);
+            frame.render_widget(message, right_section.inner(Margin { vertical: 4, horizontal: 2 }));
+            return;
+        };
+        render_centered_text(frame, right_section, &format!(" Index Plays - {} ", system));
+
+        let sets: Vec<&IndexSet> = self.index_sets.iter().filter(|set| &set.system == system).collect();
+        let rows = sets.iter().flat_map(|set| {
+            set.plays.iter().map(|play| Row::new(vec![
+                Cell::new(set.name.clone()),
+                Cell::new(play.situation.to_string()),
+                Cell::new(play.play_name()).style(self.palette.get().style(&self.strategy.action_legend, &play.play)),
+                Cell::new(play.counts()),
+            ]))
         }).collect::<Vec<_>>();
+        let row_count = rows.len() as u16;
 
-        // Create a table with consistent styling
-        let widths = self.create_table_column_constraints(3);
-        let table = self.create_strategy_table(rows, widths, "Hard Hands");
+        let header = Row::new(vec!["Set", "Hand", "Play", "True Count"])
+            .style(Style::new().bold())
+            .bottom_margin(1);
+        let widths = [Constraint::Length(16), Constraint::Length(16), Constraint::Length(10), Constraint::Length(12)];
+        let table = Table::new(rows, widths)
+            .header(header)
+            .style(Style::new().blue())
+            .column_spacing(2);
 
-        // Render in a centered area
-        let inner_rect = self.create_centered_table_area(rect, 26);
-        frame.render_widget(table, inner_rect);
+        let area = right_section.inner(Margin {
+            vertical: 2,
+            horizontal: right_section.width.saturating_sub(60) / 2,
+        });
+        let [table_area, notes_area] = Layout::vertical([
+            Constraint::Length(row_count + 3),
+            Constraint::Min(0),
+        ]).areas(area);
+        frame.render_widget(table, table_area);
+
+        let notes = sets.iter()
+            .map(|set| Line::from(format!("{}: {}", set.name, set.description)))
+            .collect::<Vec<_>>();
+        frame.render_widget(Paragraph::new(notes).wrap(ratatui::widgets::Wrap { trim: true }), notes_area);
     }
 
-    pub fn render_soft_hands_table(&mut self, frame: &mut Frame, rect: Rect) {
-        // Create rows from soft hands data
-        let rows = self.strategy.tables.soft_hands.iter().map(|row| {
-            let second_card = row.total - 11;
-            let hand_display = format!("A{}", second_card);
-
-            let mut row_cells = vec![hand_display];
-            row_cells.extend(row.actions.iter().cloned());
-            self.create_colored_row(row_cells)
+    /// Charts how the dealer's hand finishes against each upcard, with the
+    /// chosen upcard highlighted and drawn as bars below.
+    fn render_dealer_outcomes(&self, frame: &mut Frame, right_section: Rect) {
+        render_centered_text(frame, right_section, " Dealer Outcomes ");
+
+        let rules = self.current_rules();
+        let outcomes: Vec<DealerOutcomes> = CHART_UPCARDS.iter()
+            .map(|upcard| dealer_final_totals(&rules, *upcard))
+            .collect();
+
+        // Free Bet pushes a dealer 22, so it's shown apart from the other busts
+        let row = |label: &'static str, chance: fn(&DealerOutcomes) -> f64| {
+            (label, outcomes.iter().map(chance).collect::<Vec<f64>>())
+        };
+        let mut rows = vec![
+            row("17", |o| o.totals[0]),
+            row("18", |o| o.totals[1]),
+            row("19", |o| o.totals[2]),
+            row("20", |o| o.totals[3]),
+            row("21", |o| o.totals[4]),
+            row("Blackjack", |o| o.blackjack),
+        ];
+        if rules.variant == GameVariant::FreeBet {
+            rows.push(row("22 (Push)", |o| o.twenty_two));
+            rows.push(row("Bust", |o| o.bust));
+        } else {
+            rows.push(row("Bust", |o| o.busts()));
+        }
+
+        let table_rows = rows.iter().map(|(label, chances)| {
+            let mut cells = vec![Cell::new(*label).bold()];
+            cells.extend(chances.iter().map(|chance| Cell::new(format!("{:>5.1}", chance * 100.0))));
+            Row::new(cells)
+        }).collect::<Vec<_>>();
+        let header = Row::new(
+            std::iter::once("%".to_string())
+                .chain(CHART_UPCARDS.iter().map(|upcard| format!("{:>5}", card_label(*upcard))))
+                .collect::<Vec<_>>(),
+        ).style(Style::new().bold()).bottom_margin(1);
+        let mut widths = vec![Constraint::Length(10)];
+        widths.extend([Constraint::Length(5); 10]);
+        let table = Table::new(table_rows, widths)
+            .header(header)
+            .style(Style::new().blue())
+            .column_spacing(1)
+            .column_highlight_style(Style::new().red());
+        let mut state = TableState::default().with_selected_column(Some(self.dealer_upcard + 1));
+
+        let [table_area, chart_area] = Layout::vertical([
+            Constraint::Length(rows.len() as u16 + 3),
+            Constraint::Min(0),
+        ]).areas(right_section.inner(Margin { vertical: 2, horizontal: 2 }));
+        let table_area = table_area.inner(Margin {
+            vertical: 0,
+            horizontal: table_area.width.saturating_sub(70) / 2,
+        });
+        frame.render_stateful_widget(table, table_area, &mut state);
+
+        // Bars in tenths of a percent for the chosen upcard
+        let upcard = CHART_UPCARDS[self.dealer_upcard];
+        let bars = rows.iter().map(|(label, chances)| {
+            let percent = chances[self.dealer_upcard] * 100.0;
+            Bar::default()
+                .value((percent * 10.0).round() as u64)
+                .text_value(format!("{:.1}", percent))
+                .label(Line::from(*label))
+        }).collect::<Vec<_>>();
+        let chart = BarChart::default()
+            .block(Block::new()
+                .title(format!("Against {}", card_label(upcard)))
+                .title_alignment(Alignment::Center)
+                .style(Style::new().bold()))
+            .data(BarGroup::default().bars(&bars))
+            .bar_width((chart_area.width / bars.len() as u16).saturating_sub(1).clamp(1, 9))
+            .bar_gap(1)
+            .bar_style(Style::new().red())
+            .value_style(Style::new().black().on_red());
+        let chart_width = bars.len() as u16 * 10;
+        frame.render_widget(chart, chart_area.inner(Margin {
+            vertical: 1,
+            horizontal: chart_area.width.saturating_sub(chart_width) / 2,
+        }));
+    }
+
+    /// Lists the chance of busting when hitting each hard total, with the
+    /// hand's own cards out of the shoe for the chosen number of decks.
+    fn render_bust_chances(&self, frame: &mut Frame, right_section: Rect) {
+        render_centered_text(frame, right_section, " Bust Chances ");
+
+        let rules = self.current_rules();
+        let rows = (12..=21u8).map(|total| {
+            let busting_cards = match 22 - total {
+                10 => "10".to_string(),
+                lowest => format!("{}-10", card_label(lowest)),
+            };
+            Row::new(vec![
+                Cell::new(format!("Hard {}", total)).bold(),
+                Cell::new(busting_cards),
+                Cell::new(format!("{:>5.1}%", hard_bust_chance(&rules, total) * 100.0)),
+            ])
         }).collect::<Vec<_>>();
+        let row_count = rows.len() as u16;
 
-        // Create a table with consistent styling
-        let widths = self.create_table_column_constraints(3); // Wider first column for A+X format
-        let table = self.create_strategy_table(rows, widths, "Soft Hands");
+        let header = Row::new(vec!["Hand", "Busting Cards", "Bust if Hit"])
+            .style(Style::new().bold())
+            .bottom_margin(1);
+        let widths = [Constraint::Length(10), Constraint::Length(14), Constraint::Length(11)];
+        let table = Table::new(rows, widths)
+            .header(header)
+            .style(Style::new().blue())
+            .column_spacing(2);
 
-        // Render in a centered area
-        let inner_rect = self.create_centered_table_area(rect, 27); // 27 for wider first column
-        frame.render_widget(table, inner_rect);
+        let area = right_section.inner(Margin {
+            vertical: 2,
+            horizontal: right_section.width.saturating_sub(50) / 2,
+        });
+        let [table_area, notes_area] = Layout::vertical([
+            Constraint::Length(row_count + 3),
+            Constraint::Min(0),
+        ]).areas(area);
+        frame.render_widget(table, table_area);
+
+        let notes = Paragraph::new("Hard 11 or less and soft hands can't bust on the next card.")
+            .wrap(ratatui::widgets::Wrap { trim: true });
+        frame.render_widget(notes, notes_area);
     }
 
-    pub fn render_pair_hands_table(&mut self, frame: &mut Frame, rect: Rect) {
-        // Create rows from pair hands data
-        let rows = self.strategy.tables.pair_hands.iter().map(|row| {
-            let mut row_cells = vec![row.pair.to_string()];
-            row_cells.extend(row.actions.iter().cloned());
-            self.create_colored_row(row_cells)
+    /// Lists how far changing each rule on its own moves the house edge from
+    /// the chosen rules, in basis points (hundredths of a percent).
+    fn render_rule_impacts(&self, frame: &mut Frame, right_section: Rect) {
+        render_centered_text(frame, right_section, " Rule Impact ");
+        let Some(impacts) = &self.rule_impacts else {
+            return;
+        };
+
+        let rows = impacts.iter().map(|impact| {
+            let basis_points = (impact.edge_change * 10_000.0).round() as i64;
+            // A higher house edge is worse for the player
+            let (color, change) = match basis_points {
+                0 => (Color::Gray, "0".to_string()),
+                1.. => (Color::Red, format!("{:+}", basis_points)),
+                _ => (Color::Green, format!("{:+}", basis_points)),
+            };
+            Row::new(vec![
+                Cell::new(impact.baseline.clone()),
+                Cell::new(impact.change.clone()),
+                Cell::new(format!("{:>5} bps", change)).style(Style::new().fg(color)),
+            ])
         }).collect::<Vec<_>>();
+        let row_count = rows.len() as u16;
+
+        let header = Row::new(vec!["Rule", "Changed To", "Edge Change"])
+            .style(Style::new().bold())
+            .bottom_margin(1);
+        let widths = [Constraint::Length(32), Constraint::Length(32), Constraint::Length(11)];
+        let table = Table::new(rows, widths)
+            .header(header)
+            .style(Style::new().blue())
+            .column_spacing(2);
 
-        // Create a table with consistent styling
-        let widths = self.create_table_column_constraints(3);
-        let table = self.create_strategy_table(rows, widths, "Pairs");
+        let area = right_section.inner(Margin {
+            vertical: 2,
+            horizontal: right_section.width.saturating_sub(80) / 2,
+        });
+        let [baseline_area, table_area, notes_area] = Layout::vertical([
+            Constraint::Length(2),
+            Constraint::Length(row_count + 3),
+            Constraint::Min(0),
+        ]).areas(area);
+        let baseline = Line::from(format!("Baseline: the chosen rules, with a house edge of {:.2}%", self.house_edge * 100.0)).bold();
+        frame.render_widget(Paragraph::new(baseline), baseline_area);
+        frame.render_widget(table, table_area);
+
+        let notes = Paragraph::new("A basis point is a hundredth of a percent of each initial bet. Changes in red cost the player, those in green help.")
+            .wrap(ratatui::widgets::Wrap { trim: true });
+        frame.render_widget(notes, notes_area);
+    }
 
-        // Render in a centered area
-        let inner_rect = self.create_centered_table_area(rect, 26);
-        frame.render_widget(table, inner_rect);
+    /// Warns over the chart about strategy files that couldn't be loaded.
+    fn render_load_errors(&self, frame: &mut Frame, right_section: Rect) {
+        let mut lines = vec![
+            Line::from(format!("Files in {} with problems were skipped:", &strategies_dir())),
+            Line::from(""),
+        ];
+        for error in &self.load_errors {
+            lines.push(Line::from(error.file.clone()).bold());
+            lines.push(Line::from(format!("  {}", error.problems[0])));
+            if error.problems.len() > 1 {
+                lines.push(Line::from(format!("  and {} more", error.problems.len() - 1)));
+            }
+        }
+        lines.push(Line::from(""));
+        lines.push(Line::from("Press F to see every problem, or any other key to continue").fg(Color::DarkGray));
+
+        let width = right_section.width.saturating_sub(8).min(80);
+        let height = (lines.len() as u16 + 2).min(right_section.height.saturating_sub(4));
+        let area = Rect {
+            x: right_section.x + (right_section.width - width) / 2,
+            y: right_section.y + (right_section.height - height) / 2,
+            width,
+            height,
+        };
+        let warning = Paragraph::new(lines)
+            .wrap(ratatui::widgets::Wrap { trim: false })
+            .block(Block::default()
+                .borders(Borders::all())
+                .title(" Some Strategies Couldn't Be Loaded ")
+                .title_alignment(Alignment::Center)
+                .fg(Color::Yellow)
+                .padding(ratatui::widgets::Padding::horizontal(1)));
+        frame.render_widget(Clear, area);
+        frame.render_widget(warning, area);
+    }
+
+    /// Lists every loaded strategy to pick one to show, whatever rules are chosen.
+    fn render_strategy_picker(&self, frame: &mut Frame, right_section: Rect) {
+        render_centered_text(frame, right_section, " Pick Strategy ");
+
+        let area = right_section.inner(Margin { vertical: 2, horizontal: 3 });
+        let [table_area, rules_area] = Layout::vertical([
+            Constraint::Min(0),
+            Constraint::Length(2),
+        ]).areas(area);
+
+        let names = self.strategy_names();
+        let rows = names.iter().map(|name| {
+            let strategy = &self.strategy_cache[name];
+            let row = Row::new(vec![
+                Cell::new(name.clone()),
+                Cell::new(strategy.name.clone()),
+                Cell::new(strategy.description.clone()),
+            ]);
+            // The chart being shown
+            if *name == self.active_strategy_name { row.fg(Color::Green) } else { row }
+        }).collect::<Vec<_>>();
+
+        let header = Row::new(vec!["File", "Name", "Description"])
+            .style(Style::new().bold())
+            .bottom_margin(1);
+        let widths = [Constraint::Length(32), Constraint::Length(24), Constraint::Min(20)];
+        let table = Table::new(rows, widths)
+            .header(header)
+            .style(Style::new().blue())
+            .row_highlight_style(Style::new().reversed())
+            .column_spacing(2);
+        let mut state = TableState::default().with_selected(Some(self.picker_selection));
+        frame.render_stateful_widget(table, table_area, &mut state);
+
+        // The rules are too long to fit beside the rest, so show the highlighted one's below
+        if let Some(name) = names.get(self.picker_selection) {
+            let strategy = &self.strategy_cache[name];
+            let details = match &self.metadata_input {
+                Some((field, input)) => vec![Line::from(format!("{}: {}_", field, input)).fg(Color::Yellow)],
+                None => vec![
+                    Line::from(format!("Rules: {}", strategy.rules)).bold(),
+                    Line::from(format!("ID: {}", strategy.id)),
+                ],
+            };
+            frame.render_widget(Paragraph::new(details).alignment(Alignment::Center), rules_area);
+        }
+    }
+
+    /// Lists the packs in the strategy registry, with the highlighted one's
+    /// description underneath.
+    fn render_strategy_packs(&self, frame: &mut Frame, right_section: Rect) {
+        render_centered_text(frame, right_section, " Strategy Packs ");
+
+        let area = right_section.inner(Margin {
+            vertical: 2,
+            horizontal: right_section.width.saturating_sub(100) / 2,
+        });
+        let packs = match &self.strategy_packs {
+            Some(Ok(packs)) if !packs.is_empty() => packs,
+            Some(Ok(_)) => {
+                frame.render_widget(Paragraph::new("The registry doesn't list any strategy packs yet."), area);
+                return;
+            }
+            Some(Err(err)) => {
+                let message = Paragraph::new(err.as_str()).fg(Color::Red).wrap(ratatui::widgets::Wrap { trim: true });
+                frame.render_widget(message, area);
+                return;
+            }
+            None => return,
+        };
+        let [table_area, description_area] = Layout::vertical([
+            Constraint::Min(0),
+            Constraint::Length(3),
+        ]).areas(area);
+
+        let rows = packs.iter().map(|pack| {
+            Row::new(vec![
+                Cell::new(pack.name.clone()),
+                Cell::new(pack.author.clone()),
+                Cell::new(pack.strategies.len().to_string()),
+            ])
+        }).collect::<Vec<_>>();
+        let header = Row::new(vec!["Pack", "Author", "Charts"])
+            .style(Style::new().bold())
+            .bottom_margin(1);
+        let widths = [Constraint::Min(30), Constraint::Length(24), Constraint::Length(6)];
+        let table = Table::new(rows, widths)
+            .header(header)
+            .style(Style::new().blue())
+            .row_highlight_style(Style::new().reversed())
+            .column_spacing(2);
+        let mut state = TableState::default().with_selected(Some(self.pack_selection));
+        frame.render_stateful_widget(table, table_area, &mut state);
+
+        if let Some(pack) = packs.get(self.pack_selection) {
+            let description = Paragraph::new(pack.description.as_str())
+                .alignment(Alignment::Center)
+                .wrap(ratatui::widgets::Wrap { trim: true });
+            frame.render_widget(description, description_area);
+        }
+    }
+
+    /// Lists the rule combinations chosen this session that had no chart, by
+    /// the name a chart for them would be filed under.
+    fn render_missing_charts(&self, frame: &mut Frame, right_section: Rect) {
+        render_centered_text(frame, right_section, " Missing Charts ");
+
+        let area = right_section.inner(Margin {
+            vertical: 2,
+            horizontal: right_section.width.saturating_sub(100) / 2,
+        });
+        let [intro_area, table_area] = Layout::vertical([
+            Constraint::Length(4),
+            Constraint::Min(0),
+        ]).areas(area);
+
+        let intro = if self.missing_charts.is_empty() {
+            format!("Every rule combination chosen so far has a chart in {}.", &strategies_dir())
+        } else {
+            format!(
+                "These rules were chosen without a chart in {}, so the calculator worked one out. \
+                Press G to save the computed charts there, or add your own under these names.",
+                strategies_dir(),
+            )
+        };
+        frame.render_widget(Paragraph::new(intro).wrap(ratatui::widgets::Wrap { trim: true }), intro_area);
+
+        let rows = self.missing_charts.iter().map(|rules| {
+            Row::new(vec![
+                Cell::new(format!("{}.json", strategy_key(rules))),
+                Cell::new(rules.variant.to_string()),
+                Cell::new(rules.decks.to_string()),
+                Cell::new(if rules.dealer_stands_on_soft_17 { "Stands" } else { "Hits" }),
+                Cell::new(if rules.double_after_split { "Yes" } else { "No" }),
+                Cell::new(rules.surrender_allowed.to_string()),
+                Cell::new(if rules.dealer_peak { "Yes" } else { "No" }),
+            ])
+        }).collect::<Vec<_>>();
+
+        let header = Row::new(vec!["File", "Variant", "Decks", "Soft 17", "DAS", "Surrender", "Peek"])
+            .style(Style::new().bold())
+            .bottom_margin(1);
+        let widths = [
+            Constraint::Length(36), Constraint::Length(8), Constraint::Length(5), Constraint::Length(7),
+            Constraint::Length(3), Constraint::Length(19), Constraint::Length(4),
+        ];
+        let table = Table::new(rows, widths)
+            .header(header)
+            .style(Style::new().blue())
+            .column_spacing(2);
+        frame.render_widget(table, table_area);
+    }
+
+    /// Lists everything wrong with each strategy file that was skipped.
+    fn render_file_problems(&self, frame: &mut Frame, right_section: Rect) {
+        render_centered_text(frame, right_section, " Strategy File Problems ");
+
+        let mut lines = vec![
+            Line::from(format!(
+                "{} of the files in {} weren't loaded. Their charts will load once they're fixed.",
+                self.load_errors.len(),
+                strategies_dir(),
+            )),
+        ];
+        for error in &self.load_errors {
+            lines.push(Line::from(""));
+            lines.push(Line::from(error.file.clone()).bold().fg(Color::Yellow));
+            lines.extend(error.problems.iter().map(|problem| Line::from(format!("  • {}", problem))));
+        }
+
+        let area = right_section.inner(Margin {
+            vertical: 2,
+            horizontal: right_section.width.saturating_sub(90) / 2,
+        });
+        frame.render_widget(Paragraph::new(lines).wrap(ratatui::widgets::Wrap { trim: false }), area);
+    }
+
+    /// Compares the chart with another strategy under the chosen rules: the
+    /// overall difference, then each cell they play differently.
+    fn render_comparison(&self, frame: &mut Frame, right_section: Rect) {
+        let Some((other_strategy, comparison)) = &self.comparison else {
+            return;
+        };
+        let other = self.compare_with_name();
+        render_centered_text(frame, right_section, &format!(" {} vs {} ", self.active_strategy_name, other));
+
+        let width = if self.side_by_side.is_some() { 100 } else { 80 };
+        let area = right_section.inner(Margin {
+            vertical: 2,
+            horizontal: right_section.width.saturating_sub(width) / 2,
+        });
+        let [summary_area, table_area] = Layout::vertical([
+            Constraint::Length(4),
+            Constraint::Min(0),
+        ]).areas(area);
+
+        let summary = if comparison.differences.is_empty() {
+            format!("Under the chosen rules, the chart and {} make the same first play with every hand.", other)
+        } else {
+            format!(
+                "Under the chosen rules, the chart is worth {:+.3}% per hand compared with {}, from {} differing cells.",
+                comparison.ev_difference * 100.0,
+                other,
+                comparison.differences.len(),
+            )
+        };
+        frame.render_widget(Paragraph::new(summary).bold().wrap(ratatui::widgets::Wrap { trim: true }), summary_area);
+        if let Some(section) = self.side_by_side {
+            let [first_area, second_area] = Layout::horizontal([
+                Constraint::Ratio(1, 2),
+                Constraint::Ratio(1, 2),
+            ]).areas(table_area);
+            self.render_section_compared(frame, first_area, &self.strategy, &self.active_strategy_name, section, comparison);
+            self.render_section_compared(frame, second_area, other_strategy, &other, section, comparison);
+            return;
+        }
+        if comparison.differences.is_empty() {
+            return;
+        }
+
+        // Leave a line to say how many didn't fit
+        let fits = table_area.height.saturating_sub(3) as usize;
+        let shown = if comparison.differences.len() > fits { fits.saturating_sub(1) } else { fits };
+        let rows = comparison.differences.iter().take(shown).map(|difference| {
+            let color = if difference.ev_difference > 0.0 { Color::Green } else { Color::Red };
+            Row::new(vec![
+                Cell::new(difference.hand.to_string()),
+                Cell::new(card_label(difference.upcard)),
+                Cell::new(difference.first.to_string()),
+                Cell::new(difference.second.to_string()),
+                Cell::new(format!("{:>+8.2}%", difference.ev_difference * 100.0)).style(Style::new().fg(color)),
+                Cell::new(format!("{:>+9.4}%", difference.ev_difference * difference.frequency * 100.0)).style(Style::new().fg(color)),
+            ])
+        }).collect::<Vec<_>>();
+
+        let header = Row::new(vec!["Hand", "vs", "Chart", "Other", "When Dealt", "Per Hand"])
+            .style(Style::new().bold())
+            .bottom_margin(1);
+        let widths = [
+            Constraint::Length(10), Constraint::Length(3), Constraint::Length(10),
+            Constraint::Length(10), Constraint::Length(10), Constraint::Length(10),
+        ];
+        let table = Table::new(rows, widths)
+            .header(header)
+            .style(Style::new().blue())
+            .column_spacing(2);
+        frame.render_widget(table, table_area);
+
+        if comparison.differences.len() > shown {
+            let more = Line::from(format!("and {} more", comparison.differences.len() - shown));
+            let more_area = Rect { y: table_area.bottom().saturating_sub(1), height: 1, ..table_area };
+            frame.render_widget(Paragraph::new(more), more_area);
+        }
+    }
+
+    /// One section of a compared strategy's chart, titled with the strategy's
+    /// name. Cells the two strategies play differently under the chosen rules
+    /// stand out, and the rest are dimmed.
+    fn render_section_compared(
+        &self,
+        frame: &mut Frame,
+        rect: Rect,
+        strategy: &BlackjackStrategy,
+        name: &str,
+        section: usize,
+        comparison: &StrategyComparison,
+    ) {
+        let tables = &strategy.tables;
+        let hands: Vec<(ChartHand, String, &Vec<String>)> = match section {
+            0 => tables.hard_hands.iter()
+                .map(|row| (ChartHand::Hard(row.total), row.total.to_string(), &row.actions))
+                .collect(),
+            1 => tables.soft_hands.iter()
+                .map(|row| (ChartHand::Soft(row.total), format!("A{}", row.total - 11), &row.actions))
+                .collect(),
+            _ => tables.pair_hands.iter()
+                .map(|row| (ChartHand::Pair(row.pair), row.pair.to_string(), &row.actions))
+                .collect(),
+        };
+        let differs = |hand: ChartHand, upcard: u8| {
+            comparison.differences.iter().any(|difference| difference.hand == hand && difference.upcard == upcard)
+        };
+
+        let palette = self.palette.get();
+        let rows = hands.iter().map(|(hand, label, actions)| {
+            let mut cells = vec![Cell::new(label.clone())];
+            cells.extend(CHART_UPCARDS.iter().zip(actions.iter()).map(|(upcard, action)| {
+                let style = palette.style(&strategy.action_legend, action);
+                let style = if differs(*hand, *upcard) { style.reversed().bold() } else { style.dim() };
+                Cell::new(action.clone()).style(style)
+            }));
+            Row::new(cells)
+        }).collect::<Vec<_>>();
+
+        // Names can be wider than the table, so they go above it
+        let [name_area, table_area] = Layout::vertical([
+            Constraint::Length(2),
+            Constraint::Min(0),
+        ]).areas(rect);
+        frame.render_widget(Paragraph::new(name).bold().alignment(Alignment::Center), name_area);
+
+        let label_width = hands.iter().map(|(_, label, _)| label.len()).max().unwrap_or(0).max(2) as u16;
+        let table = self.create_strategy_table(rows, self.create_table_column_constraints(3), self.create_table_header(), CHART_SECTIONS[section]);
+        frame.render_widget(table, self.create_centered_table_area(table_area, 24 + label_width));
+    }
+
+    /// Tabulates what an insurance bet is worth at each true count for every
+    /// balanced counting system, with the count each starts paying at.
+    fn render_insurance(frame: &mut Frame, right_section: Rect) {
+        render_centered_text(frame, right_section, " Insurance ");
+
+        // Unbalanced counts are never converted to a true count
+        let systems: Vec<&dyn CountingSystem> = COUNTING_SYSTEMS.into_iter()
+            .filter(|system| system.is_balanced())
+            .collect();
+
+        let rows = (-2..=8).map(|true_count| {
+            let mut cells = vec![Cell::new(if true_count == 0 { "0".to_string() } else { format!("{:+}", true_count) }).bold()];
+            cells.extend(systems.iter().map(|system| {
+                let ev = insurance_ev(*system, true_count as f64);
+                let color = if ev > 0.0 { Color::Green } else { Color::Red };
+                Cell::new(format!("{:>+8.1}%", ev * 100.0)).style(Style::new().fg(color))
+            }));
+            Row::new(cells)
+        }).collect::<Vec<_>>();
+        let row_count = rows.len() as u16;
+
+        let mut break_even = vec![Cell::new("Break Even").bold()];
+        break_even.extend(systems.iter().map(|system| {
+            Cell::new(insurance_break_even(*system).map_or("Never".to_string(), |count| format!("{:>+9.1}", count))).bold()
+        }));
+        let header = Row::new(
+            std::iter::once("True Count".to_string())
+                .chain(systems.iter().map(|system| format!("{:>10}", system.name())))
+                .collect::<Vec<_>>(),
+        ).style(Style::new().bold()).bottom_margin(1);
+        let mut widths = vec![Constraint::Length(10)];
+        widths.extend(vec![Constraint::Length(10); systems.len()]);
+        let table = Table::new(rows, widths)
+            .header(header)
+            .footer(Row::new(break_even).top_margin(1))
+            .style(Style::new().blue())
+            .column_spacing(2);
+
+        let width = 10 + 12 * systems.len() as u16;
+        let area = right_section.inner(Margin {
+            vertical: 2,
+            horizontal: right_section.width.saturating_sub(width) / 2,
+        });
+        let [table_area, _, notes_area] = Layout::vertical([
+            Constraint::Length(row_count + 4),
+            Constraint::Length(1),
+            Constraint::Min(0),
+        ]).areas(area);
+        frame.render_widget(table, table_area);
+
+        let notes = Paragraph::new("Expected value per unit of insurance, which pays 2:1 when the dealer has a ten underneath. \
+            Counts are per deck remaining, and each system's count is taken to come from every card in proportion to its tag.")
+            .wrap(ratatui::widgets::Wrap { trim: true });
+        frame.render_widget(notes, notes_area);
+    }
+
+    /// Lists the composition-dependent exceptions beneath the tables, spread
+    /// across the table columns.
+    fn render_exceptions(frame: &mut Frame, exceptions: &[CompositionException], columns: &[Rect]) {
+        let mut lines: Vec<String> = exceptions.iter().map(|exception| exception.to_string()).collect();
+        if lines.is_empty() {
+            lines.push("None, every hand plays as its row".to_string());
+        }
+
+        let rows = columns[0].height.saturating_sub(1).max(1) as usize;
+        let capacity = rows * columns.len();
+        if lines.len() > capacity {
+            let hidden = lines.len() - capacity + 1;
+            lines.truncate(capacity - 1);
+            lines.push(format!("and {} more", hidden));
+        }
+        let per_column = lines.len().div_ceil(columns.len());
+
+        for (i, column) in columns.iter().enumerate() {
+            let mut body = vec![if i == 0 {
+                Line::from("Composition Exceptions").bold()
+            } else {
+                Line::from("")
+            }];
+            body.extend(lines.iter().skip(i * per_column).take(per_column).map(|line| Line::from(line.clone())));
+            frame.render_widget(Paragraph::new(body).block(Block::default()), *column);
+        }
     }
 }
 
 // ---- TRAIT IMPLEMENTATIONS ----
 impl Model for StrategyCalculatorScreen {
+    fn tick(&mut self) {
+        if self.last_reload_check.elapsed() >= STRATEGY_RELOAD_INTERVAL {
+            self.last_reload_check = Instant::now();
+            self.reload_changed_strategies();
+        }
+    }
+
 
     fn update(&mut self) -> std::io::Result<ModelResponse> {
-        if let Event::Key(key) = event::read()? {
+        if let Event::Key(key) = read_event(self.metadata_input.is_some() || self.lookup_input.is_some())? {
             if key.kind == event::KeyEventKind::Release {
                 return Ok(ModelResponse::Refresh);
             }
+            if self.metadata_input.is_some() {
+                self.update_metadata_input(key.code);
+                return Ok(ModelResponse::Refresh);
+            }
+            if self.lookup_input.is_some() {
+                self.update_lookup_input(key.code);
+                return Ok(ModelResponse::Refresh);
+            }
+            // Any key puts the load warning away, F goes on to the full report
+            if self.show_load_errors {
+                self.show_load_errors = false;
+                if key.code == KeyCode::Char('f') {
+                    self.toggle_view(ChartView::FileProblems);
+                }
+                return Ok(ModelResponse::Refresh);
+            }
             return match key.code {
                 KeyCode::Char('q') => Ok(ModelResponse::Exit),
                 KeyCode::Char('m') => Ok(ModelResponse::NavToMainMenu),
+                KeyCode::Char('c') => {
+                    self.toggle_exceptions();
+                    Ok(ModelResponse::Refresh)
+                }
+                KeyCode::Char('i') => {
+                    self.toggle_view(ChartView::IndexPlays);
+                    Ok(ModelResponse::Refresh)
+                }
+                KeyCode::Char('d') => {
+                    self.toggle_view(ChartView::DealerOutcomes);
+                    Ok(ModelResponse::Refresh)
+                }
+                KeyCode::Char('b') => {
+                    self.toggle_view(ChartView::BustChances);
+                    Ok(ModelResponse::Refresh)
+                }
+                KeyCode::Char('r') => {
+                    self.toggle_view(ChartView::RuleImpact);
+                    Ok(ModelResponse::Refresh)
+                }
+                KeyCode::Char('n') => {
+                    self.toggle_view(ChartView::Insurance);
+                    Ok(ModelResponse::Refresh)
+                }
+                KeyCode::Char('f') if !self.load_errors.is_empty() => {
+                    self.toggle_view(ChartView::FileProblems);
+                    Ok(ModelResponse::Refresh)
+                }
+                KeyCode::Char('p') => {
+                    self.toggle_strategy_picker();
+                    Ok(ModelResponse::Refresh)
+                }
+                KeyCode::Char('j') | KeyCode::Down if self.view == ChartView::StrategyPicker => {
+                    self.move_picker_selection(1);
+                    Ok(ModelResponse::Refresh)
+                }
+                KeyCode::Char('k') | KeyCode::Up if self.view == ChartView::StrategyPicker => {
+                    self.move_picker_selection(-1);
+                    Ok(ModelResponse::Refresh)
+                }
+                KeyCode::Enter if self.view == ChartView::StrategyPicker => {
+                    self.pick_strategy();
+                    Ok(ModelResponse::Refresh)
+                }
+                KeyCode::PageDown if self.view == ChartView::Strategy => {
+                    self.chart_scroll.scroll(CHART_SCROLL_LINES);
+                    Ok(ModelResponse::Refresh)
+                }
+                KeyCode::PageUp if self.view == ChartView::Strategy => {
+                    self.chart_scroll.scroll(-CHART_SCROLL_LINES);
+                    Ok(ModelResponse::Refresh)
+                }
+                KeyCode::Char('t') => {
+                    self.transposed = !self.transposed;
+                    Ok(ModelResponse::Refresh)
+                }
+                KeyCode::Char('w') => {
+                    self.toggle_strategy_packs();
+                    Ok(ModelResponse::Refresh)
+                }
+                KeyCode::Char('j') | KeyCode::Down if self.view == ChartView::StrategyPacks => {
+                    self.move_pack_selection(1);
+                    Ok(ModelResponse::Refresh)
+                }
+                KeyCode::Char('k') | KeyCode::Up if self.view == ChartView::StrategyPacks => {
+                    self.move_pack_selection(-1);
+                    Ok(ModelResponse::Refresh)
+                }
+                KeyCode::Enter if self.view == ChartView::StrategyPacks => {
+                    self.install_selected_pack();
+                    Ok(ModelResponse::Refresh)
+                }
+                KeyCode::Char('e') if self.view == ChartView::StrategyPicker => {
+                    self.start_metadata_input(MetadataField::Name);
+                    Ok(ModelResponse::Refresh)
+                }
+                KeyCode::Char('a') if self.view == ChartView::StrategyPicker => {
+                    self.start_metadata_input(MetadataField::Description);
+                    Ok(ModelResponse::Refresh)
+                }
+                KeyCode::Char('u') if self.view == ChartView::StrategyPicker => {
+                    self.save_picked_metadata(|strategy| strategy.id = Uuid::new_v4());
+                    Ok(ModelResponse::Refresh)
+                }
+                KeyCode::Char('o') => {
+                    self.toggle_view(ChartView::MissingCharts);
+                    Ok(ModelResponse::Refresh)
+                }
+                KeyCode::Char('g') if self.view == ChartView::MissingCharts && !self.missing_charts.is_empty() => {
+                    self.generate_missing_charts();
+                    Ok(ModelResponse::Refresh)
+                }
+                KeyCode::Char('v') => {
+                    self.toggle_view(ChartView::Comparison);
+                    Ok(ModelResponse::Refresh)
+                }
+                KeyCode::Tab if self.view == ChartView::Comparison => {
+                    self.cycle_comparison(1);
+                    Ok(ModelResponse::Refresh)
+                }
+                KeyCode::BackTab if self.view == ChartView::Comparison => {
+                    self.cycle_comparison(-1);
+                    Ok(ModelResponse::Refresh)
+                }
+                // Steps through the sections side by side, then back to the differences
+                KeyCode::Char('s') if self.view == ChartView::Comparison => {
+                    self.side_by_side = match self.side_by_side {
+                        None => Some(0),
+                        Some(section) if section + 1 < CHART_SECTIONS.len() => Some(section + 1),
+                        Some(_) => None,
+                    };
+                    Ok(ModelResponse::Refresh)
+                }
+                KeyCode::Char('s') if self.view == ChartView::IndexPlays => {
+                    let systems = counting_systems(&self.index_sets).len().max(1);
+                    self.counting_system = (self.counting_system + 1) % systems;
+                    Ok(ModelResponse::Refresh)
+                }
+                KeyCode::Tab if self.view == ChartView::DealerOutcomes => {
+                    self.dealer_upcard = (self.dealer_upcard + 1) % CHART_UPCARDS.len();
+                    Ok(ModelResponse::Refresh)
+                }
+                KeyCode::BackTab if self.view == ChartView::DealerOutcomes => {
+                    self.dealer_upcard = (self.dealer_upcard + CHART_UPCARDS.len() - 1) % CHART_UPCARDS.len();
+                    Ok(ModelResponse::Refresh)
+                }
+                KeyCode::Char('y') if self.view == ChartView::Strategy => {
+                    self.copy_chart(false);
+                    Ok(ModelResponse::Refresh)
+                }
+                KeyCode::Char('Y') if self.view == ChartView::Strategy => {
+                    self.copy_chart(true);
+                    Ok(ModelResponse::Refresh)
+                }
+                KeyCode::Char('x') => {
+                    self.export_chart();
+                    Ok(ModelResponse::Refresh)
+                }
+                KeyCode::Char('/') => {
+                    self.lookup_input = Some(String::new());
+                    self.notice = None;
+                    Ok(ModelResponse::Refresh)
+                }
+                KeyCode::Enter if self.cursor.is_some() && self.view == ChartView::Strategy => {
+                    self.cell_detail = !self.cell_detail;
+                    Ok(ModelResponse::Refresh)
+                }
+                KeyCode::Esc if self.cell_detail => {
+                    self.cell_detail = false;
+                    Ok(ModelResponse::Refresh)
+                }
+                KeyCode::Char('e') if self.view == ChartView::Strategy => {
+                    self.toggle_cursor();
+                    Ok(ModelResponse::Refresh)
+                }
+                KeyCode::Char('a') if self.view == ChartView::Strategy => {
+                    self.toggle_mistakes();
+                    Ok(ModelResponse::Refresh)
+                }
+                // Arrows move the cell cursor while inspecting the chart
+                KeyCode::Char('j') | KeyCode::Down | KeyCode::Char('k') | KeyCode::Up
+                | KeyCode::Char('l') | KeyCode::Right | KeyCode::Char('h') | KeyCode::Left
+                | KeyCode::Tab | KeyCode::BackTab
+                    if self.cursor.is_some() && self.view == ChartView::Strategy =>
+                {
+                    match key.code {
+                        KeyCode::Char('j') | KeyCode::Down => self.move_cursor(0, 1, 0),
+                        KeyCode::Char('k') | KeyCode::Up => self.move_cursor(0, -1, 0),
+                        KeyCode::Char('l') | KeyCode::Right => self.move_cursor(0, 0, 1),
+                        KeyCode::Char('h') | KeyCode::Left => self.move_cursor(0, 0, -1),
+                        KeyCode::Tab => self.move_cursor(1, 0, 0),
+                        _ => self.move_cursor(-1, 0, 0),
+                    }
+                    Ok(ModelResponse::Refresh)
+                }
                 // More cursor down
                 KeyCode::Char('j') | KeyCode::Down => {
                     self.increment_menu_index(1);
@@ -415,14 +2085,18 @@ impl Model for StrategyCalculatorScreen {
                 }
                 // Increment current value up
                 KeyCode::Char('l') | KeyCode::Right => {
-                    self.increment_current_menu_item(1);
-                    self.update_strategy_based_on_settings();
+                    if self.increment_current_menu_item(1) {
+                        self.update_strategy_based_on_settings();
+                        self.remember_rules();
+                    }
                     return Ok(ModelResponse::Refresh);
                 }
                 // Increment current value down
                 KeyCode::Char('h') | KeyCode::Left => {
-                    self.increment_current_menu_item(-1);
-                    self.update_strategy_based_on_settings();
+                    if self.increment_current_menu_item(-1) {
+                        self.update_strategy_based_on_settings();
+                        self.remember_rules();
+                    }
                     return Ok(ModelResponse::Refresh);
                 }
                 _ => Ok(ModelResponse::Refresh),
@@ -445,7 +2119,7 @@ impl Model for StrategyCalculatorScreen {
         render_centered_text(frame, left_section, " Game Settings ");
 
 
-        let left_section_chunks = Self::create_header_main_main_footer_layout(left_section, 10, 20, 10);
+        let left_section_chunks = Self::create_header_main_main_footer_layout(left_section, 6, 28, 8);
         let menu_rect = left_section_chunks[1];
         let strategy_key_rect = left_section_chunks[3];
 
@@ -453,26 +2127,30 @@ impl Model for StrategyCalculatorScreen {
         self.render_menu_body(frame, menu_rect);
         self.render_action_legend(frame, strategy_key_rect);
 
-        // Render the Strategy Tables
         let right_section = horizontal_chunks[1];
         render_border(frame, right_section);
-        render_centered_text(frame, right_section, " Strategy Chart ");
-
-        let tables_rect =
-            create_header_main_footer_layout(right_section, 10, 21, 10)[1];
-
-        let right_layout = Layout::horizontal([
-            Constraint::Length(4),         // Small buffer space
-            Constraint::Ratio(1, 3),       // Equal chunk 1
-            Constraint::Ratio(1, 3),       // Equal chunk 2
-            Constraint::Ratio(1, 3),       // Equal chunk 3
-            Constraint::Length(4),         // Small buffer space
-        ]);
-        let right_chunks = right_layout.split(tables_rect);
-
-        self.render_hard_hands_table(frame, right_chunks[1]);
-        self.render_soft_hands_table(frame, right_chunks[2]);
-        self.render_pair_hands_table(frame, right_chunks[3]);
+        match self.view {
+            ChartView::Strategy => self.render_strategy_chart(frame, right_section),
+            ChartView::IndexPlays => self.render_index_plays(frame, right_section),
+            ChartView::DealerOutcomes => self.render_dealer_outcomes(frame, right_section),
+            ChartView::BustChances => self.render_bust_chances(frame, right_section),
+            ChartView::RuleImpact => self.render_rule_impacts(frame, right_section),
+            ChartView::Insurance => Self::render_insurance(frame, right_section),
+            ChartView::Comparison => self.render_comparison(frame, right_section),
+            ChartView::FileProblems => self.render_file_problems(frame, right_section),
+            ChartView::MissingCharts => self.render_missing_charts(frame, right_section),
+            ChartView::StrategyPicker => self.render_strategy_picker(frame, right_section),
+            ChartView::StrategyPacks => self.render_strategy_packs(frame, right_section),
+        }
+        if self.show_load_errors {
+            self.render_load_errors(frame, right_section);
+        }
+        // Shown in the bottom border so it doesn't cover any view
+        if let Some(notice) = &self.notice {
+            let notice_area = Rect { y: right_section.bottom().saturating_sub(1), height: 1, ..right_section };
+            let notice = Paragraph::new(format!(" {} ", notice)).fg(Color::Cyan).alignment(Alignment::Center);
+            frame.render_widget(notice, notice_area);
+        }
 
         // Render Footer
         let footer_area = main_chunks[2];
@@ -484,7 +2162,92 @@ impl Model for StrategyCalculatorScreen {
                 Constraint::Min(20),
             ])
             .split(footer_area);
-        render_footer_spans(frame, vec![], footer_layout[1]);
+        // The hand being looked up, or what the chart plays for it, comes first
+        let mut spans = match (&self.lookup_input, &self.lookup_result) {
+            (Some(input), _) => vec![" Look Up ".to_string(), format!(" {}_ ", input)],
+            (None, Some(result)) => vec![" Chart ".to_string(), format!(" {} ", result)],
+            (None, None) => vec![" / ".to_string(), " Look Up Hand ".to_string()],
+        };
+        spans.extend([
+            " C ".to_string(),
+            if self.exceptions.is_some() { " Hide Exceptions " } else { " Show Exceptions " }.to_string(),
+            " I ".to_string(),
+            if self.view == ChartView::IndexPlays { " Strategy Chart " } else { " Index Plays " }.to_string(),
+            " D ".to_string(),
+            if self.view == ChartView::DealerOutcomes { " Strategy Chart " } else { " Dealer Outcomes " }.to_string(),
+            " B ".to_string(),
+            if self.view == ChartView::BustChances { " Strategy Chart " } else { " Bust Chances " }.to_string(),
+            " R ".to_string(),
+            if self.view == ChartView::RuleImpact { " Strategy Chart " } else { " Rule Impact " }.to_string(),
+            " N ".to_string(),
+            if self.view == ChartView::Insurance { " Strategy Chart " } else { " Insurance " }.to_string(),
+            " V ".to_string(),
+            if self.view == ChartView::Comparison { " Strategy Chart " } else { " Compare " }.to_string(),
+            " X ".to_string(),
+            " Export Chart ".to_string(),
+            " O ".to_string(),
+            if self.view == ChartView::MissingCharts { " Strategy Chart " } else { " Missing Charts " }.to_string(),
+            " P ".to_string(),
+            if self.view == ChartView::StrategyPicker { " Strategy Chart " } else { " Pick Strategy " }.to_string(),
+            " W ".to_string(),
+            if self.view == ChartView::StrategyPacks { " Strategy Chart " } else { " Strategy Packs " }.to_string(),
+            " T ".to_string(),
+            if self.transposed { " Hands Down Side " } else { " Upcards Down Side " }.to_string(),
+        ]);
+        if !self.load_errors.is_empty() {
+            spans.extend([
+                " F ".to_string(),
+                if self.view == ChartView::FileProblems { " Strategy Chart " } else { " File Problems " }.to_string(),
+            ]);
+        }
+        match self.view {
+            ChartView::Strategy if self.cursor.is_some() => spans.extend([
+                " E ".to_string(), " Stop Inspecting ".to_string(),
+                " Tab ".to_string(), " Next Table ".to_string(),
+                " Enter ".to_string(),
+                if self.cell_detail { " Close Explanation " } else { " Explain Play " }.to_string(),
+                " Y ".to_string(), " Copy Cell ".to_string(),
+                " Shift+Y ".to_string(), " Copy Row ".to_string(),
+            ]),
+            ChartView::Strategy => spans.extend([
+                " E ".to_string(), " Inspect EVs ".to_string(),
+                " Y ".to_string(), " Copy Chart ".to_string(),
+                " A ".to_string(),
+                if self.mistakes.is_some() { " Action Colors " } else { " Mistake Heatmap " }.to_string(),
+            ]),
+            ChartView::IndexPlays => spans.extend([" S ".to_string(), " Counting System ".to_string()]),
+            ChartView::DealerOutcomes => spans.extend([" Tab ".to_string(), " Upcard ".to_string()]),
+            ChartView::Comparison => spans.extend([
+                " Tab ".to_string(), " Compare With ".to_string(),
+                " S ".to_string(),
+                match self.side_by_side {
+                    None => " Side By Side ".to_string(),
+                    Some(section) if section + 1 < CHART_SECTIONS.len() => format!(" {} ", CHART_SECTIONS[section + 1]),
+                    Some(_) => " Differences ".to_string(),
+                },
+            ]),
+            ChartView::StrategyPicker if self.metadata_input.is_some() => spans.extend([
+                " Enter ".to_string(), " Save ".to_string(),
+                " Esc ".to_string(), " Cancel ".to_string(),
+            ]),
+            ChartView::StrategyPicker => spans.extend([
+                " Enter ".to_string(), " Show Chart ".to_string(),
+                " E ".to_string(), " Rename ".to_string(),
+                " A ".to_string(), " Edit Description ".to_string(),
+                " U ".to_string(), " New UUID ".to_string(),
+            ]),
+            ChartView::StrategyPacks if matches!(&self.strategy_packs, Some(Ok(packs)) if !packs.is_empty()) => {
+                spans.extend([" Enter ".to_string(), " Install Pack ".to_string()]);
+            }
+            ChartView::MissingCharts if !self.missing_charts.is_empty() => {
+                spans.extend([" G ".to_string(), " Save Computed Charts ".to_string()]);
+            }
+            _ => {}
+        }
+        if self.view == ChartView::Strategy && self.chart_scroll.is_scrollable() {
+            spans.extend([" PgUp/PgDn ".to_string(), " Scroll Chart ".to_string()]);
+        }
+        render_footer_spans(frame, spans, footer_layout[1]);
     }
 }
 
@@ -507,8 +2270,14 @@ impl StrategyCalculatorScreen {
 
     /// Creates a consistently styled header row for strategy tables
     fn create_table_header(&self) -> Row<'static> {
-        let header_cells = vec![" ", "2", "3", "4", "5", "6", "7", "8", "9", "10", "A"]
-            .into_iter()
+        let upcards = ["2", "3", "4", "5", "6", "7", "8", "9", "10", "A"];
+        self.create_table_header_from(upcards.into_iter().map(String::from))
+    }
+
+    /// A header with a blank corner, then `columns`
+    fn create_table_header_from(&self, columns: impl Iterator<Item = String>) -> Row<'static> {
+        let header_cells = std::iter::once(" ".to_string())
+            .chain(columns)
             .map(|h| Cell::new(h).style(Style::new().bold()))
             .collect::<Vec<_>>();
 
@@ -531,11 +2300,12 @@ impl StrategyCalculatorScreen {
         &self,
         rows: Vec<Row<'a>>,
         widths: Vec<Constraint>,
+        header: Row<'a>,
         title: &'a str
     ) -> Table<'a> {
         Table::new(rows, widths)
             .style(Style::new().blue())
-            .header(self.create_table_header())
+            .header(header)
             .block(Block::new()
                 .title(title)
                 .style(Style::new().bold())
@@ -544,7 +2314,6 @@ impl StrategyCalculatorScreen {
             .column_spacing(1)
             .column_highlight_style(Style::new().red())
             .cell_highlight_style(Style::new().blue())
-            .highlight_symbol(">>")
     }
 
     /// Creates a centered inner area for a table with appropriate margins
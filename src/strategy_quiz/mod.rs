@@ -0,0 +1 @@
+pub mod strategy_quiz_screen;
\ No newline at end of file
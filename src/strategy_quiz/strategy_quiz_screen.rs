@@ -0,0 +1,1000 @@
+use crate::constants::{HISTORY_DATABASE_FILE, STREAKS_FILE};
+use crate::logic::app_dirs::data_file;
+use crate::logic::config::strategies_dir;
+use crate::logic::history_store::HistoryStore;
+use crate::logic::decision_accuracy::DecisionAccuracy;
+use crate::logic::game::advisor::chart_hand;
+use crate::logic::game::round::PlayerAction;
+use crate::logic::strategy_calculator_logic::{find_nearest_strategy, load_strategies, BlackjackStrategy, GameVariant, StrategyVariables, SurrenderRule};
+use crate::logic::counting_stats::{format_day, now};
+use crate::logic::spaced_repetition::ReviewSchedule;
+use crate::logic::streaks::Streaks;
+use crate::logic::surrender_drill::SurrenderDrill;
+use crate::logic::strategy_quiz::{chart_cells, DrillFilter, Flashcard, FlashcardDeck, HandClass, QuizAnswer, QuizQuestion, StrategyQuiz, QUIZ_LENGTH};
+use crate::logic::time_attack::{HighScore, HighScores, TimeAttack, DEFAULT_TIME_LIMIT, TIME_LIMITS};
+use crate::model::{Model, ModelResponse};
+use crate::ui::{create_common_layout, render_border, render_centered_text, render_key_spans, render_streak, split_content_horizontally, ActionPalette, read_event};
+use crate::widgets::card_widget::{CardRow, CardStyle, CARD_HEIGHT};
+use ratatui::crossterm::event;
+use ratatui::crossterm::event::{Event, KeyCode};
+use ratatui::layout::{Alignment, Constraint, Direction, Layout, Margin, Rect};
+use ratatui::prelude::{Line, Stylize};
+use ratatui::style::Color;
+use ratatui::widgets::{Block, Borders, Paragraph, Row, Table, Wrap};
+use ratatui::Frame;
+use std::cell::Cell;
+use std::fmt;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+const HIGH_SCORES_FILE: &str = "high_scores.json";
+const REVIEW_SCHEDULE_FILE: &str = "review_schedule.json";
+
+/// Keys for each play, in the order they're offered
+const ACTION_KEYS: [(char, PlayerAction); 5] = [
+    ('h', PlayerAction::Hit),
+    ('s', PlayerAction::Stand),
+    ('d', PlayerAction::Double),
+    ('p', PlayerAction::Split),
+    ('r', PlayerAction::Surrender),
+];
+
+/// Size of a flashcard on screen, borders included
+const FLASHCARD_WIDTH: u16 = 36;
+const FLASHCARD_HEIGHT: u16 = 11;
+
+// ---- Study Modes ----
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum StudyMode {
+    /// Dealt hands, scored against the chart
+    Quiz,
+    /// Chart cells, revealed on request and graded by the player
+    Flashcards,
+    /// Dealt hands against the clock, scored for speed and accuracy
+    TimeAttack,
+    /// Hands some chart surrenders, each under a chart's surrender rule
+    Surrender,
+}
+
+impl StudyMode {
+    /// Name the mode's streak is kept under, among every drill's.
+    fn drill_name(self) -> &'static str {
+        match self {
+            StudyMode::Quiz => "Strategy Quiz",
+            StudyMode::Flashcards => "Flashcards",
+            StudyMode::TimeAttack => "Time Attack",
+            StudyMode::Surrender => "Surrender Drill",
+        }
+    }
+
+    fn next(self) -> Self {
+        match self {
+            StudyMode::Quiz => StudyMode::Flashcards,
+            StudyMode::Flashcards => StudyMode::TimeAttack,
+            StudyMode::TimeAttack => StudyMode::Surrender,
+            StudyMode::Surrender => StudyMode::Quiz,
+        }
+    }
+}
+
+impl fmt::Display for StudyMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            StudyMode::Quiz => "Quiz",
+            StudyMode::Flashcards => "Flashcards",
+            StudyMode::TimeAttack => "Time Attack",
+            StudyMode::Surrender => "Surrender",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+// ---- Strategy Quiz Screen ----
+/// Deals random starting hands against dealer upcards, no money involved,
+/// and scores each play against a strategy chart, breaking the score down
+/// by hand and by play once the quiz is done. Cells that have been missed
+/// are asked again on a spaced repetition schedule. Every mode can be
+/// limited to a few kinds of hands from the drill filters. Flashcards of the chart's
+/// cells are a lighter way to study the same chart, and time attacks a
+/// harder one, with the best runs kept in a high score table. The surrender
+/// drill works across every chart that allows surrender.
+pub struct StrategyQuizScreen {
+    mode: StudyMode,
+
+    /// Loaded charts by name, in name order, to quiz from
+    strategies: Vec<(String, BlackjackStrategy)>,
+
+    /// Position in `strategies` of the chart being quizzed, none when no
+    /// charts were loaded
+    strategy: Option<usize>,
+
+    /// The kinds of hands drilled, in every mode
+    filter: DrillFilter,
+
+    /// Whether the drill filters are being picked
+    configuring: bool,
+
+    /// Position in `HandClass::ALL` of the filter under the cursor
+    filter_cursor: usize,
+    quiz: Option<StrategyQuiz>,
+
+    /// Missed cells and when they're due for review, kept between runs
+    schedule: ReviewSchedule,
+
+    /// Whether the quiz question is a review of a missed cell
+    reviewing: bool,
+
+    flashcards: Option<FlashcardDeck>,
+
+    /// The time attack under way, none between runs
+    time_attack: Option<TimeAttack>,
+
+    /// Position in `TIME_LIMITS` of the length of the next run
+    time_limit: usize,
+    started_at: Instant,
+    asked_at: Instant,
+
+    /// The last run finished, with where it placed in the high scores
+    last_run: Option<(HighScore, Option<usize>)>,
+    high_scores: HighScores,
+
+    /// The surrender drill, or why there's none
+    surrender: Result<SurrenderDrill, String>,
+
+    /// Right answers in a row in each mode, carried over between sessions
+    streaks: Streaks,
+    /// Database every answer and chart cell play is kept in, shared with
+    /// play at the table, none when it can't be opened
+    store: Option<HistoryStore>,
+    card_style: CardStyle,
+    /// How chart codes are colored, shared with the other screens
+    palette: Rc<Cell<ActionPalette>>,
+    message: String,
+}
+
+impl StrategyQuizScreen {
+    pub fn new(palette: Rc<Cell<ActionPalette>>) -> Self {
+        let loaded = load_strategies(&strategies_dir());
+        // Start with the chart for the game's table
+        let rules = StrategyVariables {
+            decks: 6,
+            dealer_stands_on_soft_17: true,
+            double_after_split: true,
+            dealer_peak: true,
+            surrender_allowed: SurrenderRule::AnyUpcard,
+            variant: GameVariant::Classic,
+        };
+        let table_chart = find_nearest_strategy(&loaded, &rules).map(|(name, _, _)| name);
+
+        let mut strategies: Vec<(String, BlackjackStrategy)> = loaded.into_iter().collect();
+        strategies.sort_by(|a, b| a.0.cmp(&b.0));
+        let strategy = table_chart
+            .and_then(|name| strategies.iter().position(|(loaded, _)| *loaded == name))
+            .or((!strategies.is_empty()).then_some(0));
+
+        let mut screen = Self {
+            mode: StudyMode::Quiz,
+            strategies,
+            strategy,
+            filter: DrillFilter::default(),
+            configuring: false,
+            filter_cursor: 0,
+            quiz: None,
+            schedule: ReviewSchedule::load(&data_file(REVIEW_SCHEDULE_FILE)),
+            reviewing: false,
+            flashcards: None,
+            time_attack: None,
+            time_limit: DEFAULT_TIME_LIMIT,
+            started_at: Instant::now(),
+            asked_at: Instant::now(),
+            last_run: None,
+            high_scores: HighScores::load(&data_file(HIGH_SCORES_FILE)),
+            surrender: Err(String::new()),
+            streaks: Streaks::load(&data_file(STREAKS_FILE)),
+            store: HistoryStore::open(&data_file(HISTORY_DATABASE_FILE), "Strategy Quiz").ok(),
+            card_style: CardStyle::default(),
+            palette,
+            message: String::new(),
+        };
+        screen.new_quiz();
+        screen.new_flashcards();
+        screen.new_surrender_drill();
+        screen
+    }
+
+    fn new_quiz(&mut self) {
+        self.quiz = self.strategy.map(|index| StrategyQuiz::new(self.strategies[index].1.clone(), QUIZ_LENGTH, self.filter.clone()));
+        self.message = "What's the play?".to_string();
+        self.ask_review();
+    }
+
+    fn new_flashcards(&mut self) {
+        self.flashcards = self.strategy.map(|index| FlashcardDeck::new(&self.strategies[index].1, &self.filter));
+    }
+
+    fn new_surrender_drill(&mut self) {
+        self.surrender = SurrenderDrill::new(&self.strategies);
+        if self.mode == StudyMode::Surrender {
+            self.message = "Surrender, or play it out?".to_string();
+        }
+    }
+
+    /// Sometimes swaps the next quiz question for a missed cell that's due,
+    /// when the drill filters allow it. Cells missed at the table or in the
+    /// other drills are scheduled first.
+    fn ask_review(&mut self) {
+        self.reviewing = false;
+        let Some(index) = self.strategy else {
+            return;
+        };
+        let (chart, strategy) = &self.strategies[index];
+        let accuracy = self.store.as_ref().and_then(|store| store.decision_accuracy().ok()).unwrap_or_default();
+        self.schedule.schedule_missed(chart, &accuracy);
+        let review = self.schedule.next_review(chart, |question| self.filter.allows_question(strategy, question));
+        if let (Some(quiz), Some(question)) = (&mut self.quiz, review) {
+            quiz.ask_instead(question);
+            self.reviewing = true;
+        }
+    }
+
+    /// Grades the answer's cell in the review schedule, saving it.
+    fn record_review(&mut self, answer: &QuizAnswer) {
+        let chart = self.chart_name().to_string();
+        self.schedule.record(&chart, &answer.question, answer.is_correct());
+        if let Err(err) = self.schedule.save(&data_file(REVIEW_SCHEDULE_FILE)) {
+            self.message = format!("Could not save the review schedule: {}", err);
+        }
+        self.record_accuracy(&chart, answer);
+        self.record_streak(answer.is_correct());
+    }
+
+    /// Counts the answer in its chart cell's accuracy, saving it.
+    fn record_accuracy(&mut self, chart: &str, answer: &QuizAnswer) {
+        let Some((_, strategy)) = self.strategies.iter().find(|(name, _)| name == chart) else {
+            return;
+        };
+        let question = &answer.question;
+        let hand = chart_hand(strategy, &question.hand, question.hand.is_pair());
+        let mut plays = DecisionAccuracy::default();
+        plays.record(chart, hand, question.upcard, answer.is_correct());
+        self.save_plays(&plays);
+    }
+
+    /// Counts a flashcard in its chart cell's accuracy, saving it.
+    fn record_card(&mut self, card: &Flashcard, knew: bool) {
+        let chart = self.chart_name().to_string();
+        let mut plays = DecisionAccuracy::default();
+        plays.record_cell(&chart, card.chart_hand(), card.column, knew);
+        self.save_plays(&plays);
+    }
+
+    /// Adds plays to the chart cells kept in the play history.
+    fn save_plays(&mut self, plays: &DecisionAccuracy) {
+        if let Some(store) = &mut self.store
+            && let Err(err) = store.add_cells(plays) {
+            self.message = format!("Could not save decision accuracy: {}", err);
+        }
+    }
+
+    /// Adds an answer to the mode's streak and the answers kept, saving them.
+    fn record_streak(&mut self, correct: bool) {
+        self.streaks.record(self.mode.drill_name(), correct);
+        if let Err(err) = self.streaks.save(&data_file(STREAKS_FILE)) {
+            self.message = format!("Could not save streaks: {}", err);
+        }
+        if let Some(store) = &mut self.store
+            && let Err(err) = store.record_answer(self.mode.drill_name(), correct) {
+            self.message = format!("Could not save the answer: {}", err);
+        }
+    }
+
+    fn chart_name(&self) -> &str {
+        self.strategy.map_or("", |index| self.strategies[index].0.as_str())
+    }
+
+    /// Moves on to the next chart, with a new quiz and a new deck of
+    /// flashcards, calling off any time attack under way.
+    fn cycle_strategy(&mut self) {
+        if let Some(index) = self.strategy {
+            let index = (index + 1) % self.strategies.len();
+            self.strategy = Some(index);
+            self.new_quiz();
+            self.new_flashcards();
+            self.time_attack = None;
+            if self.mode == StudyMode::TimeAttack {
+                self.message = "Press Enter to start".to_string();
+            }
+        }
+    }
+
+    /// Moves on to the next mode, calling off any time attack under way.
+    fn switch_mode(&mut self) {
+        self.mode = self.mode.next();
+        self.time_attack = None;
+        self.message = match self.mode {
+            StudyMode::Quiz if self.quiz.as_ref().is_some_and(StrategyQuiz::is_finished) => "Press N for a new quiz",
+            StudyMode::Quiz => "What's the play?",
+            StudyMode::Flashcards => "Press Space to reveal the play",
+            StudyMode::TimeAttack => "Press Enter to start",
+            StudyMode::Surrender => "Surrender, or play it out?",
+        }.to_string();
+    }
+
+    /// Opens the drill filters, or closes them and starts over with the
+    /// hands picked.
+    fn toggle_configuring(&mut self) {
+        self.configuring = !self.configuring;
+        if self.configuring {
+            self.time_attack = None;
+            self.message = String::new();
+            return;
+        }
+        self.new_quiz();
+        self.new_flashcards();
+        if self.flashcards.as_ref().is_some_and(FlashcardDeck::is_empty) {
+            self.message = "Nothing on this chart matches the drill filters".to_string();
+        } else if self.mode == StudyMode::Flashcards {
+            self.message = "Press Space to reveal the play".to_string();
+        } else if self.mode == StudyMode::TimeAttack {
+            self.message = "Press Enter to start".to_string();
+        }
+    }
+
+    fn move_filter_cursor(&mut self, down: bool) {
+        let count = HandClass::ALL.len();
+        self.filter_cursor = if down { (self.filter_cursor + 1) % count } else { (self.filter_cursor + count - 1) % count };
+    }
+
+    fn start_time_attack(&mut self) {
+        let Some(index) = self.strategy else {
+            return;
+        };
+        self.time_attack = Some(TimeAttack::new(self.strategies[index].1.clone(), TIME_LIMITS[self.time_limit], self.filter.clone()));
+        self.started_at = Instant::now();
+        self.asked_at = Instant::now();
+        self.message = "What's the play?".to_string();
+    }
+
+    fn cycle_time_limit(&mut self) {
+        self.time_limit = (self.time_limit + 1) % TIME_LIMITS.len();
+    }
+
+    /// Time left in the run under way.
+    fn time_left(&self, run: &TimeAttack) -> Duration {
+        Duration::from_secs(run.seconds()).saturating_sub(self.started_at.elapsed())
+    }
+
+    /// Ends the run once its time is up.
+    fn tick_time_attack(&mut self) {
+        if self.time_attack.as_ref().is_some_and(|run| self.time_left(run).is_zero()) {
+            self.finish_time_attack();
+        }
+    }
+
+    /// Scores a play in the time attack, with the points it earned.
+    fn answer_time_attack(&mut self, action: PlayerAction) {
+        self.tick_time_attack();
+        let Some(run) = &mut self.time_attack else {
+            return;
+        };
+        let (answer, points) = match run.answer(action, self.asked_at.elapsed()) {
+            Ok(scored) => scored,
+            Err(err) => {
+                self.message = err;
+                return;
+            }
+        };
+        self.message = if answer.is_correct() {
+            format!("Right, {} on {} (+{})", answer.expected, answer.question, points)
+        } else {
+            format!("{} is a {}, not a {} ({})", answer.question, answer.expected, answer.answer, points)
+        };
+        self.asked_at = Instant::now();
+        self.record_review(&answer);
+    }
+
+    /// Puts the finished run in the high scores, saving them, unless
+    /// nothing was answered or the hands were filtered.
+    fn finish_time_attack(&mut self) {
+        let Some(run) = self.time_attack.take() else {
+            return;
+        };
+        let high_score = run.finish(self.chart_name(), now());
+        let counts = run.answered() > 0 && self.filter.is_empty();
+        let rank = if counts { self.high_scores.record(high_score.clone()) } else { None };
+        self.message = match rank {
+            Some(rank) => format!("Time! {} points, number {} in the high scores", high_score.score, rank + 1),
+            None if !self.filter.is_empty() => format!("Time! {} points, filtered runs don't make the high scores", high_score.score),
+            None => format!("Time! {} points", high_score.score),
+        };
+        if rank.is_some() && let Err(err) = self.high_scores.save(&data_file(HIGH_SCORES_FILE)) {
+            self.message = format!("Could not save high scores: {}", err);
+        }
+        self.last_run = Some((high_score, rank));
+    }
+
+    /// Shows the play on the current flashcard, or takes the player's word
+    /// for whether they knew it once it's shown.
+    fn study_flashcard(&mut self, knew: Option<bool>) {
+        let Some(deck) = &mut self.flashcards else {
+            return;
+        };
+        self.message = match knew {
+            None => {
+                deck.reveal();
+                "Did you know it?".to_string()
+            }
+            Some(knew) => {
+                let card = deck.current().cloned();
+                match deck.grade(knew) {
+                    Ok(()) => {
+                        if let Some(card) = card {
+                            self.record_card(&card, knew);
+                        }
+                        self.record_streak(knew);
+                        "Press Space to reveal the play".to_string()
+                    }
+                    Err(err) => err,
+                }
+            }
+        };
+    }
+
+    /// Scores a play in the surrender drill, saying what the hand's chart
+    /// calls for when it's wrong.
+    fn answer_surrender(&mut self, action: PlayerAction) {
+        let Ok(drill) = &mut self.surrender else {
+            return;
+        };
+        let (chart, answer) = match drill.answer(action) {
+            Ok(answer) => (answer.chart.clone(), answer.answer.clone()),
+            Err(err) => {
+                self.message = err;
+                return;
+            }
+        };
+        self.message = if answer.is_correct() {
+            format!("Right, {} on {}", answer.expected, answer.question)
+        } else {
+            format!("{} is a {} under those rules, not a {}", answer.question, answer.expected, answer.answer)
+        };
+        self.record_accuracy(&chart, &answer);
+        self.record_streak(answer.is_correct());
+    }
+
+    /// Scores a play, saying what the chart calls for when it's wrong.
+    fn answer(&mut self, action: PlayerAction) {
+        let Some(quiz) = &mut self.quiz else {
+            return;
+        };
+        let answer = match quiz.answer(action) {
+            Ok(answer) => answer.clone(),
+            Err(err) => {
+                self.message = err;
+                return;
+            }
+        };
+        self.message = if answer.is_correct() {
+            format!("Right, {} on {}", answer.expected, answer.question)
+        } else {
+            format!("{} is a {}, not a {}", answer.question, answer.expected, answer.answer)
+        };
+        self.record_review(&answer);
+        self.ask_review();
+
+        let Some(quiz) = &self.quiz else {
+            return;
+        };
+        if quiz.is_finished() {
+            self.message = format!(
+                "Quiz done: {} of {} right ({:.0}%)",
+                quiz.correct(),
+                quiz.length(),
+                quiz.correct() as f64 / quiz.length() as f64 * 100.0,
+            );
+        }
+    }
+
+    /// The chart, the score so far and the latest answers.
+    fn render_score(&self, frame: &mut Frame, rect: Rect) {
+        let Some(quiz) = &self.quiz else {
+            return;
+        };
+        let chart = self.chart_name();
+        let answers = quiz.answers();
+        let mut lines = vec![
+            Line::from(""),
+            Line::from(format!("Chart: {}", chart)),
+            Line::from(""),
+            Line::from(format!("Question: {} of {}", (answers.len() + 1).min(quiz.length()), quiz.length())),
+            Line::from(format!("Right: {} of {}", quiz.correct(), answers.len())),
+            Line::from(format!("Weak Hands: {}, {} due", self.schedule.weakest(chart).len(), self.schedule.due(chart).len())),
+            Line::from(if self.reviewing && !quiz.is_finished() { "Reviewing a missed hand" } else { "" }).fg(Color::Yellow),
+            Line::from("Answers Given").bold(),
+        ];
+        // The latest answers when they don't all fit
+        let fits = (rect.height as usize).saturating_sub(lines.len());
+        for answer in answers[answers.len().saturating_sub(fits)..].iter().rev() {
+            let line = Line::from(format!("{}: {}", answer.question, answer.answer));
+            lines.push(if answer.is_correct() { line.fg(Color::Green) } else { line.fg(Color::Red) });
+        }
+
+        let score = Paragraph::new(lines)
+            .alignment(Alignment::Center)
+            .block(Block::default());
+        frame.render_widget(score, rect);
+    }
+
+    /// The dealer's upcard over the player's hand, with the keys for the
+    /// plays that can be made.
+    fn render_question(&self, frame: &mut Frame, rect: Rect, question: &QuizQuestion, available: &[PlayerAction]) {
+        let layout = Layout::vertical([
+            Constraint::Min(0),
+            Constraint::Length(1),
+            Constraint::Length(CARD_HEIGHT),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(CARD_HEIGHT),
+            Constraint::Length(2),
+            Constraint::Length(2),
+            Constraint::Length(2),
+            Constraint::Min(0),
+        ]).split(rect);
+
+        frame.render_widget(Paragraph::new("Dealer").bold().alignment(Alignment::Center), layout[1]);
+        frame.render_widget(CardRow::from_cards(&[question.upcard], 0).style(self.card_style), layout[2]);
+        frame.render_widget(Paragraph::new("Player").bold().alignment(Alignment::Center), layout[4]);
+        frame.render_widget(CardRow::from_cards(question.hand.cards(), 0).style(self.card_style), layout[5]);
+
+        let total = if question.hand.is_soft() {
+            format!("(soft {})", question.hand.total())
+        } else {
+            format!("({})", question.hand.total())
+        };
+        frame.render_widget(Paragraph::new(total).alignment(Alignment::Center), layout[6]);
+
+        let prompt = ACTION_KEYS.iter()
+            .filter(|(_, action)| available.contains(action))
+            .map(|(key, action)| format!("{}: {}", key.to_ascii_uppercase(), action))
+            .collect::<Vec<String>>()
+            .join("    ");
+        frame.render_widget(Paragraph::new(prompt).bold().fg(Color::Yellow).alignment(Alignment::Center), layout[7]);
+        frame.render_widget(Paragraph::new(self.message.as_str()).bold().alignment(Alignment::Center), layout[8]);
+    }
+
+    /// How the quiz went for each table of the chart and each play it
+    /// called for, then the hands that were missed.
+    fn render_results(&self, frame: &mut Frame, rect: Rect) {
+        let Some(quiz) = &self.quiz else {
+            return;
+        };
+        let row = |name: String, right: usize, asked: usize| {
+            let percent = right as f64 / asked as f64 * 100.0;
+            let color = if right == asked { Color::Green } else { Color::Red };
+            Row::new(vec![name, format!("{} of {}", right, asked), format!("{:.0}%", percent)]).fg(color)
+        };
+        let categories: Vec<Row> = quiz.by_category().into_iter()
+            .map(|(category, right, asked)| row(category.to_string(), right, asked))
+            .collect();
+        let plays: Vec<Row> = quiz.by_play().into_iter()
+            .map(|(play, right, asked)| row(format!("{} Hands", play), right, asked))
+            .collect();
+        let misses: Vec<Line> = quiz.answers().iter()
+            .filter(|answer| !answer.is_correct())
+            .map(|answer| Line::from(format!("{}: {}, not {}", answer.question, answer.expected, answer.answer)).fg(Color::Red))
+            .collect();
+
+        let layout = Layout::vertical([
+            Constraint::Length(2),
+            Constraint::Length(categories.len() as u16 + 2),
+            Constraint::Length(plays.len() as u16 + 2),
+            Constraint::Length(1),
+            Constraint::Min(0),
+        ]).split(rect.inner(Margin { vertical: 1, horizontal: 2 }));
+
+        frame.render_widget(Paragraph::new(self.message.as_str()).bold().alignment(Alignment::Center), layout[0]);
+        let widths = [Constraint::Length(18), Constraint::Length(10), Constraint::Length(6)];
+        let header = |name: &str| Row::new(vec![name.to_string(), "Right".to_string(), String::new()]).bold().underlined();
+        let centered = |area: Rect| area.inner(Margin { vertical: 0, horizontal: area.width.saturating_sub(36) / 2 });
+        frame.render_widget(Table::new(categories, widths).header(header("By Hand")), centered(layout[1]));
+        frame.render_widget(Table::new(plays, widths).header(header("By Play")), centered(layout[2]));
+
+        let mut lines = vec![Line::from(if misses.is_empty() { "No Misses" } else { "Missed" }).bold()];
+        lines.extend(misses);
+        frame.render_widget(Paragraph::new(lines).alignment(Alignment::Center), layout[4]);
+    }
+
+    /// The score in the surrender drill under each rule, and the latest
+    /// answers.
+    fn render_surrender_score(&self, frame: &mut Frame, rect: Rect) {
+        let Ok(drill) = &self.surrender else {
+            return;
+        };
+        let answers = drill.answers();
+        let mut lines = vec![
+            Line::from(""),
+            Line::from(format!("Right: {} of {}", drill.correct(), answers.len())),
+            Line::from(""),
+        ];
+        for (rule, right, asked) in drill.by_rule() {
+            lines.push(Line::from(rule.to_string()).bold());
+            lines.push(Line::from(format!("{} of {} ({:.0}%)", right, asked, right as f64 / asked as f64 * 100.0)));
+        }
+        lines.extend([Line::from(""), Line::from("Answers Given").bold()]);
+        // The latest answers when they don't all fit
+        let fits = (rect.height as usize).saturating_sub(lines.len());
+        for answer in answers[answers.len().saturating_sub(fits)..].iter().rev() {
+            let answer = &answer.answer;
+            let line = Line::from(format!("{}: {}", answer.question, answer.answer));
+            lines.push(if answer.is_correct() { line.fg(Color::Green) } else { line.fg(Color::Red) });
+        }
+
+        let score = Paragraph::new(lines)
+            .alignment(Alignment::Center)
+            .block(Block::default());
+        frame.render_widget(score, rect);
+    }
+
+    /// The rules of the chart the hand is dealt under, over the hand.
+    fn render_surrender_question(&self, frame: &mut Frame, rect: Rect) {
+        let drill = match &self.surrender {
+            Ok(drill) => drill,
+            Err(err) => {
+                frame.render_widget(Paragraph::new(format!("\n{}", err)).alignment(Alignment::Center), rect);
+                return;
+            }
+        };
+        let [rules_area, question_area] = Layout::vertical([
+            Constraint::Length(3),
+            Constraint::Min(0),
+        ]).areas(rect);
+        let rules = drill.chart().1.rules.to_string();
+        frame.render_widget(Paragraph::new(format!("\n{}", rules)).bold().fg(Color::Cyan).alignment(Alignment::Center).wrap(Wrap { trim: true }), rules_area);
+        self.render_question(frame, question_area, drill.question(), &drill.available());
+    }
+
+    /// The clock and the score of the run under way, or the high scores
+    /// for the next run's length between runs.
+    fn render_time_attack_score(&self, frame: &mut Frame, rect: Rect) {
+        let mut lines = vec![
+            Line::from(""),
+            Line::from(format!("Chart: {}", self.chart_name())),
+            Line::from(""),
+        ];
+        if let Some(run) = &self.time_attack {
+            let left = self.time_left(run).as_secs_f64().ceil();
+            let clock = Line::from(format!("Time Left: {:.0}s", left)).bold();
+            lines.push(if left <= 10.0 { clock.fg(Color::Red) } else { clock });
+            lines.push(Line::from(format!("Score: {}", run.score())).bold());
+            lines.push(Line::from(format!("Right: {} of {}", run.correct(), run.answered())));
+        } else {
+            let seconds = TIME_LIMITS[self.time_limit];
+            lines.push(Line::from(format!("High Scores, {}s", seconds)).bold());
+            let table = self.high_scores.table(seconds);
+            if table.is_empty() {
+                lines.push(Line::from("None yet").fg(Color::DarkGray));
+            }
+            let latest = self.last_run.as_ref().and_then(|(run, rank)| Some((run.seconds, (*rank)?)));
+            for (rank, high_score) in table.iter().enumerate() {
+                let line = Line::from(format!("{:>2}. {:>5}  {}", rank + 1, high_score.score, format_day(high_score.day())));
+                lines.push(if latest == Some((seconds, rank)) { line.fg(Color::Yellow) } else { line });
+            }
+        }
+
+        let score = Paragraph::new(lines)
+            .alignment(Alignment::Center)
+            .block(Block::default());
+        frame.render_widget(score, rect);
+    }
+
+    /// How the last run went, with the prompt to start the next.
+    fn render_time_attack_results(&self, frame: &mut Frame, rect: Rect) {
+        let mut lines = vec![Line::from("")];
+        if let Some((run, _)) = &self.last_run {
+            lines.extend([
+                Line::from(self.message.as_str()).bold(),
+                Line::from(""),
+                Line::from(format!("Answered: {} in {}s", run.answered, run.seconds)),
+                Line::from(format!("Right: {}", run.correct)),
+                Line::from(run.accuracy().map_or(String::new(), |accuracy| format!("Accuracy: {:.0}%", accuracy * 100.0))),
+                Line::from(""),
+            ]);
+        }
+        lines.extend([
+            Line::from("Make as many plays as you can before the clock runs out."),
+            Line::from("Right answers score 100, and up to 100 more the quicker they come."),
+            Line::from("Wrong answers cost 50."),
+            Line::from(""),
+            Line::from(format!("Press Enter to start a {} second run", TIME_LIMITS[self.time_limit])).bold().fg(Color::Yellow),
+        ]);
+        frame.render_widget(Paragraph::new(lines).alignment(Alignment::Center), rect);
+    }
+
+    /// What the drill filters leave of the chart.
+    fn render_filter_summary(&self, frame: &mut Frame, rect: Rect) {
+        let Some(index) = self.strategy else {
+            return;
+        };
+        let cells = chart_cells(&self.strategies[index].1);
+        let matching = cells.iter().filter(|cell| self.filter.allows(cell)).count();
+        let lines = vec![
+            Line::from(""),
+            Line::from(format!("Chart: {}", self.chart_name())),
+            Line::from(""),
+            Line::from("Drilling").bold(),
+            Line::from(self.filter.to_string()),
+            Line::from(""),
+            Line::from(format!("Chart Cells: {} of {}", matching, cells.len())),
+        ];
+
+        let summary = Paragraph::new(lines)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true })
+            .block(Block::default());
+        frame.render_widget(summary, rect);
+    }
+
+    /// The kinds of hands that can be drilled, ticked when picked.
+    fn render_filters(&self, frame: &mut Frame, rect: Rect) {
+        let mut lines = vec![
+            Line::from(""),
+            Line::from("Limit the quiz, flashcards and time attacks to these hands."),
+            Line::from("With none picked, every hand is asked."),
+            Line::from(""),
+        ];
+        for (index, class) in HandClass::ALL.iter().enumerate() {
+            let tick = if self.filter.includes(*class) { "x" } else { " " };
+            let line = Line::from(format!("[{}] {:<20}", tick, class.to_string()));
+            lines.push(if index == self.filter_cursor { line.bold().fg(Color::Yellow) } else { line });
+        }
+        lines.extend([Line::from(""), Line::from(self.message.as_str()).bold()]);
+        frame.render_widget(Paragraph::new(lines).alignment(Alignment::Center), rect);
+    }
+
+    /// Cards studied and how many were known.
+    fn render_flashcard_score(&self, frame: &mut Frame, rect: Rect) {
+        let Some(deck) = &self.flashcards else {
+            return;
+        };
+        let chart = self.chart_name();
+        let graded = deck.known() + deck.missed();
+        let lines = vec![
+            Line::from(""),
+            Line::from(format!("Chart: {}", chart)),
+            Line::from(""),
+            Line::from(format!("Cards in the Deck: {}", deck.len())),
+            Line::from(format!("Studied: {}", graded)),
+            Line::from(format!("Knew: {}", deck.known())).fg(Color::Green),
+            Line::from(format!("Missed: {}", deck.missed())).fg(Color::Red),
+            Line::from(if graded == 0 {
+                String::new()
+            } else {
+                format!("Known: {:.0}%", deck.known() as f64 / graded as f64 * 100.0)
+            }),
+        ];
+
+        let score = Paragraph::new(lines)
+            .alignment(Alignment::Center)
+            .block(Block::default());
+        frame.render_widget(score, rect);
+    }
+
+    /// The current chart cell, with its play once revealed.
+    fn render_flashcard(&self, frame: &mut Frame, rect: Rect) {
+        let Some((deck, card)) = self.flashcards.as_ref().and_then(|deck| Some((deck, deck.current()?))) else {
+            let empty = Paragraph::new(format!("\nNo strategy charts were loaded from {}", &strategies_dir()))
+                .alignment(Alignment::Center);
+            frame.render_widget(empty, rect);
+            return;
+        };
+        let layout = Layout::vertical([
+            Constraint::Min(0),
+            Constraint::Length(FLASHCARD_HEIGHT),
+            Constraint::Length(2),
+            Constraint::Length(2),
+            Constraint::Min(0),
+        ]).split(rect);
+        let card_area = layout[1].inner(Margin {
+            vertical: 0,
+            horizontal: layout[1].width.saturating_sub(FLASHCARD_WIDTH) / 2,
+        });
+
+        let legend = self.strategy.map(|index| &self.strategies[index].1.action_legend);
+        let mut lines = vec![
+            Line::from(""),
+            Line::from(card.category.to_string()).fg(Color::DarkGray),
+            Line::from(""),
+            Line::from(card.to_string()).bold(),
+            Line::from(""),
+        ];
+        if deck.is_revealed() && let Some(legend) = legend {
+            let style = self.palette.get().style(legend, &card.code);
+            lines.push(Line::from(card.code.clone()).bold().style(style));
+            let description = legend.get(&card.code).map_or(String::new(), |entry| entry.description.clone());
+            lines.push(Line::from(description));
+        } else {
+            lines.push(Line::from("?").bold());
+        }
+        let flashcard = Paragraph::new(lines)
+            .alignment(Alignment::Center)
+            .block(Block::default().borders(Borders::all()));
+        frame.render_widget(flashcard, card_area);
+
+        let prompt = if deck.is_revealed() { "Y: Knew It    N: Missed It" } else { "Space: Reveal" };
+        frame.render_widget(Paragraph::new(prompt).bold().fg(Color::Yellow).alignment(Alignment::Center), layout[2]);
+        frame.render_widget(Paragraph::new(self.message.as_str()).bold().alignment(Alignment::Center), layout[3]);
+    }
+
+    fn footer_spans(&self) -> Vec<String> {
+        let mut spans = vec![
+            " Q ".to_string(), " Quit ".to_string(),
+            " M ".to_string(), " Menu ".to_string(),
+        ];
+        if self.configuring {
+            spans.extend([
+                " Up/Down ".to_string(), " Move ".to_string(),
+                " Space ".to_string(), " Pick ".to_string(),
+                " F ".to_string(), " Done ".to_string(),
+            ]);
+            return spans;
+        }
+        spans.extend([" Tab ".to_string(), format!(" {} ", self.mode.next())]);
+        if self.mode == StudyMode::Surrender {
+            spans.extend([
+                " H/S/D/P/R ".to_string(), " Hit, Stand, Double, Split, Surrender ".to_string(),
+                " N ".to_string(), " Start Over ".to_string(),
+                " V ".to_string(), format!(" {} Cards ", self.card_style.toggle()),
+            ]);
+            return spans;
+        }
+        if self.time_attack.is_none() {
+            spans.extend([" F ".to_string(), " Filters ".to_string()]);
+        }
+        if self.mode == StudyMode::TimeAttack {
+            if self.time_attack.is_some() {
+                spans.extend([
+                    " H/S/D/P/R ".to_string(), " Hit, Stand, Double, Split, Surrender ".to_string(),
+                ]);
+            } else {
+                spans.extend([
+                    " Enter ".to_string(), " Start ".to_string(),
+                    " T ".to_string(), format!(" {}s Runs ", TIME_LIMITS[(self.time_limit + 1) % TIME_LIMITS.len()]),
+                ]);
+            }
+            spans.extend([
+                " C ".to_string(), " Chart ".to_string(),
+                " V ".to_string(), format!(" {} Cards ", self.card_style.toggle()),
+            ]);
+            return spans;
+        }
+        if self.mode == StudyMode::Flashcards {
+            if self.flashcards.as_ref().is_some_and(FlashcardDeck::is_revealed) {
+                spans.extend([" Y ".to_string(), " Knew It ".to_string(), " N ".to_string(), " Missed It ".to_string()]);
+            } else {
+                spans.extend([" Space ".to_string(), " Reveal ".to_string()]);
+            }
+            spans.extend([" C ".to_string(), " Chart ".to_string()]);
+            return spans;
+        }
+        if self.quiz.as_ref().is_some_and(|quiz| !quiz.is_finished()) {
+            spans.extend([
+                " H/S/D/P/R ".to_string(), " Hit, Stand, Double, Split, Surrender ".to_string(),
+            ]);
+        }
+        spans.extend([
+            " C ".to_string(), " Chart ".to_string(),
+            " N ".to_string(), " New Quiz ".to_string(),
+            " V ".to_string(), format!(" {} Cards ", self.card_style.toggle()),
+        ]);
+        spans
+    }
+}
+
+// ---- TRAIT IMPLEMENTATIONS ----
+impl Model for StrategyQuizScreen {
+    fn update(&mut self) -> std::io::Result<ModelResponse> {
+        if let Event::Key(key) = read_event(false)? {
+            if key.kind == event::KeyEventKind::Release {
+                return Ok(ModelResponse::Refresh);
+            }
+            match key.code {
+                KeyCode::Char('q') => return Ok(ModelResponse::Exit),
+                KeyCode::Char('m') => return Ok(ModelResponse::NavToMainMenu),
+                KeyCode::Char('f') if self.configuring || (self.time_attack.is_none() && self.mode != StudyMode::Surrender) => self.toggle_configuring(),
+                KeyCode::Esc if self.configuring => self.toggle_configuring(),
+                KeyCode::Up if self.configuring => self.move_filter_cursor(false),
+                KeyCode::Down if self.configuring => self.move_filter_cursor(true),
+                KeyCode::Char(' ') | KeyCode::Enter if self.configuring => self.filter.toggle(HandClass::ALL[self.filter_cursor]),
+                _ if self.configuring => {}
+                KeyCode::Tab => self.switch_mode(),
+                KeyCode::Char('c') if self.mode != StudyMode::Surrender => self.cycle_strategy(),
+                KeyCode::Char(' ') if self.mode == StudyMode::Flashcards => self.study_flashcard(None),
+                KeyCode::Char('y') if self.mode == StudyMode::Flashcards => self.study_flashcard(Some(true)),
+                KeyCode::Char('n') if self.mode == StudyMode::Flashcards => self.study_flashcard(Some(false)),
+                _ if self.mode == StudyMode::Flashcards => {}
+                KeyCode::Char('v') => self.card_style = self.card_style.toggle(),
+                KeyCode::Enter if self.mode == StudyMode::TimeAttack && self.time_attack.is_none() => self.start_time_attack(),
+                KeyCode::Char('t') if self.mode == StudyMode::TimeAttack && self.time_attack.is_none() => self.cycle_time_limit(),
+                KeyCode::Char('n') if self.mode == StudyMode::Quiz => self.new_quiz(),
+                KeyCode::Char('n') if self.mode == StudyMode::Surrender => self.new_surrender_drill(),
+                KeyCode::Char(key) => {
+                    if let Some((_, action)) = ACTION_KEYS.iter().find(|(action_key, _)| *action_key == key) {
+                        match self.mode {
+                            StudyMode::TimeAttack => self.answer_time_attack(*action),
+                            StudyMode::Surrender => self.answer_surrender(*action),
+                            _ => self.answer(*action),
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(ModelResponse::Refresh)
+    }
+
+    fn ui(&mut self, frame: &mut Frame) {
+        let main_chunks = create_common_layout(frame.area());
+        let horizontal_chunks = split_content_horizontally(main_chunks[1]);
+
+        let left_section = horizontal_chunks[0];
+        render_border(frame, left_section);
+        render_centered_text(frame, left_section, " Score ");
+        let left_area = left_section.inner(Margin { vertical: 1, horizontal: 1 });
+
+        let right_section = horizontal_chunks[1];
+        render_border(frame, right_section);
+        let right_area = right_section.inner(Margin { vertical: 1, horizontal: 1 });
+        if self.strategy.is_none() {
+            let empty = Paragraph::new(format!("\nNo strategy charts were loaded from {}", &strategies_dir()))
+                .alignment(Alignment::Center);
+            frame.render_widget(empty, right_area);
+        } else if self.configuring {
+            self.render_filter_summary(frame, left_area);
+            render_centered_text(frame, right_section, " Drill Filters ");
+            self.render_filters(frame, right_area);
+        } else if self.mode == StudyMode::TimeAttack {
+            self.render_time_attack_score(frame, left_area);
+            render_centered_text(frame, right_section, " Time Attack ");
+            match &self.time_attack {
+                Some(run) => self.render_question(frame, right_area, run.question(), &run.available()),
+                None => self.render_time_attack_results(frame, right_area),
+            }
+        } else if self.mode == StudyMode::Surrender {
+            self.render_surrender_score(frame, left_area);
+            render_centered_text(frame, right_section, " Surrender Drill ");
+            self.render_surrender_question(frame, right_area);
+        } else if self.mode == StudyMode::Flashcards {
+            self.render_flashcard_score(frame, left_area);
+            render_centered_text(frame, right_section, " Flashcards ");
+            self.render_flashcard(frame, right_area);
+        } else if self.quiz.as_ref().is_some_and(StrategyQuiz::is_finished) {
+            self.render_score(frame, left_area);
+            render_centered_text(frame, right_section, " Results ");
+            self.render_results(frame, right_area);
+        } else {
+            self.render_score(frame, left_area);
+            render_centered_text(frame, right_section, " Strategy Quiz ");
+            if let Some(quiz) = &self.quiz {
+                self.render_question(frame, right_area, quiz.question(), &quiz.available());
+            }
+        }
+
+        if self.strategy.is_some() && !self.configuring {
+            render_streak(frame, left_section, self.streaks.get(self.mode.drill_name()));
+        }
+
+        // Render Footer
+        let footer_layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Length(5),
+                Constraint::Min(20),
+            ])
+            .split(main_chunks[2]);
+        render_key_spans(frame, self.footer_spans(), footer_layout[1]);
+    }
+
+    fn tick(&mut self) {
+        self.tick_time_attack();
+    }
+}
@@ -0,0 +1 @@
+pub mod table_math_screen;
\ No newline at end of file
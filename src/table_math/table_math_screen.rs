@@ -0,0 +1,348 @@
+use crate::constants::{HISTORY_DATABASE_FILE, STREAKS_FILE};
+use crate::logic::app_dirs::data_file;
+use crate::logic::history_store::HistoryStore;
+use crate::logic::streaks::Streaks;
+use crate::logic::table_math::{describe_cards, MathDrill, TotalCall, TotalDrill, DEFAULT_FLASH_TIME, FLASH_TIMES, TOTAL_ANSWER_TIME};
+use crate::model::{Model, ModelResponse};
+use crate::ui::{create_common_layout, render_border, render_centered_text, render_key_spans, render_streak, split_content_horizontally, read_event};
+use crate::widgets::card_widget::{CardRow, CardStyle, CARD_HEIGHT};
+use ratatui::crossterm::event;
+use ratatui::crossterm::event::{Event, KeyCode};
+use ratatui::layout::{Alignment, Constraint, Direction, Layout, Margin, Rect};
+use ratatui::prelude::{Line, Stylize};
+use ratatui::style::Color;
+use ratatui::widgets::{Block, Paragraph};
+use ratatui::Frame;
+use std::time::Instant;
+
+/// Longest total that can be typed
+const MAX_TOTAL_LENGTH: usize = 2;
+
+// ---- Phases ----
+/// Where the drill is with the current hand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Phase {
+    /// Waiting to flash the next hand
+    Ready,
+    /// The hand is showing
+    Flashing,
+    /// The hand is hidden and the total is being called
+    Answering,
+}
+
+// ---- Table Math Screen ----
+/// Flashes hands for a moment and asks for the total, or whether the hand
+/// busted, against the clock. Each drill keeps its own answers.
+pub struct TableMathScreen {
+    drill: MathDrill,
+    dealer_totals: TotalDrill,
+    hand_totals: TotalDrill,
+    phase: Phase,
+
+    /// Position in `FLASH_TIMES`
+    flash_time: usize,
+
+    /// When the hand was flashed, or hidden once the total is being called
+    shown_at: Instant,
+
+    /// Total being typed
+    total_input: String,
+
+    /// Right answers in a row, carried over between sessions
+    streaks: Streaks,
+    /// Database every answer is kept in, none when it can't be opened
+    store: Option<HistoryStore>,
+    card_style: CardStyle,
+    message: String,
+}
+
+impl TableMathScreen {
+    pub fn new() -> Self {
+        Self {
+            drill: MathDrill::DealerTotals,
+            dealer_totals: TotalDrill::new(MathDrill::DealerTotals, false),
+            hand_totals: TotalDrill::new(MathDrill::HandTotals, false),
+            phase: Phase::Ready,
+            flash_time: DEFAULT_FLASH_TIME,
+            shown_at: Instant::now(),
+            total_input: String::new(),
+            streaks: Streaks::load(&data_file(STREAKS_FILE)),
+            store: HistoryStore::open(&data_file(HISTORY_DATABASE_FILE), "Table Math").ok(),
+            card_style: CardStyle::default(),
+            message: MathDrill::DealerTotals.instructions().to_string(),
+        }
+    }
+
+    fn total_drill(&self) -> &TotalDrill {
+        match self.drill {
+            MathDrill::DealerTotals => &self.dealer_totals,
+            MathDrill::HandTotals => &self.hand_totals,
+        }
+    }
+
+    fn total_drill_mut(&mut self) -> &mut TotalDrill {
+        match self.drill {
+            MathDrill::DealerTotals => &mut self.dealer_totals,
+            MathDrill::HandTotals => &mut self.hand_totals,
+        }
+    }
+
+    fn switch_drill(&mut self) {
+        self.drill = self.drill.next();
+        self.message = self.drill.instructions().to_string();
+    }
+
+    fn flash(&mut self) {
+        self.phase = Phase::Flashing;
+        self.shown_at = Instant::now();
+        self.total_input.clear();
+        self.message = String::new();
+    }
+
+    /// Hides the hand once it's been up long enough, and calls time on
+    /// answers that take too long.
+    fn tick_drill(&mut self) {
+        let elapsed = self.shown_at.elapsed();
+        match self.phase {
+            Phase::Flashing if elapsed >= FLASH_TIMES[self.flash_time] => {
+                self.phase = Phase::Answering;
+                self.shown_at = Instant::now();
+                self.message = match self.drill {
+                    MathDrill::DealerTotals => "Type the total and press Enter, or B for a bust",
+                    MathDrill::HandTotals => "Type the total, then Enter for hard or S for soft, or B for a bust",
+                }.to_string();
+            }
+            Phase::Answering if elapsed > TOTAL_ANSWER_TIME => self.answer(None),
+            _ => {}
+        }
+    }
+
+    /// Scores the total called, or none when time ran out.
+    fn answer(&mut self, call: Option<TotalCall>) {
+        let time = self.shown_at.elapsed();
+        let attempt = self.total_drill_mut().answer(call, time).clone();
+        let cards = describe_cards(&attempt.hand);
+        self.message = match attempt.answer {
+            _ if attempt.is_correct() => format!("Right, {} is {}", cards, attempt.expected),
+            Some(answer) => format!("{} is {}, not {}", cards, attempt.expected, answer),
+            None => format!("Too slow, {} is {}", cards, attempt.expected),
+        };
+        self.phase = Phase::Ready;
+        self.total_input.clear();
+        let correct = attempt.is_correct();
+        self.streaks.record(&self.drill.to_string(), correct);
+        if let Err(err) = self.streaks.save(&data_file(STREAKS_FILE)) {
+            self.message = format!("Could not save streaks: {}", err);
+        }
+        if let Some(store) = &mut self.store
+            && let Err(err) = store.record_answer(&self.drill.to_string(), correct) {
+            self.message = format!("Could not save the answer: {}", err);
+        }
+    }
+
+    /// Calls the total typed, as a soft total when `soft`.
+    fn submit_total(&mut self, soft: bool) {
+        if let Ok(total) = self.total_input.parse::<u8>() {
+            self.answer(Some(if soft { TotalCall::Soft(total) } else { TotalCall::Total(total) }));
+        }
+    }
+
+    fn toggle_soft_17(&mut self) {
+        let hits_soft_17 = !self.dealer_totals.hits_soft_17();
+        self.dealer_totals.set_hits_soft_17(hits_soft_17);
+        self.phase = Phase::Ready;
+        self.message = if hits_soft_17 { "The dealer hits soft 17" } else { "The dealer stands on soft 17" }.to_string();
+    }
+
+    fn change_flash_time(&mut self, step: isize) {
+        self.flash_time = self.flash_time.saturating_add_signed(step).min(FLASH_TIMES.len() - 1);
+    }
+
+    /// The rules and timings, the score and the latest answers.
+    fn render_stats(&self, frame: &mut Frame, rect: Rect) {
+        let drill = self.total_drill();
+        let attempts = drill.attempts();
+        let rule = match self.drill {
+            MathDrill::DealerTotals if drill.hits_soft_17() => "Dealer Hits Soft 17",
+            MathDrill::DealerTotals => "Dealer Stands on Soft 17",
+            MathDrill::HandTotals => "Name Soft Totals as Soft",
+        };
+        let mut lines = vec![
+            Line::from(""),
+            Line::from(rule),
+            Line::from(format!("Flash: {:.1}s", FLASH_TIMES[self.flash_time].as_secs_f64())),
+            Line::from(format!("Time To Answer: {}s", TOTAL_ANSWER_TIME.as_secs())),
+            Line::from(format!(
+                "Right: {} of {} ({:.0}%)",
+                attempts.iter().filter(|attempt| attempt.is_correct()).count(),
+                attempts.len(),
+                drill.accuracy() * 100.0,
+            )),
+            Line::from(format!("Average Time: {:.1}s", drill.average_time().as_secs_f64())),
+            Line::from(""),
+            Line::from("Answers Given").bold(),
+        ];
+        // The latest answers when they don't all fit
+        let fits = (rect.height as usize).saturating_sub(lines.len());
+        for attempt in attempts[attempts.len().saturating_sub(fits)..].iter().rev() {
+            let cards = describe_cards(&attempt.hand);
+            let line = match attempt.answer {
+                _ if attempt.is_correct() => Line::from(format!("{}: {} in {:.1}s", cards, attempt.expected, attempt.time.as_secs_f64())).fg(Color::Green),
+                Some(answer) => Line::from(format!("{}: {}, was {}", cards, answer, attempt.expected)).fg(Color::Red),
+                None => Line::from(format!("{}: Late, was {}", cards, attempt.expected)).fg(Color::Red),
+            };
+            lines.push(line);
+        }
+
+        let stats = Paragraph::new(lines)
+            .alignment(Alignment::Center)
+            .block(Block::default());
+        frame.render_widget(stats, rect);
+    }
+
+    /// The hand while it's flashed, then face down with the clock while the
+    /// total is called, and the last hand face up between flashes.
+    fn render_hand(&self, frame: &mut Frame, rect: Rect) {
+        let layout = Layout::vertical([
+            Constraint::Min(0),
+            Constraint::Length(1),
+            Constraint::Length(CARD_HEIGHT),
+            Constraint::Length(2),
+            Constraint::Length(2),
+            Constraint::Length(2),
+            Constraint::Min(0),
+        ]).split(rect);
+
+        let drill = self.total_drill();
+        let label = match self.drill {
+            MathDrill::DealerTotals => "Dealer",
+            MathDrill::HandTotals => "Player",
+        };
+        frame.render_widget(Paragraph::new(label).bold().alignment(Alignment::Center), layout[1]);
+        match self.phase {
+            Phase::Flashing => {
+                frame.render_widget(CardRow::from_cards(drill.hand().cards(), 0).style(self.card_style), layout[2]);
+            }
+            Phase::Answering => {
+                frame.render_widget(CardRow::new(vec![None; drill.hand().len()]).style(self.card_style), layout[2]);
+                let left = TOTAL_ANSWER_TIME.saturating_sub(self.shown_at.elapsed()).as_secs_f64();
+                let clock = Paragraph::new(format!("{:.1}s left", left))
+                    .bold()
+                    .fg(if left < 2.0 { Color::Red } else { Color::White })
+                    .alignment(Alignment::Center);
+                frame.render_widget(clock, layout[3]);
+                let prompt = Paragraph::new(format!("Total: {}_", self.total_input))
+                    .bold()
+                    .fg(Color::Yellow)
+                    .alignment(Alignment::Center);
+                frame.render_widget(prompt, layout[4]);
+            }
+            Phase::Ready => {
+                if let Some(last) = drill.attempts().last() {
+                    frame.render_widget(CardRow::from_cards(last.hand.cards(), 0).style(self.card_style), layout[2]);
+                }
+                let prompt = Paragraph::new("Press Space for the next hand")
+                    .fg(Color::Yellow)
+                    .alignment(Alignment::Center);
+                frame.render_widget(prompt, layout[4]);
+            }
+        }
+        frame.render_widget(Paragraph::new(self.message.as_str()).bold().alignment(Alignment::Center), layout[5]);
+    }
+
+    fn footer_spans(&self) -> Vec<String> {
+        let mut spans = vec![
+            " Q ".to_string(), " Quit ".to_string(),
+            " M ".to_string(), " Menu ".to_string(),
+        ];
+        match self.phase {
+            Phase::Answering => {
+                spans.extend([" 0-9 ".to_string(), " Total ".to_string()]);
+                match self.drill {
+                    MathDrill::DealerTotals => spans.extend([" Enter ".to_string(), " Call It ".to_string()]),
+                    MathDrill::HandTotals => spans.extend([
+                        " Enter ".to_string(), " Hard ".to_string(),
+                        " S ".to_string(), " Soft ".to_string(),
+                    ]),
+                }
+                spans.extend([" B ".to_string(), " Bust ".to_string()]);
+            }
+            Phase::Flashing => {}
+            Phase::Ready => {
+                spans.extend([
+                    " Space ".to_string(), " Flash ".to_string(),
+                    " Tab ".to_string(), format!(" {} Drill ", self.drill.next()),
+                    " H/L ".to_string(), " Slower/Faster ".to_string(),
+                ]);
+                if self.drill == MathDrill::DealerTotals {
+                    spans.extend([" S ".to_string(), " Soft 17 Rule ".to_string()]);
+                }
+                spans.extend([" V ".to_string(), format!(" {} Cards ", self.card_style.toggle())]);
+            }
+        }
+        spans
+    }
+}
+
+// ---- TRAIT IMPLEMENTATIONS ----
+impl Model for TableMathScreen {
+    fn update(&mut self) -> std::io::Result<ModelResponse> {
+        if let Event::Key(key) = read_event(false)? {
+            if key.kind == event::KeyEventKind::Release {
+                return Ok(ModelResponse::Refresh);
+            }
+            match key.code {
+                KeyCode::Char('q') => return Ok(ModelResponse::Exit),
+                KeyCode::Char('m') => return Ok(ModelResponse::NavToMainMenu),
+                KeyCode::Char(digit @ '0'..='9') if self.phase == Phase::Answering && self.total_input.len() < MAX_TOTAL_LENGTH => {
+                    self.total_input.push(digit);
+                }
+                KeyCode::Backspace if self.phase == Phase::Answering => {
+                    self.total_input.pop();
+                }
+                KeyCode::Enter if self.phase == Phase::Answering => self.submit_total(false),
+                KeyCode::Char('s') if self.phase == Phase::Answering && self.drill == MathDrill::HandTotals => self.submit_total(true),
+                KeyCode::Char('b') if self.phase == Phase::Answering => self.answer(Some(TotalCall::Bust)),
+                _ if self.phase != Phase::Ready => {}
+                KeyCode::Char(' ') => self.flash(),
+                KeyCode::Tab => self.switch_drill(),
+                KeyCode::Char('h') | KeyCode::Left => self.change_flash_time(-1),
+                KeyCode::Char('l') | KeyCode::Right => self.change_flash_time(1),
+                KeyCode::Char('s') if self.drill == MathDrill::DealerTotals => self.toggle_soft_17(),
+                KeyCode::Char('v') => self.card_style = self.card_style.toggle(),
+                _ => {}
+            }
+        }
+        Ok(ModelResponse::Refresh)
+    }
+
+    fn ui(&mut self, frame: &mut Frame) {
+        let main_chunks = create_common_layout(frame.area());
+        let horizontal_chunks = split_content_horizontally(main_chunks[1]);
+
+        let left_section = horizontal_chunks[0];
+        render_border(frame, left_section);
+        render_centered_text(frame, left_section, " Answers ");
+        self.render_stats(frame, left_section.inner(Margin { vertical: 1, horizontal: 1 }));
+        render_streak(frame, left_section, self.streaks.get(&self.drill.to_string()));
+
+        let right_section = horizontal_chunks[1];
+        render_border(frame, right_section);
+        render_centered_text(frame, right_section, &format!(" {} ", self.drill));
+        self.render_hand(frame, right_section.inner(Margin { vertical: 1, horizontal: 1 }));
+
+        // Render Footer
+        let footer_layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Length(5),
+                Constraint::Min(20),
+            ])
+            .split(main_chunks[2]);
+        render_key_spans(frame, self.footer_spans(), footer_layout[1]);
+    }
+
+    fn tick(&mut self) {
+        self.tick_drill();
+    }
+}
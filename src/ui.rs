@@ -1,14 +1,41 @@
+use std::collections::HashMap;
+use std::fmt;
 use std::rc::Rc;
 use ratatui::Frame;
+use ratatui::crossterm::event;
+use ratatui::crossterm::event::{Event, KeyCode};
 use ratatui::layout::{Alignment, Constraint, Layout, Rect};
-use ratatui::prelude::{Color, Line, Span, Style, Text};
-use ratatui::widgets::{Block, Borders, Paragraph};
+use ratatui::prelude::{Color, Line, Span, Style, Stylize, Text};
+use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
 use crate::constants::TITLE;
+use crate::logic::config;
+use crate::logic::strategy_calculator_logic::LegendEntry;
+use crate::logic::streaks::Streak;
+use serde::{Deserialize, Serialize};
 
 // Constants for layout dimensions
 const HEADER_HEIGHT: u16 = 4;
 const FOOTER_HEIGHT: u16 = 4;
 
+/// Reads the next terminal event, with keys rebound in the config turned
+/// into the keys the screens listen for, unless they're being typed into
+/// a text box.
+pub fn read_event(typing: bool) -> std::io::Result<Event> {
+    let event = event::read()?;
+    if typing {
+        return Ok(event);
+    }
+    Ok(match event {
+        Event::Key(mut key) => {
+            if let KeyCode::Char(character) = key.code {
+                key.code = KeyCode::Char(config::current().keys.translate(character));
+            }
+            Event::Key(key)
+        }
+        event => event,
+    })
+}
+
 /// Creates the main vertical layout: header, content, footer
 pub fn create_common_layout(area: Rect) -> Rc<[Rect]> {
     Layout::vertical([
@@ -38,6 +65,144 @@ pub fn split_content_horizontally(area: Rect) -> Rc<[Rect]> {
         .split(area)
 }
 
+/// Color used for a strategy chart action code, e.g. "H" or "Ds", in the
+/// standard palette that exported charts are always drawn in. A color the
+/// strategy's legend gives the code comes first.
+pub fn get_action_color(legend: &HashMap<String, LegendEntry>, action: &str) -> Color {
+    ActionPalette::Standard.color(legend, action)
+}
+
+/// How chart actions are told apart on screen. The standard colors are hard
+/// to tell apart for colorblind players, so there are alternatives.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ActionPalette {
+    #[default]
+    Standard,
+    /// Okabe-Ito colors, which stay distinct with any color vision deficiency
+    #[serde(rename = "Colorblind Safe")]
+    ColorblindSafe,
+    /// No color at all, each action has its own text style instead
+    Monochrome,
+}
+
+pub const ACTION_PALETTES: [ActionPalette; 3] = [
+    ActionPalette::Standard,
+    ActionPalette::ColorblindSafe,
+    ActionPalette::Monochrome,
+];
+
+impl fmt::Display for ActionPalette {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ActionPalette::Standard => "Standard",
+            ActionPalette::ColorblindSafe => "Colorblind Safe",
+            ActionPalette::Monochrome => "Monochrome",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl ActionPalette {
+    /// Color for a chart action code, e.g. "H" or "Ds". In the standard
+    /// palette a color from the strategy's legend comes first, the others
+    /// are picked to be told apart and keep to their own colors. Codes the
+    /// palette doesn't know take the color of the play they start with, so
+    /// "Ph" is colored like a split.
+    pub fn color(self, legend: &HashMap<String, LegendEntry>, action: &str) -> Color {
+        let action = action.trim();
+        let custom = legend.get(action).and_then(|entry| entry.color.as_ref()?.parse().ok());
+        match (self, custom) {
+            (ActionPalette::Standard, Some(color)) => color,
+            (ActionPalette::Monochrome, _) => Color::White,
+            _ => self.code_color(action)
+                .or_else(|| self.code_color(action.get(..1)?))
+                // Anything else is drawn like a hit
+                .or_else(|| self.code_color("H"))
+                .unwrap_or(Color::Red),
+        }
+    }
+
+    fn code_color(self, action: &str) -> Option<Color> {
+        let color = match self {
+            ActionPalette::Standard => match action {
+                "H" => Color::Red,
+                "D" | "Dh" => Color::Blue,
+                "Ds" => Color::LightBlue,
+                "S" => Color::Yellow,
+                "P" => Color::LightCyan,
+                "R" | "Su" | "Rs" | "Rp" => Color::LightMagenta,
+                "Rh" => Color::Magenta,
+                _ => return None,
+            },
+            ActionPalette::ColorblindSafe => match action {
+                "H" => Color::Rgb(213, 94, 0),
+                "D" | "Dh" => Color::Rgb(0, 114, 178),
+                "Ds" => Color::Rgb(86, 180, 233),
+                "S" => Color::Rgb(240, 228, 66),
+                "P" => Color::Rgb(0, 158, 115),
+                "R" | "Su" | "Rs" | "Rp" => Color::Rgb(230, 159, 0),
+                "Rh" => Color::Rgb(204, 121, 167),
+                _ => return None,
+            },
+            ActionPalette::Monochrome => Color::White,
+        };
+        Some(color)
+    }
+
+    /// Style for a chart action code's text, which marks each action with
+    /// its own text style when there's no color to tell them apart
+    pub fn style(self, legend: &HashMap<String, LegendEntry>, action: &str) -> Style {
+        let style = Style::new().fg(self.color(legend, action));
+        if self != ActionPalette::Monochrome {
+            return style;
+        }
+        match action.trim() {
+            "S" => style.bold(),
+            "D" | "Dh" => style.underlined(),
+            "Ds" => style.bold().underlined(),
+            "P" => style.reversed(),
+            "Su" | "Rs" | "Rp" | "Rh" => style.fg(Color::DarkGray).crossed_out(),
+            _ => style,
+        }
+    }
+}
+
+/// Moves `position` by `step` through `len` options, wrapping at either end.
+pub fn step_through(position: usize, step: isize, len: usize) -> usize {
+    (position as isize + step).rem_euclid(len as isize) as usize
+}
+
+/// Formats a win or loss with its sign, e.g. "+$10.00" or "-$5.00"
+pub fn format_net(amount: f64) -> String {
+    if amount >= 0.0 {
+        format!("+${:.2}", amount)
+    } else {
+        format!("-${:.2}", amount.abs())
+    }
+}
+
+/// Formats dollars signed only when negative, e.g. "$10.00" or "-$5.00"
+pub fn dollars(amount: f64) -> String {
+    if amount < 0.0 {
+        format!("-${:.2}", -amount)
+    } else {
+        format!("${:.2}", amount)
+    }
+}
+
+/// Formats a count with thousands separated, e.g. "1,000,000"
+pub fn format_count(count: u64) -> String {
+    let digits = count.to_string();
+    let mut formatted = String::new();
+    for (index, digit) in digits.chars().enumerate() {
+        if index > 0 && (digits.len() - index).is_multiple_of(3) {
+            formatted.push(',');
+        }
+        formatted.push(digit);
+    }
+    formatted
+}
+
 pub fn render_border(frame: &mut Frame, screen: Rect) {
     let border_block = Block::default()
         .borders(Borders::all())
@@ -77,20 +242,34 @@ pub fn render_footer_spans(frame: &mut Frame, specific_spans: Vec<String>, rect:
     ];
 
     spans.extend(specific_spans);
+    render_key_spans(frame, spans, rect);
+}
+
+/// Renders a drill's streak into the bottom border of a section, picked out
+/// while it's the best yet.
+pub fn render_streak(frame: &mut Frame, rect: Rect, streak: Streak) {
+    let bottom = Rect { y: rect.y + rect.height.saturating_sub(1), height: 1.min(rect.height), ..rect };
+    let text = format!(" Streak: {}  Best: {} ", streak.current, streak.best);
+    let style = if streak.is_best() { Style::default().fg(Color::Yellow).bold() } else { Style::default().bold() };
+    frame.render_widget(Paragraph::new(text).style(style).alignment(Alignment::Center), bottom);
+}
 
+/// Renders alternating key/description spans, e.g. `[" Q ", " Quit "]`
+pub fn render_key_spans(frame: &mut Frame, spans: Vec<String>, rect: Rect) {
     let styles = [
         Style::default().bg(Color::Gray).fg(Color::DarkGray),
         Style::default().fg(Color::DarkGray),
     ];
 
+    // Wrap onto the next footer line when a screen has more keys than fit
     frame.render_widget(
-        Line::from(
+        Paragraph::new(Line::from(
             spans
                 .iter()
                 .enumerate()
                 .map(|(idx, content)| Span::styled(content, styles[idx % 2]))
                 .collect::<Vec<_>>(),
-        ).left_aligned(),
+        ).left_aligned()).wrap(Wrap { trim: false }),
         rect
     );
 }
@@ -0,0 +1,157 @@
+use crate::logic::game::card::Card;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Style};
+use ratatui::widgets::Widget;
+use std::fmt;
+
+pub const CARD_WIDTH: u16 = 7;
+pub const CARD_HEIGHT: u16 = 5;
+
+/// Gap between cards when there is room to lay them out side by side
+const CARD_GAP: u16 = 1;
+/// Narrowest a card is squeezed to when overlapping, enough to read its rank
+const MIN_CARD_STEP: u16 = 4;
+
+/// How cards are drawn. ASCII avoids box drawing and suit glyphs for
+/// terminals and fonts that can't display them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CardStyle {
+    #[default]
+    Unicode,
+    Ascii,
+}
+
+impl CardStyle {
+    pub fn toggle(self) -> Self {
+        match self {
+            CardStyle::Unicode => CardStyle::Ascii,
+            CardStyle::Ascii => CardStyle::Unicode,
+        }
+    }
+
+    fn rank(self, card: Card) -> &'static str {
+        card.rank.symbol()
+    }
+
+    fn suit(self, card: Card) -> &'static str {
+        match self {
+            CardStyle::Unicode => card.suit.symbol(),
+            CardStyle::Ascii => card.suit.letter(),
+        }
+    }
+
+    /// Corner, horizontal edge, vertical edge and card back characters
+    fn frame(self) -> [&'static str; 7] {
+        match self {
+            CardStyle::Unicode => ["┌", "┐", "└", "┘", "─", "│", "░"],
+            CardStyle::Ascii => ["+", "+", "+", "+", "-", "|", "#"],
+        }
+    }
+}
+
+impl fmt::Display for CardStyle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            CardStyle::Unicode => "Unicode",
+            CardStyle::Ascii => "ASCII",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// A row of playing cards drawn as bordered mini cards, centered in its area.
+/// `None` entries are drawn face down. Cards overlap when the row is too
+/// narrow, and fall back to a single line of text when it is too short.
+pub struct CardRow {
+    cards: Vec<Option<Card>>,
+    style: CardStyle,
+}
+
+impl CardRow {
+    pub fn new(cards: Vec<Option<Card>>) -> Self {
+        Self { cards, style: CardStyle::default() }
+    }
+
+    /// Face up cards followed by `face_down` hidden ones.
+    pub fn from_cards(cards: &[Card], face_down: usize) -> Self {
+        let mut row: Vec<Option<Card>> = cards.iter().copied().map(Some).collect();
+        row.extend(std::iter::repeat_n(None, face_down));
+        Self::new(row)
+    }
+
+    pub fn style(mut self, style: CardStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    fn card_color(card: Card) -> Color {
+        if card.suit.is_red() { Color::Red } else { Color::White }
+    }
+
+    fn render_text(&self, area: Rect, buf: &mut Buffer) {
+        let text = self.cards.iter()
+            .map(|card| match card {
+                Some(card) => format!("{}{}", self.style.rank(*card), self.style.suit(*card)),
+                None => "??".to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+        let width = (text.chars().count() as u16).min(area.width);
+        let x = area.x + (area.width - width) / 2;
+        buf.set_stringn(x, area.y, &text, area.width as usize, Style::default());
+    }
+
+    fn render_card(&self, x: u16, y: u16, card: Option<Card>, buf: &mut Buffer) {
+        let [top_left, top_right, bottom_left, bottom_right, horizontal, vertical, back] = self.style.frame();
+        let border = Style::default().fg(Color::White);
+        let inner = (CARD_WIDTH - 2) as usize;
+
+        buf.set_string(x, y, format!("{}{}{}", top_left, horizontal.repeat(inner), top_right), border);
+        buf.set_string(x, y + CARD_HEIGHT - 1, format!("{}{}{}", bottom_left, horizontal.repeat(inner), bottom_right), border);
+        for row in 1..CARD_HEIGHT - 1 {
+            buf.set_string(x, y + row, vertical, border);
+            buf.set_string(x + CARD_WIDTH - 1, y + row, vertical, border);
+        }
+
+        let Some(card) = card else {
+            for row in 1..CARD_HEIGHT - 1 {
+                buf.set_string(x + 1, y + row, back.repeat(inner), Style::default().fg(Color::Blue));
+            }
+            return;
+        };
+
+        // Rank in the top left and bottom right corners, suit in the middle
+        let face = Style::default().fg(Self::card_color(card));
+        let rank = self.style.rank(card);
+        buf.set_string(x + 1, y + 1, format!("{:<width$}", rank, width = inner), face);
+        buf.set_string(x + 1, y + 2, format!("{:^width$}", self.style.suit(card), width = inner), face);
+        buf.set_string(x + 1, y + 3, format!("{:>width$}", rank, width = inner), face);
+    }
+}
+
+impl Widget for CardRow {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if self.cards.is_empty() || area.width == 0 || area.height == 0 {
+            return;
+        }
+        let count = self.cards.len() as u16;
+        if area.height < CARD_HEIGHT || area.width < CARD_WIDTH + MIN_CARD_STEP * (count - 1) {
+            self.render_text(area, buf);
+            return;
+        }
+
+        let full_width = count * (CARD_WIDTH + CARD_GAP) - CARD_GAP;
+        let step = if full_width <= area.width || count == 1 {
+            CARD_WIDTH + CARD_GAP
+        } else {
+            (area.width - CARD_WIDTH) / (count - 1)
+        };
+        let width = CARD_WIDTH + step * (count - 1);
+        let x = area.x + area.width.saturating_sub(width) / 2;
+
+        for (index, card) in self.cards.iter().enumerate() {
+            self.render_card(x + step * index as u16, area.y, *card, buf);
+        }
+    }
+}
@@ -0,0 +1,3 @@
+pub mod card_widget;
+pub mod table_view;
+pub mod scroll_view;
\ No newline at end of file
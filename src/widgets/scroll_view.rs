@@ -0,0 +1,73 @@
+use ratatui::layout::{Alignment, Rect};
+use ratatui::style::{Color, Style};
+use ratatui::widgets::Paragraph;
+use ratatui::Frame;
+
+/// How far content taller than its area has been scrolled, with arrows
+/// above and below it while there's more to see that way.
+#[derive(Default)]
+pub struct ScrollView {
+    offset: usize,
+    max_offset: usize,
+}
+
+impl ScrollView {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Lines scrolled past at the top.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Whether the content was too tall for its area when last drawn.
+    pub fn is_scrollable(&self) -> bool {
+        self.max_offset > 0
+    }
+
+    /// Scrolls by `lines`, up when negative, stopping at either end.
+    pub fn scroll(&mut self, lines: isize) {
+        self.offset = self.offset.saturating_add_signed(lines).min(self.max_offset);
+    }
+
+    /// Scrolls just far enough for `line` to be among the `visible` lines
+    /// from the top, e.g. to follow a cursor.
+    pub fn show(&mut self, line: usize, visible: usize) {
+        if line < self.offset {
+            self.offset = line;
+        } else if visible > 0 && line >= self.offset + visible {
+            self.offset = line + 1 - visible;
+        }
+    }
+
+    /// Fits `content_height` lines into `area`, drawing the arrows, and
+    /// returns the part of `area` to draw the content in. When the content
+    /// doesn't fit, the top and bottom lines are kept for the arrows.
+    pub fn render(&mut self, frame: &mut Frame, area: Rect, content_height: usize) -> Rect {
+        if content_height <= area.height as usize || area.height < 3 {
+            self.offset = 0;
+            self.max_offset = 0;
+            return area;
+        }
+
+        let content_area = Rect {
+            y: area.y + 1,
+            height: area.height.saturating_sub(2),
+            ..area
+        };
+        self.max_offset = content_height - content_area.height as usize;
+        self.offset = self.offset.min(self.max_offset);
+
+        let indicator = |arrow| Paragraph::new(arrow)
+            .style(Style::default().fg(Color::Cyan))
+            .alignment(Alignment::Center);
+        if self.offset > 0 {
+            frame.render_widget(indicator("↑"), Rect { height: 1, ..area });
+        }
+        if self.offset < self.max_offset {
+            frame.render_widget(indicator("↓"), Rect { y: area.bottom() - 1, height: 1, ..area });
+        }
+        content_area
+    }
+}
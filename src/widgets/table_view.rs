@@ -0,0 +1,224 @@
+use crate::logic::game::animation::{DealAnimation, PeekState};
+use crate::logic::game::hand::Hand;
+use crate::logic::game::round::{HandOutcome, HandStatus, PlayerHand, Round, RoundPhase};
+use crate::ui::format_net;
+use crate::widgets::card_widget::{CardRow, CardStyle, CARD_HEIGHT};
+use ratatui::layout::{Alignment, Constraint, Layout, Margin, Rect};
+use ratatui::prelude::{Line, Stylize};
+use ratatui::style::{Color, Style};
+use ratatui::widgets::{Block, Borders, Paragraph};
+use ratatui::Frame;
+
+/// Hand panel height when cards are drawn as text, and with card art:
+/// borders, total, bet and result plus the cards themselves
+const HAND_PANEL_HEIGHT: u16 = 6;
+const CARD_PANEL_HEIGHT: u16 = CARD_HEIGHT + 5;
+
+/// Draws a round as far as its deal animation has got: the dealer's cards
+/// and every spot's hands, with results held back until all cards are shown.
+pub struct TableView<'a> {
+    round: &'a Round,
+    animation: &'a DealAnimation,
+    card_style: CardStyle,
+}
+
+impl<'a> TableView<'a> {
+    pub fn new(round: &'a Round, animation: &'a DealAnimation, card_style: CardStyle) -> Self {
+        Self {
+            round,
+            animation,
+            card_style,
+        }
+    }
+
+    /// Whether every card of the round has been revealed.
+    pub fn settled(&self) -> bool {
+        self.animation.is_done(self.round)
+    }
+
+    pub fn render_dealer(&self, frame: &mut Frame, rect: Rect) {
+        let layout = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(CARD_HEIGHT),
+            Constraint::Length(1),
+        ]).split(rect);
+        let title = if self.animation.peek() == PeekState::Peeking {
+            Line::from("Dealer - Peeking at the Hole Card").bold().fg(Color::Yellow)
+        } else {
+            Line::from("Dealer").bold()
+        };
+        frame.render_widget(Paragraph::new(title).alignment(Alignment::Center), layout[0]);
+
+        // The hole card stays face down until it is flipped once the player is
+        // done acting, under ENHC there is no hole card to show
+        let visible = visible_hand(self.round.dealer(), self.animation.dealer_shown());
+        let hole_hidden = self.round.dealer_has_hole_card() && !self.animation.hole_revealed();
+        let cards = if hole_hidden && visible.len() >= 2 {
+            CardRow::from_cards(&visible.cards()[..1], 1)
+        } else {
+            CardRow::from_cards(visible.cards(), 0)
+        };
+        frame.render_widget(cards.style(self.card_style), layout[1]);
+        frame.render_widget(Paragraph::new(self.dealer_status(&visible, hole_hidden)).alignment(Alignment::Center), layout[2]);
+    }
+
+    /// Line under the dealer's cards: the peek and its result while the
+    /// player acts, then the dealer's total.
+    fn dealer_status(&self, visible: &Hand, hole_hidden: bool) -> Line<'static> {
+        let round = self.round;
+        if !hole_hidden && round.phase() == RoundPhase::Complete {
+            return if visible.is_natural() {
+                Line::from("Blackjack!").bold().fg(Color::Red)
+            } else {
+                Line::from(format!("({})", describe_total(visible)))
+            };
+        }
+        if !round.dealer_has_hole_card() {
+            return Line::from("No Hole Card (ENHC)").fg(Color::DarkGray);
+        }
+        match self.animation.peek() {
+            PeekState::Peeking => Line::from("Checking for Blackjack...").fg(Color::Yellow),
+            PeekState::Done if round.dealer_checks_for_blackjack() && round.phase() != RoundPhase::Complete => {
+                Line::from("No Blackjack").fg(Color::Green)
+            }
+            _ => Line::from(""),
+        }
+    }
+
+    /// Renders every spot in its own row under `title`, `name` labels each spot.
+    pub fn render_spots(&self, frame: &mut Frame, rect: Rect, title: &str, name: impl Fn(usize) -> String) {
+        let spots = self.round.hands().iter().map(|player| player.spot + 1).max().unwrap_or(0);
+
+        // Card art when every spot fits, plain text otherwise
+        let panel_height = if rect.height >= 2 + CARD_PANEL_HEIGHT * spots as u16 {
+            CARD_PANEL_HEIGHT
+        } else {
+            HAND_PANEL_HEIGHT
+        };
+        let mut constraints = vec![Constraint::Length(2)];
+        constraints.extend(vec![Constraint::Length(panel_height); spots]);
+        constraints.push(Constraint::Min(0));
+        let rows = Layout::vertical(constraints).split(rect);
+
+        let title = Paragraph::new(Line::from(title.to_string()).bold()).alignment(Alignment::Center);
+        frame.render_widget(title, rows[0]);
+
+        for spot in 0..spots {
+            self.render_spot(frame, rows[spot + 1], spot, &name(spot));
+        }
+    }
+
+    /// Renders the hands of a single spot in one row, split hands side by side.
+    fn render_spot(&self, frame: &mut Frame, rect: Rect, spot: usize, name: &str) {
+        let hands: Vec<(usize, &PlayerHand)> = self.round.hands()
+            .iter()
+            .enumerate()
+            .filter(|(_, player)| player.spot == spot)
+            .collect();
+        if hands.is_empty() {
+            return;
+        }
+
+        let hand_width = (rect.width / hands.len() as u16).min(30);
+        let total_width = hand_width * hands.len() as u16;
+        let hands_rect = rect.inner(Margin {
+            vertical: 0,
+            horizontal: rect.width.saturating_sub(total_width) / 2,
+        });
+        let columns = Layout::horizontal(vec![Constraint::Ratio(1, hands.len() as u32); hands.len()])
+            .split(hands_rect);
+
+        for (column, (index, player)) in hands.iter().enumerate() {
+            let is_active = self.round.active_hand_index() == Some(*index);
+            let label = if hands.len() > 1 {
+                format!("{} - Hand {}", name, column + 1)
+            } else {
+                name.to_string()
+            };
+            let shown = self.animation.hand_shown(*index);
+            self.render_hand(frame, columns[column], player, shown, &label, is_active);
+        }
+    }
+
+    /// Renders a hand with only its first `shown` cards, the result waits
+    /// until every card of the round has been revealed.
+    fn render_hand(&self, frame: &mut Frame, rect: Rect, player: &PlayerHand, shown: usize, label: &str, is_active: bool) {
+        let settled = self.settled();
+        let outcome = player.outcome.filter(|_| settled);
+        let visible = visible_hand(&player.hand, shown);
+        let color = match outcome {
+            Some(HandOutcome::Blackjack) | Some(HandOutcome::Win) => Color::Green,
+            Some(HandOutcome::Lose) => Color::Red,
+            Some(HandOutcome::Push) | Some(HandOutcome::Surrender) => Color::Yellow,
+            None if is_active => Color::Green,
+            None => Color::White,
+        };
+
+        let status = if settled || player.status == HandStatus::Playing {
+            describe_status(player)
+        } else {
+            String::new()
+        };
+        let lines = vec![
+            Line::from(format!("({})", describe_total(&visible))),
+            Line::from(if player.free_bet > 0.0 {
+                format!("Bet: ${:.2} (${:.2} free)", player.bet, player.free_bet)
+            } else {
+                format!("Bet: ${:.2}", player.bet)
+            }),
+            Line::from(status).fg(color),
+        ];
+
+        let title = if is_active {
+            format!(" > {} ", label)
+        } else {
+            format!(" {} ", label)
+        };
+        let block = Block::default()
+            .borders(Borders::all())
+            .border_style(Style::default().fg(color))
+            .title(title)
+            .title_alignment(Alignment::Center);
+
+        let inner = block.inner(rect);
+        frame.render_widget(block, rect);
+
+        let layout = Layout::vertical([
+            Constraint::Min(1),
+            Constraint::Length(lines.len() as u16),
+        ]).split(inner);
+        frame.render_widget(CardRow::from_cards(visible.cards(), 0).style(self.card_style), layout[0]);
+        frame.render_widget(Paragraph::new(lines).alignment(Alignment::Center), layout[1]);
+    }
+}
+
+/// The cards of a hand revealed so far.
+fn visible_hand(hand: &Hand, shown: usize) -> Hand {
+    let mut visible = Hand::new();
+    for card in &hand.cards()[..shown.min(hand.len())] {
+        visible.add(*card);
+    }
+    visible
+}
+
+fn describe_total(hand: &Hand) -> String {
+    if hand.is_soft() {
+        format!("soft {}", hand.total())
+    } else {
+        hand.total().to_string()
+    }
+}
+
+fn describe_status(player: &PlayerHand) -> String {
+    if let Some(outcome) = player.outcome {
+        return format!("{} {}", outcome, format_net(player.net_result()));
+    }
+    match player.status {
+        HandStatus::Playing => String::new(),
+        HandStatus::Stood => "Stood".to_string(),
+        HandStatus::Doubled => "Doubled".to_string(),
+        HandStatus::Busted => "Busted".to_string(),
+        HandStatus::Blackjack => "Blackjack".to_string(),
+        HandStatus::Surrendered => "Surrendered".to_string(),
+    }
+}